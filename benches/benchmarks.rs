@@ -10,7 +10,7 @@
 use std::path::PathBuf;
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use palrun::core::{Command, CommandRegistry, CommandSource};
+use palrun::core::{Command, CommandRegistry, CommandSource, SearchField};
 use palrun::scanner::{CargoScanner, NpmScanner, Scanner};
 
 // ============================================================================
@@ -454,7 +454,7 @@ fn bench_command_creation(c: &mut Criterion) {
 
     group.bench_function("match_text", |b| {
         b.iter(|| {
-            let text = cmd.match_text();
+            let text = cmd.match_text(SearchField::all());
             black_box(text)
         });
     });
@@ -3,8 +3,10 @@
 //! Scans Rust Cargo projects (Cargo.toml) and extracts available
 //! commands, binaries, examples, and tests.
 
+use palrun_plugin_sdk::glob_match;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginCommand {
@@ -42,6 +44,9 @@ const WORKSPACE_COMMANDS: &[(&str, &str)] = &[
     ("check --workspace", "Check all workspace members"),
 ];
 
+/// Maximum number of workspace members that get per-member commands.
+const MAX_WORKSPACE_MEMBERS: usize = 25;
+
 /// Parsed Cargo.toml structure.
 #[derive(Debug, Deserialize, Default)]
 struct CargoToml {
@@ -55,6 +60,8 @@ struct CargoToml {
     #[serde(default)]
     bench: Vec<BinaryTarget>,
     #[serde(default)]
+    test: Vec<BinaryTarget>,
+    #[serde(default)]
     features: HashMap<String, Vec<String>>,
 }
 
@@ -143,13 +150,12 @@ pub fn parse_cargo_toml(content: &str) -> Vec<PluginCommand> {
 
     // Add benchmark targets
     for bench in &cargo.bench {
-        commands.push(PluginCommand {
-            name: format!("cargo bench --bench {}", bench.name),
-            command: format!("cargo bench --bench {}", bench.name),
-            description: Some(format!("Run '{}' benchmark", bench.name)),
-            working_dir: None,
-            tags: vec!["cargo".to_string(), "benchmark".to_string()],
-        });
+        commands.push(bench_command(&bench.name));
+    }
+
+    // Add integration test targets
+    for test in &cargo.test {
+        commands.push(test_command(&test.name));
     }
 
     // Add feature-specific builds
@@ -182,6 +188,138 @@ pub fn parse_cargo_toml(content: &str) -> Vec<PluginCommand> {
     commands
 }
 
+fn bench_command(name: &str) -> PluginCommand {
+    PluginCommand {
+        name: format!("cargo bench --bench {}", name),
+        command: format!("cargo bench --bench {}", name),
+        description: Some(format!("Run '{}' benchmark", name)),
+        working_dir: None,
+        tags: vec!["cargo".to_string(), "benchmark".to_string()],
+    }
+}
+
+fn test_command(name: &str) -> PluginCommand {
+    PluginCommand {
+        name: format!("cargo test --test {}", name),
+        command: format!("cargo test --test {}", name),
+        description: Some(format!("Run '{}' integration test", name)),
+        working_dir: None,
+        tags: vec!["cargo".to_string(), "test".to_string()],
+    }
+}
+
+/// Find target files Cargo discovers automatically, like `tests/*.rs`.
+///
+/// Files already covered by a declared target, by name or by `path`, are
+/// skipped so they don't produce duplicate commands.
+fn discover_targets(root: &Path, dir: &str, declared: &[BinaryTarget]) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(root.join(dir)) else {
+        return Vec::new();
+    };
+
+    let declared_paths: Vec<PathBuf> = declared
+        .iter()
+        .filter_map(|target| target.path.as_deref())
+        .map(|path| root.join(path.trim_start_matches("./")))
+        .collect();
+
+    let mut names: Vec<String> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rs"))
+        .filter(|path| !declared_paths.contains(path))
+        .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .filter(|name| !declared.iter().any(|target| &target.name == name))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Expand workspace member patterns like `crates/*` into package names.
+///
+/// Each matching directory's `Cargo.toml` is read for the package name.
+/// Directories without a `Cargo.toml` (or without a package name) are
+/// skipped, and members matched by several patterns are listed once.
+pub fn expand_workspace_members(root: &Path, patterns: &[String]) -> Vec<String> {
+    let mut names = Vec::new();
+
+    for pattern in patterns {
+        for dir in expand_glob(root, pattern) {
+            let Ok(content) = std::fs::read_to_string(dir.join("Cargo.toml")) else {
+                continue;
+            };
+            let name = toml::from_str::<CargoToml>(&content)
+                .ok()
+                .and_then(|cargo| cargo.package)
+                .and_then(|package| package.name);
+
+            if let Some(name) = name {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+    }
+
+    names
+}
+
+/// Create `cargo build -p` and `cargo test -p` commands for workspace members.
+pub fn workspace_member_commands(members: &[String]) -> Vec<PluginCommand> {
+    let mut commands = Vec::new();
+
+    for member in members.iter().take(MAX_WORKSPACE_MEMBERS) {
+        commands.push(PluginCommand {
+            name: format!("cargo build -p {}", member),
+            command: format!("cargo build -p {}", member),
+            description: Some(format!("Build '{}' package", member)),
+            working_dir: None,
+            tags: vec!["cargo".to_string(), "workspace".to_string()],
+        });
+        commands.push(PluginCommand {
+            name: format!("cargo test -p {}", member),
+            command: format!("cargo test -p {}", member),
+            description: Some(format!("Test '{}' package", member)),
+            working_dir: None,
+            tags: vec!["cargo".to_string(), "workspace".to_string()],
+        });
+    }
+
+    commands
+}
+
+/// Expand a relative path pattern into existing directories.
+///
+/// Supports `*` and `?` within path segments, which covers the patterns
+/// Cargo workspaces use in practice.
+fn expand_glob(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![root.to_path_buf()];
+
+    for segment in pattern.split('/').filter(|s| !s.is_empty() && *s != ".") {
+        let mut next = Vec::new();
+        for dir in &dirs {
+            if !segment.contains(['*', '?']) {
+                next.push(dir.join(segment));
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            let mut matches: Vec<PathBuf> = entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_dir())
+                .filter(|entry| glob_match(segment, &entry.file_name().to_string_lossy()))
+                .map(|entry| entry.path())
+                .collect();
+            matches.sort();
+            next.extend(matches);
+        }
+        dirs = next;
+    }
+
+    dirs.into_iter().filter(|dir| dir.is_dir()).collect()
+}
+
 /// Main entry point for the scanner plugin.
 #[no_mangle]
 pub extern "C" fn scan(project_path_ptr: *const u8, project_path_len: usize) -> *mut u8 {
@@ -200,14 +338,34 @@ pub extern "C" fn scan(project_path_ptr: *const u8, project_path_len: usize) ->
 
 /// Scan a project directory for Cargo commands.
 pub fn scan_project(project_path: &str) -> Vec<PluginCommand> {
-    let cargo_path = std::path::Path::new(project_path).join("Cargo.toml");
+    let root = Path::new(project_path);
+
+    let content = match std::fs::read_to_string(root.join("Cargo.toml")) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut commands = parse_cargo_toml(&content);
+    let cargo: CargoToml = toml::from_str(&content).unwrap_or_default();
 
-    if cargo_path.exists() {
-        // In a real plugin, read through host API
-        parse_cargo_toml("")
-    } else {
-        Vec::new()
+    for name in discover_targets(root, "tests", &cargo.test) {
+        commands.push(test_command(&name));
     }
+    for name in discover_targets(root, "benches", &cargo.bench) {
+        commands.push(bench_command(&name));
+    }
+
+    let patterns = cargo.workspace.map(|workspace| workspace.members).unwrap_or_default();
+    let members = expand_workspace_members(root, &patterns);
+    commands.extend(workspace_member_commands(&members));
+
+    commands
+}
+
+/// Scan for Cargo commands using the files provided by the host.
+#[cfg(feature = "sdk")]
+pub fn scan_context(context: &palrun_plugin_sdk::ScanContext) -> Vec<PluginCommand> {
+    context.get_file("Cargo.toml").map(parse_cargo_toml).unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -303,6 +461,158 @@ serde = ["dep:serde"]
         assert!(feature_cmds.len() >= 1);
     }
 
+    #[test]
+    fn test_scan_project_reads_cargo_toml() {
+        let dir = std::env::temp_dir().join(format!("cargo-scanner-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            r#"
+[package]
+name = "myproject"
+
+[[bin]]
+name = "mycli"
+"#,
+        )
+        .unwrap();
+
+        let commands = scan_project(dir.to_str().unwrap());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(commands.iter().any(|c| c.name == "cargo run --bin mycli"));
+        assert!(commands.iter().any(|c| c.name == "cargo test -p myproject"));
+    }
+
+    #[test]
+    fn test_scan_project_without_cargo_toml() {
+        let commands = scan_project("/nonexistent/project");
+        assert!(commands.is_empty());
+    }
+
+    #[cfg(feature = "sdk")]
+    #[test]
+    fn test_scan_context_reads_cargo_toml() {
+        let context = palrun_plugin_sdk::ScanContext::new("/project", "myproject").with_file(
+            "Cargo.toml",
+            "[package]\nname = \"myproject\"\n\n[[bin]]\nname = \"mycli\"\n",
+        );
+
+        let commands = scan_context(&context);
+        assert!(commands.iter().any(|c| c.name == "cargo run --bin mycli"));
+    }
+
+    /// Create an empty temporary directory unique to a test.
+    fn temp_project(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("cargo-scanner-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_member(root: &Path, dir: &str, name: &str) {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+        std::fs::write(
+            root.join(dir).join("Cargo.toml"),
+            format!("[package]\nname = \"{}\"\n", name),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_expand_workspace_members() {
+        let root = temp_project("members");
+        write_member(&root, "crates/core", "my-core");
+        write_member(&root, "crates/cli", "my-cli");
+        write_member(&root, "tools/xtask", "xtask");
+        // A directory matched by the glob but without a Cargo.toml
+        std::fs::create_dir_all(root.join("crates/docs")).unwrap();
+
+        let patterns =
+            vec!["crates/*".to_string(), "crates/core".to_string(), "tools/xtask".to_string()];
+        let members = expand_workspace_members(&root, &patterns);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(members, vec!["my-cli", "my-core", "xtask"]);
+    }
+
+    #[test]
+    fn test_scan_project_workspace_members() {
+        let root = temp_project("workspace");
+        std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+        write_member(&root, "crates/core", "my-core");
+
+        let commands = scan_project(root.to_str().unwrap());
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert!(commands.iter().any(|c| c.name == "cargo build --workspace"));
+        assert!(commands.iter().any(|c| c.name == "cargo build -p my-core"));
+        assert!(commands.iter().any(|c| c.name == "cargo test -p my-core"));
+    }
+
+    #[test]
+    fn test_workspace_member_commands_are_capped() {
+        let members: Vec<String> = (0..100).map(|i| format!("crate-{}", i)).collect();
+        let commands = workspace_member_commands(&members);
+        assert_eq!(commands.len(), MAX_WORKSPACE_MEMBERS * 2);
+    }
+
+    #[test]
+    fn test_parse_test_targets() {
+        let content = r#"
+[package]
+name = "myproject"
+
+[[test]]
+name = "integration"
+path = "tests/it/main.rs"
+"#;
+
+        let commands = parse_cargo_toml(content);
+        assert!(commands.iter().any(|c| c.name == "cargo test --test integration"));
+    }
+
+    #[test]
+    fn test_scan_project_discovers_test_files() {
+        let root = temp_project("tests");
+        std::fs::write(
+            root.join("Cargo.toml"),
+            r#"
+[package]
+name = "myproject"
+
+[[test]]
+name = "cli"
+
+[[test]]
+name = "smoke"
+path = "tests/smoke_test.rs"
+
+[[bench]]
+name = "throughput"
+path = "benches/perf.rs"
+"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("tests")).unwrap();
+        std::fs::create_dir_all(root.join("benches")).unwrap();
+        for file in ["tests/cli.rs", "tests/smoke_test.rs", "tests/api.rs", "benches/perf.rs"] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let commands = scan_project(root.to_str().unwrap());
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let count = |name: &str| commands.iter().filter(|c| c.name == name).count();
+        assert_eq!(count("cargo test --test cli"), 1);
+        assert_eq!(count("cargo test --test smoke"), 1);
+        assert_eq!(count("cargo test --test api"), 1);
+        assert_eq!(count("cargo test --test smoke_test"), 0);
+        assert_eq!(count("cargo bench --bench throughput"), 1);
+        assert_eq!(count("cargo bench --bench perf"), 0);
+    }
+
     #[test]
     fn test_invalid_toml() {
         let content = "not valid toml [";
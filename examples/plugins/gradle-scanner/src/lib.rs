@@ -3,6 +3,8 @@
 //! This plugin scans Gradle build files and extracts available tasks
 //! for use in the Palrun command palette.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 /// A command discovered by the scanner.
@@ -138,54 +140,74 @@ fn is_common_task(name: &str) -> bool {
     COMMON_TASKS.iter().any(|(n, _)| *n == name)
 }
 
+/// Build files this scanner reads, passed to the host by `file_patterns`.
+const BUILD_FILES: &[&str] = &["build.gradle", "build.gradle.kts"];
+
+/// The part of the host's scan context this plugin uses.
+#[derive(Debug, Default, Deserialize)]
+pub struct ScanContext {
+    /// Contents of matched files, keyed by path relative to the project root
+    #[serde(default)]
+    pub matched_files: HashMap<String, String>,
+}
+
+/// Pack a string into the `(ptr << 32) | len` value returned to the host.
+///
+/// The host frees the buffer with `dealloc(ptr, len)`.
+fn pack_string(s: String) -> i64 {
+    let bytes = s.into_bytes().into_boxed_slice();
+    let len = bytes.len() as u64;
+    let ptr = Box::into_raw(bytes) as *mut u8 as usize as u64;
+    ((ptr << 32) | len) as i64
+}
+
+/// File patterns the host should read and pass to `scan`.
+#[no_mangle]
+pub extern "C" fn file_patterns() -> i64 {
+    pack_string(serde_json::to_string(BUILD_FILES).unwrap_or_else(|_| "[]".to_string()))
+}
+
 /// Main entry point for the scanner plugin.
 ///
-/// This function is called by the Palrun host with the project path
-/// and returns a JSON-encoded list of discovered commands.
+/// The host passes a JSON scan context holding the contents of the files
+/// matched by `file_patterns` and gets back a JSON list of commands.
 #[no_mangle]
-pub extern "C" fn scan(project_path_ptr: *const u8, project_path_len: usize) -> *mut u8 {
-    // Safety: This is called by the host with valid pointers
-    let project_path = unsafe {
-        let slice = std::slice::from_raw_parts(project_path_ptr, project_path_len);
-        std::str::from_utf8(slice).unwrap_or("")
+pub extern "C" fn scan(context_ptr: *const u8, context_len: usize) -> i64 {
+    // Safety: the host wrote `context_len` bytes at `context_ptr` via `alloc`
+    let context = unsafe {
+        let slice = std::slice::from_raw_parts(context_ptr, context_len);
+        serde_json::from_slice::<ScanContext>(slice).unwrap_or_default()
     };
 
-    let commands = scan_project(project_path);
-
-    // Serialize to JSON
-    let json = serde_json::to_string(&commands).unwrap_or_else(|_| "[]".to_string());
+    let commands = scan_context(&context);
+    pack_string(serde_json::to_string(&commands).unwrap_or_else(|_| "[]".to_string()))
+}
 
-    // Return pointer to the result (host will free this)
-    let bytes = json.into_bytes();
-    let ptr = bytes.as_ptr() as *mut u8;
-    std::mem::forget(bytes);
+/// Allocate memory for the host to write the scan context into.
+#[no_mangle]
+pub extern "C" fn alloc(size: usize) -> *mut u8 {
+    let mut buf = Vec::<u8>::with_capacity(size);
+    let ptr = buf.as_mut_ptr();
+    std::mem::forget(buf);
     ptr
 }
 
-/// Scan a project directory for Gradle commands.
-pub fn scan_project(project_path: &str) -> Vec<PluginCommand> {
-    let mut commands = Vec::new();
-
-    // Check for build.gradle or build.gradle.kts
-    let build_file = std::path::Path::new(project_path).join("build.gradle");
-    let build_file_kts = std::path::Path::new(project_path).join("build.gradle.kts");
-
-    // In a real plugin, we would read these files through the host API
-    // For now, just return common tasks if the files exist
-    if build_file.exists() || build_file_kts.exists() {
-        // Add common Gradle tasks
-        for (name, desc) in COMMON_TASKS {
-            commands.push(PluginCommand {
-                name: format!("gradle {}", name),
-                command: format!("./gradlew {}", name),
-                description: Some(desc.to_string()),
-                working_dir: Some(project_path.to_string()),
-                tags: vec!["gradle".to_string()],
-            });
-        }
+/// Free memory from `alloc` or a returned buffer.
+#[no_mangle]
+pub extern "C" fn dealloc(ptr: *mut u8, size: usize) {
+    // Safety: the host only frees buffers this plugin handed out
+    unsafe {
+        drop(Vec::from_raw_parts(ptr, size, size));
     }
+}
 
-    commands
+/// Discover Gradle commands from the build files the host provided.
+pub fn scan_context(context: &ScanContext) -> Vec<PluginCommand> {
+    BUILD_FILES
+        .iter()
+        .find_map(|file| context.matched_files.get(*file))
+        .map(|content| parse_build_gradle(content))
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -230,6 +252,19 @@ mod tests {
         assert!(commands.iter().any(|c| c.name == "gradle deployProd"));
     }
 
+    #[test]
+    fn test_scan_context_uses_host_files() {
+        let mut context = ScanContext::default();
+        assert!(scan_context(&context).is_empty());
+
+        context
+            .matched_files
+            .insert("build.gradle.kts".to_string(), "tasks.register(\"deployProd\")".to_string());
+        let commands = scan_context(&context);
+        assert!(commands.iter().any(|c| c.name == "gradle build"));
+        assert!(commands.iter().any(|c| c.name == "gradle deployProd"));
+    }
+
     #[test]
     fn test_extract_task_name() {
         assert_eq!(extract_task_name("task myTask {", "task "), Some("myTask".to_string()));
@@ -37,6 +37,10 @@ pub struct Command {
     /// Tags for categorization and filtering.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+
+    /// Whether Palrun asks for confirmation before running the command.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub confirm: bool,
 }
 
 impl Command {
@@ -63,6 +67,7 @@ impl Command {
             description: None,
             working_dir: None,
             tags: Vec::new(),
+            confirm: false,
         }
     }
 
@@ -100,6 +105,24 @@ impl Command {
         self
     }
 
+    /// Require confirmation before the command runs.
+    ///
+    /// Use this for destructive commands such as deploys or resets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use palrun_plugin_sdk::Command;
+    ///
+    /// let cmd = Command::new("deploy", "./gradlew deploy")
+    ///     .with_confirm(true);
+    /// ```
+    #[must_use]
+    pub fn with_confirm(mut self, confirm: bool) -> Self {
+        self.confirm = confirm;
+        self
+    }
+
     /// Add a tag to the command.
     ///
     /// Tags help users filter and find commands.
@@ -153,6 +176,8 @@ impl Command {
 ///     .name("deploy")
 ///     .command("./deploy.sh")
 ///     .description("Deploy to production")
+///     .working_dir("infra")
+///     .confirm(true)
 ///     .tag("deploy")
 ///     .tag("production")
 ///     .build()
@@ -165,6 +190,7 @@ pub struct CommandBuilder {
     description: Option<String>,
     working_dir: Option<String>,
     tags: Vec<String>,
+    confirm: bool,
 }
 
 impl CommandBuilder {
@@ -194,13 +220,20 @@ impl CommandBuilder {
         self
     }
 
-    /// Set the working directory.
+    /// Set the working directory, relative to the project root.
     #[must_use]
     pub fn working_dir(mut self, working_dir: impl Into<String>) -> Self {
         self.working_dir = Some(working_dir.into());
         self
     }
 
+    /// Set whether to ask for confirmation before running.
+    #[must_use]
+    pub fn confirm(mut self, confirm: bool) -> Self {
+        self.confirm = confirm;
+        self
+    }
+
     /// Add a tag.
     #[must_use]
     pub fn tag(mut self, tag: impl Into<String>) -> Self {
@@ -229,6 +262,7 @@ impl CommandBuilder {
             description: self.description,
             working_dir: self.working_dir,
             tags: self.tags,
+            confirm: self.confirm,
         })
     }
 }
@@ -245,6 +279,7 @@ mod tests {
         assert!(cmd.description.is_none());
         assert!(cmd.working_dir.is_none());
         assert!(cmd.tags.is_empty());
+        assert!(!cmd.confirm);
     }
 
     #[test]
@@ -284,6 +319,24 @@ mod tests {
         assert_eq!(cmd.command, "./deploy.sh");
     }
 
+    #[test]
+    fn test_command_builder_working_dir_and_confirm() {
+        let cmd = CommandBuilder::new()
+            .name("deploy")
+            .command("./deploy.sh")
+            .working_dir("packages/api")
+            .confirm(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(cmd.working_dir, Some("packages/api".to_string()));
+        assert!(cmd.confirm);
+
+        let cmd = Command::new("deploy", "./deploy.sh").with_confirm(true);
+        assert!(cmd.confirm);
+        assert!(!cmd.with_confirm(false).confirm);
+    }
+
     #[test]
     fn test_command_builder_missing_fields() {
         let cmd = CommandBuilder::new().name("test").build();
@@ -316,5 +369,25 @@ mod tests {
         assert!(!json.contains("description"));
         assert!(!json.contains("working_dir"));
         assert!(!json.contains("tags"));
+        assert!(!json.contains("confirm"));
+    }
+
+    #[test]
+    fn test_command_working_dir_and_confirm_json() {
+        let cmd = Command::new("deploy", "./deploy.sh")
+            .with_working_dir("packages/api")
+            .with_confirm(true);
+
+        let json: serde_json::Value = serde_json::to_value(&cmd).unwrap();
+        assert_eq!(json["working_dir"], "packages/api");
+        assert_eq!(json["confirm"], true);
+
+        let deserialized: Command = serde_json::from_value(json).unwrap();
+        assert_eq!(deserialized, cmd);
+
+        // Hosts and plugins that predate `confirm` leave it out
+        let old: Command =
+            serde_json::from_str(r#"{"name":"test","command":"npm test"}"#).unwrap();
+        assert!(!old.confirm);
     }
 }
@@ -26,6 +26,10 @@ pub struct ScanContext {
     /// Only populated if the plugin has environment permission.
     #[serde(default)]
     pub environment: HashMap<String, String>,
+
+    /// Current git branch, if the project is in a repository on a branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
 }
 
 impl ScanContext {
@@ -41,9 +45,25 @@ impl ScanContext {
             project_name: project_name.into(),
             matched_files: HashMap::new(),
             environment: HashMap::new(),
+            git_branch: None,
         }
     }
 
+    /// Get the absolute path to the project root directory.
+    pub fn root(&self) -> &str {
+        &self.project_path
+    }
+
+    /// Get the current git branch.
+    ///
+    /// # Returns
+    ///
+    /// Branch name if the project is in a git repository and not on a
+    /// detached HEAD, None otherwise.
+    pub fn git_branch(&self) -> Option<&str> {
+        self.git_branch.as_deref()
+    }
+
     /// Get the content of a matched file.
     ///
     /// # Arguments
@@ -80,6 +100,31 @@ impl ScanContext {
         self.matched_files.keys().map(String::as_str)
     }
 
+    /// Get the matched file paths that match a glob pattern, sorted.
+    ///
+    /// `*` matches any run of characters and `?` a single character. A
+    /// pattern containing `/` is matched against the whole relative path,
+    /// any other pattern against the file name alone, the same way the
+    /// host matches `file_patterns`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Glob pattern, e.g. `*.gradle` or `apps/*/package.json`
+    pub fn glob(&self, pattern: &str) -> Vec<&str> {
+        let mut paths: Vec<&str> = self
+            .file_paths()
+            .filter(|path| {
+                if pattern.contains('/') {
+                    glob_match(pattern, path)
+                } else {
+                    glob_match(pattern, path.rsplit('/').next().unwrap_or(path))
+                }
+            })
+            .collect();
+        paths.sort_unstable();
+        paths
+    }
+
     /// Add a matched file to the context.
     ///
     /// This is primarily used for testing.
@@ -95,6 +140,43 @@ impl ScanContext {
         self.environment.insert(name.into(), value.into());
         self
     }
+
+    /// Set the git branch in the context.
+    ///
+    /// This is primarily used for testing.
+    pub fn with_git_branch(mut self, branch: impl Into<String>) -> Self {
+        self.git_branch = Some(branch.into());
+        self
+    }
+}
+
+/// Match `text` against a glob with `*` (any run) and `?` (one character).
+///
+/// Used by [`ScanContext::glob`]; plugins can call it directly to match
+/// names they list themselves, such as workspace members.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
 }
 
 impl Default for ScanContext {
@@ -152,6 +234,71 @@ mod tests {
         assert_eq!(ctx.get_env("NONEXISTENT"), None);
     }
 
+    #[test]
+    fn test_context_metadata() {
+        let ctx = ScanContext::new("/path/to/project", "my-project");
+        assert_eq!(ctx.root(), "/path/to/project");
+        assert_eq!(ctx.git_branch(), None);
+
+        let ctx = ctx.with_git_branch("main");
+        assert_eq!(ctx.git_branch(), Some("main"));
+    }
+
+    #[test]
+    fn test_glob_file_names() {
+        let ctx = ScanContext::new("/project", "test")
+            .with_file("build.gradle", "")
+            .with_file("app/build.gradle", "")
+            .with_file("lib/build.gradle.kts", "")
+            .with_file("settings.gradle", "");
+
+        assert_eq!(ctx.glob("build.gradle"), ["app/build.gradle", "build.gradle"]);
+        assert_eq!(
+            ctx.glob("*.gradle"),
+            ["app/build.gradle", "build.gradle", "settings.gradle"]
+        );
+        assert_eq!(ctx.glob("build.gradle*").len(), 3);
+        assert_eq!(ctx.glob("?uild.gradle.kts"), ["lib/build.gradle.kts"]);
+        assert!(ctx.glob("pom.xml").is_empty());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", "core"));
+        assert!(glob_match("palrun-*", "palrun-core"));
+        assert!(glob_match("c?re", "core"));
+        assert!(!glob_match("palrun-*", "other"));
+        assert!(!glob_match("core", "cores"));
+    }
+
+    #[test]
+    fn test_glob_paths() {
+        let ctx = ScanContext::new("/project", "test")
+            .with_file("package.json", "{}")
+            .with_file("apps/web/package.json", "{}")
+            .with_file("apps/api/package.json", "{}")
+            .with_file("packages/ui/package.json", "{}");
+
+        assert_eq!(
+            ctx.glob("apps/*/package.json"),
+            ["apps/api/package.json", "apps/web/package.json"]
+        );
+        assert_eq!(ctx.glob("*/package.json").len(), 3);
+        assert_eq!(ctx.glob("package.json").len(), 4);
+        assert!(ctx.glob("libs/*/package.json").is_empty());
+    }
+
+    #[test]
+    fn test_git_branch_absent_from_host_json() {
+        let json = r#"{"project_path": "/project", "project_name": "test"}"#;
+        let ctx: ScanContext = serde_json::from_str(json).unwrap();
+        assert_eq!(ctx.git_branch(), None);
+
+        let json = r#"{"project_path": "/project", "project_name": "test", "git_branch": "dev"}"#;
+        let ctx: ScanContext = serde_json::from_str(json).unwrap();
+        assert_eq!(ctx.git_branch(), Some("dev"));
+    }
+
     #[test]
     fn test_context_serialization() {
         let ctx =
@@ -19,6 +19,27 @@ pub fn string_to_ptr(s: String) -> *mut u8 {
     ptr
 }
 
+/// Hand a String to the host as a packed `(ptr << 32) | len` value.
+///
+/// This is the return convention of the `scan`, `file_patterns`, and
+/// `name` exports. The buffer's capacity equals its length, so the host
+/// can free it with `dealloc(ptr, len)`.
+#[inline]
+pub fn pack_string(s: String) -> i64 {
+    let bytes = s.into_bytes().into_boxed_slice();
+    let len = bytes.len();
+    let ptr = Box::into_raw(bytes) as *mut u8;
+    pack(ptr as usize, len)
+}
+
+/// Pack a pointer and length into the `i64` returned to the host.
+///
+/// WASM pointers are 32-bit, so both halves fit.
+#[inline]
+pub fn pack(ptr: usize, len: usize) -> i64 {
+    (((ptr as u64) << 32) | (len as u64 & 0xFFFF_FFFF)) as i64
+}
+
 /// Convert a raw pointer and length to a String.
 ///
 /// # Safety
@@ -138,6 +159,13 @@ mod tests {
         assert_eq!(result, "hello world");
     }
 
+    #[test]
+    fn test_pack() {
+        let packed = pack(0x1000, 42) as u64;
+        assert_eq!(packed >> 32, 0x1000);
+        assert_eq!(packed & 0xFFFF_FFFF, 42);
+    }
+
     #[test]
     fn test_alloc_dealloc() {
         let ptr = alloc(1024);
@@ -56,7 +56,7 @@ pub mod ffi;
 pub mod prelude;
 
 pub use command::{Command, CommandBuilder};
-pub use context::ScanContext;
+pub use context::{glob_match, ScanContext};
 pub use error::{PluginError, PluginResult};
 pub use scanner::Scanner;
 
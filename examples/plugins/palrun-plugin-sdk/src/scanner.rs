@@ -99,7 +99,9 @@ pub trait Scanner {
 /// Macro to export a scanner as a WASM plugin.
 ///
 /// This macro generates the necessary FFI exports for the scanner
-/// to be loaded by Palrun.
+/// to be loaded by Palrun: `scan`, `file_patterns`, `name`, `alloc`, and
+/// `dealloc`. Strings returned to the host are packed as
+/// `(ptr << 32) | len`; see `palrun::plugin::abi` for the full ABI.
 ///
 /// # Example
 ///
@@ -128,25 +130,31 @@ macro_rules! export_scanner {
 
         /// Returns the scanner name as a JSON string.
         #[no_mangle]
-        pub extern "C" fn name() -> *mut u8 {
+        pub extern "C" fn name() -> i64 {
             use $crate::Scanner;
             let name = get_scanner().name();
             let json = serde_json::to_string(&name).unwrap_or_else(|_| "\"unknown\"".to_string());
-            $crate::ffi::string_to_ptr(json)
+            $crate::ffi::pack_string(json)
         }
 
         /// Returns file patterns as a JSON array.
+        ///
+        /// The host reads every matching file it is permitted to and
+        /// passes the contents in the `ScanContext`.
         #[no_mangle]
-        pub extern "C" fn file_patterns() -> *mut u8 {
+        pub extern "C" fn file_patterns() -> i64 {
             use $crate::Scanner;
             let patterns = get_scanner().file_patterns();
             let json = serde_json::to_string(&patterns).unwrap_or_else(|_| "[]".to_string());
-            $crate::ffi::string_to_ptr(json)
+            $crate::ffi::pack_string(json)
         }
 
         /// Scans the project and returns commands as JSON.
+        ///
+        /// `context_ptr` points to a JSON `ScanContext` written by the host
+        /// into memory from `alloc`; the host frees it after the call.
         #[no_mangle]
-        pub extern "C" fn scan(context_ptr: *const u8, context_len: usize) -> *mut u8 {
+        pub extern "C" fn scan(context_ptr: *const u8, context_len: usize) -> i64 {
             use $crate::Scanner;
 
             // Parse the context from JSON
@@ -162,7 +170,7 @@ macro_rules! export_scanner {
 
             // Return commands as JSON
             let json = serde_json::to_string(&commands).unwrap_or_else(|_| "[]".to_string());
-            $crate::ffi::string_to_ptr(json)
+            $crate::ffi::pack_string(json)
         }
 
         /// Allocate memory for the host.
@@ -2,8 +2,12 @@
 //!
 //! Provides an agentic loop that allows the AI to use MCP tools
 //! to accomplish tasks autonomously.
+//!
+//! The loop can be capped with a tool-call budget, and tool calls the
+//! executor flags as destructive wait for confirmation before running.
 
 use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -84,8 +88,16 @@ pub struct AgentState {
     pub max_iterations: usize,
     /// Current iteration
     pub current_iteration: usize,
+    /// Maximum number of tool calls to execute (unlimited if `None`)
+    pub tool_budget: Option<usize>,
+    /// Tool calls executed so far
+    pub tool_calls_made: usize,
+    /// Ask before running tools flagged as destructive
+    pub confirm_destructive: bool,
     /// Whether the agent is done
     pub done: bool,
+    /// Why the agent stopped, once it has
+    pub stop_reason: Option<AgentStopReason>,
 }
 
 impl AgentState {
@@ -97,7 +109,11 @@ impl AgentState {
             context,
             max_iterations: 10,
             current_iteration: 0,
+            tool_budget: None,
+            tool_calls_made: 0,
+            confirm_destructive: true,
             done: false,
+            stop_reason: None,
         }
     }
 
@@ -113,6 +129,18 @@ impl AgentState {
         self
     }
 
+    /// Set the maximum number of tool calls.
+    pub fn with_tool_budget(mut self, budget: usize) -> Self {
+        self.tool_budget = Some(budget);
+        self
+    }
+
+    /// Set whether destructive tools need confirmation.
+    pub fn with_confirm_destructive(mut self, confirm: bool) -> Self {
+        self.confirm_destructive = confirm;
+        self
+    }
+
     /// Add a system message.
     pub fn add_system_message(&mut self, content: impl Into<String>) {
         self.messages.push(AgentMessage::System { content: content.into() });
@@ -147,6 +175,17 @@ impl AgentState {
         self.done = true;
     }
 
+    /// Mark the agent as done for the given reason.
+    pub fn stop(&mut self, reason: AgentStopReason) {
+        self.stop_reason = Some(reason);
+        self.finish();
+    }
+
+    /// Check if the tool-call budget has been used up.
+    pub fn budget_exhausted(&self) -> bool {
+        self.tool_budget.is_some_and(|budget| self.tool_calls_made >= budget)
+    }
+
     /// Increment the iteration counter.
     pub fn next_iteration(&mut self) {
         self.current_iteration += 1;
@@ -175,6 +214,8 @@ pub enum AgentStopReason {
     MaxTokens,
     /// An error occurred
     Error,
+    /// The tool-call budget ran out
+    BudgetExhausted,
 }
 
 /// Trait for AI providers that support tool use.
@@ -198,18 +239,39 @@ pub trait AgentProvider: Send + Sync {
 pub trait ToolExecutor: Send + Sync {
     /// Execute a tool call.
     async fn execute(&mut self, tool_call: &AgentToolCall) -> AgentToolResult;
+
+    /// Check if a tool call writes or deletes and so needs confirmation.
+    fn is_destructive(&self, _tool_call: &AgentToolCall) -> bool {
+        false
+    }
 }
 
+/// Decides whether a destructive tool call may run.
+pub type ConfirmFn = Box<dyn FnMut(&AgentToolCall) -> bool + Send>;
+
 /// Simple agent runner.
 pub struct Agent<P: AgentProvider, E: ToolExecutor> {
     provider: P,
     executor: E,
+    confirm: ConfirmFn,
 }
 
 impl<P: AgentProvider, E: ToolExecutor> Agent<P, E> {
     /// Create a new agent.
+    ///
+    /// Destructive tool calls are confirmed with a y/N prompt on the
+    /// terminal; use [`Agent::with_confirmation`] to decide otherwise.
     pub fn new(provider: P, executor: E) -> Self {
-        Self { provider, executor }
+        Self { provider, executor, confirm: Box::new(confirm_on_terminal) }
+    }
+
+    /// Set how destructive tool calls are confirmed.
+    pub fn with_confirmation(
+        mut self,
+        confirm: impl FnMut(&AgentToolCall) -> bool + Send + 'static,
+    ) -> Self {
+        self.confirm = Box::new(confirm);
+        self
     }
 
     /// Run the agent with a user task.
@@ -237,17 +299,39 @@ impl<P: AgentProvider, E: ToolExecutor> Agent<P, E> {
             match response.stop_reason {
                 AgentStopReason::EndTurn => {
                     // Agent is done
-                    state.finish();
+                    state.stop(AgentStopReason::EndTurn);
                 }
                 AgentStopReason::ToolUse => {
                     // Execute tool calls
                     if let Some(tool_calls) = response.tool_calls {
                         for tool_call in tool_calls {
+                            if state.budget_exhausted() {
+                                tracing::warn!(
+                                    budget = state.tool_calls_made,
+                                    "Tool budget exhausted"
+                                );
+                                state.stop(AgentStopReason::BudgetExhausted);
+                                break;
+                            }
+
+                            if state.confirm_destructive
+                                && self.executor.is_destructive(&tool_call)
+                                && !(self.confirm)(&tool_call)
+                            {
+                                tracing::info!(tool = %tool_call.name, "Tool call declined");
+                                state.add_tool_result(
+                                    tool_call.id,
+                                    "The user declined to run this tool call.".to_string(),
+                                );
+                                continue;
+                            }
+
                             tracing::info!(
                                 tool = %tool_call.name,
                                 "Executing tool"
                             );
 
+                            state.tool_calls_made += 1;
                             let result = self.executor.execute(&tool_call).await;
                             state.add_tool_result(result.tool_call_id, result.output);
                         }
@@ -255,11 +339,14 @@ impl<P: AgentProvider, E: ToolExecutor> Agent<P, E> {
                 }
                 AgentStopReason::MaxTokens => {
                     tracing::warn!("Max tokens reached");
-                    state.finish();
+                    state.stop(AgentStopReason::MaxTokens);
                 }
                 AgentStopReason::Error => {
                     tracing::error!("Agent error");
-                    state.finish();
+                    state.stop(AgentStopReason::Error);
+                }
+                AgentStopReason::BudgetExhausted => {
+                    state.stop(AgentStopReason::BudgetExhausted);
                 }
             }
         }
@@ -281,6 +368,20 @@ impl<P: AgentProvider, E: ToolExecutor> Agent<P, E> {
     }
 }
 
+/// Ask on the terminal whether a destructive tool call may run.
+fn confirm_on_terminal(tool_call: &AgentToolCall) -> bool {
+    let detail = tool_call.arguments.get("command").and_then(|v| v.as_str()).map_or_else(
+        || serde_json::to_string(&tool_call.arguments).unwrap_or_default(),
+        String::from,
+    );
+
+    eprint!("\nThe agent wants to run {}: {}\nAllow? [y/N] ", tool_call.name, detail);
+    let _ = io::stderr().flush();
+
+    let mut input = String::new();
+    io::stdin().lock().read_line(&mut input).is_ok() && input.trim().eq_ignore_ascii_case("y")
+}
+
 /// Build the system prompt for the agent.
 fn build_system_prompt(context: &ProjectContext, tools: &[AgentTool]) -> String {
     let mut prompt = format!(
@@ -404,4 +505,104 @@ mod tests {
         assert!(prompt.contains("node"));
         assert!(prompt.contains("read_file"));
     }
+
+    /// Provider that asks for the same two tool calls on every step.
+    struct GreedyProvider;
+
+    #[async_trait]
+    impl AgentProvider for GreedyProvider {
+        async fn step(&self, state: &AgentState) -> anyhow::Result<AgentResponse> {
+            let call = |n: usize, command: &str| AgentToolCall {
+                id: format!("call-{}-{n}", state.current_iteration),
+                name: "shell".to_string(),
+                arguments: HashMap::from([("command".to_string(), serde_json::json!(command))]),
+            };
+            Ok(AgentResponse {
+                content: None,
+                tool_calls: Some(vec![call(1, "ls"), call(2, "rm notes.txt")]),
+                stop_reason: AgentStopReason::ToolUse,
+            })
+        }
+
+        fn name(&self) -> &str {
+            "greedy"
+        }
+
+        fn supports_tools(&self) -> bool {
+            true
+        }
+    }
+
+    /// Executor recording what it ran; `rm` commands are destructive.
+    #[derive(Default)]
+    struct RecordingExecutor {
+        ran: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl ToolExecutor for RecordingExecutor {
+        async fn execute(&mut self, tool_call: &AgentToolCall) -> AgentToolResult {
+            self.ran.lock().unwrap().push(tool_call.id.clone());
+            AgentToolResult {
+                tool_call_id: tool_call.id.clone(),
+                success: true,
+                output: "ok".to_string(),
+            }
+        }
+
+        fn is_destructive(&self, tool_call: &AgentToolCall) -> bool {
+            tool_call.arguments["command"].as_str().is_some_and(|c| c.starts_with("rm"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_budget_exhausted() {
+        let executor = RecordingExecutor::default();
+        let ran = executor.ran.clone();
+        let mut agent = Agent::new(GreedyProvider, executor).with_confirmation(|_| true);
+
+        let state = AgentState::new(ProjectContext::new("test", PathBuf::from(".")))
+            .with_max_iterations(10)
+            .with_tool_budget(3);
+        let state = agent.run("clean up", state).await.unwrap();
+
+        assert_eq!(state.stop_reason, Some(AgentStopReason::BudgetExhausted));
+        assert_eq!(state.tool_calls_made, 3);
+        assert_eq!(*ran.lock().unwrap(), ["call-1-1", "call-1-2", "call-2-1"]);
+        assert_eq!(state.current_iteration, 2);
+    }
+
+    #[tokio::test]
+    async fn test_destructive_tools_need_confirmation() {
+        let executor = RecordingExecutor::default();
+        let ran = executor.ran.clone();
+        let asked = std::sync::Arc::new(std::sync::Mutex::new(0));
+        let counter = asked.clone();
+        let mut agent = Agent::new(GreedyProvider, executor).with_confirmation(move |call| {
+            *counter.lock().unwrap() += 1;
+            assert_eq!(call.arguments["command"], "rm notes.txt");
+            false
+        });
+
+        let state =
+            AgentState::new(ProjectContext::new("test", PathBuf::from("."))).with_max_iterations(2);
+        let state = agent.run("clean up", state).await.unwrap();
+
+        assert_eq!(*asked.lock().unwrap(), 2);
+        assert_eq!(*ran.lock().unwrap(), ["call-1-1", "call-2-1"]);
+        assert!(state.messages.iter().any(|m| matches!(
+            m,
+            AgentMessage::Tool { content, .. } if content.contains("declined")
+        )));
+
+        // Without the policy nothing is asked
+        let executor = RecordingExecutor::default();
+        let ran = executor.ran.clone();
+        let mut agent = Agent::new(GreedyProvider, executor).with_confirmation(|_| false);
+        let state = AgentState::new(ProjectContext::new("test", PathBuf::from(".")))
+            .with_max_iterations(1)
+            .with_confirm_destructive(false);
+        agent.run("clean up", state).await.unwrap();
+        assert_eq!(ran.lock().unwrap().len(), 2);
+    }
 }
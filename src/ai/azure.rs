@@ -131,6 +131,7 @@ Rules:
             context.project_type,
             context.available_commands.join(", ")
         );
+        let system = context.prepend_guidance(system);
 
         self.request(&system, prompt).await
     }
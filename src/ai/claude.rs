@@ -3,10 +3,12 @@
 //! Implements the AIProvider trait for Claude.
 
 use async_trait::async_trait;
+use futures::TryStreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use super::{AIProvider, ProjectContext};
+use super::stream::stream_lines;
+use super::{AIProvider, ProjectContext, TextStream};
 
 /// Claude API provider.
 pub struct ClaudeProvider {
@@ -32,13 +34,19 @@ impl ClaudeProvider {
         self
     }
 
-    /// Make a request to the Claude API.
-    async fn request(&self, system: &str, user_message: &str) -> anyhow::Result<String> {
+    /// Send a request to the messages endpoint and check the status.
+    async fn send(
+        &self,
+        system: &str,
+        user_message: &str,
+        stream: bool,
+    ) -> anyhow::Result<reqwest::Response> {
         let request = ClaudeRequest {
             model: self.model.clone(),
             max_tokens: 1024,
             system: system.to_string(),
             messages: vec![Message { role: "user".to_string(), content: user_message.to_string() }],
+            stream,
         };
 
         let response = self
@@ -57,7 +65,12 @@ impl ClaudeProvider {
             anyhow::bail!("API error ({}): {}", status, body);
         }
 
-        let response: ClaudeResponse = response.json().await?;
+        Ok(response)
+    }
+
+    /// Make a request to the Claude API.
+    async fn request(&self, system: &str, user_message: &str) -> anyhow::Result<String> {
+        let response: ClaudeResponse = self.send(system, user_message, false).await?.json().await?;
 
         response
             .content
@@ -67,14 +80,10 @@ impl ClaudeProvider {
     }
 }
 
-#[async_trait]
-impl AIProvider for ClaudeProvider {
-    async fn generate_command(
-        &self,
-        prompt: &str,
-        context: &ProjectContext,
-    ) -> anyhow::Result<String> {
-        let system = format!(
+impl ClaudeProvider {
+    /// Build the system prompt for command generation.
+    fn build_command_system(context: &ProjectContext) -> String {
+        context.prepend_guidance(format!(
             r"You are Palrun, an AI assistant for terminal commands.
 Your task is to generate the exact shell command the user needs.
 
@@ -92,11 +101,36 @@ Rules:
             context.project_type,
             context.available_commands.join(", "),
             context.current_directory.display()
-        );
+        ))
+    }
+}
 
+#[async_trait]
+impl AIProvider for ClaudeProvider {
+    async fn generate_command(
+        &self,
+        prompt: &str,
+        context: &ProjectContext,
+    ) -> anyhow::Result<String> {
+        let system = Self::build_command_system(context);
         self.request(&system, prompt).await
     }
 
+    fn generate_command_stream<'a>(
+        &'a self,
+        prompt: String,
+        context: &'a ProjectContext,
+    ) -> TextStream<'a> {
+        let system = Self::build_command_system(context);
+        let lines = futures::stream::once(async move {
+            let response = self.send(&system, &prompt, true).await?;
+            anyhow::Ok(stream_lines(response.bytes_stream()))
+        })
+        .try_flatten();
+
+        Box::pin(lines.try_filter_map(|line| futures::future::ready(parse_event_line(&line))))
+    }
+
     async fn explain_command(
         &self,
         command: &str,
@@ -152,6 +186,8 @@ struct ClaudeRequest {
     max_tokens: u32,
     system: String,
     messages: Vec<Message>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 /// Message in a Claude request.
@@ -173,6 +209,49 @@ struct ContentBlock {
     text: String,
 }
 
+/// Payload of a server-sent event in a streamed response.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum StreamEvent {
+    ContentBlockDelta {
+        delta: StreamDelta,
+    },
+    Error {
+        error: StreamError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Delta carried by a `content_block_delta` event.
+#[derive(Debug, Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    text: String,
+}
+
+/// Error carried by an `error` event.
+#[derive(Debug, Deserialize)]
+struct StreamError {
+    message: String,
+}
+
+/// Parse a server-sent event line into its text, if it has any.
+///
+/// Only `data:` lines carry payloads; `event:` lines and blank separators
+/// are skipped.
+fn parse_event_line(line: &str) -> anyhow::Result<Option<String>> {
+    let Some(data) = line.strip_prefix("data:") else {
+        return Ok(None);
+    };
+
+    match serde_json::from_str(data.trim_start())? {
+        StreamEvent::ContentBlockDelta { delta } => Ok(Some(delta.text).filter(|t| !t.is_empty())),
+        StreamEvent::Error { error } => anyhow::bail!("API error: {}", error.message),
+        StreamEvent::Other => Ok(None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,4 +273,18 @@ mod tests {
 
         assert_eq!(context.project_name, "test");
     }
+
+    #[test]
+    fn test_parse_event_line() {
+        let delta = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"cargo "}}"#;
+        assert_eq!(parse_event_line(delta).unwrap().as_deref(), Some("cargo "));
+
+        assert_eq!(parse_event_line("event: content_block_delta").unwrap(), None);
+        assert_eq!(parse_event_line("").unwrap(), None);
+        assert_eq!(parse_event_line(r#"data: {"type":"message_stop"}"#).unwrap(), None);
+
+        let error =
+            r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert!(parse_event_line(error).unwrap_err().to_string().contains("Overloaded"));
+    }
 }
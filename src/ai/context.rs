@@ -1,8 +1,82 @@
 //! Project context for AI requests.
 //!
 //! Builds context information about the current project for AI providers.
+//!
+//! Projects can teach palrun their conventions with `.palrun/ai.toml`:
+//!
+//! ```toml
+//! system_prompt = "We deploy with make targets, never kubectl directly."
+//!
+//! [[examples]]
+//! prompt = "deploy"
+//! command = "make deploy-staging"
+//! ```
+//!
+//! The system prompt and examples are prepended to every command generation
+//! request.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Project AI settings file, relative to the project root.
+pub const AI_CONFIG_FILE: &str = ".palrun/ai.toml";
+
+/// Approximate token budget for few-shot examples.
+///
+/// Examples past the budget are dropped; tokens are estimated at four
+/// characters each.
+pub const EXAMPLE_TOKEN_BUDGET: usize = 500;
 
-use std::path::PathBuf;
+/// Characters per token used when estimating prompt size.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// An example request and the command it should produce.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandExample {
+    /// What the user asks for
+    pub prompt: String,
+
+    /// The command that answers it
+    pub command: String,
+}
+
+impl CommandExample {
+    /// Create an example.
+    pub fn new(prompt: impl Into<String>, command: impl Into<String>) -> Self {
+        Self { prompt: prompt.into(), command: command.into() }
+    }
+
+    fn render(&self) -> String {
+        format!("Request: {}\nCommand: {}\n", self.prompt, self.command)
+    }
+}
+
+/// Contents of `.palrun/ai.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectAiConfig {
+    /// Extra instructions for the model
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+
+    /// Few-shot examples of requests and commands
+    #[serde(default)]
+    pub examples: Vec<CommandExample>,
+}
+
+impl ProjectAiConfig {
+    /// Load the settings for the project at `root`, if the file exists.
+    pub fn load(root: &Path) -> anyhow::Result<Option<Self>> {
+        let path = root.join(AI_CONFIG_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let config = toml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))?;
+        Ok(Some(config))
+    }
+}
 
 /// Project context for AI requests.
 #[derive(Debug, Clone)]
@@ -36,6 +110,12 @@ pub struct ProjectContext {
 
     /// Whether the repo has uncommitted changes
     pub git_dirty: bool,
+
+    /// Project-specific instructions for the model
+    pub system_prompt: Option<String>,
+
+    /// Few-shot examples of requests and commands
+    pub examples: Vec<CommandExample>,
 }
 
 impl ProjectContext {
@@ -53,6 +133,8 @@ impl ProjectContext {
             git_branch: None,
             git_status: None,
             git_dirty: false,
+            system_prompt: None,
+            examples: Vec::new(),
         }
     }
 
@@ -70,6 +152,13 @@ impl ProjectContext {
         // Get git info if available
         context.populate_git_info(&cwd);
 
+        // Project conventions from .palrun/ai.toml
+        match ProjectAiConfig::load(&cwd) {
+            Ok(Some(config)) => context = context.with_ai_config(config),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "Ignoring project AI settings"),
+        }
+
         Ok(context)
     }
 
@@ -141,6 +230,66 @@ impl ProjectContext {
         self
     }
 
+    /// Set a custom system prompt.
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Set few-shot examples.
+    pub fn with_examples(mut self, examples: Vec<CommandExample>) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    /// Apply settings loaded from `.palrun/ai.toml`.
+    pub fn with_ai_config(mut self, config: ProjectAiConfig) -> Self {
+        if let Some(prompt) = config.system_prompt.filter(|p| !p.trim().is_empty()) {
+            self = self.with_system_prompt(prompt);
+        }
+        self.with_examples(config.examples)
+    }
+
+    /// Render the custom system prompt and examples, if there are any.
+    ///
+    /// Examples are included in order until [`EXAMPLE_TOKEN_BUDGET`] would
+    /// be exceeded.
+    pub fn render_guidance(&self) -> Option<String> {
+        let mut guidance = String::new();
+
+        if let Some(ref prompt) = self.system_prompt {
+            guidance.push_str(prompt.trim());
+            guidance.push_str("\n\n");
+        }
+
+        let budget = EXAMPLE_TOKEN_BUDGET * CHARS_PER_TOKEN;
+        let mut examples = String::new();
+        for (included, example) in self.examples.iter().enumerate() {
+            let rendered = example.render();
+            if examples.len() + rendered.len() > budget {
+                let dropped = self.examples.len() - included;
+                tracing::debug!(dropped, "Examples exceed token budget");
+                break;
+            }
+            examples.push_str(&rendered);
+        }
+        if !examples.is_empty() {
+            guidance.push_str("Examples of requests and the commands they should produce:\n");
+            guidance.push_str(&examples);
+            guidance.push('\n');
+        }
+
+        (!guidance.is_empty()).then_some(guidance)
+    }
+
+    /// Prepend the project's system prompt and examples to `prompt`.
+    pub fn prepend_guidance(&self, prompt: String) -> String {
+        match self.render_guidance() {
+            Some(guidance) => guidance + &prompt,
+            None => prompt,
+        }
+    }
+
     /// Summarize context as a string (for debugging or logging).
     pub fn summarize(&self) -> String {
         format!(
@@ -282,6 +431,8 @@ impl Default for ProjectContext {
             git_branch: None,
             git_status: None,
             git_dirty: false,
+            system_prompt: None,
+            examples: Vec::new(),
         }
     }
 }
@@ -337,6 +488,53 @@ mod tests {
         assert!(prompt.contains("Response style"));
     }
 
+    #[test]
+    fn test_guidance_includes_examples() {
+        let context = ProjectContext::new("test", PathBuf::from("."))
+            .with_system_prompt("We deploy with make.")
+            .with_examples(vec![CommandExample::new("deploy", "make deploy-staging")]);
+
+        let prompt = context.prepend_guidance("You are Palrun.".to_string());
+        assert!(prompt.starts_with("We deploy with make."));
+        assert!(prompt.contains("Request: deploy\nCommand: make deploy-staging"));
+        assert!(prompt.ends_with("You are Palrun."));
+
+        let plain = ProjectContext::new("test", PathBuf::from("."));
+        assert_eq!(plain.render_guidance(), None);
+        assert_eq!(plain.prepend_guidance("base".to_string()), "base");
+    }
+
+    #[test]
+    fn test_examples_truncated_to_budget() {
+        let examples: Vec<_> = (0..200)
+            .map(|i| CommandExample::new(format!("task {i}"), format!("make task-{i}")))
+            .collect();
+        let context = ProjectContext::new("test", PathBuf::from(".")).with_examples(examples);
+
+        let guidance = context.render_guidance().unwrap();
+        assert!(guidance.len() <= EXAMPLE_TOKEN_BUDGET * CHARS_PER_TOKEN + 100);
+        assert!(guidance.contains("Command: make task-0\n"));
+        assert!(!guidance.contains("make task-199"));
+    }
+
+    #[test]
+    fn test_load_ai_config() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(ProjectAiConfig::load(temp.path()).unwrap().is_none());
+
+        std::fs::create_dir_all(temp.path().join(".palrun")).unwrap();
+        std::fs::write(
+            temp.path().join(AI_CONFIG_FILE),
+            "system_prompt = \"Use pnpm.\"\n\n[[examples]]\nprompt = \"deploy\"\ncommand = \"make deploy-staging\"\n",
+        )
+        .unwrap();
+
+        let config = ProjectAiConfig::load(temp.path()).unwrap().unwrap();
+        let context = ProjectContext::new("test", temp.path().to_path_buf()).with_ai_config(config);
+        assert_eq!(context.system_prompt.as_deref(), Some("Use pnpm."));
+        assert_eq!(context.examples, [CommandExample::new("deploy", "make deploy-staging")]);
+    }
+
     #[test]
     fn test_default_context() {
         let context = ProjectContext::default();
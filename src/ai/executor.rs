@@ -8,6 +8,21 @@ use async_trait::async_trait;
 
 use super::agent::{AgentToolCall, AgentToolResult, ToolExecutor};
 use crate::mcp::{MCPManager, MCPServerConfig};
+use crate::security::CommandValidator;
+
+/// Words in a tool name that mark it as writing or deleting.
+const DESTRUCTIVE_TOOL_WORDS: &[&str] = &["write", "delete", "remove", "move", "edit"];
+
+/// Get the shell command a tool call carries, if any.
+fn command_argument(tool_call: &AgentToolCall) -> Option<&str> {
+    tool_call.arguments.get("command").and_then(|v| v.as_str())
+}
+
+/// Check a tool call's command with the [`CommandValidator`].
+fn is_destructive_command(tool_call: &AgentToolCall) -> bool {
+    command_argument(tool_call)
+        .is_some_and(|command| CommandValidator::new().is_destructive(command))
+}
 
 /// MCP-based tool executor.
 ///
@@ -114,6 +129,14 @@ impl ToolExecutor for MCPToolExecutor {
             },
         }
     }
+
+    /// MCP tools are destructive if they carry a destructive shell command,
+    /// or if their name says they write or delete.
+    fn is_destructive(&self, tool_call: &AgentToolCall) -> bool {
+        let name = tool_call.name.to_lowercase();
+        is_destructive_command(tool_call)
+            || DESTRUCTIVE_TOOL_WORDS.iter().any(|word| name.contains(word))
+    }
 }
 
 /// Shell command executor for running shell commands.
@@ -138,7 +161,7 @@ impl Default for ShellExecutor {
 impl ToolExecutor for ShellExecutor {
     async fn execute(&mut self, tool_call: &AgentToolCall) -> AgentToolResult {
         // For shell executor, we expect a "command" argument
-        let command = tool_call.arguments.get("command").and_then(|v| v.as_str()).unwrap_or("");
+        let command = command_argument(tool_call).unwrap_or("");
 
         if command.is_empty() {
             return AgentToolResult {
@@ -187,6 +210,10 @@ impl ToolExecutor for ShellExecutor {
             },
         }
     }
+
+    fn is_destructive(&self, tool_call: &AgentToolCall) -> bool {
+        is_destructive_command(tool_call)
+    }
 }
 
 /// Composite executor that can use multiple executors.
@@ -228,7 +255,7 @@ impl Default for CompositeExecutor {
 impl ToolExecutor for CompositeExecutor {
     async fn execute(&mut self, tool_call: &AgentToolCall) -> AgentToolResult {
         // Check if this is a shell command
-        if tool_call.name == "execute_command" || tool_call.name == "shell" {
+        if is_shell_tool(tool_call) {
             return self.shell.execute(tool_call).await;
         }
 
@@ -244,6 +271,18 @@ impl ToolExecutor for CompositeExecutor {
             output: format!("Unknown tool: {}", tool_call.name),
         }
     }
+
+    fn is_destructive(&self, tool_call: &AgentToolCall) -> bool {
+        if is_shell_tool(tool_call) {
+            return self.shell.is_destructive(tool_call);
+        }
+        self.mcp.as_ref().is_some_and(|mcp| mcp.is_destructive(tool_call))
+    }
+}
+
+/// Check if a tool call is handled by the shell executor.
+fn is_shell_tool(tool_call: &AgentToolCall) -> bool {
+    tool_call.name == "execute_command" || tool_call.name == "shell"
 }
 
 #[cfg(test)]
@@ -275,6 +314,32 @@ mod tests {
         assert!(result.output.contains("hello"));
     }
 
+    fn call(name: &str, command: Option<&str>) -> AgentToolCall {
+        AgentToolCall {
+            id: "test".to_string(),
+            name: name.to_string(),
+            arguments: command
+                .map(|c| HashMap::from([("command".to_string(), serde_json::json!(c))]))
+                .unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn test_destructive_flags() {
+        let shell = ShellExecutor::new();
+        assert!(shell.is_destructive(&call("shell", Some("rm -r build"))));
+        assert!(!shell.is_destructive(&call("shell", Some("cargo test"))));
+
+        let mcp = MCPToolExecutor::new();
+        assert!(mcp.is_destructive(&call("write_file", None)));
+        assert!(mcp.is_destructive(&call("run", Some("echo hi > notes.txt"))));
+        assert!(!mcp.is_destructive(&call("read_file", None)));
+
+        let composite = CompositeExecutor::new();
+        assert!(composite.is_destructive(&call("execute_command", Some("rm notes.txt"))));
+        assert!(!composite.is_destructive(&call("write_file", None)));
+    }
+
     #[test]
     fn test_composite_executor_creation() {
         let executor = CompositeExecutor::new();
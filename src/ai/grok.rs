@@ -94,6 +94,7 @@ Rules:
             context.project_type,
             context.available_commands.join(", ")
         );
+        let system = context.prepend_guidance(system);
 
         self.request(&system, prompt).await
     }
@@ -4,7 +4,7 @@
 //!
 //! ## Features
 //!
-//! - Command generation from natural language
+//! - Command generation from natural language, optionally streamed
 //! - Command explanation
 //! - Error diagnosis
 //! - **Agentic tool use** - AI can use MCP tools autonomously
@@ -19,21 +19,28 @@ mod grok;
 mod ollama;
 mod openai;
 mod routing;
+mod stream;
 
 pub use agent::{
     mcp_tools_to_agent_tools, Agent, AgentMessage, AgentProvider, AgentResponse, AgentState,
-    AgentStopReason, AgentTool, AgentToolCall, AgentToolResult, ToolExecutor,
+    AgentStopReason, AgentTool, AgentToolCall, AgentToolResult, ConfirmFn, ToolExecutor,
 };
 pub use azure::AzureOpenAIProvider;
 pub use claude::ClaudeProvider;
-pub use context::ProjectContext;
+pub use context::{
+    CommandExample, ProjectAiConfig, ProjectContext, AI_CONFIG_FILE, EXAMPLE_TOKEN_BUDGET,
+};
 pub use executor::{CompositeExecutor, MCPToolExecutor, ShellExecutor};
 pub use grok::GrokProvider;
 pub use ollama::OllamaProvider;
 pub use openai::OpenAIProvider;
 pub use routing::{FallbackChain, ModelRouter, RoutingConfig, RoutingDecision, TaskCategory};
+pub use stream::{extract_command, LineBuffer, TextStream};
 
 use async_trait::async_trait;
+use futures::StreamExt;
+
+use crate::security::redact;
 
 /// Trait for AI providers.
 #[async_trait]
@@ -45,6 +52,22 @@ pub trait AIProvider: Send + Sync {
         context: &ProjectContext,
     ) -> anyhow::Result<String>;
 
+    /// Generate a command from natural language, yielding text as it arrives.
+    ///
+    /// The chunks concatenate to the raw model reply; use
+    /// [`extract_command`] on the whole text to get the command. Providers
+    /// without streaming support yield the result of `generate_command` as a
+    /// single chunk.
+    fn generate_command_stream<'a>(
+        &'a self,
+        prompt: String,
+        context: &'a ProjectContext,
+    ) -> TextStream<'a> {
+        Box::pin(futures::stream::once(
+            async move { self.generate_command(&prompt, context).await },
+        ))
+    }
+
     /// Explain what a command does.
     async fn explain_command(
         &self,
@@ -84,6 +107,9 @@ pub enum AIError {
 
     #[error("No response from AI")]
     NoResponse,
+
+    #[error("Provider {0} is unavailable in offline mode")]
+    Offline(String),
 }
 
 /// AI provider manager with fallback support.
@@ -94,8 +120,11 @@ pub enum AIError {
 /// 3. Azure (if AZURE_OPENAI_* vars set)
 /// 4. Grok (if XAI_API_KEY set)
 /// 5. Ollama (if running locally)
+///
+/// In offline mode only the local Ollama provider is considered.
 pub struct AIManager {
     providers: Vec<Box<dyn AIProvider>>,
+    routing: Option<RoutingConfig>,
 }
 
 impl AIManager {
@@ -103,6 +132,15 @@ impl AIManager {
     pub async fn new() -> Self {
         let mut providers: Vec<Box<dyn AIProvider>> = Vec::new();
 
+        // Offline mode: skip all hosted providers without touching the network
+        if crate::core::is_offline_mode() {
+            let ollama = OllamaProvider::new();
+            if ollama.is_available().await {
+                providers.push(Box::new(ollama));
+            }
+            return Self { providers, routing: None };
+        }
+
         // Try Claude first (requires API key)
         if let Ok(claude) = ClaudeProvider::new() {
             if claude.is_available().await {
@@ -137,12 +175,16 @@ impl AIManager {
             providers.push(Box::new(ollama));
         }
 
-        Self { providers }
+        Self { providers, routing: None }
     }
 
     /// Create with a specific provider.
     pub fn with_provider(provider: impl Into<String>) -> anyhow::Result<Self> {
         let provider_name = provider.into();
+        if crate::core::is_offline_mode() && provider_name != "ollama" {
+            return Err(AIError::Offline(provider_name).into());
+        }
+
         let provider: Box<dyn AIProvider> = match provider_name.as_str() {
             "claude" => Box::new(ClaudeProvider::new()?),
             "openai" => Box::new(OpenAIProvider::new()?),
@@ -151,12 +193,43 @@ impl AIManager {
             "ollama" => Box::new(OllamaProvider::new()),
             other => anyhow::bail!("Unknown provider: {}", other),
         };
-        Ok(Self { providers: vec![provider] })
+        Ok(Self { providers: vec![provider], routing: None })
     }
 
     /// Create with only Ollama (for local-only usage).
     pub fn ollama_only() -> Self {
-        Self { providers: vec![Box::new(OllamaProvider::new())] }
+        Self { providers: vec![Box::new(OllamaProvider::new())], routing: None }
+    }
+
+    /// Route each task category to its configured provider.
+    ///
+    /// Without a routing table every task tries the available providers in
+    /// their default order.
+    pub fn with_routing(mut self, routing: Option<RoutingConfig>) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    /// Providers to try for a task category, in order.
+    ///
+    /// With a routing table that is the category's provider, then the
+    /// fallback, then the local model, skipping any that are unavailable.
+    pub fn fallback_chain(&self, category: TaskCategory) -> FallbackChain<'_> {
+        let providers = match self.routing {
+            Some(ref routing) => routing
+                .route(category)
+                .into_iter()
+                .filter_map(|name| self.providers.iter().find(|p| p.name() == name))
+                .map(AsRef::as_ref)
+                .collect(),
+            None => self.providers.iter().map(AsRef::as_ref).collect(),
+        };
+        FallbackChain::new(providers)
+    }
+
+    /// Get the provider a task category would use first.
+    pub fn routed_provider(&self, category: TaskCategory) -> Option<&str> {
+        self.fallback_chain(category).current().map(|p| p.name())
     }
 
     /// List all available providers.
@@ -175,14 +248,17 @@ impl AIManager {
     }
 
     /// Generate a command from natural language.
+    ///
+    /// Routed as [`TaskCategory::CodeGeneration`].
     pub async fn generate_command(
         &self,
         prompt: &str,
         context: &ProjectContext,
     ) -> anyhow::Result<String> {
-        for provider in &self.providers {
-            match provider.generate_command(prompt, context).await {
-                Ok(result) => return Ok(result),
+        let prompt = redact(prompt);
+        for provider in self.fallback_chain(TaskCategory::CodeGeneration).providers() {
+            match provider.generate_command(&prompt, context).await {
+                Ok(result) => return Ok(redact(&result).into_owned()),
                 Err(e) => {
                     tracing::warn!(provider = provider.name(), error = %e, "Provider failed, trying next");
                 }
@@ -192,15 +268,48 @@ impl AIManager {
         Err(AIError::ProviderNotAvailable("No AI provider available".to_string()).into())
     }
 
+    /// Generate a command from natural language as a stream of text chunks.
+    ///
+    /// Falls back to the next provider if one fails before producing any
+    /// text. Once text has been produced, errors are passed through.
+    /// Routed as [`TaskCategory::CodeGeneration`].
+    pub async fn generate_command_stream<'a>(
+        &'a self,
+        prompt: &str,
+        context: &'a ProjectContext,
+    ) -> anyhow::Result<TextStream<'a>> {
+        let prompt = redact(prompt).into_owned();
+        for &provider in self.fallback_chain(TaskCategory::CodeGeneration).providers() {
+            let mut stream = provider.generate_command_stream(prompt.clone(), context);
+            match stream.next().await {
+                Some(Ok(first)) => {
+                    let chunks = futures::stream::once(async { Ok(first) })
+                        .chain(stream)
+                        .map(|chunk| chunk.map(|text| redact(&text).into_owned()));
+                    return Ok(Box::pin(chunks));
+                }
+                Some(Err(e)) => {
+                    tracing::warn!(provider = provider.name(), error = %e, "Provider failed, trying next");
+                }
+                None => return Ok(Box::pin(futures::stream::empty())),
+            }
+        }
+
+        Err(AIError::ProviderNotAvailable("No AI provider available".to_string()).into())
+    }
+
     /// Explain what a command does.
+    ///
+    /// Routed as [`TaskCategory::Documentation`].
     pub async fn explain_command(
         &self,
         command: &str,
         context: &ProjectContext,
     ) -> anyhow::Result<String> {
-        for provider in &self.providers {
-            match provider.explain_command(command, context).await {
-                Ok(result) => return Ok(result),
+        let command = redact(command);
+        for provider in self.fallback_chain(TaskCategory::Documentation).providers() {
+            match provider.explain_command(&command, context).await {
+                Ok(result) => return Ok(redact(&result).into_owned()),
                 Err(e) => {
                     tracing::warn!(provider = provider.name(), error = %e, "Provider failed, trying next");
                 }
@@ -211,15 +320,18 @@ impl AIManager {
     }
 
     /// Diagnose why a command failed.
+    ///
+    /// Routed as [`TaskCategory::ErrorDiagnosis`].
     pub async fn diagnose_error(
         &self,
         command: &str,
         error: &str,
         context: &ProjectContext,
     ) -> anyhow::Result<String> {
-        for provider in &self.providers {
-            match provider.diagnose_error(command, error, context).await {
-                Ok(result) => return Ok(result),
+        let (command, error) = (redact(command), redact(error));
+        for provider in self.fallback_chain(TaskCategory::ErrorDiagnosis).providers() {
+            match provider.diagnose_error(&command, &error, context).await {
+                Ok(result) => return Ok(redact(&result).into_owned()),
                 Err(e) => {
                     tracing::warn!(provider = provider.name(), error = %e, "Provider failed, trying next");
                 }
@@ -233,6 +345,7 @@ impl AIManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_ai_manager_ollama_only() {
@@ -259,4 +372,180 @@ mod tests {
         let providers = manager.available_providers();
         assert_eq!(providers, vec!["ollama"]);
     }
+
+    #[test]
+    #[serial]
+    fn test_with_provider_offline() {
+        crate::core::set_offline_mode(true);
+        let result = AIManager::with_provider("claude");
+        let ollama = AIManager::with_provider("ollama");
+        crate::core::set_offline_mode(false);
+
+        let err = result.err().unwrap();
+        assert!(err.to_string().contains("offline mode"));
+        assert!(ollama.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_manager_offline_skips_hosted_providers() {
+        crate::core::set_offline_mode(true);
+        std::env::set_var("ANTHROPIC_API_KEY", "test-key");
+        let manager = AIManager::new().await;
+        std::env::remove_var("ANTHROPIC_API_KEY");
+        crate::core::set_offline_mode(false);
+
+        assert!(!manager.available_providers().contains(&"claude"));
+    }
+
+    /// Provider replying with fixed chunks; fails when there are none.
+    ///
+    /// Only streams chunk by chunk when `streaming` is set; otherwise it
+    /// behaves like the trait's single-chunk default.
+    struct MockProvider {
+        name: &'static str,
+        chunks: Vec<&'static str>,
+        streaming: bool,
+    }
+
+    #[async_trait]
+    impl AIProvider for MockProvider {
+        async fn generate_command(
+            &self,
+            _prompt: &str,
+            _context: &ProjectContext,
+        ) -> anyhow::Result<String> {
+            if self.chunks.is_empty() {
+                anyhow::bail!("mock provider unavailable");
+            }
+            Ok(self.chunks.concat())
+        }
+
+        fn generate_command_stream<'a>(
+            &'a self,
+            prompt: String,
+            context: &'a ProjectContext,
+        ) -> TextStream<'a> {
+            if !self.streaming || self.chunks.is_empty() {
+                return Box::pin(futures::stream::once(async move {
+                    self.generate_command(&prompt, context).await
+                }));
+            }
+            Box::pin(futures::stream::iter(self.chunks.iter().map(|c| Ok((*c).to_string()))))
+        }
+
+        async fn explain_command(
+            &self,
+            command: &str,
+            context: &ProjectContext,
+        ) -> anyhow::Result<String> {
+            self.generate_command(command, context).await.map(|_| self.name.to_string())
+        }
+
+        async fn diagnose_error(
+            &self,
+            command: &str,
+            _error: &str,
+            context: &ProjectContext,
+        ) -> anyhow::Result<String> {
+            self.generate_command(command, context).await.map(|_| self.name.to_string())
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    fn mock(chunks: &[&'static str], streaming: bool) -> Box<dyn AIProvider> {
+        Box::new(MockProvider { name: "mock", chunks: chunks.to_vec(), streaming })
+    }
+
+    /// A provider named `name` that answers with its name, or is down.
+    fn named(name: &'static str, up: bool) -> Box<dyn AIProvider> {
+        let chunks = if up { vec![name] } else { vec![] };
+        Box::new(MockProvider { name, chunks, streaming: false })
+    }
+
+    #[tokio::test]
+    async fn test_generate_command_stream_chunks() {
+        let manager = AIManager {
+            providers: vec![mock(&[], true), mock(&["cargo ", "test ", "--all"], true)],
+            routing: None,
+        };
+        let context = ProjectContext::new("test", std::path::PathBuf::from("."));
+
+        let stream = manager.generate_command_stream("run all tests", &context).await.unwrap();
+        let chunks: Vec<String> = stream.map(Result::unwrap).collect().await;
+
+        assert_eq!(chunks, ["cargo ", "test ", "--all"]);
+        assert_eq!(extract_command(&chunks.concat()), "cargo test --all");
+    }
+
+    #[tokio::test]
+    async fn test_non_streaming_provider_yields_single_chunk() {
+        let manager = AIManager { providers: vec![mock(&["npm ", "test"], false)], routing: None };
+        let context = ProjectContext::new("test", std::path::PathBuf::from("."));
+
+        let stream = manager.generate_command_stream("run tests", &context).await.unwrap();
+        let chunks: Vec<String> = stream.map(Result::unwrap).collect().await;
+        assert_eq!(chunks, ["npm test"]);
+
+        let failing = AIManager { providers: vec![mock(&[], false)], routing: None };
+        assert!(failing.generate_command_stream("anything", &context).await.is_err());
+    }
+
+    fn routing() -> RoutingConfig {
+        RoutingConfig {
+            code_generation: "claude".to_string(),
+            documentation: "ollama".to_string(),
+            error_diagnosis: "claude".to_string(),
+            fallback: "openai".to_string(),
+            local: "ollama".to_string(),
+            ..RoutingConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routing_selects_provider_per_category() {
+        let manager = AIManager {
+            providers: vec![named("claude", true), named("openai", true), named("ollama", true)],
+            routing: None,
+        }
+        .with_routing(Some(routing()));
+        let context = ProjectContext::new("test", std::path::PathBuf::from("."));
+
+        assert_eq!(manager.generate_command("deploy", &context).await.unwrap(), "claude");
+        assert_eq!(manager.explain_command("ls", &context).await.unwrap(), "ollama");
+        assert_eq!(manager.diagnose_error("ls", "oops", &context).await.unwrap(), "claude");
+        assert_eq!(manager.routed_provider(TaskCategory::Documentation), Some("ollama"));
+
+        let chain = manager.fallback_chain(TaskCategory::Documentation);
+        let names: Vec<_> = chain.providers().iter().map(|p| p.name()).collect();
+        assert_eq!(names, ["ollama", "openai"]);
+    }
+
+    #[tokio::test]
+    async fn test_routing_falls_back_when_primary_down() {
+        let manager = AIManager {
+            providers: vec![named("claude", false), named("openai", true), named("ollama", true)],
+            routing: Some(routing()),
+        };
+        let context = ProjectContext::new("test", std::path::PathBuf::from("."));
+
+        assert_eq!(manager.generate_command("deploy", &context).await.unwrap(), "openai");
+
+        // Unavailable providers are skipped entirely
+        let manager =
+            AIManager { providers: vec![named("ollama", true)], routing: Some(routing()) };
+        assert_eq!(manager.routed_provider(TaskCategory::CodeGeneration), Some("ollama"));
+        assert_eq!(manager.generate_command("deploy", &context).await.unwrap(), "ollama");
+
+        // Providers outside the route are never used
+        let manager = AIManager { providers: vec![named("grok", true)], routing: Some(routing()) };
+        assert!(manager.explain_command("ls", &context).await.is_err());
+    }
 }
@@ -7,6 +7,7 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
+use futures::TryStreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
@@ -14,7 +15,8 @@ use super::agent::{
     AgentMessage, AgentProvider, AgentResponse, AgentState, AgentStopReason, AgentTool,
     AgentToolCall,
 };
-use super::{AIProvider, ProjectContext};
+use super::stream::{extract_command, stream_lines};
+use super::{AIProvider, ProjectContext, TextStream};
 
 /// Ollama API provider for local LLM.
 pub struct OllamaProvider {
@@ -48,10 +50,10 @@ impl OllamaProvider {
         self
     }
 
-    /// Make a request to the Ollama API.
-    async fn request(&self, prompt: &str) -> anyhow::Result<String> {
+    /// Send a request to the generate endpoint and check the status.
+    async fn send(&self, prompt: &str, stream: bool) -> anyhow::Result<reqwest::Response> {
         let request =
-            OllamaRequest { model: self.model.clone(), prompt: prompt.to_string(), stream: false };
+            OllamaRequest { model: self.model.clone(), prompt: prompt.to_string(), stream };
 
         let response = self
             .client
@@ -66,13 +68,18 @@ impl OllamaProvider {
             anyhow::bail!("Ollama API error ({}): {}", status, body);
         }
 
-        let response: OllamaResponse = response.json().await?;
+        Ok(response)
+    }
+
+    /// Make a request to the Ollama API.
+    async fn request(&self, prompt: &str) -> anyhow::Result<String> {
+        let response: OllamaResponse = self.send(prompt, false).await?.json().await?;
         Ok(response.response)
     }
 
     /// Build a system prompt for command generation.
     fn build_command_prompt(prompt: &str, context: &ProjectContext) -> String {
-        format!(
+        context.prepend_guidance(format!(
             r"You are Palrun, an AI assistant for terminal commands.
 Your task is to generate the exact shell command the user needs.
 
@@ -93,7 +100,7 @@ Rules:
             context.available_commands.join(", "),
             context.current_directory.display(),
             prompt
-        )
+        ))
     }
 
     /// Build a prompt for command explanation.
@@ -151,14 +158,22 @@ impl AIProvider for OllamaProvider {
         let response = self.request(&full_prompt).await?;
 
         // Clean up the response - remove any markdown or extra whitespace
-        let command = response
-            .lines()
-            .find(|line| !line.trim().is_empty() && !line.starts_with("```"))
-            .unwrap_or(&response)
-            .trim()
-            .to_string();
-
-        Ok(command)
+        Ok(extract_command(&response))
+    }
+
+    fn generate_command_stream<'a>(
+        &'a self,
+        prompt: String,
+        context: &'a ProjectContext,
+    ) -> TextStream<'a> {
+        let full_prompt = Self::build_command_prompt(&prompt, context);
+        let lines = futures::stream::once(async move {
+            let response = self.send(&full_prompt, true).await?;
+            anyhow::Ok(stream_lines(response.bytes_stream()))
+        })
+        .try_flatten();
+
+        Box::pin(lines.try_filter_map(|line| futures::future::ready(parse_stream_line(&line))))
     }
 
     async fn explain_command(
@@ -211,6 +226,27 @@ struct OllamaResponse {
     response: String,
 }
 
+/// One NDJSON line of a streamed generate response.
+#[derive(Debug, Deserialize)]
+struct OllamaStreamChunk {
+    #[serde(default)]
+    response: String,
+    error: Option<String>,
+}
+
+/// Parse a line of a streamed response into its text, if it has any.
+fn parse_stream_line(line: &str) -> anyhow::Result<Option<String>> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let chunk: OllamaStreamChunk = serde_json::from_str(line)?;
+    if let Some(error) = chunk.error {
+        anyhow::bail!("Ollama API error: {}", error);
+    }
+    Ok(Some(chunk.response).filter(|text| !text.is_empty()))
+}
+
 // ============================================================================
 // Agentic Tool Use Support (Chat API)
 // ============================================================================
@@ -416,6 +452,7 @@ impl AgentProvider for OllamaProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ai::CommandExample;
     use std::path::PathBuf;
 
     #[test]
@@ -448,4 +485,30 @@ mod tests {
         assert!(prompt.contains("npm run build"));
         assert!(prompt.contains("run tests"));
     }
+
+    #[test]
+    fn test_command_prompt_includes_examples() {
+        let context = ProjectContext::new("test-project", PathBuf::from("/project"))
+            .with_system_prompt("Deployments go through make.")
+            .with_examples(vec![CommandExample::new("deploy", "make deploy-staging")]);
+
+        let prompt = OllamaProvider::build_command_prompt("deploy please", &context);
+
+        assert!(prompt.starts_with("Deployments go through make."));
+        assert!(prompt.contains("Command: make deploy-staging"));
+        assert!(prompt.contains("User request: deploy please"));
+    }
+
+    #[test]
+    fn test_parse_stream_line() {
+        let text = parse_stream_line(r#"{"model":"llama3.2","response":"npm ","done":false}"#);
+        assert_eq!(text.unwrap().as_deref(), Some("npm "));
+
+        let done = parse_stream_line(r#"{"model":"llama3.2","response":"","done":true}"#);
+        assert_eq!(done.unwrap(), None);
+        assert_eq!(parse_stream_line("").unwrap(), None);
+
+        let error = parse_stream_line(r#"{"error":"model 'nope' not found"}"#);
+        assert!(error.unwrap_err().to_string().contains("not found"));
+    }
 }
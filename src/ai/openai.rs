@@ -7,6 +7,10 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 use super::{AIProvider, ProjectContext};
+use crate::core::OpenAIConfig;
+
+/// Official OpenAI API endpoint.
+pub const DEFAULT_BASE_URL: &str = "https://api.openai.com/v1";
 
 /// OpenAI API provider.
 pub struct OpenAIProvider {
@@ -19,16 +23,31 @@ pub struct OpenAIProvider {
 impl OpenAIProvider {
     /// Create a new OpenAI provider.
     ///
-    /// Reads API key from OPENAI_API_KEY environment variable.
+    /// Uses the `[ai.openai]` config section, overridden by the
+    /// OPENAI_API_KEY, OPENAI_BASE_URL, and OPENAI_MODEL environment
+    /// variables. The base URL never comes from a project config file, so
+    /// the API key only goes to an endpoint the user chose.
     pub fn new() -> anyhow::Result<Self> {
-        let api_key = std::env::var("OPENAI_API_KEY")
-            .map_err(|_| anyhow::anyhow!("OPENAI_API_KEY not set"))?;
+        let config = crate::Config::load().map(|c| c.ai.openai).unwrap_or_else(|_| {
+            let mut config = OpenAIConfig::default();
+            config.apply_env();
+            config
+        });
+        Self::from_config(&config)
+    }
+
+    /// Create from an `[ai.openai]` config section.
+    ///
+    /// Without a base URL the official endpoint is used.
+    pub fn from_config(config: &OpenAIConfig) -> anyhow::Result<Self> {
+        let api_key =
+            config.api_key.clone().ok_or_else(|| anyhow::anyhow!("OPENAI_API_KEY not set"))?;
 
         Ok(Self {
             client: Client::new(),
             api_key,
-            model: "gpt-4o".to_string(),
-            base_url: "https://api.openai.com/v1".to_string(),
+            model: config.model.clone(),
+            base_url: config.base_url.clone().unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
         })
     }
 
@@ -44,6 +63,12 @@ impl OpenAIProvider {
         self
     }
 
+    /// Build the URL for an API path, whether or not the base URL ends
+    /// with a slash.
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path)
+    }
+
     /// Make a request to the OpenAI API.
     async fn request(&self, system: &str, user_message: &str) -> anyhow::Result<String> {
         let request = OpenAIRequest {
@@ -58,7 +83,7 @@ impl OpenAIProvider {
 
         let response = self
             .client
-            .post(format!("{}/chat/completions", self.base_url))
+            .post(self.endpoint("chat/completions"))
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
@@ -105,6 +130,7 @@ Rules:
             context.project_type,
             context.available_commands.join(", ")
         );
+        let system = context.prepend_guidance(system);
 
         self.request(&system, prompt).await
     }
@@ -236,4 +262,50 @@ mod tests {
             None => std::env::remove_var("OPENAI_API_KEY"),
         }
     }
+
+    #[test]
+    fn test_request_url_from_base_url() {
+        let config = OpenAIConfig {
+            api_key: Some("test-key".to_string()),
+            base_url: Some("http://localhost:4000/v1/".to_string()),
+            ..OpenAIConfig::default()
+        };
+
+        let provider = OpenAIProvider::from_config(&config).unwrap();
+        assert_eq!(
+            provider.endpoint("chat/completions"),
+            "http://localhost:4000/v1/chat/completions"
+        );
+
+        let config = OpenAIConfig { base_url: None, ..config };
+        let provider = OpenAIProvider::from_config(&config).unwrap();
+        assert_eq!(
+            provider.endpoint("chat/completions"),
+            format!("{DEFAULT_BASE_URL}/chat/completions")
+        );
+    }
+
+    #[test]
+    #[serial(openai_env)]
+    fn test_env_overrides() {
+        let saved: Vec<_> = ["OPENAI_API_KEY", "OPENAI_BASE_URL", "OPENAI_MODEL"]
+            .into_iter()
+            .map(|name| (name, std::env::var(name).ok()))
+            .collect();
+        std::env::set_var("OPENAI_API_KEY", "test-key");
+        std::env::set_var("OPENAI_BASE_URL", "https://llm.internal/v1");
+        std::env::set_var("OPENAI_MODEL", "llama-3-70b");
+
+        let provider = OpenAIProvider::new().unwrap();
+
+        for (name, value) in saved {
+            match value {
+                Some(val) => std::env::set_var(name, val),
+                None => std::env::remove_var(name),
+            }
+        }
+
+        assert_eq!(provider.base_url, "https://llm.internal/v1");
+        assert_eq!(provider.model, "llama-3-70b");
+    }
 }
@@ -156,6 +156,18 @@ impl RoutingConfig {
             TaskCategory::ErrorDiagnosis => &self.error_diagnosis,
         }
     }
+
+    /// Provider names to try for a task category, in order: the category's
+    /// model, then the fallback, then the local model.
+    pub fn route(&self, category: TaskCategory) -> Vec<&str> {
+        let mut route = Vec::new();
+        for name in [self.model_for(category), &self.fallback, &self.local] {
+            if !route.contains(&name) {
+                route.push(name);
+            }
+        }
+        route
+    }
 }
 
 /// Model router that selects the best provider for each task.
@@ -174,22 +186,24 @@ impl ModelRouter {
     pub async fn with_config(config: RoutingConfig) -> Self {
         let mut providers: Vec<(String, Box<dyn AIProvider>)> = Vec::new();
 
-        // Try to initialize each provider
-        if let Ok(claude) = ClaudeProvider::new() {
-            if claude.is_available().await {
-                providers.push(("claude".to_string(), Box::new(claude)));
+        // Try to initialize each hosted provider (skipped in offline mode)
+        if !crate::core::is_offline_mode() {
+            if let Ok(claude) = ClaudeProvider::new() {
+                if claude.is_available().await {
+                    providers.push(("claude".to_string(), Box::new(claude)));
+                }
             }
-        }
 
-        if let Ok(openai) = OpenAIProvider::new() {
-            if openai.is_available().await {
-                providers.push(("openai".to_string(), Box::new(openai)));
+            if let Ok(openai) = OpenAIProvider::new() {
+                if openai.is_available().await {
+                    providers.push(("openai".to_string(), Box::new(openai)));
+                }
             }
-        }
 
-        if let Ok(grok) = GrokProvider::new() {
-            if grok.is_available().await {
-                providers.push(("grok".to_string(), Box::new(grok)));
+            if let Ok(grok) = GrokProvider::new() {
+                if grok.is_available().await {
+                    providers.push(("grok".to_string(), Box::new(grok)));
+                }
             }
         }
 
@@ -213,29 +227,13 @@ impl ModelRouter {
     }
 
     /// Get a fallback chain for a task category.
+    ///
+    /// Primary model for the category, then the fallback model, then the
+    /// local model as last resort; unavailable ones are skipped.
     pub fn fallback_chain(&self, category: TaskCategory) -> FallbackChain<'_> {
-        let mut chain = Vec::new();
-
-        // Primary model for this category
-        if let Some(primary) = self.select(category) {
-            chain.push(primary);
-        }
-
-        // Fallback model
-        if let Some(fallback) = self.get_provider(&self.config.fallback) {
-            if !chain.iter().any(|p| p.name() == fallback.name()) {
-                chain.push(fallback);
-            }
-        }
-
-        // Local model as last resort
-        if let Some(local) = self.get_provider(&self.config.local) {
-            if !chain.iter().any(|p| p.name() == local.name()) {
-                chain.push(local);
-            }
-        }
-
-        FallbackChain::new(chain)
+        let chain =
+            self.config.route(category).into_iter().filter_map(|name| self.get_provider(name));
+        FallbackChain::new(chain.collect())
     }
 
     /// List available providers.
@@ -368,6 +366,13 @@ mod tests {
         assert_eq!(config.model_for(TaskCategory::QuickTask), "ollama");
     }
 
+    #[test]
+    fn test_routing_config_route() {
+        let config = RoutingConfig::default();
+        assert_eq!(config.route(TaskCategory::Planning), ["claude", "openai", "ollama"]);
+        assert_eq!(config.route(TaskCategory::QuickTask), ["ollama", "openai"]);
+    }
+
     #[test]
     fn test_task_category_default_model() {
         assert_eq!(TaskCategory::Planning.default_model(), "claude");
@@ -0,0 +1,139 @@
+//! Streaming support for AI responses.
+//!
+//! Providers stream responses as NDJSON (Ollama) or server-sent events
+//! (Claude). Both are line-based, so the raw byte stream is first split into
+//! complete lines; a line is only parsed once its newline has arrived, which
+//! keeps a JSON object or a multi-byte character split across network chunks
+//! from being decoded half-way.
+
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+
+/// A stream of text chunks from an AI provider.
+pub type TextStream<'a> = Pin<Box<dyn Stream<Item = anyhow::Result<String>> + Send + 'a>>;
+
+/// Accumulates bytes and hands out complete lines.
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    pending: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a chunk of bytes.
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.pending.extend_from_slice(chunk);
+    }
+
+    /// Take the next complete line, without its line ending.
+    pub fn next_line(&mut self) -> Option<String> {
+        let end = self.pending.iter().position(|&b| b == b'\n')?;
+        let line: Vec<u8> = self.pending.drain(..=end).collect();
+        Some(decode_line(&line[..end]))
+    }
+
+    /// Take whatever is left once the stream has ended.
+    pub fn finish(&mut self) -> Option<String> {
+        if self.pending.iter().all(u8::is_ascii_whitespace) {
+            self.pending.clear();
+            return None;
+        }
+        let rest = std::mem::take(&mut self.pending);
+        Some(decode_line(&rest))
+    }
+}
+
+fn decode_line(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Split a byte stream into lines.
+pub fn stream_lines<'a, S, B, E>(bytes: S) -> impl Stream<Item = anyhow::Result<String>> + Send + 'a
+where
+    S: Stream<Item = Result<B, E>> + Send + 'a,
+    B: AsRef<[u8]>,
+    E: Into<anyhow::Error>,
+{
+    let state = (Box::pin(bytes), LineBuffer::new(), false);
+    futures::stream::unfold(state, |(mut bytes, mut buffer, mut done)| async move {
+        loop {
+            if let Some(line) = buffer.next_line() {
+                return Some((Ok(line), (bytes, buffer, done)));
+            }
+            if done {
+                return buffer.finish().map(|line| (Ok(line), (bytes, buffer, done)));
+            }
+            match bytes.next().await {
+                Some(Ok(chunk)) => buffer.push(chunk.as_ref()),
+                Some(Err(e)) => {
+                    buffer = LineBuffer::new();
+                    return Some((Err(e.into()), (bytes, buffer, true)));
+                }
+                None => done = true,
+            }
+        }
+    })
+}
+
+/// Pull the command out of a model's reply.
+///
+/// Models sometimes wrap the command in a code fence or add blank lines;
+/// this returns the first line that is neither, trimmed.
+pub fn extract_command(response: &str) -> String {
+    response
+        .lines()
+        .find(|line| !line.trim().is_empty() && !line.trim_start().starts_with("```"))
+        .unwrap_or(response)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer_waits_for_newline() {
+        let mut buffer = LineBuffer::new();
+        buffer.push(br#"{"response":"npm "#);
+        assert_eq!(buffer.next_line(), None);
+
+        buffer.push(b"run\"}\r\n{\"resp");
+        assert_eq!(buffer.next_line().as_deref(), Some(r#"{"response":"npm run"}"#));
+        assert_eq!(buffer.next_line(), None);
+        assert_eq!(buffer.finish().as_deref(), Some(r#"{"resp"#));
+        assert_eq!(buffer.finish(), None);
+    }
+
+    #[test]
+    fn test_line_buffer_keeps_split_characters_whole() {
+        let mut buffer = LineBuffer::new();
+        let text = "echo ✨\n".as_bytes();
+        buffer.push(&text[..7]);
+        buffer.push(&text[7..]);
+        assert_eq!(buffer.next_line().as_deref(), Some("echo ✨"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_lines() {
+        let chunks: Vec<Result<&[u8], std::io::Error>> =
+            vec![Ok(b"data: one\ndata: t"), Ok(b"wo\n"), Ok(b"\ndata: three")];
+        let lines: Vec<String> =
+            stream_lines(futures::stream::iter(chunks)).map(Result::unwrap).collect().await;
+
+        assert_eq!(lines, ["data: one", "data: two", "", "data: three"]);
+    }
+
+    #[test]
+    fn test_extract_command() {
+        assert_eq!(extract_command("npm test"), "npm test");
+        assert_eq!(extract_command("```bash\ncargo build\n```"), "cargo build");
+        assert_eq!(extract_command("\n  ls -la  \n"), "ls -la");
+    }
+}
@@ -4,14 +4,17 @@
 //! and coordinates between the TUI, command registry, and scanners.
 
 use std::collections::HashSet;
+use std::fmt::Write as _;
 use std::path::PathBuf;
 
 use crate::core::{
-    send_notification, BackgroundEvent, BackgroundManager, CaptureManager, ChainExecutor,
-    ChainStepStatus, Command, CommandChain, CommandContext, CommandRegistry, Config, ContextFilter,
-    HistoryManager, ParsedQuery, TrustStore,
+    get_source_types, get_tags, resolve_alias, send_notification, AliasError, BackgroundEvent,
+    BackgroundManager, CaptureManager, ChainExecutor, ChainStepStatus, Command, CommandChain,
+    CommandContext, CommandRegistry, CommandSource, Config, ContextFilter, EnterAction,
+    HistoryManager, ParsedQuery, SourceRegistry, Stream, TrustStore,
 };
-use crate::tui::Theme;
+use crate::security::{SecurityConfig, SecurityManager, ValidationResult};
+use crate::tui::{CommandPane, PaneExit, Theme};
 
 #[cfg(feature = "git")]
 use crate::git::GitInfo;
@@ -34,6 +37,9 @@ pub struct App {
     /// The command registry containing all discovered commands
     pub registry: CommandRegistry,
 
+    /// Display metadata for plugin-provided command sources
+    pub sources: SourceRegistry,
+
     /// Currently filtered/matched commands (indices into registry)
     pub filtered_commands: Vec<usize>,
 
@@ -89,15 +95,27 @@ pub struct App {
     /// Current UI theme
     pub theme: Theme,
 
-    /// Active filters display string (from parsed query)
+    /// Active filters display string (filter bar and parsed query)
     pub active_filters: Option<String>,
 
+    /// Source type the filter bar narrows commands to (cycled with Ctrl+F)
+    pub source_filter: Option<String>,
+
+    /// Tag the filter bar narrows commands to (cycled with Ctrl+L)
+    pub tag_filter: Option<String>,
+
     /// Command history and favorites manager
     pub history_manager: Option<HistoryManager>,
 
+    /// Command running or last run in the output pane (`ui.run_in_pane`)
+    pub pane: Option<CommandPane>,
+
     /// Shell command for pass-through mode
     pub pass_through_command: Option<String>,
 
+    /// Command to print after the TUI exits (`print` Enter action)
+    pub print_on_exit: Option<String>,
+
     /// Command palette search input
     pub palette_input: String,
 
@@ -323,15 +341,17 @@ impl App {
     /// Returns an error if the current working directory cannot be determined.
     pub fn new() -> anyhow::Result<Self> {
         let cwd = std::env::current_dir()?;
-        let config = Config::load()?;
-        let context = CommandContext::new(&cwd, &cwd);
+        let config = Config::load_for(&cwd)?;
+        let project_root = Config::find_project_root(&cwd).unwrap_or_else(|| cwd.clone());
+        let context = CommandContext::new(&cwd, &project_root);
 
         #[cfg(feature = "git")]
         let git_info = crate::git::current_git_info();
 
         let background_manager = BackgroundManager::new().ok();
         let capture_manager = CaptureManager::new().ok();
-        let history_manager = HistoryManager::new().ok();
+        let history_manager =
+            HistoryManager::new().ok().map(|m| m.with_max_entries(config.general.max_history));
 
         // Resolve theme from config
         let theme = Self::resolve_theme(&config);
@@ -346,7 +366,8 @@ impl App {
         Ok(Self {
             input: String::new(),
             cursor_position: 0,
-            registry: CommandRegistry::new(),
+            registry: CommandRegistry::with_search(config.ui.search),
+            sources: SourceRegistry::new(),
             filtered_commands: Vec::new(),
             selected: 0,
             should_quit: false,
@@ -367,8 +388,12 @@ impl App {
             capture_manager,
             theme,
             active_filters: None,
+            source_filter: None,
+            tag_filter: None,
             history_manager,
+            pane: None,
             pass_through_command: None,
+            print_on_exit: None,
             palette_input: String::new(),
             palette_selected: 0,
             context_menu_selected: 0,
@@ -497,6 +522,7 @@ impl App {
             input: String::new(),
             cursor_position: 0,
             registry: CommandRegistry::new(),
+            sources: SourceRegistry::new(),
             filtered_commands: Vec::new(),
             selected: 0,
             should_quit: false,
@@ -517,8 +543,12 @@ impl App {
             capture_manager: None,
             theme: Theme::default(),
             active_filters: None,
+            source_filter: None,
+            tag_filter: None,
             history_manager: None,
+            pane: None,
             pass_through_command: None,
+            print_on_exit: None,
             palette_input: String::new(),
             palette_selected: 0,
             context_menu_selected: 0,
@@ -651,33 +681,40 @@ impl App {
     /// - `#tag` - Filter by tag
     /// - `source:npm` - Filter by source type
     /// - `@workspace` - Filter by workspace name
+    ///
+    /// The filter bar's source and tag filters apply as well. All filters
+    /// narrow the commands before the fuzzy search ranks them.
     pub fn update_filtered_commands(&mut self) {
         // Parse the input for filters
         let query = ParsedQuery::parse(&self.input);
 
         // Update active filters display
-        self.active_filters = query.filter_display();
+        let bar = self.filter_bar_display();
+        self.active_filters = match (bar, query.filter_display()) {
+            (Some(bar), Some(typed)) => Some(format!("{} {}", bar, typed)),
+            (bar, typed) => bar.or(typed),
+        };
 
-        // Get base filtered results using fuzzy search on the pattern
-        let mut candidates = if self.context_aware {
-            self.registry.search_filtered(&query.pattern, &self.context)
+        let keep = |cmd: &Command| self.matches_filter_bar(cmd) && query.matches(cmd);
+        self.filtered_commands = if self.context_aware {
+            self.registry.search_with_context_where(&query.pattern, &self.context, |cmd| {
+                self.context.matches_filter(cmd) && keep(cmd)
+            })
         } else {
-            self.registry.search(&query.pattern)
+            self.registry.search_where(&query.pattern, keep)
         };
 
-        // Apply additional filters if present
-        if query.has_filters() {
-            candidates.retain(|&idx| {
-                if let Some(cmd) = self.registry.get_by_index(idx) {
-                    query.matches(cmd)
-                } else {
-                    false
-                }
-            });
+        // An alias typed in full comes before everything it fuzzy-matches
+        let alias = self.filtered_commands.iter().position(|&i| {
+            self.registry
+                .get_by_index(i)
+                .is_some_and(|cmd| cmd.source == CommandSource::Alias && cmd.name == query.pattern)
+        });
+        if let Some(pos) = alias {
+            let index = self.filtered_commands.remove(pos);
+            self.filtered_commands.insert(0, index);
         }
 
-        self.filtered_commands = candidates;
-
         // Reset selection if it's now out of bounds
         if self.selected >= self.filtered_commands.len() {
             self.selected = self.filtered_commands.len().saturating_sub(1);
@@ -687,6 +724,68 @@ impl App {
         self.update_dir_listing();
     }
 
+    /// Whether a command passes the filter bar's source and tag filters.
+    fn matches_filter_bar(&self, cmd: &Command) -> bool {
+        self.source_filter.as_deref().is_none_or(|source| cmd.source.short_name() == source)
+            && self.tag_filter.as_deref().is_none_or(|tag| cmd.tags.iter().any(|t| t == tag))
+    }
+
+    /// Display string for the filter bar's filters, if any are active.
+    fn filter_bar_display(&self) -> Option<String> {
+        let parts: Vec<String> = self
+            .source_filter
+            .iter()
+            .map(|source| format!("source:{}", source))
+            .chain(self.tag_filter.iter().map(|tag| format!("#{}", tag)))
+            .collect();
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(" "))
+        }
+    }
+
+    /// Whether the filter bar has a source or tag filter set.
+    pub fn has_filter_bar(&self) -> bool {
+        self.source_filter.is_some() || self.tag_filter.is_some()
+    }
+
+    /// Cycle the source filter through the sources of the loaded commands.
+    ///
+    /// Goes from no filter to each source in turn, then back to no filter.
+    pub fn cycle_source_filter(&mut self) {
+        let sources = get_source_types(self.registry.get_all());
+        self.source_filter = next_filter(&sources, self.source_filter.as_deref());
+        self.selected = 0;
+        self.update_filtered_commands();
+    }
+
+    /// Cycle the tag filter through the tags of the commands in the current source.
+    ///
+    /// Goes from no filter to each tag in turn, then back to no filter.
+    pub fn cycle_tag_filter(&mut self) {
+        let commands: Vec<Command> = self
+            .registry
+            .get_all()
+            .iter()
+            .filter(|cmd| {
+                self.source_filter.as_deref().is_none_or(|source| cmd.source.short_name() == source)
+            })
+            .cloned()
+            .collect();
+        let tags = get_tags(&commands);
+        self.tag_filter = next_filter(&tags, self.tag_filter.as_deref());
+        self.selected = 0;
+        self.update_filtered_commands();
+    }
+
+    /// Clear the filter bar's source and tag filters.
+    pub fn clear_filter_bar(&mut self) {
+        self.source_filter = None;
+        self.tag_filter = None;
+        self.update_filtered_commands();
+    }
+
     /// Update directory listing and slash commands based on input.
     fn update_dir_listing(&mut self) {
         let input = self.input.trim();
@@ -1048,6 +1147,7 @@ impl App {
     pub fn tick(&mut self) {
         // Update spinner animation frame
         self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        self.poll_pane();
         // Future: Update file watchers, refresh commands, etc.
     }
 
@@ -1099,6 +1199,9 @@ impl App {
         // Load aliases from config
         self.load_aliases();
 
+        // Rank frequently and recently used commands first among equal matches
+        self.refresh_usage();
+
         // Update filtered list with all commands initially
         self.update_filtered_commands();
 
@@ -1142,151 +1245,425 @@ impl App {
         self.ai_status = None;
     }
 
+    /// Pass the history's frecency scores to the registry for ranking.
+    fn refresh_usage(&mut self) {
+        if let Some(ref manager) = self.history_manager {
+            self.registry.set_usage(manager.frecency_scores());
+        }
+    }
+
     /// Load aliases from config into the registry.
+    ///
+    /// Aliases are resolved against the scanned commands first, and any
+    /// that are part of a cycle are skipped with a warning.
     fn load_aliases(&mut self) {
-        for alias in &self.config.aliases {
-            let cmd = Command::from_alias(alias);
+        let resolved: Vec<_> = self
+            .config
+            .aliases
+            .iter()
+            .filter_map(|alias| {
+                resolve_alias(alias, &self.config.aliases, self.registry.get_all())
+                    .inspect_err(|e| tracing::warn!("Skipping alias '{}': {e}", alias.name))
+                    .ok()
+            })
+            .collect();
+
+        for cmd in resolved {
             self.registry.add(cmd);
         }
     }
 
+    /// Resolve `name` if it is a configured alias.
+    pub fn find_alias(&self, name: &str) -> Result<Option<Command>, AliasError> {
+        self.config
+            .aliases
+            .iter()
+            .find(|alias| alias.name == name)
+            .map(|alias| resolve_alias(alias, &self.config.aliases, self.registry.get_all()))
+            .transpose()
+    }
+
+    /// Check a command with the security validator before running it.
+    ///
+    /// Project commands only get a length check when the user config sets
+    /// `security.trust_project_commands`.
+    pub fn validate_command(&self, cmd: &Command) -> ValidationResult {
+        let trust = self.config.security.trust_project_commands;
+        SecurityManager::new(SecurityConfig::default().with_trusted_project_commands(trust))
+            .validate_with_source(&cmd.command, &cmd.source)
+    }
+
     /// Scan the current project for commands.
+    ///
+    /// Subdirectories are scanned when `scanner.recursive` is set, within
+    /// the same limits as `pal scan --recursive`.
     fn scan_project(&mut self) -> anyhow::Result<()> {
-        use crate::scanner::ProjectScanner;
+        use crate::scanner::{ProjectScanner, ScanLimits};
 
-        let scanner = ProjectScanner::new(&self.cwd);
-        let commands = scanner.scan()?;
+        let root = self.context.project_root.clone();
+        let config = &self.config.scanner;
+        let scanner = ProjectScanner::new(&root).with_limits(ScanLimits::from(config));
+        let commands = if config.recursive {
+            scanner.scan_recursive(config.max_depth)?
+        } else {
+            scanner.scan()?
+        };
 
-        for cmd in commands {
+        for mut cmd in commands {
+            // Project commands run from the root, not the subdirectory we started in
+            if root != self.cwd && cmd.working_dir.is_none() {
+                cmd.working_dir = Some(root.clone());
+            }
             self.registry.add(cmd);
         }
 
+        #[cfg(feature = "plugins")]
+        if self.config.plugins.scan {
+            match crate::plugin::PluginManager::default_dir().map(crate::plugin::PluginManager::new)
+            {
+                Some(Ok(mut manager)) => self.add_plugin_commands(&mut manager),
+                Some(Err(e)) => tracing::warn!(error = %e, "Could not load plugins"),
+                None => {}
+            }
+        }
+
         Ok(())
     }
 
+    /// Add commands found by enabled scanner plugins.
+    ///
+    /// A command the built-in scanners already found (same name and
+    /// command line) is skipped.
+    #[cfg(feature = "plugins")]
+    fn add_plugin_commands(&mut self, manager: &mut crate::plugin::PluginManager) {
+        for source in manager.sources() {
+            self.sources.register(source);
+        }
+
+        let root = self.context.project_root.clone();
+        let existing: HashSet<(String, String)> = self
+            .registry
+            .get_all()
+            .iter()
+            .map(|cmd| (cmd.name.clone(), cmd.command.clone()))
+            .collect();
+
+        for mut cmd in manager.scan(&root) {
+            if existing.contains(&(cmd.name.clone(), cmd.command.clone())) {
+                continue;
+            }
+            if root != self.cwd && cmd.working_dir.is_none() {
+                cmd.working_dir = Some(root.clone());
+            }
+            self.registry.add(cmd);
+        }
+    }
+
     /// Execute the currently selected command and capture output.
     ///
     /// Supports command chaining with `&&`, `||`, and `;` operators.
     pub fn execute_selected_command(&mut self) {
         if let Some(cmd) = self.get_selected_command().cloned() {
-            self.mode = AppMode::Executing(cmd.name.clone());
-
-            // Check if this is a chained command
-            let chain = CommandChain::parse(&cmd.command);
-
-            if chain.is_simple() {
-                // Simple command - execute directly
-                let output = std::process::Command::new("sh")
-                    .arg("-c")
-                    .arg(&cmd.command)
-                    .current_dir(cmd.working_dir.as_ref().unwrap_or(&self.cwd))
-                    .output();
-
-                match output {
-                    Ok(result) => {
-                        self.last_output = Some(CommandOutput {
-                            command_name: cmd.name.clone(),
-                            command_str: cmd.command.clone(),
-                            stdout: String::from_utf8_lossy(&result.stdout).to_string(),
-                            stderr: String::from_utf8_lossy(&result.stderr).to_string(),
-                            exit_code: result.status.code(),
-                            success: result.status.success(),
-                        });
-                    }
-                    Err(e) => {
-                        self.last_output = Some(CommandOutput {
-                            command_name: cmd.name.clone(),
-                            command_str: cmd.command.clone(),
-                            stdout: String::new(),
-                            stderr: format!("Failed to execute: {}", e),
-                            exit_code: None,
-                            success: false,
-                        });
-                    }
-                }
-            } else {
-                // Chained command - use ChainExecutor
-                let working_dir = cmd
-                    .working_dir
-                    .as_ref()
-                    .map(|p| p.display().to_string())
-                    .unwrap_or_else(|| self.cwd.display().to_string());
-
-                let executor = ChainExecutor::new().working_dir(working_dir);
-
-                match executor.execute(&chain) {
-                    Ok(result) => {
-                        // Build output showing chain progress
-                        let mut stdout = String::new();
-                        let mut stderr = String::new();
-
-                        for step in &result.steps {
-                            // Show step status
-                            let status_icon = match &step.status {
-                                ChainStepStatus::Success => "✓",
-                                ChainStepStatus::Failed(_) => "✗",
-                                ChainStepStatus::Skipped => "○",
-                                _ => "→",
-                            };
-
-                            stdout.push_str(&format!(
-                                "[{}] {} ({:.2?})\n",
-                                status_icon, step.command, step.duration
-                            ));
-
-                            if !step.stdout.is_empty() {
-                                stdout.push_str(&step.stdout);
-                                if !step.stdout.ends_with('\n') {
-                                    stdout.push('\n');
-                                }
-                            }
+            self.execute_command(&cmd);
+        }
+    }
 
-                            if !step.stderr.is_empty() {
-                                stderr.push_str(&format!("--- {} ---\n", step.command));
-                                stderr.push_str(&step.stderr);
-                                if !step.stderr.ends_with('\n') {
-                                    stderr.push('\n');
-                                }
-                            }
-                        }
+    /// Apply the configured Enter action to the selected command.
+    pub fn activate_selected_command(&mut self) {
+        let Some(cmd) = self.get_selected_command().cloned() else {
+            return;
+        };
 
-                        // Summary line
-                        stdout.push_str(&format!(
-                            "\n━━━ Chain complete: {}/{} succeeded ({:.2?}) ━━━\n",
-                            result.success_count(),
-                            result.steps.len(),
-                            result.total_duration
-                        ));
+        match self.config.ui.enter_action(&cmd) {
+            EnterAction::Run => self.execute_command(&cmd),
+            EnterAction::Confirm => self.mode = AppMode::Confirm,
+            EnterAction::Print => {
+                self.print_on_exit = Some(cmd.command);
+                self.quit();
+            }
+            EnterAction::Copy => match crate::tui::copy_to_clipboard(&cmd.command) {
+                Ok(()) => self.set_status(format!("Copied: {}", cmd.command)),
+                Err(e) => self.set_status(format!("Copy failed: {e}")),
+            },
+        }
+    }
+
+    /// Run the selected command after the user confirmed it.
+    pub fn confirm_selected_command(&mut self) {
+        self.mode = AppMode::Normal;
+        self.execute_selected_command();
+    }
 
-                        let exit_code = if result.success { Some(0) } else { Some(1) };
+    /// Dismiss the run confirmation without running.
+    pub fn cancel_confirm(&mut self) {
+        self.mode = AppMode::Normal;
+    }
 
-                        self.last_output = Some(CommandOutput {
-                            command_name: cmd.name.clone(),
-                            command_str: cmd.command.clone(),
+    /// Re-run the most recent command whose last run failed.
+    pub fn rerun_last_failed(&mut self) {
+        let Some(entry) = self.history_manager.as_ref().and_then(HistoryManager::last_failed)
+        else {
+            self.set_status("No failed command in history");
+            return;
+        };
+
+        if let Some(cmd) = self.registry.get_by_id(&entry.command_id).cloned() {
+            self.execute_command(&cmd);
+        } else {
+            let name = entry.command_name.clone();
+            self.set_status(format!("Last failed command '{name}' is no longer available"));
+        }
+    }
+
+    /// Execute a command, capture its output, and record it in history.
+    fn execute_command(&mut self, cmd: &Command) {
+        let validation = self.validate_command(cmd);
+        if !validation.is_safe() {
+            let reason = validation.errors.first().map_or_else(
+                || validation.severity.description().to_string(),
+                crate::security::ValidationError::description,
+            );
+            self.set_status(format!("Not running '{}': {reason}", cmd.name));
+            return;
+        }
+
+        if self.config.ui.run_in_pane {
+            self.run_in_pane(cmd);
+            return;
+        }
+
+        let started = std::time::Instant::now();
+        self.mode = AppMode::Executing(cmd.name.clone());
+
+        // Check if this is a chained command
+        let chain = CommandChain::parse(&cmd.command);
+
+        if chain.is_simple() {
+            // Simple command - execute directly
+            let executor =
+                crate::core::Executor::new().capture(true).wrapper(self.config.general.wrapper());
+
+            match executor.execute(&self.prepare_command(cmd)) {
+                Ok(result) => {
+                    self.last_output = Some(CommandOutput {
+                        command_name: cmd.name.clone(),
+                        command_str: cmd.command.clone(),
+                        exit_code: result.code(),
+                        success: result.success(),
+                        stdout: result.stdout.unwrap_or_default(),
+                        stderr: result.stderr.unwrap_or_default(),
+                    });
+                }
+                Err(e) => {
+                    self.last_output = Some(CommandOutput {
+                        command_name: cmd.name.clone(),
+                        command_str: cmd.command.clone(),
+                        stdout: String::new(),
+                        stderr: format!("Failed to execute: {}", e),
+                        exit_code: None,
+                        success: false,
+                    });
+                }
+            }
+        } else {
+            // Chained command - use ChainExecutor
+            let working_dir = cmd
+                .working_dir
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| self.cwd.display().to_string());
+
+            let executor = ChainExecutor::new().working_dir(working_dir);
+
+            match executor.execute(&chain) {
+                Ok(result) => {
+                    // Build output showing chain progress
+                    let mut stdout = String::new();
+                    let mut stderr = String::new();
+
+                    for step in &result.steps {
+                        // Show step status
+                        let status_icon = match &step.status {
+                            ChainStepStatus::Success => "✓",
+                            ChainStepStatus::Failed(_) => "✗",
+                            ChainStepStatus::Skipped => "○",
+                            _ => "→",
+                        };
+
+                        let _ = writeln!(
                             stdout,
-                            stderr,
-                            exit_code,
-                            success: result.success,
-                        });
-                    }
-                    Err(e) => {
-                        self.last_output = Some(CommandOutput {
-                            command_name: cmd.name.clone(),
-                            command_str: cmd.command.clone(),
-                            stdout: String::new(),
-                            stderr: format!("Failed to execute chain: {}", e),
-                            exit_code: None,
-                            success: false,
-                        });
+                            "[{}] {} ({:.2?})",
+                            status_icon, step.command, step.duration
+                        );
+
+                        if !step.stdout.is_empty() {
+                            stdout.push_str(&step.stdout);
+                            if !step.stdout.ends_with('\n') {
+                                stdout.push('\n');
+                            }
+                        }
+
+                        if !step.stderr.is_empty() {
+                            let _ = writeln!(stderr, "--- {} ---", step.command);
+                            stderr.push_str(&step.stderr);
+                            if !step.stderr.ends_with('\n') {
+                                stderr.push('\n');
+                            }
+                        }
                     }
+
+                    // Summary line
+                    let _ = writeln!(
+                        stdout,
+                        "\n━━━ Chain complete: {}/{} succeeded ({:.2?}) ━━━",
+                        result.success_count(),
+                        result.steps.len(),
+                        result.total_duration
+                    );
+
+                    let exit_code = if result.success { Some(0) } else { Some(1) };
+
+                    self.last_output = Some(CommandOutput {
+                        command_name: cmd.name.clone(),
+                        command_str: cmd.command.clone(),
+                        stdout,
+                        stderr,
+                        exit_code,
+                        success: result.success,
+                    });
+                }
+                Err(e) => {
+                    self.last_output = Some(CommandOutput {
+                        command_name: cmd.name.clone(),
+                        command_str: cmd.command.clone(),
+                        stdout: String::new(),
+                        stderr: format!("Failed to execute chain: {}", e),
+                        exit_code: None,
+                        success: false,
+                    });
                 }
             }
+        }
 
-            self.output_scroll = 0; // Reset scroll for new output
-            self.mode = AppMode::ExecutionResult;
+        // Mask known secret values before showing or capturing the output
+        if let Some(output) = self.last_output.as_mut() {
+            output.stdout = crate::security::redact(&output.stdout).into_owned();
+            output.stderr = crate::security::redact(&output.stderr).into_owned();
+        }
+
+        self.output_scroll = 0; // Reset scroll for new output
+        self.mode = AppMode::ExecutionResult;
+
+        // Capture output for replay
+        self.capture_last_output();
+
+        let exit_code = self.last_output.as_ref().and_then(|o| o.exit_code);
+        let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        if let Some(ref mut manager) = self.history_manager {
+            manager.record_exit(&cmd.id, &cmd.name, duration_ms, exit_code);
+            let _ = manager.save();
+        }
+        self.refresh_usage();
+    }
+
+    /// Start a command in the output pane, keeping the palette open.
+    ///
+    /// Only one command runs in the pane at a time.
+    fn run_in_pane(&mut self, cmd: &Command) {
+        if let Some(pane) = self.pane.as_ref().filter(|pane| pane.is_running()) {
+            let name = pane.command.name.clone();
+            self.set_status(format!("'{name}' is still running (Ctrl+C to stop it)"));
+            return;
+        }
+
+        let executor = crate::core::Executor::new().wrapper(self.config.general.wrapper());
+        self.pane = Some(CommandPane::spawn(self.prepare_command(cmd), executor));
+        self.mode = AppMode::Normal;
+    }
 
-            // Capture output for replay
-            self.capture_last_output();
+    /// Copy of `cmd` set up to run from the TUI.
+    ///
+    /// Runs in the current directory unless the command sets its own. The
+    /// result screen and pane render ANSI codes, but most tools disable color
+    /// when stdout is not a terminal, so they are asked to keep it unless
+    /// `NO_COLOR` is set.
+    fn prepare_command(&self, cmd: &Command) -> Command {
+        let mut command = cmd.clone();
+        if command.working_dir.is_none() {
+            command.working_dir = Some(self.cwd.clone());
+        }
+        if std::env::var_os("NO_COLOR").is_none() {
+            command = command
+                .with_env("CLICOLOR_FORCE", "1")
+                .with_env("FORCE_COLOR", "1")
+                .with_env("CARGO_TERM_COLOR", "always");
+        }
+        command
+    }
+
+    /// Pick up output from the pane's command and record it once finished.
+    pub fn poll_pane(&mut self) {
+        let Some(pane) = self.pane.as_mut() else {
+            return;
+        };
+        if !pane.poll() {
+            return;
+        }
+
+        let cmd = pane.command.clone();
+        let exit_code = pane.exit_code();
+        let status = match pane.exit() {
+            _ if pane.was_interrupted() => format!("Stopped '{}'", cmd.name),
+            Some(PaneExit::Failed(e)) => format!("Failed to run '{}': {e}", cmd.name),
+            _ if pane.success() => format!("✓ '{}' finished", cmd.name),
+            _ => match exit_code {
+                Some(code) => format!("✗ '{}' exited with code {code}", cmd.name),
+                None => format!("✗ '{}' was killed", cmd.name),
+            },
+        };
+        self.last_output = Some(CommandOutput {
+            command_name: cmd.name.clone(),
+            command_str: cmd.command.clone(),
+            stdout: pane.output.text(Stream::Stdout),
+            stderr: pane.output.text(Stream::Stderr),
+            exit_code,
+            success: pane.success(),
+        });
+        let duration_ms = u64::try_from(pane.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        self.capture_last_output();
+        if let Some(ref mut manager) = self.history_manager {
+            manager.record_exit(&cmd.id, &cmd.name, duration_ms, exit_code);
+            let _ = manager.save();
+        }
+        self.refresh_usage();
+        self.set_status(status);
+    }
+
+    /// Stop the command running in the pane.
+    ///
+    /// Returns false when nothing is running there.
+    pub fn interrupt_pane(&mut self) -> bool {
+        match self.pane.as_mut().filter(|pane| pane.is_running()) {
+            Some(pane) => {
+                pane.interrupt();
+                let name = pane.command.name.clone();
+                self.set_status(format!("Stopping '{name}'..."));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Close the output pane once its command has finished.
+    ///
+    /// Returns false when there is no pane or its command is still running.
+    pub fn close_pane(&mut self) -> bool {
+        if self.pane.as_ref().is_some_and(|pane| !pane.is_running()) {
+            self.pane = None;
+            true
+        } else {
+            false
         }
     }
 
@@ -2001,7 +2378,7 @@ impl App {
 
     /// Rescan commands in the current directory.
     pub fn rescan_commands(&mut self) {
-        self.registry = CommandRegistry::new();
+        self.registry = CommandRegistry::with_search(self.config.ui.search);
         self.filtered_commands.clear();
         self.selected = 0;
         // Note: actual scanning happens in main.rs scan_and_run
@@ -2417,6 +2794,15 @@ async fn delete_ollama_model(model: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// The value after `current` in `values`, wrapping around to no value.
+fn next_filter(values: &[String], current: Option<&str>) -> Option<String> {
+    let next = match current {
+        None => 0,
+        Some(current) => values.iter().position(|v| v == current).map_or(0, |i| i + 1),
+    };
+    values.get(next).cloned()
+}
+
 impl Default for App {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| {
@@ -2425,6 +2811,7 @@ impl Default for App {
                 input: String::new(),
                 cursor_position: 0,
                 registry: CommandRegistry::new(),
+                sources: SourceRegistry::new(),
                 filtered_commands: Vec::new(),
                 selected: 0,
                 should_quit: false,
@@ -2445,8 +2832,12 @@ impl Default for App {
                 capture_manager: None,
                 theme: Theme::default(),
                 active_filters: None,
+                source_filter: None,
+                tag_filter: None,
                 history_manager: None,
+                pane: None,
                 pass_through_command: None,
+                print_on_exit: None,
                 palette_input: String::new(),
                 palette_selected: 0,
                 context_menu_selected: 0,
@@ -2501,6 +2892,34 @@ mod tests {
         assert!(!app.should_quit);
     }
 
+    #[cfg(feature = "plugins")]
+    #[test]
+    fn test_plugin_commands_join_registry() {
+        use crate::core::CommandSource;
+
+        let plugins_dir = tempfile::tempdir().unwrap();
+        let fixture = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/plugins/hello-scanner/hello_scanner.wasm");
+        let mut manager = crate::plugin::PluginManager::new(plugins_dir.path().into()).unwrap();
+        manager.install_from_file(&fixture).unwrap();
+
+        let mut app = App::new_test();
+        app.registry.add(Command::new("build", "make build"));
+        app.add_plugin_commands(&mut manager);
+
+        let hello = app.registry.get_all().iter().find(|c| c.name == "hello").unwrap();
+        assert_eq!(hello.source, CommandSource::Plugin { name: "hello-scanner".to_string() });
+        assert_eq!(app.registry.len(), 2);
+
+        // `pal list` and the palette show the icon declared in the manifest
+        assert_eq!(app.config.ui.icons.icon(&hello.source, &app.sources), "👋");
+        assert_eq!(app.sources.name(&hello.source), "hello");
+
+        // Running again doesn't duplicate what is already registered
+        app.add_plugin_commands(&mut manager);
+        assert_eq!(app.registry.len(), 2);
+    }
+
     #[test]
     fn test_char_input() {
         let mut app = App::new_test();
@@ -2655,4 +3074,204 @@ mod tests {
         app.update_filtered_commands();
         assert!(app.active_filters.is_none());
     }
+
+    fn filter_bar_app() -> App {
+        use crate::core::CommandSource;
+
+        let mut app = App::new_test();
+        app.registry.add(
+            Command::new("npm test", "npm test")
+                .with_source(CommandSource::PackageJson(PathBuf::from(".")))
+                .with_tag("test"),
+        );
+        app.registry.add(
+            Command::new("npm lint", "npm run lint")
+                .with_source(CommandSource::PackageJson(PathBuf::from(".")))
+                .with_tag("lint"),
+        );
+        app.registry.add(
+            Command::new("cargo test", "cargo test")
+                .with_source(CommandSource::Cargo(PathBuf::from(".")))
+                .with_tag("test"),
+        );
+        app.update_filtered_commands();
+        app
+    }
+
+    fn filtered_names(app: &App) -> Vec<&str> {
+        app.filtered_commands
+            .iter()
+            .map(|&idx| app.registry.get_by_index(idx).unwrap().name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_cycle_source_filter() {
+        let mut app = filter_bar_app();
+
+        app.cycle_source_filter();
+        assert_eq!(app.source_filter.as_deref(), Some("cargo"));
+        assert_eq!(filtered_names(&app), vec!["cargo test"]);
+        assert_eq!(app.active_filters.as_deref(), Some("source:cargo"));
+
+        app.cycle_source_filter();
+        assert_eq!(app.source_filter.as_deref(), Some("npm"));
+        assert_eq!(app.filtered_commands.len(), 2);
+
+        // Wraps back around to no filter
+        app.cycle_source_filter();
+        assert!(app.source_filter.is_none());
+        assert_eq!(app.filtered_commands.len(), 3);
+        assert!(app.active_filters.is_none());
+    }
+
+    #[test]
+    fn test_cycle_tag_filter_within_source() {
+        let mut app = filter_bar_app();
+
+        app.cycle_source_filter();
+        app.cycle_source_filter();
+        assert_eq!(app.source_filter.as_deref(), Some("npm"));
+
+        // Only tags of npm commands are offered
+        app.cycle_tag_filter();
+        assert_eq!(app.tag_filter.as_deref(), Some("lint"));
+        assert_eq!(filtered_names(&app), vec!["npm lint"]);
+
+        app.cycle_tag_filter();
+        assert_eq!(app.tag_filter.as_deref(), Some("test"));
+        assert_eq!(filtered_names(&app), vec!["npm test"]);
+
+        app.clear_filter_bar();
+        assert!(!app.has_filter_bar());
+        assert_eq!(app.filtered_commands.len(), 3);
+    }
+
+    #[test]
+    fn test_filter_bar_composes_with_search() {
+        let mut app = filter_bar_app();
+        app.tag_filter = Some("test".to_string());
+
+        app.input = "cargo".to_string();
+        app.update_filtered_commands();
+        assert_eq!(filtered_names(&app), vec!["cargo test"]);
+
+        // Typed filters add to the filter bar's
+        app.input = "source:npm".to_string();
+        app.update_filtered_commands();
+        assert_eq!(filtered_names(&app), vec!["npm test"]);
+        assert_eq!(app.active_filters.as_deref(), Some("#test source:npm"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_in_pane_keeps_palette_open() {
+        let mut app = App::new_test();
+        app.config.ui.run_in_pane = true;
+        app.registry.add(Command::new("greet", "echo hello; echo oops >&2; exit 2"));
+        app.update_filtered_commands();
+
+        app.execute_selected_command();
+        assert_eq!(app.mode, AppMode::Normal);
+        while app.pane.as_ref().unwrap().is_running() {
+            app.poll_pane();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let output = app.last_output.as_ref().unwrap();
+        assert_eq!(output.stdout, "hello");
+        assert_eq!(output.stderr, "oops");
+        assert_eq!(output.exit_code, Some(2));
+        assert_eq!(app.status_message.as_deref(), Some("✗ 'greet' exited with code 2"));
+
+        // Nothing left to stop, and Esc-style close drops the pane
+        assert!(!app.interrupt_pane());
+        assert!(app.close_pane());
+        assert!(app.pane.is_none());
+    }
+
+    #[test]
+    fn test_aliases_resolve_and_rank_first() {
+        use crate::core::AliasConfig;
+
+        let mut app = App::new_test();
+        app.registry.add(Command::new("test", "cargo test --workspace"));
+        app.registry.add(Command::new("tidy", "cargo fmt"));
+        app.config.aliases = vec![
+            AliasConfig::new("t", "test"),
+            AliasConfig::new("loop", "again"),
+            AliasConfig::new("again", "loop"),
+        ];
+        app.load_aliases();
+
+        // The cycle is skipped rather than listed
+        let aliases: Vec<_> = app
+            .registry
+            .get_all()
+            .iter()
+            .filter(|cmd| cmd.source == CommandSource::Alias)
+            .map(|cmd| (cmd.name.as_str(), cmd.command.as_str()))
+            .collect();
+        assert_eq!(aliases, [("t", "cargo test --workspace")]);
+
+        app.input = "t".to_string();
+        app.update_filtered_commands();
+        assert_eq!(app.get_selected_command().unwrap().name, "t");
+
+        assert_eq!(app.find_alias("t").unwrap().unwrap().command, "cargo test --workspace");
+        assert!(app.find_alias("test").unwrap().is_none());
+        assert!(app.find_alias("loop").is_err());
+    }
+
+    #[test]
+    fn test_validate_command_trusts_project_commands_when_configured() {
+        let mut app = App::new_test();
+        let wipe = Command::new("wipe", "dd if=/dev/zero of=disk.img")
+            .with_source(CommandSource::Makefile("Makefile".into()));
+        let generated = wipe.clone().with_source(CommandSource::AiGenerated);
+
+        assert!(!app.validate_command(&wipe).is_safe());
+        app.execute_command(&wipe);
+        assert!(app.status_message.as_deref().unwrap().starts_with("Not running 'wipe'"));
+        assert!(app.last_output.is_none());
+
+        app.config.security.trust_project_commands = true;
+        assert!(app.validate_command(&wipe).is_safe());
+        assert!(!app.validate_command(&generated).is_safe());
+    }
+
+    #[test]
+    fn test_scan_project_applies_scan_limits() {
+        let temp = tempfile::TempDir::new().unwrap();
+        for name in ["a", "b", "c"] {
+            let dir = temp.path().join(name);
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(dir.join("Makefile"), format!("{name}:\n\techo\n")).unwrap();
+        }
+
+        let mut app = App::new_test();
+        app.context = CommandContext::new(temp.path(), temp.path());
+        app.cwd = temp.path().to_path_buf();
+        app.config.plugins.scan = false;
+        app.config.scanner.recursive = true;
+        app.config.scanner.max_dirs = 2;
+        app.scan_project().unwrap();
+
+        let mut targets: Vec<_> =
+            app.registry.get_all().iter().filter_map(|c| c.name.strip_prefix("make ")).collect();
+        targets.sort_unstable();
+        assert_eq!(targets, ["a", "b"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_command_uses_command_shell() {
+        let mut app = App::new_test();
+        let cmd = Command::new("bash-only", "echo $0").with_shell("bash");
+
+        app.execute_command(&cmd);
+        let output = app.last_output.as_ref().unwrap();
+        assert!(output.success);
+        assert_eq!(output.stdout.trim(), "bash");
+    }
 }
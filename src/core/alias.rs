@@ -0,0 +1,153 @@
+//! Resolving config aliases to the commands they run.
+//!
+//! An alias's `command` is either the name of another command, scanned or
+//! aliased, or a raw command line. Names are tried first, so `t = "test"`
+//! runs the project's `test` command and `ci = "t"` runs whatever `t` runs.
+
+use super::config::AliasConfig;
+use super::{Command, CommandSource};
+
+/// Errors from resolving an alias.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AliasError {
+    /// Following the alias leads back to an alias already followed
+    #[error("alias cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Resolve an alias to the command it runs.
+///
+/// Follows aliases that name other aliases, then looks the final target up
+/// among `commands` by name, taking the first match. A target that names
+/// no command is run as a command line. The result keeps the alias's name,
+/// description, and tags, and asks for confirmation if either the alias or
+/// its target does.
+///
+/// Commands from [`CommandSource::Alias`] in `commands` are ignored, since
+/// aliases are looked up in `aliases`.
+pub fn resolve_alias(
+    alias: &AliasConfig,
+    aliases: &[AliasConfig],
+    commands: &[Command],
+) -> Result<Command, AliasError> {
+    let mut chain = vec![alias.name.clone()];
+    let mut last = alias;
+    while let Some(next) = aliases.iter().find(|a| a.name == last.command) {
+        let seen = chain.contains(&next.name);
+        chain.push(next.name.clone());
+        if seen {
+            return Err(AliasError::Cycle(chain));
+        }
+        last = next;
+    }
+
+    let mut command = Command::from_alias(alias);
+    let target =
+        commands.iter().find(|cmd| cmd.source != CommandSource::Alias && cmd.name == last.command);
+    if let Some(target) = target {
+        command.command.clone_from(&target.command);
+        command.working_dir.clone_from(&target.working_dir);
+        command.env.clone_from(&target.env);
+        command.shell.clone_from(&target.shell);
+        command.confirm |= target.confirm;
+        if command.description.is_none() {
+            command.description.clone_from(&target.description);
+        }
+    } else {
+        command.command.clone_from(&last.command);
+        command.working_dir.clone_from(&last.working_dir);
+        command.env.clone_from(&last.env);
+        command.shell.clone_from(&last.shell);
+        command.confirm |= last.confirm;
+    }
+
+    Ok(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scanned() -> Vec<Command> {
+        vec![
+            Command::new("test", "cargo test --workspace")
+                .with_description("Run all tests")
+                .with_source(CommandSource::Cargo("Cargo.toml".into())),
+            Command::new("deploy", "./deploy.sh").with_confirm(true),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_raw_command() {
+        let alias = AliasConfig::new("t", "cargo test --workspace");
+        let command = resolve_alias(&alias, std::slice::from_ref(&alias), &[]).unwrap();
+
+        assert_eq!(command.name, "t");
+        assert_eq!(command.command, "cargo test --workspace");
+        assert_eq!(command.source, CommandSource::Alias);
+    }
+
+    #[test]
+    fn test_resolve_command_name() {
+        let aliases = [AliasConfig::new("t", "test"), AliasConfig::new("ship", "deploy")];
+
+        let command = resolve_alias(&aliases[0], &aliases, &scanned()).unwrap();
+        assert_eq!(command.name, "t");
+        assert_eq!(command.command, "cargo test --workspace");
+        assert_eq!(command.description.as_deref(), Some("Run all tests"));
+        assert_eq!(command.source, CommandSource::Alias);
+
+        // The target's confirmation carries over
+        assert!(resolve_alias(&aliases[1], &aliases, &scanned()).unwrap().confirm);
+    }
+
+    #[test]
+    fn test_resolve_alias_chain() {
+        let mut inner = AliasConfig::new("fmt", "cargo fmt --all");
+        inner.shell = Some("bash".to_string());
+        let aliases = [
+            AliasConfig::new("f", "fmt"),
+            inner,
+            AliasConfig::new("ci", "t"),
+            AliasConfig::new("t", "test"),
+        ];
+
+        let command = resolve_alias(&aliases[0], &aliases, &scanned()).unwrap();
+        assert_eq!(command.name, "f");
+        assert_eq!(command.command, "cargo fmt --all");
+        assert_eq!(command.shell.as_deref(), Some("bash"));
+
+        let command = resolve_alias(&aliases[2], &aliases, &scanned()).unwrap();
+        assert_eq!(command.command, "cargo test --workspace");
+    }
+
+    #[test]
+    fn test_resolve_ignores_alias_commands() {
+        let alias = AliasConfig::new("t", "test");
+        let listed = [Command::from_alias(&AliasConfig::new("test", "echo loop"))];
+
+        // An alias already in the registry is not a target; only `aliases` are
+        let command = resolve_alias(&alias, std::slice::from_ref(&alias), &listed).unwrap();
+        assert_eq!(command.command, "test");
+    }
+
+    #[test]
+    fn test_detect_cycle() {
+        let aliases = [
+            AliasConfig::new("a", "b"),
+            AliasConfig::new("b", "c"),
+            AliasConfig::new("c", "a"),
+            AliasConfig::new("d", "a"),
+        ];
+
+        let err = resolve_alias(&aliases[0], &aliases, &scanned()).unwrap_err();
+        assert_eq!(err, AliasError::Cycle(vec!["a".into(), "b".into(), "c".into(), "a".into()]));
+        assert_eq!(err.to_string(), "alias cycle: a -> b -> c -> a");
+
+        // Leading into a cycle fails too
+        assert!(resolve_alias(&aliases[3], &aliases, &scanned()).is_err());
+
+        let own = AliasConfig::new("test", "test");
+        assert!(resolve_alias(&own, std::slice::from_ref(&own), &scanned()).is_err());
+    }
+}
@@ -338,6 +338,8 @@ mod tests {
             total_duration_ms: duration_ms,
             success_count: successes,
             failure_count: count - successes,
+            last_success: None,
+            last_exit_code: None,
         }
     }
 
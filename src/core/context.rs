@@ -191,10 +191,14 @@ impl CommandContext {
             CommandSource::PackageJson(p)
             | CommandSource::Makefile(p)
             | CommandSource::Taskfile(p)
+            | CommandSource::Justfile(p)
             | CommandSource::DockerCompose(p)
             | CommandSource::Cargo(p)
             | CommandSource::GoMod(p)
-            | CommandSource::Python(p) => Some(p.clone()),
+            | CommandSource::CMake(p)
+            | CommandSource::Meson(p)
+            | CommandSource::Python(p)
+            | CommandSource::VsCode(p) => Some(p.clone()),
             CommandSource::NxProject(_) | CommandSource::Turbo => Some(self.project_root.clone()),
             CommandSource::Git
             | CommandSource::Manual
@@ -202,7 +206,9 @@ impl CommandContext {
             | CommandSource::Favorite
             | CommandSource::Alias
             | CommandSource::Builtin
-            | CommandSource::Mcp { .. } => None,
+            | CommandSource::Mcp { .. }
+            | CommandSource::AiGenerated
+            | CommandSource::Plugin { .. } => None,
         }
     }
 
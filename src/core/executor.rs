@@ -2,12 +2,16 @@
 //!
 //! Handles spawning shell processes and capturing output.
 
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
-use std::process::{Command as ProcessCommand, ExitStatus, Stdio};
+use std::process::{Child, Command as ProcessCommand, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
-use super::Command;
+use super::{process_group, Command};
+use crate::security::{redact, SecurityManager};
 
 /// Result of executing a command.
 #[derive(Debug)]
@@ -35,6 +39,118 @@ impl ExecutionResult {
     pub fn code(&self) -> Option<i32> {
         self.status.code()
     }
+
+    /// Exit code to report for the command, as a shell would.
+    ///
+    /// A command killed by a signal reports `128 + signal`.
+    pub fn exit_code(&self) -> i32 {
+        if let Some(code) = self.status.code() {
+            return code;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = self.status.signal() {
+                return 128 + signal;
+            }
+        }
+        1
+    }
+}
+
+/// Errors from running a command.
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    /// The command ran past its timeout and was killed
+    #[error("'{command}' timed out after {}s", timeout.as_secs())]
+    TimedOut {
+        /// Name of the command
+        command: String,
+
+        /// Timeout that was exceeded
+        timeout: Duration,
+    },
+}
+
+/// How long a timed-out command gets to exit after SIGTERM before SIGKILL.
+const TERMINATE_GRACE: Duration = Duration::from_secs(2);
+
+/// How often a command with a timeout is checked for having exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Output stream a line was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    /// Standard output
+    Stdout,
+
+    /// Standard error
+    Stderr,
+}
+
+/// A line of output from [`Executor::execute_streaming`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamLine {
+    /// Stream the line was read from
+    pub stream: Stream,
+
+    /// Line text, without the trailing newline and with secrets redacted
+    pub text: String,
+}
+
+/// A prefix that every matching command is run through.
+///
+/// Useful for running commands inside a container, e.g.
+/// `docker compose exec app`. The command is passed to `sh -c` inside
+/// the wrapper so pipes and `&&` chains run entirely inside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecWrapper {
+    /// Wrapper command line
+    pub prefix: String,
+
+    /// Only wrap commands from these sources (empty = all)
+    pub sources: Vec<String>,
+
+    /// Only wrap commands with one of these tags (empty = all)
+    pub tags: Vec<String>,
+}
+
+impl ExecWrapper {
+    /// Create a wrapper that applies to every command.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self { prefix: prefix.into(), sources: Vec::new(), tags: Vec::new() }
+    }
+
+    /// Check whether a command should be wrapped.
+    ///
+    /// With no sources or tags configured every command matches; otherwise
+    /// a command matches if its source or any of its tags is listed.
+    pub fn applies_to(&self, command: &Command) -> bool {
+        if self.sources.is_empty() && self.tags.is_empty() {
+            return true;
+        }
+        self.sources.iter().any(|s| s == command.source.type_name())
+            || command.tags.iter().any(|t| self.tags.contains(t))
+    }
+
+    /// Wrap a shell command line.
+    pub fn wrap(&self, command_line: &str) -> String {
+        format!("{} sh -c {}", self.prefix.trim(), shell_quote(command_line))
+    }
+}
+
+/// Quote a string as a single POSIX shell word.
+///
+/// Words made only of characters the shell treats literally are returned
+/// as they are; anything else is single-quoted.
+pub fn shell_quote(s: &str) -> String {
+    let plain =
+        !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,@%+".contains(c));
+    if plain {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', r"'\''"))
+    }
 }
 
 /// Command executor.
@@ -45,6 +161,9 @@ pub struct Executor {
 
     /// Timeout for command execution
     pub timeout: Option<Duration>,
+
+    /// Wrapper applied to matching commands
+    pub wrapper: Option<ExecWrapper>,
 }
 
 impl Executor {
@@ -60,25 +179,47 @@ impl Executor {
         self
     }
 
-    /// Set execution timeout.
+    /// Set the timeout for commands that do not set their own.
     #[must_use]
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
         self
     }
 
+    /// Set the wrapper applied to matching commands.
+    #[must_use]
+    pub fn wrapper(mut self, wrapper: Option<ExecWrapper>) -> Self {
+        self.wrapper = wrapper;
+        self
+    }
+
+    /// Get the shell command line that will run for a command.
+    pub fn command_line(&self, command: &Command) -> String {
+        match self.wrapper {
+            Some(ref wrapper) if wrapper.applies_to(command) => wrapper.wrap(&command.command),
+            _ => command.command.clone(),
+        }
+    }
+
     /// Execute a command.
     ///
     /// By default, this passes stdin/stdout/stderr through to the terminal
     /// for interactive commands. Use `capture(true)` to capture output instead.
+    ///
+    /// The command runs in its own process group, and SIGINT/SIGTERM sent to
+    /// Palrun are forwarded to it; this returns once the command has exited.
+    ///
+    /// A command that outlives its timeout (its own `timeout_secs`, else
+    /// the executor's) is terminated and [`CommandError::TimedOut`] is
+    /// returned.
     pub fn execute(&self, command: &Command) -> anyhow::Result<ExecutionResult> {
         let start = Instant::now();
 
-        let (shell, shell_arg) = get_shell();
+        let (shell, shell_arg) = resolve_shell(command);
 
         let mut cmd = ProcessCommand::new(shell);
         cmd.arg(shell_arg);
-        cmd.arg(&command.command);
+        cmd.arg(self.command_line(command));
 
         // Set working directory if specified
         if let Some(ref dir) = command.working_dir {
@@ -86,12 +227,11 @@ impl Executor {
         }
 
         // Set environment variables
-        for (key, value) in &command.env {
-            cmd.env(key, value);
-        }
+        cmd.envs(command_env(command));
 
         // Configure stdio based on capture mode
         if self.capture_output {
+            cmd.stdin(Stdio::null());
             cmd.stdout(Stdio::piped());
             cmd.stderr(Stdio::piped());
         } else {
@@ -100,92 +240,150 @@ impl Executor {
             cmd.stdin(Stdio::inherit());
         }
 
-        let output = cmd.output()?;
+        let (mut child, _group) = process_group::spawn(&mut cmd, !self.capture_output)?;
+
+        // Pipes are drained on threads so the child never blocks on a full one
+        let stdout = child.stdout.take().map(read_all);
+        let stderr = child.stderr.take().map(read_all);
+
+        let timeout = command.timeout_secs.map(Duration::from_secs).or(self.timeout);
+        let status = match timeout {
+            Some(timeout) => {
+                let Some(status) = wait_timeout(&mut child, timeout)? else {
+                    process_group::terminate(&mut child, TERMINATE_GRACE)?;
+                    return Err(
+                        CommandError::TimedOut { command: command.name.clone(), timeout }.into()
+                    );
+                };
+                status
+            }
+            None => child.wait()?,
+        };
 
         let duration = start.elapsed();
 
-        let (stdout, stderr) = if self.capture_output {
-            (
-                Some(String::from_utf8_lossy(&output.stdout).to_string()),
-                Some(String::from_utf8_lossy(&output.stderr).to_string()),
-            )
-        } else {
-            (None, None)
+        let collect = |reader: Option<JoinHandle<Vec<u8>>>| {
+            reader.map(|r| {
+                let bytes = r.join().unwrap_or_default();
+                redact(&String::from_utf8_lossy(&bytes)).into_owned()
+            })
         };
 
-        Ok(ExecutionResult { status: output.status, stdout, stderr, duration })
+        Ok(ExecutionResult { status, stdout: collect(stdout), stderr: collect(stderr), duration })
     }
 
-    /// Execute a command with streaming output.
+    /// Execute a command, streaming its output line by line.
     ///
-    /// Calls the provided callback for each line of output.
+    /// stdout and stderr are read on separate threads and `on_line` is
+    /// called for each line as soon as it arrives, so a child that fills
+    /// one pipe never blocks on the other. The command runs in its own
+    /// process group, so Ctrl+C still reaches it. With [`Self::capture`]
+    /// set, the collected output is also returned; otherwise lines are only
+    /// passed to `on_line`, so a long-running command doesn't grow memory.
+    ///
+    /// Timeouts apply as in [`Self::execute`].
     pub fn execute_streaming<F>(
         &self,
         command: &Command,
+        on_line: F,
+    ) -> anyhow::Result<ExecutionResult>
+    where
+        F: FnMut(StreamLine),
+    {
+        self.execute_streaming_until(command, &AtomicBool::new(false), on_line)
+    }
+
+    /// Execute a command, streaming its output until it exits or is interrupted.
+    ///
+    /// Like [`Self::execute_streaming`], but once `interrupt` is set the
+    /// command is terminated as a timed-out one would be. Output read
+    /// before it exits is still passed to `on_line`.
+    pub fn execute_streaming_until<F>(
+        &self,
+        command: &Command,
+        interrupt: &AtomicBool,
         mut on_line: F,
     ) -> anyhow::Result<ExecutionResult>
     where
-        F: FnMut(&str, bool), // (line, is_stderr)
+        F: FnMut(StreamLine),
     {
         let start = Instant::now();
 
-        let (shell, shell_arg) = get_shell();
+        let (shell, shell_arg) = resolve_shell(command);
 
         let mut cmd = ProcessCommand::new(shell);
         cmd.arg(shell_arg);
-        cmd.arg(&command.command);
+        cmd.arg(self.command_line(command));
 
         if let Some(ref dir) = command.working_dir {
             cmd.current_dir(dir);
         }
 
-        for (key, value) in &command.env {
-            cmd.env(key, value);
-        }
+        cmd.envs(command_env(command));
 
+        cmd.stdin(Stdio::null());
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
 
-        let mut child = cmd.spawn()?;
+        let (mut child, _group) = process_group::spawn(&mut cmd, false)?;
 
-        let stdout = child.stdout.take();
-        let stderr = child.stderr.take();
+        let (tx, rx) = mpsc::channel();
+        let readers = [
+            child.stdout.take().map(|out| read_lines(out, Stream::Stdout, tx.clone())),
+            child.stderr.take().map(|err| read_lines(err, Stream::Stderr, tx.clone())),
+        ];
+        drop(tx);
 
-        // Read stdout in a thread
-        let stdout_handle = std::thread::spawn(move || {
-            let mut lines = Vec::new();
-            if let Some(stdout) = stdout {
-                let reader = BufReader::new(stdout);
-                for line in reader.lines().map_while(Result::ok) {
-                    lines.push(line);
+        // Ends once both pipes are closed
+        let timeout = command.timeout_secs.map(Duration::from_secs).or(self.timeout);
+        let mut stdout_lines = Vec::new();
+        let mut stderr_lines = Vec::new();
+        let mut stopped = None;
+        let mut timed_out = None;
+        loop {
+            // Checked before every read, so a command that never stops
+            // printing is still stopped
+            if stopped.is_none() {
+                timed_out = timeout.filter(|timeout| start.elapsed() >= *timeout);
+                if timed_out.is_some() || interrupt.load(Ordering::Relaxed) {
+                    let status = process_group::terminate(&mut child, TERMINATE_GRACE);
+                    if status.is_err() {
+                        let _ = child.kill();
+                    }
+                    stopped = Some(status);
                 }
             }
-            lines
-        });
-
-        // Read stderr in main thread
-        let mut stderr_lines = Vec::new();
-        if let Some(stderr) = stderr {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines().map_while(Result::ok) {
-                on_line(&line, true);
-                stderr_lines.push(line);
+            let line = match rx.recv_timeout(POLL_INTERVAL) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            if self.capture_output {
+                match line.stream {
+                    Stream::Stdout => stdout_lines.push(line.text.clone()),
+                    Stream::Stderr => stderr_lines.push(line.text.clone()),
+                }
             }
+            on_line(line);
         }
-
-        // Get stdout results
-        let stdout_lines = stdout_handle.join().unwrap_or_default();
-        for line in &stdout_lines {
-            on_line(line, false);
+        for reader in readers.into_iter().flatten() {
+            let _ = reader.join();
         }
 
-        let status = child.wait()?;
+        let status = match stopped {
+            Some(status) => status?,
+            None => child.wait()?,
+        };
+        if let Some(timeout) = timed_out {
+            return Err(CommandError::TimedOut { command: command.name.clone(), timeout }.into());
+        }
         let duration = start.elapsed();
 
+        let collected = |lines: Vec<String>| self.capture_output.then(|| lines.join("\n"));
         Ok(ExecutionResult {
             status,
-            stdout: Some(stdout_lines.join("\n")),
-            stderr: Some(stderr_lines.join("\n")),
+            stdout: collected(stdout_lines),
+            stderr: collected(stderr_lines),
             duration,
         })
     }
@@ -203,6 +401,55 @@ impl Executor {
     }
 }
 
+/// Read a pipe to the end from a new thread.
+fn read_all(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = pipe.read_to_end(&mut bytes);
+        bytes
+    })
+}
+
+/// Wait for a child to exit, giving up after `timeout`.
+fn wait_timeout(child: &mut Child, timeout: Duration) -> std::io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        std::thread::sleep(POLL_INTERVAL.min(deadline - now));
+    }
+}
+
+/// Send each line of a pipe to `tx` from a new thread.
+fn read_lines(
+    pipe: impl Read + Send + 'static,
+    stream: Stream,
+    tx: mpsc::Sender<StreamLine>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            let text = redact(&line).into_owned();
+            if tx.send(StreamLine { stream, text }).is_err() {
+                break;
+            }
+        }
+    })
+}
+
+/// Environment variables to set for a command, after sanitization.
+fn command_env(command: &Command) -> Vec<(String, String)> {
+    let result = SecurityManager::with_defaults().sanitize_env(&command.env);
+    for warning in &result.warnings {
+        tracing::warn!(command = %command.name, "{warning}");
+    }
+    result.value
+}
+
 /// Get the shell and argument for the current platform.
 fn get_shell() -> (&'static str, &'static str) {
     if cfg!(target_os = "windows") {
@@ -212,6 +459,48 @@ fn get_shell() -> (&'static str, &'static str) {
     }
 }
 
+/// Get the shell and argument to run a command with.
+///
+/// Uses the command's own `shell` when it is installed, otherwise warns
+/// and falls back to the platform default.
+fn resolve_shell(command: &Command) -> (String, &'static str) {
+    if let Some(ref shell) = command.shell {
+        if program_exists(shell) {
+            return (shell.clone(), shell_arg(shell));
+        }
+        tracing::warn!("Shell '{shell}' for '{}' not found; using the default shell", command.name);
+    }
+
+    let (shell, arg) = get_shell();
+    (shell.to_string(), arg)
+}
+
+/// Argument that makes a shell run a command string.
+fn shell_arg(shell: &str) -> &'static str {
+    let name = Path::new(shell).file_stem().and_then(|s| s.to_str()).unwrap_or(shell);
+    match name.to_lowercase().as_str() {
+        "cmd" => "/C",
+        "pwsh" | "powershell" => "-Command",
+        _ => "-c",
+    }
+}
+
+/// Check whether a program is a path to a file or an executable on `PATH`.
+pub fn program_exists(program: &str) -> bool {
+    let path = Path::new(program);
+    if path.components().count() > 1 {
+        return path.is_file();
+    }
+
+    let Some(paths) = std::env::var_os("PATH") else {
+        return false;
+    };
+    std::env::split_paths(&paths).any(|dir| {
+        dir.join(program).is_file()
+            || (cfg!(windows) && dir.join(format!("{program}.exe")).is_file())
+    })
+}
+
 /// Check if a command string looks dangerous.
 #[allow(dead_code)]
 pub fn is_dangerous_command(cmd: &str) -> bool {
@@ -251,6 +540,7 @@ pub fn is_dangerous_command(cmd: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_executor_creation() {
@@ -259,6 +549,187 @@ mod tests {
         assert!(executor.timeout.is_none());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_command_shell_runs_bash() {
+        let executor = Executor::new().capture(true);
+        let command = Command::new("bash-only", "echo $0").with_shell("bash");
+
+        let result = executor.execute(&command).unwrap();
+        assert!(result.success());
+        assert_eq!(result.stdout.unwrap().trim(), "bash");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_missing_shell_falls_back_to_default() {
+        let executor = Executor::new().capture(true);
+        let command = Command::new("fallback", "echo ok").with_shell("no-such-shell-palrun");
+
+        let result = executor.execute(&command).unwrap();
+        assert!(result.success());
+        assert_eq!(result.stdout.unwrap().trim(), "ok");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("--port=3000"), "--port=3000");
+        assert_eq!(shell_quote("/usr/local/bin/node"), "/usr/local/bin/node");
+        assert_eq!(shell_quote("two words"), "'two words'");
+        assert_eq!(shell_quote("$HOME"), "'$HOME'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn test_shell_arg() {
+        assert_eq!(shell_arg("bash"), "-c");
+        assert_eq!(shell_arg("/usr/local/bin/zsh"), "-c");
+        assert_eq!(shell_arg("pwsh"), "-Command");
+        assert_eq!(shell_arg("cmd.exe"), "/C");
+    }
+
+    /// Restores the redaction state a test replaced.
+    struct RedactionGuard(Option<crate::security::Redactor>);
+
+    impl Drop for RedactionGuard {
+        fn drop(&mut self) {
+            match self.0.take() {
+                Some(redactor) => crate::security::enable_redaction(redactor),
+                None => drop(crate::security::disable_redaction()),
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial(redaction)]
+    fn test_known_secret_is_masked_in_captured_output() {
+        let _guard = RedactionGuard(crate::security::disable_redaction());
+        crate::security::enable_redaction(crate::security::Redactor::new([
+            "palrun-executor-test-secret".to_string(),
+        ]));
+        let command = Command::new("leak", "echo token=palrun-executor-test-secret >&2; echo ok");
+
+        let result = Executor::new().capture(true).execute(&command).unwrap();
+        assert_eq!(result.stderr.unwrap().trim(), "token=****");
+
+        let mut streamed = Vec::new();
+        let result = Executor::new().execute_streaming(&command, |line| streamed.push(line.text));
+        assert!(result.unwrap().success());
+        streamed.sort();
+        assert_eq!(streamed, vec!["ok", "token=****"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_env_is_set_for_the_child_only() {
+        let command = Command::new("env", "printf %s \"$PALRUN_TEST_ONLY_VAR\"")
+            .with_env("PALRUN_TEST_ONLY_VAR", "first")
+            .with_env("PALRUN_TEST_ONLY_VAR", "from-with-env");
+
+        let result = Executor::new().capture(true).execute(&command).unwrap();
+        assert_eq!(result.stdout.unwrap(), "from-with-env");
+        assert!(std::env::var_os("PALRUN_TEST_ONLY_VAR").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_streaming_tags_streams_and_drains_full_pipes() {
+        // Far more stderr than a pipe buffer holds, written before stdout
+        let command = Command::new(
+            "noisy",
+            "i=0; while [ $i -lt 5000 ]; do echo err-line-$i >&2; i=$((i+1)); done; echo done",
+        );
+
+        let mut lines = Vec::new();
+        let result = Executor::new()
+            .capture(true)
+            .execute_streaming(&command, |line| lines.push(line))
+            .unwrap();
+
+        assert!(result.success());
+        assert_eq!(lines.iter().filter(|l| l.stream == Stream::Stderr).count(), 5000);
+        assert_eq!(
+            lines.iter().filter(|l| l.stream == Stream::Stdout).collect::<Vec<_>>(),
+            vec![&StreamLine { stream: Stream::Stdout, text: "done".to_string() }]
+        );
+        assert_eq!(result.stdout.as_deref(), Some("done"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_streaming_without_capture_keeps_no_output() {
+        let command = Command::new("chatty", "echo out; echo err >&2");
+
+        let mut count = 0;
+        let result = Executor::new().execute_streaming(&command, |_| count += 1).unwrap();
+
+        assert_eq!(count, 2);
+        assert!(result.stdout.is_none());
+        assert!(result.stderr.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_streaming_stops_when_interrupted() {
+        let command = Command::new("slow", "echo started; sleep 30; echo finished");
+        let interrupt = AtomicBool::new(false);
+
+        let mut lines = Vec::new();
+        let result = Executor::new()
+            .execute_streaming_until(&command, &interrupt, |line| {
+                interrupt.store(true, Ordering::Relaxed);
+                lines.push(line.text);
+            })
+            .unwrap();
+
+        assert!(!result.success());
+        assert!(result.duration < Duration::from_secs(10));
+        assert_eq!(lines, vec!["started"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_streaming_times_out() {
+        let command = Command::new("slow", "echo started; sleep 30").with_timeout(1);
+
+        let mut lines = Vec::new();
+        let start = Instant::now();
+        let err = Executor::new().execute_streaming(&command, |line| lines.push(line.text));
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(matches!(
+            err.unwrap_err().downcast_ref::<CommandError>(),
+            Some(CommandError::TimedOut { timeout, .. }) if *timeout == Duration::from_secs(1)
+        ));
+        assert_eq!(lines, vec!["started"]);
+
+        // The executor's timeout applies when the command sets none
+        let command = Command::new("slow", "sleep 30");
+        let executor = Executor::new().timeout(Duration::from_millis(200));
+        assert!(executor.execute_streaming(&command, |_| {}).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_streaming_interrupts_a_command_that_keeps_printing() {
+        let command = Command::new("yes", "while true; do echo y; done");
+        let interrupt = AtomicBool::new(false);
+
+        let mut count = 0;
+        let result = Executor::new()
+            .execute_streaming_until(&command, &interrupt, |_| {
+                count += 1;
+                if count == 100 {
+                    interrupt.store(true, Ordering::Relaxed);
+                }
+            })
+            .unwrap();
+
+        assert!(!result.success());
+        assert!(result.duration < Duration::from_secs(10));
+    }
+
     #[test]
     fn test_executor_builder() {
         let executor = Executor::new().capture(true).timeout(Duration::from_secs(30));
@@ -291,6 +762,76 @@ mod tests {
         assert!(stdout.contains("tmp"));
     }
 
+    #[test]
+    fn test_exec_wrapper_quotes_command() {
+        let wrapper = ExecWrapper::new("docker compose exec app");
+        assert_eq!(
+            wrapper.wrap("npm test && echo 'done' ok"),
+            r"docker compose exec app sh -c 'npm test && echo '\''done'\'' ok'"
+        );
+    }
+
+    #[test]
+    fn test_exec_wrapper_scoped_to_source() {
+        let wrapper = ExecWrapper {
+            sources: vec!["npm".to_string()],
+            ..ExecWrapper::new("docker compose exec app")
+        };
+        let executor = Executor::new().wrapper(Some(wrapper));
+
+        let npm = Command::from_npm_script("build", "tsc", "npm", None);
+        let make = Command::from_make_target("build", None);
+
+        assert_eq!(executor.command_line(&npm), "docker compose exec app sh -c 'npm run build'");
+        assert_eq!(executor.command_line(&make), "make build");
+    }
+
+    #[test]
+    fn test_exec_wrapper_scoped_to_tag() {
+        let wrapper = ExecWrapper { tags: vec!["db".to_string()], ..ExecWrapper::new("ssh box") };
+
+        assert!(wrapper.applies_to(&Command::new("migrate", "rake db:migrate").with_tag("db")));
+        assert!(!wrapper.applies_to(&Command::new("lint", "rubocop")));
+        assert!(ExecWrapper::new("ssh box").applies_to(&Command::new("lint", "rubocop")));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_execute_applies_wrapper() {
+        // `env` stands in for a container runner: it runs the rest of the line
+        let executor = Executor::new().capture(true).wrapper(Some(ExecWrapper::new("env")));
+        let command = Command::new("echo", "echo wrapped | tr a-z A-Z");
+
+        let result = executor.execute(&command).unwrap();
+        assert!(result.success());
+        assert_eq!(result.stdout.unwrap().trim(), "WRAPPED");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_execute_times_out() {
+        let executor = Executor::new().capture(true);
+        let command = Command::new("sleep", "sleep 10").with_timeout(1);
+
+        let start = Instant::now();
+        let err = executor.execute(&command).unwrap_err();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(matches!(
+            err.downcast_ref::<CommandError>(),
+            Some(CommandError::TimedOut { timeout, .. }) if *timeout == Duration::from_secs(1)
+        ));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_command_timeout_overrides_default() {
+        let executor = Executor::new().capture(true).timeout(Duration::from_millis(100));
+        let command = Command::new("sleep", "sleep 0.3 && echo done").with_timeout(5);
+
+        let result = executor.execute(&command).unwrap();
+        assert_eq!(result.stdout.unwrap().trim(), "done");
+    }
+
     #[test]
     fn test_dangerous_command_detection() {
         assert!(is_dangerous_command("rm -rf /"));
@@ -0,0 +1,71 @@
+//! File helpers shared across modules.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// Write a file atomically (write to temp, then rename).
+///
+/// The temporary file sits next to `path`, named after it with `.tmp`
+/// appended, so the rename never crosses filesystems. When `path` already
+/// exists, its permissions carry over, so a private file stays private.
+pub fn write_file_atomic(path: &Path, content: &str) -> Result<()> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut file = fs::File::create(&temp_path)
+        .with_context(|| format!("Failed to create {}", temp_path.display()))?;
+    if let Ok(metadata) = fs::metadata(path) {
+        // Restrict the temp file before any content is written to it
+        file.set_permissions(metadata.permissions())
+            .with_context(|| format!("Failed to set permissions on {}", temp_path.display()))?;
+    }
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("Failed to write to {}", temp_path.display()))?;
+    drop(file);
+
+    // Rename to final location (atomic on most systems)
+    fs::rename(&temp_path, path).with_context(|| {
+        format!("Failed to rename {} to {}", temp_path.display(), path.display())
+    })?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_file_atomic() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(".env.local");
+        fs::write(temp.path().join(".env"), "KEEP=1\n").unwrap();
+
+        write_file_atomic(&path, "A=1\n").unwrap();
+        write_file_atomic(&path, "A=2\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A=2\n");
+        assert_eq!(fs::read_to_string(temp.path().join(".env")).unwrap(), "KEEP=1\n");
+        assert!(!temp.path().join(".env.local.tmp").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_atomic_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(".env");
+        fs::write(&path, "SECRET=1\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_file_atomic(&path, "SECRET=2\n").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,48 @@
+//! Glob matching for file and directory names.
+
+/// Match `text` against a glob with `*` (any run) and `?` (one character).
+///
+/// `*` also matches `/`, so callers that match path segments split the
+/// path themselves.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("Cargo.toml", "Cargo.toml"));
+        assert!(glob_match("*.gradle", "build.gradle"));
+        assert!(glob_match("build.gradle*", "build.gradle.kts"));
+        assert!(glob_match("?akefile", "Makefile"));
+        assert!(glob_match("gradle/*.toml", "gradle/libs.versions.toml"));
+        assert!(glob_match("web-*", "web-admin"));
+        assert!(!glob_match("web-*", "api"));
+        assert!(!glob_match("*.gradle", "build.gradle.kts"));
+        assert!(!glob_match("Cargo.toml", "Cargo.lock"));
+    }
+}
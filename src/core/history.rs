@@ -52,6 +52,12 @@ pub struct HistoryEntry {
     pub success_count: u32,
     /// Number of failed executions
     pub failure_count: u32,
+    /// Whether the most recent execution succeeded
+    #[serde(default)]
+    pub last_success: Option<bool>,
+    /// Exit code of the most recent execution
+    #[serde(default)]
+    pub last_exit_code: Option<i32>,
 }
 
 impl HistoryEntry {
@@ -67,6 +73,8 @@ impl HistoryEntry {
             total_duration_ms: 0,
             success_count: 0,
             failure_count: 0,
+            last_success: None,
+            last_exit_code: None,
         }
     }
 
@@ -75,6 +83,7 @@ impl HistoryEntry {
         self.execution_count += 1;
         self.last_executed = current_timestamp();
         self.total_duration_ms += duration_ms;
+        self.last_success = Some(success);
         if success {
             self.success_count += 1;
         } else {
@@ -175,6 +184,13 @@ impl HistoryManager {
         Ok(Self { history_path: path, history, max_entries: 1000 })
     }
 
+    /// Set the maximum number of history entries to keep.
+    #[must_use]
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
     /// Get the default history file path.
     fn default_history_path() -> anyhow::Result<PathBuf> {
         let home =
@@ -235,6 +251,31 @@ impl HistoryManager {
         self.prune_old_entries();
     }
 
+    /// Record a command execution along with its exit code.
+    ///
+    /// `None` means the process was killed by a signal and counts as a failure.
+    pub fn record_exit(
+        &mut self,
+        command_id: &str,
+        command_name: &str,
+        duration_ms: u64,
+        exit_code: Option<i32>,
+    ) {
+        self.record_execution(command_id, command_name, duration_ms, exit_code == Some(0));
+        if let Some(entry) = self.history.entries.get_mut(command_id) {
+            entry.last_exit_code = exit_code;
+        }
+    }
+
+    /// Get the most recently executed command whose last run failed.
+    pub fn last_failed(&self) -> Option<&HistoryEntry> {
+        self.history
+            .entries
+            .values()
+            .filter(|e| e.last_success == Some(false))
+            .max_by_key(|e| e.last_executed)
+    }
+
     /// Get history entry for a command.
     pub fn get_entry(&self, command_id: &str) -> Option<&HistoryEntry> {
         self.history.entries.get(command_id)
@@ -245,6 +286,15 @@ impl HistoryManager {
         self.history.entries.get(command_id).map(|e| e.frecency_score()).unwrap_or(0.0)
     }
 
+    /// Get the frecency score of every command in history, by command ID.
+    pub fn frecency_scores(&self) -> HashMap<String, f64> {
+        self.history
+            .entries
+            .iter()
+            .map(|(id, entry)| (id.clone(), entry.frecency_score()))
+            .collect()
+    }
+
     /// Get sorted command IDs by frecency (highest first).
     pub fn get_by_frecency(&self) -> Vec<&str> {
         let mut entries: Vec<_> = self.history.entries.iter().collect();
@@ -531,6 +581,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_corrupt_history_starts_empty() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        fs::write(&path, "{ not json").unwrap();
+
+        let mut manager = HistoryManager::with_path(path).unwrap();
+        assert!(!manager.has_history());
+
+        manager.record_execution("cmd1", "test", 100, true);
+        manager.save().unwrap();
+        assert!(manager.frecency_scores()["cmd1"] > 0.0);
+    }
+
     #[test]
     fn test_clear_history() {
         let dir = tempdir().unwrap();
@@ -549,4 +613,43 @@ mod tests {
         // Non-favorites should be cleared
         assert!(manager.get_entry("cmd2").is_none());
     }
+
+    #[test]
+    fn test_last_failed() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let mut manager = HistoryManager::with_path(path).unwrap();
+
+        assert!(manager.last_failed().is_none());
+
+        manager.record_exit("build", "build", 100, Some(1));
+        manager.record_exit("test", "test", 100, Some(101));
+        manager.record_exit("lint", "lint", 100, Some(0));
+        manager.record_exit("fixed", "fixed", 100, Some(2));
+        manager.record_exit("fixed", "fixed", 100, Some(0));
+
+        // Spread the runs out so ordering doesn't depend on the clock
+        for (id, ts) in [("build", 100), ("test", 200), ("lint", 300), ("fixed", 400)] {
+            manager.history.entries.get_mut(id).unwrap().last_executed = ts;
+        }
+
+        let entry = manager.last_failed().unwrap();
+        assert_eq!(entry.command_id, "test");
+        assert_eq!(entry.last_exit_code, Some(101));
+        assert_eq!(manager.get_entry("fixed").unwrap().failure_count, 1);
+
+        // Killed by a signal counts as a failure
+        manager.record_exit("lint", "lint", 100, None);
+        manager.history.entries.get_mut("lint").unwrap().last_executed = 500;
+        assert_eq!(manager.last_failed().unwrap().command_id, "lint");
+    }
+
+    #[test]
+    fn test_legacy_entries_are_not_failures() {
+        let json = r#"{"entries":{"a":{"command_id":"a","command_name":"a","execution_count":1,
+            "last_executed":1,"first_executed":1,"total_duration_ms":0,"success_count":0,
+            "failure_count":1}},"favorites":[]}"#;
+        let history: CommandHistory = serde_json::from_str(json).unwrap();
+        assert_eq!(history.entries["a"].last_success, None);
+    }
 }
@@ -3,6 +3,7 @@
 //! This module contains the fundamental data structures used throughout
 //! the application: commands, the registry, configuration, and execution.
 
+mod alias;
 mod analytics;
 mod background;
 mod capture;
@@ -12,16 +13,23 @@ mod config;
 mod context;
 mod degradation;
 mod executor;
+mod files;
 mod filter;
+mod glob;
 mod history;
 mod network;
 mod offline;
 mod parallel;
+mod process_group;
 mod registry;
 mod resilience;
+mod result_cache;
 mod retry;
+mod select;
+mod sources;
 mod trust;
 
+pub use alias::{resolve_alias, AliasError};
 pub use analytics::{
     Analytics, AnalyticsReport, CommandStats, Insight, InsightCategory, TimePeriod,
 };
@@ -36,31 +44,45 @@ pub use chain::{
     ChainExecutor, ChainOperator, ChainResult, ChainStep, ChainStepResult, ChainStepStatus,
     CommandChain,
 };
-pub use command::{Command, CommandSource};
-pub use config::Config;
-#[cfg(feature = "git")]
-pub use config::HooksConfig;
+pub use command::{Command, CommandList, CommandSource, ListedCommand, LIST_FORMAT_VERSION};
 #[cfg(feature = "ai")]
 pub use config::{
-    AiConfig, AzureOpenAIConfig, ClaudeConfig, GrokConfig, OllamaConfig, OpenAIConfig,
+    AiConfig, AzureOpenAIConfig, ClaudeConfig, GrokConfig, NotifyConfig, OllamaConfig, OpenAIConfig,
+};
+pub use config::{
+    AliasConfig, Config, ConfigSources, EnterAction, ExecCacheConfig, ExecConfig, IconPreset,
+    IconsConfig, MCPConfig, MCPServerEntry, PluginsConfig, ScannerConfig, SearchCase, SearchConfig,
 };
+#[cfg(feature = "git")]
+pub use config::{HookCommands, HooksConfig};
 pub use context::{CommandContext, ContextFilter, LocationIndicator};
 pub use degradation::{
     with_fallback, DegradationManager, DegradationReason, DegradedFeature, FallbackResult, Feature,
 };
-pub use executor::{ExecutionResult, Executor};
+pub use executor::{
+    program_exists, shell_quote, CommandError, ExecWrapper, ExecutionResult, Executor, Stream,
+    StreamLine,
+};
+pub use files::write_file_atomic;
 pub use filter::{
     filter_by_source, filter_by_tag, filter_by_workspace, get_source_types, get_tags,
     get_workspaces, ParsedQuery,
 };
+pub use glob::glob_match;
 pub use history::{CommandHistory, HistoryEntry, HistoryManager};
-pub use network::{NetworkChecker, NetworkStatus, ServiceChecker};
+pub use network::{
+    is_offline_mode, set_offline_mode, NetworkChecker, NetworkStatus, ServiceChecker,
+    OFFLINE_ENV_VAR,
+};
 pub use offline::{OfflineManager, OfflineQueue, QueueEntry, QueueSummary, QueuedOperation};
 pub use parallel::{
     ParallelExecutor, ParallelProcess, ParallelResult, ProcessEvent, ProcessId, ProcessOutput,
     ProcessStatus,
 };
-pub use registry::CommandRegistry;
+pub use registry::{CommandRegistry, SearchField};
 pub use resilience::{execute_resilient, FeatureResilience, ResilienceManager, ResilientResult};
+pub use result_cache::{CachedResult, ResultCache, CACHEABLE_TAG};
 pub use retry::{retry, retry_async, CircuitBreaker, CircuitState, RetryConfig, RetryResult};
+pub use select::{choose_match, exact_matches, format_matches, MatchChoice};
+pub use sources::{SourceInfo, SourceRegistry};
 pub use trust::{trust_warning_message, TrustDecision, TrustStore};
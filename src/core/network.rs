@@ -1,11 +1,37 @@
 //! Network connectivity utilities.
 //!
 //! Provides offline detection and network status monitoring.
+//!
+//! Offline mode (`--offline` or `PALRUN_OFFLINE=1`) is a process-wide switch
+//! that network-backed features consult before making any request, so they
+//! fail fast instead of hanging on an unreachable network.
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Process-wide offline mode flag, set by the `--offline` CLI flag.
+static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Environment variable that enables offline mode.
+pub const OFFLINE_ENV_VAR: &str = "PALRUN_OFFLINE";
+
+/// Enable or disable offline mode for the current process.
+pub fn set_offline_mode(offline: bool) {
+    OFFLINE_MODE.store(offline, Ordering::SeqCst);
+}
+
+/// Check whether offline mode is active.
+///
+/// Offline mode is enabled by [`set_offline_mode`] or by setting
+/// `PALRUN_OFFLINE` to `1`, `true`, or `yes`.
+pub fn is_offline_mode() -> bool {
+    OFFLINE_MODE.load(Ordering::SeqCst)
+        || std::env::var(OFFLINE_ENV_VAR)
+            .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false)
+}
+
 /// Network status for graceful degradation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NetworkStatus {
@@ -63,6 +89,11 @@ impl NetworkChecker {
     /// Performs a quick DNS lookup or HTTP request to verify connectivity.
     #[cfg(feature = "ai")]
     pub fn check(&self) -> NetworkStatus {
+        if is_offline_mode() {
+            self.is_online.store(false, Ordering::SeqCst);
+            return NetworkStatus::Offline;
+        }
+
         // Try a simple HTTP request to a reliable endpoint
         let client =
             if let Ok(c) = reqwest::blocking::Client::builder().timeout(self.timeout).build() {
@@ -91,6 +122,11 @@ impl NetworkChecker {
     /// Check network connectivity (async version).
     #[cfg(feature = "ai")]
     pub async fn check_async(&self) -> NetworkStatus {
+        if is_offline_mode() {
+            self.is_online.store(false, Ordering::SeqCst);
+            return NetworkStatus::Offline;
+        }
+
         let client = if let Ok(c) = reqwest::Client::builder().timeout(self.timeout).build() {
             c
         } else {
@@ -174,6 +210,10 @@ impl ServiceChecker {
     /// Check if the service is available.
     #[cfg(feature = "ai")]
     pub async fn is_available(&self) -> bool {
+        if is_offline_mode() {
+            return false;
+        }
+
         let client = match reqwest::Client::builder().timeout(self.timeout).build() {
             Ok(c) => c,
             Err(_) => return false,
@@ -200,6 +240,7 @@ impl ServiceChecker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_network_status() {
@@ -240,4 +281,36 @@ mod tests {
         let checker = ServiceChecker::ollama();
         assert_eq!(checker.name, "Ollama");
     }
+
+    #[test]
+    #[serial]
+    fn test_offline_mode_toggle() {
+        set_offline_mode(true);
+        assert!(is_offline_mode());
+        set_offline_mode(false);
+        assert!(!is_offline_mode());
+    }
+
+    #[test]
+    #[serial]
+    fn test_offline_mode_from_env() {
+        std::env::set_var(OFFLINE_ENV_VAR, "1");
+        assert!(is_offline_mode());
+        std::env::set_var(OFFLINE_ENV_VAR, "0");
+        assert!(!is_offline_mode());
+        std::env::remove_var(OFFLINE_ENV_VAR);
+    }
+
+    #[cfg(feature = "ai")]
+    #[test]
+    #[serial]
+    fn test_check_fails_fast_offline() {
+        set_offline_mode(true);
+        let checker = NetworkChecker::new();
+        let start = std::time::Instant::now();
+        assert_eq!(checker.check(), NetworkStatus::Offline);
+        assert!(start.elapsed() < Duration::from_millis(100));
+        assert!(!checker.is_online());
+        set_offline_mode(false);
+    }
 }
@@ -0,0 +1,193 @@
+//! Process-group handling for executed commands.
+//!
+//! On Unix each command runs in its own process group, so the shell and
+//! everything it started can be signalled together. SIGINT and SIGTERM
+//! received by Palrun are forwarded to the running groups, and Palrun
+//! keeps waiting until the children have exited. Interactive commands are
+//! given the terminal while they run, as a job-control shell would.
+//!
+//! On Windows the console already delivers Ctrl+C and Ctrl+Break to every
+//! process attached to it, so children are spawned as-is. Job objects and
+//! `CTRL_BREAK_EVENT` are not used, since both need Win32 calls that the
+//! crate's `forbid(unsafe_code)` rules out: a command that has to be
+//! stopped has its whole process tree killed with `taskkill /T /F`, with
+//! no chance to clean up first.
+
+use std::io;
+use std::process::{Child, Command, ExitStatus};
+use std::time::Duration;
+
+/// Spawn `command`, forwarding termination signals to it until the
+/// returned guard is dropped.
+///
+/// `interactive` should be set when the child inherits the terminal; it is
+/// then made the foreground job so it can read from the terminal.
+/// Drop the guard only after the child has been waited on.
+pub fn spawn(command: &mut Command, interactive: bool) -> io::Result<(Child, Guard)> {
+    imp::spawn(command, interactive)
+}
+
+/// Stop a child that has run too long, and wait for it.
+///
+/// On Unix its process group gets SIGTERM, then SIGKILL if it is still
+/// running after `grace`. On Windows the child and everything it started
+/// are killed right away.
+pub fn terminate(child: &mut Child, grace: Duration) -> io::Result<ExitStatus> {
+    imp::terminate(child, grace)
+}
+
+pub use imp::Guard;
+
+#[cfg(unix)]
+mod imp {
+    use std::io::{self, IsTerminal};
+    use std::os::unix::process::CommandExt;
+    use std::process::{Child, Command, ExitStatus};
+    use std::sync::{Mutex, Once};
+    use std::time::{Duration, Instant};
+
+    use nix::sys::signal::{killpg, pthread_sigmask, SigSet, SigmaskHow, Signal};
+    use nix::unistd::{getpgrp, tcgetpgrp, tcsetpgrp, Pid};
+    use signal_hook::consts::{SIGINT, SIGTERM};
+    use signal_hook::iterator::Signals;
+
+    /// Process groups of running children.
+    static GROUPS: Mutex<Vec<Pid>> = Mutex::new(Vec::new());
+
+    /// Keeps a child's group registered for signal forwarding.
+    pub struct Guard {
+        pgid: Pid,
+        owns_terminal: bool,
+    }
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            if let Ok(mut groups) = GROUPS.lock() {
+                groups.retain(|pgid| *pgid != self.pgid);
+            }
+            if self.owns_terminal {
+                set_foreground(getpgrp());
+            }
+        }
+    }
+
+    pub fn spawn(command: &mut Command, interactive: bool) -> io::Result<(Child, Guard)> {
+        install_forwarding();
+
+        command.process_group(0);
+        let child = command.spawn()?;
+        let pgid = Pid::from_raw(i32::try_from(child.id()).unwrap_or(0));
+
+        if let Ok(mut groups) = GROUPS.lock() {
+            groups.push(pgid);
+        }
+
+        // Hand the terminal to the child, as a job-control shell would.
+        // Resume it in case it already tried to read as a background job.
+        let owns_terminal = interactive && is_foreground() && set_foreground(pgid);
+        if owns_terminal {
+            let _ = killpg(pgid, Signal::SIGCONT);
+        }
+
+        Ok((child, Guard { pgid, owns_terminal }))
+    }
+
+    pub fn terminate(child: &mut Child, grace: Duration) -> io::Result<ExitStatus> {
+        let pgid = Pid::from_raw(i32::try_from(child.id()).unwrap_or(0));
+        let _ = killpg(pgid, Signal::SIGTERM);
+
+        let deadline = Instant::now() + grace;
+        while Instant::now() < deadline {
+            if let Some(status) = child.try_wait()? {
+                return Ok(status);
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let _ = killpg(pgid, Signal::SIGKILL);
+        child.wait()
+    }
+
+    /// Start the thread that forwards SIGINT/SIGTERM to child groups.
+    ///
+    /// With no child running the signal gets its default action, so
+    /// Palrun still terminates as usual.
+    fn install_forwarding() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            let mut signals = match Signals::new([SIGINT, SIGTERM]) {
+                Ok(signals) => signals,
+                Err(e) => {
+                    tracing::warn!("Could not install signal forwarding: {e}");
+                    return;
+                }
+            };
+
+            std::thread::spawn(move || {
+                for signal in signals.forever() {
+                    let groups = GROUPS.lock().map(|g| g.clone()).unwrap_or_default();
+                    if groups.is_empty() {
+                        let _ = signal_hook::low_level::emulate_default_handler(signal);
+                        continue;
+                    }
+                    let Ok(signal) = Signal::try_from(signal) else { continue };
+                    for pgid in groups {
+                        let _ = killpg(pgid, signal);
+                    }
+                }
+            });
+        });
+    }
+
+    /// Whether Palrun is the foreground job of a terminal on stdin.
+    fn is_foreground() -> bool {
+        let stdin = io::stdin();
+        stdin.is_terminal() && tcgetpgrp(&stdin).is_ok_and(|pgid| pgid == getpgrp())
+    }
+
+    /// Make `pgid` the terminal's foreground process group.
+    ///
+    /// SIGTTOU is blocked meanwhile so a background caller is not stopped.
+    fn set_foreground(pgid: Pid) -> bool {
+        let mut ttou = SigSet::empty();
+        ttou.add(Signal::SIGTTOU);
+        let mut previous = SigSet::empty();
+        let blocked = pthread_sigmask(SigmaskHow::SIG_BLOCK, Some(&ttou), Some(&mut previous));
+
+        let result = tcsetpgrp(io::stdin(), pgid);
+
+        if blocked.is_ok() {
+            let _ = pthread_sigmask(SigmaskHow::SIG_SETMASK, Some(&previous), None);
+        }
+        result.is_ok()
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use std::io;
+    use std::process::{Child, Command, ExitStatus, Stdio};
+    use std::time::Duration;
+
+    /// No state is needed; console control events reach the child directly.
+    pub struct Guard;
+
+    pub fn spawn(command: &mut Command, _interactive: bool) -> io::Result<(Child, Guard)> {
+        Ok((command.spawn()?, Guard))
+    }
+
+    /// `Child::kill` only stops the shell, so the tree is killed with
+    /// `taskkill`. Console programs have no gentler request to honor.
+    pub fn terminate(child: &mut Child, _grace: Duration) -> io::Result<ExitStatus> {
+        let killed = Command::new("taskkill")
+            .args(["/T", "/F", "/PID", &child.id().to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        if !killed.is_ok_and(|status| status.success()) {
+            // The tree may be gone already, or taskkill unavailable
+            let _ = child.kill();
+        }
+        child.wait()
+    }
+}
@@ -3,16 +3,40 @@
 //! The registry maintains all discovered commands and provides
 //! fuzzy search functionality using the nucleo library.
 
-use std::sync::Arc;
+use std::collections::HashMap;
 
-use nucleo::{
-    pattern::{CaseMatching, Normalization},
-    Config, Nucleo,
-};
+use bitflags::bitflags;
+use nucleo::pattern::Pattern;
+use nucleo::{Config, Matcher, Utf32Str};
 use parking_lot::Mutex;
 
+use super::config::SearchConfig;
 use super::Command;
 
+bitflags! {
+    /// Command fields a search matches against.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SearchField: u8 {
+        /// The command name
+        const NAME = 1;
+
+        /// The description
+        const DESCRIPTION = 1 << 1;
+
+        /// The tags
+        const TAGS = 1 << 2;
+    }
+}
+
+impl Default for SearchField {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Extra weight given to a match in the name, so name matches rank first.
+const NAME_WEIGHT: u32 = 2;
+
 /// Registry for storing and searching commands.
 ///
 /// Uses nucleo for high-performance fuzzy matching.
@@ -21,7 +45,13 @@ pub struct CommandRegistry {
     commands: Vec<Command>,
 
     /// Nucleo fuzzy matcher
-    matcher: Arc<Mutex<Nucleo<String>>>,
+    matcher: Mutex<Matcher>,
+
+    /// Case and normalization options for queries
+    search: SearchConfig,
+
+    /// Usage (frecency) scores by command ID, used to break ties
+    usage: HashMap<String, f64>,
 }
 
 impl std::fmt::Debug for CommandRegistry {
@@ -33,29 +63,30 @@ impl std::fmt::Debug for CommandRegistry {
 impl CommandRegistry {
     /// Create a new empty registry.
     pub fn new() -> Self {
-        let config = Config::DEFAULT.match_paths();
-        let matcher = Nucleo::new(config, Arc::new(|| {}), None, 1);
+        Self::with_search(SearchConfig::default())
+    }
+
+    /// Create a new empty registry with the given search options.
+    pub fn with_search(search: SearchConfig) -> Self {
+        let matcher = Matcher::new(Config::DEFAULT.match_paths());
+        Self { commands: Vec::new(), matcher: Mutex::new(matcher), search, usage: HashMap::new() }
+    }
+
+    /// Set the usage scores that break ties between equally good matches.
+    ///
+    /// Scores are keyed by command ID; higher means more frequently and
+    /// recently used. Commands without a score rank as unused.
+    pub fn set_usage(&mut self, usage: HashMap<String, f64>) {
+        self.usage = usage;
+    }
 
-        Self { commands: Vec::new(), matcher: Arc::new(Mutex::new(matcher)) }
+    /// Usage score of the command at `index`.
+    fn usage_score(&self, index: usize) -> f64 {
+        self.commands.get(index).and_then(|c| self.usage.get(&c.id)).copied().unwrap_or(0.0)
     }
 
     /// Add a command to the registry.
     pub fn add(&mut self, command: Command) {
-        let index = self.commands.len(); // Get index before adding
-        let match_text = command.match_text();
-
-        // Add to nucleo matcher with index as data
-        {
-            let matcher = self.matcher.lock();
-            let injector = matcher.injector();
-            // Store the index as a string for later retrieval
-            injector.push(index.to_string(), {
-                move |_, cols| {
-                    cols[0] = match_text.as_str().into();
-                }
-            });
-        }
-
         self.commands.push(command);
     }
 
@@ -91,65 +122,97 @@ impl CommandRegistry {
         &self.commands
     }
 
-    /// Search commands with fuzzy matching.
+    /// Search commands with fuzzy matching on name, description, and tags.
     ///
     /// Returns indices of matching commands, sorted by match score.
     pub fn search(&self, pattern: &str) -> Vec<usize> {
-        if pattern.is_empty() {
-            // Return all commands in order
-            return (0..self.commands.len()).collect();
-        }
-
-        let mut matcher = self.matcher.lock();
+        self.search_fields(pattern, SearchField::all())
+    }
 
-        // Update the search pattern
-        matcher.pattern.reparse(
-            0,
-            pattern,
-            CaseMatching::Smart,
-            Normalization::Smart,
-            false, // append
-        );
+    /// Search commands with fuzzy matching on the given fields only.
+    ///
+    /// Returns indices of matching commands, sorted by match score.
+    pub fn search_fields(&self, pattern: &str, fields: SearchField) -> Vec<usize> {
+        self.search_fields_where(pattern, fields, |_| true)
+    }
 
-        // Tick to process matches
-        let status = matcher.tick(10);
+    /// Search only the commands for which `keep` returns true.
+    ///
+    /// Commands are filtered before they are ranked, so filters narrow the
+    /// set the fuzzy search runs over rather than trimming its results.
+    pub fn search_where(&self, pattern: &str, keep: impl Fn(&Command) -> bool) -> Vec<usize> {
+        self.search_fields_where(pattern, SearchField::all(), keep)
+    }
 
-        // If still running, do another tick
-        if status.running {
-            matcher.tick(100);
+    fn search_fields_where(
+        &self,
+        pattern: &str,
+        fields: SearchField,
+        keep: impl Fn(&Command) -> bool,
+    ) -> Vec<usize> {
+        if pattern.is_empty() {
+            // Return all commands, most used first, otherwise in order
+            let mut indices: Vec<usize> =
+                (0..self.commands.len()).filter(|&idx| keep(&self.commands[idx])).collect();
+            indices.sort_by(|&a, &b| self.usage_score(b).total_cmp(&self.usage_score(a)));
+            return indices;
         }
 
-        // Get snapshot and collect results
-        let snapshot = matcher.snapshot();
-        let matched_count = snapshot.matched_item_count();
+        self.scored_where(pattern, fields, keep).into_iter().map(|(idx, _)| idx).collect()
+    }
 
-        let mut results: Vec<(usize, u32)> = (0..matched_count)
-            .filter_map(|i| {
-                snapshot
-                    .get_matched_item(i)
-                    .map(|item| (item.data.parse::<usize>().unwrap_or(0), i))
+    /// Score every matching command, best first.
+    ///
+    /// The selected fields are joined into one haystack so a query can span
+    /// them ("build prod" matches name "build" with description "...prod...").
+    /// A match within the name alone adds a weighted bonus on top.
+    /// Ties go to the more used command, then keep registration order.
+    pub fn scored(&self, pattern: &str, fields: SearchField) -> Vec<(usize, u32)> {
+        self.scored_where(pattern, fields, |_| true)
+    }
+
+    fn scored_where(
+        &self,
+        pattern: &str,
+        fields: SearchField,
+        keep: impl Fn(&Command) -> bool,
+    ) -> Vec<(usize, u32)> {
+        let pattern =
+            Pattern::parse(pattern, self.search.case_matching(), self.search.normalization());
+        let mut matcher = self.matcher.lock();
+        let mut buf = Vec::new();
+
+        let mut results: Vec<(usize, u32)> = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter(|(_, command)| keep(command))
+            .filter_map(|(idx, command)| {
+                let haystack = command.match_text(fields);
+                let score = pattern.score(Utf32Str::new(&haystack, &mut buf), &mut matcher)?;
+                let name_score = if fields.contains(SearchField::NAME) {
+                    pattern.score(Utf32Str::new(&command.name, &mut buf), &mut matcher)
+                } else {
+                    None
+                };
+                Some((idx, score + NAME_WEIGHT * name_score.unwrap_or(0)))
             })
             .collect();
 
-        // Sort by score (highest first)
-        results.sort_by(|a, b| b.1.cmp(&a.1));
-
-        // Return just the indices
-        results.into_iter().map(|(idx, _)| idx).collect()
+        results.sort_by(|a, b| {
+            b.1.cmp(&a.1).then_with(|| self.usage_score(b.0).total_cmp(&self.usage_score(a.0)))
+        });
+        results
     }
 
     /// Clear all commands from the registry.
     pub fn clear(&mut self) {
         self.commands.clear();
-        // Recreate matcher
-        let config = Config::DEFAULT.match_paths();
-        self.matcher = Arc::new(Mutex::new(Nucleo::new(config, Arc::new(|| {}), None, 1)));
     }
 
     /// Remove a command by ID.
     pub fn remove(&mut self, id: &str) -> Option<Command> {
         if let Some(pos) = self.commands.iter().position(|c| c.id == id) {
-            // Note: This doesn't remove from nucleo - would need to rebuild
             Some(self.commands.remove(pos))
         } else {
             None
@@ -192,10 +255,23 @@ impl CommandRegistry {
         &self,
         pattern: &str,
         context: &super::CommandContext,
+    ) -> Vec<usize> {
+        self.search_with_context_where(pattern, context, |_| true)
+    }
+
+    /// Search with context-aware scoring among the commands `keep` accepts.
+    ///
+    /// Like [`Self::search_where`], filtering happens before ranking.
+    pub fn search_with_context_where(
+        &self,
+        pattern: &str,
+        context: &super::CommandContext,
+        keep: impl Fn(&Command) -> bool,
     ) -> Vec<usize> {
         if pattern.is_empty() {
             // Return all commands sorted by proximity
-            let mut indices: Vec<usize> = (0..self.commands.len()).collect();
+            let mut indices: Vec<usize> =
+                (0..self.commands.len()).filter(|&idx| keep(&self.commands[idx])).collect();
             indices.sort_by(|&a, &b| {
                 let score_a = context.proximity_score(&self.commands[a]);
                 let score_b = context.proximity_score(&self.commands[b]);
@@ -204,30 +280,15 @@ impl CommandRegistry {
             return indices;
         }
 
-        let mut matcher = self.matcher.lock();
-
-        // Update the search pattern
-        matcher.pattern.reparse(0, pattern, CaseMatching::Smart, Normalization::Smart, false);
-
-        // Tick to process matches
-        let status = matcher.tick(10);
-        if status.running {
-            matcher.tick(100);
-        }
-
-        // Get snapshot and collect results with scores
-        let snapshot = matcher.snapshot();
-        let matched_count = snapshot.matched_item_count();
-
-        let mut results: Vec<(usize, u32, u32)> = (0..matched_count)
-            .filter_map(|i| {
-                snapshot.get_matched_item(i).map(|item| {
-                    let idx = item.data.parse::<usize>().unwrap_or(0);
-                    let fuzzy_score = i; // Lower index = better fuzzy match
-                    let proximity_score =
-                        self.commands.get(idx).map(|c| context.proximity_score(c)).unwrap_or(0);
-                    (idx, fuzzy_score, proximity_score)
-                })
+        // Rank among the fuzzy matches; lower is better
+        let mut results: Vec<(usize, u32, u32)> = self
+            .scored_where(pattern, SearchField::all(), keep)
+            .into_iter()
+            .zip(0u32..)
+            .map(|((idx, _), fuzzy_score)| {
+                let proximity_score =
+                    self.commands.get(idx).map(|c| context.proximity_score(c)).unwrap_or(0);
+                (idx, fuzzy_score, proximity_score)
             })
             .collect();
 
@@ -236,22 +297,17 @@ impl CommandRegistry {
         results.sort_by(|a, b| {
             let combined_a = (100 - a.1.min(100)) + (a.2 / 5);
             let combined_b = (100 - b.1.min(100)) + (b.2 / 5);
-            combined_b.cmp(&combined_a)
+            combined_b
+                .cmp(&combined_a)
+                .then_with(|| self.usage_score(b.0).total_cmp(&self.usage_score(a.0)))
         });
 
         results.into_iter().map(|(idx, _, _)| idx).collect()
     }
 
-    /// Filter search results by context filter.
+    /// Search with context-aware scoring among commands passing the context filter.
     pub fn search_filtered(&self, pattern: &str, context: &super::CommandContext) -> Vec<usize> {
-        let results = self.search_with_context(pattern, context);
-
-        results
-            .into_iter()
-            .filter(|&idx| {
-                self.commands.get(idx).map(|c| context.matches_filter(c)).unwrap_or(false)
-            })
-            .collect()
+        self.search_with_context_where(pattern, context, |c| context.matches_filter(c))
     }
 }
 
@@ -261,13 +317,12 @@ impl Default for CommandRegistry {
     }
 }
 
-// Manual implementation since Nucleo doesn't implement Clone
+// Manual implementation so the clone gets its own matcher
 impl Clone for CommandRegistry {
     fn clone(&self) -> Self {
-        let mut new_registry = Self::new();
-        for cmd in &self.commands {
-            new_registry.add(cmd.clone());
-        }
+        let mut new_registry = Self::with_search(self.search);
+        new_registry.add_all(self.commands.iter().cloned());
+        new_registry.set_usage(self.usage.clone());
         new_registry
     }
 }
@@ -286,6 +341,51 @@ mod tests {
         ]
     }
 
+    fn search_names(registry: &CommandRegistry, pattern: &str) -> Vec<String> {
+        let mut names: Vec<String> = registry
+            .search(pattern)
+            .into_iter()
+            .map(|idx| registry.get_by_index(idx).unwrap().name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn mixed_case_registry(search: SearchConfig) -> CommandRegistry {
+        let mut registry = CommandRegistry::with_search(search);
+        registry.add(Command::new("Deploy", "make Deploy"));
+        registry.add(Command::new("deploy-docs", "make deploy-docs"));
+        registry.add(Command::new("café", "open café"));
+        registry
+    }
+
+    #[test]
+    fn test_search_case_modes() {
+        use crate::core::config::SearchCase;
+
+        let smart = mixed_case_registry(SearchConfig::default());
+        assert_eq!(search_names(&smart, "deploy"), vec!["Deploy", "deploy-docs"]);
+        assert_eq!(search_names(&smart, "Deploy"), vec!["Deploy"]);
+
+        let sensitive =
+            mixed_case_registry(SearchConfig { case: SearchCase::Sensitive, normalize: true });
+        assert_eq!(search_names(&sensitive, "deploy"), vec!["deploy-docs"]);
+
+        let insensitive =
+            mixed_case_registry(SearchConfig { case: SearchCase::Insensitive, normalize: true });
+        assert_eq!(search_names(&insensitive, "Deploy"), vec!["Deploy", "deploy-docs"]);
+    }
+
+    #[test]
+    fn test_search_normalization() {
+        let normalized = mixed_case_registry(SearchConfig::default());
+        assert_eq!(search_names(&normalized, "cafe"), vec!["café"]);
+
+        let exact =
+            mixed_case_registry(SearchConfig { normalize: false, ..SearchConfig::default() });
+        assert_eq!(search_names(&exact, "cafe"), Vec::<String>::new());
+    }
+
     #[test]
     fn test_registry_creation() {
         let registry = CommandRegistry::new();
@@ -348,6 +448,53 @@ mod tests {
         assert!(!results.is_empty());
     }
 
+    #[test]
+    fn test_search_matches_tags() {
+        let mut registry = CommandRegistry::new();
+        registry.add_all(create_test_commands());
+        registry.add(Command::new("npm run lcov", "npm run lcov").with_tag("coverage"));
+
+        let results = registry.search("coverage");
+        assert_eq!(results.len(), 1);
+        assert_eq!(registry.get_by_index(results[0]).unwrap().name, "npm run lcov");
+
+        assert_eq!(registry.search_fields("coverage", SearchField::NAME), Vec::<usize>::new());
+        assert_eq!(registry.search_fields("coverage", SearchField::TAGS), results);
+    }
+
+    #[test]
+    fn test_name_match_outranks_description() {
+        let mut registry = CommandRegistry::new();
+        registry.add(
+            Command::new("ship", "./ship.sh").with_description("deploy the site to production"),
+        );
+        registry.add(Command::new("deploy", "./deploy.sh"));
+
+        let results = registry.search("deploy");
+        assert_eq!(results.len(), 2);
+        assert_eq!(registry.get_by_index(results[0]).unwrap().name, "deploy");
+
+        let description_only = registry.search_fields("deploy", SearchField::DESCRIPTION);
+        assert_eq!(description_only, vec![0]);
+    }
+
+    #[test]
+    fn test_usage_breaks_ties() {
+        let mut registry = CommandRegistry::new();
+        registry.add(Command::new("build web", "npm run build:web"));
+        registry.add(Command::new("build api", "npm run build:api"));
+        let api_id = registry.get_by_index(1).unwrap().id.clone();
+
+        assert_eq!(registry.search("build"), vec![0, 1]);
+
+        registry.set_usage(HashMap::from([(api_id, 2.5)]));
+        assert_eq!(registry.search("build"), vec![1, 0]);
+        assert_eq!(registry.search(""), vec![1, 0]);
+
+        // Usage only breaks ties; a better match still wins
+        assert_eq!(registry.search("web"), vec![0]);
+    }
+
     #[test]
     fn test_clear() {
         let mut registry = CommandRegistry::new();
@@ -419,4 +566,81 @@ mod tests {
         let results = registry.search_on_branch("npm", Some("feature/foo"));
         assert_eq!(results.len(), 1);
     }
+
+    fn tagged_registry() -> CommandRegistry {
+        use super::super::CommandSource;
+        use std::path::PathBuf;
+
+        let mut registry = CommandRegistry::new();
+        registry.add(
+            Command::new("npm test", "npm test")
+                .with_source(CommandSource::PackageJson(PathBuf::from(".")))
+                .with_tag("test"),
+        );
+        registry.add(
+            Command::new("npm build", "npm run build")
+                .with_source(CommandSource::PackageJson(PathBuf::from(".")))
+                .with_tag("build"),
+        );
+        registry.add(
+            Command::new("cargo test", "cargo test")
+                .with_source(CommandSource::Cargo(PathBuf::from(".")))
+                .with_tag("test"),
+        );
+        registry.add(
+            Command::new("cargo build", "cargo build")
+                .with_source(CommandSource::Cargo(PathBuf::from(".")))
+                .with_tag("build"),
+        );
+        registry
+    }
+
+    fn names(registry: &CommandRegistry, indices: Vec<usize>) -> Vec<&str> {
+        indices.into_iter().map(|idx| registry.get_by_index(idx).unwrap().name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_get_by_tag() {
+        let registry = tagged_registry();
+        let tests: Vec<&str> =
+            registry.get_by_tag("test").iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(tests, vec!["npm test", "cargo test"]);
+        assert_eq!(registry.get_by_tag("deploy"), Vec::<&Command>::new());
+    }
+
+    #[test]
+    fn test_search_where_combines_filter_and_search() {
+        let registry = tagged_registry();
+        let is_cargo = |c: &Command| c.source.type_name() == "cargo";
+
+        // An empty pattern lists everything the filter keeps
+        assert_eq!(
+            names(&registry, registry.search_where("", is_cargo)),
+            vec!["cargo test", "cargo build"]
+        );
+
+        // A pattern only ranks commands the filter keeps
+        assert_eq!(names(&registry, registry.search_where("build", is_cargo)), vec!["cargo build"]);
+
+        // Source and tag filters compose
+        let cargo_tests = |c: &Command| is_cargo(c) && c.tags.iter().any(|t| t == "test");
+        assert_eq!(names(&registry, registry.search_where("t", cargo_tests)), vec!["cargo test"]);
+        assert_eq!(registry.search_where("npm", cargo_tests), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_search_with_context_where_filters_before_ranking() {
+        use super::super::CommandContext;
+
+        let registry = tagged_registry();
+        let root = std::path::Path::new(".");
+        let context = CommandContext::new(root, root);
+        let is_test = |c: &Command| c.tags.iter().any(|t| t == "test");
+
+        let results = registry.search_with_context_where("test", &context, is_test);
+        assert_eq!(names(&registry, results), vec!["npm test", "cargo test"]);
+
+        let results = registry.search_with_context_where("", &context, is_test);
+        assert_eq!(results.len(), 2);
+    }
 }
@@ -0,0 +1,169 @@
+//! Result cache for idempotent commands.
+//!
+//! Stores the stdout of successful runs keyed by the command line, working
+//! directory, and modification times of the files that affect the result,
+//! so repeating a slow query like `mvn dependency:tree` returns instantly.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Tag marking a command as safe to cache.
+pub const CACHEABLE_TAG: &str = "cacheable";
+
+/// A stored command result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResult {
+    /// Captured standard output
+    pub stdout: String,
+
+    /// When the result was stored (seconds since the Unix epoch)
+    pub created_at: u64,
+}
+
+/// On-disk cache of command results.
+#[derive(Debug)]
+pub struct ResultCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl ResultCache {
+    /// Open the cache in the user cache directory.
+    pub fn new(ttl: Duration) -> anyhow::Result<Self> {
+        let dir =
+            dirs::cache_dir().unwrap_or_else(|| PathBuf::from(".")).join("palrun").join("results");
+
+        Self::with_dir(dir, ttl)
+    }
+
+    /// Open a cache in a custom directory.
+    pub fn with_dir(dir: PathBuf, ttl: Duration) -> anyhow::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir, ttl })
+    }
+
+    /// Build the cache key for a run.
+    ///
+    /// Any change to the command line, the directory, or a watched file's
+    /// modification time (including it appearing or disappearing) produces
+    /// a different key, so stale results are never returned.
+    pub fn key(command_line: &str, working_dir: &Path, watched: &[PathBuf]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(command_line.as_bytes());
+        hasher.update([0]);
+        hasher.update(working_dir.to_string_lossy().as_bytes());
+
+        for file in watched {
+            hasher.update([0]);
+            hasher.update(file.to_string_lossy().as_bytes());
+            let modified = fs::metadata(file)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok());
+            match modified {
+                Some(modified) => hasher.update(modified.as_nanos().to_le_bytes()),
+                None => hasher.update(b"missing"),
+            }
+        }
+
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Look up a result that is younger than the TTL.
+    pub fn get(&self, key: &str) -> Option<CachedResult> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        let result: CachedResult = serde_json::from_str(&content).ok()?;
+
+        let age = now_secs().saturating_sub(result.created_at);
+        (age < self.ttl.as_secs()).then_some(result)
+    }
+
+    /// Store the stdout of a successful run.
+    pub fn put(&self, key: &str, stdout: &str) -> anyhow::Result<()> {
+        let result = CachedResult { stdout: stdout.to_string(), created_at: now_secs() };
+        fs::write(self.path(key), serde_json::to_string(&result)?)?;
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(ttl: Duration) -> (tempfile::TempDir, ResultCache) {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = ResultCache::with_dir(temp.path().join("results"), ttl).unwrap();
+        (temp, cache)
+    }
+
+    #[test]
+    fn test_hit_and_miss() {
+        let (temp, cache) = cache(Duration::from_secs(60));
+        let key = ResultCache::key("mvn dependency:tree", temp.path(), &[]);
+
+        assert!(cache.get(&key).is_none());
+        cache.put(&key, "[INFO] com.example:app:jar:1.0\n").unwrap();
+        assert_eq!(cache.get(&key).unwrap().stdout, "[INFO] com.example:app:jar:1.0\n");
+
+        let other = ResultCache::key("mvn dependency:tree -Dverbose", temp.path(), &[]);
+        assert!(cache.get(&other).is_none());
+        let elsewhere = ResultCache::key("mvn dependency:tree", &temp.path().join("sub"), &[]);
+        assert!(cache.get(&elsewhere).is_none());
+    }
+
+    #[test]
+    fn test_expired_results_miss() {
+        let (temp, cache) = cache(Duration::ZERO);
+        let key = ResultCache::key("mvn dependency:tree", temp.path(), &[]);
+
+        cache.put(&key, "tree").unwrap();
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[test]
+    fn test_file_change_invalidates() {
+        let (temp, cache) = cache(Duration::from_secs(60));
+        let pom = temp.path().join("pom.xml");
+        let watched = vec![pom.clone()];
+
+        let missing = ResultCache::key("mvn dependency:tree", temp.path(), &watched);
+        fs::write(&pom, "<project/>").unwrap();
+        let set_mtime = |secs: u64| {
+            fs::File::options()
+                .write(true)
+                .open(&pom)
+                .unwrap()
+                .set_modified(UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+
+        set_mtime(1_000);
+        let key = ResultCache::key("mvn dependency:tree", temp.path(), &watched);
+        assert_ne!(key, missing);
+        cache.put(&key, "tree v1").unwrap();
+        assert_eq!(
+            cache
+                .get(&ResultCache::key("mvn dependency:tree", temp.path(), &watched))
+                .unwrap()
+                .stdout,
+            "tree v1"
+        );
+
+        set_mtime(2_000);
+        let changed = ResultCache::key("mvn dependency:tree", temp.path(), &watched);
+        assert_ne!(changed, key);
+        assert!(cache.get(&changed).is_none());
+    }
+}
@@ -0,0 +1,175 @@
+//! Choosing between several commands that match a name.
+//!
+//! `pal exec` resolves its argument with a fuzzy search, which can match
+//! more than one command. Rather than run whichever ranks first, the user
+//! picks one, or the run fails when there is nobody to ask.
+
+use std::io::{self, BufRead, Write};
+
+use super::Command;
+
+/// Outcome of [`choose_match`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchChoice {
+    /// Run the command at this index into the matches
+    Selected(usize),
+
+    /// Several commands match and there is nobody to ask which one
+    Ambiguous,
+
+    /// The user declined to pick one
+    Cancelled,
+}
+
+/// Narrow search matches to those named exactly `pattern`, if any are.
+///
+/// `pal exec build` should run `build`, not ask about `build:prod` and
+/// `rebuild` as well; it still asks if two sources both define `build`.
+pub fn exact_matches<'a>(pattern: &str, matches: Vec<&'a Command>) -> Vec<&'a Command> {
+    if matches.iter().any(|cmd| cmd.name == pattern) {
+        matches.into_iter().filter(|cmd| cmd.name == pattern).collect()
+    } else {
+        matches
+    }
+}
+
+/// Format matches as a numbered list, one per line.
+pub fn format_matches(matches: &[&Command]) -> String {
+    matches
+        .iter()
+        .enumerate()
+        .map(|(i, cmd)| {
+            format!("  {}. {} ({}: {})", i + 1, cmd.name, cmd.source_type(), cmd.command)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Choose which of `matches` to run.
+///
+/// A single match, or `first`, picks the best-ranked match without asking.
+/// Otherwise the matches are listed on `output` and a number is read from
+/// `input`, asking again until the answer is valid; an empty answer or end
+/// of input cancels. When not `interactive`, several matches are
+/// [`MatchChoice::Ambiguous`].
+///
+/// `matches` must not be empty.
+pub fn choose_match(
+    matches: &[&Command],
+    first: bool,
+    interactive: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<MatchChoice> {
+    if matches.len() <= 1 || first {
+        return Ok(MatchChoice::Selected(0));
+    }
+    if !interactive {
+        return Ok(MatchChoice::Ambiguous);
+    }
+
+    writeln!(output, "{} commands match:\n{}\n", matches.len(), format_matches(matches))?;
+    loop {
+        write!(output, "Select a command [1-{}]: ", matches.len())?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(MatchChoice::Cancelled);
+        }
+
+        match line.trim().parse::<usize>() {
+            Ok(n) if (1..=matches.len()).contains(&n) => return Ok(MatchChoice::Selected(n - 1)),
+            _ => writeln!(output, "Enter a number from 1 to {}", matches.len())?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands() -> Vec<Command> {
+        vec![
+            Command::new("build", "npm run build"),
+            Command::new("build:prod", "npm run build:prod"),
+            Command::new("rebuild", "make rebuild"),
+        ]
+    }
+
+    fn choose(matches: &[&Command], first: bool, interactive: bool, answer: &str) -> MatchChoice {
+        let mut output = Vec::new();
+        choose_match(matches, first, interactive, &mut answer.as_bytes(), &mut output).unwrap()
+    }
+
+    #[test]
+    fn test_single_match_runs_without_asking() {
+        let commands = commands();
+        let matches = vec![&commands[0]];
+
+        // The prompt is never read, so an answer that would cancel is ignored
+        assert_eq!(choose(&matches, false, true, ""), MatchChoice::Selected(0));
+        assert_eq!(choose(&matches, false, false, ""), MatchChoice::Selected(0));
+    }
+
+    #[test]
+    fn test_first_picks_best_match() {
+        let commands = commands();
+        let matches: Vec<_> = commands.iter().collect();
+        assert_eq!(choose(&matches, true, false, ""), MatchChoice::Selected(0));
+        assert_eq!(choose(&matches, true, true, "3\n"), MatchChoice::Selected(0));
+    }
+
+    #[test]
+    fn test_several_matches_non_interactive_are_ambiguous() {
+        let commands = commands();
+        let matches: Vec<_> = commands.iter().collect();
+        assert_eq!(choose(&matches, false, false, "2\n"), MatchChoice::Ambiguous);
+    }
+
+    #[test]
+    fn test_several_matches_prompt() {
+        let commands = commands();
+        let matches: Vec<_> = commands.iter().collect();
+
+        let mut output = Vec::new();
+        let choice = choose_match(&matches, false, true, &mut &b"2\n"[..], &mut output);
+        assert_eq!(choice.unwrap(), MatchChoice::Selected(1));
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("  1. build (manual: npm run build)"));
+        assert!(output.contains("  3. rebuild (manual: make rebuild)"));
+        assert!(output.contains("Select a command [1-3]"));
+    }
+
+    #[test]
+    fn test_prompt_retries_invalid_answers() {
+        let commands = commands();
+        let matches: Vec<_> = commands.iter().collect();
+        assert_eq!(choose(&matches, false, true, "0\nx\n4\n3\n"), MatchChoice::Selected(2));
+    }
+
+    #[test]
+    fn test_prompt_cancel() {
+        let commands = commands();
+        let matches: Vec<_> = commands.iter().collect();
+        assert_eq!(choose(&matches, false, true, "\n"), MatchChoice::Cancelled);
+        assert_eq!(choose(&matches, false, true, ""), MatchChoice::Cancelled);
+        assert_eq!(choose(&matches, false, true, "9\n"), MatchChoice::Cancelled);
+    }
+
+    #[test]
+    fn test_exact_matches() {
+        let commands = commands();
+        let matches: Vec<_> = commands.iter().collect();
+
+        let exact = exact_matches("build", matches.clone());
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].name, "build");
+
+        assert_eq!(exact_matches("bld", matches).len(), 3);
+
+        let twice = [Command::new("build", "npm run build"), Command::new("build", "make build")];
+        assert_eq!(exact_matches("build", twice.iter().collect()).len(), 2);
+    }
+}
@@ -0,0 +1,165 @@
+//! Display metadata for command sources.
+//!
+//! Built-in sources carry their name and icons on [`CommandSource`] itself.
+//! Plugin sources can't, so plugins register theirs here when they are
+//! loaded, keyed by plugin name.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::config::IconPreset;
+use super::CommandSource;
+
+/// Display metadata for a source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceInfo {
+    /// Key the source is registered under ([`CommandSource::key`])
+    pub key: String,
+
+    /// Display name
+    pub name: String,
+
+    /// Emoji icon
+    pub icon: String,
+
+    /// Plain ASCII icon
+    pub ascii_icon: String,
+}
+
+impl SourceInfo {
+    /// Metadata for a plugin that declared none of its own.
+    pub fn plugin(name: impl Into<String>) -> Self {
+        let name = name.into();
+        let source = CommandSource::Plugin { name: name.clone() };
+        Self {
+            key: name.clone(),
+            name,
+            icon: source.icon().to_string(),
+            ascii_icon: source.ascii_icon().to_string(),
+        }
+    }
+}
+
+/// Source metadata registered at runtime, by source key.
+#[derive(Debug, Clone, Default)]
+pub struct SourceRegistry {
+    sources: BTreeMap<String, SourceInfo>,
+}
+
+impl SourceRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a source, replacing any earlier entry with the same key.
+    pub fn register(&mut self, info: SourceInfo) {
+        self.sources.insert(info.key.clone(), info);
+    }
+
+    /// Get the metadata registered under a key.
+    pub fn get(&self, key: &str) -> Option<&SourceInfo> {
+        self.sources.get(key)
+    }
+
+    /// Get the registered metadata for a command source.
+    ///
+    /// Only plugin sources are looked up; built-in sources always use
+    /// their own metadata.
+    pub fn lookup(&self, source: &CommandSource) -> Option<&SourceInfo> {
+        match source {
+            CommandSource::Plugin { name } => self.get(name),
+            _ => None,
+        }
+    }
+
+    /// Get the display name for a command source.
+    pub fn name<'a>(&'a self, source: &'a CommandSource) -> &'a str {
+        self.lookup(source).map_or_else(|| source.key(), |info| info.name.as_str())
+    }
+
+    /// Get the icon for a command source from an icon set.
+    pub fn icon<'a>(&'a self, source: &CommandSource, preset: IconPreset) -> &'a str {
+        match (self.lookup(source), preset) {
+            (Some(info), IconPreset::Emoji) => &info.icon,
+            (Some(info), IconPreset::Ascii) => &info.ascii_icon,
+            (None, IconPreset::Emoji) => source.icon(),
+            (None, IconPreset::Ascii) => source.ascii_icon(),
+        }
+    }
+
+    /// Iterate over the registered sources, in key order.
+    pub fn iter(&self) -> impl Iterator<Item = &SourceInfo> {
+        self.sources.values()
+    }
+
+    /// Get the number of registered sources.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Check if no sources are registered.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn gradle() -> SourceInfo {
+        SourceInfo {
+            key: "gradle-scanner".to_string(),
+            name: "gradle".to_string(),
+            icon: "🐘".to_string(),
+            ascii_icon: "gr".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_registered_plugin_source() {
+        let mut registry = SourceRegistry::new();
+        registry.register(gradle());
+
+        let source = CommandSource::Plugin { name: "gradle-scanner".to_string() };
+        assert_eq!(registry.icon(&source, IconPreset::Emoji), "🐘");
+        assert_eq!(registry.icon(&source, IconPreset::Ascii), "gr");
+        assert_eq!(registry.name(&source), "gradle");
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_unregistered_sources_fall_back() {
+        let registry = SourceRegistry::new();
+
+        let plugin = CommandSource::Plugin { name: "other".to_string() };
+        assert_eq!(registry.icon(&plugin, IconPreset::Emoji), "🧰");
+        assert_eq!(registry.name(&plugin), "other");
+
+        let cargo = CommandSource::Cargo(PathBuf::from("."));
+        assert_eq!(registry.icon(&cargo, IconPreset::Emoji), "🦀");
+        assert_eq!(registry.icon(&cargo, IconPreset::Ascii), "rs");
+        assert_eq!(registry.name(&cargo), "cargo");
+    }
+
+    #[test]
+    fn test_builtin_sources_are_not_overridden() {
+        let mut registry = SourceRegistry::new();
+        registry.register(SourceInfo { key: "cargo".to_string(), ..gradle() });
+
+        let cargo = CommandSource::Cargo(PathBuf::from("."));
+        assert_eq!(registry.icon(&cargo, IconPreset::Emoji), "🦀");
+    }
+
+    #[test]
+    fn test_plugin_defaults() {
+        let info = SourceInfo::plugin("hello");
+        assert_eq!(info.key, "hello");
+        assert_eq!(info.name, "hello");
+        assert_eq!(info.icon, "🧰");
+        assert_eq!(info.ascii_icon, "pl");
+    }
+}
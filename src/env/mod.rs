@@ -7,9 +7,13 @@ pub mod secrets;
 pub mod version;
 
 pub use secrets::{
-    ProviderStatus, ResolvedSecret, SecretProvider, SecretReference, SecretsManager,
+    AwsSecretRef, GcpSecretRef, ProviderStatus, ResolvedSecret, SecretCommand, SecretProvider,
+    SecretReference, SecretsManager,
+};
+pub use version::{
+    parse_engines, satisfies_range, switchable_version, EngineCheck, ManagerCommand, RuntimeType,
+    RuntimeVersion, VersionManager, VersionManagerKind,
 };
-pub use version::{RuntimeType, RuntimeVersion, VersionManager};
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -17,6 +21,8 @@ use std::{env, fs};
 
 use anyhow::{Context, Result};
 
+use crate::core::write_file_atomic;
+
 /// Known .env file patterns to scan for.
 pub const ENV_FILE_PATTERNS: &[&str] = &[
     ".env",
@@ -47,6 +53,7 @@ const SENSITIVE_PATTERNS: &[&str] = &[
     "API_KEY",
     "ACCESS_KEY",
     "CLIENT_SECRET",
+    "DATABASE_URL",
 ];
 
 /// Information about a detected .env file.
@@ -66,6 +73,9 @@ pub struct EnvFile {
 
     /// Whether this file is currently active (loaded)
     pub is_active: bool,
+
+    /// How many directories above the project root the file lives (0 = root)
+    pub depth: usize,
 }
 
 impl EnvFile {
@@ -97,6 +107,15 @@ impl EnvFile {
             _ => "📄",
         }
     }
+
+    /// Location of the file's directory relative to the project root.
+    pub fn location(&self) -> String {
+        if self.depth == 0 {
+            ".".to_string()
+        } else {
+            vec![".."; self.depth].join("/")
+        }
+    }
 }
 
 /// An environment variable with metadata.
@@ -190,7 +209,7 @@ impl EnvManager {
         for pattern in ENV_FILE_PATTERNS {
             let path = self.root.join(pattern);
             if path.exists() && path.is_file() {
-                if let Ok(env_file) = self.parse_env_file(&path) {
+                if let Ok(env_file) = self.parse_env_file(&path, 0) {
                     self.env_files.push(env_file);
                 }
             }
@@ -202,8 +221,52 @@ impl EnvManager {
         Ok(&self.env_files)
     }
 
+    /// Scan the project root and up to `max_levels` parent directories.
+    ///
+    /// The walk stops after the directory containing `.git` (the
+    /// repository root) or at the filesystem root. Files are ordered by
+    /// depth, project root first, then by name.
+    pub fn scan_ancestors(&mut self, max_levels: usize) -> Result<&[EnvFile]> {
+        self.scan()?;
+
+        let mut dir = self.root.clone();
+        for depth in 1..=max_levels {
+            if dir.join(".git").exists() {
+                break;
+            }
+            let Some(parent) = dir.parent() else {
+                break;
+            };
+            dir = parent.to_path_buf();
+
+            let mut found: Vec<EnvFile> = ENV_FILE_PATTERNS
+                .iter()
+                .map(|pattern| dir.join(pattern))
+                .filter(|path| path.is_file())
+                .filter_map(|path| self.parse_env_file(&path, depth).ok())
+                .collect();
+            found.sort_by(|a, b| a.name.cmp(&b.name));
+            self.env_files.extend(found);
+        }
+
+        Ok(&self.env_files)
+    }
+
+    /// Load several .env files as layers, later files overriding earlier ones.
+    ///
+    /// Pass the farthest file first so files closer to the project win.
+    pub fn load_layered(&self, files: &[PathBuf]) -> Result<HashMap<String, String>> {
+        let mut merged = HashMap::new();
+        for path in files {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            merged.extend(Self::parse_entries(&content));
+        }
+        Ok(merged)
+    }
+
     /// Parse a .env file and return metadata.
-    fn parse_env_file(&self, path: &Path) -> Result<EnvFile> {
+    fn parse_env_file(&self, path: &Path, depth: usize) -> Result<EnvFile> {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
 
@@ -223,7 +286,14 @@ impl EnvManager {
 
         let is_active = self.active_file.as_ref() == Some(&path.to_path_buf());
 
-        Ok(EnvFile { name, path: path.to_path_buf(), variable_count, is_template, is_active })
+        Ok(EnvFile {
+            name,
+            path: path.to_path_buf(),
+            variable_count,
+            is_template,
+            is_active,
+            depth,
+        })
     }
 
     /// Get all detected .env files.
@@ -238,21 +308,7 @@ impl EnvManager {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
 
-        for line in content.lines() {
-            let trimmed = line.trim();
-
-            // Skip empty lines and comments
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                continue;
-            }
-
-            // Parse KEY=VALUE
-            if let Some((key, value)) = trimmed.split_once('=') {
-                let key = key.trim().to_string();
-                let value = Self::parse_value(value.trim());
-                self.loaded_vars.insert(key, value);
-            }
-        }
+        self.loaded_vars.extend(Self::parse_entries(&content));
 
         self.active_file = Some(path.to_path_buf());
 
@@ -264,14 +320,43 @@ impl EnvManager {
         Ok(self.loaded_vars.len())
     }
 
+    /// Parse the `KEY=VALUE` lines of a .env file, in order.
+    ///
+    /// `${VAR}` and `$VAR` in unquoted and double-quoted values expand to
+    /// a key defined earlier in the file, or else to the process
+    /// environment. Single-quoted values are taken literally.
+    fn parse_entries(content: &str) -> Vec<(String, String)> {
+        let mut entries: Vec<(String, String)> = Vec::new();
+        let mut defined: HashMap<String, String> = HashMap::new();
+
+        for (key, raw) in Self::split_lines(content) {
+            let key = key.to_string();
+            let value = Self::parse_value(raw);
+            let value = if is_quoted(raw, '\'') { value } else { expand(&value, &defined) };
+            defined.insert(key.clone(), value.clone());
+            entries.push((key, value));
+        }
+
+        entries
+    }
+
+    /// Split the `KEY=VALUE` lines of a .env file into trimmed key and raw
+    /// value, skipping blank lines and comments.
+    fn split_lines(content: &str) -> impl Iterator<Item = (&str, &str)> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+    }
+
     /// Parse an environment variable value, handling quotes.
     fn parse_value(value: &str) -> String {
         let value = value.trim();
 
         // Handle quoted strings
-        if (value.starts_with('"') && value.ends_with('"'))
-            || (value.starts_with('\'') && value.ends_with('\''))
-        {
+        if is_quoted(value, '"') || is_quoted(value, '\'') {
             value[1..value.len() - 1].to_string()
         } else {
             // Remove inline comments
@@ -279,6 +364,79 @@ impl EnvManager {
         }
     }
 
+    /// Set a variable in a .env file, creating the file if needed.
+    ///
+    /// An existing definition is replaced in place; otherwise the variable
+    /// is appended. Every other line is kept byte for byte.
+    pub fn set_variable_in_file(&mut self, path: &Path, key: &str, value: &str) -> Result<()> {
+        validate_key(key)?;
+        if value.contains(['\n', '\r']) {
+            anyhow::bail!("Value for {key} must be a single line");
+        }
+
+        let content = if path.exists() {
+            fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?
+        } else {
+            String::new()
+        };
+
+        let assignment = format!("{key}={}", quote_value(value));
+        let mut output = String::with_capacity(content.len() + assignment.len() + 1);
+        let mut replaced = false;
+
+        for line in content.split_inclusive('\n') {
+            if !replaced && defines_key(line, key) {
+                let ending = &line[line.trim_end_matches(['\n', '\r']).len()..];
+                output.push_str(&assignment);
+                output.push_str(ending);
+                replaced = true;
+            } else {
+                output.push_str(line);
+            }
+        }
+
+        if !replaced {
+            if !output.is_empty() && !output.ends_with('\n') {
+                output.push('\n');
+            }
+            output.push_str(&assignment);
+            output.push('\n');
+        }
+
+        write_file_atomic(path, &output)?;
+
+        if self.active_file.as_deref() == Some(path) {
+            self.loaded_vars.insert(key.to_string(), value.to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Remove a variable from a .env file.
+    ///
+    /// Returns whether the file defined the variable.
+    pub fn remove_variable_from_file(&mut self, path: &Path, key: &str) -> Result<bool> {
+        validate_key(key)?;
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let output: String =
+            content.split_inclusive('\n').filter(|line| !defines_key(line, key)).collect();
+        if output.len() == content.len() {
+            return Ok(false);
+        }
+
+        write_file_atomic(path, &output)?;
+
+        if self.active_file.as_deref() == Some(path) {
+            self.loaded_vars.remove(key);
+        }
+
+        Ok(true)
+    }
+
     /// Apply loaded environment variables to the current process.
     pub fn apply_to_process(&self) {
         for (key, value) in &self.loaded_vars {
@@ -343,26 +501,33 @@ impl EnvManager {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
 
-        let mut variables = Vec::new();
+        let variables = Self::parse_entries(&content)
+            .into_iter()
+            .map(|(name, value)| EnvVariable {
+                is_sensitive: Self::is_sensitive_var(&name),
+                name,
+                value,
+                source: EnvSource::DotEnv(path.to_path_buf()),
+            })
+            .collect();
 
-        for line in content.lines() {
-            let trimmed = line.trim();
+        Ok(variables)
+    }
 
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                continue;
-            }
+    /// Get variables from a .env file as written, without expanding
+    /// `$VAR` references.
+    pub fn preview_env_file_raw(&self, path: &Path) -> Result<Vec<EnvVariable>> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
 
-            if let Some((key, value)) = trimmed.split_once('=') {
-                let name = key.trim().to_string();
-                let value = Self::parse_value(value.trim());
-                variables.push(EnvVariable {
-                    name: name.clone(),
-                    value,
-                    source: EnvSource::DotEnv(path.to_path_buf()),
-                    is_sensitive: Self::is_sensitive_var(&name),
-                });
-            }
-        }
+        let variables = Self::split_lines(&content)
+            .map(|(name, raw)| EnvVariable {
+                is_sensitive: Self::is_sensitive_var(name),
+                name: name.to_string(),
+                value: Self::parse_value(raw),
+                source: EnvSource::DotEnv(path.to_path_buf()),
+            })
+            .collect();
 
         Ok(variables)
     }
@@ -399,6 +564,84 @@ impl EnvManager {
     }
 }
 
+/// Check whether a value is wrapped in a pair of `quote` characters.
+fn is_quoted(value: &str, quote: char) -> bool {
+    value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote)
+}
+
+/// Check that a variable name is a valid identifier.
+fn validate_key(key: &str) -> Result<()> {
+    let mut chars = key.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        anyhow::bail!("Invalid variable name '{key}'");
+    }
+    Ok(())
+}
+
+/// Check whether a .env line assigns `key`.
+fn defines_key(line: &str, key: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.starts_with('#') && trimmed.split_once('=').is_some_and(|(name, _)| name.trim() == key)
+}
+
+/// Format a value for a .env file.
+///
+/// Values with whitespace or special characters are single-quoted, which
+/// reads back literally: no comment stripping and no `$` expansion.
+fn quote_value(value: &str) -> String {
+    let plain = value.chars().all(|c| {
+        c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | ':' | '@' | ',' | '+')
+    });
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{value}'")
+    }
+}
+
+/// Expand `${VAR}` and `$VAR` references in a .env value.
+///
+/// Names resolve against `defined`, then the process environment; an
+/// undefined name expands to nothing, with a warning. `\$` is a literal `$`.
+fn expand(value: &str, defined: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'$') => {
+                out.push('$');
+                chars.next();
+            }
+            '$' => {
+                let name: String = if chars.next_if_eq(&'{').is_some() {
+                    chars.by_ref().take_while(|&ch| ch != '}').collect()
+                } else if chars.peek().is_some_and(|&ch| ch.is_ascii_alphabetic() || ch == '_') {
+                    std::iter::from_fn(|| {
+                        chars.next_if(|&ch| ch.is_ascii_alphanumeric() || ch == '_')
+                    })
+                    .collect()
+                } else {
+                    out.push('$');
+                    continue;
+                };
+
+                if let Some(resolved) = defined.get(&name).cloned().or_else(|| env::var(&name).ok())
+                {
+                    out.push_str(&resolved);
+                } else {
+                    tracing::warn!("Undefined variable '{name}' in .env value");
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    out
+}
+
 /// Difference between two .env files.
 #[derive(Debug)]
 pub struct EnvDiff {
@@ -489,6 +732,122 @@ DEBUG=true  # inline comment
         assert_eq!(debug.value, "true");
     }
 
+    #[test]
+    fn test_env_interpolation() {
+        let temp = TempDir::new().unwrap();
+        let path = create_test_env_file(
+            temp.path(),
+            ".env",
+            r#"
+A=1
+B=${A}2
+C="$B-${A}"
+DB_URL=postgres://${DB_HOST}:${DB_PORT}/app
+DB_HOST=db
+SEARCH_PATH=${PATH}
+"#,
+        );
+
+        let vars: HashMap<_, _> = EnvManager::new(temp.path())
+            .preview_env_file(&path)
+            .unwrap()
+            .into_iter()
+            .map(|v| (v.name, v.value))
+            .collect();
+
+        assert_eq!(vars["B"], "12");
+        assert_eq!(vars["C"], "12-1");
+        // Later keys are not visible yet; undefined references expand to nothing
+        assert_eq!(vars["DB_URL"], "postgres://:/app");
+        assert_eq!(vars["SEARCH_PATH"], env::var("PATH").unwrap_or_default());
+    }
+
+    #[test]
+    fn test_env_interpolation_escaping() {
+        let defined = HashMap::from([("A".to_string(), "1".to_string())]);
+
+        assert_eq!(expand(r"\$A costs \$5", &defined), "$A costs $5");
+        assert_eq!(expand("$ and $1x and ${A}", &defined), "$ and $1x and 1");
+
+        let temp = TempDir::new().unwrap();
+        let path = create_test_env_file(temp.path(), ".env", "A=1\nB='${A}'\nC=\"\\${A}\"\n");
+        let mut manager = EnvManager::new(temp.path());
+        manager.load_env_file(&path).unwrap();
+
+        let vars = manager.get_all_variables();
+        let value = |name: &str| vars.iter().find(|v| v.name == name).unwrap().value.clone();
+        assert_eq!(value("B"), "${A}");
+        assert_eq!(value("C"), "${A}");
+    }
+
+    #[test]
+    fn test_set_variable_preserves_layout() {
+        let temp = TempDir::new().unwrap();
+        let path = create_test_env_file(
+            temp.path(),
+            ".env",
+            "# Database\nDB_HOST=localhost  \nDB_PORT=5432 # default\n\n# App\nAPP_NAME=demo",
+        );
+        let mut manager = EnvManager::new(temp.path());
+
+        manager.set_variable_in_file(&path, "DB_PORT", "6543").unwrap();
+        manager.set_variable_in_file(&path, "GREETING", "hello world #1").unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            content,
+            "# Database\nDB_HOST=localhost  \nDB_PORT=6543\n\n# App\nAPP_NAME=demo\nGREETING='hello world #1'\n"
+        );
+
+        let vars: HashMap<_, _> = manager
+            .preview_env_file(&path)
+            .unwrap()
+            .into_iter()
+            .map(|v| (v.name, v.value))
+            .collect();
+        assert_eq!(vars["GREETING"], "hello world #1");
+
+        manager.set_variable_in_file(&path, "PRICE", "$5").unwrap();
+        let vars = manager.preview_env_file(&path).unwrap();
+        assert_eq!(vars.last().unwrap().value, "$5");
+
+        assert!(manager.set_variable_in_file(&path, "BAD KEY", "x").is_err());
+        assert!(manager.set_variable_in_file(&path, "KEY", "a\nb").is_err());
+    }
+
+    #[test]
+    fn test_remove_variable_preserves_layout() {
+        let temp = TempDir::new().unwrap();
+        let path =
+            create_test_env_file(temp.path(), ".env", "# First\nA=1\n# B is unused\nB=2\nC=3\n");
+        let mut manager = EnvManager::new(temp.path());
+        manager.load_env_file(&path).unwrap();
+
+        assert!(manager.remove_variable_from_file(&path, "B").unwrap());
+        assert!(!manager.remove_variable_from_file(&path, "MISSING").unwrap());
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content, "# First\nA=1\n# B is unused\nC=3\n");
+        assert!(manager.get_all_variables().iter().all(|v| v.name != "B"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_editing_keeps_file_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let path = create_test_env_file(temp.path(), ".env", "API_KEY=secret\nOLD=1\n");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        let mut manager = EnvManager::new(temp.path());
+
+        manager.set_variable_in_file(&path, "API_KEY", "rotated").unwrap();
+        manager.remove_variable_from_file(&path, "OLD").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
     #[test]
     fn test_sensitive_detection() {
         assert!(EnvManager::is_sensitive_var("API_KEY"));
@@ -507,6 +866,7 @@ DEBUG=true  # inline comment
             variable_count: 5,
             is_template: false,
             is_active: false,
+            depth: 0,
         };
         assert_eq!(production.env_type(), "production");
         assert_eq!(production.icon(), "🔴");
@@ -517,6 +877,7 @@ DEBUG=true  # inline comment
             variable_count: 5,
             is_template: false,
             is_active: false,
+            depth: 0,
         };
         assert_eq!(development.env_type(), "development");
         assert_eq!(development.icon(), "🟢");
@@ -527,11 +888,47 @@ DEBUG=true  # inline comment
             variable_count: 5,
             is_template: true,
             is_active: false,
+            depth: 0,
         };
         assert_eq!(example.env_type(), "template");
         assert_eq!(example.icon(), "📋");
     }
 
+    #[test]
+    fn test_scan_ancestors() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let package = repo.join("packages").join("web");
+        fs::create_dir_all(&package).unwrap();
+        fs::create_dir(repo.join(".git")).unwrap();
+
+        // Above the repository root, so never reached
+        create_test_env_file(temp.path(), ".env", "OUTSIDE=1\n");
+        let shared = create_test_env_file(&repo, ".env", "DB_HOST=shared\nLOG=info\n");
+        create_test_env_file(&repo, ".env.production", "LOG=warn\n");
+        let local = create_test_env_file(&package, ".env", "LOG=debug\nPORT=3000\n");
+
+        let mut manager = EnvManager::new(&package);
+        let files = manager.scan_ancestors(10).unwrap();
+        let found: Vec<_> = files.iter().map(|f| (f.name.as_str(), f.location())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (".env", ".".to_string()),
+                (".env", "../..".to_string()),
+                (".env.production", "../..".to_string()),
+            ]
+        );
+
+        // A level limit stops before the repository root
+        assert_eq!(manager.scan_ancestors(1).unwrap().len(), 1);
+
+        let merged = manager.load_layered(&[shared, local]).unwrap();
+        assert_eq!(merged["DB_HOST"], "shared");
+        assert_eq!(merged["LOG"], "debug");
+        assert_eq!(merged["PORT"], "3000");
+    }
+
     #[test]
     fn test_compare_env_files() {
         let temp = TempDir::new().unwrap();
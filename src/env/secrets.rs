@@ -1,7 +1,7 @@
 //! Secrets management module.
 //!
 //! Provides integration with secret managers like 1Password, HashiCorp Vault,
-//! and custom providers to securely inject secrets into environment variables.
+//! AWS Secrets Manager, GCP Secret Manager, and custom providers to securely inject secrets into environment variables.
 
 use std::collections::HashMap;
 use std::fs;
@@ -17,6 +17,10 @@ pub enum SecretProvider {
     OnePassword,
     /// HashiCorp Vault
     Vault,
+    /// AWS Secrets Manager (aws CLI)
+    AwsSecretsManager,
+    /// Google Cloud Secret Manager (gcloud CLI)
+    GcpSecretManager,
     /// Custom command-based provider
     Custom(String),
 }
@@ -27,15 +31,30 @@ impl SecretProvider {
         match self {
             SecretProvider::OnePassword => "1Password",
             SecretProvider::Vault => "HashiCorp Vault",
+            SecretProvider::AwsSecretsManager => "AWS Secrets Manager",
+            SecretProvider::GcpSecretManager => "GCP Secret Manager",
             SecretProvider::Custom(_) => "Custom",
         }
     }
 
+    /// Look up a provider by the name used on the command line.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "1password" | "op" => Some(SecretProvider::OnePassword),
+            "vault" => Some(SecretProvider::Vault),
+            "aws" | "aws-sm" => Some(SecretProvider::AwsSecretsManager),
+            "gcp" | "gcp-sm" => Some(SecretProvider::GcpSecretManager),
+            _ => None,
+        }
+    }
+
     /// Get an icon for the provider.
     pub fn icon(&self) -> &str {
         match self {
             SecretProvider::OnePassword => "🔐",
             SecretProvider::Vault => "🗄️",
+            SecretProvider::AwsSecretsManager => "🟧",
+            SecretProvider::GcpSecretManager => "🟦",
             SecretProvider::Custom(_) => "🔧",
         }
     }
@@ -62,28 +81,111 @@ impl SecretReference {
     pub fn parse(variable: &str, reference: &str, source: &Path) -> Option<Self> {
         let reference = reference.trim();
 
-        // 1Password: op://vault/item/field
-        if reference.starts_with("op://") {
-            return Some(Self {
-                variable: variable.to_string(),
-                reference: reference.to_string(),
-                provider: SecretProvider::OnePassword,
-                source: source.to_path_buf(),
-            });
+        let provider = if reference.starts_with("op://") {
+            // 1Password: op://vault/item/field
+            SecretProvider::OnePassword
+        } else if reference.starts_with("vault://") {
+            // Vault: vault://path/to/secret#field
+            SecretProvider::Vault
+        } else if AwsSecretRef::parse(reference).is_some() {
+            // AWS: aws-sm://region/secret-name#json-key
+            SecretProvider::AwsSecretsManager
+        } else if GcpSecretRef::parse(reference).is_some() {
+            // GCP: gcp-sm://project/secret/version#json-key
+            SecretProvider::GcpSecretManager
+        } else {
+            // Custom: ${secret:key} or similar patterns could be added
+            return None;
+        };
+
+        Some(Self {
+            variable: variable.to_string(),
+            reference: reference.to_string(),
+            provider,
+            source: source.to_path_buf(),
+        })
+    }
+}
+
+/// A parsed `aws-sm://region/secret-name#json-key` reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AwsSecretRef<'a> {
+    /// AWS region, e.g. `us-east-1`
+    pub region: &'a str,
+
+    /// Secret name or ARN suffix; may contain `/`
+    pub secret_id: &'a str,
+
+    /// Field to select from a JSON secret
+    pub key: Option<&'a str>,
+}
+
+impl<'a> AwsSecretRef<'a> {
+    /// Parse an AWS Secrets Manager reference.
+    pub fn parse(reference: &'a str) -> Option<Self> {
+        let rest = reference.strip_prefix("aws-sm://")?;
+        let (path, key) = split_key(rest);
+        let (region, secret_id) = path.split_once('/')?;
+        if region.is_empty() || secret_id.is_empty() {
+            return None;
         }
+        Some(Self { region, secret_id, key })
+    }
+}
 
-        // Vault: vault://path/to/secret#field
-        if reference.starts_with("vault://") {
-            return Some(Self {
-                variable: variable.to_string(),
-                reference: reference.to_string(),
-                provider: SecretProvider::Vault,
-                source: source.to_path_buf(),
-            });
+/// A parsed `gcp-sm://project/secret/version#json-key` reference.
+///
+/// The version may be omitted and defaults to `latest`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcpSecretRef<'a> {
+    /// Google Cloud project ID
+    pub project: &'a str,
+
+    /// Secret name
+    pub secret: &'a str,
+
+    /// Secret version number or alias
+    pub version: &'a str,
+
+    /// Field to select from a JSON secret
+    pub key: Option<&'a str>,
+}
+
+impl<'a> GcpSecretRef<'a> {
+    /// Parse a GCP Secret Manager reference.
+    pub fn parse(reference: &'a str) -> Option<Self> {
+        let rest = reference.strip_prefix("gcp-sm://")?;
+        let (path, key) = split_key(rest);
+        let mut parts = path.split('/');
+        let project = parts.next().filter(|p| !p.is_empty())?;
+        let secret = parts.next().filter(|s| !s.is_empty())?;
+        let version = parts.next().unwrap_or("latest");
+        if version.is_empty() || parts.next().is_some() {
+            return None;
         }
+        Some(Self { project, secret, version, key })
+    }
+}
 
-        // Custom: ${secret:key} or similar patterns could be added
-        None
+/// Split a `path#key` reference into the path and the optional key.
+fn split_key(reference: &str) -> (&str, Option<&str>) {
+    match reference.rsplit_once('#') {
+        Some((path, key)) if !key.is_empty() => (path, Some(key)),
+        Some((path, _)) => (path, None),
+        None => (reference, None),
+    }
+}
+
+/// Select a top-level field from a JSON secret.
+///
+/// String fields are returned as-is; other values as JSON text.
+fn select_json_field(secret: &str, key: &str) -> Result<String> {
+    let value: serde_json::Value =
+        serde_json::from_str(secret).context("Secret is not JSON, so '#key' cannot be used")?;
+    match value.get(key) {
+        Some(serde_json::Value::String(s)) => Ok(s.clone()),
+        Some(other) => Ok(other.to_string()),
+        None => anyhow::bail!("Secret has no field '{key}'"),
     }
 }
 
@@ -100,6 +202,48 @@ pub struct ResolvedSecret {
     pub provider: SecretProvider,
 }
 
+#[cfg(feature = "secrets")]
+impl Drop for ResolvedSecret {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.value);
+    }
+}
+
+/// A copy of a command carrying resolved secrets in its own environment.
+///
+/// Only the child started from it sees the values; the process environment
+/// is not modified, unlike [`SecretsManager::inject_secrets`]. The copied
+/// values are zeroed when it is dropped.
+pub struct SecretCommand(crate::core::Command);
+
+impl SecretCommand {
+    /// Copy `command` and add the secrets to its environment.
+    pub fn new(command: &crate::core::Command, secrets: &[ResolvedSecret]) -> Self {
+        let mut command = command.clone();
+        for secret in secrets {
+            command.env.push((secret.variable.clone(), secret.value.clone()));
+        }
+        Self(command)
+    }
+}
+
+impl std::ops::Deref for SecretCommand {
+    type Target = crate::core::Command;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "secrets")]
+impl Drop for SecretCommand {
+    fn drop(&mut self) {
+        for (_, value) in &mut self.0.env {
+            zeroize::Zeroize::zeroize(value);
+        }
+    }
+}
+
 /// Status of a secret provider.
 #[derive(Debug, Clone)]
 pub struct ProviderStatus {
@@ -151,9 +295,74 @@ impl SecretsManager {
         // Check Vault
         self.providers.insert("vault".to_string(), Self::check_vault());
 
+        // Check AWS Secrets Manager
+        self.providers.insert(
+            "aws".to_string(),
+            Self::check_cli(
+                SecretProvider::AwsSecretsManager,
+                ("aws", &["--version"]),
+                &["sts", "get-caller-identity"],
+                "AWS CLI (aws) not found in PATH",
+                "Not authenticated. Run 'aws configure' or 'aws sso login' first.",
+            ),
+        );
+
+        // Check GCP Secret Manager
+        self.providers.insert(
+            "gcp".to_string(),
+            Self::check_cli(
+                SecretProvider::GcpSecretManager,
+                ("gcloud", &["--version"]),
+                &["auth", "print-access-token", "--quiet"],
+                "Google Cloud CLI (gcloud) not found in PATH",
+                "Not authenticated. Run 'gcloud auth login' first.",
+            ),
+        );
+
         &self.providers
     }
 
+    /// Check a provider CLI: installed if `version_args` succeeds,
+    /// authenticated if `auth_args` succeeds.
+    fn check_cli(
+        provider: SecretProvider,
+        (program, version_args): (&str, &[&str]),
+        auth_args: &[&str],
+        missing: &str,
+        unauthenticated: &str,
+    ) -> ProviderStatus {
+        match Command::new(program).args(version_args).output() {
+            Ok(output) if output.status.success() => {
+                let version = String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                let authenticated = Command::new(program)
+                    .args(auth_args)
+                    .output()
+                    .map(|o| o.status.success())
+                    .unwrap_or(false);
+
+                ProviderStatus {
+                    provider,
+                    installed: true,
+                    authenticated,
+                    version: Some(version),
+                    error: (!authenticated).then(|| unauthenticated.to_string()),
+                }
+            }
+            _ => ProviderStatus {
+                provider,
+                installed: false,
+                authenticated: false,
+                version: None,
+                error: Some(missing.to_string()),
+            },
+        }
+    }
+
     /// Check if 1Password CLI is available.
     fn check_onepassword() -> ProviderStatus {
         let output = Command::new("op").args(["--version"]).output();
@@ -359,11 +568,77 @@ impl SecretsManager {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
+    /// Resolve an AWS Secrets Manager reference.
+    pub fn resolve_aws(&self, reference: &str) -> Result<String> {
+        let parsed = AwsSecretRef::parse(reference)
+            .ok_or_else(|| anyhow::anyhow!("Invalid AWS Secrets Manager reference"))?;
+
+        // aws secretsmanager get-secret-value --region <r> --secret-id <id>
+        let output = Command::new("aws")
+            .args([
+                "secretsmanager",
+                "get-secret-value",
+                "--region",
+                parsed.region,
+                "--secret-id",
+                parsed.secret_id,
+                "--query",
+                "SecretString",
+                "--output",
+                "text",
+            ])
+            .output()
+            .context("Failed to execute AWS CLI")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("AWS Secrets Manager error: {}", stderr.trim());
+        }
+
+        let secret = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        match parsed.key {
+            Some(key) => select_json_field(&secret, key),
+            None => Ok(secret),
+        }
+    }
+
+    /// Resolve a GCP Secret Manager reference.
+    pub fn resolve_gcp(&self, reference: &str) -> Result<String> {
+        let parsed = GcpSecretRef::parse(reference)
+            .ok_or_else(|| anyhow::anyhow!("Invalid GCP Secret Manager reference"))?;
+
+        // gcloud secrets versions access <version> --secret=<s> --project=<p>
+        let output = Command::new("gcloud")
+            .args([
+                "secrets",
+                "versions",
+                "access",
+                parsed.version,
+                &format!("--secret={}", parsed.secret),
+                &format!("--project={}", parsed.project),
+            ])
+            .output()
+            .context("Failed to execute Google Cloud CLI")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("GCP Secret Manager error: {}", stderr.trim());
+        }
+
+        let secret = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        match parsed.key {
+            Some(key) => select_json_field(&secret, key),
+            None => Ok(secret),
+        }
+    }
+
     /// Resolve a single secret reference.
     pub fn resolve_reference(&self, reference: &SecretReference) -> Result<ResolvedSecret> {
         let value = match &reference.provider {
             SecretProvider::OnePassword => self.resolve_onepassword(&reference.reference)?,
             SecretProvider::Vault => self.resolve_vault(&reference.reference)?,
+            SecretProvider::AwsSecretsManager => self.resolve_aws(&reference.reference)?,
+            SecretProvider::GcpSecretManager => self.resolve_gcp(&reference.reference)?,
             SecretProvider::Custom(cmd) => self.resolve_custom(cmd, &reference.reference)?,
         };
 
@@ -444,6 +719,83 @@ mod tests {
         assert_eq!(ref_val.provider, SecretProvider::Vault);
     }
 
+    #[test]
+    fn test_parse_aws_reference() {
+        let path = PathBuf::from(".env");
+        let reference =
+            SecretReference::parse("DB_PASSWORD", "aws-sm://us-east-1/prod/db#password", &path)
+                .unwrap();
+        assert_eq!(reference.provider, SecretProvider::AwsSecretsManager);
+
+        assert_eq!(
+            AwsSecretRef::parse("aws-sm://us-east-1/prod/db#password"),
+            Some(AwsSecretRef { region: "us-east-1", secret_id: "prod/db", key: Some("password") })
+        );
+        assert_eq!(
+            AwsSecretRef::parse("aws-sm://eu-west-1/api-token"),
+            Some(AwsSecretRef { region: "eu-west-1", secret_id: "api-token", key: None })
+        );
+        assert!(AwsSecretRef::parse("aws-sm://us-east-1").is_none());
+        assert!(AwsSecretRef::parse("aws-sm:///name").is_none());
+        assert!(SecretReference::parse("X", "aws-sm://us-east-1/", &path).is_none());
+    }
+
+    #[test]
+    fn test_parse_gcp_reference() {
+        let path = PathBuf::from(".env");
+        let reference =
+            SecretReference::parse("API_KEY", "gcp-sm://my-project/api-key/3", &path).unwrap();
+        assert_eq!(reference.provider, SecretProvider::GcpSecretManager);
+
+        assert_eq!(
+            GcpSecretRef::parse("gcp-sm://my-project/api-key/3"),
+            Some(GcpSecretRef {
+                project: "my-project",
+                secret: "api-key",
+                version: "3",
+                key: None
+            })
+        );
+        assert_eq!(
+            GcpSecretRef::parse("gcp-sm://my-project/db-creds#user"),
+            Some(GcpSecretRef {
+                project: "my-project",
+                secret: "db-creds",
+                version: "latest",
+                key: Some("user")
+            })
+        );
+        assert!(GcpSecretRef::parse("gcp-sm://my-project").is_none());
+        assert!(GcpSecretRef::parse("gcp-sm://p/s/1/extra").is_none());
+    }
+
+    #[test]
+    fn test_select_json_field() {
+        let secret = r#"{"username": "admin", "port": 5432}"#;
+        assert_eq!(select_json_field(secret, "username").unwrap(), "admin");
+        assert_eq!(select_json_field(secret, "port").unwrap(), "5432");
+        assert!(select_json_field(secret, "missing").is_err());
+        assert!(select_json_field("plain-text", "username").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_secret_command_env_reaches_only_the_child() {
+        let secret = ResolvedSecret {
+            variable: "PALRUN_TEST_EXEC_SECRET".to_string(),
+            value: "hunter2".to_string(),
+            provider: SecretProvider::OnePassword,
+        };
+        let command = crate::core::Command::new("show", "printf %s \"$PALRUN_TEST_EXEC_SECRET\"");
+
+        let with_secrets = SecretCommand::new(&command, std::slice::from_ref(&secret));
+        let result = crate::core::Executor::new().capture(true).execute(&with_secrets).unwrap();
+
+        assert_eq!(result.stdout.as_deref(), Some("hunter2"));
+        assert!(std::env::var("PALRUN_TEST_EXEC_SECRET").is_err());
+        assert_eq!(command.env, Vec::<(String, String)>::new());
+    }
+
     #[test]
     fn test_parse_regular_value() {
         let path = PathBuf::from(".env");
@@ -514,6 +866,11 @@ SECRET3=vault://path/secret#field
         assert_eq!(SecretProvider::Vault.name(), "HashiCorp Vault");
         assert_eq!(SecretProvider::Vault.icon(), "🗄️");
 
+        assert_eq!(SecretProvider::from_name("AWS"), Some(SecretProvider::AwsSecretsManager));
+        assert_eq!(SecretProvider::from_name("gcp"), Some(SecretProvider::GcpSecretManager));
+        assert_eq!(SecretProvider::from_name("op"), Some(SecretProvider::OnePassword));
+        assert_eq!(SecretProvider::from_name("azure"), None);
+
         let custom = SecretProvider::Custom("my-tool".to_string());
         assert_eq!(custom.name(), "Custom");
         assert_eq!(custom.icon(), "🔧");
@@ -10,6 +10,8 @@ use std::process::Command;
 
 use anyhow::Result;
 
+use crate::core::shell_quote;
+
 /// Supported runtime types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RuntimeType {
@@ -116,6 +118,70 @@ impl RuntimeVersion {
     }
 }
 
+/// An `engines` constraint from package.json checked against the active tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineCheck {
+    /// Engine name (`node` or `npm`)
+    pub engine: String,
+
+    /// Required version range, e.g. `>=18`
+    pub required: String,
+
+    /// Active version, if the tool is installed
+    pub current: Option<String>,
+
+    /// Whether the active version satisfies the range
+    pub is_compatible: Option<bool>,
+}
+
+impl EngineCheck {
+    /// Check a required range against the active version.
+    pub fn evaluate(engine: &str, required: &str, current: Option<String>) -> Self {
+        let is_compatible = current.as_deref().map(|current| satisfies_range(required, current));
+        Self { engine: engine.to_string(), required: required.to_string(), current, is_compatible }
+    }
+
+    /// Get a status icon based on compatibility.
+    pub fn status_icon(&self) -> &'static str {
+        match self.is_compatible {
+            Some(true) => "✓",
+            Some(false) => "⚠",
+            None => "?",
+        }
+    }
+
+    /// Warning to show when the active version does not satisfy the range.
+    pub fn warning(&self) -> Option<String> {
+        if self.is_compatible != Some(false) {
+            return None;
+        }
+        Some(format!(
+            "package.json engines.{} requires {}, but the active version is {}",
+            self.engine,
+            self.required,
+            self.current.as_deref().unwrap_or_default()
+        ))
+    }
+}
+
+/// Engines from package.json that are checked, with the command reporting their version.
+const CHECKED_ENGINES: &[(&str, &str)] = &[("node", "node"), ("npm", "npm")];
+
+/// Read the checked `engines` entries from package.json content.
+pub fn parse_engines(content: &str) -> Vec<(String, String)> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+
+    CHECKED_ENGINES
+        .iter()
+        .filter_map(|(engine, _)| {
+            let range = json.get("engines")?.get(engine)?.as_str()?;
+            Some(((*engine).to_string(), range.to_string()))
+        })
+        .collect()
+}
+
 /// Version manager for detecting and managing runtime versions.
 pub struct VersionManager {
     /// Project root directory
@@ -173,6 +239,46 @@ impl VersionManager {
         self.versions.get(&runtime)
     }
 
+    /// Find an installed version manager that can switch `runtime`.
+    pub fn resolve_manager(&self, runtime: RuntimeType) -> Option<VersionManagerKind> {
+        pick_manager(runtime, |kind| kind.is_available())
+    }
+
+    /// Runtimes whose current version does not satisfy the requirement,
+    /// or that are required but not installed.
+    pub fn mismatched(&self) -> Vec<&RuntimeVersion> {
+        let mut mismatched: Vec<_> = self
+            .versions
+            .values()
+            .filter(|v| {
+                v.required.is_some() && (v.is_compatible == Some(false) || v.current.is_none())
+            })
+            .collect();
+        mismatched.sort_by_key(|v| v.runtime.name());
+        mismatched
+    }
+
+    /// Check package.json `engines` against the active Node.js and npm versions.
+    pub fn check_engines(&self) -> Vec<EngineCheck> {
+        let Ok(content) = fs::read_to_string(self.root.join("package.json")) else {
+            return Vec::new();
+        };
+
+        parse_engines(&content)
+            .into_iter()
+            .map(|(engine, range)| {
+                let current = match engine.as_str() {
+                    "node" => detect_current_version(RuntimeType::Node),
+                    _ => CHECKED_ENGINES
+                        .iter()
+                        .find(|(name, _)| *name == engine)
+                        .and_then(|(_, command)| detect_tool_version(command)),
+                };
+                EngineCheck::evaluate(&engine, &range, current)
+            })
+            .collect()
+    }
+
     /// Detect Node.js version from .nvmrc, .node-version, or package.json.
     fn detect_node_version(&self) -> Option<RuntimeVersion> {
         let version = RuntimeVersion::new(RuntimeType::Node);
@@ -464,6 +570,260 @@ impl VersionManager {
     }
 }
 
+/// An installed tool that can install and switch runtime versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VersionManagerKind {
+    /// nvm (Node.js), a shell function sourced from `$NVM_DIR/nvm.sh`
+    Nvm,
+    /// fnm (Node.js)
+    Fnm,
+    /// asdf (any runtime with a plugin)
+    Asdf,
+    /// mise (any runtime)
+    Mise,
+    /// rustup (Rust)
+    Rustup,
+    /// pyenv (Python)
+    Pyenv,
+}
+
+impl VersionManagerKind {
+    /// Get the display name for the manager.
+    pub fn name(&self) -> &'static str {
+        match self {
+            VersionManagerKind::Nvm => "nvm",
+            VersionManagerKind::Fnm => "fnm",
+            VersionManagerKind::Asdf => "asdf",
+            VersionManagerKind::Mise => "mise",
+            VersionManagerKind::Rustup => "rustup",
+            VersionManagerKind::Pyenv => "pyenv",
+        }
+    }
+
+    /// Managers to try for a runtime, most specific first.
+    pub fn candidates(runtime: RuntimeType) -> &'static [VersionManagerKind] {
+        use VersionManagerKind::{Asdf, Fnm, Mise, Nvm, Pyenv, Rustup};
+        match runtime {
+            RuntimeType::Node => &[Fnm, Nvm, Mise, Asdf],
+            RuntimeType::Python => &[Pyenv, Mise, Asdf],
+            RuntimeType::Rust => &[Rustup, Mise, Asdf],
+            RuntimeType::Go | RuntimeType::Ruby | RuntimeType::Java => &[Mise, Asdf],
+        }
+    }
+
+    /// Check whether the manager is installed.
+    pub fn is_available(&self) -> bool {
+        if *self == VersionManagerKind::Nvm {
+            return nvm_script().is_some_and(|script| script.exists());
+        }
+        Command::new(self.name()).arg("--version").output().is_ok_and(|o| o.status.success())
+    }
+
+    /// Command that installs `version` of `runtime`.
+    pub fn install_command(&self, runtime: RuntimeType, version: &str) -> ManagerCommand {
+        match self {
+            VersionManagerKind::Nvm => ManagerCommand::new(*self, &["install", version]),
+            VersionManagerKind::Fnm => ManagerCommand::new(*self, &["install", version]),
+            VersionManagerKind::Asdf => {
+                ManagerCommand::new(*self, &["install", asdf_plugin(runtime), version])
+            }
+            VersionManagerKind::Mise => {
+                ManagerCommand::new(*self, &["install", &mise_tool(runtime, version)])
+            }
+            VersionManagerKind::Rustup => {
+                ManagerCommand::new(*self, &["toolchain", "install", version])
+            }
+            VersionManagerKind::Pyenv => ManagerCommand::new(*self, &["install", version]),
+        }
+    }
+
+    /// Command that makes `version` of `runtime` the active one.
+    ///
+    /// Managers with per-directory pins (asdf, mise, rustup, pyenv) pin the
+    /// project directory; nvm and fnm change the default version, since a
+    /// `use` would only last for the child shell.
+    pub fn use_command(&self, runtime: RuntimeType, version: &str) -> ManagerCommand {
+        match self {
+            VersionManagerKind::Nvm => ManagerCommand::new(*self, &["alias", "default", version]),
+            VersionManagerKind::Fnm => ManagerCommand::new(*self, &["default", version]),
+            VersionManagerKind::Asdf => {
+                ManagerCommand::new(*self, &["local", asdf_plugin(runtime), version])
+            }
+            VersionManagerKind::Mise => {
+                ManagerCommand::new(*self, &["use", &mise_tool(runtime, version)])
+            }
+            VersionManagerKind::Rustup => ManagerCommand::new(*self, &["override", "set", version]),
+            VersionManagerKind::Pyenv => ManagerCommand::new(*self, &["local", version]),
+        }
+    }
+
+    /// Command that succeeds only if `version` of `runtime` is installed.
+    fn installed_check_command(self, runtime: RuntimeType, version: &str) -> ManagerCommand {
+        match self {
+            VersionManagerKind::Nvm => ManagerCommand::new(self, &["ls", version]),
+            VersionManagerKind::Fnm => {
+                ManagerCommand::new(self, &["exec", &format!("--using={version}"), "node", "-v"])
+            }
+            VersionManagerKind::Asdf => {
+                ManagerCommand::new(self, &["where", asdf_plugin(runtime), version])
+            }
+            VersionManagerKind::Mise => {
+                ManagerCommand::new(self, &["where", &mise_tool(runtime, version)])
+            }
+            VersionManagerKind::Rustup => {
+                ManagerCommand::new(self, &["run", version, "rustc", "--version"])
+            }
+            VersionManagerKind::Pyenv => ManagerCommand::new(self, &["prefix", version]),
+        }
+    }
+
+    /// Check whether `version` of `runtime` is already installed.
+    pub fn has_version(&self, runtime: RuntimeType, version: &str) -> bool {
+        self.installed_check_command(runtime, version).run_quiet()
+    }
+
+    /// Install `version` of `runtime`.
+    pub fn install(&self, runtime: RuntimeType, version: &str) -> Result<()> {
+        self.install_command(runtime, version).run()
+    }
+
+    /// Switch `runtime` to `version`.
+    pub fn switch_to(&self, runtime: RuntimeType, version: &str) -> Result<()> {
+        self.use_command(runtime, version).run()
+    }
+}
+
+/// A version manager invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagerCommand {
+    /// Manager to invoke
+    pub manager: VersionManagerKind,
+
+    /// Arguments after the manager name
+    pub args: Vec<String>,
+}
+
+impl ManagerCommand {
+    /// Create a command for `manager` with `args`.
+    fn new(manager: VersionManagerKind, args: &[&str]) -> Self {
+        Self { manager, args: args.iter().map(ToString::to_string).collect() }
+    }
+
+    /// Build the process to run.
+    ///
+    /// nvm is a shell function, so it is run through bash after sourcing
+    /// `nvm.sh`.
+    fn process(&self) -> Command {
+        if self.manager == VersionManagerKind::Nvm {
+            let script = nvm_script().unwrap_or_else(|| PathBuf::from("nvm.sh"));
+            let mut command = Command::new("bash");
+            command.args([
+                "-c",
+                &format!(". {} && {}", shell_quote(&script.to_string_lossy()), self.shell_line()),
+            ]);
+            command
+        } else {
+            let mut command = Command::new(self.manager.name());
+            command.args(&self.args);
+            command
+        }
+    }
+
+    /// The command as a shell line, with arguments quoted as needed.
+    fn shell_line(&self) -> String {
+        std::iter::once(self.manager.name().to_string())
+            .chain(self.args.iter().map(|arg| shell_quote(arg)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Run the command with inherited stdio, failing on a non-zero exit.
+    pub fn run(&self) -> Result<()> {
+        let status = self
+            .process()
+            .status()
+            .map_err(|e| anyhow::anyhow!("Failed to run {}: {e}", self.manager.name()))?;
+        if !status.success() {
+            anyhow::bail!("`{self}` failed with {status}");
+        }
+        Ok(())
+    }
+
+    /// Run the command silently and report whether it succeeded.
+    fn run_quiet(&self) -> bool {
+        self.process().output().is_ok_and(|o| o.status.success())
+    }
+}
+
+impl std::fmt::Display for ManagerCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.shell_line())
+    }
+}
+
+/// Path to nvm's shell script, from `$NVM_DIR` or `~/.nvm`.
+fn nvm_script() -> Option<PathBuf> {
+    let dir = std::env::var_os("NVM_DIR")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".nvm")))?;
+    Some(dir.join("nvm.sh"))
+}
+
+/// The asdf plugin name for a runtime.
+fn asdf_plugin(runtime: RuntimeType) -> &'static str {
+    match runtime {
+        RuntimeType::Node => "nodejs",
+        RuntimeType::Python => "python",
+        RuntimeType::Rust => "rust",
+        RuntimeType::Go => "golang",
+        RuntimeType::Ruby => "ruby",
+        RuntimeType::Java => "java",
+    }
+}
+
+/// The mise `tool@version` argument for a runtime.
+fn mise_tool(runtime: RuntimeType, version: &str) -> String {
+    let tool = match runtime {
+        RuntimeType::Node => "node",
+        RuntimeType::Python => "python",
+        RuntimeType::Rust => "rust",
+        RuntimeType::Go => "go",
+        RuntimeType::Ruby => "ruby",
+        RuntimeType::Java => "java",
+    };
+    format!("{tool}@{version}")
+}
+
+/// Turn a version requirement into a version a manager can install.
+///
+/// Exact versions and aliases (`18.19.0`, `v20`, `lts/*`, `stable`) are
+/// returned without a leading `v`; ranges like `>=18` or `^3.11` cannot be
+/// switched to and return `None`.
+pub fn switchable_version(required: &str) -> Option<&str> {
+    let required = required.trim();
+    let ranged = required.starts_with(['<', '>', '=', '^', '~'])
+        || required.contains(char::is_whitespace)
+        || required.contains(['|', ','])
+        || required.split('.').any(|part| part.eq_ignore_ascii_case("x"));
+    if required.is_empty() || ranged {
+        return None;
+    }
+    Some(
+        required
+            .strip_prefix('v')
+            .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+            .unwrap_or(required),
+    )
+}
+
+/// Pick the first candidate manager for `runtime` that is available.
+fn pick_manager(
+    runtime: RuntimeType,
+    is_available: impl Fn(VersionManagerKind) -> bool,
+) -> Option<VersionManagerKind> {
+    VersionManagerKind::candidates(runtime).iter().copied().find(|&kind| is_available(kind))
+}
+
 /// Detect the current installed version of a runtime.
 fn detect_current_version(runtime: RuntimeType) -> Option<String> {
     let (cmd, args) = runtime.version_command();
@@ -488,6 +848,18 @@ fn detect_current_version(runtime: RuntimeType) -> Option<String> {
     parse_version_output(runtime, &version_output)
 }
 
+/// Detect a tool version from `<command> --version` printing a bare version.
+fn detect_tool_version(command: &str) -> Option<String> {
+    let output = Command::new(command).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = stdout.lines().next()?.trim().trim_start_matches('v');
+    (!version.is_empty()).then(|| version.to_string())
+}
+
 /// Parse version from command output.
 fn parse_version_output(runtime: RuntimeType, output: &str) -> Option<String> {
     let first_line = output.lines().next()?;
@@ -561,6 +933,11 @@ fn check_version_compatibility(required: &str, current: &str) -> bool {
         return true;
     }
 
+    // Compound ranges like ">=18 <21" or "^18 || ^20"
+    if required.contains("||") || required.split_whitespace().nth(1).is_some() {
+        return satisfies_range(required, current);
+    }
+
     // Handle semver ranges
     if required.starts_with('^') || required.starts_with('~') {
         return check_semver_range(required, current);
@@ -650,6 +1027,122 @@ fn check_semver_range(required: &str, current: &str) -> bool {
     compare_versions(current, version) >= 0
 }
 
+/// Check whether a version satisfies an npm-style semver range.
+///
+/// Supports `||` alternatives, space-separated comparators (`>=18 <21`),
+/// hyphen ranges (`18 - 20`), caret and tilde ranges, and x-ranges
+/// (`18.x`, `18`, `*`). Prerelease tags are ignored. A version that
+/// cannot be parsed is treated as compatible.
+pub fn satisfies_range(range: &str, version: &str) -> bool {
+    let Some(version) = parse_full_version(version) else {
+        return true;
+    };
+    range.split("||").any(|set| comparator_set_matches(set.trim(), version))
+}
+
+/// A version as `(major, minor, patch)`.
+type Triple = (u64, u64, u64);
+
+fn parse_full_version(version: &str) -> Option<Triple> {
+    let parts = parse_partial(version)?;
+    let get = |i: usize| parts.get(i).copied().unwrap_or(0);
+    (!parts.is_empty()).then(|| (get(0), get(1), get(2)))
+}
+
+/// Parse a possibly partial version (`18`, `18.2`, `18.x`), stopping at the first wildcard.
+fn parse_partial(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches(['v', '=']);
+    let version = version.split(['-', '+']).next().unwrap_or(version);
+
+    let mut parts = Vec::new();
+    for part in version.split('.').take(3) {
+        if matches!(part, "x" | "X" | "*" | "") {
+            break;
+        }
+        parts.push(part.parse().ok()?);
+    }
+    Some(parts)
+}
+
+fn lower_bound(parts: &[u64]) -> Triple {
+    let get = |i: usize| parts.get(i).copied().unwrap_or(0);
+    (get(0), get(1), get(2))
+}
+
+/// Exclusive upper bound of an x-range (`1.2` → `1.3.0`), or `None` for `*`.
+fn x_range_upper(parts: &[u64]) -> Option<Triple> {
+    match *parts {
+        [] => None,
+        [major] => Some((major + 1, 0, 0)),
+        [major, minor] => Some((major, minor + 1, 0)),
+        [major, minor, patch, ..] => Some((major, minor, patch + 1)),
+    }
+}
+
+fn comparator_set_matches(set: &str, version: Triple) -> bool {
+    if let Some((low, high)) = set.split_once(" - ") {
+        let (Some(low), Some(high)) = (parse_partial(low), parse_partial(high)) else {
+            return false;
+        };
+        return version >= lower_bound(&low) && x_range_upper(&high).is_none_or(|u| version < u);
+    }
+
+    // Join operators separated from their version (">= 18" -> ">=18")
+    let mut comparators = Vec::new();
+    let mut pending = String::new();
+    for token in set.split_whitespace() {
+        pending.push_str(token);
+        if !token.chars().all(|c| "<>=~^".contains(c)) {
+            comparators.push(std::mem::take(&mut pending));
+        }
+    }
+
+    comparators.iter().all(|comparator| comparator_matches(comparator, version))
+}
+
+fn comparator_matches(comparator: &str, version: Triple) -> bool {
+    let split = comparator.find(|c: char| !"<>=~^".contains(c)).unwrap_or(comparator.len());
+    let (op, rest) = comparator.split_at(split);
+    let Some(parts) = parse_partial(rest) else {
+        return false;
+    };
+    let lower = lower_bound(&parts);
+    let upper = x_range_upper(&parts);
+    let exact = parts.len() == 3;
+
+    match op {
+        "" | "=" => version >= lower && upper.is_none_or(|u| version < u),
+        ">=" => version >= lower,
+        ">" if exact => version > lower,
+        ">" => upper.is_some_and(|u| version >= u),
+        "<" => version < lower,
+        "<=" if exact => version <= lower,
+        "<=" => upper.is_none_or(|u| version < u),
+        "~" => {
+            let upper = match *parts {
+                [] => None,
+                [major] => Some((major + 1, 0, 0)),
+                [major, minor, ..] => Some((major, minor + 1, 0)),
+            };
+            version >= lower && upper.is_none_or(|u| version < u)
+        }
+        "^" => {
+            let (major, minor, patch) = lower;
+            let upper = if parts.is_empty() {
+                None
+            } else if major > 0 || parts.len() == 1 {
+                Some((major + 1, 0, 0))
+            } else if minor > 0 || parts.len() == 2 {
+                Some((0, minor + 1, 0))
+            } else {
+                Some((0, 0, patch + 1))
+            };
+            version >= lower && upper.is_none_or(|u| version < u)
+        }
+        _ => false,
+    }
+}
+
 /// Compare two version strings.
 /// Returns: -1 if a < b, 0 if a == b, 1 if a > b
 fn compare_versions(a: &str, b: &str) -> i32 {
@@ -716,6 +1209,62 @@ mod tests {
         assert_eq!(node.required, Some(">=18.0.0".to_string()));
     }
 
+    #[test]
+    fn test_parse_engines() {
+        let engines = parse_engines(
+            r#"{"engines": {"node": ">=18", "npm": "^9 || ^10", "vscode": "^1.80.0"}}"#,
+        );
+        assert_eq!(
+            engines,
+            vec![
+                ("node".to_string(), ">=18".to_string()),
+                ("npm".to_string(), "^9 || ^10".to_string())
+            ]
+        );
+        assert_eq!(parse_engines(r#"{"name": "no-engines"}"#), Vec::<(String, String)>::new());
+    }
+
+    #[test]
+    fn test_engine_check_against_current() {
+        let ok = EngineCheck::evaluate("node", ">=18", Some("20.11.1".to_string()));
+        assert_eq!(ok.is_compatible, Some(true));
+        assert_eq!(ok.warning(), None);
+
+        let old = EngineCheck::evaluate("node", ">=18", Some("16.20.2".to_string()));
+        assert_eq!(old.is_compatible, Some(false));
+        assert_eq!(
+            old.warning().unwrap(),
+            "package.json engines.node requires >=18, but the active version is 16.20.2"
+        );
+
+        let missing = EngineCheck::evaluate("npm", ">=9", None);
+        assert_eq!(missing.is_compatible, None);
+        assert_eq!(missing.warning(), None);
+    }
+
+    #[test]
+    fn test_satisfies_range() {
+        assert!(satisfies_range(">=18", "18.0.0"));
+        assert!(!satisfies_range(">=18", "17.9.1"));
+        assert!(satisfies_range(">= 18.12.0 < 21", "20.5.0"));
+        assert!(!satisfies_range(">=18.12.0 <21", "21.0.0"));
+        assert!(satisfies_range("^18 || ^20", "20.1.0"));
+        assert!(!satisfies_range("^18 || ^20", "19.0.0"));
+        assert!(satisfies_range("18.x", "18.19.0"));
+        assert!(!satisfies_range("18.x", "19.0.0"));
+        assert!(satisfies_range("16 - 18", "18.4.0"));
+        assert!(!satisfies_range("16 - 18", "19.0.0"));
+        assert!(satisfies_range("~18.2", "18.2.9"));
+        assert!(!satisfies_range("~18.2", "18.3.0"));
+        assert!(satisfies_range("^0.2.3", "0.2.9"));
+        assert!(!satisfies_range("^0.2.3", "0.3.0"));
+        assert!(satisfies_range("*", "1.0.0"));
+        assert!(satisfies_range(">18", "19.0.0"));
+        assert!(!satisfies_range(">18", "18.5.0"));
+        assert!(satisfies_range("<=18", "18.5.0"));
+        assert!(satisfies_range("v20.10.0", "20.10.0"));
+    }
+
     #[test]
     fn test_rust_version_from_toolchain_toml() {
         let temp = TempDir::new().unwrap();
@@ -843,6 +1392,66 @@ ruby 3.3.0
         );
     }
 
+    #[test]
+    fn test_manager_commands() {
+        use VersionManagerKind::{Asdf, Fnm, Mise, Nvm, Pyenv, Rustup};
+
+        let cases = [
+            (Nvm, RuntimeType::Node, "18.19.0", "nvm install 18.19.0", "nvm alias default 18.19.0"),
+            (Fnm, RuntimeType::Node, "20", "fnm install 20", "fnm default 20"),
+            (
+                Asdf,
+                RuntimeType::Go,
+                "1.22.0",
+                "asdf install golang 1.22.0",
+                "asdf local golang 1.22.0",
+            ),
+            (Mise, RuntimeType::Python, "3.12", "mise install python@3.12", "mise use python@3.12"),
+            (
+                Rustup,
+                RuntimeType::Rust,
+                "1.82.0",
+                "rustup toolchain install 1.82.0",
+                "rustup override set 1.82.0",
+            ),
+            (Pyenv, RuntimeType::Python, "3.11.4", "pyenv install 3.11.4", "pyenv local 3.11.4"),
+        ];
+        for (manager, runtime, version, install, switch) in cases {
+            assert_eq!(manager.install_command(runtime, version).to_string(), install);
+            assert_eq!(manager.use_command(runtime, version).to_string(), switch);
+        }
+
+        // Aliases with shell metacharacters are quoted
+        assert_eq!(
+            Nvm.install_command(RuntimeType::Node, "lts/*").to_string(),
+            "nvm install 'lts/*'"
+        );
+    }
+
+    #[test]
+    fn test_pick_manager() {
+        use VersionManagerKind::{Asdf, Mise, Nvm, Rustup};
+
+        let only = |kinds: &'static [VersionManagerKind]| move |k| kinds.contains(&k);
+        assert_eq!(pick_manager(RuntimeType::Node, only(&[Asdf, Nvm])), Some(Nvm));
+        assert_eq!(pick_manager(RuntimeType::Rust, only(&[Mise, Rustup])), Some(Rustup));
+        assert_eq!(pick_manager(RuntimeType::Ruby, only(&[Asdf, Nvm])), Some(Asdf));
+        assert_eq!(pick_manager(RuntimeType::Go, only(&[Nvm, Rustup])), None);
+    }
+
+    #[test]
+    fn test_switchable_version() {
+        assert_eq!(switchable_version("v18.19.0"), Some("18.19.0"));
+        assert_eq!(switchable_version(" 3.12 "), Some("3.12"));
+        assert_eq!(switchable_version("lts/*"), Some("lts/*"));
+        assert_eq!(switchable_version("stable"), Some("stable"));
+        assert_eq!(switchable_version(">=18"), None);
+        assert_eq!(switchable_version("^3.11"), None);
+        assert_eq!(switchable_version(">=16 <20"), None);
+        assert_eq!(switchable_version("18.x"), None);
+        assert_eq!(switchable_version(""), None);
+    }
+
     #[test]
     fn test_version_comparison() {
         assert_eq!(compare_versions("1.0.0", "1.0.0"), 0);
@@ -0,0 +1,205 @@
+//! Changed-package detection for `pal list --changed`.
+//!
+//! Works out which files differ from a base ref and keeps only the
+//! commands belonging to the directories those files live in.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use git2::DiffOptions;
+
+use super::GitRepository;
+use crate::core::Command;
+use crate::scanner::META_SOURCE_FILE;
+
+impl GitRepository {
+    /// Files changed since `base`, relative to the repository root.
+    ///
+    /// Compares against the merge base of `base` and `HEAD`, so changes
+    /// that landed on the base branch afterwards are not included. Staged,
+    /// unstaged, and untracked changes all count.
+    pub fn changed_files(&self, base: &str) -> Result<Vec<PathBuf>> {
+        let base_commit = self
+            .repo
+            .revparse_single(base)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Base ref '{base}' not found; pass --base <ref>"))?;
+
+        let head = self.repo.head().ok().and_then(|head| head.target());
+        let since = head
+            .and_then(|head| self.repo.merge_base(base_commit.id(), head).ok())
+            .unwrap_or_else(|| base_commit.id());
+        let tree = self.repo.find_commit(since)?.tree()?;
+
+        let mut options = DiffOptions::new();
+        options.include_untracked(true).recurse_untracked_dirs(true);
+        let diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))
+            .context("Failed to diff against base")?;
+
+        let mut files: Vec<PathBuf> = diff
+            .deltas()
+            .flat_map(|delta| [delta.old_file().path(), delta.new_file().path()])
+            .flatten()
+            .map(Path::to_path_buf)
+            .collect();
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+}
+
+/// Keep the commands belonging to directories with changed files.
+///
+/// Each changed file (relative to `root`) is attributed to the deepest
+/// command directory containing it, so a change inside `packages/api`
+/// selects that package's commands but not the root's.
+pub fn filter_changed(commands: &[Command], changed: &[PathBuf], root: &Path) -> Vec<Command> {
+    let dirs: Vec<PathBuf> = commands.iter().map(|cmd| command_dir(cmd, root)).collect();
+
+    let mut touched: Vec<&Path> = changed
+        .iter()
+        .filter_map(|file| {
+            let file = root.join(file);
+            dirs.iter()
+                .filter(|dir| file.starts_with(dir))
+                .max_by_key(|dir| dir.components().count())
+                .map(PathBuf::as_path)
+        })
+        .collect();
+    touched.sort();
+    touched.dedup();
+
+    commands
+        .iter()
+        .zip(&dirs)
+        .filter(|(_, dir)| touched.contains(&dir.as_path()))
+        .map(|(cmd, _)| cmd.clone())
+        .collect()
+}
+
+/// Directory a command belongs to.
+///
+/// Its working directory if set, otherwise the directory of the file it
+/// was discovered in, otherwise the project root.
+fn command_dir(command: &Command, root: &Path) -> PathBuf {
+    let dir = command
+        .working_dir
+        .clone()
+        .or_else(|| {
+            let file = command.metadata.get(META_SOURCE_FILE)?;
+            Path::new(file).parent().map(Path::to_path_buf)
+        })
+        .map_or_else(|| root.to_path_buf(), |dir| root.join(dir));
+
+    // Match the repository root, which git reports with symlinks resolved
+    std::fs::canonicalize(&dir).unwrap_or(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(commands: &[Command]) -> Vec<&str> {
+        commands.iter().map(|cmd| cmd.name.as_str()).collect()
+    }
+
+    fn monorepo() -> (tempfile::TempDir, Vec<Command>) {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("packages/api")).unwrap();
+        std::fs::create_dir_all(root.join("packages/web")).unwrap();
+
+        let commands = vec![
+            Command::new("lint", "npm run lint").with_working_dir(&root),
+            Command::new("api:test", "npm test").with_working_dir(root.join("packages/api")),
+            Command::new("web:test", "npm test").with_working_dir(root.join("packages/web")),
+            Command::new("web:build", "npm run build").with_working_dir(root.join("packages/web")),
+        ];
+        (temp, commands)
+    }
+
+    #[test]
+    fn test_changes_select_their_package() {
+        let (temp, commands) = monorepo();
+        let root = temp.path().canonicalize().unwrap();
+        let changed = vec![PathBuf::from("packages/web/src/index.ts")];
+
+        let filtered = filter_changed(&commands, &changed, &root);
+        assert_eq!(names(&filtered), vec!["web:test", "web:build"]);
+    }
+
+    #[test]
+    fn test_root_changes_select_root_commands() {
+        let (temp, commands) = monorepo();
+        let root = temp.path().canonicalize().unwrap();
+        let changed = vec![PathBuf::from("README.md"), PathBuf::from("packages/api/lib.rs")];
+
+        let filtered = filter_changed(&commands, &changed, &root);
+        assert_eq!(names(&filtered), vec!["lint", "api:test"]);
+    }
+
+    #[test]
+    fn test_no_changes_selects_nothing() {
+        let (temp, commands) = monorepo();
+        let root = temp.path().canonicalize().unwrap();
+
+        assert_eq!(filter_changed(&commands, &[], &root), Vec::<Command>::new());
+    }
+
+    #[test]
+    fn test_relative_working_dir_and_provenance() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("tools")).unwrap();
+
+        let mut from_file = Command::new("gen", "make gen");
+        from_file.metadata.insert(
+            META_SOURCE_FILE.to_string(),
+            root.join("tools/Makefile").display().to_string(),
+        );
+        let commands = vec![
+            Command::new("docs", "mkdocs build").with_working_dir("docs"),
+            from_file,
+            Command::new("all", "make all"),
+        ];
+
+        let filtered = filter_changed(&commands, &[PathBuf::from("tools/gen.py")], &root);
+        assert_eq!(names(&filtered), vec!["gen"]);
+
+        let filtered = filter_changed(&commands, &[PathBuf::from("docs/index.md")], &root);
+        assert_eq!(names(&filtered), vec!["docs"]);
+    }
+
+    #[test]
+    fn test_changed_files_since_base() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+        let commit = |path: &str| {
+            std::fs::create_dir_all(temp.path().join(path).parent().unwrap()).unwrap();
+            std::fs::write(temp.path().join(path), path).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(path)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parents: Vec<_> =
+                repo.head().ok().and_then(|h| h.peel_to_commit().ok()).into_iter().collect();
+            let parents: Vec<_> = parents.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, path, &tree, &parents).unwrap()
+        };
+
+        let base = commit("README.md");
+        repo.reference("refs/heads/base", base, true, "base").unwrap();
+        commit("packages/api/lib.rs");
+        std::fs::write(temp.path().join("new.txt"), "untracked").unwrap();
+
+        let repo = GitRepository::open(temp.path()).unwrap();
+        let files = repo.changed_files("base").unwrap();
+        assert_eq!(files, vec![PathBuf::from("new.txt"), PathBuf::from("packages/api/lib.rs")]);
+
+        assert!(repo.changed_files("does-not-exist").is_err());
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
@@ -25,6 +26,35 @@ pub const HOOK_NAMES: &[&str] = &[
     "post-rewrite",
 ];
 
+/// Hooks that have a built-in template, installable without a command.
+pub const TEMPLATE_HOOKS: &[&str] = &["prepare-commit-msg", "post-merge"];
+
+/// Built-in templates by name, with the hook each one installs into.
+pub const HOOK_TEMPLATES: &[(&str, &str)] =
+    &[("issue-id", "prepare-commit-msg"), ("lockfile-install", "post-merge")];
+
+/// Default pattern for issue IDs in branch names, matched case-insensitively.
+pub const DEFAULT_ISSUE_PATTERN: &str = r"[A-Z][A-Z0-9]*-\d+";
+
+/// Command run by hooks installed with `--require-signing`.
+pub const SIGNING_CHECK_COMMAND: &str = "pal hooks builtin check-signing";
+
+/// Lockfiles and the command that installs their dependencies.
+pub const LOCKFILE_INSTALL_COMMANDS: &[(&str, &str)] = &[
+    ("package-lock.json", "npm install"),
+    ("yarn.lock", "yarn install"),
+    ("pnpm-lock.yaml", "pnpm install"),
+    ("bun.lockb", "bun install"),
+    ("bun.lock", "bun install"),
+    ("Cargo.lock", "cargo fetch"),
+    ("poetry.lock", "poetry install"),
+    ("uv.lock", "uv sync"),
+    ("Pipfile.lock", "pipenv install"),
+    ("Gemfile.lock", "bundle install"),
+    ("composer.lock", "composer install"),
+    ("go.sum", "go mod download"),
+];
+
 /// Information about an installed Git hook.
 #[derive(Debug, Clone)]
 pub struct HookInfo {
@@ -42,6 +72,53 @@ pub struct HookInfo {
 
     /// Content preview (first few lines)
     pub preview: Option<String>,
+
+    /// Command run by a Palrun-generated hook
+    pub managed_command: Option<String>,
+
+    /// Hook manager this hook hands off to, if any
+    pub delegate: Option<HookDelegate>,
+}
+
+/// Third-party hook managers that hooks commonly delegate to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookDelegate {
+    /// husky (Node.js)
+    Husky,
+    /// lefthook
+    Lefthook,
+    /// pre-commit framework (Python)
+    PreCommit,
+}
+
+impl HookDelegate {
+    /// Detect a hook manager from a hook script's content.
+    pub fn detect(content: &str) -> Option<Self> {
+        if content.contains("husky") {
+            Some(Self::Husky)
+        } else if content.contains("lefthook") {
+            Some(Self::Lefthook)
+        } else if content.contains("pre-commit.com") || content.contains("pre_commit") {
+            Some(Self::PreCommit)
+        } else {
+            None
+        }
+    }
+
+    /// Get the display name for this hook manager.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Husky => "husky",
+            Self::Lefthook => "lefthook",
+            Self::PreCommit => "pre-commit",
+        }
+    }
+}
+
+impl std::fmt::Display for HookDelegate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
 }
 
 /// Git hooks manager.
@@ -52,8 +129,14 @@ pub struct HooksManager {
 
 impl HooksManager {
     /// Create a hooks manager for the given repository root.
+    ///
+    /// In a linked worktree, hooks live in the main repository's Git
+    /// directory, so that is where they are read and installed.
     pub fn new(repo_root: impl AsRef<Path>) -> Self {
-        Self { hooks_dir: repo_root.as_ref().join(".git").join("hooks") }
+        let repo_root = repo_root.as_ref();
+        let hooks_dir = super::GitRepository::open(repo_root)
+            .map_or_else(|| repo_root.join(".git").join("hooks"), |repo| repo.hooks_dir());
+        Self { hooks_dir }
     }
 
     /// Create a hooks manager by discovering the Git repository.
@@ -93,12 +176,17 @@ impl HooksManager {
                 let preview =
                     content.as_ref().map(|c| c.lines().take(3).collect::<Vec<_>>().join("\n"));
 
+                let managed_command = content.as_deref().and_then(extract_managed_command);
+                let delegate = content.as_deref().and_then(HookDelegate::detect);
+
                 hooks.push(HookInfo {
                     name: name.to_string(),
                     path,
                     is_palrun,
                     is_executable,
                     preview,
+                    managed_command,
+                    delegate,
                 });
             }
         }
@@ -181,6 +269,82 @@ impl HooksManager {
         Ok(())
     }
 
+    /// Install the built-in template for a hook.
+    ///
+    /// See [`TEMPLATE_HOOKS`] for the hooks that have one.
+    pub fn install_template(&self, name: &str, force: bool) -> Result<String> {
+        let command = template_command(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No built-in template for '{}'. Templates exist for: {}",
+                name,
+                TEMPLATE_HOOKS.join(", ")
+            )
+        })?;
+        self.install_hook(name, &command, force)?;
+        Ok(command)
+    }
+
+    /// Install a built-in template by name (see [`HOOK_TEMPLATES`]).
+    pub fn install_named_template(
+        &self,
+        name: &str,
+        template: &str,
+        force: bool,
+    ) -> Result<String> {
+        let hook = template_hook(template)?;
+        if hook != name {
+            anyhow::bail!("Template '{}' installs into {}, not '{}'", template, hook, name);
+        }
+        self.install_template(name, force)
+    }
+
+    /// Install a check that fails commits when signing is not configured.
+    ///
+    /// An existing Palrun command for the hook is kept and runs after the check.
+    pub fn install_signing_check(&self, name: &str, force: bool) -> Result<String> {
+        if !matches!(name, "pre-commit" | "commit-msg") {
+            anyhow::bail!(
+                "--require-signing installs into pre-commit or commit-msg, not '{}'",
+                name
+            );
+        }
+
+        let existing = fs::read_to_string(self.hooks_dir.join(name))
+            .ok()
+            .and_then(|content| extract_managed_command(&content));
+        let command = match existing {
+            Some(cmd) if cmd.contains(SIGNING_CHECK_COMMAND) => cmd,
+            Some(cmd) => format!("{SIGNING_CHECK_COMMAND} && {cmd}"),
+            None => SIGNING_CHECK_COMMAND.to_string(),
+        };
+
+        self.install_hook(name, &command, force)?;
+        Ok(command)
+    }
+
+    /// Install a hook that runs the commands configured for it in palrun.toml.
+    ///
+    /// The script calls `pal hooks run <name>`, so later changes to the
+    /// `[hooks]` section take effect without reinstalling.
+    pub fn install_runner(&self, name: &str, force: bool) -> Result<String> {
+        let command = runner_command(name);
+        self.install_hook(name, &command, force)?;
+        Ok(command)
+    }
+
+    /// Run the commands configured in palrun.toml for a hook.
+    ///
+    /// `args` are the arguments Git passed to the hook; each command sees
+    /// them as `$1`, `$2`, .... Stops at the first command that fails.
+    pub fn run(&self, hook_name: &str, args: &[String]) -> Result<()> {
+        if !HOOK_NAMES.contains(&hook_name) {
+            anyhow::bail!("Unknown hook name: {}", hook_name);
+        }
+
+        let config = crate::core::Config::load()?;
+        run_commands(hook_name, &config.hooks.commands_for(hook_name), args)
+    }
+
     /// Install multiple hooks from configuration.
     pub fn install_hooks(&self, hooks: &[(String, String)], force: bool) -> Result<()> {
         for (name, command) in hooks {
@@ -217,8 +381,230 @@ impl HooksManager {
             .map(|c| c.contains("# Managed by Palrun") || c.contains("pal run"))
             .unwrap_or(false);
         let preview = content.as_ref().map(|c| c.lines().take(5).collect::<Vec<_>>().join("\n"));
+        let managed_command = content.as_deref().and_then(extract_managed_command);
+        let delegate = content.as_deref().and_then(HookDelegate::detect);
+
+        Some(HookInfo {
+            name: name.to_string(),
+            path,
+            is_palrun,
+            is_executable,
+            preview,
+            managed_command,
+            delegate,
+        })
+    }
+}
+
+/// Extract the command from a hook script generated by Palrun.
+///
+/// Returns `None` for hooks that Palrun did not write.
+pub fn extract_managed_command(content: &str) -> Option<String> {
+    if !content.contains("# Managed by Palrun") {
+        return None;
+    }
+
+    // The body sits between these markers and may span several lines
+    if let Some((_, rest)) = content.split_once("# Run the configured command\n") {
+        if let Some((body, _)) = rest.split_once("\n# Exit with the command's exit code") {
+            let body = body.trim();
+            if !body.is_empty() {
+                return Some(body.to_string());
+            }
+        }
+    }
+
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("# Command: "))
+        .map(|cmd| cmd.trim().to_string())
+}
+
+/// Get the executable lines of a hook script, without the shebang, comments, or blank lines.
+pub fn script_body(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .collect()
+}
+
+/// Get the command a built-in hook template runs.
+pub fn template_command(hook_name: &str) -> Option<String> {
+    TEMPLATE_HOOKS.contains(&hook_name).then(|| format!("pal hooks builtin {hook_name} \"$@\""))
+}
+
+/// Get the hook a named built-in template installs into.
+pub fn template_hook(template: &str) -> Result<&'static str> {
+    HOOK_TEMPLATES.iter().find(|(name, _)| *name == template).map(|(_, hook)| *hook).ok_or_else(
+        || {
+            let names: Vec<_> = HOOK_TEMPLATES.iter().map(|(name, _)| *name).collect();
+            anyhow::anyhow!("Unknown template '{}'. Available: {}", template, names.join(", "))
+        },
+    )
+}
+
+/// A pattern that finds issue IDs in branch names.
+#[derive(Debug, Clone)]
+pub struct IssuePattern(regex::Regex);
+
+impl IssuePattern {
+    /// Compile a pattern such as `[A-Z]+-\d+`.
+    ///
+    /// It is matched case-insensitively at the start of a branch path
+    /// segment and must end the segment or be followed by `-` or `_`.
+    pub fn new(pattern: &str) -> Result<Self> {
+        let re = regex::Regex::new(&format!("(?i)^(?:{pattern})(?:$|[-_])"))
+            .with_context(|| format!("Invalid issue pattern '{pattern}'"))?;
+        Ok(Self(re))
+    }
+
+    /// Find the issue ID in a branch name, uppercased.
+    pub fn find(&self, branch: &str) -> Option<String> {
+        const NOT_TICKETS: &[&str] = &["RELEASE", "HOTFIX", "VERSION", "RC"];
+
+        branch.split('/').find_map(|segment| {
+            let matched = self.0.find(segment)?.as_str().trim_end_matches(['-', '_']);
+            let id = matched.to_ascii_uppercase();
+            let key = id.split('-').next().unwrap_or_default();
+            (!NOT_TICKETS.contains(&key)).then_some(id)
+        })
+    }
+}
+
+impl Default for IssuePattern {
+    fn default() -> Self {
+        Self::new(DEFAULT_ISSUE_PATTERN).expect("default issue pattern is valid")
+    }
+}
+
+/// Extract a ticket ID (e.g. `ABC-123`) from a branch name.
+///
+/// Matches Jira/Linear-style keys at the start of any path segment, so
+/// `feature/abc-123-login` yields `ABC-123`.
+pub fn ticket_from_branch(branch: &str) -> Option<String> {
+    static DEFAULT: LazyLock<IssuePattern> = LazyLock::new(IssuePattern::default);
+    DEFAULT.find(branch)
+}
+
+/// Prepend `ticket` to a commit message unless it already mentions it.
+///
+/// Returns `None` when the message does not need to change.
+pub fn prepend_ticket(message: &str, ticket: &str) -> Option<String> {
+    let upper = message.to_ascii_uppercase();
+    if upper.contains(&ticket.to_ascii_uppercase()) {
+        return None;
+    }
+    Some(format!("{ticket}: {message}"))
+}
+
+/// Commit signing settings from Git config.
+#[derive(Debug, Clone, Default)]
+pub struct SigningConfig {
+    /// `commit.gpgsign`
+    pub gpg_sign: Option<bool>,
+
+    /// `user.signingkey`
+    pub signing_key: Option<String>,
+
+    /// `gpg.format` (openpgp, ssh, x509)
+    pub format: Option<String>,
+}
+
+impl SigningConfig {
+    /// Read signing settings from a repository's effective config.
+    pub fn from_repo(repo: &super::GitRepository) -> Self {
+        Self {
+            gpg_sign: repo.config_bool("commit.gpgsign"),
+            signing_key: repo.config_string("user.signingkey"),
+            format: repo.config_string("gpg.format"),
+        }
+    }
+
+    /// Check that commits will be signed.
+    ///
+    /// Returns guidance for each missing setting on failure.
+    pub fn check(&self) -> std::result::Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.gpg_sign != Some(true) {
+            problems.push(
+                "commit.gpgsign is not enabled: run `git config commit.gpgsign true`".to_string(),
+            );
+        }
+
+        if self.signing_key.as_deref().map_or(true, |key| key.trim().is_empty()) {
+            let hint = if self.format.as_deref() == Some("ssh") {
+                "run `git config user.signingkey ~/.ssh/id_ed25519.pub`"
+            } else {
+                "run `git config user.signingkey <KEY-ID>` (list keys with `gpg --list-secret-keys`)"
+            };
+            problems.push(format!("user.signingkey is not set: {hint}"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// Get the install commands for lockfiles among `changed_files`, deduplicated.
+///
+/// Each command comes with the directory of its lockfile, relative like
+/// `changed_files` and empty for a lockfile at the top level.
+pub fn install_commands_for(changed_files: &[String]) -> Vec<(PathBuf, &'static str)> {
+    let mut commands = Vec::new();
+    for file in changed_files {
+        let path = Path::new(file);
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or(file);
+        if let Some((_, cmd)) = LOCKFILE_INSTALL_COMMANDS.iter().find(|(lock, _)| *lock == name) {
+            let entry = (path.parent().map(Path::to_path_buf).unwrap_or_default(), *cmd);
+            if !commands.contains(&entry) {
+                commands.push(entry);
+            }
+        }
+    }
+    commands
+}
+
+/// Get the command a hook script runs to hand off to `pal hooks run`.
+pub fn runner_command(hook_name: &str) -> String {
+    format!("pal hooks run {hook_name} \"$@\"")
+}
+
+/// Run hook commands in order through the shell, stopping at the first failure.
+///
+/// `hook_args` are passed as the shell's positional parameters.
+pub fn run_commands(hook_name: &str, commands: &[String], hook_args: &[String]) -> Result<()> {
+    for command in commands {
+        let status = shell_command(hook_name, command, hook_args)
+            .status()
+            .with_context(|| format!("Failed to run `{command}`"))?;
+
+        if !status.success() {
+            let code = status.code().map_or_else(|| "a signal".to_string(), |c| c.to_string());
+            anyhow::bail!("{hook_name}: `{command}` failed with exit code {code}");
+        }
+    }
+    Ok(())
+}
 
-        Some(HookInfo { name: name.to_string(), path, is_palrun, is_executable, preview })
+/// Build a shell invocation of `command` with `args` as positional parameters.
+fn shell_command(hook_name: &str, command: &str, args: &[String]) -> std::process::Command {
+    #[cfg(unix)]
+    {
+        let mut process = std::process::Command::new("sh");
+        process.arg("-c").arg(command).arg(hook_name).args(args);
+        process
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = hook_name;
+        let mut process = std::process::Command::new("cmd");
+        process.arg("/C").arg(command).args(args);
+        process
     }
 }
 
@@ -348,6 +734,49 @@ mod tests {
         assert!(script.contains("cargo test && cargo fmt --check"));
     }
 
+    #[test]
+    fn test_install_runner() {
+        let (_temp, manager) = setup_git_repo();
+
+        let command = manager.install_runner("pre-commit", false).unwrap();
+        assert_eq!(command, "pal hooks run pre-commit \"$@\"");
+        assert!(manager.is_palrun_hook("pre-commit"));
+
+        let info = manager.get_hook_info("pre-commit").unwrap();
+        assert_eq!(info.managed_command.as_deref(), Some(command.as_str()));
+        assert!(manager.run("not-a-hook", &[]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_commands_stops_at_first_failure() {
+        let temp = TempDir::new().unwrap();
+        let log = temp.path().join("log");
+        let log = log.display();
+        let commands = vec![
+            format!("echo lint >> {log}"),
+            format!("echo test >> {log} && exit 3"),
+            format!("echo deploy >> {log}"),
+        ];
+
+        let err = run_commands("pre-commit", &commands, &[]).unwrap_err();
+        assert!(err.to_string().contains("exit code 3"));
+        assert_eq!(fs::read_to_string(temp.path().join("log")).unwrap(), "lint\ntest\n");
+
+        run_commands("pre-commit", &commands[..1], &[]).unwrap();
+        run_commands("pre-commit", &[], &[]).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_commands_passes_hook_arguments() {
+        let commands = vec![r#"test "$0" = commit-msg && test "$1" = .git/COMMIT_EDITMSG"#.into()];
+        let args = vec![".git/COMMIT_EDITMSG".to_string()];
+
+        run_commands("commit-msg", &commands, &args).unwrap();
+        assert!(run_commands("commit-msg", &commands, &[]).is_err());
+    }
+
     #[test]
     fn test_install_multiple_hooks() {
         let (_temp, manager) = setup_git_repo();
@@ -375,4 +804,223 @@ mod tests {
         assert!(!manager.hook_exists("pre-commit"));
         assert!(!manager.hook_exists("pre-push"));
     }
+
+    #[test]
+    fn test_extract_managed_command() {
+        let script = generate_hook_script("pre-commit", "cargo fmt --check\ncargo test");
+        assert_eq!(
+            extract_managed_command(&script).as_deref(),
+            Some("cargo fmt --check\ncargo test")
+        );
+
+        let header_only = "#!/bin/sh\n# Managed by Palrun\n# Command: npm test\nnpm test\n";
+        assert_eq!(extract_managed_command(header_only).as_deref(), Some("npm test"));
+
+        assert!(extract_managed_command("#!/bin/sh\nnpm test\n").is_none());
+    }
+
+    #[test]
+    fn test_detect_hook_delegate() {
+        let husky = "#!/usr/bin/env sh\n. \"$(dirname -- \"$0\")/_/husky.sh\"\n\nnpx lint-staged\n";
+        assert_eq!(HookDelegate::detect(husky), Some(HookDelegate::Husky));
+
+        let lefthook = "#!/bin/sh\ncall_lefthook()\n{\n  lefthook run \"pre-commit\" \"$@\"\n}\n";
+        assert_eq!(HookDelegate::detect(lefthook), Some(HookDelegate::Lefthook));
+
+        let pre_commit =
+            "#!/usr/bin/env bash\n# File generated by pre-commit: https://pre-commit.com\n";
+        assert_eq!(HookDelegate::detect(pre_commit), Some(HookDelegate::PreCommit));
+
+        assert_eq!(HookDelegate::detect("#!/bin/sh\ncargo test\n"), None);
+    }
+
+    #[test]
+    fn test_hook_info_shows_managed_command() {
+        let (temp, manager) = setup_git_repo();
+
+        manager.install_hook("pre-push", "cargo build --release", false).unwrap();
+        fs::write(
+            temp.path().join(".git/hooks/pre-commit"),
+            "#!/bin/sh\nnpx lefthook run pre-commit\n",
+        )
+        .unwrap();
+
+        let hooks = manager.list_hooks();
+        let pre_push = hooks.iter().find(|h| h.name == "pre-push").unwrap();
+        assert_eq!(pre_push.managed_command.as_deref(), Some("cargo build --release"));
+        assert_eq!(pre_push.delegate, None);
+
+        let pre_commit = hooks.iter().find(|h| h.name == "pre-commit").unwrap();
+        assert_eq!(pre_commit.managed_command, None);
+        assert_eq!(pre_commit.delegate, Some(HookDelegate::Lefthook));
+    }
+
+    #[test]
+    fn test_script_body() {
+        let body = script_body("#!/bin/sh\n# comment\n\nset -e\n  npm test  \n");
+        assert_eq!(body, vec!["set -e", "  npm test"]);
+    }
+
+    #[test]
+    fn test_ticket_from_branch() {
+        assert_eq!(ticket_from_branch("ABC-123").as_deref(), Some("ABC-123"));
+        assert_eq!(ticket_from_branch("feature/ABC-123-add-login").as_deref(), Some("ABC-123"));
+        assert_eq!(ticket_from_branch("fix/eng-42_crash").as_deref(), Some("ENG-42"));
+        assert_eq!(ticket_from_branch("user/jane/PROJ2-7").as_deref(), Some("PROJ2-7"));
+        assert_eq!(ticket_from_branch("main"), None);
+        assert_eq!(ticket_from_branch("feature/add-login"), None);
+        assert_eq!(ticket_from_branch("release-2024"), None);
+        assert_eq!(ticket_from_branch("dependabot/npm/lodash-4.17.21"), None);
+    }
+
+    #[test]
+    fn test_issue_pattern_conventions() {
+        let default = IssuePattern::default();
+        for (branch, expected) in [
+            ("eng-123-fix-thing", Some("ENG-123")),
+            ("ENG-123", Some("ENG-123")),
+            ("feature/ENG-123/login", Some("ENG-123")),
+            ("bugfix/eng-7_typo", Some("ENG-7")),
+            ("jane.doe/abc-1-spike", Some("ABC-1")),
+            ("fix-login-eng-42", None),
+            ("hotfix-12", None),
+            ("v1.2.3", None),
+        ] {
+            assert_eq!(default.find(branch).as_deref(), expected, "{branch}");
+        }
+
+        // GitHub-style numeric branches with a custom pattern
+        let github = IssuePattern::new(r"\d+").unwrap();
+        assert_eq!(github.find("123-fix-login").as_deref(), Some("123"));
+        assert_eq!(github.find("feature/login"), None);
+
+        // Only the configured project key
+        let eng_only = IssuePattern::new(r"ENG-\d+").unwrap();
+        assert_eq!(eng_only.find("eng-9-docs").as_deref(), Some("ENG-9"));
+        assert_eq!(eng_only.find("ops-9-docs"), None);
+
+        assert!(IssuePattern::new("(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_install_named_template() {
+        let (_temp, manager) = setup_git_repo();
+
+        let command =
+            manager.install_named_template("prepare-commit-msg", "issue-id", false).unwrap();
+        assert_eq!(command, "pal hooks builtin prepare-commit-msg \"$@\"");
+        assert!(manager.install_named_template("pre-commit", "issue-id", false).is_err());
+        assert!(template_hook("nope").is_err());
+        assert_eq!(template_hook("lockfile-install").unwrap(), "post-merge");
+    }
+
+    #[test]
+    fn test_prepend_ticket() {
+        assert_eq!(prepend_ticket("Add login", "ABC-123").as_deref(), Some("ABC-123: Add login"));
+        assert_eq!(prepend_ticket("abc-123: Add login", "ABC-123"), None);
+    }
+
+    #[test]
+    fn test_install_commands_for_lockfiles() {
+        let changed = vec![
+            "src/main.rs".to_string(),
+            "web/package-lock.json".to_string(),
+            "Cargo.lock".to_string(),
+            "package-lock.json".to_string(),
+            "web/package-lock.json".to_string(),
+        ];
+        assert_eq!(
+            install_commands_for(&changed),
+            vec![
+                (PathBuf::from("web"), "npm install"),
+                (PathBuf::new(), "cargo fetch"),
+                (PathBuf::new(), "npm install"),
+            ]
+        );
+        assert_eq!(install_commands_for(&["README.md".to_string()]), Vec::new());
+    }
+
+    #[test]
+    fn test_install_template() {
+        let (_temp, manager) = setup_git_repo();
+
+        let command = manager.install_template("prepare-commit-msg", false).unwrap();
+        assert_eq!(command, "pal hooks builtin prepare-commit-msg \"$@\"");
+
+        let info = manager.get_hook_info("prepare-commit-msg").unwrap();
+        assert!(info.is_palrun);
+        assert_eq!(info.managed_command.as_deref(), Some(command.as_str()));
+
+        manager.install_template("post-merge", false).unwrap();
+        assert!(manager.install_template("pre-commit", false).is_err());
+    }
+
+    #[test]
+    fn test_worktree_installs_into_common_hooks_dir() {
+        let temp = TempDir::new().unwrap();
+        let main = temp.path().join("main");
+        let repo = git2::Repository::init(&main).unwrap();
+
+        // A worktree needs a commit to branch from
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[]).unwrap();
+
+        let worktree_path = temp.path().join("feature");
+        repo.worktree("feature", &worktree_path, None).unwrap();
+        assert!(worktree_path.join(".git").is_file());
+
+        let manager = HooksManager::new(&worktree_path);
+        manager.install_hook("pre-commit", "cargo test", false).unwrap();
+
+        let main_hook = main.join(".git").join("hooks").join("pre-commit");
+        assert_eq!(
+            manager.hooks_dir().canonicalize().unwrap(),
+            main_hook.parent().unwrap().canonicalize().unwrap()
+        );
+        assert!(main_hook.exists());
+        assert!(HooksManager::new(&main).is_palrun_hook("pre-commit"));
+    }
+
+    #[test]
+    fn test_signing_check() {
+        let configured = SigningConfig {
+            gpg_sign: Some(true),
+            signing_key: Some("ABCDEF1234567890".to_string()),
+            format: None,
+        };
+        assert!(configured.check().is_ok());
+
+        let problems = SigningConfig::default().check().unwrap_err();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("commit.gpgsign"));
+        assert!(problems[1].contains("gpg --list-secret-keys"));
+
+        let disabled = SigningConfig { gpg_sign: Some(false), ..configured.clone() };
+        assert_eq!(disabled.check().unwrap_err().len(), 1);
+
+        let ssh_without_key = SigningConfig {
+            gpg_sign: Some(true),
+            signing_key: Some("  ".to_string()),
+            format: Some("ssh".to_string()),
+        };
+        let problems = ssh_without_key.check().unwrap_err();
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains(".ssh"));
+    }
+
+    #[test]
+    fn test_install_signing_check_keeps_existing_command() {
+        let (_temp, manager) = setup_git_repo();
+
+        manager.install_hook("pre-commit", "cargo test", false).unwrap();
+        let command = manager.install_signing_check("pre-commit", false).unwrap();
+        assert_eq!(command, format!("{SIGNING_CHECK_COMMAND} && cargo test"));
+
+        // Installing twice does not chain the check again
+        let again = manager.install_signing_check("pre-commit", false).unwrap();
+        assert_eq!(again, command);
+
+        assert!(manager.install_signing_check("pre-push", false).is_err());
+    }
 }
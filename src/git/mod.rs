@@ -3,13 +3,17 @@
 //! Provides Git repository detection, branch awareness, and Git operations
 //! for enhanced command palette functionality.
 
+pub mod changed;
 pub mod hooks;
+pub mod stash;
 
 use std::path::{Path, PathBuf};
 
-use git2::{BranchType, Repository, StatusOptions};
+use git2::{BranchType, Repository, RepositoryState, StatusOptions};
 
-pub use hooks::HooksManager;
+pub use changed::filter_changed;
+pub use hooks::{HookDelegate, HookInfo, HooksManager};
+pub use stash::{run_clean, CleanRun, WorkingTree};
 
 /// Information about a Git repository.
 #[derive(Debug, Clone)]
@@ -87,6 +91,38 @@ impl GitInfo {
     }
 }
 
+/// A stash entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashInfo {
+    /// Position in the stash list (0 is the most recent, `stash@{0}`)
+    pub index: usize,
+
+    /// Stash message
+    pub message: String,
+
+    /// Abbreviated commit id of the stash
+    pub id: String,
+}
+
+/// A commit on the current branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitInfo {
+    /// Abbreviated commit id
+    pub id: String,
+
+    /// First line of the commit message
+    pub summary: String,
+
+    /// Author name
+    pub author: String,
+
+    /// Commit time, in seconds since the Unix epoch
+    pub time: i64,
+}
+
+/// Length of abbreviated commit ids.
+const SHORT_ID_LEN: usize = 7;
+
 /// Git repository wrapper with high-level operations.
 pub struct GitRepository {
     repo: Repository,
@@ -138,6 +174,41 @@ impl GitRepository {
         self.repo.is_worktree()
     }
 
+    /// Get the common Git directory.
+    ///
+    /// For a linked worktree this is the main repository's `.git`
+    /// directory, which holds the shared hooks and config.
+    #[must_use]
+    pub fn common_dir(&self) -> PathBuf {
+        let git_dir = self.repo.path();
+
+        // Linked worktrees record the common dir, relative to their own
+        // git dir, in a `commondir` file
+        std::fs::read_to_string(git_dir.join("commondir"))
+            .ok()
+            .map(|common| git_dir.join(common.trim()))
+            .and_then(|common| common.canonicalize().ok())
+            .unwrap_or_else(|| git_dir.to_path_buf())
+    }
+
+    /// Get the hooks directory shared by all worktrees.
+    #[must_use]
+    pub fn hooks_dir(&self) -> PathBuf {
+        self.common_dir().join("hooks")
+    }
+
+    /// Read a string value from the effective Git config.
+    #[must_use]
+    pub fn config_string(&self, key: &str) -> Option<String> {
+        self.repo.config().ok()?.get_string(key).ok()
+    }
+
+    /// Read a boolean value from the effective Git config.
+    #[must_use]
+    pub fn config_bool(&self, key: &str) -> Option<bool> {
+        self.repo.config().ok()?.get_bool(key).ok()
+    }
+
     /// Get the remote URL for the given remote name.
     #[must_use]
     pub fn remote_url(&self, name: &str) -> Option<String> {
@@ -235,6 +306,76 @@ impl GitRepository {
         (staged, unstaged, untracked)
     }
 
+    /// Get the stash list, most recent first.
+    #[must_use]
+    pub fn stashes(&self) -> Vec<StashInfo> {
+        // The stash list is the reflog of refs/stash, which only needs a
+        // shared reference unlike `Repository::stash_foreach`
+        let Ok(reflog) = self.repo.reflog("refs/stash") else {
+            return Vec::new();
+        };
+
+        reflog
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| StashInfo {
+                index,
+                message: entry.message().unwrap_or_default().to_string(),
+                id: short_id(entry.id_new()),
+            })
+            .collect()
+    }
+
+    /// Get up to `n` commits reachable from HEAD, newest first.
+    ///
+    /// Empty for a repository with no commits yet.
+    #[must_use]
+    pub fn recent_commits(&self, n: usize) -> Vec<CommitInfo> {
+        let Ok(mut revwalk) = self.repo.revwalk() else {
+            return Vec::new();
+        };
+        if revwalk.push_head().is_err() {
+            return Vec::new();
+        }
+
+        revwalk
+            .filter_map(std::result::Result::ok)
+            .filter_map(|oid| self.repo.find_commit(oid).ok())
+            .take(n)
+            .map(|commit| CommitInfo {
+                id: short_id(commit.id()),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                author: commit.author().name().unwrap_or_default().to_string(),
+                time: commit.time().seconds(),
+            })
+            .collect()
+    }
+
+    /// Check whether the index has unresolved merge conflicts.
+    #[must_use]
+    pub fn has_conflicts(&self) -> bool {
+        self.repo.index().is_ok_and(|index| index.has_conflicts())
+    }
+
+    /// Get the git command for the operation in progress, if any.
+    ///
+    /// Returns `"merge"`, `"rebase"`, `"cherry-pick"`, or `"revert"`, as
+    /// used in `git <operation> --continue`.
+    #[must_use]
+    pub fn operation_in_progress(&self) -> Option<&'static str> {
+        match self.repo.state() {
+            RepositoryState::Merge => Some("merge"),
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Some("rebase"),
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                Some("cherry-pick")
+            }
+            RepositoryState::Revert | RepositoryState::RevertSequence => Some("revert"),
+            _ => None,
+        }
+    }
+
     /// Get complete Git information.
     #[must_use]
     pub fn info(&self) -> GitInfo {
@@ -261,6 +402,13 @@ impl GitRepository {
     }
 }
 
+/// Abbreviate a commit id.
+fn short_id(oid: git2::Oid) -> String {
+    let mut id = oid.to_string();
+    id.truncate(SHORT_ID_LEN);
+    id
+}
+
 /// Discover Git repository from the current directory.
 #[must_use]
 pub fn discover_repo() -> Option<GitRepository> {
@@ -352,6 +500,81 @@ mod tests {
         assert_eq!(detached.branch_display(), "HEAD");
     }
 
+    /// Create a repository with one commit per message.
+    fn repo_with_commits(messages: &[&str]) -> (tempfile::TempDir, Repository) {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+
+        for (i, message) in messages.iter().enumerate() {
+            std::fs::write(temp.path().join("file.txt"), i.to_string()).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("file.txt")).unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+            let parents: Vec<_> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents).unwrap();
+        }
+
+        (temp, repo)
+    }
+
+    #[test]
+    fn test_fresh_repo_has_no_state() {
+        let (temp, _) = repo_with_commits(&[]);
+        let repo = GitRepository::open(temp.path()).unwrap();
+
+        assert_eq!(repo.stashes(), Vec::new());
+        assert_eq!(repo.recent_commits(5), Vec::new());
+        assert!(!repo.has_conflicts());
+        assert_eq!(repo.operation_in_progress(), None);
+        assert_eq!(repo.current_branch(), None);
+    }
+
+    #[test]
+    fn test_recent_commits_and_detached_head() {
+        let (temp, raw) = repo_with_commits(&["first", "second\n\nbody", "third"]);
+        let repo = GitRepository::open(temp.path()).unwrap();
+
+        let commits = repo.recent_commits(2);
+        let summaries: Vec<_> = commits.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["third", "second"]);
+        assert_eq!(commits[0].author, "Test");
+        assert_eq!(commits[0].id.len(), SHORT_ID_LEN);
+
+        let head = raw.head().unwrap().target().unwrap();
+        raw.set_head_detached(head).unwrap();
+        assert!(repo.is_detached());
+        assert_eq!(repo.recent_commits(10).len(), 3);
+    }
+
+    #[test]
+    fn test_stashes() {
+        let (temp, _) = repo_with_commits(&["init"]);
+        let mut repo = GitRepository::open(temp.path()).unwrap();
+
+        std::fs::write(temp.path().join("file.txt"), "first change").unwrap();
+        repo.stash("first").unwrap();
+        std::fs::write(temp.path().join("file.txt"), "second change").unwrap();
+        repo.stash("second").unwrap();
+
+        let stashes = repo.stashes();
+        assert_eq!(stashes.len(), 2);
+        assert_eq!(stashes[0].index, 0);
+        assert!(stashes[0].message.ends_with("second"));
+        assert!(stashes[1].message.ends_with("first"));
+    }
+
+    #[test]
+    fn test_merge_in_progress() {
+        let (temp, raw) = repo_with_commits(&["init"]);
+        let head = raw.head().unwrap().target().unwrap();
+        std::fs::write(raw.path().join("MERGE_HEAD"), format!("{head}\n")).unwrap();
+
+        let repo = GitRepository::open(temp.path()).unwrap();
+        assert_eq!(repo.operation_in_progress(), Some("merge"));
+    }
+
     #[test]
     fn test_discover_repo_from_current_dir() {
         // This test will work if run from within a git repo
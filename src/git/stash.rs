@@ -0,0 +1,192 @@
+//! Stash-and-run support.
+//!
+//! Runs an operation against a clean working tree by stashing local
+//! changes first and restoring them afterwards.
+
+use anyhow::{Context, Result};
+
+use super::GitRepository;
+
+/// Message used for stashes created by Palrun.
+pub const STASH_MESSAGE: &str = "palrun: stash before exec --clean";
+
+/// A working tree that can be stashed and restored.
+pub trait WorkingTree {
+    /// Whether the tree has staged, unstaged, or untracked changes.
+    fn is_dirty(&self) -> bool;
+
+    /// Stash all local changes, including untracked files.
+    fn stash(&mut self, message: &str) -> Result<()>;
+
+    /// Restore the most recent stash.
+    fn pop(&mut self) -> Result<()>;
+}
+
+impl WorkingTree for GitRepository {
+    fn is_dirty(&self) -> bool {
+        let (staged, unstaged, untracked) = self.status_counts();
+        staged + unstaged + untracked > 0
+    }
+
+    fn stash(&mut self, message: &str) -> Result<()> {
+        let signature = self
+            .repo
+            .signature()
+            .or_else(|_| git2::Signature::now("palrun", "palrun@localhost"))
+            .context("Failed to create stash signature")?;
+
+        self.repo
+            .stash_save(&signature, message, Some(git2::StashFlags::INCLUDE_UNTRACKED))
+            .context("Failed to stash changes")?;
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<()> {
+        self.repo.stash_pop(0, None).context("Failed to restore stashed changes")
+    }
+}
+
+/// Outcome of [`run_clean`].
+#[derive(Debug)]
+pub struct CleanRun<T> {
+    /// Value returned by the operation.
+    pub output: T,
+
+    /// Whether changes were stashed (and restored) around the operation.
+    pub stashed: bool,
+}
+
+/// Run `operation` on a clean tree, stashing and restoring changes if dirty.
+///
+/// If stashing fails the operation is not run. If restoring fails the
+/// error says where the changes were left, so nothing is silently lost.
+pub fn run_clean<W, T>(tree: &mut W, operation: impl FnOnce() -> Result<T>) -> Result<CleanRun<T>>
+where
+    W: WorkingTree,
+{
+    if !tree.is_dirty() {
+        return Ok(CleanRun { output: operation()?, stashed: false });
+    }
+
+    tree.stash(STASH_MESSAGE).context("Aborting: could not stash local changes")?;
+
+    // Restore the stash even if the operation itself failed
+    let output = operation();
+
+    tree.pop().with_context(|| {
+        format!(
+            "Your changes are still stashed as \"{STASH_MESSAGE}\". \
+             Run `git stash pop` to restore them."
+        )
+    })?;
+
+    Ok(CleanRun { output: output?, stashed: true })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct MockTree {
+        dirty: bool,
+        fail_stash: bool,
+        fail_pop: bool,
+        calls: Vec<&'static str>,
+    }
+
+    impl WorkingTree for MockTree {
+        fn is_dirty(&self) -> bool {
+            self.dirty
+        }
+
+        fn stash(&mut self, _message: &str) -> Result<()> {
+            self.calls.push("stash");
+            if self.fail_stash {
+                anyhow::bail!("stash failed");
+            }
+            self.dirty = false;
+            Ok(())
+        }
+
+        fn pop(&mut self) -> Result<()> {
+            self.calls.push("pop");
+            if self.fail_pop {
+                anyhow::bail!("conflict");
+            }
+            self.dirty = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_clean_tree_runs_without_stash() {
+        let mut tree = MockTree::default();
+        let run = run_clean(&mut tree, || Ok(42)).unwrap();
+
+        assert_eq!(run.output, 42);
+        assert!(!run.stashed);
+        assert_eq!(tree.calls, Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_dirty_tree_stashes_and_pops() {
+        let mut tree = MockTree { dirty: true, ..Default::default() };
+        let run = run_clean(&mut tree, || Ok("built")).unwrap();
+
+        assert_eq!(run.output, "built");
+        assert!(run.stashed);
+        assert_eq!(tree.calls, vec!["stash", "pop"]);
+        assert!(tree.dirty);
+    }
+
+    #[test]
+    fn test_stash_failure_skips_operation() {
+        let mut tree = MockTree { dirty: true, fail_stash: true, ..Default::default() };
+        let mut ran = false;
+        let result = run_clean(&mut tree, || {
+            ran = true;
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert!(!ran);
+        assert_eq!(tree.calls, vec!["stash"]);
+    }
+
+    #[test]
+    fn test_pop_runs_after_failed_operation() {
+        let mut tree = MockTree { dirty: true, ..Default::default() };
+        let result: Result<CleanRun<()>> = run_clean(&mut tree, || anyhow::bail!("build failed"));
+
+        assert!(result.unwrap_err().to_string().contains("build failed"));
+        assert_eq!(tree.calls, vec!["stash", "pop"]);
+    }
+
+    #[test]
+    fn test_pop_failure_reports_stash() {
+        let mut tree = MockTree { dirty: true, fail_pop: true, ..Default::default() };
+        let err = run_clean(&mut tree, || Ok(())).unwrap_err();
+
+        assert!(format!("{err:#}").contains("git stash pop"));
+    }
+
+    #[test]
+    fn test_git_repository_round_trip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[]).unwrap();
+
+        let file = temp.path().join("wip.txt");
+        std::fs::write(&file, "work in progress").unwrap();
+
+        let mut repo = GitRepository::open(temp.path()).unwrap();
+        let run = run_clean(&mut repo, || Ok(file.exists())).unwrap();
+
+        assert!(run.stashed);
+        assert!(!run.output, "file should be stashed while the operation runs");
+        assert!(file.exists());
+    }
+}
@@ -43,6 +43,21 @@ impl ProjectType {
         }
     }
 
+    /// Short lowercase name, used in generated file names.
+    pub fn id(&self) -> &str {
+        match self {
+            Self::NodeJs => "node",
+            Self::NextJs => "nextjs",
+            Self::React => "react",
+            Self::Rust => "rust",
+            Self::Go => "go",
+            Self::Python => "python",
+            Self::NxMonorepo => "nx",
+            Self::Turborepo => "turbo",
+            Self::Generic => "generic",
+        }
+    }
+
     /// Get recommended scanners for this project type.
     pub fn recommended_scanners(&self) -> Vec<&str> {
         match self {
@@ -91,6 +106,34 @@ impl ProjectType {
     }
 }
 
+/// Files that signal a project type, with how strongly.
+///
+/// Lockfiles weigh more than manifests, and manifests more than loose files
+/// like `requirements.txt`: a lockfile shows the toolchain is actually used.
+type Signals = &'static [(&'static str, u32)];
+
+const NODE_SIGNALS: Signals = &[
+    ("package.json", 2),
+    ("package-lock.json", 3),
+    ("yarn.lock", 3),
+    ("pnpm-lock.yaml", 3),
+    ("bun.lockb", 3),
+];
+const NEXTJS_SIGNALS: Signals =
+    &[("next.config.js", 2), ("next.config.mjs", 2), ("next.config.ts", 2)];
+const NX_SIGNALS: Signals = &[("nx.json", 2)];
+const TURBO_SIGNALS: Signals = &[("turbo.json", 2)];
+const RUST_SIGNALS: Signals = &[("Cargo.toml", 2), ("Cargo.lock", 3)];
+const GO_SIGNALS: Signals = &[("go.mod", 2), ("go.sum", 3)];
+const PYTHON_SIGNALS: Signals = &[
+    ("pyproject.toml", 2),
+    ("setup.py", 2),
+    ("requirements.txt", 1),
+    ("poetry.lock", 3),
+    ("uv.lock", 3),
+    ("Pipfile.lock", 3),
+];
+
 /// Project type detector.
 pub struct ProjectDetector<'a> {
     path: &'a Path,
@@ -102,72 +145,80 @@ impl<'a> ProjectDetector<'a> {
         Self { path }
     }
 
-    /// Detect the project type.
-    pub fn detect(&self) -> Result<ProjectType> {
+    /// Detect every project type, strongest signal first.
+    ///
+    /// A JavaScript project is reported once, as its most specific type
+    /// (Next.js, Nx, Turborepo, React, then plain Node.js). Types with equal
+    /// signals keep that order, with Rust, Go, and Python after the
+    /// frameworks and before React and Node.js. The list is never empty:
+    /// [`ProjectType::Generic`] is returned only when nothing else matched.
+    pub fn detect(&self) -> Result<Vec<ProjectType>> {
+        let node = self.score(NODE_SIGNALS);
+        let mut detected = Vec::new();
+
         // Check for specific frameworks first
         if self.is_nextjs() {
-            return Ok(ProjectType::NextJs);
-        }
-
-        if self.is_nx_monorepo() {
-            return Ok(ProjectType::NxMonorepo);
-        }
-
-        if self.is_turborepo() {
-            return Ok(ProjectType::Turborepo);
+            detected.push((ProjectType::NextJs, node + self.score(NEXTJS_SIGNALS)));
+        } else if self.is_nx_monorepo() {
+            detected.push((ProjectType::NxMonorepo, node + self.score(NX_SIGNALS)));
+        } else if self.is_turborepo() {
+            detected.push((ProjectType::Turborepo, node + self.score(TURBO_SIGNALS)));
         }
+        let has_framework = !detected.is_empty();
 
         // Check for language-specific projects
-        if self.is_rust() {
-            return Ok(ProjectType::Rust);
+        for (project_type, signals) in [
+            (ProjectType::Rust, RUST_SIGNALS),
+            (ProjectType::Go, GO_SIGNALS),
+            (ProjectType::Python, PYTHON_SIGNALS),
+        ] {
+            let score = self.score(signals);
+            if score > 0 {
+                detected.push((project_type, score));
+            }
         }
 
-        if self.is_go() {
-            return Ok(ProjectType::Go);
+        if !has_framework {
+            if self.is_react() {
+                detected.push((ProjectType::React, node));
+            } else if self.is_nodejs() {
+                detected.push((ProjectType::NodeJs, node));
+            }
         }
 
-        if self.is_python() {
-            return Ok(ProjectType::Python);
-        }
+        // Stable, so equal signals keep the order above
+        detected.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
 
-        if self.is_react() {
-            return Ok(ProjectType::React);
+        if detected.is_empty() {
+            return Ok(vec![ProjectType::Generic]);
         }
+        Ok(detected.into_iter().map(|(project_type, _)| project_type).collect())
+    }
 
-        if self.is_nodejs() {
-            return Ok(ProjectType::NodeJs);
-        }
+    /// Detect the project type with the strongest signal.
+    pub fn primary(&self) -> Result<ProjectType> {
+        Ok(self.detect()?[0])
+    }
 
-        // Default to generic
-        Ok(ProjectType::Generic)
+    /// Total weight of the signal files present.
+    fn score(&self, signals: Signals) -> u32 {
+        signals
+            .iter()
+            .filter(|(file, _)| self.path.join(file).exists())
+            .map(|(_, weight)| weight)
+            .sum()
     }
 
     fn is_nextjs(&self) -> bool {
-        self.path.join("next.config.js").exists()
-            || self.path.join("next.config.mjs").exists()
-            || self.path.join("next.config.ts").exists()
+        self.score(NEXTJS_SIGNALS) > 0
     }
 
     fn is_nx_monorepo(&self) -> bool {
-        self.path.join("nx.json").exists()
+        self.score(NX_SIGNALS) > 0
     }
 
     fn is_turborepo(&self) -> bool {
-        self.path.join("turbo.json").exists()
-    }
-
-    fn is_rust(&self) -> bool {
-        self.path.join("Cargo.toml").exists()
-    }
-
-    fn is_go(&self) -> bool {
-        self.path.join("go.mod").exists()
-    }
-
-    fn is_python(&self) -> bool {
-        self.path.join("pyproject.toml").exists()
-            || self.path.join("setup.py").exists()
-            || self.path.join("requirements.txt").exists()
+        self.score(TURBO_SIGNALS) > 0
     }
 
     fn is_react(&self) -> bool {
@@ -182,3 +233,63 @@ impl<'a> ProjectDetector<'a> {
         self.path.join("package.json").exists()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(files: &[&str]) -> Vec<ProjectType> {
+        let temp = tempfile::TempDir::new().unwrap();
+        for file in files {
+            std::fs::write(temp.path().join(file), "{}").unwrap();
+        }
+        ProjectDetector::new(temp.path()).detect().unwrap()
+    }
+
+    #[test]
+    fn test_detect_rust_and_node() {
+        assert_eq!(
+            detect(&["Cargo.toml", "package.json"]),
+            [ProjectType::Rust, ProjectType::NodeJs]
+        );
+
+        // Lockfiles outweigh a bare manifest
+        assert_eq!(
+            detect(&["Cargo.toml", "package.json", "package-lock.json"]),
+            [ProjectType::NodeJs, ProjectType::Rust]
+        );
+        assert_eq!(
+            detect(&["Cargo.toml", "Cargo.lock", "package.json", "yarn.lock", "requirements.txt"]),
+            [ProjectType::Rust, ProjectType::NodeJs, ProjectType::Python]
+        );
+    }
+
+    #[test]
+    fn test_javascript_reported_once() {
+        assert_eq!(
+            detect(&["package.json", "next.config.js", "go.mod"]),
+            [ProjectType::NextJs, ProjectType::Go]
+        );
+        assert_eq!(
+            detect(&["package.json", "pnpm-lock.yaml", "nx.json"]),
+            [ProjectType::NxMonorepo]
+        );
+    }
+
+    #[test]
+    fn test_generic_only_when_nothing_matches() {
+        assert_eq!(detect(&[]), [ProjectType::Generic]);
+        assert_eq!(detect(&["README.md"]), [ProjectType::Generic]);
+        assert!(!detect(&["go.mod"]).contains(&ProjectType::Generic));
+    }
+
+    #[test]
+    fn test_primary() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("pyproject.toml"), "").unwrap();
+        std::fs::write(temp.path().join("go.mod"), "").unwrap();
+        std::fs::write(temp.path().join("go.sum"), "").unwrap();
+
+        assert_eq!(ProjectDetector::new(temp.path()).primary().unwrap(), ProjectType::Go);
+    }
+}
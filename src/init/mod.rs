@@ -4,9 +4,11 @@
 
 mod detector;
 mod runbooks;
+mod shell;
 mod templates;
 
 pub use detector::{ProjectDetector, ProjectType};
+pub use shell::{detect_shell, init_script, shell_from_name, SUPPORTED_SHELLS};
 
 use std::fs;
 use std::io::{self, Write};
@@ -14,7 +16,7 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 
-use crate::core::Config;
+use crate::core::{write_file_atomic, Config};
 
 /// Options for project setup.
 #[derive(Debug, Clone)]
@@ -37,11 +39,12 @@ impl Default for SetupOptions {
 pub fn setup_project(path: &Path, options: SetupOptions) -> Result<()> {
     println!("🔍 Detecting project type...\n");
 
-    // Detect project type
+    // Detect project types, primary first
     let detector = ProjectDetector::new(path);
-    let project_type = detector.detect()?;
+    let project_types = detector.detect()?;
 
-    println!("✓ Detected: {}\n", project_type.display_name());
+    let names: Vec<&str> = project_types.iter().map(ProjectType::display_name).collect();
+    println!("✓ Detected: {}\n", names.join(", "));
 
     // Check if .palrun.toml already exists
     let config_path = path.join(".palrun.toml");
@@ -64,7 +67,7 @@ pub fn setup_project(path: &Path, options: SetupOptions) -> Result<()> {
 
     // Generate configuration
     println!("📝 Generating configuration...\n");
-    let config_content = templates::generate_config(project_type)?;
+    let config_content = templates::generate_config(&project_types)?;
 
     // Validate the generated config
     let _config: Config = toml::from_str(&config_content)
@@ -89,7 +92,7 @@ pub fn setup_project(path: &Path, options: SetupOptions) -> Result<()> {
     }
 
     // Generate sample runbooks
-    let sample_runbooks = runbooks::generate_samples(project_type)?;
+    let sample_runbooks = runbooks::generate_samples(&project_types)?;
     for (name, content) in &sample_runbooks {
         let runbook_path = runbooks_dir.join(name);
         if options.dry_run {
@@ -114,7 +117,7 @@ pub fn setup_project(path: &Path, options: SetupOptions) -> Result<()> {
     println!("  4. Run 'palrun runbook <name>' to execute a runbook");
 
     // Show suggestions
-    let suggestions = get_suggestions(project_type);
+    let suggestions = get_suggestions(&project_types);
     if !suggestions.is_empty() {
         println!("\n💡 Suggested next steps:");
         for suggestion in suggestions {
@@ -125,46 +128,26 @@ pub fn setup_project(path: &Path, options: SetupOptions) -> Result<()> {
     Ok(())
 }
 
-/// Write a file atomically (write to temp, then rename).
-fn write_file_atomic(path: &Path, content: &str) -> Result<()> {
-    let temp_path = path.with_extension("tmp");
-
-    // Write to temp file
-    fs::write(&temp_path, content)
-        .with_context(|| format!("Failed to write to {}", temp_path.display()))?;
-
-    // Rename to final location (atomic on most systems)
-    fs::rename(&temp_path, path).with_context(|| {
-        format!("Failed to rename {} to {}", temp_path.display(), path.display())
-    })?;
-
-    Ok(())
-}
-
-/// Get suggestions for next steps based on project type.
-fn get_suggestions(project_type: ProjectType) -> Vec<String> {
+/// Get suggestions for next steps based on the project types.
+fn get_suggestions(project_types: &[ProjectType]) -> Vec<String> {
     let mut suggestions = vec![
         "Set up shell integration: eval \"$(palrun init bash)\"".to_string(),
         "Try running: palrun".to_string(),
     ];
 
-    match project_type {
-        ProjectType::NodeJs | ProjectType::NextJs | ProjectType::React => {
-            suggestions.push("Run: palrun runbook deploy".to_string());
-        }
-        ProjectType::Rust => {
-            suggestions.push("Run: palrun runbook build".to_string());
-        }
-        ProjectType::Go => {
-            suggestions.push("Run: palrun runbook build".to_string());
-        }
-        ProjectType::Python => {
-            suggestions.push("Run: palrun runbook test".to_string());
-        }
-        ProjectType::NxMonorepo | ProjectType::Turborepo => {
-            suggestions.push("Run: palrun runbook build-all".to_string());
+    for project_type in project_types {
+        let suggestion = match project_type {
+            ProjectType::NodeJs | ProjectType::NextJs | ProjectType::React => {
+                "Run: palrun runbook deploy"
+            }
+            ProjectType::Rust | ProjectType::Go => "Run: palrun runbook build",
+            ProjectType::Python => "Run: palrun runbook test",
+            ProjectType::NxMonorepo | ProjectType::Turborepo => "Run: palrun runbook build-all",
+            ProjectType::Generic => continue,
+        };
+        if !suggestions.iter().any(|s| s == suggestion) {
+            suggestions.push(suggestion.to_string());
         }
-        ProjectType::Generic => {}
     }
 
     suggestions
@@ -3,38 +3,43 @@
 use super::ProjectType;
 use anyhow::Result;
 
-/// Generate sample runbooks for the given project type.
+/// Generate sample runbooks for the detected project types.
 /// Returns a vector of (filename, content) pairs.
-pub fn generate_samples(project_type: ProjectType) -> Result<Vec<(String, String)>> {
-    let mut runbooks = Vec::new();
+///
+/// When two types have a runbook with the same file name, the first type
+/// keeps it and the later one is prefixed with its type, like
+/// `go-build.yml`.
+pub fn generate_samples(project_types: &[ProjectType]) -> Result<Vec<(String, String)>> {
+    let mut runbooks: Vec<(String, String)> = Vec::new();
+
+    for &project_type in project_types {
+        for &(name, content) in samples(project_type) {
+            let name = if runbooks.iter().any(|(existing, _)| existing == name) {
+                format!("{}-{name}", project_type.id())
+            } else {
+                name.to_string()
+            };
+            runbooks.push((name, content.to_string()));
+        }
+    }
 
+    Ok(runbooks)
+}
+
+/// Sample runbooks for one project type, as (filename, content) pairs.
+fn samples(project_type: ProjectType) -> &'static [(&'static str, &'static str)] {
     match project_type {
         ProjectType::NodeJs | ProjectType::React | ProjectType::NextJs => {
-            runbooks.push(("deploy.yml".to_string(), NODEJS_DEPLOY.to_string()));
-            runbooks.push(("dev-setup.yml".to_string(), NODEJS_DEV_SETUP.to_string()));
-        }
-        ProjectType::Rust => {
-            runbooks.push(("build.yml".to_string(), RUST_BUILD.to_string()));
-            runbooks.push(("test.yml".to_string(), RUST_TEST.to_string()));
-        }
-        ProjectType::Go => {
-            runbooks.push(("build.yml".to_string(), GO_BUILD.to_string()));
-            runbooks.push(("test.yml".to_string(), GO_TEST.to_string()));
-        }
-        ProjectType::Python => {
-            runbooks.push(("test.yml".to_string(), PYTHON_TEST.to_string()));
-            runbooks.push(("dev-setup.yml".to_string(), PYTHON_DEV_SETUP.to_string()));
+            &[("deploy.yml", NODEJS_DEPLOY), ("dev-setup.yml", NODEJS_DEV_SETUP)]
         }
+        ProjectType::Rust => &[("build.yml", RUST_BUILD), ("test.yml", RUST_TEST)],
+        ProjectType::Go => &[("build.yml", GO_BUILD), ("test.yml", GO_TEST)],
+        ProjectType::Python => &[("test.yml", PYTHON_TEST), ("dev-setup.yml", PYTHON_DEV_SETUP)],
         ProjectType::NxMonorepo | ProjectType::Turborepo => {
-            runbooks.push(("build-all.yml".to_string(), MONOREPO_BUILD_ALL.to_string()));
-            runbooks.push(("deploy.yml".to_string(), NODEJS_DEPLOY.to_string()));
-        }
-        ProjectType::Generic => {
-            runbooks.push(("example.yml".to_string(), GENERIC_EXAMPLE.to_string()));
+            &[("build-all.yml", MONOREPO_BUILD_ALL), ("deploy.yml", NODEJS_DEPLOY)]
         }
+        ProjectType::Generic => &[("example.yml", GENERIC_EXAMPLE)],
     }
-
-    Ok(runbooks)
 }
 
 /// Generic example runbook
@@ -249,3 +254,20 @@ steps:
     description: Run all tests
     optional: true
 ";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_cover_all_types() {
+        let runbooks =
+            generate_samples(&[ProjectType::Rust, ProjectType::Go, ProjectType::NodeJs]).unwrap();
+        let names: Vec<_> = runbooks.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["build.yml", "test.yml", "go-build.yml", "go-test.yml", "deploy.yml", "dev-setup.yml"]
+        );
+        assert_eq!(runbooks[2].1, GO_BUILD);
+    }
+}
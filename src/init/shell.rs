@@ -0,0 +1,109 @@
+//! Shell integration scripts for `pal init`.
+//!
+//! Also works out which script to emit when no shell is named, so
+//! `eval "$(pal init)"` does the right thing.
+
+/// Shells with an integration script, as listed in error messages.
+pub const SUPPORTED_SHELLS: &str = "bash, zsh, fish, powershell, nu, elvish";
+
+/// Get the integration script for a shell name such as `zsh` or `pwsh`.
+pub fn init_script(shell: &str) -> Option<&'static str> {
+    let script = match shell_from_name(shell)? {
+        "bash" => include_str!("../../shell/palrun.bash"),
+        "zsh" => include_str!("../../shell/palrun.zsh"),
+        "fish" => include_str!("../../shell/palrun.fish"),
+        "powershell" => include_str!("../../shell/palrun.ps1"),
+        "nu" => include_str!("../../shell/palrun.nu"),
+        "elvish" => include_str!("../../shell/palrun.elv"),
+        _ => return None,
+    };
+    Some(script)
+}
+
+/// Map a shell executable name or path to a supported shell.
+///
+/// Accepts full paths (`/usr/bin/zsh`), login-shell names (`-bash`),
+/// and Windows executables (`pwsh.exe`).
+pub fn shell_from_name(name: &str) -> Option<&'static str> {
+    let name = name.trim().rsplit(['/', '\\']).next()?;
+    let name = name.trim_start_matches('-').to_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+
+    match name {
+        "bash" => Some("bash"),
+        "zsh" => Some("zsh"),
+        "fish" => Some("fish"),
+        "pwsh" | "powershell" => Some("powershell"),
+        "nu" | "nushell" => Some("nu"),
+        "elvish" => Some("elvish"),
+        _ => None,
+    }
+}
+
+/// Detect the shell that invoked Palrun.
+///
+/// The parent process is checked first since it reflects the shell
+/// actually running, then `$SHELL` (the login shell).
+pub fn detect_shell() -> Option<&'static str> {
+    parent_process_name()
+        .as_deref()
+        .and_then(shell_from_name)
+        .or_else(|| std::env::var("SHELL").ok().as_deref().and_then(shell_from_name))
+}
+
+/// Name of the parent process, where the platform exposes it cheaply.
+#[cfg(target_os = "linux")]
+fn parent_process_name() -> Option<String> {
+    let ppid = std::os::unix::process::parent_id();
+    std::fs::read_to_string(format!("/proc/{ppid}/comm")).ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn parent_process_name() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_from_common_values() {
+        assert_eq!(shell_from_name("/bin/bash"), Some("bash"));
+        assert_eq!(shell_from_name("/usr/local/bin/bash"), Some("bash"));
+        assert_eq!(shell_from_name("/bin/zsh"), Some("zsh"));
+        assert_eq!(shell_from_name("/opt/homebrew/bin/fish"), Some("fish"));
+        assert_eq!(shell_from_name("/usr/bin/pwsh"), Some("powershell"));
+        assert_eq!(shell_from_name("zsh\n"), Some("zsh"));
+        assert_eq!(shell_from_name("/usr/bin/nu"), Some("nu"));
+        assert_eq!(shell_from_name("/usr/bin/elvish"), Some("elvish"));
+    }
+
+    #[test]
+    fn test_shell_from_login_and_windows_names() {
+        assert_eq!(shell_from_name("-bash"), Some("bash"));
+        assert_eq!(shell_from_name("-zsh"), Some("zsh"));
+        assert_eq!(shell_from_name(r"C:\Program Files\PowerShell\7\pwsh.exe"), Some("powershell"));
+        assert_eq!(shell_from_name("powershell.exe"), Some("powershell"));
+    }
+
+    #[test]
+    fn test_init_script() {
+        for shell in ["bash", "zsh", "fish", "powershell", "pwsh", "nu", "nushell", "elvish"] {
+            let script = init_script(shell).unwrap();
+            assert!(!script.trim().is_empty(), "{shell} script is empty");
+        }
+
+        assert!(init_script("nu").unwrap().contains("alias pal = palrun"));
+        assert!(init_script("elvish").unwrap().contains("edit:insert:binding"));
+        assert_eq!(init_script("tcsh"), None);
+    }
+
+    #[test]
+    fn test_unsupported_shells() {
+        assert_eq!(shell_from_name("/bin/sh"), None);
+        assert_eq!(shell_from_name("/bin/dash"), None);
+        assert_eq!(shell_from_name("cargo"), None);
+        assert_eq!(shell_from_name(""), None);
+    }
+}
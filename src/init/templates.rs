@@ -1,11 +1,69 @@
 //! Configuration template generation.
 
 use super::ProjectType;
-use anyhow::Result;
+use anyhow::{Context, Result};
+
+/// Generate a configuration file for the detected project types.
+///
+/// The first type's template is used, with its scanner settings widened to
+/// cover the others: their scanners and ignored directories are added, the
+/// deepest `max_depth` is kept, and scanning is recursive if any of them
+/// is.
+pub fn generate_config(project_types: &[ProjectType]) -> Result<String> {
+    let (primary, others) = project_types.split_first().unwrap_or((&ProjectType::Generic, &[]));
+    let base = template(*primary);
+    if others.is_empty() {
+        return Ok(base.to_string());
+    }
+
+    let mut enabled: Vec<String> = Vec::new();
+    let mut ignore_dirs: Vec<String> = Vec::new();
+    let mut max_depth = 0;
+    let mut recursive = false;
+    for &project_type in project_types {
+        let config: toml::Table = template(project_type).parse()?;
+        let scanner = config
+            .get("scanner")
+            .and_then(toml::Value::as_table)
+            .context("template has no [scanner] section")?;
+
+        for (list, key) in [(&mut enabled, "enabled"), (&mut ignore_dirs, "ignore_dirs")] {
+            let values = scanner.get(key).and_then(toml::Value::as_array).into_iter().flatten();
+            for value in values.filter_map(toml::Value::as_str) {
+                if !list.iter().any(|v| v == value) {
+                    list.push(value.to_string());
+                }
+            }
+        }
+        max_depth =
+            max_depth.max(scanner.get("max_depth").and_then(toml::Value::as_integer).unwrap_or(5));
+        recursive |= scanner.get("recursive").and_then(toml::Value::as_bool).unwrap_or(false);
+    }
+
+    let mut scanner = toml::Table::new();
+    scanner.insert("enabled".into(), enabled.into());
+    scanner.insert("ignore_dirs".into(), ignore_dirs.into());
+    scanner.insert("max_depth".into(), max_depth.into());
+    scanner.insert("recursive".into(), recursive.into());
+
+    let mut config: toml::Table = base.parse()?;
+    config.insert("scanner".into(), scanner.into());
+
+    // Keep the template's header comments, retitled for all the types
+    let names: Vec<&str> = project_types.iter().map(ProjectType::display_name).collect();
+    let mut content = format!("# Palrun Configuration for {} Project\n", names.join(" + "));
+    for line in base.lines().skip(1).take_while(|line| line.starts_with('#')) {
+        content.push_str(line);
+        content.push('\n');
+    }
+    content.push('\n');
+    content.push_str(&toml::to_string_pretty(&config)?);
+    Ok(content)
+}
 
-/// Generate a configuration file for the given project type.
-pub fn generate_config(project_type: ProjectType) -> Result<String> {
-    let template = match project_type {
+/// The configuration template for a project type.
+fn template(project_type: ProjectType) -> &'static str {
+    match project_type {
         ProjectType::NodeJs => NODEJS_TEMPLATE,
         ProjectType::NextJs => NEXTJS_TEMPLATE,
         ProjectType::React => REACT_TEMPLATE,
@@ -15,9 +73,7 @@ pub fn generate_config(project_type: ProjectType) -> Result<String> {
         ProjectType::NxMonorepo => NX_TEMPLATE,
         ProjectType::Turborepo => TURBO_TEMPLATE,
         ProjectType::Generic => GENERIC_TEMPLATE,
-    };
-
-    Ok(template.to_string())
+    }
 }
 
 /// Generic/default template
@@ -360,3 +416,32 @@ up = "up"
 down = "down"
 clear = "ctrl+u"
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_type_uses_template() {
+        assert_eq!(generate_config(&[ProjectType::Go]).unwrap(), GO_TEMPLATE);
+        assert_eq!(generate_config(&[]).unwrap(), GENERIC_TEMPLATE);
+    }
+
+    #[test]
+    fn test_config_covers_all_types() {
+        let content = generate_config(&[ProjectType::Rust, ProjectType::NodeJs]).unwrap();
+        assert!(
+            content.starts_with("# Palrun Configuration for Rust/Cargo + Node.js/NPM Project\n")
+        );
+
+        let config: crate::core::Config = toml::from_str(&content).unwrap();
+        assert_eq!(config.scanner.enabled, ["cargo", "make", "docker", "taskfile", "npm"]);
+        assert!(config.scanner.ignore_dirs.iter().any(|dir| dir == "coverage"));
+        assert_eq!(config.keys.quit, "q");
+        assert_eq!(config.ui.theme, "default");
+
+        let table: toml::Table = content.parse().unwrap();
+        assert_eq!(table["scanner"]["recursive"].as_bool(), Some(true));
+        assert_eq!(table["scanner"]["max_depth"].as_integer(), Some(5));
+    }
+}
@@ -5,8 +5,15 @@
 
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Default page size for paginated requests.
+pub const DEFAULT_PER_PAGE: usize = 30;
+
+/// Largest page size the GitHub API allows.
+pub const MAX_PER_PAGE: usize = 100;
+
 /// GitHub Actions API client.
 pub struct GitHubActions {
     /// GitHub API base URL
@@ -134,6 +141,15 @@ impl std::fmt::Display for WorkflowStatus {
     }
 }
 
+impl std::str::FromStr for WorkflowStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(serde_json::Value::String(s.to_lowercase()))
+            .map_err(|_| format!("unknown workflow status: {s}"))
+    }
+}
+
 /// A GitHub Actions workflow run.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowRun {
@@ -187,6 +203,223 @@ pub struct Actor {
     pub avatar_url: Option<String>,
 }
 
+/// Filters and paging for [`GitHubActions::list_runs_with`].
+#[derive(Debug, Clone)]
+pub struct ListRunsOptions {
+    /// Only runs of this workflow
+    pub workflow_id: Option<u64>,
+
+    /// Only runs on this branch
+    pub branch: Option<String>,
+
+    /// Only runs created at or after this time
+    pub created_since: Option<DateTime<Utc>>,
+
+    /// Only runs with this status or conclusion (e.g. `failure`)
+    pub conclusion: Option<WorkflowStatus>,
+
+    /// Runs fetched per request, at most [`MAX_PER_PAGE`]
+    pub per_page: usize,
+
+    /// Stop paging once this many runs have been collected
+    pub max: Option<usize>,
+}
+
+impl Default for ListRunsOptions {
+    fn default() -> Self {
+        Self {
+            workflow_id: None,
+            branch: None,
+            created_since: None,
+            conclusion: None,
+            per_page: DEFAULT_PER_PAGE,
+            max: None,
+        }
+    }
+}
+
+impl ListRunsOptions {
+    /// Query parameters for the first page.
+    fn query(&self) -> Vec<(&'static str, String)> {
+        // Never fetch more per page than will be kept
+        let per_page = self.max.map_or(self.per_page, |max| max.min(self.per_page));
+        let mut query = vec![("per_page", per_page.clamp(1, MAX_PER_PAGE).to_string())];
+        if let Some(ref branch) = self.branch {
+            query.push(("branch", branch.clone()));
+        }
+        if let Some(since) = self.created_since {
+            query.push(("created", format!(">={}", since.format("%Y-%m-%dT%H:%M:%SZ"))));
+        }
+        if let Some(conclusion) = self.conclusion {
+            query.push(("status", conclusion.to_string()));
+        }
+        query
+    }
+}
+
+/// Parse a relative age such as `30m`, `12h`, `7d`, or `2w`.
+pub fn parse_age(spec: &str) -> Option<chrono::Duration> {
+    let spec = spec.trim();
+    let unit = spec.chars().last()?;
+    let amount: i64 = spec[..spec.len() - unit.len_utf8()].parse().ok()?;
+    match unit {
+        'm' => chrono::Duration::try_minutes(amount),
+        'h' => chrono::Duration::try_hours(amount),
+        'd' => chrono::Duration::try_days(amount),
+        'w' => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// The `rel="next"` URL from a `Link` response header.
+fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == "rel=\"next\"")
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+/// A job within a workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    /// Job ID
+    pub id: u64,
+
+    /// Job name, as shown in the run's summary
+    pub name: String,
+
+    /// Status of the job
+    pub status: WorkflowStatus,
+
+    /// Conclusion of the job (if completed)
+    pub conclusion: Option<WorkflowStatus>,
+}
+
+/// The log of one job, taken from a run's log archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobLog {
+    /// Job name
+    pub name: String,
+
+    /// Full log text
+    pub content: String,
+}
+
+impl JobLog {
+    /// The last `lines` lines of the log.
+    pub fn tail(&self, lines: usize) -> String {
+        let all: Vec<&str> = self.content.lines().collect();
+        all[all.len().saturating_sub(lines)..].join("\n")
+    }
+
+    /// Whether the runner marked an error in the log.
+    pub fn has_errors(&self) -> bool {
+        self.content.contains("##[error]")
+    }
+
+    /// Whether this is the log of the job named `job`.
+    ///
+    /// Archive file names drop characters that are not allowed in paths,
+    /// so those are ignored when comparing.
+    pub fn is_for(&self, job: &str) -> bool {
+        let clean = |s: &str| -> String {
+            s.chars()
+                .filter(|c| !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+                .collect()
+        };
+        clean(&self.name) == clean(job)
+    }
+}
+
+/// Decompress `bytes` if they are gzip data.
+///
+/// Storage hosts sometimes serve the archive gzip-encoded; the magic number
+/// is checked too, in case the `Content-Encoding` header was dropped.
+fn gunzip_if_needed(bytes: &[u8], gzipped: bool) -> GitHubResult<Vec<u8>> {
+    use std::io::Read;
+
+    if !gzipped && !bytes.starts_with(&[0x1f, 0x8b]) {
+        return Ok(bytes.to_vec());
+    }
+    let mut out = Vec::new();
+    flate2::read::GzDecoder::new(bytes)
+        .read_to_end(&mut out)
+        .map_err(|e| GitHubError::InvalidResponse(format!("bad gzip data: {e}")))?;
+    Ok(out)
+}
+
+/// Split a run's log archive into per-job logs.
+///
+/// The archive has a `<n>_<job>.txt` file with each job's full log, plus a
+/// `<job>/` directory of `<n>_<step>.txt` files. The full logs are used when
+/// present; otherwise each job's steps are joined in order.
+pub fn extract_job_logs(archive: &[u8]) -> GitHubResult<Vec<JobLog>> {
+    use std::collections::BTreeMap;
+    use std::io::Read;
+
+    let invalid = |e: zip::result::ZipError| GitHubError::InvalidResponse(e.to_string());
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(archive)).map_err(invalid)?;
+
+    let mut jobs: Vec<(u32, JobLog)> = Vec::new();
+    let mut steps: BTreeMap<String, Vec<(u32, String)>> = BTreeMap::new();
+    for index in 0..zip.len() {
+        let mut file = zip.by_index(index).map_err(invalid)?;
+        if file.is_dir() {
+            continue;
+        }
+        let path = file.name().to_string();
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)
+            .map_err(|e| GitHubError::InvalidResponse(format!("{path}: {e}")))?;
+        let content = String::from_utf8_lossy(&bytes).into_owned();
+
+        match path.rsplit_once('/') {
+            None => {
+                let (order, name) = split_numbered(&path);
+                jobs.push((order, JobLog { name, content }));
+            }
+            Some((dir, file_name)) => {
+                let (order, _) = split_numbered(file_name);
+                steps.entry(dir.to_string()).or_default().push((order, content));
+            }
+        }
+    }
+
+    if jobs.is_empty() {
+        return Ok(steps
+            .into_iter()
+            .map(|(name, mut parts)| {
+                parts.sort_by_key(|(order, _)| *order);
+                let content = parts.into_iter().map(|(_, text)| text).collect();
+                JobLog { name, content }
+            })
+            .collect());
+    }
+
+    jobs.sort_by_key(|(order, _)| *order);
+    Ok(jobs.into_iter().map(|(_, log)| log).collect())
+}
+
+/// Split `"3_Run tests.txt"` into `(3, "Run tests")`.
+fn split_numbered(file_name: &str) -> (u32, String) {
+    let stem = file_name.strip_suffix(".txt").unwrap_or(file_name);
+    if let Some((n, rest)) = stem.split_once('_') {
+        if let Ok(order) = n.parse() {
+            return (order, rest.to_string());
+        }
+    }
+    (u32::MAX, stem.to_string())
+}
+
+/// Response from listing jobs.
+#[derive(Debug, Deserialize)]
+struct JobsResponse {
+    jobs: Vec<Job>,
+}
+
 /// Response from listing workflows.
 #[derive(Debug, Deserialize)]
 struct WorkflowsResponse {
@@ -229,6 +462,10 @@ pub enum GitHubError {
     /// Invalid response
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
+
+    /// Offline mode is enabled
+    #[error("GitHub Actions is unavailable in offline mode")]
+    Offline,
 }
 
 /// Result type for GitHub operations.
@@ -260,6 +497,13 @@ impl GitHubActions {
         })
     }
 
+    /// Use a different API base URL, e.g. for GitHub Enterprise Server.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
     /// Create a client from environment variables.
     ///
     /// Uses `GITHUB_TOKEN` for authentication and `GITHUB_REPOSITORY` for owner/repo.
@@ -293,19 +537,43 @@ impl GitHubActions {
         format!("{}/repos/{}/{}", self.base_url, self.owner, self.repo)
     }
 
+    /// Fail fast when offline mode is enabled.
+    fn ensure_online() -> GitHubResult<()> {
+        if crate::core::is_offline_mode() {
+            return Err(GitHubError::Offline);
+        }
+        Ok(())
+    }
+
     /// Make an authenticated GET request.
     fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> GitHubResult<T> {
         let url = format!("{}{}", self.repo_url(), path);
+        self.get_page(&url).map(|(value, _)| value)
+    }
+
+    /// Make an authenticated GET request for a full URL, also returning the
+    /// URL of the next page from the `Link` header.
+    fn get_page<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> GitHubResult<(T, Option<String>)> {
+        Self::ensure_online()?;
 
         let response = self
             .client
-            .get(&url)
+            .get(url)
             .header("Authorization", format!("Bearer {}", self.token))
             .header("Accept", "application/vnd.github+json")
             .header("X-GitHub-Api-Version", "2022-11-28")
             .send()?;
 
-        self.handle_response(response)
+        let next = response
+            .headers()
+            .get(reqwest::header::LINK)
+            .and_then(|link| link.to_str().ok())
+            .and_then(next_page_url);
+
+        Ok((self.handle_response(response)?, next))
     }
 
     /// Make an authenticated POST request.
@@ -315,6 +583,7 @@ impl GitHubActions {
         path: &str,
         body: &B,
     ) -> GitHubResult<T> {
+        Self::ensure_online()?;
         let url = format!("{}{}", self.repo_url(), path);
 
         let response = self
@@ -339,18 +608,36 @@ impl GitHubActions {
         if status.is_success() {
             response.json().map_err(|e| GitHubError::InvalidResponse(e.to_string()))
         } else {
-            // Try to parse error message
-            let message = response.text().unwrap_or_else(|_| "Unknown error".to_string());
-
-            match status.as_u16() {
-                401 => Err(GitHubError::Auth(message)),
-                403 => {
-                    // Check if rate limited
-                    Err(GitHubError::RateLimited { reset_at: "unknown".to_string() })
-                }
-                404 => Err(GitHubError::NotFound(message)),
-                _ => Err(GitHubError::Api { status: status.as_u16(), message }),
+            Err(Self::error_for(response))
+        }
+    }
+
+    /// Turn an unsuccessful response into an error.
+    ///
+    /// A 429, or a 403 with no requests remaining, is a rate limit; the reset
+    /// time comes from the `x-ratelimit-reset` header when present.
+    fn error_for(response: reqwest::blocking::Response) -> GitHubError {
+        let status = response.status().as_u16();
+        let header = |name: &str| {
+            response.headers().get(name).and_then(|v| v.to_str().ok()).map(ToString::to_string)
+        };
+        let remaining = header("x-ratelimit-remaining");
+        let reset_at = header("x-ratelimit-reset")
+            .and_then(|reset| reset.parse::<i64>().ok())
+            .and_then(|reset| DateTime::from_timestamp(reset, 0))
+            .map_or_else(|| "unknown".to_string(), |reset| reset.to_rfc3339());
+
+        // Try to parse error message
+        let message = response.text().unwrap_or_else(|_| "Unknown error".to_string());
+
+        match status {
+            401 => GitHubError::Auth(message),
+            429 => GitHubError::RateLimited { reset_at },
+            403 if remaining.as_deref() == Some("0") || message.contains("rate limit") => {
+                GitHubError::RateLimited { reset_at }
             }
+            404 => GitHubError::NotFound(message),
+            _ => GitHubError::Api { status, message },
         }
     }
 
@@ -365,32 +652,51 @@ impl GitHubActions {
         self.get(&format!("/actions/workflows/{workflow_id}"))
     }
 
-    /// List workflow runs, optionally filtered.
+    /// List up to `limit` of the most recent workflow runs, optionally filtered.
     pub fn list_runs(
         &self,
         workflow_id: Option<u64>,
         branch: Option<&str>,
         limit: usize,
     ) -> GitHubResult<Vec<WorkflowRun>> {
-        let mut path = if let Some(wf_id) = workflow_id {
+        self.list_runs_with(&ListRunsOptions {
+            workflow_id,
+            branch: branch.map(ToString::to_string),
+            per_page: limit.min(MAX_PER_PAGE),
+            max: Some(limit),
+            ..ListRunsOptions::default()
+        })
+    }
+
+    /// List workflow runs, following `Link` pagination until the results
+    /// run out or `options.max` runs have been collected.
+    pub fn list_runs_with(&self, options: &ListRunsOptions) -> GitHubResult<Vec<WorkflowRun>> {
+        let path = if let Some(wf_id) = options.workflow_id {
             format!("/actions/workflows/{wf_id}/runs")
         } else {
             "/actions/runs".to_string()
         };
-
-        // Add query parameters
-        let mut params = vec![format!("per_page={limit}")];
-        if let Some(b) = branch {
-            params.push(format!("branch={b}"));
-        }
-
-        if !params.is_empty() {
-            path.push('?');
-            path.push_str(&params.join("&"));
+        let url =
+            reqwest::Url::parse_with_params(&format!("{}{path}", self.repo_url()), options.query())
+                .map_err(|e| GitHubError::InvalidResponse(e.to_string()))?;
+
+        let mut runs = Vec::new();
+        let mut next = Some(url.to_string());
+        while let Some(url) = next {
+            let (page, link): (WorkflowRunsResponse, _) = self.get_page(&url)?;
+            let empty = page.workflow_runs.is_empty();
+            runs.extend(page.workflow_runs);
+
+            if let Some(max) = options.max {
+                if runs.len() >= max {
+                    runs.truncate(max);
+                    break;
+                }
+            }
+            next = if empty { None } else { link };
         }
 
-        let response: WorkflowRunsResponse = self.get(&path)?;
-        Ok(response.workflow_runs)
+        Ok(runs)
     }
 
     /// Get the latest run for a workflow.
@@ -399,6 +705,57 @@ impl GitHubActions {
         Ok(runs.into_iter().next())
     }
 
+    /// List the jobs of a workflow run.
+    pub fn list_jobs(&self, run_id: u64) -> GitHubResult<Vec<Job>> {
+        let response: JobsResponse =
+            self.get(&format!("/actions/runs/{run_id}/jobs?per_page={MAX_PER_PAGE}"))?;
+        Ok(response.jobs)
+    }
+
+    /// Download the logs of a workflow run, one entry per job.
+    ///
+    /// The API answers with a redirect to a short-lived storage URL. It is
+    /// followed by hand so the GitHub token is never sent to that host.
+    pub fn download_run_logs(&self, run_id: u64) -> GitHubResult<Vec<JobLog>> {
+        Self::ensure_online()?;
+        let url = format!("{}/actions/runs/{run_id}/logs", self.repo_url());
+
+        let no_redirect = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent(format!("palrun/{}", env!("CARGO_PKG_VERSION")))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+        let mut response = no_redirect
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()?;
+
+        if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .ok_or_else(|| {
+                    GitHubError::InvalidResponse("log redirect without a Location".to_string())
+                })?
+                .to_string();
+            response = self.client.get(location).timeout(Duration::from_secs(120)).send()?;
+        }
+
+        if !response.status().is_success() {
+            return Err(Self::error_for(response));
+        }
+
+        let gzipped = response
+            .headers()
+            .get(reqwest::header::CONTENT_ENCODING)
+            .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"gzip"));
+        let bytes = response.bytes()?;
+        extract_job_logs(&gunzip_if_needed(&bytes, gzipped)?)
+    }
+
     /// Get a specific workflow run.
     pub fn get_run(&self, run_id: u64) -> GitHubResult<WorkflowRun> {
         self.get(&format!("/actions/runs/{run_id}"))
@@ -419,6 +776,7 @@ impl GitHubActions {
             inputs: Option<serde_json::Value>,
         }
 
+        Self::ensure_online()?;
         let body = DispatchRequest { branch: branch.to_string(), inputs };
 
         let url = format!("{}/actions/workflows/{workflow_id}/dispatches", self.repo_url());
@@ -443,6 +801,7 @@ impl GitHubActions {
 
     /// Re-run a failed workflow.
     pub fn rerun_workflow(&self, run_id: u64) -> GitHubResult<()> {
+        Self::ensure_online()?;
         let url = format!("{}/actions/runs/{run_id}/rerun", self.repo_url());
 
         let response = self
@@ -464,6 +823,7 @@ impl GitHubActions {
 
     /// Cancel a workflow run.
     pub fn cancel_run(&self, run_id: u64) -> GitHubResult<()> {
+        Self::ensure_online()?;
         let url = format!("{}/actions/runs/{run_id}/cancel", self.repo_url());
 
         let response = self
@@ -578,7 +938,11 @@ fn detect_github_repo() -> GitHubResult<Option<(String, String)>> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{Arc, Mutex, OnceLock};
+
     use super::*;
+    use crate::test_util::{http_response, mock_http, MockRequest};
+    use serial_test::serial;
 
     #[test]
     fn test_workflow_status_icon() {
@@ -603,8 +967,192 @@ mod tests {
         assert!(!WorkflowStatus::Success.is_failure());
     }
 
+    /// Serve canned `(extra headers, body)` responses, one per request.
+    /// `pages` gets the server's base URL.
+    fn mock_api(
+        pages: impl FnOnce(&str) -> Vec<(String, String)>,
+    ) -> (String, Arc<Mutex<Vec<MockRequest>>>) {
+        let responses = Arc::new(OnceLock::new());
+        let canned = Arc::clone(&responses);
+        let (base, requests) = mock_http(move |_, index| {
+            let pages: &Vec<(String, String)> = canned.get().unwrap();
+            let (headers, body) = &pages[index];
+            http_response(200, &format!("Content-Type: application/json\r\n{headers}"), body)
+        });
+        responses.set(pages(&base)).unwrap();
+        (base, requests)
+    }
+
+    fn runs_page(ids: &[u64]) -> String {
+        let runs: Vec<_> = ids
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "id": id,
+                    "workflow_id": 7,
+                    "name": "CI",
+                    "run_number": id,
+                    "run_attempt": 1,
+                    "status": "completed",
+                    "conclusion": "failure",
+                    "head_branch": "main",
+                    "head_sha": "0123456789abcdef",
+                    "html_url": format!("https://github.com/o/r/actions/runs/{id}"),
+                    "created_at": "2026-01-01T00:00:00Z",
+                    "updated_at": "2026-01-01T00:05:00Z",
+                    "triggering_actor": null
+                })
+            })
+            .collect();
+        serde_json::json!({"total_count": 4, "workflow_runs": runs}).to_string()
+    }
+
+    fn two_pages(base: &str) -> Vec<(String, String)> {
+        let next = format!("{base}/repos/o/r/actions/runs?per_page=2&page=2");
+        vec![
+            (
+                format!("Link: <{next}>; rel=\"next\", <{next}>; rel=\"last\"\r\n"),
+                runs_page(&[4, 3]),
+            ),
+            (String::new(), runs_page(&[2, 1])),
+        ]
+    }
+
+    #[test]
+    #[serial]
+    fn test_list_runs_follows_pagination() {
+        let (base, requests) = mock_api(two_pages);
+        let client = GitHubActions::new("o", "r", "token").unwrap().with_base_url(base);
+
+        let since = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().to_utc();
+        let runs = client
+            .list_runs_with(&ListRunsOptions {
+                created_since: Some(since),
+                conclusion: Some(WorkflowStatus::Failure),
+                per_page: 2,
+                ..ListRunsOptions::default()
+            })
+            .unwrap();
+
+        assert_eq!(runs.iter().map(|r| r.id).collect::<Vec<_>>(), [4, 3, 2, 1]);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "GET");
+        assert!(requests[0].target.starts_with("/repos/o/r/actions/runs?per_page=2&"));
+        assert!(requests[0].target.contains("created=%3E%3D2026-01-01T00%3A00%3A00Z"));
+        assert!(requests[0].target.contains("status=failure"));
+        assert!(requests[1].target.contains("page=2"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_list_runs_stops_at_max() {
+        let (base, requests) = mock_api(two_pages);
+        let client = GitHubActions::new("o", "r", "token").unwrap().with_base_url(base);
+
+        let runs = client
+            .list_runs_with(&ListRunsOptions { per_page: 2, max: Some(2), ..Default::default() })
+            .unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        // `list_runs` keeps its single-request behavior for small limits
+        let (base, requests) = mock_api(two_pages);
+        let client = GitHubActions::new("o", "r", "token").unwrap().with_base_url(base);
+        assert_eq!(client.list_runs(None, Some("main"), 1).unwrap()[0].id, 4);
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].target.contains("per_page=1&branch=main"));
+    }
+
+    const RUN_LOGS: &[u8] = include_bytes!("../../tests/fixtures/github-actions/run-logs.zip");
+
+    #[test]
+    fn test_extract_job_logs() {
+        let logs = extract_job_logs(RUN_LOGS).unwrap();
+
+        assert_eq!(
+            logs.iter().map(|l| l.name.as_str()).collect::<Vec<_>>(),
+            ["build", "test (ubuntu-latest)"]
+        );
+        assert!(!logs[0].has_errors());
+        assert!(logs[1].has_errors());
+        assert!(logs[1].is_for("test (ubuntu-latest)"));
+        assert!(logs[1].content.starts_with("2026-01-01T00:00:01.0000000Z Current runner"));
+        assert_eq!(
+            logs[1].tail(2),
+            "2026-01-01T00:00:06.0000000Z test parses ... FAILED\n\
+             2026-01-01T00:00:06.0000000Z ##[error]Process completed with exit code 101."
+        );
+        assert_eq!(logs[0].tail(100), logs[0].content.trim_end());
+        assert_eq!(logs[0].tail(0), "");
+    }
+
+    #[test]
+    fn test_extract_job_logs_from_steps_and_gzip() {
+        use std::io::Write as _;
+
+        let mut archive = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for (path, text) in [
+            ("lint/2_Run clippy.txt", "warning\n##[error]clippy failed\n"),
+            ("lint/1_Set up job.txt", "setup\n"),
+            ("deploy prod/1_Set up job.txt", "setup\n"),
+        ] {
+            archive.start_file(path, options).unwrap();
+            archive.write_all(text.as_bytes()).unwrap();
+        }
+        let archive = archive.finish().unwrap().into_inner();
+
+        let mut gzipped = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        gzipped.write_all(&archive).unwrap();
+        let gzipped = gzipped.finish().unwrap();
+
+        // Detected by magic number even without a Content-Encoding header
+        let logs = extract_job_logs(&gunzip_if_needed(&gzipped, false).unwrap()).unwrap();
+        assert_eq!(logs.len(), 2);
+        assert!(logs[0].is_for("deploy: prod"));
+        assert_eq!(logs[1].name, "lint");
+        assert_eq!(logs[1].content, "setup\nwarning\n##[error]clippy failed\n");
+
+        assert_eq!(gunzip_if_needed(RUN_LOGS, false).unwrap(), RUN_LOGS);
+        assert!(extract_job_logs(b"not a zip").is_err());
+    }
+
+    #[test]
+    fn test_parse_age_and_links() {
+        assert_eq!(parse_age("7d"), chrono::Duration::try_days(7));
+        assert_eq!(parse_age("12h"), chrono::Duration::try_hours(12));
+        assert_eq!(parse_age("2w"), chrono::Duration::try_weeks(2));
+        assert_eq!(parse_age("30m"), chrono::Duration::try_minutes(30));
+        assert_eq!(parse_age("7"), None);
+        assert_eq!(parse_age("d"), None);
+        assert_eq!(parse_age("1y"), None);
+
+        let link = r#"<https://api.github.com/x?page=3>; rel="next", <https://api.github.com/x?page=9>; rel="last""#;
+        assert_eq!(next_page_url(link).as_deref(), Some("https://api.github.com/x?page=3"));
+        assert_eq!(next_page_url(r#"<https://api.github.com/x?page=1>; rel="prev""#), None);
+
+        assert_eq!("failure".parse::<WorkflowStatus>(), Ok(WorkflowStatus::Failure));
+        assert_eq!("Timed_Out".parse::<WorkflowStatus>(), Ok(WorkflowStatus::TimedOut));
+        assert!("broken".parse::<WorkflowStatus>().is_err());
+    }
+
     #[test]
     fn test_parse_github_url() {
         // This would test detect_github_repo but it requires git to be present
     }
+
+    #[test]
+    #[serial]
+    fn test_requests_fail_fast_offline() {
+        let client = GitHubActions::new("owner", "repo", "token").unwrap();
+
+        crate::core::set_offline_mode(true);
+        let workflows = client.list_workflows();
+        let rerun = client.rerun_workflow(1);
+        crate::core::set_offline_mode(false);
+
+        assert!(matches!(workflows, Err(GitHubError::Offline)));
+        assert!(matches!(rerun, Err(GitHubError::Offline)));
+    }
 }
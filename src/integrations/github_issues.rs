@@ -187,6 +187,9 @@ pub enum IssuesError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("GitHub Issues is unavailable in offline mode")]
+    Offline,
 }
 
 impl GitHubIssues {
@@ -218,13 +221,20 @@ impl GitHubIssues {
     }
 
     /// Make an authenticated request.
-    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
-        self.client
+    ///
+    /// Fails fast with [`IssuesError::Offline`] when offline mode is enabled.
+    fn request(&self, method: reqwest::Method, url: &str) -> IssuesResult<reqwest::RequestBuilder> {
+        if crate::core::is_offline_mode() {
+            return Err(IssuesError::Offline);
+        }
+
+        Ok(self
+            .client
             .request(method, url)
             .header("Authorization", format!("Bearer {}", self.token))
             .header("Accept", "application/vnd.github+json")
             .header("User-Agent", "palrun")
-            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("X-GitHub-Api-Version", "2022-11-28"))
     }
 
     /// Parse error response from GitHub API.
@@ -293,7 +303,7 @@ impl GitHubIssues {
             url = format!("{}?{}", url, params.join("&"));
         }
 
-        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let response = self.request(reqwest::Method::GET, &url)?.send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -307,7 +317,7 @@ impl GitHubIssues {
     pub async fn get_issue(&self, issue_number: u64) -> IssuesResult<Issue> {
         let url = self.api_url(&format!("issues/{}", issue_number));
 
-        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let response = self.request(reqwest::Method::GET, &url)?.send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -341,7 +351,7 @@ impl GitHubIssues {
             body.insert("milestone", serde_json::json!(milestone));
         }
 
-        let response = self.request(reqwest::Method::POST, &url).json(&body).send().await?;
+        let response = self.request(reqwest::Method::POST, &url)?.json(&body).send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -380,7 +390,7 @@ impl GitHubIssues {
             body.insert("milestone", serde_json::json!(milestone));
         }
 
-        let response = self.request(reqwest::Method::PATCH, &url).json(&body).send().await?;
+        let response = self.request(reqwest::Method::PATCH, &url)?.json(&body).send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -418,7 +428,7 @@ impl GitHubIssues {
 
         let body = serde_json::json!({ "labels": labels });
 
-        let response = self.request(reqwest::Method::POST, &url).json(&body).send().await?;
+        let response = self.request(reqwest::Method::POST, &url)?.json(&body).send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -433,7 +443,7 @@ impl GitHubIssues {
         let url =
             self.api_url(&format!("issues/{}/labels/{}", issue_number, urlencoding::encode(label)));
 
-        let response = self.request(reqwest::Method::DELETE, &url).send().await?;
+        let response = self.request(reqwest::Method::DELETE, &url)?.send().await?;
 
         if !response.status().is_success() && response.status().as_u16() != 404 {
             return Err(self.parse_error(response).await);
@@ -442,17 +452,31 @@ impl GitHubIssues {
         Ok(())
     }
 
-    /// List comments on an issue.
+    /// List all comments on an issue, following pagination.
     pub async fn list_comments(&self, issue_number: u64) -> IssuesResult<Vec<IssueComment>> {
-        let url = self.api_url(&format!("issues/{}/comments", issue_number));
+        const PER_PAGE: usize = 100;
 
-        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let mut comments = Vec::new();
+        for page in 1.. {
+            let url = self.api_url(&format!(
+                "issues/{}/comments?per_page={}&page={}",
+                issue_number, PER_PAGE, page
+            ));
 
-        if !response.status().is_success() {
-            return Err(self.parse_error(response).await);
+            let response = self.request(reqwest::Method::GET, &url)?.send().await?;
+
+            if !response.status().is_success() {
+                return Err(self.parse_error(response).await);
+            }
+
+            let batch: Vec<IssueComment> = response.json().await?;
+            let done = batch.len() < PER_PAGE;
+            comments.extend(batch);
+            if done {
+                break;
+            }
         }
 
-        let comments: Vec<IssueComment> = response.json().await?;
         Ok(comments)
     }
 
@@ -462,7 +486,7 @@ impl GitHubIssues {
 
         let payload = serde_json::json!({ "body": body });
 
-        let response = self.request(reqwest::Method::POST, &url).json(&payload).send().await?;
+        let response = self.request(reqwest::Method::POST, &url)?.json(&payload).send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -530,7 +554,7 @@ impl GitHubIssues {
             urlencoding::encode(&search_query)
         );
 
-        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let response = self.request(reqwest::Method::GET, &url)?.send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -549,7 +573,7 @@ impl GitHubIssues {
     pub async fn list_labels(&self) -> IssuesResult<Vec<Label>> {
         let url = self.api_url("labels");
 
-        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let response = self.request(reqwest::Method::GET, &url)?.send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -563,7 +587,7 @@ impl GitHubIssues {
     pub async fn list_milestones(&self) -> IssuesResult<Vec<Milestone>> {
         let url = self.api_url("milestones");
 
-        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let response = self.request(reqwest::Method::GET, &url)?.send().await?;
 
         if !response.status().is_success() {
             return Err(self.parse_error(response).await);
@@ -635,6 +659,7 @@ pub fn format_stats(stats: &IssueStats) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_format_issue() {
@@ -681,4 +706,16 @@ mod tests {
         assert!(options.labels.is_none());
         assert!(options.per_page.is_none());
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_requests_fail_fast_offline() {
+        let client = GitHubIssues::new("token", "owner", "repo");
+
+        crate::core::set_offline_mode(true);
+        let result = client.list_issues(ListIssuesOptions::default()).await;
+        crate::core::set_offline_mode(false);
+
+        assert!(matches!(result, Err(IssuesError::Offline)));
+    }
 }
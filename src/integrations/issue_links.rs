@@ -0,0 +1,189 @@
+//! Links between GitHub issues and Linear issues.
+//!
+//! `pal issues link` comments on both issues with the other's URL and
+//! records the pair in `.palrun/issue-links.json`, so `pal issues view`
+//! can show the linked Linear issue without querying Linear.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Location of the link store, relative to the project root.
+pub const LINKS_FILE: &str = ".palrun/issue-links.json";
+
+/// A GitHub issue linked to a Linear issue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueLink {
+    /// GitHub issue number
+    pub github: u64,
+    /// GitHub issue URL
+    pub github_url: String,
+    /// Linear issue identifier (e.g., "ENG-123")
+    pub linear: String,
+    /// Linear issue URL
+    pub linear_url: String,
+    /// When the link was made (RFC 3339)
+    pub linked_at: String,
+}
+
+/// The links recorded for a project.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueLinks {
+    /// Recorded links
+    #[serde(default)]
+    pub links: Vec<IssueLink>,
+}
+
+impl IssueLinks {
+    /// Path of the link store for a project.
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(LINKS_FILE)
+    }
+
+    /// Load the links for a project, or an empty set if none are recorded.
+    pub fn load(root: &Path) -> anyhow::Result<Self> {
+        let path = Self::path(root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Save the links for a project.
+    pub fn save(&self, root: &Path) -> anyhow::Result<()> {
+        let path = Self::path(root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Record a link, replacing any earlier link for the same pair.
+    ///
+    /// Returns `false` if the pair was already linked.
+    pub fn add(&mut self, link: IssueLink) -> bool {
+        let existing = self
+            .links
+            .iter_mut()
+            .find(|l| l.github == link.github && l.linear.eq_ignore_ascii_case(&link.linear));
+
+        if let Some(existing) = existing {
+            // Keep the original date, but pick up moved or renamed URLs
+            existing.github_url = link.github_url;
+            existing.linear_url = link.linear_url;
+            false
+        } else {
+            self.links.push(link);
+            true
+        }
+    }
+
+    /// Linear issues linked to a GitHub issue.
+    pub fn for_github(&self, number: u64) -> impl Iterator<Item = &IssueLink> {
+        self.links.iter().filter(move |l| l.github == number)
+    }
+
+    /// GitHub issues linked to a Linear issue.
+    pub fn for_linear<'a>(&'a self, identifier: &'a str) -> impl Iterator<Item = &'a IssueLink> {
+        self.links.iter().filter(move |l| l.linear.eq_ignore_ascii_case(identifier))
+    }
+}
+
+/// Comment posted on the GitHub issue.
+pub fn github_link_comment(linear: &str, linear_url: &str) -> String {
+    format!("Linked to Linear issue [{linear}]({linear_url})")
+}
+
+/// Comment posted on the Linear issue.
+pub fn linear_link_comment(github: u64, github_url: &str) -> String {
+    format!("Linked to GitHub issue [#{github}]({github_url})")
+}
+
+/// Whether any of `comments` already links to `url`.
+///
+/// Matching on the URL rather than the exact wording means a link someone
+/// pasted by hand also counts, so re-running `pal issues link` never adds
+/// a duplicate comment.
+pub fn has_link_comment<'a>(comments: impl IntoIterator<Item = &'a str>, url: &str) -> bool {
+    let url = url.trim_end_matches('/');
+    comments.into_iter().any(|body| {
+        body.match_indices(url).any(|(i, _)| {
+            // "…/issues/4" must not match a comment linking "…/issues/42"
+            let next = body[i + url.len()..].chars().next();
+            !next.is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(github: u64, linear: &str) -> IssueLink {
+        IssueLink {
+            github,
+            github_url: format!("https://github.com/acme/app/issues/{github}"),
+            linear: linear.to_string(),
+            linear_url: format!("https://linear.app/acme/issue/{linear}"),
+            linked_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_store_roundtrip() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(IssueLinks::load(temp.path()).unwrap().links, Vec::new());
+
+        let mut links = IssueLinks::default();
+        assert!(links.add(link(42, "ENG-7")));
+        assert!(links.add(link(42, "OPS-1")));
+        links.save(temp.path()).unwrap();
+
+        assert!(temp.path().join(".palrun/issue-links.json").exists());
+        let loaded = IssueLinks::load(temp.path()).unwrap();
+        assert_eq!(loaded.links, links.links);
+
+        let linear: Vec<&str> = loaded.for_github(42).map(|l| l.linear.as_str()).collect();
+        assert_eq!(linear, ["ENG-7", "OPS-1"]);
+        assert_eq!(loaded.for_linear("eng-7").next().unwrap().github, 42);
+        assert_eq!(loaded.for_github(7).count(), 0);
+    }
+
+    #[test]
+    fn test_add_is_idempotent() {
+        let mut links = IssueLinks::default();
+        assert!(links.add(link(42, "ENG-7")));
+
+        let mut moved = link(42, "eng-7");
+        moved.linear_url = "https://linear.app/acme/issue/ENG-7/renamed".to_string();
+        moved.linked_at = "2025-01-01T00:00:00Z".to_string();
+        assert!(!links.add(moved));
+
+        assert_eq!(links.links.len(), 1);
+        assert_eq!(links.links[0].linear_url, "https://linear.app/acme/issue/ENG-7/renamed");
+        assert_eq!(links.links[0].linked_at, "2024-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_has_link_comment() {
+        let url = "https://github.com/acme/app/issues/4";
+        let comments = ["Looks good", &linear_link_comment(4, url)];
+        assert!(has_link_comment(comments.iter().copied(), url));
+        assert!(has_link_comment(["see https://github.com/acme/app/issues/4."], url));
+        assert!(has_link_comment(["see https://github.com/acme/app/issues/4"], &format!("{url}/")));
+
+        assert!(!has_link_comment(["see https://github.com/acme/app/issues/42"], url));
+        assert!(!has_link_comment(["Looks good"], url));
+        assert!(!has_link_comment(Vec::<&str>::new(), url));
+    }
+
+    #[test]
+    fn test_link_comments_contain_urls() {
+        let body = github_link_comment("ENG-7", "https://linear.app/acme/issue/ENG-7");
+        assert!(has_link_comment([body.as_str()], "https://linear.app/acme/issue/ENG-7"));
+    }
+}
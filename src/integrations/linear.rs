@@ -85,6 +85,15 @@ pub struct LinearUser {
     pub display_name: String,
 }
 
+/// A comment on a Linear issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinearComment {
+    /// Comment ID
+    pub id: String,
+    /// Comment body (markdown)
+    pub body: String,
+}
+
 /// A Linear team.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinearTeam {
@@ -168,6 +177,9 @@ pub enum LinearError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("Linear is unavailable in offline mode")]
+    Offline,
 }
 
 impl LinearClient {
@@ -188,6 +200,10 @@ impl LinearClient {
         query: &str,
         variables: Option<serde_json::Value>,
     ) -> LinearResult<T> {
+        if crate::core::is_offline_mode() {
+            return Err(LinearError::Offline);
+        }
+
         let body = serde_json::json!({
             "query": query,
             "variables": variables.unwrap_or(serde_json::json!({}))
@@ -422,26 +438,7 @@ impl LinearClient {
             }
         ";
 
-        let (team_key, number) = identifier.split_once('-').ok_or_else(|| {
-            LinearError::InvalidInput(format!(
-                "Invalid issue identifier '{}'. Expected format: TEAM-123",
-                identifier
-            ))
-        })?;
-
-        let number: i32 = number.parse().map_err(|_| {
-            LinearError::InvalidInput(format!(
-                "Invalid issue number in identifier '{}'",
-                identifier
-            ))
-        })?;
-
-        let variables = serde_json::json!({
-            "filter": {
-                "team": { "key": { "eq": team_key } },
-                "number": { "eq": number }
-            }
-        });
+        let variables = serde_json::json!({ "filter": issue_filter(identifier)? });
 
         let response: Response = self.query(query, Some(variables)).await?;
         response
@@ -543,11 +540,239 @@ impl LinearClient {
         Ok(response.issue_create.issue)
     }
 
-    /// Update an issue's state.
+    /// Move an issue (e.g. "ENG-123") to the workflow state named `state_name`.
+    ///
+    /// Each team has its own workflow states, so the name is resolved
+    /// against the states of the issue's team; see [`resolve_state`].
     pub async fn update_issue_state(
+        &self,
+        identifier: &str,
+        state_name: &str,
+    ) -> LinearResult<LinearIssue> {
+        #[derive(Deserialize)]
+        struct StatesNode {
+            nodes: Vec<LinearState>,
+        }
+
+        #[derive(Deserialize)]
+        struct Team {
+            key: String,
+            states: StatesNode,
+        }
+
+        #[derive(Deserialize)]
+        struct Issue {
+            id: String,
+            team: Team,
+        }
+
+        #[derive(Deserialize)]
+        struct IssuesNode {
+            nodes: Vec<Issue>,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            issues: IssuesNode,
+        }
+
+        let query = r"
+            query IssueTeamStates($filter: IssueFilter) {
+                issues(filter: $filter, first: 1) {
+                    nodes {
+                        id
+                        team {
+                            key
+                            states {
+                                nodes {
+                                    id
+                                    name
+                                    color
+                                    type
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        ";
+
+        let variables = serde_json::json!({ "filter": issue_filter(identifier)? });
+
+        let response: Response = self.query(query, Some(variables)).await?;
+        let issue = response
+            .issues
+            .nodes
+            .into_iter()
+            .next()
+            .ok_or_else(|| LinearError::NotFound(format!("Issue {} not found", identifier)))?;
+
+        let state = resolve_state(&issue.team.states.nodes, state_name, &issue.team.key)?;
+        self.update_issue(&issue.id, serde_json::json!({ "stateId": state.id })).await
+    }
+
+    /// Assign an issue (e.g. "ENG-123") to a user.
+    ///
+    /// `user` is a user ID, an email address, or "me" for the current user.
+    pub async fn assign_issue(&self, identifier: &str, user: &str) -> LinearResult<LinearIssue> {
+        let assignee_id = if user.eq_ignore_ascii_case("me") {
+            self.get_viewer().await?.id
+        } else if user.contains('@') {
+            self.find_user_by_email(user).await?.id
+        } else {
+            user.to_string()
+        };
+
+        let issue = self.get_issue(identifier).await?;
+        self.update_issue(&issue.id, serde_json::json!({ "assigneeId": assignee_id })).await
+    }
+
+    /// List all comments on an issue (e.g. "ENG-123"), following pagination.
+    pub async fn list_comments(&self, identifier: &str) -> LinearResult<Vec<LinearComment>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct PageInfo {
+            has_next_page: bool,
+            end_cursor: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct CommentsNode {
+            nodes: Vec<LinearComment>,
+            page_info: PageInfo,
+        }
+
+        #[derive(Deserialize)]
+        struct Issue {
+            comments: CommentsNode,
+        }
+
+        #[derive(Deserialize)]
+        struct IssuesNode {
+            nodes: Vec<Issue>,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            issues: IssuesNode,
+        }
+
+        let query = r"
+            query IssueComments($filter: IssueFilter, $after: String) {
+                issues(filter: $filter, first: 1) {
+                    nodes {
+                        comments(first: 250, after: $after) {
+                            nodes {
+                                id
+                                body
+                            }
+                            pageInfo {
+                                hasNextPage
+                                endCursor
+                            }
+                        }
+                    }
+                }
+            }
+        ";
+
+        let filter = issue_filter(identifier)?;
+        let mut comments = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let variables = serde_json::json!({ "filter": filter, "after": after });
+            let response: Response = self.query(query, Some(variables)).await?;
+            let page =
+                response.issues.nodes.into_iter().next().map(|issue| issue.comments).ok_or_else(
+                    || LinearError::NotFound(format!("Issue {} not found", identifier)),
+                )?;
+
+            comments.extend(page.nodes);
+            match page.page_info.end_cursor {
+                Some(cursor) if page.page_info.has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+
+        Ok(comments)
+    }
+
+    /// Add a comment to an issue by ID.
+    pub async fn add_comment(&self, issue_id: &str, body: &str) -> LinearResult<LinearComment> {
+        #[derive(Deserialize)]
+        struct CommentPayload {
+            comment: LinearComment,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            #[serde(rename = "commentCreate")]
+            comment_create: CommentPayload,
+        }
+
+        let query = r"
+            mutation CreateComment($input: CommentCreateInput!) {
+                commentCreate(input: $input) {
+                    comment {
+                        id
+                        body
+                    }
+                }
+            }
+        ";
+
+        let variables = serde_json::json!({
+            "input": { "issueId": issue_id, "body": body }
+        });
+
+        let response: Response = self.query(query, Some(variables)).await?;
+        Ok(response.comment_create.comment)
+    }
+
+    /// Find a user in the workspace by email address.
+    async fn find_user_by_email(&self, email: &str) -> LinearResult<LinearUser> {
+        #[derive(Deserialize)]
+        struct UsersNode {
+            nodes: Vec<LinearUser>,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            users: UsersNode,
+        }
+
+        let query = r"
+            query FindUser($filter: UserFilter) {
+                users(filter: $filter, first: 1) {
+                    nodes {
+                        id
+                        name
+                        email
+                        displayName
+                    }
+                }
+            }
+        ";
+
+        let variables = serde_json::json!({
+            "filter": { "email": { "eqIgnoreCase": email } }
+        });
+
+        let response: Response = self.query(query, Some(variables)).await?;
+        response
+            .users
+            .nodes
+            .into_iter()
+            .next()
+            .ok_or_else(|| LinearError::NotFound(format!("No user with email {}", email)))
+    }
+
+    /// Apply an `IssueUpdateInput` to an issue by ID.
+    async fn update_issue(
         &self,
         issue_id: &str,
-        state_id: &str,
+        input: serde_json::Value,
     ) -> LinearResult<LinearIssue> {
         #[derive(Deserialize)]
         struct IssuePayload {
@@ -598,10 +823,7 @@ impl LinearClient {
             }
         ";
 
-        let variables = serde_json::json!({
-            "id": issue_id,
-            "input": { "stateId": state_id }
-        });
+        let variables = serde_json::json!({ "id": issue_id, "input": input });
 
         let response: Response = self.query(query, Some(variables)).await?;
         Ok(response.issue_update.issue)
@@ -750,6 +972,66 @@ impl LinearClient {
     }
 }
 
+/// Build an `IssueFilter` matching an identifier like "ENG-123".
+fn issue_filter(identifier: &str) -> LinearResult<serde_json::Value> {
+    let (team_key, number) = identifier.rsplit_once('-').ok_or_else(|| {
+        LinearError::InvalidInput(format!(
+            "Invalid issue identifier '{}'. Expected format: TEAM-123",
+            identifier
+        ))
+    })?;
+
+    let number: i32 = number.parse().map_err(|_| {
+        LinearError::InvalidInput(format!("Invalid issue number in identifier '{}'", identifier))
+    })?;
+
+    Ok(serde_json::json!({
+        "team": { "key": { "eq": team_key } },
+        "number": { "eq": number }
+    }))
+}
+
+/// Pick the workflow state `name` refers to among a team's `states`.
+///
+/// A state whose name matches (ignoring case, spaces, `-`, and `_`) wins.
+/// Otherwise `name` may be a state type (`backlog`, `unstarted`, `started`,
+/// `completed`, `canceled`) or one of the aliases `todo`, `start`,
+/// `in progress`, `done`, `closed`, and `cancel`, which select the first
+/// state of that type.
+pub fn resolve_state<'a>(
+    states: &'a [LinearState],
+    name: &str,
+    team_key: &str,
+) -> LinearResult<&'a LinearState> {
+    let normalize = |s: &str| -> String {
+        s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+    };
+    let wanted = normalize(name);
+
+    if let Some(state) = states.iter().find(|s| normalize(&s.name) == wanted) {
+        return Ok(state);
+    }
+
+    let state_type = match wanted.as_str() {
+        "backlog" => Some("backlog"),
+        "todo" | "unstarted" => Some("unstarted"),
+        "start" | "started" | "inprogress" | "doing" => Some("started"),
+        "done" | "complete" | "completed" | "closed" => Some("completed"),
+        "cancel" | "canceled" | "cancelled" => Some("canceled"),
+        _ => None,
+    };
+
+    state_type.and_then(|t| states.iter().find(|s| s.state_type == t)).ok_or_else(|| {
+        let available: Vec<&str> = states.iter().map(|s| s.name.as_str()).collect();
+        LinearError::NotFound(format!(
+            "No state '{}' in team {}. Available: {}",
+            name,
+            team_key,
+            available.join(", ")
+        ))
+    })
+}
+
 /// Format a Linear issue for display.
 pub fn format_linear_issue(issue: &LinearIssue, verbose: bool) -> String {
     let state_icon = match issue.state.state_type.as_str() {
@@ -813,6 +1095,7 @@ pub fn format_linear_stats(stats: &LinearStats) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     #[test]
     fn test_format_linear_issue() {
@@ -860,4 +1143,85 @@ mod tests {
         assert!(options.team_id.is_none());
         assert!(!options.include_archived);
     }
+
+    fn state(name: &str, state_type: &str) -> LinearState {
+        LinearState {
+            id: format!("id-{}", name.to_lowercase().replace(' ', "-")),
+            name: name.to_string(),
+            color: "#000".to_string(),
+            state_type: state_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_state_by_name() {
+        let states = vec![
+            state("Backlog", "backlog"),
+            state("Todo", "unstarted"),
+            state("In Progress", "started"),
+            state("In Review", "started"),
+            state("Done", "completed"),
+        ];
+
+        assert_eq!(resolve_state(&states, "In Review", "ENG").unwrap().id, "id-in-review");
+        assert_eq!(resolve_state(&states, "in-review", "ENG").unwrap().id, "id-in-review");
+        assert_eq!(resolve_state(&states, "TODO", "ENG").unwrap().id, "id-todo");
+        assert_eq!(resolve_state(&states, "done", "ENG").unwrap().id, "id-done");
+    }
+
+    #[test]
+    fn test_resolve_state_by_type() {
+        // A team with custom state names
+        let states = vec![
+            state("Icebox", "backlog"),
+            state("Ready", "unstarted"),
+            state("Building", "started"),
+            state("Shipped", "completed"),
+            state("Won't Do", "canceled"),
+        ];
+
+        assert_eq!(resolve_state(&states, "done", "OPS").unwrap().name, "Shipped");
+        assert_eq!(resolve_state(&states, "start", "OPS").unwrap().name, "Building");
+        assert_eq!(resolve_state(&states, "in progress", "OPS").unwrap().name, "Building");
+        assert_eq!(resolve_state(&states, "todo", "OPS").unwrap().name, "Ready");
+        assert_eq!(resolve_state(&states, "cancelled", "OPS").unwrap().name, "Won't Do");
+        assert_eq!(resolve_state(&states, "wont do", "OPS").unwrap().name, "Won't Do");
+    }
+
+    #[test]
+    fn test_resolve_state_missing() {
+        let states = vec![state("Todo", "unstarted"), state("Done", "completed")];
+
+        let err = resolve_state(&states, "In QA", "ENG").unwrap_err();
+        assert!(matches!(err, LinearError::NotFound(_)));
+        assert_eq!(
+            err.to_string(),
+            "Not found: No state 'In QA' in team ENG. Available: Todo, Done"
+        );
+
+        // No started state in this team
+        assert!(resolve_state(&states, "start", "ENG").is_err());
+    }
+
+    #[test]
+    fn test_issue_filter() {
+        let filter = issue_filter("ENG-123").unwrap();
+        assert_eq!(filter["team"]["key"]["eq"], "ENG");
+        assert_eq!(filter["number"]["eq"], 123);
+
+        assert!(matches!(issue_filter("ENG"), Err(LinearError::InvalidInput(_))));
+        assert!(matches!(issue_filter("ENG-abc"), Err(LinearError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_requests_fail_fast_offline() {
+        let client = LinearClient::new("token");
+
+        crate::core::set_offline_mode(true);
+        let result = client.get_viewer().await;
+        crate::core::set_offline_mode(false);
+
+        assert!(matches!(result, Err(LinearError::Offline)));
+    }
 }
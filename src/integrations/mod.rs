@@ -6,6 +6,7 @@
 pub mod api;
 pub mod github_actions;
 pub mod github_issues;
+pub mod issue_links;
 pub mod linear;
 pub mod notifications;
 pub mod webhooks;
@@ -14,18 +15,24 @@ pub use api::{
     ApiConfig, ApiError, ApiResult, ApiServer, ApiState, CommandInfo, ExecuteRequest,
     ExecuteResponse, HistoryEntry, RateLimiter, StatusResponse,
 };
-pub use github_actions::{GitHubActions, Workflow, WorkflowRun, WorkflowStatus};
+pub use github_actions::{
+    extract_job_logs, parse_age, GitHubActions, Job, JobLog, ListRunsOptions, Workflow,
+    WorkflowRun, WorkflowStatus,
+};
 pub use github_issues::{
     CreateIssueOptions, GitHubIssues, Issue, IssueComment, IssueStats, IssuesError, IssuesResult,
     Label, ListIssuesOptions, Milestone, UpdateIssueOptions, User,
 };
+pub use issue_links::{IssueLink, IssueLinks};
 pub use linear::{
-    CreateLinearIssueOptions, LinearClient, LinearError, LinearIssue, LinearLabel, LinearResult,
-    LinearState, LinearStats, LinearTeam, LinearUser, ListLinearIssuesOptions,
+    resolve_state, CreateLinearIssueOptions, LinearClient, LinearComment, LinearError, LinearIssue,
+    LinearLabel, LinearResult, LinearState, LinearStats, LinearTeam, LinearUser,
+    ListLinearIssuesOptions,
 };
 pub use notifications::{
     NotificationClient, NotificationConfig, NotificationError, NotificationEvent,
-    NotificationMessage, NotificationResult, NotificationType,
+    NotificationMessage, NotificationResult, NotificationType, DEFAULT_MAX_CODE_LENGTH,
+    MAX_STDIN_BYTES,
 };
 pub use webhooks::{
     AgentEventData, CommandEventData, McpToolEventData, RunbookEventData, WebhookConfig,
@@ -1,12 +1,16 @@
 //! Notification services integration.
 //!
-//! Provides webhook-based notifications to Slack, Discord, and custom endpoints.
+//! Provides webhook-based notifications to Slack, Discord, Microsoft Teams,
+//! and custom endpoints.
 
 use std::collections::HashMap;
+use std::io::Read;
 use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+use crate::core::RetryConfig;
+
 /// Notification service type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -15,6 +19,8 @@ pub enum NotificationType {
     Slack,
     /// Discord webhook
     Discord,
+    /// Microsoft Teams incoming webhook
+    Teams,
     /// Generic HTTP webhook
     Webhook,
 }
@@ -26,6 +32,7 @@ impl NotificationType {
         match self {
             Self::Slack => "Slack",
             Self::Discord => "Discord",
+            Self::Teams => "Teams",
             Self::Webhook => "Webhook",
         }
     }
@@ -36,6 +43,7 @@ impl NotificationType {
         match self {
             Self::Slack => "#",
             Self::Discord => "D",
+            Self::Teams => "T",
             Self::Webhook => "W",
         }
     }
@@ -103,7 +111,7 @@ pub struct NotificationConfig {
     pub webhook_url: String,
 
     /// Events that trigger this notification.
-    #[serde(default)]
+    #[serde(default = "default_events")]
     pub events: Vec<NotificationEvent>,
 
     /// Optional filter pattern for commands (glob pattern).
@@ -117,12 +125,32 @@ pub struct NotificationConfig {
     /// Custom headers for webhook requests.
     #[serde(default)]
     pub headers: HashMap<String, String>,
+
+    /// Timeout in seconds for each delivery attempt.
+    #[serde(default = "default_timeout")]
+    pub timeout_secs: u64,
+
+    /// Retry count on transient failures (connection errors, 429, 5xx).
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+}
+
+fn default_events() -> Vec<NotificationEvent> {
+    vec![NotificationEvent::CommandComplete]
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_timeout() -> u64 {
+    10
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
 impl NotificationConfig {
     /// Create a new Slack notification config.
     pub fn slack(name: impl Into<String>, webhook_url: impl Into<String>) -> Self {
@@ -130,10 +158,12 @@ impl NotificationConfig {
             name: name.into(),
             notification_type: NotificationType::Slack,
             webhook_url: webhook_url.into(),
-            events: vec![NotificationEvent::CommandComplete],
+            events: default_events(),
             filter: None,
             enabled: true,
             headers: HashMap::new(),
+            timeout_secs: default_timeout(),
+            retries: default_retries(),
         }
     }
 
@@ -143,10 +173,27 @@ impl NotificationConfig {
             name: name.into(),
             notification_type: NotificationType::Discord,
             webhook_url: webhook_url.into(),
-            events: vec![NotificationEvent::CommandComplete],
+            events: default_events(),
             filter: None,
             enabled: true,
             headers: HashMap::new(),
+            timeout_secs: default_timeout(),
+            retries: default_retries(),
+        }
+    }
+
+    /// Create a new Microsoft Teams notification config.
+    pub fn teams(name: impl Into<String>, webhook_url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            notification_type: NotificationType::Teams,
+            webhook_url: webhook_url.into(),
+            events: default_events(),
+            filter: None,
+            enabled: true,
+            headers: HashMap::new(),
+            timeout_secs: default_timeout(),
+            retries: default_retries(),
         }
     }
 
@@ -156,10 +203,12 @@ impl NotificationConfig {
             name: name.into(),
             notification_type: NotificationType::Webhook,
             webhook_url: webhook_url.into(),
-            events: vec![NotificationEvent::CommandComplete],
+            events: default_events(),
             filter: None,
             enabled: true,
             headers: HashMap::new(),
+            timeout_secs: default_timeout(),
+            retries: default_retries(),
         }
     }
 
@@ -175,6 +224,18 @@ impl NotificationConfig {
         self
     }
 
+    /// Set the timeout for each delivery attempt.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout_secs = timeout.as_secs().max(1);
+        self
+    }
+
+    /// Set how many times a transient failure is retried.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
     /// Check if this notification matches an event and command.
     pub fn matches(&self, event: NotificationEvent, command: Option<&str>) -> bool {
         if !self.enabled {
@@ -225,6 +286,27 @@ pub struct NotificationMessage {
     /// Whether this is an error/failure message.
     #[serde(default)]
     pub is_error: bool,
+
+    /// Preformatted output (e.g. command stderr) rendered as a code block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_block: Option<String>,
+
+    /// Maximum characters of `code_block` to send before truncating.
+    #[serde(default = "default_max_code_length")]
+    pub max_code_length: usize,
+}
+
+/// Default limit for code blocks, chosen to fit inside a Discord embed.
+pub const DEFAULT_MAX_CODE_LENGTH: usize = 3000;
+
+/// Maximum bytes of piped input kept by [`NotificationMessage::from_reader`].
+pub const MAX_STDIN_BYTES: usize = 64 * 1024;
+
+/// Marker prepended to code blocks that were cut down to fit.
+pub const TRUNCATION_MARKER: &str = "…truncated";
+
+fn default_max_code_length() -> usize {
+    DEFAULT_MAX_CODE_LENGTH
 }
 
 /// A field in a notification message.
@@ -242,7 +324,15 @@ pub struct MessageField {
 impl NotificationMessage {
     /// Create a simple text message.
     pub fn text(text: impl Into<String>) -> Self {
-        Self { title: None, text: text.into(), color: None, fields: Vec::new(), is_error: false }
+        Self {
+            title: None,
+            text: text.into(),
+            color: None,
+            fields: Vec::new(),
+            is_error: false,
+            code_block: None,
+            max_code_length: DEFAULT_MAX_CODE_LENGTH,
+        }
     }
 
     /// Create a message with a title.
@@ -253,9 +343,39 @@ impl NotificationMessage {
             color: None,
             fields: Vec::new(),
             is_error: false,
+            code_block: None,
+            max_code_length: DEFAULT_MAX_CODE_LENGTH,
         }
     }
 
+    /// Create a message whose code block is read from `reader` (e.g. stdin).
+    ///
+    /// Only the last [`MAX_STDIN_BYTES`] are kept, so piping a long log
+    /// does not buffer it all in memory.
+    pub fn from_reader(text: impl Into<String>, reader: impl Read) -> std::io::Result<Self> {
+        let output = read_tail(reader, MAX_STDIN_BYTES)?;
+        Ok(Self::text(text).code_block(output.trim_end()))
+    }
+
+    /// Attach output to render as a code block.
+    pub fn code_block(mut self, code: impl Into<String>) -> Self {
+        self.code_block = Some(code.into());
+        self
+    }
+
+    /// Set the maximum code block length before truncation.
+    pub fn max_code_length(mut self, max: usize) -> Self {
+        self.max_code_length = max;
+        self
+    }
+
+    /// Get the code block, truncated to `max_code_length`.
+    ///
+    /// The tail of the output is kept since that is where errors usually are.
+    pub fn truncated_code(&self) -> Option<String> {
+        self.code_block.as_deref().map(|code| truncate_output(code, self.max_code_length))
+    }
+
     /// Set the message color.
     pub fn color(mut self, color: impl Into<String>) -> Self {
         self.color = Some(color.into());
@@ -291,6 +411,21 @@ impl NotificationMessage {
         self
     }
 
+    /// Substitute `{name}` placeholders from `ctx` in the title, text,
+    /// and field values.
+    ///
+    /// Typical keys are `branch`, `status`, `duration`, and `command`.
+    /// Placeholders with no value in `ctx` are left as they are.
+    pub fn render(&self, ctx: &HashMap<String, String>) -> Self {
+        let mut rendered = self.clone();
+        rendered.title = self.title.as_deref().map(|t| render_template(t, ctx));
+        rendered.text = render_template(&self.text, ctx);
+        for field in &mut rendered.fields {
+            field.value = render_template(&field.value, ctx);
+        }
+        rendered
+    }
+
     /// Create a command completion message.
     pub fn command_completed(command: &str, success: bool, duration: Option<Duration>) -> Self {
         let status = if success { "succeeded" } else { "failed" };
@@ -311,6 +446,29 @@ impl NotificationMessage {
             msg.error()
         }
     }
+
+    /// Create a message for a command that has exited, with its exit code
+    /// and the last `tail_lines` lines of `output` as a code block.
+    pub fn command_finished(
+        command: &str,
+        exit_code: i32,
+        duration: Duration,
+        output: &str,
+        tail_lines: usize,
+    ) -> Self {
+        let mut msg = Self::command_completed(command, exit_code == 0, Some(duration)).add_field(
+            "Exit code",
+            exit_code.to_string(),
+            true,
+        );
+
+        let lines: Vec<&str> = output.trim_end().lines().collect();
+        let tail = lines[lines.len().saturating_sub(tail_lines)..].join("\n");
+        if !tail.trim().is_empty() {
+            msg = msg.code_block(tail);
+        }
+        msg
+    }
 }
 
 /// Error type for notification operations.
@@ -331,6 +489,21 @@ pub enum NotificationError {
     /// Serialization error.
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    /// Offline mode is enabled.
+    #[error("Notifications are unavailable in offline mode")]
+    Offline,
+}
+
+impl NotificationError {
+    /// Whether a later attempt might succeed (network errors, 429, 5xx).
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Http(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            Self::Webhook { status, .. } => *status == 429 || *status >= 500,
+            Self::Config(_) | Self::Serialization(_) | Self::Offline => false,
+        }
+    }
 }
 
 /// Result type for notification operations.
@@ -340,17 +513,29 @@ pub type NotificationResult<T> = Result<T, NotificationError>;
 pub struct NotificationClient {
     /// HTTP client.
     client: reqwest::blocking::Client,
+
+    /// Backoff between retries; attempt count comes from each config.
+    backoff: RetryConfig,
 }
 
 impl NotificationClient {
     /// Create a new notification client.
     pub fn new() -> NotificationResult<Self> {
         let client = reqwest::blocking::Client::builder()
-            .timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(default_timeout()))
             .user_agent(format!("palrun/{}", env!("CARGO_PKG_VERSION")))
             .build()?;
 
-        Ok(Self { client })
+        Ok(Self { client, backoff: RetryConfig::network() })
+    }
+
+    /// Set the backoff used between retries.
+    ///
+    /// `max_attempts` is ignored; the retry count is taken from each
+    /// [`NotificationConfig`].
+    pub fn with_backoff(mut self, backoff: RetryConfig) -> Self {
+        self.backoff = backoff;
+        self
     }
 
     /// Send a notification.
@@ -363,9 +548,14 @@ impl NotificationClient {
             return Ok(());
         }
 
+        if crate::core::is_offline_mode() {
+            return Err(NotificationError::Offline);
+        }
+
         match config.notification_type {
             NotificationType::Slack => self.send_slack(config, message),
             NotificationType::Discord => self.send_discord(config, message),
+            NotificationType::Teams => self.send_teams(config, message),
             NotificationType::Webhook => self.send_webhook(config, message),
         }
     }
@@ -377,37 +567,28 @@ impl NotificationClient {
         message: &NotificationMessage,
     ) -> NotificationResult<()> {
         // Build Slack message payload
-        let payload = self.build_slack_payload(message);
-
-        let mut request = self.client.post(&config.webhook_url).json(&payload);
-
-        // Add custom headers
-        for (key, value) in &config.headers {
-            request = request.header(key, value);
-        }
-
-        let response = request.send()?;
-
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            let status = response.status().as_u16();
-            let message = response.text().unwrap_or_else(|_| "Unknown error".to_string());
-            Err(NotificationError::Webhook { status, message })
-        }
+        let payload = Self::build_slack_payload(message);
+        self.post(config, &payload)
     }
 
     /// Build Slack message payload.
-    fn build_slack_payload(&self, message: &NotificationMessage) -> serde_json::Value {
+    fn build_slack_payload(message: &NotificationMessage) -> serde_json::Value {
+        let mut text = message.text.clone();
+        if let Some(code) = message.truncated_code() {
+            // Slack mrkdwn has no language hints and requires &, <, > escaped
+            let code = code.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+            text = format!("{text}\n```\n{code}\n```");
+        }
+
         if message.fields.is_empty() && message.title.is_none() && message.color.is_none() {
             // Simple text message
             serde_json::json!({
-                "text": message.text
+                "text": text
             })
         } else {
             // Rich message with attachment
             let mut attachment = serde_json::json!({
-                "text": message.text,
+                "text": text,
                 "mrkdwn_in": ["text"]
             });
 
@@ -447,37 +628,28 @@ impl NotificationClient {
         message: &NotificationMessage,
     ) -> NotificationResult<()> {
         // Build Discord message payload
-        let payload = self.build_discord_payload(message);
-
-        let mut request = self.client.post(&config.webhook_url).json(&payload);
-
-        // Add custom headers
-        for (key, value) in &config.headers {
-            request = request.header(key, value);
-        }
-
-        let response = request.send()?;
-
-        if response.status().is_success() || response.status().as_u16() == 204 {
-            Ok(())
-        } else {
-            let status = response.status().as_u16();
-            let message = response.text().unwrap_or_else(|_| "Unknown error".to_string());
-            Err(NotificationError::Webhook { status, message })
-        }
+        let payload = Self::build_discord_payload(message);
+        self.post(config, &payload)
     }
 
     /// Build Discord message payload.
-    fn build_discord_payload(&self, message: &NotificationMessage) -> serde_json::Value {
+    fn build_discord_payload(message: &NotificationMessage) -> serde_json::Value {
+        let mut text = message.text.clone();
+        if let Some(code) = message.truncated_code() {
+            // A zero-width space stops embedded fences from closing the block early
+            let code = code.replace("```", "`\u{200b}``");
+            text = format!("{text}\n```\n{code}\n```");
+        }
+
         if message.fields.is_empty() && message.title.is_none() && message.color.is_none() {
             // Simple text message
             serde_json::json!({
-                "content": message.text
+                "content": text
             })
         } else {
             // Rich embed message
             let mut embed = serde_json::json!({
-                "description": message.text
+                "description": text
             });
 
             if let Some(ref title) = message.title {
@@ -512,6 +684,58 @@ impl NotificationClient {
         }
     }
 
+    /// Send a Microsoft Teams message.
+    fn send_teams(
+        &self,
+        config: &NotificationConfig,
+        message: &NotificationMessage,
+    ) -> NotificationResult<()> {
+        let payload = Self::build_teams_payload(message);
+        self.post(config, &payload)
+    }
+
+    /// Build a Teams `MessageCard` payload.
+    fn build_teams_payload(message: &NotificationMessage) -> serde_json::Value {
+        let mut text = message.text.clone();
+        if let Some(code) = message.truncated_code() {
+            text = format!("{text}\n\n```\n{code}\n```");
+        }
+
+        // Teams requires a summary; it is what shows in the activity feed
+        let summary = message.title.as_deref().unwrap_or(&message.text);
+        let mut card = serde_json::json!({
+            "@type": "MessageCard",
+            "@context": "https://schema.org/extensions",
+            "summary": summary,
+            "text": text
+        });
+
+        if let Some(ref title) = message.title {
+            card["title"] = serde_json::json!(title);
+        }
+
+        if let Some(ref color) = message.color {
+            // themeColor is hex without the leading '#'
+            card["themeColor"] = serde_json::json!(color.trim_start_matches('#'));
+        }
+
+        if !message.fields.is_empty() {
+            let facts: Vec<serde_json::Value> = message
+                .fields
+                .iter()
+                .map(|f| {
+                    serde_json::json!({
+                        "name": f.name,
+                        "value": f.value
+                    })
+                })
+                .collect();
+            card["sections"] = serde_json::json!([{ "facts": facts }]);
+        }
+
+        card
+    }
+
     /// Send a generic webhook.
     fn send_webhook(
         &self,
@@ -525,10 +749,50 @@ impl NotificationClient {
             "color": message.color,
             "is_error": message.is_error,
             "fields": message.fields,
+            "code_block": message.truncated_code(),
             "timestamp": chrono::Utc::now().to_rfc3339()
         });
 
-        let mut request = self.client.post(&config.webhook_url).json(&payload);
+        self.post(config, &payload)
+    }
+
+    /// POST a payload, retrying transient failures with backoff.
+    fn post(
+        &self,
+        config: &NotificationConfig,
+        payload: &serde_json::Value,
+    ) -> NotificationResult<()> {
+        let mut attempt = 0;
+        loop {
+            let result = self.post_once(config, payload);
+            match result {
+                Err(ref e) if attempt < config.retries && e.is_transient() => {
+                    attempt += 1;
+                    tracing::debug!(
+                        "Notification '{}' failed ({}), retry {}/{}",
+                        config.name,
+                        e,
+                        attempt,
+                        config.retries
+                    );
+                    std::thread::sleep(self.backoff.delay_for_attempt(attempt));
+                }
+                _ => return result,
+            }
+        }
+    }
+
+    /// Make a single delivery attempt.
+    fn post_once(
+        &self,
+        config: &NotificationConfig,
+        payload: &serde_json::Value,
+    ) -> NotificationResult<()> {
+        let mut request = self
+            .client
+            .post(&config.webhook_url)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .json(payload);
 
         // Add custom headers
         for (key, value) in &config.headers {
@@ -571,6 +835,72 @@ impl Default for NotificationClient {
     }
 }
 
+/// Read `reader` to the end, keeping at most the last `cap` bytes.
+fn read_tail(mut reader: impl Read, cap: usize) -> std::io::Result<String> {
+    let mut tail = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let mut dropped = false;
+
+    loop {
+        let n = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        tail.extend_from_slice(&chunk[..n]);
+        if tail.len() > cap {
+            tail.drain(..tail.len() - cap);
+            dropped = true;
+        }
+    }
+
+    if dropped {
+        // Don't start in the middle of a multi-byte character
+        let start = tail.iter().position(|b| (*b & 0xC0) != 0x80).unwrap_or(tail.len());
+        tail.drain(..start);
+    }
+
+    Ok(String::from_utf8_lossy(&tail).into_owned())
+}
+
+/// Keep the last `max` characters of `output`, marking the cut.
+fn truncate_output(output: &str, max: usize) -> String {
+    let count = output.chars().count();
+    if count <= max {
+        return output.to_string();
+    }
+
+    let tail: String = output.chars().skip(count - max).collect();
+    format!("{TRUNCATION_MARKER}\n{tail}")
+}
+
+/// Replace `{key}` placeholders with values from `ctx`.
+fn render_template(template: &str, ctx: &HashMap<String, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            let key = &after[..end];
+            ctx.get(key).map(|value| (value, end))
+        });
+
+        if let Some((value, end)) = value {
+            out.push_str(value);
+            rest = &after[end + 1..];
+        } else {
+            out.push('{');
+            rest = after;
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
 /// Convert hex color to decimal (for Discord).
 fn hex_to_decimal(hex: &str) -> Option<u32> {
     let hex = hex.trim_start_matches('#');
@@ -579,12 +909,37 @@ fn hex_to_decimal(hex: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{Arc, Mutex};
+
     use super::*;
+    use crate::test_util::{http_response, mock_http, MockRequest};
+    use serial_test::serial;
+
+    type Requests = Arc<Mutex<Vec<MockRequest>>>;
+
+    /// Serve one canned response per request: `(status, delay before replying)`.
+    fn mock_server(responses: Vec<(u16, Duration)>) -> (String, Requests) {
+        let (base, requests) = mock_http(move |_, index| {
+            let (status, delay) = responses[index];
+            std::thread::sleep(delay);
+            http_response(status, "", "")
+        });
+        (format!("{base}/hook"), requests)
+    }
+
+    fn fast_client() -> NotificationClient {
+        NotificationClient::new().unwrap().with_backoff(RetryConfig {
+            initial_delay: Duration::from_millis(1),
+            jitter: false,
+            ..RetryConfig::no_retry()
+        })
+    }
 
     #[test]
     fn test_notification_type_display() {
         assert_eq!(NotificationType::Slack.name(), "Slack");
         assert_eq!(NotificationType::Discord.name(), "Discord");
+        assert_eq!(NotificationType::Teams.name(), "Teams");
         assert_eq!(NotificationType::Webhook.name(), "Webhook");
     }
 
@@ -632,6 +987,34 @@ mod tests {
         assert!(msg.is_error);
     }
 
+    #[test]
+    fn test_command_finished_reports_failure() {
+        let output = "compiling\nstep 1\nstep 2\nerror: linker failed\n";
+        let msg = NotificationMessage::command_finished(
+            "cargo build",
+            101,
+            Duration::from_secs(95),
+            output,
+            2,
+        );
+
+        assert!(msg.is_error);
+        assert_eq!(msg.code_block.as_deref(), Some("step 2\nerror: linker failed"));
+
+        let payload = NotificationClient::build_slack_payload(&msg);
+        let attachment = &payload["attachments"][0];
+        assert_eq!(attachment["title"], "Command failed");
+        assert_eq!(attachment["color"], "#dc3545");
+        assert_eq!(attachment["fields"][0]["value"], "95.00s");
+        assert_eq!(attachment["fields"][1]["title"], "Exit code");
+        assert_eq!(attachment["fields"][1]["value"], "101");
+        assert!(attachment["text"].as_str().unwrap().contains("error: linker failed"));
+
+        let msg = NotificationMessage::command_finished("true", 0, Duration::ZERO, "", 10);
+        assert!(!msg.is_error);
+        assert!(msg.code_block.is_none());
+    }
+
     #[test]
     fn test_hex_to_decimal() {
         assert_eq!(hex_to_decimal("#28a745"), Some(2664261));
@@ -641,9 +1024,8 @@ mod tests {
 
     #[test]
     fn test_slack_payload_simple() {
-        let client = NotificationClient::new().unwrap();
         let message = NotificationMessage::text("Hello");
-        let payload = client.build_slack_payload(&message);
+        let payload = NotificationClient::build_slack_payload(&message);
 
         assert_eq!(payload["text"], "Hello");
         assert!(payload.get("attachments").is_none());
@@ -651,11 +1033,202 @@ mod tests {
 
     #[test]
     fn test_discord_payload_simple() {
-        let client = NotificationClient::new().unwrap();
         let message = NotificationMessage::text("Hello");
-        let payload = client.build_discord_payload(&message);
+        let payload = NotificationClient::build_discord_payload(&message);
 
         assert_eq!(payload["content"], "Hello");
         assert!(payload.get("embeds").is_none());
     }
+
+    #[test]
+    fn test_teams_payload() {
+        let message = NotificationMessage::command_completed(
+            "npm test",
+            false,
+            Some(Duration::from_millis(1500)),
+        )
+        .code_block("1 failing");
+        let payload = NotificationClient::build_teams_payload(&message);
+
+        assert_eq!(payload["@type"], "MessageCard");
+        assert_eq!(payload["@context"], "https://schema.org/extensions");
+        assert_eq!(payload["title"], "Command failed");
+        assert_eq!(payload["summary"], "Command failed");
+        assert_eq!(payload["themeColor"], "dc3545");
+        assert!(payload["text"].as_str().unwrap().ends_with("\n\n```\n1 failing\n```"));
+        assert_eq!(payload["sections"][0]["facts"][0]["name"], "Duration");
+        assert_eq!(payload["sections"][0]["facts"][0]["value"], "1.50s");
+
+        let payload = NotificationClient::build_teams_payload(&NotificationMessage::text("Hello"));
+        assert_eq!(payload["summary"], "Hello");
+        assert!(payload.get("themeColor").is_none());
+        assert!(payload.get("sections").is_none());
+
+        let config: NotificationConfig =
+            toml::from_str("name = \"ops\"\ntype = \"teams\"\nwebhook_url = \"https://x\"")
+                .unwrap();
+        assert_eq!(config.notification_type, NotificationType::Teams);
+    }
+
+    #[test]
+    fn test_render_template_variables() {
+        let ctx: HashMap<String, String> = [
+            ("command", "cargo test"),
+            ("status", "succeeded"),
+            ("duration", "12.3s"),
+            ("branch", "main"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let message =
+            NotificationMessage::with_title("{command} {status}", "Finished in {duration}")
+                .add_field("Branch", "{branch}", true);
+        let rendered = message.render(&ctx);
+
+        assert_eq!(rendered.title.as_deref(), Some("cargo test succeeded"));
+        assert_eq!(rendered.text, "Finished in 12.3s");
+        assert_eq!(rendered.fields[0].value, "main");
+        assert_eq!(message.text, "Finished in {duration}");
+
+        assert_eq!(render_template("{unknown} {status}", &ctx), "{unknown} succeeded");
+        assert_eq!(render_template("{\"a\": {status}}", &ctx), "{\"a\": succeeded}");
+        assert_eq!(render_template("open {", &ctx), "open {");
+    }
+
+    #[test]
+    #[serial]
+    fn test_send_fails_fast_offline() {
+        let client = NotificationClient::new().unwrap();
+        let config = NotificationConfig::slack("team", "https://hooks.slack.com/services/x");
+
+        crate::core::set_offline_mode(true);
+        let result = client.send(&config, &NotificationMessage::text("Hello"));
+        crate::core::set_offline_mode(false);
+
+        assert!(matches!(result, Err(NotificationError::Offline)));
+    }
+
+    #[test]
+    #[serial]
+    fn test_send_retries_until_success() {
+        let (url, hits) =
+            mock_server(vec![(503, Duration::ZERO), (500, Duration::ZERO), (200, Duration::ZERO)]);
+        let config = NotificationConfig::webhook("flaky", url).with_retries(3);
+
+        let result = fast_client().send(&config, &NotificationMessage::text("Hello"));
+
+        assert!(result.is_ok(), "{result:?}");
+        assert_eq!(hits.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    #[serial]
+    fn test_send_gives_up_after_retries() {
+        let (url, hits) = mock_server(vec![(500, Duration::ZERO); 3]);
+        let config = NotificationConfig::slack("down", url).with_retries(2);
+
+        let result = fast_client().send(&config, &NotificationMessage::text("Hello"));
+
+        assert!(matches!(result, Err(NotificationError::Webhook { status: 500, .. })));
+        assert_eq!(hits.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    #[serial]
+    fn test_send_does_not_retry_client_errors() {
+        let (url, hits) = mock_server(vec![(404, Duration::ZERO), (200, Duration::ZERO)]);
+        let config = NotificationConfig::discord("gone", url).with_retries(3);
+
+        let result = fast_client().send(&config, &NotificationMessage::text("Hello"));
+
+        assert!(matches!(result, Err(NotificationError::Webhook { status: 404, .. })));
+        assert_eq!(hits.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_send_times_out_slow_endpoint() {
+        let (url, _) = mock_server(vec![(200, Duration::from_secs(3))]);
+        let config = NotificationConfig::webhook("slow", url)
+            .with_timeout(Duration::from_secs(1))
+            .with_retries(0);
+
+        let start = std::time::Instant::now();
+        let result = fast_client().send(&config, &NotificationMessage::text("Hello"));
+
+        assert!(matches!(result, Err(NotificationError::Http(ref e)) if e.is_timeout()));
+        assert!(start.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_notification_config_retry_defaults() {
+        let config: NotificationConfig =
+            toml::from_str("name = \"ci\"\ntype = \"slack\"\nwebhook_url = \"https://x\"").unwrap();
+        assert_eq!(config.timeout_secs, 10);
+        assert_eq!(config.retries, 3);
+        assert_eq!(config.events, [NotificationEvent::CommandComplete]);
+    }
+
+    #[test]
+    fn test_slack_code_block() {
+        let message = NotificationMessage::text("Build failed").code_block("error: a < b && c");
+        let payload = NotificationClient::build_slack_payload(&message);
+
+        assert_eq!(payload["text"], "Build failed\n```\nerror: a &lt; b &amp;&amp; c\n```");
+
+        let payload = NotificationClient::build_slack_payload(&message.error());
+        let attachment = &payload["attachments"][0];
+        assert!(attachment["text"].as_str().unwrap().ends_with("\n```"));
+        assert_eq!(attachment["mrkdwn_in"][0], "text");
+    }
+
+    #[test]
+    fn test_discord_code_block() {
+        let message = NotificationMessage::text("Build failed").code_block("see ```here```");
+        let payload = NotificationClient::build_discord_payload(&message);
+
+        assert_eq!(payload["content"], "Build failed\n```\nsee `\u{200b}``here`\u{200b}``\n```");
+
+        let payload = NotificationClient::build_discord_payload(&message.error());
+        assert!(payload["embeds"][0]["description"]
+            .as_str()
+            .unwrap()
+            .starts_with("Build failed\n```"));
+    }
+
+    #[test]
+    fn test_code_block_truncation() {
+        let message = NotificationMessage::text("Output")
+            .code_block("line 1\nline 2\nline 3")
+            .max_code_length(6);
+
+        assert_eq!(message.truncated_code().unwrap(), "…truncated\nline 3");
+        assert_eq!(truncate_output("short", 10), "short");
+        assert_eq!(truncate_output("ééééé", 2), "…truncated\néé");
+    }
+
+    #[test]
+    fn test_message_from_reader() {
+        let stdin =
+            std::io::Cursor::new("error[E0308]: mismatched types\n  --> src/main.rs:2:5\n\n");
+        let message = NotificationMessage::from_reader("cargo build failed", stdin).unwrap();
+
+        assert_eq!(message.text, "cargo build failed");
+        assert_eq!(
+            message.code_block.as_deref(),
+            Some("error[E0308]: mismatched types\n  --> src/main.rs:2:5")
+        );
+    }
+
+    #[test]
+    fn test_read_tail_caps_input() {
+        let input = format!("{}é-end", "x".repeat(100));
+        let tail = read_tail(std::io::Cursor::new(input), 5).unwrap();
+        assert_eq!(tail, "-end");
+
+        let tail = read_tail(std::io::Cursor::new("short"), 100).unwrap();
+        assert_eq!(tail, "short");
+    }
 }
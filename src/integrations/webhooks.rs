@@ -419,6 +419,18 @@ impl WebhookManager {
             }
         };
 
+        if crate::core::is_offline_mode() {
+            return WebhookDelivery {
+                webhook_name: webhook.name.clone(),
+                event,
+                status_code: None,
+                success: false,
+                error: Some("Webhooks are unavailable in offline mode".to_string()),
+                duration_ms: 0,
+                retries: 0,
+            };
+        }
+
         // Retry loop
         while retries <= webhook.retries {
             let mut request = match webhook.method.to_uppercase().as_str() {
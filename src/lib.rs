@@ -119,7 +119,7 @@ pub use workflow::{
 pub mod git;
 
 #[cfg(feature = "git")]
-pub use git::{GitInfo, GitRepository};
+pub use git::{CommitInfo, GitInfo, GitRepository, StashInfo};
 
 pub mod env;
 pub use env::{
@@ -147,7 +147,8 @@ pub use integrations::{
 
 pub mod mcp;
 pub use mcp::{
-    MCPClient, MCPManager, MCPServer, MCPServerConfig, MCPTool, ToolCall, ToolRegistry, ToolResult,
+    MCPClient, MCPManager, MCPServer, MCPServerConfig, MCPTool, MCPTransport, ToolCall,
+    ToolRegistry, ToolResult,
 };
 
 pub mod security;
@@ -156,6 +157,9 @@ pub use security::{
     ValidationSeverity,
 };
 
+#[cfg(test)]
+mod test_util;
+
 // Re-export commonly used types
 pub use app::App;
 pub use core::{Command, CommandRegistry, CommandSource, Config};
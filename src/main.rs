@@ -6,6 +6,7 @@
 #![allow(clippy::single_match_else)]
 
 use std::io::{self, Write};
+use std::process::ExitCode;
 
 use anyhow::Result;
 use clap::{CommandFactory, Parser, Subcommand};
@@ -19,6 +20,7 @@ use palrun::{tui, App};
 #[command(name = "palrun")]
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
+#[allow(clippy::struct_excessive_bools)]
 struct Cli {
     /// Subcommand to run
     #[command(subcommand)]
@@ -35,6 +37,10 @@ struct Cli {
     /// Dry run mode - show what would be executed without running
     #[arg(long, global = true)]
     dry_run: bool,
+
+    /// Disable all network access (also enabled by PALRUN_OFFLINE=1)
+    #[arg(long, global = true)]
+    offline: bool,
 }
 
 #[derive(Subcommand)]
@@ -44,19 +50,28 @@ enum Commands {
 
     /// List all available commands
     List {
-        /// Output format (text, json)
+        /// Output format (text, json, json-schema)
         #[arg(short, long, default_value = "text")]
         format: String,
 
         /// Filter by source type (npm, make, etc.)
         #[arg(short, long)]
         source: Option<String>,
+
+        /// Only list commands for directories changed since the base ref
+        #[arg(long)]
+        changed: bool,
+
+        /// Base ref for --changed
+        #[arg(long, default_value = "origin/main", requires = "changed")]
+        base: String,
     },
 
     /// Execute a command directly by name
     Exec {
         /// Command name or pattern to execute
-        name: String,
+        #[arg(required_unless_present = "last_failed")]
+        name: Option<String>,
 
         /// Don't confirm before executing
         #[arg(short = 'y', long)]
@@ -65,6 +80,22 @@ enum Commands {
         /// Dry run - show command without executing
         #[arg(short, long)]
         dry_run: bool,
+
+        /// Stash local changes before running and restore them afterwards
+        #[arg(long)]
+        clean: bool,
+
+        /// Re-run the most recent command that exited non-zero
+        #[arg(long, conflicts_with = "name")]
+        last_failed: bool,
+
+        /// Resolve secret references from .env and pass them to this command only
+        #[arg(long, conflicts_with = "clean")]
+        with_secrets: bool,
+
+        /// Run the best match without asking when several commands match
+        #[arg(long)]
+        first: bool,
     },
 
     /// Scan the project and show what would be discovered
@@ -76,6 +107,22 @@ enum Commands {
         /// Enable recursive scanning
         #[arg(short, long)]
         recursive: bool,
+
+        /// Show which scanner, file, and rule produced each command
+        #[arg(long)]
+        explain: bool,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Save the discovered commands as a JSON baseline
+        #[arg(long, value_name = "FILE")]
+        save: Option<std::path::PathBuf>,
+
+        /// Compare with a baseline saved by --save or --format json
+        #[arg(long, value_name = "BASELINE")]
+        diff: Option<std::path::PathBuf>,
     },
 
     /// Run a runbook
@@ -90,6 +137,14 @@ enum Commands {
         /// Variable assignments (key=value)
         #[arg(long)]
         var: Vec<String>,
+
+        /// Skip the steps before this one (resume a failed run)
+        #[arg(long, value_name = "STEP", conflicts_with = "only")]
+        from_step: Option<String>,
+
+        /// Run only these steps
+        #[arg(long, value_name = "STEP", value_delimiter = ',')]
+        only: Vec<String>,
     },
 
     /// Generate shell completions
@@ -100,7 +155,8 @@ enum Commands {
 
     /// Initialize shell integration
     Init {
-        /// Shell to initialize
+        /// Shell to initialize (bash, zsh, fish, powershell, nu, elvish, or auto to detect)
+        #[arg(default_value = "auto")]
         shell: String,
     },
 
@@ -123,7 +179,7 @@ enum Commands {
         non_interactive: bool,
     },
 
-    /// Show configuration
+    /// Show the effective configuration and the file each value came from
     Config {
         /// Show config file path
         #[arg(long)]
@@ -165,8 +221,15 @@ enum Commands {
         /// Show all detected runtimes (including those without requirements)
         #[arg(short, long)]
         all: bool,
+
+        /// Versions operation
+        #[command(subcommand)]
+        operation: Option<VersionsOperation>,
     },
 
+    /// Check the project setup for problems
+    Doctor,
+
     /// Manage secrets from external providers
     Secrets {
         /// Secrets operation
@@ -174,6 +237,13 @@ enum Commands {
         operation: SecretsOperation,
     },
 
+    /// Audit the project for security issues
+    Security {
+        /// Security operation
+        #[command(subcommand)]
+        operation: SecurityOperation,
+    },
+
     /// Manage plugins
     #[cfg(feature = "plugins")]
     Plugin {
@@ -250,6 +320,10 @@ enum DebugOperation {
         /// Show detailed information
         #[arg(short, long)]
         detailed: bool,
+
+        /// Dump every command with full metadata as JSON, grouped by source
+        #[arg(long)]
+        json: bool,
     },
 
     /// Show project detection results
@@ -276,13 +350,21 @@ enum DebugOperation {
 #[derive(Subcommand)]
 enum McpOperation {
     /// List configured MCP servers
-    Servers,
+    Servers {
+        /// Output format (text, json); json redacts env values
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
 
     /// List available tools from MCP servers
     Tools {
         /// Only show tools from a specific server
         #[arg(short, long)]
         server: Option<String>,
+
+        /// Output format (text, json)
+        #[arg(short, long, default_value = "text")]
+        format: String,
     },
 
     /// Call an MCP tool
@@ -304,10 +386,21 @@ enum McpOperation {
         name: String,
     },
 
-    /// Stop an MCP server
+    /// Stop a server running in the MCP daemon
     Stop {
-        /// Server name
-        name: String,
+        /// Server name; stops the whole daemon when omitted
+        name: Option<String>,
+    },
+
+    /// Keep configured servers running in the background for other commands
+    Daemon {
+        /// Return once the daemon is ready instead of running in the foreground
+        #[arg(short, long)]
+        detach: bool,
+
+        /// Start a new session first; set by `--detach` for the daemon it spawns
+        #[arg(long, hide = true)]
+        new_session: bool,
     },
 
     /// Show MCP configuration
@@ -472,6 +565,49 @@ enum EnvOperation {
 
     /// Show which .env file is currently active
     Active,
+
+    /// Set a variable in a .env file
+    Set {
+        /// Assignment in KEY=value form
+        assignment: String,
+
+        /// .env file to edit
+        #[arg(short, long, default_value = ".env")]
+        file: String,
+    },
+
+    /// Remove a variable from a .env file
+    Unset {
+        /// Variable name
+        key: String,
+
+        /// .env file to edit
+        #[arg(short, long, default_value = ".env")]
+        file: String,
+    },
+}
+
+/// Runtime version operations.
+#[derive(Subcommand)]
+enum VersionsOperation {
+    /// Switch mismatched runtimes to their required versions
+    Use {
+        /// Print the version manager commands without running them
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Install missing versions without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+/// Security operations.
+#[derive(Subcommand)]
+enum SecurityOperation {
+    /// Check discovered commands, .env files, and Git hooks; exits non-zero
+    /// on high or critical findings
+    Audit,
 }
 
 /// Secrets operations.
@@ -489,14 +625,14 @@ enum SecretsOperation {
         #[arg(short, long)]
         dry_run: bool,
 
-        /// Only inject secrets from a specific provider (1password, vault)
+        /// Only inject secrets from a specific provider (1password, vault, aws, gcp)
         #[arg(short, long)]
         provider: Option<String>,
     },
 
     /// List detected secret references
     List {
-        /// Filter by provider (1password, vault)
+        /// Filter by provider (1password, vault, aws, gcp)
         #[arg(short, long)]
         provider: Option<String>,
     },
@@ -507,19 +643,33 @@ enum SecretsOperation {
 #[derive(Subcommand)]
 enum HooksOperation {
     /// List installed Git hooks
-    List,
+    List {
+        /// Show what each hook runs
+        #[arg(long)]
+        show: bool,
+    },
 
     /// Install a Git hook
     Install {
         /// Hook name (pre-commit, pre-push, etc.)
-        hook: String,
+        #[arg(required_unless_present_any = ["require_signing", "template"])]
+        hook: Option<String>,
 
-        /// Command to run
-        command: String,
+        /// Command to run (omit to use the built-in template for
+        /// prepare-commit-msg or post-merge)
+        command: Option<String>,
 
         /// Force overwrite existing hook
         #[arg(short, long)]
         force: bool,
+
+        /// Fail commits unless commit signing is configured (pre-commit by default)
+        #[arg(long, conflicts_with = "command")]
+        require_signing: bool,
+
+        /// Install a built-in template by name (issue-id, lockfile-install)
+        #[arg(long, conflicts_with_all = ["command", "require_signing"])]
+        template: Option<String>,
     },
 
     /// Uninstall a Git hook
@@ -541,6 +691,57 @@ enum HooksOperation {
         #[arg(short, long)]
         force: bool,
     },
+
+    /// Run the palrun.toml commands for a hook (invoked by the installed hook script)
+    #[command(hide = true)]
+    Run {
+        /// Hook name
+        hook: String,
+
+        /// Arguments Git passed to the hook
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Run a built-in hook template (invoked by the installed hook script)
+    #[command(hide = true)]
+    Builtin {
+        #[command(subcommand)]
+        hook: BuiltinHook,
+    },
+}
+
+/// Built-in hook templates.
+#[cfg(feature = "git")]
+#[derive(Subcommand)]
+enum BuiltinHook {
+    /// Prefix the commit message with the ticket ID from the branch name
+    #[command(name = "prepare-commit-msg")]
+    PrepareCommitMsg {
+        /// Path to the commit message file
+        file: std::path::PathBuf,
+
+        /// Source of the message (message, template, merge, squash, commit)
+        source: Option<String>,
+
+        /// Commit SHA (when amending)
+        sha: Option<String>,
+    },
+
+    /// Fail unless commit signing is configured
+    #[command(name = "check-signing")]
+    CheckSigning,
+
+    /// Install dependencies when a merge changed lockfiles
+    #[command(name = "post-merge")]
+    PostMerge {
+        /// Whether the merge was a squash (passed by Git)
+        squash: Option<String>,
+
+        /// Only print the install commands instead of running them
+        #[arg(long)]
+        suggest: bool,
+    },
 }
 
 /// Plugin operations.
@@ -595,6 +796,10 @@ enum PluginOperation {
         /// Force install (overwrite if exists)
         #[arg(short, long)]
         force: bool,
+
+        /// Skip checksum and signature verification (local development only)
+        #[arg(long)]
+        no_verify: bool,
     },
 
     /// Uninstall a plugin
@@ -637,6 +842,16 @@ enum PluginOperation {
 
     /// Clear the registry cache
     ClearCache,
+
+    /// Load a local plugin without installing it and run it on this project
+    Dev {
+        /// Plugin crate directory (built for wasm32-wasip1) or a .wasm file
+        path: std::path::PathBuf,
+
+        /// Rebuild and rerun whenever the plugin's sources change
+        #[arg(short, long)]
+        watch: bool,
+    },
 }
 
 /// AI operation modes.
@@ -680,6 +895,14 @@ enum AiOperation {
         #[arg(short, long, default_value = "10")]
         max_iterations: usize,
 
+        /// Maximum number of tool calls to execute
+        #[arg(long)]
+        tool_budget: Option<usize>,
+
+        /// Run destructive tools without asking for confirmation
+        #[arg(long)]
+        yes: bool,
+
         /// Use only local LLM (Ollama)
         #[arg(long)]
         local: bool,
@@ -715,6 +938,14 @@ enum CiOperation {
         #[arg(short, long)]
         branch: Option<String>,
 
+        /// Only runs created within this long ago (e.g. 12h, 7d, 2w)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only runs with this status or conclusion (e.g. failure, success)
+        #[arg(short, long)]
+        status: Option<String>,
+
         /// Number of runs to show
         #[arg(short, long, default_value = "10")]
         limit: usize,
@@ -746,6 +977,24 @@ enum CiOperation {
         run_id: u64,
     },
 
+    /// Show the end of a run's failed job logs
+    Logs {
+        /// Run ID
+        run_id: u64,
+
+        /// Show this job instead of the failed ones
+        #[arg(short, long)]
+        job: Option<String>,
+
+        /// Number of lines to show from the end of each log
+        #[arg(short = 'n', long, default_value = "50")]
+        lines: usize,
+
+        /// Summarize the failure with the configured AI provider
+        #[arg(long)]
+        explain: bool,
+    },
+
     /// Open CI page in browser
     Open {
         /// Open a specific run ID
@@ -763,7 +1012,31 @@ enum NotifyOperation {
         url: String,
 
         /// Message to send
-        message: String,
+        #[arg(required_unless_present = "from_stdin")]
+        message: Option<String>,
+
+        /// Optional title
+        #[arg(short, long)]
+        title: Option<String>,
+
+        /// Color (hex format: #RRGGBB)
+        #[arg(short, long)]
+        color: Option<String>,
+
+        /// Read output from stdin and send it as a code block
+        #[arg(long)]
+        from_stdin: bool,
+    },
+
+    /// Send a message to Microsoft Teams
+    Teams {
+        /// Teams incoming webhook URL
+        #[arg(short, long, env = "TEAMS_WEBHOOK_URL")]
+        url: String,
+
+        /// Message to send
+        #[arg(required_unless_present = "from_stdin")]
+        message: Option<String>,
 
         /// Optional title
         #[arg(short, long)]
@@ -772,6 +1045,10 @@ enum NotifyOperation {
         /// Color (hex format: #RRGGBB)
         #[arg(short, long)]
         color: Option<String>,
+
+        /// Read output from stdin and send it as a code block
+        #[arg(long)]
+        from_stdin: bool,
     },
 
     /// Send a message to Discord
@@ -781,7 +1058,8 @@ enum NotifyOperation {
         url: String,
 
         /// Message to send
-        message: String,
+        #[arg(required_unless_present = "from_stdin")]
+        message: Option<String>,
 
         /// Optional title
         #[arg(short, long)]
@@ -790,6 +1068,10 @@ enum NotifyOperation {
         /// Color (hex format: #RRGGBB)
         #[arg(short, long)]
         color: Option<String>,
+
+        /// Read output from stdin and send it as a code block
+        #[arg(long)]
+        from_stdin: bool,
     },
 
     /// Send a message to a generic webhook
@@ -799,16 +1081,36 @@ enum NotifyOperation {
         url: String,
 
         /// Message to send
-        message: String,
+        #[arg(required_unless_present = "from_stdin")]
+        message: Option<String>,
 
         /// Optional title
         #[arg(short, long)]
         title: Option<String>,
+
+        /// Read output from stdin and send it as a code block
+        #[arg(long)]
+        from_stdin: bool,
+    },
+
+    /// Run a command and notify the `[notify]` endpoints when it finishes
+    OnComplete {
+        /// Only notify the endpoint with this name
+        #[arg(short, long)]
+        endpoint: Option<String>,
+
+        /// Minimum run time in seconds before notifying (overrides `[notify]`)
+        #[arg(long)]
+        threshold: Option<u64>,
+
+        /// Command to run, as separate arguments or one shell command line
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
     },
 
     /// Test a notification endpoint
     Test {
-        /// Notification type (slack, discord, webhook)
+        /// Notification type (slack, discord, teams, webhook)
         #[arg(short = 't', long)]
         notification_type: String,
 
@@ -903,6 +1205,15 @@ enum IssuesOperation {
         labels: String,
     },
 
+    /// Link an issue to a Linear issue, commenting on both
+    Link {
+        /// Issue number
+        number: u64,
+
+        /// Linear issue identifier (e.g., ENG-123)
+        linear: String,
+    },
+
     /// Search for issues
     Search {
         /// Search query (GitHub search syntax)
@@ -962,6 +1273,36 @@ enum LinearOperation {
         priority: Option<i32>,
     },
 
+    /// Mark an issue as done
+    Done {
+        /// Issue identifier (e.g., ENG-123)
+        identifier: String,
+    },
+
+    /// Move an issue to in progress
+    Start {
+        /// Issue identifier (e.g., ENG-123)
+        identifier: String,
+    },
+
+    /// Move an issue to a workflow state by name
+    Move {
+        /// Issue identifier (e.g., ENG-123)
+        identifier: String,
+
+        /// State name (e.g., "In Review", todo, done)
+        state: String,
+    },
+
+    /// Assign an issue to a user
+    Assign {
+        /// Issue identifier (e.g., ENG-123)
+        identifier: String,
+
+        /// User ID, email, or "me"
+        user: String,
+    },
+
     /// List available teams
     Teams,
 
@@ -978,34 +1319,60 @@ enum LinearOperation {
     Me,
 }
 
-fn main() -> Result<()> {
+fn main() -> Result<ExitCode> {
     let cli = Cli::parse();
 
     // Setup logging
     let filter = if cli.verbose { EnvFilter::new("debug") } else { EnvFilter::new("warn") };
 
-    tracing_subscriber::registry().with(fmt::layer().with_target(false)).with(filter).init();
+    tracing_subscriber::registry()
+        .with(
+            fmt::layer().with_target(false).with_writer(palrun::security::RedactingWriter::stderr),
+        )
+        .with(filter)
+        .init();
+
+    if palrun::core::Config::load().is_ok_and(|config| config.security.redact_output) {
+        let redactor = palrun::security::Redactor::from_env();
+        let redactor = match std::env::current_dir() {
+            Ok(cwd) => redactor.with_dotenv(&cwd),
+            Err(_) => redactor,
+        };
+        palrun::security::enable_redaction(redactor);
+    }
+
+    if cli.offline {
+        palrun::core::set_offline_mode(true);
+    }
 
     // Handle commands
     match cli.command {
         None | Some(Commands::Run) => {
             if cli.non_interactive {
-                cmd_list("text", None)?;
+                cmd_list("text", None, None)?;
             } else {
                 cmd_run()?;
             }
         }
-        Some(Commands::List { format, source }) => {
-            cmd_list(&format, source.as_deref())?;
+        Some(Commands::List { format, source, changed, base }) => {
+            cmd_list(&format, source.as_deref(), changed.then_some(base.as_str()))?;
         }
-        Some(Commands::Exec { name, yes, dry_run }) => {
-            cmd_exec(&name, yes, dry_run || cli.dry_run)?;
+        Some(Commands::Exec { name, yes, dry_run, clean, last_failed, with_secrets, first }) => {
+            let options = ExecOptions {
+                last_failed,
+                skip_confirm: yes,
+                dry_run: dry_run || cli.dry_run,
+                clean,
+                with_secrets,
+                first,
+            };
+            cmd_exec(name.as_deref(), &options)?;
         }
-        Some(Commands::Scan { path, recursive }) => {
-            cmd_scan(&path, recursive)?;
+        Some(Commands::Scan { path, recursive, explain, format, save, diff }) => {
+            cmd_scan(&path, recursive, explain, &format, save.as_deref(), diff.as_deref())?;
         }
-        Some(Commands::Runbook { name, dry_run, var }) => {
-            cmd_runbook(&name, dry_run, &var)?;
+        Some(Commands::Runbook { name, dry_run, var, from_step, only }) => {
+            cmd_runbook(&name, dry_run, &var, from_step.as_deref(), &only)?;
         }
         Some(Commands::Completions { shell }) => {
             cmd_completions(shell);
@@ -1033,12 +1400,17 @@ fn main() -> Result<()> {
         Some(Commands::Env { operation }) => {
             cmd_env(operation)?;
         }
-        Some(Commands::Versions { all }) => {
-            cmd_versions(all)?;
-        }
+        Some(Commands::Versions { all, operation }) => match operation {
+            Some(VersionsOperation::Use { dry_run, yes }) => cmd_versions_use(dry_run, yes)?,
+            None => cmd_versions(all)?,
+        },
+        Some(Commands::Doctor) => return cmd_doctor(),
         Some(Commands::Secrets { operation }) => {
             cmd_secrets(operation)?;
         }
+        Some(Commands::Security { operation }) => {
+            cmd_security(&operation)?;
+        }
         #[cfg(feature = "plugins")]
         Some(Commands::Plugin { operation }) => {
             cmd_plugin(operation)?;
@@ -1046,9 +1418,7 @@ fn main() -> Result<()> {
         Some(Commands::Ci { operation }) => {
             cmd_ci(operation)?;
         }
-        Some(Commands::Notify { operation }) => {
-            cmd_notify(operation)?;
-        }
+        Some(Commands::Notify { operation }) => return cmd_notify(operation),
         Some(Commands::Issues { operation }) => {
             cmd_issues(operation)?;
         }
@@ -1069,7 +1439,7 @@ fn main() -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
 /// Run the interactive TUI.
@@ -1079,26 +1449,34 @@ fn cmd_run() -> Result<()> {
 }
 
 /// List available commands.
-fn cmd_list(format: &str, source_filter: Option<&str>) -> Result<()> {
+fn cmd_list(format: &str, source_filter: Option<&str>, changed_since: Option<&str>) -> Result<()> {
     let mut app = App::new()?;
     app.initialize()?;
 
-    let commands: Vec<_> = if let Some(source) = source_filter {
+    let mut commands: Vec<_> = if let Some(source) = source_filter {
         app.registry.get_by_source_type(source).into_iter().cloned().collect()
     } else {
         app.registry.get_all().to_vec()
     };
 
+    if let Some(base) = changed_since {
+        commands = changed_commands(&commands, base)?;
+    }
+
     match format {
         "json" => {
-            let json = serde_json::to_string_pretty(&commands)?;
+            let json = serde_json::to_string_pretty(&palrun::core::CommandList::new(&commands))?;
             println!("{json}");
         }
+        "json-schema" => {
+            let schema = serde_json::to_string_pretty(&palrun::core::CommandList::schema())?;
+            println!("{schema}");
+        }
         _ => {
             for cmd in &commands {
                 println!(
                     "{} {} - {}",
-                    cmd.source.icon(),
+                    app.config.ui.icons.icon(&cmd.source, &app.sources),
                     cmd.name,
                     cmd.description.as_deref().unwrap_or("")
                 );
@@ -1110,25 +1488,155 @@ fn cmd_list(format: &str, source_filter: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Every discovered command with debug-only fields, grouped by source type.
+fn debug_commands_json(app: &App) -> serde_json::Value {
+    use palrun::scanner::Provenance;
+
+    let mut by_source: std::collections::BTreeMap<&str, Vec<serde_json::Value>> =
+        std::collections::BTreeMap::new();
+    for cmd in app.registry.get_all() {
+        let provenance = Provenance::of(cmd).map(|p| {
+            serde_json::json!({
+                "scanner": p.scanner,
+                "source_file": p.source_file,
+                "rule": p.rule,
+            })
+        });
+
+        by_source.entry(cmd.source.type_name()).or_default().push(serde_json::json!({
+            "id": cmd.id,
+            "name": cmd.name,
+            "command": cmd.command,
+            "description": cmd.description,
+            "source": cmd.source,
+            "provenance": provenance,
+            "working_dir": cmd.working_dir,
+            "tags": cmd.tags,
+            "confirm": cmd.confirm,
+            "env": cmd.env,
+            "branch_patterns": cmd.branch_patterns,
+            "workspace": cmd.workspace,
+            "shell": cmd.shell,
+            "metadata": cmd.metadata,
+        }));
+    }
+
+    serde_json::json!({
+        "total": app.registry.len(),
+        "sources": by_source,
+    })
+}
+
+/// Keep only commands for directories changed since `base` (`pal list --changed`).
+#[cfg(feature = "git")]
+fn changed_commands(commands: &[palrun::Command], base: &str) -> Result<Vec<palrun::Command>> {
+    let repo = palrun::git::discover_repo()
+        .ok_or_else(|| anyhow::anyhow!("--changed requires a Git repository"))?;
+    let root = repo.root().ok_or_else(|| anyhow::anyhow!("--changed requires a working tree"))?;
+
+    let changed = repo.changed_files(base)?;
+    Ok(palrun::git::filter_changed(commands, &changed, &root))
+}
+
+/// Keep only commands for directories changed since `base` (`pal list --changed`).
+#[cfg(not(feature = "git"))]
+fn changed_commands(_commands: &[palrun::Command], _base: &str) -> Result<Vec<palrun::Command>> {
+    anyhow::bail!("--changed requires Palrun to be built with the 'git' feature")
+}
+
+/// Flags that change how `pal exec` picks and runs a command.
+#[allow(clippy::struct_excessive_bools)]
+struct ExecOptions {
+    /// Re-run the most recent command that exited non-zero
+    last_failed: bool,
+    /// Don't confirm before executing
+    skip_confirm: bool,
+    /// Show the command without executing it
+    dry_run: bool,
+    /// Stash local changes before running and restore them afterwards
+    clean: bool,
+    /// Resolve secret references for this command only
+    with_secrets: bool,
+    /// Run the best match when several commands match
+    first: bool,
+}
+
 /// Execute a command directly.
-fn cmd_exec(name: &str, skip_confirm: bool, dry_run: bool) -> Result<()> {
+fn cmd_exec(name: Option<&str>, options: &ExecOptions) -> Result<()> {
+    use palrun::core::{choose_match, exact_matches, format_matches, MatchChoice};
+    use std::io::IsTerminal;
+
+    let ExecOptions { last_failed, skip_confirm, dry_run, clean, with_secrets, first } = *options;
+
     let mut app = App::new()?;
     app.initialize()?;
 
-    // Search for the command
-    let matches = app.registry.search(name);
+    let cmd = if last_failed {
+        let entry = app
+            .history_manager
+            .as_ref()
+            .and_then(palrun::core::HistoryManager::last_failed)
+            .ok_or_else(|| anyhow::anyhow!("No failed command in history"))?;
+
+        let cmd = app.registry.get_by_id(&entry.command_id).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Last failed command '{}' is no longer available in this project",
+                entry.command_name
+            )
+        })?;
+
+        let exit =
+            entry.last_exit_code.map_or_else(|| "killed".to_string(), |c| format!("exit {c}"));
+        println!("Last failed: {} ({}, {})", cmd.name, exit, entry.last_used_display());
+        cmd.clone()
+    } else {
+        let name = name.unwrap_or_default();
+
+        // A configured alias runs without searching
+        if let Some(cmd) = app.find_alias(name)? {
+            cmd
+        } else {
+            // Search for the command
+            let matches: Vec<_> = app
+                .registry
+                .search(name)
+                .into_iter()
+                .filter_map(|i| app.registry.get_by_index(i))
+                .collect();
 
-    if matches.is_empty() {
-        anyhow::bail!("No command matching '{name}' found");
-    }
+            if matches.is_empty() {
+                anyhow::bail!("No command matching '{name}' found");
+            }
+
+            // Only ask when someone is there to answer
+            let matches = exact_matches(name, matches);
+            let interactive = !skip_confirm && io::stdin().is_terminal();
+            let mut stdin = io::stdin().lock();
+            match choose_match(&matches, first, interactive, &mut stdin, &mut io::stdout())? {
+                MatchChoice::Selected(i) => matches[i].clone(),
+                MatchChoice::Ambiguous => anyhow::bail!(
+                    "'{name}' matches {} commands:\n{}\n\nUse a more specific name, or --first to run the best match",
+                    matches.len(),
+                    format_matches(&matches)
+                ),
+                MatchChoice::Cancelled => {
+                    println!("Cancelled");
+                    return Ok(());
+                }
+            }
+        }
+    };
 
-    let cmd = app.registry.get_by_index(matches[0]).unwrap();
+    let mut executor = palrun::core::Executor::new().wrapper(app.config.general.wrapper());
+    if let Some(timeout) = app.config.general.timeout() {
+        executor = executor.timeout(timeout);
+    }
 
     // Dry run - just show what would be executed
     if dry_run {
         println!("[DRY RUN] Would execute:");
         println!("  Name: {}", cmd.name);
-        println!("  Command: {}", cmd.command);
+        println!("  Command: {}", executor.command_line(&cmd));
         if let Some(dir) = &cmd.working_dir {
             println!("  Working dir: {}", dir.display());
         }
@@ -1142,6 +1650,17 @@ fn cmd_exec(name: &str, skip_confirm: bool, dry_run: bool) -> Result<()> {
         return Ok(());
     }
 
+    let validation = app.validate_command(&cmd);
+    if !validation.is_safe() {
+        let reasons: Vec<_> = validation.errors.iter().map(|e| e.description()).collect();
+        anyhow::bail!(
+            "Not executing '{}': {}\n  - {}\n\nSet security.trust_project_commands in your user config to run project commands without these checks",
+            cmd.name,
+            validation.severity.description(),
+            reasons.join("\n  - ")
+        );
+    }
+
     // Confirm if needed
     if cmd.confirm && !skip_confirm {
         print!("Execute '{}'? [y/N] ", cmd.command);
@@ -1156,44 +1675,272 @@ fn cmd_exec(name: &str, skip_confirm: bool, dry_run: bool) -> Result<()> {
         }
     }
 
+    if clean {
+        return exec_clean(&mut app, &cmd, &executor);
+    }
+
+    // Secret-bearing runs skip the result cache so their output is never stored
+    if with_secrets {
+        let secrets = resolve_exec_secrets()?;
+        let secret_cmd = palrun::env::SecretCommand::new(&cmd, &secrets);
+        drop(secrets);
+
+        println!("Executing: {}", executor.command_line(&cmd));
+        let result = executor.execute(&secret_cmd)?;
+        drop(secret_cmd);
+        record_exec(&mut app, &cmd, &result);
+
+        std::process::exit(result.exit_code());
+    }
+
+    if app.config.exec.cache.allows(&cmd) {
+        return exec_cached(&mut app, &cmd, &executor.capture(true));
+    }
+
     // Execute
-    println!("Executing: {}", cmd.command);
-    let executor = palrun::core::Executor::new();
+    println!("Executing: {}", executor.command_line(&cmd));
+    let result = executor.execute(&cmd)?;
+    record_exec(&mut app, &cmd, &result);
+
+    std::process::exit(result.exit_code());
+}
+
+/// Resolve the secret references in the project's `.env` files.
+fn resolve_exec_secrets() -> Result<Vec<palrun::env::ResolvedSecret>> {
+    let mut manager = palrun::env::SecretsManager::new(std::env::current_dir()?);
+    manager.scan_references()?;
+
+    manager
+        .get_references()
+        .iter()
+        .map(|reference| {
+            let secret = manager.resolve_reference(reference).map_err(|e| {
+                anyhow::anyhow!("Failed to resolve secret for {}: {e}", reference.variable)
+            })?;
+            palrun::security::register_secret(secret.value.clone());
+            Ok(secret)
+        })
+        .collect()
+}
+
+/// Execute a cacheable command, reusing a fresh result when there is one.
+fn exec_cached(
+    app: &mut App,
+    cmd: &palrun::Command,
+    executor: &palrun::core::Executor,
+) -> Result<()> {
+    use palrun::core::ResultCache;
+
+    let config = &app.config.exec.cache;
+    let cwd = std::env::current_dir()?;
+    let dir = cmd.working_dir.as_ref().map_or_else(|| cwd.clone(), |dir| cwd.join(dir));
+    let command_line = executor.command_line(cmd);
+    let key = ResultCache::key(&command_line, &dir, &config.watched_files(cmd, &dir));
+    let cache = ResultCache::new(std::time::Duration::from_secs(config.ttl_secs))?;
+
+    let started = std::time::Instant::now();
+    if let Some(cached) = cache.get(&key) {
+        print!("{}", cached.stdout);
+        eprintln!("(cached)");
+        // Only successful runs are cached
+        record_run(app, cmd, started.elapsed(), Some(0));
+        return Ok(());
+    }
+
+    println!("Executing: {command_line}");
     let result = executor.execute(cmd)?;
+    let stdout = result.stdout.as_deref().unwrap_or_default();
+    print!("{stdout}");
+    eprint!("{}", result.stderr.as_deref().unwrap_or_default());
+
+    if result.success() {
+        if let Err(e) = cache.put(&key, stdout) {
+            tracing::warn!("Could not cache result: {e}");
+        }
+    }
+    record_exec(app, cmd, &result);
 
-    std::process::exit(result.code().unwrap_or(0));
+    std::process::exit(result.exit_code());
+}
+
+/// Record a `pal exec` run in history so `--last-failed` can find it.
+fn record_exec(app: &mut App, cmd: &palrun::Command, result: &palrun::core::ExecutionResult) {
+    record_run(app, cmd, result.duration, result.code());
+}
+
+/// Record a run of `cmd` that took `duration` and exited with `code`.
+fn record_run(
+    app: &mut App,
+    cmd: &palrun::Command,
+    duration: std::time::Duration,
+    code: Option<i32>,
+) {
+    let duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+    if let Some(ref mut manager) = app.history_manager {
+        manager.record_exit(&cmd.id, &cmd.name, duration_ms, code);
+        let _ = manager.save();
+    }
+}
+
+/// Execute a command on a clean working tree (`pal exec --clean`).
+#[cfg(feature = "git")]
+fn exec_clean(
+    app: &mut App,
+    cmd: &palrun::Command,
+    executor: &palrun::core::Executor,
+) -> Result<()> {
+    let mut repo = palrun::git::discover_repo()
+        .ok_or_else(|| anyhow::anyhow!("--clean requires a Git repository"))?;
+
+    if palrun::git::WorkingTree::is_dirty(&repo) {
+        println!("Stashing local changes...");
+    }
+
+    let run = palrun::git::run_clean(&mut repo, || {
+        println!("Executing: {}", executor.command_line(cmd));
+        executor.execute(cmd)
+    })?;
+
+    if run.stashed {
+        println!("Restored stashed changes.");
+    }
+    record_exec(app, cmd, &run.output);
+
+    std::process::exit(run.output.exit_code());
+}
+
+/// Execute a command on a clean working tree (`pal exec --clean`).
+#[cfg(not(feature = "git"))]
+fn exec_clean(
+    _app: &mut App,
+    _cmd: &palrun::Command,
+    _executor: &palrun::core::Executor,
+) -> Result<()> {
+    anyhow::bail!("--clean requires Palrun to be built with the 'git' feature")
 }
 
 /// Scan a project and show discovered commands.
-fn cmd_scan(path: &str, recursive: bool) -> Result<()> {
-    use palrun::scanner::ProjectScanner;
+fn cmd_scan(
+    path: &str,
+    recursive: bool,
+    explain: bool,
+    format: &str,
+    save: Option<&std::path::Path>,
+    diff: Option<&std::path::Path>,
+) -> Result<()> {
+    use palrun::scanner::{ProjectScanner, Provenance, ScanLimits};
+    use palrun::Config;
 
+    let Config { scanner: config, ui, .. } = Config::load().unwrap_or_default();
     let path = std::path::Path::new(path);
-    let scanner = ProjectScanner::new(path);
+    let scanner = ProjectScanner::new(path).with_limits(ScanLimits::from(&config));
 
-    let commands = if recursive { scanner.scan_recursive(5)? } else { scanner.scan()? };
+    let commands =
+        if recursive { scanner.scan_recursive(config.max_depth)? } else { scanner.scan()? };
+
+    if save.is_some() || diff.is_some() || format == "json" {
+        let mut commands = palrun::scanner::relativize(&commands, path);
+        palrun::scanner::sort_commands(&mut commands);
+        if let Some(file) = save {
+            std::fs::write(file, serde_json::to_string_pretty(&commands)?)?;
+            eprintln!("Saved {} commands to {}", commands.len(), file.display());
+        }
+        if let Some(baseline) = diff {
+            return print_scan_diff(baseline, &commands, format);
+        }
+        if format == "json" {
+            println!("{}", serde_json::to_string_pretty(&commands)?);
+        }
+        return Ok(());
+    }
 
     println!("Discovered {} commands in {:?}\n", commands.len(), path);
 
     // Group by source
-    let mut by_source: std::collections::HashMap<&str, Vec<_>> = std::collections::HashMap::new();
+    let mut by_source: std::collections::BTreeMap<&str, Vec<_>> = std::collections::BTreeMap::new();
     for cmd in &commands {
         by_source.entry(cmd.source.type_name()).or_default().push(cmd);
     }
 
+    // Plugins don't run here, so only built-in sources need icons
+    let sources = palrun::core::SourceRegistry::new();
     for (source, cmds) in &by_source {
-        println!("{}:", source.to_uppercase());
+        println!("{} {}:", ui.icons.icon(&cmds[0].source, &sources), source.to_uppercase());
         for cmd in cmds {
             println!("  - {}", cmd.name);
+            if explain {
+                if let Some(provenance) = Provenance::of(cmd) {
+                    println!("      {provenance}");
+                }
+            }
+        }
+        println!();
+    }
+
+    for drift in palrun::scanner::check_lockfiles(path) {
+        println!("warning: {}", drift.message());
+    }
+
+    Ok(())
+}
+
+/// Print how `commands` differ from a saved scan baseline.
+fn print_scan_diff(
+    baseline: &std::path::Path,
+    commands: &[palrun::Command],
+    format: &str,
+) -> Result<()> {
+    let content = std::fs::read_to_string(baseline)
+        .map_err(|e| anyhow::anyhow!("Failed to read baseline {}: {e}", baseline.display()))?;
+    let saved: Vec<palrun::Command> = serde_json::from_str(&content).map_err(|e| {
+        anyhow::anyhow!("{} is not a `pal scan --format json` snapshot: {e}", baseline.display())
+    })?;
+    let diff = palrun::scanner::diff_commands(&saved, commands);
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        return Ok(());
+    }
+
+    let location = |cmd: &palrun::Command| {
+        cmd.working_dir.as_ref().map(|dir| format!(" ({})", dir.display())).unwrap_or_default()
+    };
+
+    println!(
+        "Compared with {}: {} added, {} removed, {} changed\n",
+        baseline.display(),
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    );
+    for cmd in &diff.added {
+        println!("+ {}{}", cmd.name, location(cmd));
+    }
+    for cmd in &diff.removed {
+        println!("- {}{}", cmd.name, location(cmd));
+    }
+    for change in &diff.changed {
+        println!("~ {}{}", change.after.name, location(&change.after));
+        if change.fields.contains(&"command") {
+            println!("    {} -> {}", change.before.command, change.after.command);
+        }
+        let others: Vec<_> = change.fields.iter().filter(|f| **f != "command").copied().collect();
+        if !others.is_empty() {
+            println!("    changed: {}", others.join(", "));
         }
-        println!();
     }
 
     Ok(())
 }
 
 /// Run a runbook.
-fn cmd_runbook(name: &str, dry_run: bool, vars: &[String]) -> Result<()> {
+fn cmd_runbook(
+    name: &str,
+    dry_run: bool,
+    vars: &[String],
+    from_step: Option<&str>,
+    only: &[String],
+) -> Result<()> {
     use palrun::runbook::{discover_runbooks, RunbookRunner};
 
     let cwd = std::env::current_dir()?;
@@ -1211,16 +1958,27 @@ fn cmd_runbook(name: &str, dry_run: bool, vars: &[String]) -> Result<()> {
     }
     println!("Steps: {}\n", runbook.steps.len());
 
+    let mut runner = RunbookRunner::new(runbook);
+
+    let warnings = if let Some(step) = from_step {
+        runner.start_from(step)?
+    } else if !only.is_empty() {
+        runner.only(only)?
+    } else {
+        Vec::new()
+    };
+    for warning in warnings {
+        eprintln!("Warning: {warning}");
+    }
+
     if dry_run {
         println!("DRY RUN - Steps that would be executed:");
-        for (i, step) in runbook.steps.iter().enumerate() {
+        for (i, step) in runner.planned_steps().iter().enumerate() {
             println!("  {}. {} - {}", i + 1, step.name, step.command);
         }
         return Ok(());
     }
 
-    let mut runner = RunbookRunner::new(runbook);
-
     // Set variables from command line
     for var_str in vars {
         if let Some((key, value)) = var_str.split_once('=') {
@@ -1242,14 +2000,23 @@ fn cmd_completions(shell: Shell) {
 
 /// Output shell initialization script.
 fn cmd_init(shell: &str) -> Result<()> {
-    let script = match shell.to_lowercase().as_str() {
-        "bash" => include_str!("../shell/palrun.bash"),
-        "zsh" => include_str!("../shell/palrun.zsh"),
-        "fish" => include_str!("../shell/palrun.fish"),
-        "powershell" | "pwsh" => include_str!("../shell/palrun.ps1"),
-        _ => anyhow::bail!("Unsupported shell: {shell}. Supported: bash, zsh, fish, powershell"),
+    use palrun::init::{detect_shell, init_script, SUPPORTED_SHELLS};
+
+    let shell = if shell.eq_ignore_ascii_case("auto") {
+        detect_shell().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Could not detect your shell from the parent process or $SHELL. \
+                 Run `palrun init <shell>` with one of: {SUPPORTED_SHELLS}"
+            )
+        })?
+    } else {
+        shell
     };
 
+    let script = init_script(shell).ok_or_else(|| {
+        anyhow::anyhow!("Unsupported shell: {shell}. Supported: {SUPPORTED_SHELLS}")
+    })?;
+
     println!("{script}");
     Ok(())
 }
@@ -1278,9 +2045,22 @@ fn cmd_config(show_path: bool) -> Result<()> {
         return Ok(());
     }
 
-    let config = Config::load()?;
+    let cwd = std::env::current_dir()?;
+    let (config, sources) = Config::load_with_sources(&cwd)?;
+
+    println!("# Effective configuration for {}", sources.root.display());
+    if sources.files.is_empty() {
+        println!("# No config files found; using defaults");
+    } else {
+        println!("# Merged from (later files override earlier ones):");
+        for file in &sources.files {
+            println!("#   {}", file.display());
+        }
+    }
+    println!();
+
     let toml = toml::to_string_pretty(&config)?;
-    println!("{toml}");
+    print!("{}", sources.annotate(&toml));
 
     Ok(())
 }
@@ -1577,9 +2357,15 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
         context = context.with_commands(commands);
 
         // Initialize AI manager
-        let ai = AIManager::new().await;
+        let ai = AIManager::new().await.with_routing(app.config.ai.routing.clone());
 
         if !ai.is_available() {
+            if palrun::core::is_offline_mode() {
+                anyhow::bail!(
+                    "No AI provider available in offline mode.\n\
+                     Run Ollama locally to use AI features without network access."
+                );
+            }
             anyhow::bail!(
                 "No AI provider available.\n\
                  Set ANTHROPIC_API_KEY for Claude, or run Ollama locally."
@@ -1588,12 +2374,40 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
 
         match operation {
             AiOperation::Gen { prompt, execute } => {
+                use futures::StreamExt;
+
                 println!("Generating command...\n");
 
-                let command = ai.generate_command(&prompt, &context).await?;
-                println!("Generated: {command}");
+                let mut stream = ai.generate_command_stream(&prompt, &context).await?;
+                let mut response = String::new();
+                print!("Generated: ");
+                while let Some(chunk) = stream.next().await {
+                    let chunk = chunk?;
+                    print!("{chunk}");
+                    io::stdout().flush()?;
+                    response.push_str(&chunk);
+                }
+                println!();
+                drop(stream);
+
+                let command = palrun::ai::extract_command(&response);
 
                 if execute {
+                    let security = palrun::SecurityManager::new(
+                        palrun::SecurityConfig::default().with_trusted_project_commands(
+                            app.config.security.trust_project_commands,
+                        ),
+                    );
+                    let validation = security
+                        .validate_with_source(&command, &palrun::core::CommandSource::AiGenerated);
+                    if !validation.is_safe() {
+                        println!("\nNot executing: {}", validation.severity.description());
+                        for error in &validation.errors {
+                            println!("  - {}", error.description());
+                        }
+                        return Ok(());
+                    }
+
                     print!("\nExecute? [y/N] ");
                     io::stdout().flush()?;
 
@@ -1601,10 +2415,11 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
                     io::stdin().read_line(&mut input)?;
 
                     if input.trim().eq_ignore_ascii_case("y") {
-                        let cmd = palrun::Command::new("ai-generated", &command);
+                        let cmd = palrun::Command::new("ai-generated", &command)
+                            .with_source(palrun::core::CommandSource::AiGenerated);
                         let executor = palrun::core::Executor::new();
                         let result = executor.execute(&cmd)?;
-                        std::process::exit(result.code().unwrap_or(0));
+                        std::process::exit(result.exit_code());
                     }
                 }
             }
@@ -1629,9 +2444,23 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
                 } else {
                     println!("No AI provider available");
                 }
+
+                if app.config.ai.routing.is_some() {
+                    use palrun::ai::TaskCategory;
+
+                    println!("\nRouting:");
+                    for (task, category) in [
+                        ("gen", TaskCategory::CodeGeneration),
+                        ("explain", TaskCategory::Documentation),
+                        ("diagnose", TaskCategory::ErrorDiagnosis),
+                    ] {
+                        let provider = ai.routed_provider(category).unwrap_or("(unavailable)");
+                        println!("  {task:<10} {provider}");
+                    }
+                }
             }
 
-            AiOperation::Agent { task, max_iterations, local } => {
+            AiOperation::Agent { task, max_iterations, tool_budget, yes, local } => {
                 use palrun::ai::{
                     AIProvider, Agent, AgentProvider, AgentState, MCPToolExecutor, OllamaProvider,
                 };
@@ -1648,13 +2477,7 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
 
                 // Add MCP servers from config
                 for server_entry in &config.mcp.servers {
-                    let mcp_config = MCPServerConfig {
-                        name: server_entry.name.clone(),
-                        command: server_entry.command.clone(),
-                        args: server_entry.args.clone(),
-                        env: server_entry.env.clone(),
-                        cwd: server_entry.cwd.clone(),
-                    };
+                    let mcp_config = MCPServerConfig::from(server_entry);
 
                     if let Err(e) = executor.add_server(mcp_config) {
                         eprintln!("Warning: Failed to add server '{}': {}", server_entry.name, e);
@@ -1683,9 +2506,13 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
                 }
 
                 // Create agent state
-                let state = AgentState::new(context.clone())
+                let mut state = AgentState::new(context.clone())
                     .with_tools(tools)
-                    .with_max_iterations(max_iterations);
+                    .with_max_iterations(max_iterations)
+                    .with_confirm_destructive(!yes);
+                if let Some(budget) = tool_budget {
+                    state = state.with_tool_budget(budget);
+                }
 
                 // Run agent
                 if local {
@@ -1700,19 +2527,7 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
 
                     let mut agent = Agent::new(provider, executor);
                     let final_state = agent.run(&task, state).await?;
-
-                    // Print final response
-                    if let Some(response) =
-                        Agent::<OllamaProvider, MCPToolExecutor>::get_final_response(&final_state)
-                    {
-                        println!("\n--- Agent Response ---\n");
-                        println!("{}", response);
-                    }
-
-                    println!(
-                        "\nAgent completed in {} iteration(s).",
-                        final_state.current_iteration
-                    );
+                    print_agent_summary(&final_state);
                 } else {
                     // Use provider from AIManager (Claude or Ollama)
                     let provider = OllamaProvider::new();
@@ -1727,19 +2542,7 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
 
                     let mut agent = Agent::new(provider, executor);
                     let final_state = agent.run(&task, state).await?;
-
-                    // Print final response
-                    if let Some(response) =
-                        Agent::<OllamaProvider, MCPToolExecutor>::get_final_response(&final_state)
-                    {
-                        println!("\n--- Agent Response ---\n");
-                        println!("{}", response);
-                    }
-
-                    println!(
-                        "\nAgent completed in {} iteration(s).",
-                        final_state.current_iteration
-                    );
+                    print_agent_summary(&final_state);
                 }
             }
 
@@ -1753,6 +2556,22 @@ fn cmd_ai(operation: AiOperation) -> Result<()> {
     })
 }
 
+/// Print the agent's final response and how the run ended.
+#[cfg(feature = "ai")]
+fn print_agent_summary(state: &palrun::ai::AgentState) {
+    use palrun::ai::{Agent, AgentStopReason, MCPToolExecutor, OllamaProvider};
+
+    if let Some(response) = Agent::<OllamaProvider, MCPToolExecutor>::get_final_response(state) {
+        println!("\n--- Agent Response ---\n");
+        println!("{}", response);
+    }
+
+    println!("\nAgent completed in {} iteration(s).", state.current_iteration);
+    if state.stop_reason == Some(AgentStopReason::BudgetExhausted) {
+        println!("Stopped after {} tool call(s): tool budget exhausted.", state.tool_calls_made);
+    }
+}
+
 /// Check Ollama status for AI chat.
 #[cfg(feature = "ai")]
 fn check_ollama_status() -> String {
@@ -1781,7 +2600,7 @@ fn cmd_hooks(operation: HooksOperation) -> Result<()> {
         HooksManager::discover().ok_or_else(|| anyhow::anyhow!("Not in a Git repository"))?;
 
     match operation {
-        HooksOperation::List => {
+        HooksOperation::List { show } => {
             let hooks = manager.list_hooks();
 
             if hooks.is_empty() {
@@ -1795,7 +2614,12 @@ fn cmd_hooks(operation: HooksOperation) -> Result<()> {
                 for hook in hooks {
                     let managed = if hook.is_palrun { " (palrun)" } else { "" };
                     let exec = if hook.is_executable { "" } else { " [not executable]" };
-                    println!("  {} {}{}", hook.name, managed, exec);
+                    let delegate = hook.delegate.map(|d| format!(" (via {d})")).unwrap_or_default();
+                    println!("  {} {}{}{}", hook.name, managed, delegate, exec);
+
+                    if show {
+                        print_hook_details(&hook);
+                    }
                 }
             }
 
@@ -1804,16 +2628,31 @@ fn cmd_hooks(operation: HooksOperation) -> Result<()> {
             let configured = config.hooks.get_configured_hooks();
             if !configured.is_empty() {
                 println!("\nConfigured in palrun.toml:");
-                for (name, cmd) in &configured {
+                for (name, commands) in &configured {
                     let installed = if manager.hook_exists(name) { " [installed]" } else { "" };
-                    println!("  {name}: {cmd}{installed}");
+                    println!("  {name}: {}{installed}", commands.join(", then "));
                 }
                 println!("\nRun 'pal hooks sync' to install configured hooks.");
             }
         }
 
-        HooksOperation::Install { hook, command, force } => {
-            manager.install_hook(&hook, &command, force)?;
+        HooksOperation::Install { hook, command, force, require_signing, template } => {
+            let hook = match (hook, &template) {
+                (Some(hook), _) => hook,
+                (None, Some(template)) => palrun::git::hooks::template_hook(template)?.to_string(),
+                (None, None) => "pre-commit".to_string(),
+            };
+            let command = match command {
+                Some(command) => {
+                    manager.install_hook(&hook, &command, force)?;
+                    command
+                }
+                None if require_signing => manager.install_signing_check(&hook, force)?,
+                None => match template {
+                    Some(template) => manager.install_named_template(&hook, &template, force)?,
+                    None => manager.install_template(&hook, force)?,
+                },
+            };
             println!("Installed hook: {hook}");
             println!("Command: {command}");
         }
@@ -1836,8 +2675,8 @@ fn cmd_hooks(operation: HooksOperation) -> Result<()> {
                 println!("No hooks configured in palrun.toml.");
                 println!("\nAdd hooks to your configuration:");
                 println!("  [hooks]");
-                println!("  pre-commit = \"cargo test && cargo fmt --check\"");
-                println!("  pre-push = \"cargo build --release\"");
+                println!("  pre_commit = [\"cargo fmt --check\", \"cargo test\"]");
+                println!("  pre_push = \"cargo build --release\"");
                 return Ok(());
             }
 
@@ -1846,24 +2685,151 @@ fn cmd_hooks(operation: HooksOperation) -> Result<()> {
             let mut installed = 0;
             let mut skipped = 0;
 
-            for (name, command) in &configured {
+            for (name, commands) in &configured {
                 if manager.hook_exists(name) && !manager.is_palrun_hook(name) && !force {
                     println!("  {name}: skipped (external hook exists, use --force)");
                     skipped += 1;
                 } else {
-                    manager.install_hook(name, command, force)?;
-                    println!("  {name}: installed");
+                    manager.install_runner(name, force)?;
+                    println!("  {name}: installed ({} command(s))", commands.len());
                     installed += 1;
                 }
             }
 
             println!("\nSynced {installed} hooks ({skipped} skipped)");
         }
+
+        HooksOperation::Run { hook, args } => manager.run(&hook, &args)?,
+
+        HooksOperation::Builtin { hook } => run_builtin_hook(hook)?,
+    }
+
+    Ok(())
+}
+
+/// Run a built-in hook template.
+#[cfg(feature = "git")]
+fn run_builtin_hook(hook: BuiltinHook) -> Result<()> {
+    use palrun::git::hooks::{install_commands_for, prepend_ticket, IssuePattern, SigningConfig};
+
+    match hook {
+        BuiltinHook::PrepareCommitMsg { file, source, .. } => {
+            // Leave merges, squashes, and amends alone
+            if matches!(source.as_deref(), Some("merge" | "squash" | "commit")) {
+                return Ok(());
+            }
+
+            // A bad config or pattern should not block commits, so warn and carry on
+            let config = palrun::core::Config::load().unwrap_or_else(|e| {
+                eprintln!("palrun: {e:#}; using the default issue pattern");
+                palrun::core::Config::default()
+            });
+            let pattern = match config.hooks.issue_pattern {
+                Some(pattern) => match IssuePattern::new(&pattern) {
+                    Ok(pattern) => pattern,
+                    Err(e) => {
+                        eprintln!("palrun: {e:#}; commit message left unchanged");
+                        return Ok(());
+                    }
+                },
+                None => IssuePattern::default(),
+            };
+
+            let Some(ticket) = palrun::git::discover_repo()
+                .and_then(|repo| repo.current_branch())
+                .and_then(|branch| pattern.find(&branch))
+            else {
+                return Ok(());
+            };
+
+            let message = std::fs::read_to_string(&file)?;
+            if let Some(updated) = prepend_ticket(&message, &ticket) {
+                std::fs::write(&file, updated)?;
+            }
+        }
+
+        BuiltinHook::CheckSigning => {
+            let repo = palrun::git::discover_repo()
+                .ok_or_else(|| anyhow::anyhow!("Not in a Git repository"))?;
+
+            if let Err(problems) = SigningConfig::from_repo(&repo).check() {
+                eprintln!("palrun: this repository requires signed commits.");
+                for problem in &problems {
+                    eprintln!("  - {problem}");
+                }
+                std::process::exit(1);
+            }
+        }
+
+        BuiltinHook::PostMerge { suggest, .. } => {
+            let output = std::process::Command::new("git")
+                .args(["diff-tree", "-r", "--name-only", "--no-commit-id", "ORIG_HEAD", "HEAD"])
+                .output()?;
+            if !output.status.success() {
+                return Ok(());
+            }
+
+            let changed: Vec<String> =
+                String::from_utf8_lossy(&output.stdout).lines().map(String::from).collect();
+
+            // Hooks run at the top of the work tree, which the paths are relative to
+            for (dir, command) in install_commands_for(&changed) {
+                let location = if dir.as_os_str().is_empty() {
+                    String::new()
+                } else {
+                    format!(" in {}", dir.display())
+                };
+                if suggest {
+                    println!(
+                        "palrun: lockfile changed, run `{command}`{location} to update dependencies"
+                    );
+                    continue;
+                }
+
+                println!("palrun: lockfile changed, running `{command}`{location}...");
+                let mut process = std::process::Command::new("sh");
+                process.args(["-c", command]);
+                if !dir.as_os_str().is_empty() {
+                    process.current_dir(&dir);
+                }
+                let status = process.status()?;
+                if !status.success() {
+                    eprintln!("palrun: `{command}`{location} failed ({status})");
+                }
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Print what a hook runs, for `pal hooks list --show`.
+fn print_hook_details(hook: &palrun::git::hooks::HookInfo) {
+    const MAX_LINES: usize = 10;
+
+    if let Some(ref command) = hook.managed_command {
+        for line in command.lines() {
+            println!("      $ {line}");
+        }
+        println!();
+        return;
+    }
+
+    let Ok(content) = std::fs::read_to_string(&hook.path) else {
+        println!("      (unreadable)\n");
+        return;
+    };
+
+    let body = palrun::git::hooks::script_body(&content);
+    for line in body.iter().take(MAX_LINES) {
+        println!("      {line}");
+    }
+    if body.len() > MAX_LINES {
+        println!("      ... ({} more lines in {})", body.len() - MAX_LINES, hook.path.display());
+    }
+    println!();
+}
+
 /// Handle environment commands.
 fn cmd_env(operation: EnvOperation) -> Result<()> {
     use palrun::env::EnvManager;
@@ -2028,6 +2994,27 @@ fn cmd_env(operation: EnvOperation) -> Result<()> {
                 }
             }
         }
+
+        EnvOperation::Set { assignment, file } => {
+            let Some((key, value)) = assignment.split_once('=') else {
+                anyhow::bail!("Expected KEY=value, got '{assignment}'");
+            };
+            let path = cwd.join(&file);
+            manager.set_variable_in_file(&path, key.trim(), value)?;
+            println!("Set {} in {}", key.trim(), file);
+        }
+
+        EnvOperation::Unset { key, file } => {
+            let path = cwd.join(&file);
+            if !path.exists() {
+                anyhow::bail!("File not found: {}", file);
+            }
+            if manager.remove_variable_from_file(&path, &key)? {
+                println!("Removed {key} from {file}");
+            } else {
+                println!("{key} is not set in {file}");
+            }
+        }
     }
 
     Ok(())
@@ -2100,6 +3087,16 @@ fn cmd_versions(show_all: bool) -> Result<()> {
         }
     }
 
+    let engines = manager.check_engines();
+    if !engines.is_empty() {
+        println!("package.json engines:\n");
+        for check in &engines {
+            println!("  {} {} {}", check.status_icon(), check.engine, check.required);
+            println!("      Current:  {}", check.current.as_deref().unwrap_or("not installed"));
+        }
+        println!();
+    }
+
     // Summary
     let with_requirements: Vec<_> = versions.values().filter(|v| v.required.is_some()).collect();
     let incompatible: Vec<_> =
@@ -2108,6 +3105,186 @@ fn cmd_versions(show_all: bool) -> Result<()> {
     if !incompatible.is_empty() {
         println!("Warning: {} runtime(s) have version mismatches", incompatible.len());
     }
+    for warning in engines.iter().filter_map(palrun::env::EngineCheck::warning) {
+        println!("Warning: {warning}");
+    }
+
+    Ok(())
+}
+
+/// Check the project setup, failing if any problem is found.
+///
+/// Covers the configuration, required runtime versions, package.json
+/// `engines` against the active Node.js and npm, and lockfiles older than
+/// their manifests.
+fn cmd_doctor() -> Result<ExitCode> {
+    use palrun::env::VersionManager;
+
+    let cwd = std::env::current_dir()?;
+    let mut problems = 0;
+
+    match palrun::core::Config::load() {
+        Ok(_) => println!("✓ Configuration loads"),
+        Err(e) => {
+            println!("✗ Configuration: {e}");
+            problems += 1;
+        }
+    }
+
+    let mut manager = VersionManager::new(&cwd);
+    manager.scan()?;
+
+    let mismatched = manager.mismatched();
+    for version in &mismatched {
+        let runtime = version.runtime;
+        let required = version.required.as_deref().unwrap_or_default();
+        let current = version.current.as_deref().unwrap_or("not installed");
+        println!("✗ {} requires {required}, but the active version is {current}", runtime.name());
+        problems += 1;
+    }
+
+    for check in manager.check_engines() {
+        match check.warning() {
+            Some(warning) => {
+                println!("✗ {warning}");
+                problems += 1;
+            }
+            None => println!(
+                "{} package.json engines.{} {}",
+                check.status_icon(),
+                check.engine,
+                check.required
+            ),
+        }
+    }
+
+    for drift in palrun::scanner::check_lockfiles(&cwd) {
+        println!("✗ {}", drift.message());
+        problems += 1;
+    }
+
+    if problems == 0 {
+        println!("\nNo problems found.");
+        return Ok(ExitCode::SUCCESS);
+    }
+    println!("\n{problems} problem(s) found.");
+    if !mismatched.is_empty() {
+        println!("Run 'pal versions use' to switch to the required runtime versions.");
+    }
+    Ok(ExitCode::FAILURE)
+}
+
+/// Switch mismatched runtimes to their required versions.
+fn cmd_versions_use(dry_run: bool, yes: bool) -> Result<()> {
+    use palrun::env::{switchable_version, VersionManager};
+
+    let cwd = std::env::current_dir()?;
+    let mut manager = VersionManager::new(&cwd);
+    manager.scan()?;
+
+    let mismatched = manager.mismatched();
+    if mismatched.is_empty() {
+        println!("All required runtime versions are active.");
+        return Ok(());
+    }
+
+    for version in mismatched {
+        let runtime = version.runtime;
+        let required = version.required.as_deref().unwrap_or_default();
+        println!("{} {} requires {}", runtime.icon(), runtime.name(), required);
+
+        let Some(target) = switchable_version(required) else {
+            println!("    '{required}' is a range; install a matching version manually.\n");
+            continue;
+        };
+
+        let Some(kind) = manager.resolve_manager(runtime) else {
+            println!("    No version manager found for {}.", runtime.name());
+            println!("    Install {target} manually, or install one of:");
+            for candidate in palrun::env::VersionManagerKind::candidates(runtime) {
+                println!("      - {}", candidate.name());
+            }
+            println!();
+            continue;
+        };
+
+        let install = kind.install_command(runtime, target);
+        let switch = kind.use_command(runtime, target);
+
+        if dry_run {
+            println!("    Would run: {install}  (if {target} is not installed)");
+            println!("    Would run: {switch}\n");
+            continue;
+        }
+
+        if !kind.has_version(runtime, target) {
+            if !yes {
+                print!("    {} {target} is not installed. Run `{install}`? [y/N] ", runtime.name());
+                io::stdout().flush()?;
+
+                let mut input = String::new();
+                io::stdin().read_line(&mut input)?;
+
+                if !input.trim().eq_ignore_ascii_case("y") {
+                    println!("    Skipped\n");
+                    continue;
+                }
+            }
+            kind.install(runtime, target)?;
+        }
+
+        println!("    Running: {switch}");
+        kind.switch_to(runtime, target)?;
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Handle security commands.
+fn cmd_security(operation: &SecurityOperation) -> Result<()> {
+    use palrun::security::{AuditReport, CommandValidator};
+
+    match operation {
+        SecurityOperation::Audit => {
+            let cwd = std::env::current_dir()?;
+            let mut report = AuditReport::new();
+
+            let mut app = App::new()?;
+            app.initialize()?;
+            let commands = app.registry.get_all();
+            report.audit_commands(commands, &CommandValidator::new());
+            report.audit_env_files(&cwd)?;
+
+            #[cfg(feature = "git")]
+            if let Some(manager) = palrun::git::HooksManager::discover() {
+                report.audit_git_hooks(&manager.list_hooks());
+            }
+
+            println!(
+                "Audited {} commands: {} finding{}\n",
+                commands.len(),
+                report.findings.len(),
+                if report.findings.len() == 1 { "" } else { "s" }
+            );
+            for (severity, findings) in report.by_severity() {
+                println!("{} ({})", severity.description(), findings.len());
+                for finding in findings {
+                    println!(
+                        "  [{}] {}: {}",
+                        finding.kind.label(),
+                        finding.location,
+                        finding.message
+                    );
+                }
+                println!();
+            }
+
+            if report.has_blocking_findings() {
+                std::process::exit(1);
+            }
+        }
+    }
 
     Ok(())
 }
@@ -2125,41 +3302,22 @@ fn cmd_secrets(operation: SecretsOperation) -> Result<()> {
 
             manager.check_providers();
 
-            // 1Password
-            if let Some(status) = manager.get_provider_status("1password") {
-                let icon = status.provider.icon();
-                let name = status.provider.name();
-
-                if status.installed {
-                    let version = status.version.as_deref().unwrap_or("unknown");
-                    let auth_status = if status.authenticated {
-                        "✓ authenticated"
-                    } else {
-                        "⚠ not signed in"
-                    };
-                    println!("  {} {} (v{})", icon, name, version);
-                    println!("      Status: {}", auth_status);
-                } else {
-                    println!("  {} {} - not installed", icon, name);
-                    if let Some(ref err) = status.error {
-                        println!("      {}", err);
-                    }
-                }
-                println!();
-            }
-
-            // Vault
-            if let Some(status) = manager.get_provider_status("vault") {
+            for (key, unauthenticated) in [
+                ("1password", "⚠ not signed in"),
+                ("vault", "⚠ not authenticated"),
+                ("aws", "⚠ not authenticated"),
+                ("gcp", "⚠ not authenticated"),
+            ] {
+                let Some(status) = manager.get_provider_status(key) else {
+                    continue;
+                };
                 let icon = status.provider.icon();
                 let name = status.provider.name();
 
                 if status.installed {
                     let version = status.version.as_deref().unwrap_or("unknown");
-                    let auth_status = if status.authenticated {
-                        "✓ authenticated"
-                    } else {
-                        "⚠ not authenticated"
-                    };
+                    let auth_status =
+                        if status.authenticated { "✓ authenticated" } else { unauthenticated };
                     println!("  {} {} ({})", icon, name, version);
                     println!("      Status: {}", auth_status);
                 } else {
@@ -2174,6 +3332,8 @@ fn cmd_secrets(operation: SecretsOperation) -> Result<()> {
             println!("Supported secret reference formats:");
             println!("  1Password: op://vault/item/field");
             println!("  Vault:     vault://path/to/secret#field");
+            println!("  AWS:       aws-sm://region/secret-name#json-key");
+            println!("  GCP:       gcp-sm://project/secret/version#json-key");
         }
 
         SecretsOperation::Scan => {
@@ -2203,19 +3363,13 @@ fn cmd_secrets(operation: SecretsOperation) -> Result<()> {
             manager.scan_references()?;
 
             let refs: Vec<_> = if let Some(ref p) = provider {
-                let provider_type = match p.to_lowercase().as_str() {
-                    "1password" | "op" => Some(SecretProvider::OnePassword),
-                    "vault" => Some(SecretProvider::Vault),
-                    _ => {
-                        anyhow::bail!("Unknown provider: {}. Use '1password' or 'vault'.", p);
-                    }
+                let Some(provider_type) = SecretProvider::from_name(p) else {
+                    anyhow::bail!(
+                        "Unknown provider: {}. Use '1password', 'vault', 'aws', or 'gcp'.",
+                        p
+                    );
                 };
-
-                if let Some(pt) = provider_type {
-                    manager.get_references_for_provider(&pt)
-                } else {
-                    vec![]
-                }
+                manager.get_references_for_provider(&provider_type)
             } else {
                 manager.get_references().iter().collect()
             };
@@ -2250,19 +3404,13 @@ fn cmd_secrets(operation: SecretsOperation) -> Result<()> {
 
             // Filter by provider if specified
             let refs_to_inject: Vec<_> = if let Some(ref p) = provider {
-                let provider_type = match p.to_lowercase().as_str() {
-                    "1password" | "op" => Some(SecretProvider::OnePassword),
-                    "vault" => Some(SecretProvider::Vault),
-                    _ => {
-                        anyhow::bail!("Unknown provider: {}. Use '1password' or 'vault'.", p);
-                    }
+                let Some(provider_type) = SecretProvider::from_name(p) else {
+                    anyhow::bail!(
+                        "Unknown provider: {}. Use '1password', 'vault', 'aws', or 'gcp'.",
+                        p
+                    );
                 };
-
-                if let Some(pt) = provider_type {
-                    manager.get_references_for_provider(&pt)
-                } else {
-                    vec![]
-                }
+                manager.get_references_for_provider(&provider_type)
             } else {
                 refs.iter().collect()
             };
@@ -2337,13 +3485,15 @@ fn parse_plugin_type(type_str: &str) -> Result<palrun::plugin::PluginType> {
 /// Handle plugin commands.
 #[cfg(feature = "plugins")]
 fn cmd_plugin(operation: PluginOperation) -> Result<()> {
+    use palrun::core::Config;
     use palrun::plugin::{PluginManager, PluginState, RegistryClient};
 
+    // Registry source (custom URL and local registries) comes from config
+    let config = Config::load().unwrap_or_default();
+
     // Get plugins directory
-    let plugins_dir = dirs::data_dir()
-        .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?
-        .join("palrun")
-        .join("plugins");
+    let plugins_dir = PluginManager::default_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not determine data directory"))?;
 
     // Get cache directory for registry
     let cache_dir = dirs::cache_dir()
@@ -2406,7 +3556,7 @@ fn cmd_plugin(operation: PluginOperation) -> Result<()> {
         PluginOperation::Search { query, plugin_type, all } => {
             println!("Searching for '{}'...\n", query);
 
-            let mut registry_client = RegistryClient::new(cache_dir)?;
+            let mut registry_client = RegistryClient::from_config(cache_dir, &config.plugins)?;
             let registry = registry_client.fetch(false)?;
 
             let mut results = registry.search(&query);
@@ -2450,7 +3600,7 @@ fn cmd_plugin(operation: PluginOperation) -> Result<()> {
         PluginOperation::Browse { plugin_type, sort, refresh } => {
             println!("Fetching plugin registry...\n");
 
-            let mut registry_client = RegistryClient::new(cache_dir)?;
+            let mut registry_client = RegistryClient::from_config(cache_dir, &config.plugins)?;
             let registry = registry_client.fetch(refresh)?;
 
             let mut plugins: Vec<_> = if let Some(ref type_str) = plugin_type {
@@ -2504,7 +3654,7 @@ fn cmd_plugin(operation: PluginOperation) -> Result<()> {
             }
         }
 
-        PluginOperation::Install { source, force } => {
+        PluginOperation::Install { source, force, no_verify } => {
             let path = std::path::Path::new(&source);
 
             if path.exists() {
@@ -2528,7 +3678,11 @@ fn cmd_plugin(operation: PluginOperation) -> Result<()> {
                 // Try to install from registry
                 println!("Looking up '{}' in registry...", source);
 
-                let mut registry_client = RegistryClient::new(cache_dir)?;
+                let mut registry_client = RegistryClient::from_config(cache_dir, &config.plugins)?;
+                if no_verify {
+                    eprintln!("Warning: installing without checksum or signature verification");
+                    registry_client = registry_client.without_verification();
+                }
                 let registry = registry_client.fetch(false)?;
 
                 // Clone the plugin data to avoid borrow issues
@@ -2696,7 +3850,7 @@ description = "{}"
                 }
             } else {
                 // Check registry
-                let mut registry_client = RegistryClient::new(cache_dir)?;
+                let mut registry_client = RegistryClient::from_config(cache_dir, &config.plugins)?;
                 let registry = registry_client.fetch(false)?;
 
                 if let Some(plugin) = registry.find(&name) {
@@ -2739,7 +3893,7 @@ description = "{}"
         }
 
         PluginOperation::Update { name, check } => {
-            let mut registry_client = RegistryClient::new(cache_dir)?;
+            let mut registry_client = RegistryClient::from_config(cache_dir, &config.plugins)?;
             let registry = registry_client.fetch(true)?;
 
             let plugins_to_check: Vec<_> = if let Some(ref plugin_name) = name {
@@ -2833,18 +3987,81 @@ description = "{}"
                             Err(e) => println!("failed (download): {}", e),
                         }
                     }
-                    println!("\nUpdate complete.");
+                    println!("\nUpdate complete.");
+                }
+            }
+        }
+
+        PluginOperation::ClearCache => {
+            let registry_client = RegistryClient::from_config(cache_dir, &config.plugins)?;
+            registry_client.clear_cache()?;
+            println!("Registry cache cleared.");
+        }
+
+        PluginOperation::Dev { path, watch } => {
+            let source = palrun::plugin::DevSource::detect(&path)?;
+            let project = std::env::current_dir()?;
+
+            let mut loaded = false;
+            let mut last_modified = source.last_modified();
+            loop {
+                match plugin_dev_reload(&mut manager, &source, &project) {
+                    Ok(()) => loaded = true,
+                    Err(e) if watch => {
+                        eprintln!("{e}");
+                        if loaded {
+                            eprintln!("Keeping the previously loaded version.");
+                        }
+                    }
+                    Err(e) => return Err(e),
+                }
+
+                if !watch {
+                    break;
+                }
+                println!();
+                println!("Watching for changes (Ctrl+C to stop)...");
+                loop {
+                    std::thread::sleep(std::time::Duration::from_millis(500));
+                    let modified = source.last_modified();
+                    if modified != last_modified {
+                        last_modified = modified;
+                        break;
+                    }
                 }
             }
         }
+    }
 
-        PluginOperation::ClearCache => {
-            let registry_client = RegistryClient::new(cache_dir)?;
-            registry_client.clear_cache()?;
-            println!("Registry cache cleared.");
-        }
+    Ok(())
+}
+
+/// Build and load a development plugin, then print what it finds in `project`.
+#[cfg(feature = "plugins")]
+fn plugin_dev_reload(
+    manager: &mut palrun::plugin::PluginManager,
+    source: &palrun::plugin::DevSource,
+    project: &std::path::Path,
+) -> Result<()> {
+    if matches!(source, palrun::plugin::DevSource::Crate { .. }) {
+        println!("Building for {}...", palrun::plugin::DEV_TARGET);
     }
+    let wasm = source.build()?;
+    let name = manager.load_transient(&wasm, &source.manifest_path())?;
+    let commands = manager.scan_plugin(&name, project)?;
 
+    println!("Loaded {} from {}", name, wasm.display());
+    if commands.is_empty() {
+        println!("No commands discovered in {}", project.display());
+    }
+    for command in &commands {
+        match &command.description {
+            Some(description) => {
+                println!("  {:<24} {}  # {}", command.name, command.command, description);
+            }
+            None => println!("  {:<24} {}", command.name, command.command),
+        }
+    }
     Ok(())
 }
 
@@ -2938,7 +4155,19 @@ fn cmd_ci(operation: CiOperation) -> Result<()> {
             }
         }
 
-        CiOperation::Runs { workflow, branch, limit } => {
+        CiOperation::Runs { workflow, branch, since, status, limit } => {
+            let created_since = since
+                .map(|spec| {
+                    palrun::integrations::parse_age(&spec)
+                        .map(|age| chrono::Utc::now() - age)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid --since '{}', e.g. 7d", spec))
+                })
+                .transpose()?;
+            let conclusion = status
+                .map(|s| s.parse::<palrun::integrations::WorkflowStatus>())
+                .transpose()
+                .map_err(|e| anyhow::anyhow!(e))?;
+
             // Find workflow ID if name provided
             let workflow_id = if let Some(ref wf) = workflow {
                 // Try to parse as ID first
@@ -2960,7 +4189,16 @@ fn cmd_ci(operation: CiOperation) -> Result<()> {
 
             println!("Recent workflow runs for {}/{}:\n", github.owner(), github.repo());
 
-            match github.list_runs(workflow_id, branch.as_deref(), limit) {
+            let options = palrun::integrations::ListRunsOptions {
+                workflow_id,
+                branch,
+                created_since,
+                conclusion,
+                per_page: limit.min(palrun::integrations::github_actions::MAX_PER_PAGE),
+                max: Some(limit),
+            };
+
+            match github.list_runs_with(&options) {
                 Ok(runs) => {
                     if runs.is_empty() {
                         println!("  No runs found.");
@@ -3064,6 +4302,73 @@ fn cmd_ci(operation: CiOperation) -> Result<()> {
             }
         }
 
+        CiOperation::Logs { run_id, job, lines, explain } => {
+            println!("Downloading logs for run {}...\n", run_id);
+
+            let logs = github
+                .download_run_logs(run_id)
+                .map_err(|e| anyhow::anyhow!("Failed to download logs: {}", e))?;
+
+            let selected: Vec<_> = if let Some(ref name) = job {
+                logs.iter().filter(|log| log.is_for(name)).collect()
+            } else {
+                // Prefer the jobs the API reports as failed, then any log
+                // the runner marked with an error
+                let failed: Vec<String> = github
+                    .list_jobs(run_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|j| j.conclusion.is_some_and(|c| c.is_failure()))
+                    .map(|j| j.name)
+                    .collect();
+                let by_job: Vec<_> =
+                    logs.iter().filter(|log| failed.iter().any(|name| log.is_for(name))).collect();
+                if by_job.is_empty() {
+                    logs.iter().filter(|log| log.has_errors()).collect()
+                } else {
+                    by_job
+                }
+            };
+
+            if selected.is_empty() {
+                match job {
+                    Some(name) => anyhow::bail!("No log found for job '{}'.", name),
+                    None => {
+                        println!("No failed jobs in run {}.", run_id);
+                        return Ok(());
+                    }
+                }
+            }
+
+            for log in &selected {
+                println!("── {} ──", log.name);
+                println!("{}\n", log.tail(lines));
+            }
+
+            if explain {
+                use palrun::ai::{AIManager, ProjectContext};
+
+                let rt = tokio::runtime::Runtime::new()?;
+                let summary = rt.block_on(async {
+                    let routing = palrun::Config::load().unwrap_or_default().ai.routing;
+                    let ai = AIManager::new().await.with_routing(routing);
+                    if !ai.is_available() {
+                        anyhow::bail!("No AI provider available to explain the failure.");
+                    }
+                    let context = ProjectContext::from_current_dir()?;
+                    let names: Vec<_> = selected.iter().map(|log| log.name.as_str()).collect();
+                    let output: Vec<_> = selected.iter().map(|log| log.tail(lines)).collect();
+                    ai.diagnose_error(
+                        &format!("GitHub Actions job(s) {} in run {}", names.join(", "), run_id),
+                        &output.join("\n"),
+                        &context,
+                    )
+                    .await
+                })?;
+                println!("Summary:\n{}", summary);
+            }
+        }
+
         CiOperation::Open { run_id } => {
             let url = if let Some(id) = run_id {
                 format!(
@@ -3111,22 +4416,38 @@ fn get_current_branch() -> Option<String> {
     }
 }
 
+/// Build a notification message from CLI arguments, optionally reading stdin.
+fn build_notify_message(
+    message: Option<String>,
+    title: Option<String>,
+    from_stdin: bool,
+) -> Result<palrun::integrations::NotificationMessage> {
+    use palrun::integrations::NotificationMessage;
+
+    let text = message.unwrap_or_else(|| "Command output".to_string());
+    let mut msg = if from_stdin {
+        NotificationMessage::from_reader(text, std::io::stdin().lock())
+            .map_err(|e| anyhow::anyhow!("Failed to read stdin: {}", e))?
+    } else {
+        NotificationMessage::text(text)
+    };
+
+    msg.title = title;
+    Ok(msg)
+}
+
 /// Handle notification commands.
-fn cmd_notify(operation: NotifyOperation) -> Result<()> {
+fn cmd_notify(operation: NotifyOperation) -> Result<ExitCode> {
     use palrun::integrations::{NotificationClient, NotificationConfig, NotificationMessage};
 
     let client = NotificationClient::new()
         .map_err(|e| anyhow::anyhow!("Failed to create notification client: {}", e))?;
 
     match operation {
-        NotifyOperation::Slack { url, message, title, color } => {
+        NotifyOperation::Slack { url, message, title, color, from_stdin } => {
             let config = NotificationConfig::slack("cli", &url);
 
-            let mut msg = if let Some(ref t) = title {
-                NotificationMessage::with_title(t, &message)
-            } else {
-                NotificationMessage::text(&message)
-            };
+            let mut msg = build_notify_message(message, title, from_stdin)?;
 
             if let Some(c) = color {
                 msg = msg.color(c);
@@ -3143,14 +4464,10 @@ fn cmd_notify(operation: NotifyOperation) -> Result<()> {
             }
         }
 
-        NotifyOperation::Discord { url, message, title, color } => {
+        NotifyOperation::Discord { url, message, title, color, from_stdin } => {
             let config = NotificationConfig::discord("cli", &url);
 
-            let mut msg = if let Some(ref t) = title {
-                NotificationMessage::with_title(t, &message)
-            } else {
-                NotificationMessage::text(&message)
-            };
+            let mut msg = build_notify_message(message, title, from_stdin)?;
 
             if let Some(c) = color {
                 msg = msg.color(c);
@@ -3167,14 +4484,30 @@ fn cmd_notify(operation: NotifyOperation) -> Result<()> {
             }
         }
 
-        NotifyOperation::Webhook { url, message, title } => {
+        NotifyOperation::Teams { url, message, title, color, from_stdin } => {
+            let config = NotificationConfig::teams("cli", &url);
+
+            let mut msg = build_notify_message(message, title, from_stdin)?;
+
+            if let Some(c) = color {
+                msg = msg.color(c);
+            }
+
+            println!("Sending Teams notification...");
+            match client.send(&config, &msg) {
+                Ok(()) => {
+                    println!("Notification sent successfully!");
+                }
+                Err(e) => {
+                    anyhow::bail!("Failed to send notification: {}", e);
+                }
+            }
+        }
+
+        NotifyOperation::Webhook { url, message, title, from_stdin } => {
             let config = NotificationConfig::webhook("cli", &url);
 
-            let msg = if let Some(ref t) = title {
-                NotificationMessage::with_title(t, &message)
-            } else {
-                NotificationMessage::text(&message)
-            };
+            let msg = build_notify_message(message, title, from_stdin)?;
 
             println!("Sending webhook notification...");
             match client.send(&config, &msg) {
@@ -3187,13 +4520,28 @@ fn cmd_notify(operation: NotifyOperation) -> Result<()> {
             }
         }
 
+        NotifyOperation::OnComplete { endpoint, threshold, command } => {
+            // A single argument is a full command line; several are quoted
+            let command_line = match command.as_slice() {
+                [line] => line.clone(),
+                args => args
+                    .iter()
+                    .map(|arg| palrun::core::shell_quote(arg))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            };
+            let code = notify_on_complete(&client, endpoint.as_deref(), threshold, &command_line)?;
+            return Ok(u8::try_from(code).map_or(ExitCode::FAILURE, ExitCode::from));
+        }
+
         NotifyOperation::Test { notification_type, url } => {
             let config = match notification_type.to_lowercase().as_str() {
                 "slack" => NotificationConfig::slack("test", &url),
                 "discord" => NotificationConfig::discord("test", &url),
+                "teams" => NotificationConfig::teams("test", &url),
                 "webhook" => NotificationConfig::webhook("test", &url),
                 _ => anyhow::bail!(
-                    "Unknown notification type: {}. Use: slack, discord, webhook",
+                    "Unknown notification type: {}. Use: slack, discord, teams, webhook",
                     notification_type
                 ),
             };
@@ -3216,14 +4564,119 @@ fn cmd_notify(operation: NotifyOperation) -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Run `command_line`, then notify the `[notify]` endpoints if it ran for
+/// at least the threshold. Returns the command's exit code.
+fn notify_on_complete(
+    client: &palrun::integrations::NotificationClient,
+    endpoint: Option<&str>,
+    threshold: Option<u64>,
+    command_line: &str,
+) -> Result<i32> {
+    use palrun::core::{Command, Executor, Stream};
+    use palrun::integrations::{NotificationEvent, NotificationMessage};
+
+    let config = palrun::core::Config::load()?;
+    let mut notify = config.notify;
+    if let Some(threshold) = threshold {
+        notify.threshold_secs = threshold;
+    }
+
+    let endpoints = match endpoint {
+        Some(name) => vec![notify
+            .endpoint(name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No [notify] endpoint named '{}'", name))?],
+        None => notify.endpoints.clone(),
+    };
+    if endpoints.is_empty() {
+        anyhow::bail!(
+            "No notification endpoints configured.\n\n\
+             Add one to your global config or .palrun.local.toml:\n\n  \
+             [[notify.endpoints]]\n  \
+             name = \"team\"\n  \
+             type = \"slack\"\n  \
+             webhook_url = \"https://hooks.slack.com/services/...\""
+        );
+    }
+
+    // Output is shown as it arrives; only the tail is kept for the message
+    let mut tail = std::collections::VecDeque::with_capacity(notify.tail_lines);
+    let command = Command::new("on-complete", command_line);
+    let mut executor = Executor::new();
+    if let Some(timeout) = config.general.timeout() {
+        executor = executor.timeout(timeout);
+    }
+    let result = executor.execute_streaming(&command, |line| {
+        match line.stream {
+            Stream::Stdout => println!("{}", line.text),
+            Stream::Stderr => eprintln!("{}", line.text),
+        }
+        if tail.len() == notify.tail_lines {
+            tail.pop_front();
+        }
+        if notify.tail_lines > 0 {
+            tail.push_back(line.text);
+        }
+    })?;
+
+    let code = result.exit_code();
+    if !notify.should_notify(result.duration) {
+        tracing::debug!(
+            "'{}' finished in {:.1}s, under the {}s notification threshold",
+            command_line,
+            result.duration.as_secs_f64(),
+            notify.threshold_secs
+        );
+        return Ok(code);
+    }
+
+    // The message leaves the machine, so scrub secrets even when output
+    // redaction is off
+    let redactor = palrun::security::Redactor::from_env().with_dotenv(&project_root());
+    let scrub = |text: &str| palrun::security::redact(&redactor.redact(text)).into_owned();
+    let output = scrub(&Vec::from(tail).join("\n"));
+    let msg = NotificationMessage::command_finished(
+        &scrub(command_line),
+        code,
+        result.duration,
+        &output,
+        notify.tail_lines,
+    );
+    let event = if result.success() {
+        NotificationEvent::CommandSuccess
+    } else {
+        NotificationEvent::CommandFailure
+    };
+
+    for config in &endpoints {
+        if !config.matches(NotificationEvent::CommandComplete, Some(command_line))
+            && !config.matches(event, Some(command_line))
+        {
+            continue;
+        }
+        if let Err(e) = client.send(config, &msg) {
+            eprintln!("Failed to notify '{}': {}", config.name, e);
+        }
+    }
+
+    Ok(code)
+}
+
+/// Root of the current project, or the current directory outside one.
+fn project_root() -> std::path::PathBuf {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    palrun::core::Config::find_project_root(&cwd).unwrap_or(cwd)
 }
 
 /// Handle GitHub Issues commands.
 fn cmd_issues(operation: IssuesOperation) -> Result<()> {
     use palrun::integrations::{
         github_issues::{format_issue, format_stats, CreateIssueOptions, ListIssuesOptions},
-        GitHubIssues,
+        issue_links::{github_link_comment, has_link_comment, linear_link_comment},
+        GitHubIssues, IssueLink, IssueLinks, LinearClient,
     };
 
     // Create tokio runtime for async operations
@@ -3280,6 +4733,11 @@ fn cmd_issues(operation: IssuesOperation) -> Result<()> {
                         println!("Milestone: {} ({})", milestone.title, milestone.state);
                     }
 
+                    let links = IssueLinks::load(&project_root()).unwrap_or_default();
+                    for link in links.for_github(number) {
+                        println!("Linear: {} ({})", link.linear, link.linear_url);
+                    }
+
                     if comments {
                         println!("\n--- Comments ---\n");
                         match github.list_comments(number).await {
@@ -3392,6 +4850,68 @@ fn cmd_issues(operation: IssuesOperation) -> Result<()> {
                 }
             }
 
+            IssuesOperation::Link { number, linear } => {
+                let linear_client = LinearClient::from_env().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Linear integration not configured.\n\n\
+                         To enable, set LINEAR_API_KEY with your Linear API key."
+                    )
+                })?;
+
+                let issue = github
+                    .get_issue(number)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to get issue #{}: {}", number, e))?;
+                let linear_issue = linear_client
+                    .get_issue(&linear)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to get issue {}: {}", linear, e))?;
+
+                // A recorded link means both comments were already posted
+                let root = project_root();
+                let mut links = IssueLinks::load(&root)?;
+                let recorded = links
+                    .for_github(number)
+                    .any(|l| l.linear.eq_ignore_ascii_case(&linear_issue.identifier));
+
+                if !recorded {
+                    let comments = github.list_comments(number).await?;
+                    if has_link_comment(comments.iter().map(|c| c.body.as_str()), &linear_issue.url)
+                    {
+                        println!("#{} already links to {}", number, linear_issue.identifier);
+                    } else {
+                        let body = github_link_comment(&linear_issue.identifier, &linear_issue.url);
+                        github.add_comment(number, &body).await?;
+                        println!("Commented on #{}", number);
+                    }
+
+                    let comments = linear_client.list_comments(&linear_issue.identifier).await?;
+                    if has_link_comment(comments.iter().map(|c| c.body.as_str()), &issue.html_url) {
+                        println!("{} already links to #{}", linear_issue.identifier, number);
+                    } else {
+                        let body = linear_link_comment(number, &issue.html_url);
+                        linear_client.add_comment(&linear_issue.id, &body).await?;
+                        println!("Commented on {}", linear_issue.identifier);
+                    }
+                }
+
+                let identifier = linear_issue.identifier.clone();
+                let added = links.add(IssueLink {
+                    github: number,
+                    github_url: issue.html_url,
+                    linear: linear_issue.identifier,
+                    linear_url: linear_issue.url,
+                    linked_at: chrono::Utc::now().to_rfc3339(),
+                });
+                links.save(&root)?;
+
+                if added {
+                    println!("\nLinked #{} ↔ {}", number, identifier);
+                } else {
+                    println!("#{} and {} are already linked", number, identifier);
+                }
+            }
+
             IssuesOperation::Search { query } => {
                 println!("Searching issues for: {}\n", query);
 
@@ -3615,6 +5135,31 @@ fn cmd_linear(operation: LinearOperation) -> Result<()> {
                 }
             }
 
+            LinearOperation::Done { identifier } => {
+                move_linear_issue(&linear, &identifier, "done").await?;
+            }
+
+            LinearOperation::Start { identifier } => {
+                move_linear_issue(&linear, &identifier, "started").await?;
+            }
+
+            LinearOperation::Move { identifier, state } => {
+                move_linear_issue(&linear, &identifier, &state).await?;
+            }
+
+            LinearOperation::Assign { identifier, user } => {
+                match linear.assign_issue(&identifier, &user).await {
+                    Ok(issue) => {
+                        let assignee =
+                            issue.assignee.as_ref().map_or(user.as_str(), |a| &a.display_name);
+                        println!("Assigned {} to {}", issue.identifier, assignee);
+                    }
+                    Err(e) => {
+                        anyhow::bail!("Failed to assign {}: {}", identifier, e);
+                    }
+                }
+            }
+
             LinearOperation::Teams => {
                 println!("Your Linear teams:\n");
 
@@ -3682,6 +5227,21 @@ fn cmd_linear(operation: LinearOperation) -> Result<()> {
     })
 }
 
+/// Move a Linear issue to the state named `state` and report the result.
+async fn move_linear_issue(
+    linear: &palrun::integrations::LinearClient,
+    identifier: &str,
+    state: &str,
+) -> Result<()> {
+    match linear.update_issue_state(identifier, state).await {
+        Ok(issue) => {
+            println!("{} → {}", issue.identifier, issue.state.name);
+            Ok(())
+        }
+        Err(e) => anyhow::bail!("Failed to update {}: {}", identifier, e),
+    }
+}
+
 /// Handle MCP (Model Context Protocol) commands.
 fn cmd_mcp(operation: McpOperation) -> Result<()> {
     use palrun::mcp::{MCPManager, MCPServerConfig};
@@ -3691,7 +5251,12 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
     let config = Config::load().unwrap_or_default();
 
     match operation {
-        McpOperation::Servers => {
+        McpOperation::Servers { format } if format == "json" => {
+            let servers: Vec<_> = config.mcp.servers.iter().map(|s| s.redacted()).collect();
+            println!("{}", serde_json::to_string_pretty(&servers)?);
+        }
+
+        McpOperation::Servers { .. } => {
             println!("Configured MCP servers:\n");
 
             if config.mcp.servers.is_empty() {
@@ -3701,12 +5266,13 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
                 println!("    name = \"my-server\"");
                 println!("    command = \"/path/to/mcp-server\"");
                 println!("    args = []");
+                println!("\n  Or connect to a remote server:");
+                println!("    [[mcp.servers]]");
+                println!("    name = \"remote\"");
+                println!("    url = \"https://example.com/mcp\"");
             } else {
                 for server in &config.mcp.servers {
-                    println!("  {} - {}", server.name, server.command);
-                    if !server.args.is_empty() {
-                        println!("    Args: {}", server.args.join(" "));
-                    }
+                    println!("  {} - {}", server.name, server.transport.describe());
                     if !server.env.is_empty() {
                         println!(
                             "    Env: {}",
@@ -3718,7 +5284,13 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
             }
         }
 
-        McpOperation::Tools { server } => {
+        McpOperation::Tools { server, format } => {
+            #[cfg(unix)]
+            if let Some(daemon) = mcp_daemon() {
+                let tools = daemon.list_tools(server.as_deref())?;
+                return print_mcp_tools(&tools, &format);
+            }
+
             if config.mcp.servers.is_empty() {
                 anyhow::bail!("No MCP servers configured.");
             }
@@ -3741,13 +5313,7 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
 
             // Start servers and list tools
             for server_entry in servers {
-                let mcp_config = MCPServerConfig {
-                    name: server_entry.name.clone(),
-                    command: server_entry.command.clone(),
-                    args: server_entry.args.clone(),
-                    env: server_entry.env.clone(),
-                    cwd: server_entry.cwd.clone(),
-                };
+                let mcp_config = MCPServerConfig::from(server_entry);
 
                 let _ = manager.add_server(mcp_config);
             }
@@ -3757,34 +5323,35 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
                 eprintln!("Warning: Some servers failed to start: {}", e);
             }
 
-            println!("Available MCP tools:\n");
-
             let tools = manager.list_tools();
-            if tools.is_empty() {
-                println!("  No tools available.");
-            } else {
-                for reg_tool in &tools {
-                    print!("  [{}] {}", reg_tool.server, reg_tool.tool.name);
-                    if let Some(ref desc) = reg_tool.tool.description {
-                        print!(" - {}", desc);
-                    }
-                    println!();
-
-                    // Show required parameters
-                    if let Some(ref required) = reg_tool.tool.input_schema.required {
-                        if !required.is_empty() {
-                            println!("    Required: {}", required.join(", "));
-                        }
-                    }
-                }
-                println!("\nTotal: {} tool(s)", tools.len());
-            }
+            print_mcp_tools(&tools, &format)?;
 
             // Stop all servers
             let _ = manager.stop_all();
         }
 
         McpOperation::Call { server, tool, args } => {
+            // Parse arguments
+            let arguments: Option<std::collections::HashMap<String, serde_json::Value>> =
+                if let Some(ref args_json) = args {
+                    Some(
+                        serde_json::from_str(args_json)
+                            .map_err(|e| anyhow::anyhow!("Invalid JSON arguments: {}", e))?,
+                    )
+                } else {
+                    None
+                };
+
+            #[cfg(unix)]
+            if let Some(daemon) = mcp_daemon() {
+                println!("Calling tool '{}' on server '{}'...\n", tool, server);
+                let result = daemon
+                    .call_tool(&server, &tool, arguments)
+                    .map_err(|e| anyhow::anyhow!("Failed to call tool: {}", e))?;
+                print_mcp_tool_result(&result);
+                return Ok(());
+            }
+
             let server_entry = config
                 .mcp
                 .servers
@@ -3792,43 +5359,16 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
                 .find(|s| s.name == server)
                 .ok_or_else(|| anyhow::anyhow!("Server '{}' not found.", server))?;
 
-            let mcp_config = MCPServerConfig {
-                name: server_entry.name.clone(),
-                command: server_entry.command.clone(),
-                args: server_entry.args.clone(),
-                env: server_entry.env.clone(),
-                cwd: server_entry.cwd.clone(),
-            };
+            let mcp_config = MCPServerConfig::from(server_entry);
 
             let mut manager = MCPManager::new();
             let _ = manager.add_server(mcp_config);
             manager.start_all()?;
 
-            // Parse arguments
-            let arguments: Option<std::collections::HashMap<String, serde_json::Value>> =
-                if let Some(ref args_json) = args {
-                    Some(
-                        serde_json::from_str(args_json)
-                            .map_err(|e| anyhow::anyhow!("Invalid JSON arguments: {}", e))?,
-                    )
-                } else {
-                    None
-                };
-
             println!("Calling tool '{}' on server '{}'...\n", tool, server);
 
             match manager.call_tool(&tool, arguments) {
-                Ok(result) => {
-                    if result.is_error.unwrap_or(false) {
-                        println!("Tool returned an error:");
-                    }
-
-                    for content in &result.content {
-                        if let Some(text) = content.as_text() {
-                            println!("{}", text);
-                        }
-                    }
-                }
+                Ok(result) => print_mcp_tool_result(&result),
                 Err(e) => {
                     anyhow::bail!("Failed to call tool: {}", e);
                 }
@@ -3838,6 +5378,13 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
         }
 
         McpOperation::Start { name } => {
+            #[cfg(unix)]
+            if let Some(daemon) = mcp_daemon() {
+                daemon.start_server(&name)?;
+                println!("Server '{}' started in the MCP daemon.", name);
+                return Ok(());
+            }
+
             let server_entry = config
                 .mcp
                 .servers
@@ -3845,13 +5392,7 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
                 .find(|s| s.name == name)
                 .ok_or_else(|| anyhow::anyhow!("Server '{}' not found.", name))?;
 
-            let mcp_config = MCPServerConfig {
-                name: server_entry.name.clone(),
-                command: server_entry.command.clone(),
-                args: server_entry.args.clone(),
-                env: server_entry.env.clone(),
-                cwd: server_entry.cwd.clone(),
-            };
+            let mcp_config = MCPServerConfig::from(server_entry);
 
             let mut manager = MCPManager::new();
             let _ = manager.add_server(mcp_config);
@@ -3887,10 +5428,85 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
         }
 
         McpOperation::Stop { name } => {
-            // In this CLI context, servers are ephemeral
-            // This command is mainly useful when we add daemon mode
-            println!("Server '{}' is not running (servers are ephemeral in CLI mode).", name);
-            println!("\nTip: Use 'pal mcp start {}' to start a persistent server.", name);
+            #[cfg(unix)]
+            if let Some(daemon) = mcp_daemon() {
+                match name {
+                    Some(name) => {
+                        daemon.stop_server(&name)?;
+                        println!("Server '{}' stopped.", name);
+                    }
+                    None => {
+                        daemon.shutdown()?;
+                        println!("MCP daemon stopped.");
+                    }
+                }
+                return Ok(());
+            }
+
+            match name {
+                Some(name) => {
+                    println!("Server '{}' is not running: no MCP daemon is running.", name);
+                }
+                None => println!("No MCP daemon is running."),
+            }
+            println!("\nTip: Use 'pal mcp daemon --detach' to keep servers running.");
+        }
+
+        #[cfg(unix)]
+        McpOperation::Daemon { detach, new_session } => {
+            use palrun::mcp::{DaemonClient, MCPDaemon};
+
+            let socket = MCPDaemon::default_socket_path(&project_root()).ok_or_else(|| {
+                anyhow::anyhow!("No runtime or data directory for the MCP daemon socket")
+            })?;
+            if DaemonClient::connect(&socket).is_some() {
+                anyhow::bail!("An MCP daemon is already running at {}", socket.display());
+            }
+
+            if detach {
+                // The spawned daemon starts a session of its own, detaching it
+                // from this terminal so it keeps running when the terminal closes
+                std::process::Command::new(std::env::current_exe()?)
+                    .args(["mcp", "daemon", "--new-session"])
+                    .stdin(std::process::Stdio::null())
+                    .stdout(std::process::Stdio::null())
+                    .stderr(std::process::Stdio::null())
+                    .spawn()?;
+
+                // Wait for the servers to start and the socket to answer
+                for _ in 0..100 {
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                    if let Some(daemon) = DaemonClient::connect(&socket) {
+                        let running = daemon.running()?;
+                        println!("MCP daemon running with {} server(s).", running.len());
+                        return Ok(());
+                    }
+                }
+                anyhow::bail!("MCP daemon did not start; run 'pal mcp daemon' to see why");
+            }
+
+            if new_session {
+                nix::unistd::setsid()?;
+            }
+
+            let mut manager = MCPManager::new();
+            for server_entry in &config.mcp.servers {
+                manager.add_server(MCPServerConfig::from(server_entry))?;
+                if let Err(e) = manager.start_server(&server_entry.name) {
+                    eprintln!("Warning: Failed to start server '{}': {}", server_entry.name, e);
+                }
+            }
+
+            let daemon = MCPDaemon::new(manager, &socket);
+            let listener = daemon.bind()?;
+            println!("MCP daemon listening on {}", socket.display());
+            println!("Stop it with 'pal mcp stop'.");
+            daemon.serve(&listener)?;
+        }
+
+        #[cfg(not(unix))]
+        McpOperation::Daemon { .. } => {
+            anyhow::bail!("The MCP daemon is only available on Unix platforms.");
         }
 
         McpOperation::Config => {
@@ -3902,7 +5518,7 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
             if !config.mcp.servers.is_empty() {
                 println!("\n  Configured servers:");
                 for server in &config.mcp.servers {
-                    println!("    - {}: {}", server.name, server.command);
+                    println!("    - {}: {}", server.name, server.transport.describe());
                 }
             }
 
@@ -3921,6 +5537,56 @@ fn cmd_mcp(operation: McpOperation) -> Result<()> {
     Ok(())
 }
 
+/// Connect to the running MCP daemon, if there is one.
+#[cfg(unix)]
+fn mcp_daemon() -> Option<palrun::mcp::DaemonClient> {
+    palrun::mcp::MCPDaemon::default_socket_path(&project_root())
+        .and_then(palrun::mcp::DaemonClient::connect)
+}
+
+/// Print MCP tools as text or JSON.
+fn print_mcp_tools(tools: &[palrun::mcp::RegisteredTool], format: &str) -> Result<()> {
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(tools)?);
+        return Ok(());
+    }
+
+    println!("Available MCP tools:\n");
+    if tools.is_empty() {
+        println!("  No tools available.");
+    } else {
+        for reg_tool in tools {
+            print!("  [{}] {}", reg_tool.server, reg_tool.tool.name);
+            if let Some(ref desc) = reg_tool.tool.description {
+                print!(" - {}", desc);
+            }
+            println!();
+
+            // Show required parameters
+            if let Some(ref required) = reg_tool.tool.input_schema.required {
+                if !required.is_empty() {
+                    println!("    Required: {}", required.join(", "));
+                }
+            }
+        }
+        println!("\nTotal: {} tool(s)", tools.len());
+    }
+    Ok(())
+}
+
+/// Print the text content of an MCP tool result.
+fn print_mcp_tool_result(result: &palrun::mcp::CallToolResult) {
+    if result.is_error.unwrap_or(false) {
+        println!("Tool returned an error:");
+    }
+
+    for content in &result.content {
+        if let Some(text) = content.as_text() {
+            println!("{}", text);
+        }
+    }
+}
+
 /// Handle slash command operations.
 fn cmd_slash(operation: SlashOperation) -> Result<()> {
     use palrun::commands::{default_registry, PALRUN_COMMANDS};
@@ -4306,10 +5972,15 @@ fn cmd_debug(operation: DebugOperation) -> Result<()> {
             }
         }
 
-        DebugOperation::Commands { detailed } => {
+        DebugOperation::Commands { detailed, json } => {
             let mut app = App::new()?;
             app.initialize()?;
 
+            if json {
+                println!("{}", serde_json::to_string_pretty(&debug_commands_json(&app))?);
+                return Ok(());
+            }
+
             println!("Discovered Commands Debug\n");
             println!("{}", "=".repeat(50));
             println!("\nTotal commands: {}", app.registry.len());
@@ -4389,8 +6060,6 @@ fn cmd_debug(operation: DebugOperation) -> Result<()> {
         }
 
         DebugOperation::Search { query } => {
-            use nucleo::{Config as NucleoConfig, Matcher, Utf32Str};
-
             let mut app = App::new()?;
             app.initialize()?;
 
@@ -4399,29 +6068,14 @@ fn cmd_debug(operation: DebugOperation) -> Result<()> {
             println!("\nQuery: \"{}\"", query);
             println!("Commands searched: {}", app.registry.len());
 
-            let config = NucleoConfig::DEFAULT.match_paths();
-            let mut matcher = Matcher::new(config);
-            let pattern = nucleo::pattern::Pattern::parse(
-                &query,
-                nucleo::pattern::CaseMatching::Smart,
-                nucleo::pattern::Normalization::Smart,
-            );
-
             println!("\nTop matches (by score):");
-            let mut scored: Vec<_> = app
+            let scored: Vec<_> = app
                 .registry
-                .get_all()
-                .iter()
-                .filter_map(|cmd| {
-                    let mut buf = vec![];
-                    let haystack = Utf32Str::new(&cmd.name, &mut buf);
-                    let score = pattern.score(haystack, &mut matcher)?;
-                    Some((cmd, score))
-                })
+                .scored(&query, palrun::core::SearchField::all())
+                .into_iter()
+                .filter_map(|(idx, score)| Some((app.registry.get_by_index(idx)?, score)))
                 .collect();
 
-            scored.sort_by(|a, b| b.1.cmp(&a.1));
-
             for (cmd, score) in scored.iter().take(10) {
                 println!("  {:>4} | {}", score, cmd.name);
             }
@@ -4518,34 +6172,41 @@ fn cmd_debug(operation: DebugOperation) -> Result<()> {
             println!("Scanner Detection Debug\n");
             println!("{}", "=".repeat(50));
 
-            // Get unique sources
-            let mut sources: std::collections::HashSet<&str> = std::collections::HashSet::new();
+            // Group by source key, so each plugin gets its own line
+            let mut by_source: std::collections::BTreeMap<&str, Vec<&palrun::core::Command>> =
+                std::collections::BTreeMap::new();
             for cmd in app.registry.get_all() {
-                sources.insert(cmd.source.type_name());
+                by_source.entry(cmd.source.key()).or_default().push(cmd);
             }
 
-            println!("\nActive scanners (found commands):");
-            for source in &sources {
-                let count = app.registry.get_by_source_type(source).len();
+            println!("\nActive sources (found commands):");
+            for cmds in by_source.values() {
+                let source = &cmds[0].source;
                 println!(
                     "  {} {} - {} commands",
-                    app.registry
-                        .get_by_source_type(source)
-                        .first()
-                        .map(|c| c.source.icon())
-                        .unwrap_or(""),
-                    source,
-                    count
+                    app.config.ui.icons.icon(source, &app.sources),
+                    app.sources.name(source),
+                    cmds.len()
                 );
             }
 
+            // Built-in scanners record themselves on each command; plugin
+            // commands are keyed by plugin name
+            let active: std::collections::HashSet<String> = app
+                .registry
+                .get_all()
+                .iter()
+                .map(|cmd| {
+                    palrun::scanner::Provenance::of(cmd)
+                        .map_or_else(|| cmd.source.key().to_string(), |p| p.scanner)
+                })
+                .collect();
+
             println!("\nSupported scanners:");
-            let supported = [
-                "npm", "cargo", "make", "go", "python", "task", "docker", "nx", "turbo", "gradle",
-                "maven", "runbook",
-            ];
-            for scanner in supported {
-                let active = sources.contains(scanner);
+            let builtin = palrun::scanner::ProjectScanner::new(&app.context.project_root);
+            let plugins = app.sources.iter().map(|info| info.key.as_str());
+            for scanner in builtin.scanner_names().chain(plugins) {
+                let active = active.contains(scanner);
                 println!("  [{}] {}", if active { "x" } else { " " }, scanner);
             }
         }
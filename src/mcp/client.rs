@@ -1,8 +1,16 @@
 //! MCP Client for communicating with MCP servers.
 //!
-//! Provides a high-level interface for MCP operations.
+//! Provides a high-level interface for MCP operations, and the HTTP
+//! transport used for servers configured with a `url`.
+//!
+//! Over HTTP every JSON-RPC message is POSTed to the server's endpoint. The
+//! server answers with either a plain JSON body or a `text/event-stream` whose
+//! events carry the response (possibly after notifications of its own). If
+//! that stream drops before the response arrives, the client reconnects with
+//! a GET carrying `Last-Event-ID` so the server can replay what was missed.
 
 use std::collections::HashMap;
+use std::io::BufRead;
 
 use super::protocol::{CallToolResult, MCPTool};
 use super::server::{MCPServer, MCPServerConfig, MCPServerError};
@@ -103,6 +111,292 @@ impl MCPClient {
     }
 }
 
+/// A single server-sent event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Event ID, used to resume the stream
+    pub id: Option<String>,
+    /// Event type (`message` when not given)
+    pub event: Option<String>,
+    /// Data lines, joined with newlines
+    pub data: String,
+}
+
+/// Reads server-sent events from a byte stream.
+pub struct SseReader<R> {
+    reader: R,
+}
+
+impl<R: BufRead> SseReader<R> {
+    /// Wrap a buffered reader.
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Read the next complete event.
+    ///
+    /// Returns `None` once the stream ends; an event cut off by the end of
+    /// the stream is discarded, as the SSE spec requires.
+    pub fn next_event(&mut self) -> std::io::Result<Option<SseEvent>> {
+        let mut event = SseEvent::default();
+        let mut has_data = false;
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None);
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                if has_data || event.id.is_some() {
+                    return Ok(Some(event));
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "data" => {
+                    if has_data {
+                        event.data.push('\n');
+                    }
+                    event.data.push_str(value);
+                    has_data = true;
+                }
+                "id" => event.id = Some(value.to_string()),
+                "event" => event.event = Some(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ai")]
+pub use http::HttpTransport;
+
+#[cfg(feature = "ai")]
+mod http {
+    use std::collections::HashMap;
+    use std::io::BufReader;
+    use std::time::Duration;
+
+    use reqwest::blocking::{Client, RequestBuilder, Response};
+    use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
+
+    use super::SseReader;
+    use crate::mcp::protocol::{JsonRpcRequest, JsonRpcResponse, RequestId};
+    use crate::mcp::server::MCPServerError;
+
+    /// Header carrying the session assigned by the server on `initialize`.
+    const SESSION_HEADER: &str = "Mcp-Session-Id";
+
+    /// Header asking the server to replay events after the given ID.
+    const LAST_EVENT_ID_HEADER: &str = "Last-Event-ID";
+
+    /// How many times a dropped event stream is resumed before giving up.
+    const MAX_RECONNECTS: u32 = 3;
+
+    /// Base delay between reconnection attempts, doubled on each retry.
+    const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+    /// How long to wait for a server to answer a single request.
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+    /// JSON-RPC over HTTP, with responses as JSON or server-sent events.
+    pub struct HttpTransport {
+        client: Client,
+        url: String,
+        headers: HeaderMap,
+        session_id: Option<String>,
+        reconnect_delay: Duration,
+    }
+
+    impl HttpTransport {
+        /// Create a transport for the server at `url`.
+        ///
+        /// `${VAR}` references in header values are expanded from the
+        /// environment, so tokens can stay out of `palrun.toml`. A reference
+        /// to an unset variable is an error rather than a literal header.
+        pub fn new(url: &str, headers: &HashMap<String, String>) -> Result<Self, MCPServerError> {
+            let mut header_map = HeaderMap::new();
+            for (name, value) in headers {
+                let invalid = |reason: String| {
+                    MCPServerError::InitializationFailed(format!("Invalid header {name}: {reason}"))
+                };
+                let value = shellexpand::env(value).map_err(|e| invalid(e.to_string()))?;
+                let name =
+                    HeaderName::from_bytes(name.as_bytes()).map_err(|e| invalid(e.to_string()))?;
+                let value = HeaderValue::from_str(&value).map_err(|e| invalid(e.to_string()))?;
+                header_map.insert(name, value);
+            }
+
+            let client = Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()
+                .map_err(|e| MCPServerError::InitializationFailed(e.to_string()))?;
+
+            Ok(Self {
+                client,
+                url: url.to_string(),
+                headers: header_map,
+                session_id: None,
+                reconnect_delay: RECONNECT_DELAY,
+            })
+        }
+
+        /// Set the base delay between reconnection attempts.
+        pub fn with_reconnect_delay(mut self, delay: Duration) -> Self {
+            self.reconnect_delay = delay;
+            self
+        }
+
+        /// Send a request and wait for its response.
+        pub fn request(
+            &mut self,
+            request: &JsonRpcRequest,
+        ) -> Result<JsonRpcResponse, MCPServerError> {
+            let body = serde_json::to_string(request)?;
+            tracing::debug!("MCP {} <- {}", self.url, body);
+            let response = self.send(self.post(body))?;
+
+            let is_stream = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.starts_with("text/event-stream"));
+            if !is_stream {
+                let body = response.text().map_err(|e| communication_error(&e))?;
+                tracing::debug!("MCP {} -> {}", self.url, body.trim());
+                return Ok(serde_json::from_str(&body)?);
+            }
+
+            self.read_stream(response, &request.id)
+        }
+
+        /// Send a notification, which has no response.
+        pub fn notify(&mut self, notification: &serde_json::Value) -> Result<(), MCPServerError> {
+            let body = serde_json::to_string(notification)?;
+            self.send(self.post(body))?;
+            Ok(())
+        }
+
+        /// End the session, if the server assigned one.
+        pub fn close(&mut self) {
+            if self.session_id.is_some() {
+                let _ = self.send(self.client.delete(&self.url));
+            }
+        }
+
+        /// Wait for the response to `id` on an event stream, resuming the
+        /// stream if the connection drops first.
+        fn read_stream(
+            &mut self,
+            mut response: Response,
+            id: &RequestId,
+        ) -> Result<JsonRpcResponse, MCPServerError> {
+            let mut last_event_id: Option<String> = None;
+            let mut reconnects = 0;
+
+            loop {
+                let mut events = SseReader::new(BufReader::new(response));
+                loop {
+                    match events.next_event() {
+                        Ok(Some(event)) => {
+                            if event.id.is_some() {
+                                last_event_id = event.id;
+                            }
+                            tracing::debug!("MCP {} -> {}", self.url, event.data);
+                            if let Some(found) = match_response(&event.data, id) {
+                                return Ok(found);
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            tracing::debug!("MCP {} stream dropped: {}", self.url, e);
+                            break;
+                        }
+                    }
+                }
+
+                if reconnects == MAX_RECONNECTS {
+                    return Err(MCPServerError::CommunicationError(
+                        "event stream closed before the response arrived".to_string(),
+                    ));
+                }
+                std::thread::sleep(self.reconnect_delay * 2u32.pow(reconnects));
+                reconnects += 1;
+
+                let mut resume = self.client.get(&self.url).header(ACCEPT, "text/event-stream");
+                if let Some(ref event_id) = last_event_id {
+                    resume = resume.header(LAST_EVENT_ID_HEADER, event_id);
+                }
+                response = self.send(resume)?;
+            }
+        }
+
+        /// A POST of one JSON-RPC message.
+        fn post(&self, body: String) -> RequestBuilder {
+            self.client
+                .post(&self.url)
+                .header(ACCEPT, "application/json, text/event-stream")
+                .header(CONTENT_TYPE, "application/json")
+                .body(body)
+        }
+
+        /// Add the configured headers, send, and record the session ID.
+        fn send(&mut self, request: RequestBuilder) -> Result<Response, MCPServerError> {
+            let mut request = request.headers(self.headers.clone());
+            if let Some(ref session) = self.session_id {
+                request = request.header(SESSION_HEADER, session);
+            }
+
+            let response = request.send().map_err(|e| {
+                if e.is_timeout() {
+                    MCPServerError::Timeout
+                } else {
+                    communication_error(&e)
+                }
+            })?;
+
+            if let Some(session) = response.headers().get(SESSION_HEADER) {
+                self.session_id = session.to_str().ok().map(ToString::to_string);
+            }
+
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().unwrap_or_default();
+                return Err(MCPServerError::CommunicationError(format!(
+                    "HTTP {status} from {}: {}",
+                    self.url,
+                    body.trim()
+                )));
+            }
+            Ok(response)
+        }
+    }
+
+    /// Parse an event's data as the response to `id`, skipping the server's
+    /// own requests and notifications.
+    fn match_response(data: &str, id: &RequestId) -> Option<JsonRpcResponse> {
+        let value: serde_json::Value = serde_json::from_str(data).ok()?;
+        if value.get("method").is_some() {
+            return None;
+        }
+        let response: JsonRpcResponse = serde_json::from_value(value).ok()?;
+        (response.id == *id).then_some(response)
+    }
+
+    fn communication_error(e: &reqwest::Error) -> MCPServerError {
+        MCPServerError::CommunicationError(e.to_string())
+    }
+}
+
 /// Format tools for display.
 pub fn format_tools(tools: &[MCPTool]) -> String {
     let mut output = String::new();
@@ -146,4 +440,159 @@ mod tests {
         assert!(output.contains("A test tool"));
         assert!(output.contains("arg1"));
     }
+
+    #[test]
+    fn test_sse_reader() {
+        let stream =
+            ": keep-alive\n\nid: 1\nevent: message\ndata: {\"a\":\ndata: 1}\n\r\ndata: cut off";
+        let mut reader = SseReader::new(stream.as_bytes());
+
+        let event = reader.next_event().unwrap().unwrap();
+        assert_eq!(event.id.as_deref(), Some("1"));
+        assert_eq!(event.event.as_deref(), Some("message"));
+        assert_eq!(event.data, "{\"a\":\n1}");
+        assert_eq!(reader.next_event().unwrap(), None);
+    }
+
+    #[cfg(feature = "ai")]
+    mod http {
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+
+        use serial_test::serial;
+
+        use super::super::HttpTransport;
+        use crate::mcp::protocol::JsonRpcRequest;
+        use crate::mcp::{MCPClient, MCPServerConfig, MCPTransport};
+        use crate::test_util::{http_response, mock_http, MockRequest};
+
+        /// Serve MCP over HTTP at `/mcp` with `reply`.
+        fn mock_server(
+            reply: impl Fn(&MockRequest, usize) -> String + Send + 'static,
+        ) -> (String, Arc<Mutex<Vec<MockRequest>>>) {
+            let (base, seen) = mock_http(reply);
+            (format!("{base}/mcp"), seen)
+        }
+
+        fn json_reply(body: &str, extra_headers: &str) -> String {
+            http_response(200, &format!("Content-Type: application/json\r\n{extra_headers}"), body)
+        }
+
+        fn sse_reply(events: &str) -> String {
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n{events}"
+            )
+        }
+
+        fn id_of(request: &MockRequest) -> serde_json::Value {
+            serde_json::from_str::<serde_json::Value>(&request.body).unwrap()["id"].clone()
+        }
+
+        const TOOLS: &str = r#"{"tools":[{"name":"search_issues","description":"Search issues","inputSchema":{"type":"object"}}]}"#;
+
+        #[test]
+        #[serial]
+        fn test_http_client_lists_tools() {
+            let (url, seen) = mock_server(|request, _| {
+                if request.method == "DELETE" {
+                    return http_response(200, "", "");
+                }
+                let body: serde_json::Value =
+                    serde_json::from_str(&request.body).unwrap_or_default();
+                match body["method"].as_str() {
+                    Some("initialize") => json_reply(
+                        &format!(
+                            r#"{{"jsonrpc":"2.0","id":{},"result":{{"protocolVersion":"2024-11-05","capabilities":{{}},"serverInfo":{{"name":"mock","version":"1.0"}}}}}}"#,
+                            id_of(request)
+                        ),
+                        "Mcp-Session-Id: session-42\r\n",
+                    ),
+                    Some("notifications/initialized") => http_response(202, "", ""),
+                    // Answer over SSE, after a notification of the server's own
+                    _ => sse_reply(&format!(
+                        "data: {{\"jsonrpc\":\"2.0\",\"method\":\"notifications/message\"}}\n\ndata: {{\"jsonrpc\":\"2.0\",\"id\":{},\"result\":{TOOLS}}}\n\n",
+                        id_of(request)
+                    )),
+                }
+            });
+
+            std::env::set_var("PALRUN_TEST_MCP_TOKEN", "s3cret");
+            let mut client = MCPClient::new(MCPServerConfig {
+                name: "remote".to_string(),
+                transport: MCPTransport::Http {
+                    url,
+                    headers: HashMap::from([(
+                        "Authorization".to_string(),
+                        "Bearer ${PALRUN_TEST_MCP_TOKEN}".to_string(),
+                    )]),
+                },
+                env: HashMap::new(),
+                cwd: None,
+            });
+            client.start().unwrap();
+            std::env::remove_var("PALRUN_TEST_MCP_TOKEN");
+
+            assert!(client.is_connected());
+            assert_eq!(client.server_name(), Some("mock"));
+            assert_eq!(client.tools().len(), 1);
+            assert_eq!(client.tools()[0].name, "search_issues");
+
+            client.stop().unwrap();
+
+            let seen = seen.lock().unwrap();
+            assert!(seen.iter().all(|r| r.headers["authorization"] == "Bearer s3cret"));
+            assert!(!seen[0].headers.contains_key("mcp-session-id"));
+            assert!(seen[1..].iter().all(|r| r.headers["mcp-session-id"] == "session-42"));
+            assert_eq!(seen.last().unwrap().method, "DELETE");
+        }
+
+        #[test]
+        fn test_http_resumes_dropped_stream() {
+            let (url, seen) = mock_server(|_, index| {
+                if index == 0 {
+                    // The first stream drops after one event, before the response
+                    sse_reply(
+                        "id: 7\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\n",
+                    )
+                } else {
+                    sse_reply(&format!(
+                        "id: 8\ndata: {{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{TOOLS}}}\n\n"
+                    ))
+                }
+            });
+
+            let mut transport = HttpTransport::new(&url, &HashMap::new())
+                .unwrap()
+                .with_reconnect_delay(Duration::from_millis(1));
+            let response = transport.request(&JsonRpcRequest::new(1, "tools/list", None)).unwrap();
+            assert!(response.is_success());
+
+            let seen = seen.lock().unwrap();
+            assert_eq!(seen.len(), 2);
+            assert_eq!(seen[1].method, "GET");
+            assert_eq!(seen[1].headers["last-event-id"], "7");
+            assert_eq!(seen[1].headers["accept"], "text/event-stream");
+        }
+
+        #[test]
+        fn test_http_gives_up_after_reconnects() {
+            let (url, seen) = mock_server(|_, _| sse_reply(": nothing to see\n\n"));
+
+            let mut transport = HttpTransport::new(&url, &HashMap::new())
+                .unwrap()
+                .with_reconnect_delay(Duration::from_millis(1));
+            assert!(transport.request(&JsonRpcRequest::new(1, "tools/list", None)).is_err());
+            assert_eq!(seen.lock().unwrap().len(), 4);
+        }
+
+        #[test]
+        fn test_http_missing_header_variable() {
+            let headers = HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer ${PALRUN_TEST_MCP_UNSET_TOKEN}".to_string(),
+            )]);
+            assert!(HttpTransport::new("http://127.0.0.1:1/mcp", &headers).is_err());
+        }
+    }
 }
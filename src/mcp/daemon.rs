@@ -0,0 +1,457 @@
+//! Background daemon that keeps MCP servers running between invocations.
+//!
+//! `pal mcp daemon` starts the configured servers once and then answers
+//! requests on a Unix socket, so `pal mcp tools` and `pal mcp call` can use
+//! the running servers instead of spawning them again.
+//!
+//! The control protocol is one JSON [`DaemonRequest`] line per connection,
+//! answered by one JSON [`DaemonResponse`] line.
+//!
+//! A socket file can outlive its daemon if the daemon crashes. Both sides
+//! treat a socket that refuses connections as stale: clients remove it and
+//! fall back to starting servers themselves, and a new daemon replaces it.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::manager::{MCPManager, RegisteredTool};
+use super::protocol::CallToolResult;
+
+/// Length of the project hash in socket file names.
+const SOCKET_HASH_LEN: usize = 16;
+
+/// How long a client waits for the daemon to answer.
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Error type for MCP daemon operations.
+#[derive(Debug, thiserror::Error)]
+pub enum DaemonError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("An MCP daemon is already running at {0}")]
+    AlreadyRunning(PathBuf),
+
+    #[error("Invalid daemon message: {0}")]
+    Protocol(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Remote(String),
+}
+
+/// A request sent to the daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    /// Check that the daemon is alive
+    Ping,
+    /// List tools, optionally from one server only
+    ListTools {
+        /// Server to list tools from
+        server: Option<String>,
+    },
+    /// Call a tool on a server
+    CallTool {
+        /// Server providing the tool
+        server: String,
+        /// Tool name
+        tool: String,
+        /// Tool arguments
+        arguments: Option<HashMap<String, serde_json::Value>>,
+    },
+    /// Start a configured server that is not running
+    Start {
+        /// Server name
+        server: String,
+    },
+    /// Stop a running server
+    Stop {
+        /// Server name
+        server: String,
+    },
+    /// Stop all servers and exit
+    Shutdown,
+}
+
+/// The daemon's answer to a [`DaemonRequest`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DaemonResponse {
+    /// Answer to `Ping`
+    Pong {
+        /// Names of the servers currently running
+        running: Vec<String>,
+    },
+    /// Answer to `ListTools`
+    Tools {
+        /// Tools from running servers
+        tools: Vec<RegisteredTool>,
+    },
+    /// Answer to `CallTool`
+    ToolResult {
+        /// What the tool returned
+        result: CallToolResult,
+    },
+    /// The request succeeded and has nothing to return
+    Done,
+    /// The request failed
+    Error {
+        /// What went wrong
+        message: String,
+    },
+}
+
+/// Serves a [`MCPManager`] over the control socket.
+pub struct MCPDaemon {
+    manager: MCPManager,
+    socket_path: PathBuf,
+}
+
+impl MCPDaemon {
+    /// Create a daemon for `manager`, listening at `socket_path`.
+    pub fn new(manager: MCPManager, socket_path: impl Into<PathBuf>) -> Self {
+        Self { manager, socket_path: socket_path.into() }
+    }
+
+    /// Default control socket location for the project at `project_root`.
+    ///
+    /// Each project gets its own socket, named after a hash of its root, so
+    /// a daemon only serves the servers of the project that started it.
+    /// Uses the runtime directory where the platform has one, falling back
+    /// to the local data directory; `None` if there is neither.
+    pub fn default_socket_path(project_root: &Path) -> Option<PathBuf> {
+        use sha2::{Digest, Sha256};
+
+        let root = project_root.canonicalize().unwrap_or_else(|_| project_root.to_path_buf());
+        let hash = format!("{:x}", Sha256::digest(root.to_string_lossy().as_bytes()));
+        let dir = dirs::runtime_dir().or_else(dirs::data_local_dir)?;
+        Some(dir.join("palrun").join(format!("mcp-{}.sock", &hash[..SOCKET_HASH_LEN])))
+    }
+
+    /// Path of the control socket.
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Bind the control socket, replacing a stale one left by a crashed daemon.
+    pub fn bind(&self) -> Result<UnixListener, DaemonError> {
+        if self.socket_path.exists() {
+            if UnixStream::connect(&self.socket_path).is_ok() {
+                return Err(DaemonError::AlreadyRunning(self.socket_path.clone()));
+            }
+            tracing::debug!(path = %self.socket_path.display(), "Removing stale MCP daemon socket");
+            std::fs::remove_file(&self.socket_path)?;
+        }
+        if let Some(parent) = self.socket_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(UnixListener::bind(&self.socket_path)?)
+    }
+
+    /// Answer requests until a `Shutdown` arrives, then stop all servers and
+    /// remove the socket.
+    pub fn serve(mut self, listener: &UnixListener) -> Result<(), DaemonError> {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    tracing::warn!("MCP daemon accept failed: {e}");
+                    continue;
+                }
+            };
+
+            match self.handle_connection(stream) {
+                Ok(true) => break,
+                Ok(false) => {}
+                Err(e) => tracing::warn!("MCP daemon request failed: {e}"),
+            }
+        }
+
+        let _ = self.manager.stop_all();
+        let _ = std::fs::remove_file(&self.socket_path);
+        Ok(())
+    }
+
+    /// Read one request, answer it, and report whether it was `Shutdown`.
+    fn handle_connection(&mut self, stream: UnixStream) -> Result<bool, DaemonError> {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            // A liveness probe that connected and hung up
+            return Ok(false);
+        }
+
+        let (response, shutdown) = match serde_json::from_str(&line) {
+            Ok(request) => {
+                let shutdown = matches!(request, DaemonRequest::Shutdown);
+                (self.handle(request), shutdown)
+            }
+            Err(e) => (DaemonResponse::Error { message: e.to_string() }, false),
+        };
+
+        let mut stream = reader.into_inner();
+        writeln!(stream, "{}", serde_json::to_string(&response)?)?;
+        stream.flush()?;
+        Ok(shutdown)
+    }
+
+    /// Answer a single request.
+    ///
+    /// `Shutdown` is only acknowledged here; [`serve`](Self::serve) does
+    /// the stopping.
+    pub fn handle(&mut self, request: DaemonRequest) -> DaemonResponse {
+        let error = |e: &dyn std::fmt::Display| DaemonResponse::Error { message: e.to_string() };
+
+        match request {
+            DaemonRequest::Ping => {
+                let mut running: Vec<String> = self
+                    .manager
+                    .server_names()
+                    .into_iter()
+                    .filter(|name| self.manager.is_server_connected(name))
+                    .map(ToString::to_string)
+                    .collect();
+                running.sort();
+                DaemonResponse::Pong { running }
+            }
+            DaemonRequest::ListTools { server } => {
+                let mut tools = self.manager.list_tools();
+                if let Some(server) = server {
+                    tools.retain(|t| t.server == server);
+                }
+                DaemonResponse::Tools { tools }
+            }
+            DaemonRequest::CallTool { server, tool, arguments } => {
+                match self.manager.get_tool(&tool) {
+                    Some(found) if found.server == server => {}
+                    _ => return error(&format!("Tool '{tool}' not found on server '{server}'")),
+                }
+                match self.manager.call_tool(&tool, arguments) {
+                    Ok(result) => DaemonResponse::ToolResult { result },
+                    Err(e) => error(&e),
+                }
+            }
+            DaemonRequest::Start { server } => match self.manager.start_server(&server) {
+                Ok(()) => DaemonResponse::Done,
+                Err(e) => error(&e),
+            },
+            DaemonRequest::Stop { server } => match self.manager.stop_server(&server) {
+                Ok(()) => DaemonResponse::Done,
+                Err(e) => error(&e),
+            },
+            DaemonRequest::Shutdown => DaemonResponse::Done,
+        }
+    }
+}
+
+/// Connection to a running [`MCPDaemon`].
+#[derive(Debug, Clone)]
+pub struct DaemonClient {
+    socket_path: PathBuf,
+}
+
+impl DaemonClient {
+    /// Connect to the daemon at `socket_path`, if one is running.
+    ///
+    /// A socket that refuses connections is left over from a crashed
+    /// daemon; it is removed and `None` returned.
+    pub fn connect(socket_path: impl Into<PathBuf>) -> Option<Self> {
+        let socket_path = socket_path.into();
+        if let Err(e) = UnixStream::connect(&socket_path) {
+            if e.kind() == std::io::ErrorKind::ConnectionRefused {
+                tracing::debug!(
+                    path = %socket_path.display(),
+                    "MCP daemon not running, removing stale socket"
+                );
+                let _ = std::fs::remove_file(&socket_path);
+            }
+            return None;
+        }
+        Some(Self { socket_path })
+    }
+
+    /// Send a request and read the response.
+    pub fn send(&self, request: &DaemonRequest) -> Result<DaemonResponse, DaemonError> {
+        let mut stream = UnixStream::connect(&self.socket_path)?;
+        stream.set_read_timeout(Some(RESPONSE_TIMEOUT))?;
+        writeln!(stream, "{}", serde_json::to_string(request)?)?;
+        stream.flush()?;
+
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line)?;
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    /// Names of the servers the daemon is running.
+    pub fn running(&self) -> Result<Vec<String>, DaemonError> {
+        match self.send(&DaemonRequest::Ping)? {
+            DaemonResponse::Pong { running } => Ok(running),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// List tools from the daemon's servers.
+    pub fn list_tools(&self, server: Option<&str>) -> Result<Vec<RegisteredTool>, DaemonError> {
+        let request = DaemonRequest::ListTools { server: server.map(ToString::to_string) };
+        match self.send(&request)? {
+            DaemonResponse::Tools { tools } => Ok(tools),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Call a tool on one of the daemon's servers.
+    pub fn call_tool(
+        &self,
+        server: &str,
+        tool: &str,
+        arguments: Option<HashMap<String, serde_json::Value>>,
+    ) -> Result<CallToolResult, DaemonError> {
+        let request = DaemonRequest::CallTool {
+            server: server.to_string(),
+            tool: tool.to_string(),
+            arguments,
+        };
+        match self.send(&request)? {
+            DaemonResponse::ToolResult { result } => Ok(result),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Start a configured server in the daemon.
+    pub fn start_server(&self, server: &str) -> Result<(), DaemonError> {
+        self.expect_done(&DaemonRequest::Start { server: server.to_string() })
+    }
+
+    /// Stop one of the daemon's servers.
+    pub fn stop_server(&self, server: &str) -> Result<(), DaemonError> {
+        self.expect_done(&DaemonRequest::Stop { server: server.to_string() })
+    }
+
+    /// Stop all servers and the daemon itself.
+    pub fn shutdown(&self) -> Result<(), DaemonError> {
+        self.expect_done(&DaemonRequest::Shutdown)
+    }
+
+    fn expect_done(&self, request: &DaemonRequest) -> Result<(), DaemonError> {
+        match self.send(request)? {
+            DaemonResponse::Done => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+}
+
+fn unexpected(response: DaemonResponse) -> DaemonError {
+    match response {
+        DaemonResponse::Error { message } => DaemonError::Remote(message),
+        other => DaemonError::Remote(format!("Unexpected daemon response: {other:?}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::{MCPServerConfig, MCPTransport};
+
+    #[test]
+    fn test_socket_path_is_per_project() {
+        let a = tempfile::tempdir().unwrap();
+        let b = tempfile::tempdir().unwrap();
+
+        let Some(path_a) = MCPDaemon::default_socket_path(a.path()) else { return };
+        let path_b = MCPDaemon::default_socket_path(b.path()).unwrap();
+        assert_ne!(path_a, path_b);
+        assert_eq!(MCPDaemon::default_socket_path(a.path()), Some(path_a));
+    }
+
+    /// A stdio MCP server that answers `initialize`, `tools/list`, and one
+    /// `tools/call`, in that order.
+    fn mock_server_config() -> MCPServerConfig {
+        let script = r#"
+read -r line
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{},"serverInfo":{"name":"mock"}}}'
+read -r line
+read -r line
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo","inputSchema":{"type":"object","properties":{"text":{"type":"string"}},"required":["text"]}}]}}'
+read -r line
+echo '{"jsonrpc":"2.0","id":3,"result":{"content":[{"type":"text","text":"hello"}]}}'
+cat > /dev/null
+"#;
+        MCPServerConfig {
+            name: "mock".to_string(),
+            transport: MCPTransport::Stdio {
+                command: "sh".to_string(),
+                args: vec!["-c".to_string(), script.to_string()],
+            },
+            env: HashMap::new(),
+            cwd: None,
+        }
+    }
+
+    #[test]
+    fn test_ipc_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let socket = temp.path().join("mcp.sock");
+
+        let mut manager = MCPManager::new();
+        manager.add_server(mock_server_config()).unwrap();
+        manager.start_all().unwrap();
+
+        let daemon = MCPDaemon::new(manager, &socket);
+        let listener = daemon.bind().unwrap();
+        let handle = std::thread::spawn(move || daemon.serve(&listener));
+
+        let client = DaemonClient::connect(&socket).expect("daemon should be running");
+        assert_eq!(client.running().unwrap(), ["mock"]);
+
+        let tools = client.list_tools(None).unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].tool.name, "echo");
+        assert_eq!(tools[0].server, "mock");
+        assert!(client.list_tools(Some("other")).unwrap().is_empty());
+
+        // Wrong server, then invalid arguments: neither reaches the mock
+        assert!(client.call_tool("other", "echo", None).is_err());
+        let err = client.call_tool("mock", "echo", None).unwrap_err();
+        assert!(err.to_string().contains("missing required argument `text`"));
+
+        let args = HashMap::from([("text".to_string(), serde_json::json!("hello"))]);
+        let result = client.call_tool("mock", "echo", Some(args)).unwrap();
+        assert_eq!(result.content[0].as_text(), Some("hello"));
+
+        client.stop_server("mock").unwrap();
+        assert_eq!(client.running().unwrap(), Vec::<String>::new());
+        assert!(client.stop_server("missing").is_err());
+
+        client.shutdown().unwrap();
+        handle.join().unwrap().unwrap();
+        assert!(!socket.exists());
+        assert!(DaemonClient::connect(&socket).is_none());
+    }
+
+    #[test]
+    fn test_stale_socket_is_replaced() {
+        let temp = tempfile::tempdir().unwrap();
+        let socket = temp.path().join("mcp.sock");
+
+        // A crashed daemon leaves its socket file behind
+        drop(UnixListener::bind(&socket).unwrap());
+        assert!(socket.exists());
+
+        assert!(DaemonClient::connect(&socket).is_none());
+        assert!(!socket.exists());
+
+        drop(UnixListener::bind(&socket).unwrap());
+        let daemon = MCPDaemon::new(MCPManager::new(), &socket);
+        let listener = daemon.bind().unwrap();
+
+        // While it is listening, a second daemon is refused
+        let second = MCPDaemon::new(MCPManager::new(), &socket);
+        assert!(matches!(second.bind(), Err(DaemonError::AlreadyRunning(_))));
+        drop(listener);
+    }
+}
@@ -26,12 +26,23 @@ pub enum MCPManagerError {
 
     #[error("Configuration error: {0}")]
     Config(String),
+
+    #[error("Invalid arguments for {tool}: {}", problems.join("; "))]
+    InvalidArguments {
+        /// Tool being called
+        tool: String,
+        /// Everything wrong with the arguments
+        problems: Vec<String>,
+    },
 }
 
 /// Represents a tool with its source server.
-#[derive(Debug, Clone)]
+///
+/// Serializes as the tool definition plus a `server` field.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RegisteredTool {
     /// The tool definition
+    #[serde(flatten)]
     pub tool: MCPTool,
     /// Server name that provides this tool
     pub server: String,
@@ -152,6 +163,10 @@ impl MCPManager {
     }
 
     /// Call a tool by name.
+    ///
+    /// The arguments are checked against the tool's input schema first, so
+    /// a missing or mistyped argument is reported here instead of as an
+    /// error from the server.
     pub fn call_tool(
         &mut self,
         tool_name: &str,
@@ -168,6 +183,10 @@ impl MCPManager {
             .get_mut(&server_name)
             .ok_or(MCPManagerError::ServerNotFound(server_name))?;
 
+        if let Some(tool) = client.get_tool(tool_name) {
+            validate_arguments(tool, arguments.as_ref())?;
+        }
+
         let result = client.call_tool(tool_name, arguments)?;
         Ok(result)
     }
@@ -219,6 +238,19 @@ impl MCPManager {
     }
 }
 
+/// Check `arguments` against `tool`'s input schema.
+pub fn validate_arguments(
+    tool: &MCPTool,
+    arguments: Option<&HashMap<String, serde_json::Value>>,
+) -> Result<(), MCPManagerError> {
+    let problems = tool.input_schema.validate(arguments);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(MCPManagerError::InvalidArguments { tool: tool.name.clone(), problems })
+    }
+}
+
 impl Default for MCPManager {
     fn default() -> Self {
         Self::new()
@@ -234,6 +266,7 @@ impl Drop for MCPManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mcp::MCPTransport;
 
     #[test]
     fn test_manager_creation() {
@@ -248,8 +281,7 @@ mod tests {
 
         let config = MCPServerConfig {
             name: "test".to_string(),
-            command: "echo".to_string(),
-            args: vec![],
+            transport: MCPTransport::Stdio { command: "echo".to_string(), args: vec![] },
             env: HashMap::new(),
             cwd: None,
         };
@@ -257,4 +289,54 @@ mod tests {
         manager.add_server(config.clone()).unwrap();
         assert!(manager.add_server(config).is_err());
     }
+
+    #[test]
+    fn test_validate_arguments() {
+        let tool: MCPTool = serde_json::from_value(serde_json::json!({
+            "name": "read_file",
+            "inputSchema": {
+                "type": "object",
+                "properties": {"path": {"type": "string"}},
+                "required": ["path"]
+            }
+        }))
+        .unwrap();
+
+        let args = HashMap::from([("encoding".to_string(), serde_json::json!("utf-8"))]);
+        let err = validate_arguments(&tool, Some(&args)).unwrap_err();
+        assert!(matches!(
+            err,
+            MCPManagerError::InvalidArguments { ref tool, ref problems }
+                if tool == "read_file" && problems.len() == 1
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Invalid arguments for read_file: missing required argument `path`"
+        );
+
+        let args = HashMap::from([("path".to_string(), serde_json::json!("README.md"))]);
+        assert!(validate_arguments(&tool, Some(&args)).is_ok());
+    }
+
+    #[test]
+    fn test_registered_tool_serialization() {
+        let tool: MCPTool = serde_json::from_value(serde_json::json!({
+            "name": "read_file",
+            "description": "Read a file",
+            "inputSchema": {
+                "type": "object",
+                "properties": {"path": {"type": "string"}},
+                "required": ["path"]
+            }
+        }))
+        .unwrap();
+        let registered = RegisteredTool { tool, server: "files".to_string() };
+
+        let json = serde_json::to_value(&registered).unwrap();
+        assert_eq!(json["server"], "files");
+        assert_eq!(json["name"], "read_file");
+        assert_eq!(json["description"], "Read a file");
+        assert_eq!(json["inputSchema"]["type"], "object");
+        assert_eq!(json["inputSchema"]["required"], serde_json::json!(["path"]));
+    }
 }
@@ -33,9 +33,12 @@
 //! // Add a server from config
 //! manager.add_server(MCPServerConfig {
 //!     name: "github".to_string(),
-//!     command: "npx".to_string(),
-//!     args: vec!["-y", "@modelcontextprotocol/server-github"],
+//!     transport: MCPTransport::Stdio {
+//!         command: "npx".to_string(),
+//!         args: vec!["-y", "@modelcontextprotocol/server-github"],
+//!     },
 //!     env: HashMap::new(),
+//!     cwd: None,
 //! })?;
 //!
 //! // Start all servers
@@ -49,17 +52,23 @@
 //! ```
 
 mod client;
+#[cfg(unix)]
+mod daemon;
 mod manager;
 mod protocol;
 mod server;
 mod tools;
 
-pub use client::{MCPClient, MCPClientError};
+#[cfg(feature = "ai")]
+pub use client::HttpTransport;
+pub use client::{MCPClient, MCPClientError, SseEvent, SseReader};
+#[cfg(unix)]
+pub use daemon::{DaemonClient, DaemonError, DaemonRequest, DaemonResponse, MCPDaemon};
 pub use manager::{MCPManager, MCPManagerError, RegisteredTool};
 pub use protocol::{
     CallToolParams, CallToolResult, JsonRpcError, JsonRpcRequest, JsonRpcResponse, ListToolsResult,
     MCPCapabilities, MCPInitializeParams, MCPInitializeResult, MCPTool, MCPToolInputSchema,
     ToolContent,
 };
-pub use server::{MCPServer, MCPServerConfig, MCPServerError, MCPServerState};
+pub use server::{MCPServer, MCPServerConfig, MCPServerError, MCPServerState, MCPTransport};
 pub use tools::{ToolCall, ToolRegistry, ToolResult};
@@ -271,6 +271,79 @@ pub struct MCPToolInputSchema {
     pub required: Option<Vec<String>>,
 }
 
+impl MCPToolInputSchema {
+    /// Check tool arguments against the schema before they are sent.
+    ///
+    /// Covers the `required` list and the `type` of each property that has
+    /// one (a single type or a list of allowed types). Returns a description
+    /// of every problem found, empty when the arguments are acceptable.
+    pub fn validate(&self, arguments: Option<&HashMap<String, Value>>) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for name in self.required.iter().flatten() {
+            if !arguments.is_some_and(|args| args.contains_key(name)) {
+                problems.push(format!("missing required argument `{name}`"));
+            }
+        }
+
+        let (Some(arguments), Some(properties)) = (arguments, &self.properties) else {
+            return problems;
+        };
+
+        let mut names: Vec<_> = arguments.keys().collect();
+        names.sort();
+        for name in names {
+            let Some(expected) = properties.get(name).and_then(|p| p.get("type")) else {
+                continue;
+            };
+            let allowed: Vec<&str> = match expected {
+                Value::String(ty) => vec![ty.as_str()],
+                Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+                _ => continue,
+            };
+            let value = &arguments[name];
+            if !allowed.iter().any(|ty| matches_type(value, ty)) {
+                problems.push(format!(
+                    "argument `{name}` should be {}, got {}",
+                    allowed.join(" or "),
+                    json_type(value)
+                ));
+            }
+        }
+
+        problems
+    }
+}
+
+/// Whether `value` is an instance of the JSON Schema type `ty`.
+///
+/// Unknown type names are accepted so that schemas using features this
+/// check does not understand never block a call.
+fn matches_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "object" => value.is_object(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+/// JSON type name of `value`, for error messages.
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
 /// Result from listing tools.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListToolsResult {
@@ -363,6 +436,56 @@ mod tests {
         assert!(response.is_success());
     }
 
+    fn path_schema() -> MCPToolInputSchema {
+        serde_json::from_value(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "path": {"type": "string"},
+                "limit": {"type": "integer"},
+                "recursive": {"type": "boolean"},
+                "tags": {"type": "array"},
+                "options": {"type": ["object", "null"]}
+            },
+            "required": ["path"]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_schema_requires_arguments() {
+        let schema = path_schema();
+        assert_eq!(schema.validate(None), ["missing required argument `path`"]);
+
+        let args = HashMap::from([("limit".to_string(), serde_json::json!(10))]);
+        assert_eq!(schema.validate(Some(&args)), ["missing required argument `path`"]);
+
+        let args = HashMap::from([("path".to_string(), serde_json::json!("src"))]);
+        assert_eq!(schema.validate(Some(&args)), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_schema_checks_types() {
+        let schema = path_schema();
+        let args: HashMap<String, Value> = serde_json::from_value(serde_json::json!({
+            "path": 42,
+            "limit": 1.5,
+            "recursive": "yes",
+            "tags": ["a"],
+            "options": null,
+            "extra": "not in the schema"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            schema.validate(Some(&args)),
+            [
+                "argument `limit` should be integer, got number",
+                "argument `path` should be string, got number",
+                "argument `recursive` should be boolean, got string",
+            ]
+        );
+    }
+
     #[test]
     fn test_tool_content_text() {
         let content = ToolContent::Text { text: "Hello".to_string() };
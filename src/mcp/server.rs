@@ -10,29 +10,76 @@ use std::sync::{Arc, Mutex};
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ai")]
+use super::client::HttpTransport;
 use super::protocol::{
     CallToolParams, CallToolResult, JsonRpcError, JsonRpcRequest, JsonRpcResponse, ListToolsResult,
     MCPInitializeParams, MCPInitializeResult, MCPTool,
 };
 
+/// How palrun talks to an MCP server.
+///
+/// In `palrun.toml` the form is picked by its fields: `command` (and
+/// `args`) for a local process, `url` (and `headers`) for a remote server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum MCPTransport {
+    /// Spawn a process and exchange JSON-RPC over its stdin/stdout
+    Stdio {
+        /// Command to run
+        command: String,
+        /// Command arguments
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// POST JSON-RPC to a remote server, which answers with JSON or SSE
+    Http {
+        /// Endpoint URL
+        url: String,
+        /// Extra request headers; `${VAR}` in a value is read from the environment
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        headers: HashMap<String, String>,
+    },
+}
+
+impl MCPTransport {
+    /// Short description for display: the command line or the URL.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Stdio { command, args } if args.is_empty() => command.clone(),
+            Self::Stdio { command, args } => format!("{command} {}", args.join(" ")),
+            Self::Http { url, .. } => url.clone(),
+        }
+    }
+}
+
 /// MCP server configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MCPServerConfig {
     /// Server name (unique identifier)
     pub name: String,
-    /// Command to run
-    pub command: String,
-    /// Command arguments
-    #[serde(default)]
-    pub args: Vec<String>,
-    /// Environment variables
+    /// How to reach the server
+    #[serde(flatten)]
+    pub transport: MCPTransport,
+    /// Environment variables (stdio servers only)
     #[serde(default)]
     pub env: HashMap<String, String>,
-    /// Working directory
+    /// Working directory (stdio servers only)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cwd: Option<String>,
 }
 
+impl From<&crate::core::MCPServerEntry> for MCPServerConfig {
+    fn from(entry: &crate::core::MCPServerEntry) -> Self {
+        Self {
+            name: entry.name.clone(),
+            transport: entry.transport.clone(),
+            env: entry.env.clone(),
+            cwd: entry.cwd.clone(),
+        }
+    }
+}
+
 /// MCP server state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MCPServerState {
@@ -89,6 +136,9 @@ pub struct MCPServer {
     stdin: Option<Arc<Mutex<std::process::ChildStdin>>>,
     /// Stdout reader
     stdout: Option<Arc<Mutex<BufReader<std::process::ChildStdout>>>>,
+    /// Connection to a remote server, for the HTTP transport
+    #[cfg(feature = "ai")]
+    http: Option<HttpTransport>,
 }
 
 impl MCPServer {
@@ -103,6 +153,8 @@ impl MCPServer {
             server_info: None,
             stdin: None,
             stdout: None,
+            #[cfg(feature = "ai")]
+            http: None,
         }
     }
 
@@ -134,9 +186,25 @@ impl MCPServer {
 
         self.state = MCPServerState::Starting;
 
-        // Build command
-        let mut cmd = Command::new(&self.config.command);
-        cmd.args(&self.config.args);
+        match self.config.transport.clone() {
+            MCPTransport::Stdio { command, args } => self.spawn(&command, &args)?,
+            MCPTransport::Http { url, headers } => self.connect(&url, &headers)?,
+        }
+
+        // Initialize the server
+        self.initialize()?;
+
+        // Fetch available tools
+        self.refresh_tools()?;
+
+        self.state = MCPServerState::Running;
+        Ok(())
+    }
+
+    /// Spawn the server process for the stdio transport.
+    fn spawn(&mut self, command: &str, args: &[String]) -> Result<(), MCPServerError> {
+        let mut cmd = Command::new(command);
+        cmd.args(args);
 
         // Set environment
         for (key, value) in &self.config.env {
@@ -169,17 +237,32 @@ impl MCPServer {
         self.stdin = Some(Arc::new(Mutex::new(stdin)));
         self.stdout = Some(Arc::new(Mutex::new(BufReader::new(stdout))));
         self.process = Some(child);
+        Ok(())
+    }
 
-        // Initialize the server
-        self.initialize()?;
-
-        // Fetch available tools
-        self.refresh_tools()?;
-
-        self.state = MCPServerState::Running;
+    /// Set up the HTTP transport.
+    #[cfg(feature = "ai")]
+    fn connect(
+        &mut self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> Result<(), MCPServerError> {
+        self.http = Some(HttpTransport::new(url, headers)?);
         Ok(())
     }
 
+    /// Set up the HTTP transport.
+    #[cfg(not(feature = "ai"))]
+    fn connect(
+        &mut self,
+        _url: &str,
+        _headers: &HashMap<String, String>,
+    ) -> Result<(), MCPServerError> {
+        Err(MCPServerError::InitializationFailed(
+            "the HTTP transport requires palrun to be built with the `ai` feature".to_string(),
+        ))
+    }
+
     /// Stop the server process.
     pub fn stop(&mut self) -> Result<(), MCPServerError> {
         if let Some(mut process) = self.process.take() {
@@ -187,6 +270,11 @@ impl MCPServer {
             let _ = process.wait();
         }
 
+        #[cfg(feature = "ai")]
+        if let Some(mut http) = self.http.take() {
+            http.close();
+        }
+
         self.stdin = None;
         self.stdout = None;
         self.state = MCPServerState::Stopped;
@@ -203,16 +291,21 @@ impl MCPServer {
 
     /// Send a request and wait for response.
     fn send_request(
-        &self,
+        &mut self,
         method: &str,
         params: Option<serde_json::Value>,
     ) -> Result<JsonRpcResponse, MCPServerError> {
-        let stdin = self.stdin.as_ref().ok_or(MCPServerError::NotRunning)?;
-        let stdout = self.stdout.as_ref().ok_or(MCPServerError::NotRunning)?;
-
         let id = self.request_id.fetch_add(1, Ordering::SeqCst);
         let request = JsonRpcRequest::new(id, method, params);
 
+        #[cfg(feature = "ai")]
+        if let Some(ref mut http) = self.http {
+            return http.request(&request);
+        }
+
+        let stdin = self.stdin.as_ref().ok_or(MCPServerError::NotRunning)?;
+        let stdout = self.stdout.as_ref().ok_or(MCPServerError::NotRunning)?;
+
         // Serialize and send
         let request_json = serde_json::to_string(&request)?;
         tracing::debug!("MCP {} <- {}", self.config.name, request_json);
@@ -255,6 +348,11 @@ impl MCPServer {
             "method": "notifications/initialized"
         });
 
+        #[cfg(feature = "ai")]
+        if let Some(ref mut http) = self.http {
+            return http.notify(&notification);
+        }
+
         if let Some(ref stdin) = self.stdin {
             let mut stdin_guard = stdin.lock().map_err(|e| {
                 MCPServerError::CommunicationError(format!("Failed to lock stdin: {}", e))
@@ -313,22 +411,45 @@ mod tests {
     fn test_server_config() {
         let config = MCPServerConfig {
             name: "test".to_string(),
-            command: "echo".to_string(),
-            args: vec!["hello".to_string()],
+            transport: MCPTransport::Stdio {
+                command: "echo".to_string(),
+                args: vec!["hello".to_string()],
+            },
             env: HashMap::new(),
             cwd: None,
         };
 
         assert_eq!(config.name, "test");
-        assert_eq!(config.command, "echo");
+        assert_eq!(config.transport.describe(), "echo hello");
+    }
+
+    #[test]
+    fn test_transport_from_toml() {
+        let stdio: MCPServerConfig =
+            toml::from_str("name = \"files\"\ncommand = \"mcp-files\"\nargs = [\".\"]\n").unwrap();
+        assert_eq!(
+            stdio.transport,
+            MCPTransport::Stdio { command: "mcp-files".to_string(), args: vec![".".to_string()] }
+        );
+
+        let http: MCPServerConfig = toml::from_str(
+            "name = \"linear\"\nurl = \"https://mcp.linear.app/mcp\"\n\n[headers]\nAuthorization = \"Bearer ${LINEAR_TOKEN}\"\n",
+        )
+        .unwrap();
+        assert_eq!(http.transport.describe(), "https://mcp.linear.app/mcp");
+        let MCPTransport::Http { headers, .. } = http.transport else {
+            panic!("expected the HTTP transport");
+        };
+        assert_eq!(headers["Authorization"], "Bearer ${LINEAR_TOKEN}");
+
+        assert!(toml::from_str::<MCPServerConfig>("name = \"broken\"\n").is_err());
     }
 
     #[test]
     fn test_server_initial_state() {
         let config = MCPServerConfig {
             name: "test".to_string(),
-            command: "echo".to_string(),
-            args: vec![],
+            transport: MCPTransport::Stdio { command: "echo".to_string(), args: vec![] },
             env: HashMap::new(),
             cwd: None,
         };
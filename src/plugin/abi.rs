@@ -0,0 +1,289 @@
+//! Host side of the scanner plugin ABI.
+//!
+//! A scanner plugin is a WASM module that exports:
+//!
+//! | Export                            | Purpose                                   |
+//! |-----------------------------------|-------------------------------------------|
+//! | `memory`                          | Linear memory shared with the host        |
+//! | `alloc(len: i32) -> i32`          | Reserve `len` bytes for host input        |
+//! | `dealloc(ptr: i32, len: i32)`     | Free a buffer from `alloc` or an export   |
+//! | `file_patterns() -> i64`          | Optional: JSON array of file patterns     |
+//! | `scan(ptr: i32, len: i32) -> i64` | JSON [`ScanContext`] in, commands out     |
+//!
+//! Strings cross the boundary as UTF-8 JSON. Buffers returned by the guest
+//! are packed into an `i64` as `(ptr << 32) | len`; the host copies the
+//! bytes out and then frees them with `dealloc(ptr, len)`.
+//!
+//! A scan goes like this:
+//!
+//! 1. The host calls `file_patterns()` (if exported) and reads every project
+//!    file that matches a pattern and that the plugin's filesystem
+//!    permissions allow.
+//! 2. It serializes a [`ScanContext`] with those contents, calls
+//!    `alloc(len)`, writes the JSON at the returned pointer, and calls
+//!    `scan(ptr, len)`. The input buffer is freed by the host afterwards.
+//! 3. The returned buffer holds a JSON array of [`PluginCommand`]s.
+//!
+//! Plugins never touch the filesystem themselves; the host does all reads.
+//!
+//! [`PluginCommand`]: super::PluginCommand
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::PluginPermissions;
+use crate::core::glob_match;
+
+/// How deep below the project root matched files are looked for.
+pub const MAX_SCAN_DEPTH: usize = 3;
+
+/// Files larger than this are not passed to plugins.
+pub const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Directories never searched for matched files.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// Context passed to a scanner plugin's `scan` export.
+///
+/// Mirrors `ScanContext` in the plugin SDK; both sides must agree on the
+/// JSON shape.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanContext {
+    /// Absolute path to the project root directory.
+    pub project_path: String,
+
+    /// Name of the project (the root directory's name).
+    pub project_name: String,
+
+    /// Contents of matched files, keyed by path relative to the project root.
+    #[serde(default)]
+    pub matched_files: HashMap<String, String>,
+
+    /// Environment variables, only when the plugin has environment permission.
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
+
+    /// Current git branch, if the project is in a repository on a branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+}
+
+impl ScanContext {
+    /// Build the context for a plugin: read every file under `root` that
+    /// matches one of `patterns` and that `permissions` allow.
+    ///
+    /// Without filesystem read permission no files are included.
+    pub fn collect(root: &Path, patterns: &[String], permissions: &PluginPermissions) -> Self {
+        let mut context = Self {
+            project_path: root.display().to_string(),
+            project_name: root
+                .file_name()
+                .map_or_else(|| "unknown".to_string(), |n| n.to_string_lossy().into_owned()),
+            git_branch: git_branch(root),
+            ..Self::default()
+        };
+
+        if permissions.requires_filesystem_read() && !patterns.is_empty() {
+            collect_files(root, root, patterns, permissions, 0, &mut context.matched_files);
+        }
+
+        if permissions.environment {
+            context.environment = std::env::vars().collect();
+        }
+
+        context
+    }
+}
+
+#[cfg(feature = "git")]
+fn git_branch(root: &Path) -> Option<String> {
+    crate::git::GitRepository::discover(root)?.current_branch()
+}
+
+#[cfg(not(feature = "git"))]
+const fn git_branch(_root: &Path) -> Option<String> {
+    None
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    patterns: &[String],
+    permissions: &PluginPermissions,
+    depth: usize,
+    files: &mut HashMap<String, String>,
+) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            if depth < MAX_SCAN_DEPTH
+                && !name.starts_with('.')
+                && !SKIPPED_DIRS.contains(&name.as_str())
+            {
+                collect_files(root, &path, patterns, permissions, depth + 1, files);
+            }
+            continue;
+        }
+
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+
+        if !patterns.iter().any(|pattern| matches_pattern(pattern, &relative, &name)) {
+            continue;
+        }
+        if !permissions.is_path_allowed(&relative) {
+            tracing::debug!(path = %relative, "Plugin not permitted to read matched file");
+            continue;
+        }
+        // A symlink could point anywhere, so only follow it within the root
+        if file_type.is_symlink() && !resolves_within(&path, root) {
+            tracing::debug!(path = %relative, "Skipping symlink that leaves the project");
+            continue;
+        }
+        if std::fs::metadata(&path).map_or(true, |m| m.len() > MAX_FILE_SIZE) {
+            continue;
+        }
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            files.insert(relative, content);
+        }
+    }
+}
+
+/// Whether `path` resolves to a file under `root` once symlinks are followed.
+fn resolves_within(path: &Path, root: &Path) -> bool {
+    match (path.canonicalize(), root.canonicalize()) {
+        (Ok(path), Ok(root)) => path.starts_with(root),
+        _ => false,
+    }
+}
+
+/// Patterns containing `/` match the relative path, others the file name.
+fn matches_pattern(pattern: &str, relative: &str, name: &str) -> bool {
+    if pattern.contains('/') {
+        glob_match(pattern, relative)
+    } else {
+        glob_match(pattern, name)
+    }
+}
+
+/// Pack a guest buffer into the `i64` returned by plugin exports.
+#[allow(clippy::cast_possible_wrap)]
+pub const fn pack(ptr: u32, len: u32) -> i64 {
+    (((ptr as u64) << 32) | len as u64) as i64
+}
+
+/// Split a packed `i64` into `(ptr, len)`.
+pub const fn unpack(packed: i64) -> (u32, u32) {
+    let [p0, p1, p2, p3, l0, l1, l2, l3] = packed.to_be_bytes();
+    (u32::from_be_bytes([p0, p1, p2, p3]), u32::from_be_bytes([l0, l1, l2, l3]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::FilesystemPermissions;
+
+    fn read_permissions(paths: &[&str]) -> PluginPermissions {
+        PluginPermissions {
+            filesystem: FilesystemPermissions {
+                read: true,
+                write: false,
+                paths: paths.iter().map(ToString::to_string).collect(),
+            },
+            ..PluginPermissions::default()
+        }
+    }
+
+    #[test]
+    fn test_pack_roundtrip() {
+        assert_eq!(unpack(pack(0x0010_0000, 42)), (0x0010_0000, 42));
+        assert_eq!(unpack(pack(u32::MAX, u32::MAX)), (u32::MAX, u32::MAX));
+        assert_eq!(unpack(0), (0, 0));
+    }
+
+    #[test]
+    fn test_collect_matched_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("build.gradle"), "task hello").unwrap();
+        std::fs::create_dir_all(root.join("app")).unwrap();
+        std::fs::write(root.join("app/build.gradle.kts"), "plugins {}").unwrap();
+        std::fs::write(root.join("README.md"), "# readme").unwrap();
+        std::fs::create_dir_all(root.join("node_modules/x")).unwrap();
+        std::fs::write(root.join("node_modules/x/build.gradle"), "ignored").unwrap();
+
+        let patterns = vec!["build.gradle*".to_string()];
+        let context = ScanContext::collect(root, &patterns, &read_permissions(&[]));
+
+        assert_eq!(context.matched_files.len(), 2);
+        assert_eq!(context.matched_files["build.gradle"], "task hello");
+        assert_eq!(context.matched_files["app/build.gradle.kts"], "plugins {}");
+        assert!(context.environment.is_empty());
+    }
+
+    #[test]
+    fn test_collect_respects_permissions() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("build.gradle"), "task hello").unwrap();
+        std::fs::write(root.join("settings.gradle"), "include 'app'").unwrap();
+        let patterns = vec!["*.gradle".to_string()];
+
+        let none = ScanContext::collect(root, &patterns, &PluginPermissions::default());
+        assert!(none.matched_files.is_empty());
+
+        let limited = ScanContext::collect(root, &patterns, &read_permissions(&["build.gradle"]));
+        assert_eq!(limited.matched_files.keys().collect::<Vec<_>>(), ["build.gradle"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_collect_skips_symlinks_outside_root() {
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secrets.json"), "{\"token\": \"x\"}").unwrap();
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("package.json"), "{}").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secrets.json"), root.join("leak.json"))
+            .unwrap();
+        std::os::unix::fs::symlink(root.join("package.json"), root.join("alias.json")).unwrap();
+
+        let patterns = vec!["*.json".to_string()];
+        let context = ScanContext::collect(root, &patterns, &read_permissions(&[]));
+
+        let mut names: Vec<_> = context.matched_files.keys().collect();
+        names.sort();
+        assert_eq!(names, ["alias.json", "package.json"]);
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_collect_git_branch() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[]).unwrap();
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let context = ScanContext::collect(temp.path(), &[], &PluginPermissions::default());
+        assert_eq!(context.git_branch.as_deref(), Some(branch.as_str()));
+
+        let json = serde_json::to_value(&context).unwrap();
+        assert_eq!(json["git_branch"], branch.as_str());
+    }
+}
@@ -0,0 +1,185 @@
+//! Local plugin development support for `pal plugin dev`.
+//!
+//! A development plugin is either a prebuilt `.wasm` file with a
+//! `plugin.toml` next to it, or a Cargo crate that is built for
+//! `wasm32-wasip1` on every reload. Either way it is loaded straight from
+//! where it was built, never copied into the plugins directory.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use super::{PluginError, PluginResult, MANIFEST_FILE};
+
+/// WASM target plugin crates are built for.
+pub const DEV_TARGET: &str = "wasm32-wasip1";
+
+/// Where a development plugin comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DevSource {
+    /// A Cargo crate, built with `cargo build --target wasm32-wasip1 --release`
+    Crate {
+        /// Crate root directory
+        dir: PathBuf,
+        /// Package name from `Cargo.toml`
+        package: String,
+    },
+    /// A prebuilt WASM module
+    Wasm(PathBuf),
+}
+
+impl DevSource {
+    /// Work out what `path` points at: a crate directory (or its
+    /// `Cargo.toml`) or a `.wasm` file.
+    pub fn detect(path: &Path) -> PluginResult<Self> {
+        if !path.exists() {
+            return Err(PluginError::NotFound(path.to_path_buf()));
+        }
+
+        let dir = if path.is_dir() {
+            path.to_path_buf()
+        } else if path.file_name().is_some_and(|n| n == "Cargo.toml") {
+            path.parent().map_or_else(|| PathBuf::from("."), Path::to_path_buf)
+        } else {
+            return Ok(Self::Wasm(path.to_path_buf()));
+        };
+
+        let cargo_toml = dir.join("Cargo.toml");
+        if !cargo_toml.exists() {
+            return Err(PluginError::LoadError(format!(
+                "{} is neither a .wasm file nor a Cargo project",
+                path.display()
+            )));
+        }
+
+        let content = std::fs::read_to_string(&cargo_toml)?;
+        let table: toml::Table =
+            toml::from_str(&content).map_err(|e| PluginError::Config(e.to_string()))?;
+        let package = table
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(toml::Value::as_str)
+            .ok_or_else(|| {
+                PluginError::Config(format!("no [package] name in {}", cargo_toml.display()))
+            })?
+            .to_string();
+
+        Ok(Self::Crate { dir, package })
+    }
+
+    /// Path to the plugin's `plugin.toml`.
+    pub fn manifest_path(&self) -> PathBuf {
+        match self {
+            Self::Crate { dir, .. } => dir.join(MANIFEST_FILE),
+            Self::Wasm(path) => path.with_file_name(MANIFEST_FILE),
+        }
+    }
+
+    /// Path of the WASM module once built.
+    pub fn wasm_path(&self) -> PathBuf {
+        match self {
+            Self::Crate { dir, package } => dir
+                .join("target")
+                .join(DEV_TARGET)
+                .join("release")
+                .join(format!("{}.wasm", package.replace('-', "_"))),
+            Self::Wasm(path) => path.clone(),
+        }
+    }
+
+    /// Build the plugin if needed and return the path to its WASM module.
+    ///
+    /// A failed build returns [`PluginError::LoadError`] carrying cargo's
+    /// output, leaving any previously built module in place.
+    pub fn build(&self) -> PluginResult<PathBuf> {
+        if let Self::Crate { dir, .. } = self {
+            let output = Command::new("cargo")
+                .args(["build", "--release", "--target", DEV_TARGET])
+                .current_dir(dir)
+                .output()?;
+
+            if !output.status.success() {
+                return Err(PluginError::LoadError(format!(
+                    "cargo build failed:\n{}",
+                    String::from_utf8_lossy(&output.stderr).trim_end()
+                )));
+            }
+        }
+
+        let wasm = self.wasm_path();
+        if !wasm.exists() {
+            return Err(PluginError::NotFound(wasm));
+        }
+        Ok(wasm)
+    }
+
+    /// Latest modification time of the files a rebuild depends on.
+    ///
+    /// For a crate that is `Cargo.toml`, `plugin.toml`, and everything
+    /// under `src/`; for a WASM file, the module and its manifest.
+    pub fn last_modified(&self) -> Option<SystemTime> {
+        let mut paths = vec![self.manifest_path()];
+        match self {
+            Self::Crate { dir, .. } => {
+                paths.push(dir.join("Cargo.toml"));
+                collect_files(&dir.join("src"), &mut paths);
+            }
+            Self::Wasm(path) => paths.push(path.clone()),
+        }
+
+        paths.iter().filter_map(|p| p.metadata().and_then(|m| m.modified()).ok()).max()
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_crate() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"my-scanner\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let source = DevSource::detect(temp.path()).unwrap();
+        assert_eq!(
+            source,
+            DevSource::Crate { dir: temp.path().to_path_buf(), package: "my-scanner".to_string() }
+        );
+        assert_eq!(source, DevSource::detect(&temp.path().join("Cargo.toml")).unwrap());
+        assert!(source.wasm_path().ends_with("target/wasm32-wasip1/release/my_scanner.wasm"));
+        assert_eq!(source.manifest_path(), temp.path().join("plugin.toml"));
+    }
+
+    #[test]
+    fn test_detect_wasm() {
+        let temp = tempfile::tempdir().unwrap();
+        let wasm = temp.path().join("scanner.wasm");
+        std::fs::write(&wasm, b"\0asm").unwrap();
+
+        let source = DevSource::detect(&wasm).unwrap();
+        assert_eq!(source, DevSource::Wasm(wasm.clone()));
+        assert_eq!(source.build().unwrap(), wasm);
+        assert!(source.last_modified().is_some());
+
+        assert!(DevSource::detect(&temp.path().join("missing.wasm")).is_err());
+        assert!(DevSource::detect(temp.path()).is_err());
+    }
+}
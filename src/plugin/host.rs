@@ -252,6 +252,7 @@ mod tests {
             description: None,
             working_dir: None,
             tags: vec![],
+            confirm: false,
         });
 
         let commands = host.take_commands();
@@ -1,13 +1,18 @@
 //! Plugin manager for installing, loading, and managing plugins.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 use super::{
-    PluginError, PluginManifest, PluginResult, PluginRuntime, PluginType, PLUGIN_API_VERSION,
+    PluginCommand, PluginError, PluginManifest, PluginResult, PluginRuntime, PluginType,
+    PLUGIN_API_VERSION,
 };
+use crate::core::{Command, SourceInfo};
+
+/// Maximum time a plugin call may run before it is interrupted.
+const PLUGIN_TIMEOUT_SECS: u64 = 10;
 
 /// State of an installed plugin.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -56,8 +61,9 @@ pub struct PluginManager {
     /// Installed plugins.
     plugins: HashMap<String, InstalledPlugin>,
     /// Plugin runtimes (lazily initialized).
-    #[allow(dead_code)]
     runtimes: HashMap<String, PluginRuntime>,
+    /// Plugins loaded for development, never written to the registry.
+    transient: HashSet<String>,
 }
 
 impl PluginManager {
@@ -66,7 +72,12 @@ impl PluginManager {
         // Ensure plugins directory exists
         std::fs::create_dir_all(&plugins_dir)?;
 
-        let mut manager = Self { plugins_dir, plugins: HashMap::new(), runtimes: HashMap::new() };
+        let mut manager = Self {
+            plugins_dir,
+            plugins: HashMap::new(),
+            runtimes: HashMap::new(),
+            transient: HashSet::new(),
+        };
 
         // Load plugin registry
         manager.load_registry()?;
@@ -74,6 +85,11 @@ impl PluginManager {
         Ok(manager)
     }
 
+    /// The default plugins directory, under the user's data directory.
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("palrun").join("plugins"))
+    }
+
     /// Get the plugins directory.
     pub fn plugins_dir(&self) -> &Path {
         &self.plugins_dir
@@ -100,7 +116,13 @@ impl PluginManager {
 
     /// Save the plugin registry to disk.
     fn save_registry(&self) -> PluginResult<()> {
-        let registry = PluginRegistry { plugins: self.plugins.clone() };
+        let plugins = self
+            .plugins
+            .iter()
+            .filter(|(name, _)| !self.transient.contains(*name))
+            .map(|(name, plugin)| (name.clone(), plugin.clone()))
+            .collect();
+        let registry = PluginRegistry { plugins };
 
         let content = serde_json::to_string_pretty(&registry)
             .map_err(|e| PluginError::Config(e.to_string()))?;
@@ -174,6 +196,57 @@ impl PluginManager {
         Ok(name)
     }
 
+    /// Load a plugin straight from where it was built, without installing it.
+    ///
+    /// The plugin is usable through [`scan_plugin`](Self::scan_plugin) for the life of
+    /// this manager but is never saved to the registry. Loading again
+    /// replaces it; if the new module fails to load, the previously loaded
+    /// version stays in place.
+    pub fn load_transient(
+        &mut self,
+        wasm_path: &Path,
+        manifest_path: &Path,
+    ) -> PluginResult<String> {
+        let manifest = PluginManifest::from_file(manifest_path)?;
+        manifest.validate()?;
+        if !manifest.is_compatible_with(PLUGIN_API_VERSION) {
+            return Err(PluginError::IncompatibleVersion {
+                name: manifest.plugin.name.clone(),
+                required: manifest.plugin.api_version.clone(),
+                available: PLUGIN_API_VERSION.to_string(),
+            });
+        }
+
+        let name = manifest.plugin.name.clone();
+        if self.plugins.contains_key(&name) && !self.transient.contains(&name) {
+            return Err(PluginError::AlreadyInstalled(name));
+        }
+
+        let runtime = PluginRuntime::new(&name, wasm_path, PLUGIN_TIMEOUT_SECS)?
+            .with_permissions(manifest.permissions.clone());
+        runtime.check_imports()?;
+
+        let plugin = InstalledPlugin {
+            manifest,
+            wasm_path: wasm_path.to_path_buf(),
+            state: PluginState::Enabled,
+            installed_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            last_error: None,
+        };
+        self.plugins.insert(name.clone(), plugin);
+        self.runtimes.insert(name.clone(), runtime);
+        self.transient.insert(name.clone());
+
+        Ok(name)
+    }
+
+    /// Check whether a plugin was loaded with [`load_transient`](Self::load_transient).
+    pub fn is_transient(&self, name: &str) -> bool {
+        self.transient.contains(name)
+    }
+
     /// Uninstall a plugin.
     pub fn uninstall(&mut self, name: &str) -> PluginResult<()> {
         if !self.plugins.contains_key(name) {
@@ -221,6 +294,63 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Run every enabled scanner plugin against a project.
+    ///
+    /// Commands are attributed to the plugin that found them. A failing
+    /// plugin is logged and skipped so it can't break command discovery.
+    pub fn scan(&mut self, project_root: &Path) -> Vec<Command> {
+        let mut names: Vec<String> = self
+            .list_by_type(PluginType::Scanner)
+            .filter(|p| p.state == PluginState::Enabled)
+            .map(|p| p.manifest.plugin.name.clone())
+            .collect();
+        names.sort();
+
+        let mut commands = Vec::new();
+        for name in names {
+            match self.scan_plugin(&name, project_root) {
+                Ok(found) => {
+                    commands.extend(found.into_iter().map(|c| c.into_command(&name, project_root)));
+                }
+                Err(e) => tracing::warn!(plugin = %name, error = %e, "Scanner plugin failed"),
+            }
+        }
+        commands
+    }
+
+    /// Display metadata for the sources of enabled scanner plugins.
+    pub fn sources(&self) -> Vec<SourceInfo> {
+        self.list_by_type(PluginType::Scanner)
+            .filter(|p| p.state == PluginState::Enabled)
+            .map(|p| p.manifest.source_info())
+            .collect()
+    }
+
+    /// Run one enabled scanner plugin against a project.
+    ///
+    /// The plugin's runtime is loaded on first use with the permissions
+    /// from its manifest; loading fails if the module imports a host
+    /// function those permissions don't cover.
+    pub fn scan_plugin(
+        &mut self,
+        name: &str,
+        project_root: &Path,
+    ) -> PluginResult<Vec<PluginCommand>> {
+        let plugin = self.plugins.get(name).ok_or_else(|| PluginError::NotFound(name.into()))?;
+        if plugin.state != PluginState::Enabled {
+            return Err(PluginError::Disabled(name.to_string()));
+        }
+
+        if !self.runtimes.contains_key(name) {
+            let runtime = PluginRuntime::new(name, &plugin.wasm_path, PLUGIN_TIMEOUT_SECS)?
+                .with_permissions(plugin.manifest.permissions.clone());
+            runtime.check_imports()?;
+            self.runtimes.insert(name.to_string(), runtime);
+        }
+
+        self.runtimes[name].scan(project_root)
+    }
+
     /// Get an installed plugin by name.
     pub fn get(&self, name: &str) -> Option<&InstalledPlugin> {
         self.plugins.get(name)
@@ -359,6 +489,68 @@ api_version = "0.1.0"
         assert!(matches!(result, Err(PluginError::AlreadyInstalled(_))));
     }
 
+    fn fixture_dir() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/plugins/hello-scanner")
+    }
+
+    #[test]
+    fn test_load_transient() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        let fixture = fixture_dir();
+
+        let mut manager = PluginManager::new(temp_dir.path().to_path_buf()).unwrap();
+        let name = manager
+            .load_transient(&fixture.join("hello_scanner.wasm"), &fixture.join("plugin.toml"))
+            .unwrap();
+
+        assert_eq!(name, "hello-scanner");
+        assert!(manager.is_transient(&name));
+        let commands = manager.scan_plugin(&name, project.path()).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].command, "echo hello");
+
+        // Loaded from the build location, not copied or persisted
+        assert_eq!(manager.get(&name).unwrap().wasm_path, fixture.join("hello_scanner.wasm"));
+        assert!(!temp_dir.path().join(&name).exists());
+        let reloaded = PluginManager::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(reloaded.count(), 0);
+    }
+
+    #[test]
+    fn test_failed_reload_keeps_previous_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        let fixture = fixture_dir();
+        let manifest = fixture.join("plugin.toml");
+
+        let mut manager = PluginManager::new(temp_dir.path().to_path_buf()).unwrap();
+        manager.load_transient(&fixture.join("hello_scanner.wasm"), &manifest).unwrap();
+
+        let broken = temp_dir.path().join("broken.wasm");
+        std::fs::write(&broken, b"not wasm").unwrap();
+        assert!(manager.load_transient(&broken, &manifest).is_err());
+
+        let plugin = manager.get("hello-scanner").unwrap();
+        assert_eq!(plugin.wasm_path, fixture.join("hello_scanner.wasm"));
+        assert_eq!(manager.scan_plugin("hello-scanner", project.path()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_transient_does_not_shadow_installed() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = TempDir::new().unwrap();
+        let fixture = fixture_dir();
+
+        let wasm_path = create_test_plugin(source_dir.path(), "hello-scanner");
+        let mut manager = PluginManager::new(temp_dir.path().to_path_buf()).unwrap();
+        manager.install_from_file(&wasm_path).unwrap();
+
+        let result = manager
+            .load_transient(&fixture.join("hello_scanner.wasm"), &fixture.join("plugin.toml"));
+        assert!(matches!(result, Err(PluginError::AlreadyInstalled(_))));
+    }
+
     #[test]
     fn test_list_by_type() {
         let temp_dir = TempDir::new().unwrap();
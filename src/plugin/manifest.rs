@@ -7,12 +7,16 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 use super::{PluginError, PluginResult, PluginType};
+use crate::core::SourceInfo;
 
 /// Plugin manifest containing metadata and configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginManifest {
     /// Plugin metadata.
     pub plugin: PluginMetadata,
+    /// How commands from a scanner plugin are shown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<PluginSource>,
     /// Plugin permissions.
     #[serde(default)]
     pub permissions: PluginPermissions,
@@ -54,6 +58,23 @@ pub struct PluginMetadata {
     pub keywords: Vec<String>,
 }
 
+/// Command source section, for scanner plugins.
+///
+/// Anything left out falls back to the plugin name and the generic
+/// plugin icons.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginSource {
+    /// Display name for the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Emoji icon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Plain ASCII icon, for terminals without emoji support.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ascii_icon: Option<String>,
+}
+
 fn default_api_version() -> String {
     "0.1.0".to_string()
 }
@@ -135,6 +156,23 @@ impl PluginManifest {
         Ok(())
     }
 
+    /// Display metadata for the commands this plugin provides.
+    pub fn source_info(&self) -> SourceInfo {
+        let mut info = SourceInfo::plugin(&self.plugin.name);
+        if let Some(ref source) = self.source {
+            if let Some(ref name) = source.name {
+                info.name.clone_from(name);
+            }
+            if let Some(ref icon) = source.icon {
+                info.icon.clone_from(icon);
+            }
+            if let Some(ref ascii_icon) = source.ascii_icon {
+                info.ascii_icon.clone_from(ascii_icon);
+            }
+        }
+        info
+    }
+
     /// Check if this manifest is compatible with the given API version.
     pub fn is_compatible_with(&self, host_api_version: &str) -> bool {
         // Simple compatibility check: major version must match
@@ -164,6 +202,21 @@ impl PluginPermissions {
         self.filesystem.write
     }
 
+    /// Check if a permission is granted, by the name used in
+    /// [`PluginError::PermissionDenied`](super::PluginError::PermissionDenied).
+    ///
+    /// Unknown names are never granted.
+    pub fn grants(&self, permission: &str) -> bool {
+        match permission {
+            "filesystem.read" => self.filesystem.read,
+            "filesystem.write" => self.filesystem.write,
+            "network" => self.network,
+            "execute" => self.execute,
+            "environment" => self.environment,
+            _ => false,
+        }
+    }
+
     /// Check if a path is allowed for read access.
     pub fn is_path_allowed(&self, path: &str) -> bool {
         if self.filesystem.paths.is_empty() {
@@ -226,6 +279,25 @@ include_subprojects = true
         assert!(!manifest.permissions.network);
     }
 
+    #[test]
+    fn test_source_info() {
+        let manifest = PluginManifest::from_toml(SAMPLE_MANIFEST).unwrap();
+        assert_eq!(manifest.source_info(), SourceInfo::plugin("gradle-scanner"));
+
+        let with_source =
+            format!("{SAMPLE_MANIFEST}\n[source]\nname = \"gradle\"\nicon = \"🐘\"\n");
+        let manifest = PluginManifest::from_toml(&with_source).unwrap();
+        let info = manifest.source_info();
+        assert_eq!(info.key, "gradle-scanner");
+        assert_eq!(info.name, "gradle");
+        assert_eq!(info.icon, "🐘");
+        assert_eq!(info.ascii_icon, "pl");
+
+        // Survives the copy written on install
+        let reparsed = PluginManifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(reparsed.source_info(), info);
+    }
+
     #[test]
     fn test_validate_manifest() {
         let manifest = PluginManifest::from_toml(SAMPLE_MANIFEST).unwrap();
@@ -273,6 +345,19 @@ type = "scanner"
         assert!(manifest.permissions.is_path_allowed("build.gradle.kts"));
     }
 
+    #[test]
+    fn test_grants() {
+        let manifest = PluginManifest::from_toml(SAMPLE_MANIFEST).unwrap();
+        let permissions = &manifest.permissions;
+
+        assert!(permissions.grants("filesystem.read"));
+        assert!(!permissions.grants("filesystem.write"));
+        assert!(!permissions.grants("network"));
+        assert!(!permissions.grants("execute"));
+        assert!(!permissions.grants("environment"));
+        assert!(!permissions.grants("everything"));
+    }
+
     #[test]
     fn test_serialize_manifest() {
         let manifest = PluginManifest::from_toml(SAMPLE_MANIFEST).unwrap();
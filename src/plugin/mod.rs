@@ -26,6 +26,8 @@
 //! scan_depth = 3
 //! ```
 
+pub mod abi;
+mod dev;
 mod error;
 mod host;
 mod manager;
@@ -34,10 +36,11 @@ mod registry;
 mod runtime;
 mod types;
 
+pub use dev::{DevSource, DEV_TARGET};
 pub use error::{PluginError, PluginResult};
 pub use host::{HostCapabilities, PluginHost};
 pub use manager::{InstalledPlugin, PluginManager, PluginState};
-pub use manifest::{FilesystemPermissions, PluginManifest, PluginPermissions};
+pub use manifest::{FilesystemPermissions, PluginManifest, PluginPermissions, PluginSource};
 pub use registry::{
     RegistryClient, RegistryPlugin, RemoteRegistry, SearchResult, DEFAULT_REGISTRY_URL,
 };
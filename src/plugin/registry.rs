@@ -2,6 +2,10 @@
 //!
 //! The registry is a JSON index hosted on GitHub that lists all available plugins
 //! with their metadata, download URLs, and compatibility information.
+//!
+//! A custom index URL can be configured via `plugins.registry_url`, and local
+//! directories of plugin manifests (`plugins.local_registries`) are merged on
+//! top of the remote index, which lets teams host private plugins.
 
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -9,6 +13,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 
 use super::{PluginError, PluginResult, PluginType, PLUGIN_API_VERSION};
+use crate::core::PluginsConfig;
 
 /// Default registry URL (GitHub-hosted JSON index in the same repo)
 pub const DEFAULT_REGISTRY_URL: &str =
@@ -75,9 +80,13 @@ pub struct RegistryPlugin {
     #[serde(default)]
     pub updated_at: Option<u64>,
 
-    /// SHA256 checksum of the download
+    /// SHA-256 checksum of the download (hex)
+    #[serde(default, alias = "checksum")]
+    pub sha256: Option<String>,
+
+    /// Minisign signature of the download, checked against the trusted keys
     #[serde(default)]
-    pub checksum: Option<String>,
+    pub signature: Option<String>,
 }
 
 impl RegistryPlugin {
@@ -168,6 +177,79 @@ impl RemoteRegistry {
     pub fn find(&self, name: &str) -> Option<&RegistryPlugin> {
         self.plugins.iter().find(|p| p.name == name)
     }
+
+    /// Merge another registry into this one.
+    ///
+    /// Plugins from `other` replace plugins with the same name, so a private
+    /// registry can shadow entries from the public index.
+    pub fn merge(&mut self, other: RemoteRegistry) {
+        for plugin in other.plugins {
+            if let Some(existing) = self.plugins.iter_mut().find(|p| p.name == plugin.name) {
+                *existing = plugin;
+            } else {
+                self.plugins.push(plugin);
+            }
+        }
+        self.updated_at = self.updated_at.max(other.updated_at);
+    }
+
+    /// Load a registry from a local directory of plugin manifests.
+    ///
+    /// Every `*.json` or `*.toml` file in the directory describes a single
+    /// [`RegistryPlugin`]. A `registry.json` file is instead read as a full
+    /// registry index. Relative download URLs are resolved against the directory.
+    pub fn from_dir(dir: &Path) -> PluginResult<Self> {
+        if !dir.is_dir() {
+            return Err(PluginError::NotFound(dir.to_path_buf()));
+        }
+
+        let mut registry = Self::default();
+
+        let mut entries: Vec<PathBuf> =
+            std::fs::read_dir(dir)?.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+        entries.sort();
+
+        for path in entries {
+            let is_index = path.file_name().is_some_and(|n| n == "registry.json");
+            let content = match path.extension().and_then(|e| e.to_str()) {
+                Some("json" | "toml") => std::fs::read_to_string(&path)?,
+                _ => continue,
+            };
+
+            let parse_error = |e: &dyn std::fmt::Display| {
+                PluginError::InvalidManifest(format!("{}: {e}", path.display()))
+            };
+
+            if is_index {
+                let index: Self = serde_json::from_str(&content).map_err(|e| parse_error(&e))?;
+                registry.merge(index);
+            } else if path.extension().is_some_and(|e| e == "json") {
+                let plugin: RegistryPlugin =
+                    serde_json::from_str(&content).map_err(|e| parse_error(&e))?;
+                registry.merge(Self { plugins: vec![plugin], ..Self::default() });
+            } else {
+                let plugin: RegistryPlugin =
+                    toml::from_str(&content).map_err(|e| parse_error(&e))?;
+                registry.merge(Self { plugins: vec![plugin], ..Self::default() });
+            }
+        }
+
+        for plugin in &mut registry.plugins {
+            if !is_remote_url(&plugin.download_url) {
+                let local = plugin.download_url.trim_start_matches("file://");
+                if Path::new(local).is_relative() {
+                    plugin.download_url = dir.join(local).to_string_lossy().into_owned();
+                }
+            }
+        }
+
+        Ok(registry)
+    }
+}
+
+/// Check whether a download URL points to a remote (HTTP) location.
+fn is_remote_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
 }
 
 /// Cached registry data.
@@ -188,6 +270,15 @@ pub struct RegistryClient {
     /// Registry URL
     registry_url: String,
 
+    /// Local registry directories merged over the remote index
+    local_registries: Vec<PathBuf>,
+
+    /// Minisign public keys that plugin signatures are checked against
+    trusted_keys: Vec<String>,
+
+    /// Whether downloads are checked against their checksum and signature
+    verify: bool,
+
     /// HTTP client
     client: reqwest::blocking::Client,
 
@@ -206,7 +297,15 @@ impl RegistryClient {
             .build()
             .map_err(|e| PluginError::Network(e.to_string()))?;
 
-        Ok(Self { cache_dir, registry_url: DEFAULT_REGISTRY_URL.to_string(), client, cache: None })
+        Ok(Self {
+            cache_dir,
+            registry_url: DEFAULT_REGISTRY_URL.to_string(),
+            local_registries: Vec::new(),
+            trusted_keys: Vec::new(),
+            verify: true,
+            client,
+            cache: None,
+        })
     }
 
     /// Create a client with a custom registry URL.
@@ -216,9 +315,63 @@ impl RegistryClient {
         Ok(client)
     }
 
+    /// Create a client honoring the `[plugins]` configuration section.
+    pub fn from_config(cache_dir: PathBuf, config: &PluginsConfig) -> PluginResult<Self> {
+        let mut client = match config.registry_url.as_deref() {
+            Some(url) => Self::with_url(cache_dir, url)?,
+            None => Self::new(cache_dir)?,
+        };
+        for dir in &config.local_registries {
+            client = client.with_local_registry(dir.clone());
+        }
+        for key in &config.trusted_keys {
+            client = client.with_trusted_key(key);
+        }
+        Ok(client)
+    }
+
+    /// Trust a minisign public key (the base64 line of a `.pub` file).
+    pub fn with_trusted_key(mut self, key: &str) -> Self {
+        self.trusted_keys.push(key.trim().to_string());
+        self
+    }
+
+    /// Skip checksum and signature verification of downloads.
+    ///
+    /// Meant for local plugin development only.
+    pub fn without_verification(mut self) -> Self {
+        self.verify = false;
+        self
+    }
+
+    /// Add a local registry directory to merge over the remote index.
+    pub fn with_local_registry(mut self, dir: PathBuf) -> Self {
+        self.local_registries.push(dir);
+        self
+    }
+
+    /// Get the remote registry URL.
+    pub fn registry_url(&self) -> &str {
+        &self.registry_url
+    }
+
+    /// Get the configured local registry directories.
+    pub fn local_registries(&self) -> &[PathBuf] {
+        &self.local_registries
+    }
+
     /// Get the path to the cache file.
+    ///
+    /// Custom registry URLs get their own cache file so switching sources
+    /// never serves a stale index from another registry.
     fn cache_path(&self) -> PathBuf {
-        self.cache_dir.join("registry_cache.json")
+        if self.registry_url == DEFAULT_REGISTRY_URL {
+            return self.cache_dir.join("registry_cache.json");
+        }
+
+        use sha2::{Digest, Sha256};
+        let hash = format!("{:x}", Sha256::digest(self.registry_url.as_bytes()));
+        self.cache_dir.join(format!("registry_cache_{}.json", &hash[..16]))
     }
 
     /// Check if the cache is still valid.
@@ -271,16 +424,43 @@ impl RegistryClient {
     }
 
     /// Fetch the registry from the remote source.
+    ///
+    /// Local registries are merged over the remote index after it is loaded.
+    /// In offline mode only the on-disk cache is used, regardless of its age.
     pub fn fetch(&mut self, force_refresh: bool) -> PluginResult<&RemoteRegistry> {
+        let offline = crate::core::is_offline_mode();
+
         // Check cache first (unless forced refresh)
-        if !force_refresh && self.is_cache_valid() {
-            if let Some(registry) = self.load_cache() {
-                self.cache = Some(registry);
-                return Ok(self.cache.as_ref().unwrap());
+        let cached = if offline || (!force_refresh && self.is_cache_valid()) {
+            self.load_cache()
+        } else {
+            None
+        };
+
+        let mut registry = match cached {
+            Some(registry) => registry,
+            None => self.fetch_remote()?,
+        };
+
+        for dir in &self.local_registries {
+            registry.merge(RemoteRegistry::from_dir(dir)?);
+        }
+
+        self.cache = Some(registry);
+        Ok(self.cache.as_ref().unwrap())
+    }
+
+    /// Fetch the remote index and refresh the on-disk cache.
+    fn fetch_remote(&self) -> PluginResult<RemoteRegistry> {
+        if crate::core::is_offline_mode() {
+            if !self.local_registries.is_empty() {
+                return Ok(RemoteRegistry::default());
             }
+            return Err(PluginError::Network(
+                "offline mode is enabled and no cached registry is available".to_string(),
+            ));
         }
 
-        // Fetch from remote
         let response = self
             .client
             .get(&self.registry_url)
@@ -301,8 +481,7 @@ impl RegistryClient {
         // Save to cache
         self.save_cache(&registry)?;
 
-        self.cache = Some(registry);
-        Ok(self.cache.as_ref().unwrap())
+        Ok(registry)
     }
 
     /// Get the cached registry (without fetching).
@@ -311,36 +490,56 @@ impl RegistryClient {
     }
 
     /// Download a plugin from the registry.
+    ///
+    /// Plugins from local registries are copied from disk instead. Unless
+    /// verification is disabled, the download must match the plugin's
+    /// `sha256` (required for remote downloads) and, when the registry lists
+    /// a `signature`, validate against one of the trusted keys.
     pub fn download(&self, plugin: &RegistryPlugin, dest_dir: &Path) -> PluginResult<PathBuf> {
         std::fs::create_dir_all(dest_dir)?;
 
-        let response = self
-            .client
-            .get(&plugin.download_url)
-            .send()
-            .map_err(|e| PluginError::Network(e.to_string()))?;
-
-        if !response.status().is_success() {
-            return Err(PluginError::Network(format!(
-                "Failed to download plugin: HTTP {}",
-                response.status()
+        let remote = is_remote_url(&plugin.download_url);
+        if self.verify && remote && plugin.sha256.is_none() {
+            return Err(PluginError::Validation(format!(
+                "'{}' has no sha256 checksum in the registry; use --no-verify to install anyway",
+                plugin.name
             )));
         }
 
-        let bytes = response.bytes().map_err(|e| PluginError::Network(e.to_string()))?;
+        let bytes = if remote {
+            if crate::core::is_offline_mode() {
+                return Err(PluginError::Network(format!(
+                    "cannot download '{}' in offline mode",
+                    plugin.name
+                )));
+            }
 
-        // Verify checksum if available
-        if let Some(ref expected) = plugin.checksum {
-            use sha2::{Digest, Sha256};
-            let mut hasher = Sha256::new();
-            hasher.update(&bytes);
-            let actual = format!("{:x}", hasher.finalize());
+            let response = self
+                .client
+                .get(&plugin.download_url)
+                .send()
+                .map_err(|e| PluginError::Network(e.to_string()))?;
 
-            if &actual != expected {
-                return Err(PluginError::Validation(format!(
-                    "Checksum mismatch: expected {expected}, got {actual}"
+            if !response.status().is_success() {
+                return Err(PluginError::Network(format!(
+                    "Failed to download plugin: HTTP {}",
+                    response.status()
                 )));
             }
+
+            response.bytes().map_err(|e| PluginError::Network(e.to_string()))?.to_vec()
+        } else {
+            let local = Path::new(plugin.download_url.trim_start_matches("file://"));
+            if !local.exists() {
+                return Err(PluginError::NotFound(local.to_path_buf()));
+            }
+            std::fs::read(local)?
+        };
+
+        if self.verify {
+            self.verify_download(plugin, &bytes)?;
+        } else {
+            tracing::warn!(plugin = %plugin.name, "Skipping plugin download verification");
         }
 
         // Save to destination
@@ -351,6 +550,46 @@ impl RegistryClient {
         Ok(dest_path)
     }
 
+    /// Check downloaded bytes against the plugin's checksum and signature.
+    pub fn verify_download(&self, plugin: &RegistryPlugin, bytes: &[u8]) -> PluginResult<()> {
+        if let Some(ref expected) = plugin.sha256 {
+            use sha2::{Digest, Sha256};
+            let actual = format!("{:x}", Sha256::digest(bytes));
+
+            if !actual.eq_ignore_ascii_case(expected.trim()) {
+                return Err(PluginError::Validation(format!(
+                    "Checksum mismatch for '{}': expected {expected}, got {actual}",
+                    plugin.name
+                )));
+            }
+        }
+
+        if let Some(ref signature) = plugin.signature {
+            let signature = minisign_verify::Signature::decode(signature).map_err(|e| {
+                PluginError::Validation(format!("Invalid signature for '{}': {e}", plugin.name))
+            })?;
+            if self.trusted_keys.is_empty() {
+                return Err(PluginError::Validation(format!(
+                    "'{}' is signed but no trusted keys are configured (plugins.trusted_keys)",
+                    plugin.name
+                )));
+            }
+
+            let verified = self.trusted_keys.iter().any(|key| {
+                minisign_verify::PublicKey::from_base64(key)
+                    .is_ok_and(|key| key.verify(bytes, &signature, false).is_ok())
+            });
+            if !verified {
+                return Err(PluginError::Validation(format!(
+                    "Signature for '{}' does not match any trusted key",
+                    plugin.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Search the registry.
     pub fn search(&mut self, query: &str) -> PluginResult<Vec<&RegistryPlugin>> {
         let registry = self.fetch(false)?;
@@ -441,6 +680,7 @@ pub fn search_with_score<'a>(registry: &'a RemoteRegistry, query: &str) -> Vec<S
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serial_test::serial;
 
     fn create_test_registry() -> RemoteRegistry {
         RemoteRegistry {
@@ -464,7 +704,8 @@ mod tests {
                     downloads: 1000,
                     stars: 50,
                     updated_at: Some(1704067200),
-                    checksum: None,
+                    sha256: None,
+                    signature: None,
                 },
                 RegistryPlugin {
                     name: "maven-scanner".to_string(),
@@ -482,7 +723,8 @@ mod tests {
                     downloads: 500,
                     stars: 25,
                     updated_at: None,
-                    checksum: None,
+                    sha256: None,
+                    signature: None,
                 },
                 RegistryPlugin {
                     name: "slack-notify".to_string(),
@@ -500,7 +742,8 @@ mod tests {
                     downloads: 2000,
                     stars: 100,
                     updated_at: None,
-                    checksum: None,
+                    sha256: None,
+                    signature: None,
                 },
             ],
         }
@@ -566,7 +809,8 @@ mod tests {
             downloads: 0,
             stars: 0,
             updated_at: None,
-            checksum: None,
+            sha256: None,
+            signature: None,
         };
 
         // Should be compatible with 0.x.x
@@ -581,6 +825,229 @@ mod tests {
         assert!(registry.find("nonexistent").is_none());
     }
 
+    #[test]
+    fn test_client_with_custom_url() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = PluginsConfig {
+            registry_url: Some("https://plugins.example.com/index.json".to_string()),
+            local_registries: vec![PathBuf::from("/opt/palrun/registry")],
+            ..PluginsConfig::default()
+        };
+
+        let client = RegistryClient::from_config(temp.path().to_path_buf(), &config).unwrap();
+        assert_eq!(client.registry_url(), "https://plugins.example.com/index.json");
+        assert_eq!(client.local_registries(), &[PathBuf::from("/opt/palrun/registry")]);
+
+        // Custom URLs must not share the default cache file
+        let default_client = RegistryClient::new(temp.path().to_path_buf()).unwrap();
+        assert_ne!(client.cache_path(), default_client.cache_path());
+    }
+
+    #[test]
+    fn test_client_default_url() {
+        let temp = tempfile::tempdir().unwrap();
+        let client =
+            RegistryClient::from_config(temp.path().to_path_buf(), &PluginsConfig::default())
+                .unwrap();
+        assert_eq!(client.registry_url(), DEFAULT_REGISTRY_URL);
+        assert_eq!(client.local_registries().len(), 0);
+    }
+
+    #[test]
+    fn test_registry_merge() {
+        let mut registry = create_test_registry();
+        let mut private = create_test_registry();
+        private.plugins.truncate(1);
+        private.plugins[0].version = "9.9.9".to_string();
+        private.plugins.push(RegistryPlugin {
+            name: "internal-scanner".to_string(),
+            ..registry.plugins[1].clone()
+        });
+
+        registry.merge(private);
+
+        assert_eq!(registry.plugins.len(), 4);
+        assert_eq!(registry.find("gradle-scanner").unwrap().version, "9.9.9");
+        assert!(registry.find("internal-scanner").is_some());
+    }
+
+    #[test]
+    fn test_registry_from_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("internal.toml"),
+            r#"
+name = "internal-scanner"
+description = "Scans internal build files"
+version = "1.0.0"
+type = "scanner"
+api_version = "0.1.0"
+download_url = "internal-scanner.wasm"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("registry.json"),
+            serde_json::to_string(&create_test_registry()).unwrap(),
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("README.md"), "ignored").unwrap();
+
+        let registry = RemoteRegistry::from_dir(temp.path()).unwrap();
+        assert_eq!(registry.plugins.len(), 4);
+
+        let internal = registry.find("internal-scanner").unwrap();
+        assert_eq!(
+            PathBuf::from(&internal.download_url),
+            temp.path().join("internal-scanner.wasm")
+        );
+        // Remote URLs are left untouched
+        assert!(registry.find("gradle-scanner").unwrap().download_url.starts_with("https://"));
+    }
+
+    #[test]
+    fn test_fetch_merges_local_registries_from_cache() {
+        let cache = tempfile::tempdir().unwrap();
+        let local = tempfile::tempdir().unwrap();
+        std::fs::write(
+            local.path().join("internal.json"),
+            r#"{"name": "internal-scanner", "description": "Internal", "version": "1.0.0",
+                "type": "scanner", "api_version": "0.1.0", "download_url": "internal.wasm"}"#,
+        )
+        .unwrap();
+
+        let mut client =
+            RegistryClient::with_url(cache.path().to_path_buf(), "https://plugins.example.com")
+                .unwrap()
+                .with_local_registry(local.path().to_path_buf());
+        client.save_cache(&create_test_registry()).unwrap();
+
+        let registry = client.fetch(false).unwrap();
+        assert_eq!(registry.plugins.len(), 4);
+        assert!(registry.find("internal-scanner").is_some());
+        assert!(registry.find("slack-notify").is_some());
+    }
+
+    #[test]
+    fn test_download_from_local_registry() {
+        let cache = tempfile::tempdir().unwrap();
+        let local = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::write(local.path().join("internal.wasm"), b"\0asm").unwrap();
+
+        let mut plugin = create_test_registry().plugins[0].clone();
+        plugin.download_url = local.path().join("internal.wasm").to_string_lossy().into_owned();
+
+        let client = RegistryClient::new(cache.path().to_path_buf()).unwrap();
+        let path = client.download(&plugin, dest.path()).unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), b"\0asm");
+    }
+
+    /// Minisign test vector: a prehashed signature of `test`.
+    const TEST_PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+    const TEST_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQf6LRCGA9i559r3g7V1qNyJDApGip8MfqcadIgT9CuhV3EMhHoN1mGTkUidF/z7SrlQgXdy8ofjb7bNJJylDOocrCo8KLzZwo=
+trusted comment: timestamp:1556193335\tfile:test
+y/rUw2y8/hOUYjZU71eHp/Wo1KZ40fGy2VJEDl34XMJM+TX48Ss/17u3IvIfbVR1FkZZSNCisQbuQY+bHwhEBg==";
+    const TEST_SHA256: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    fn local_plugin(dir: &Path, payload: &[u8]) -> RegistryPlugin {
+        std::fs::write(dir.join("signed.wasm"), payload).unwrap();
+        let mut plugin = create_test_registry().plugins[0].clone();
+        plugin.download_url = dir.join("signed.wasm").to_string_lossy().into_owned();
+        plugin
+    }
+
+    #[test]
+    fn test_download_verifies_checksum() {
+        let cache = tempfile::tempdir().unwrap();
+        let local = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        let client = RegistryClient::new(cache.path().to_path_buf()).unwrap();
+
+        let mut good = local_plugin(local.path(), b"test");
+        good.sha256 = Some(TEST_SHA256.to_uppercase());
+        assert!(client.download(&good, dest.path()).is_ok());
+
+        let mut tampered = local_plugin(local.path(), b"Test");
+        tampered.sha256 = Some(TEST_SHA256.to_string());
+        let result = client.download(&tampered, dest.path());
+        assert!(matches!(result, Err(PluginError::Validation(ref m)) if m.contains("Checksum")));
+
+        // The escape hatch installs it anyway
+        let unverified =
+            RegistryClient::new(cache.path().to_path_buf()).unwrap().without_verification();
+        assert!(unverified.download(&tampered, dest.path()).is_ok());
+    }
+
+    #[test]
+    fn test_download_verifies_signature() {
+        let cache = tempfile::tempdir().unwrap();
+        let local = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        let client = RegistryClient::new(cache.path().to_path_buf())
+            .unwrap()
+            .with_trusted_key(TEST_PUBLIC_KEY);
+
+        let mut good = local_plugin(local.path(), b"test");
+        good.sha256 = Some(TEST_SHA256.to_string());
+        good.signature = Some(TEST_SIGNATURE.to_string());
+        let path = client.download(&good, dest.path()).unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), b"test");
+
+        // A tampered payload without a checksum still fails the signature
+        let mut tampered = local_plugin(local.path(), b"Test");
+        tampered.signature = Some(TEST_SIGNATURE.to_string());
+        let result = client.download(&tampered, dest.path());
+        assert!(matches!(result, Err(PluginError::Validation(ref m)) if m.contains("Signature")));
+
+        // Signed plugins can't be installed without a trusted key
+        let untrusted = RegistryClient::new(cache.path().to_path_buf()).unwrap();
+        assert!(untrusted.download(&good, dest.path()).is_err());
+    }
+
+    #[test]
+    fn test_remote_download_requires_checksum() {
+        let cache = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        let client = RegistryClient::new(cache.path().to_path_buf()).unwrap();
+
+        let plugin = create_test_registry().plugins[0].clone();
+        let result = client.download(&plugin, dest.path());
+        assert!(matches!(result, Err(PluginError::Validation(ref m)) if m.contains("sha256")));
+    }
+
+    #[test]
+    #[serial]
+    fn test_fetch_offline_uses_stale_cache() {
+        let cache = tempfile::tempdir().unwrap();
+        let mut client = RegistryClient::new(cache.path().to_path_buf()).unwrap();
+        let stale = CachedRegistry { cached_at: 0, registry: create_test_registry() };
+        std::fs::write(client.cache_path(), serde_json::to_string(&stale).unwrap()).unwrap();
+
+        crate::core::set_offline_mode(true);
+        let result = client.fetch(true).map(|r| r.plugins.len());
+        crate::core::set_offline_mode(false);
+
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    #[serial]
+    fn test_fetch_offline_without_cache_fails_fast() {
+        let cache = tempfile::tempdir().unwrap();
+        let mut client = RegistryClient::new(cache.path().to_path_buf()).unwrap();
+
+        crate::core::set_offline_mode(true);
+        let start = std::time::Instant::now();
+        let result = client.fetch(false).map(|_| ());
+        let elapsed = start.elapsed();
+        crate::core::set_offline_mode(false);
+
+        assert!(matches!(result, Err(PluginError::Network(_))));
+        assert!(elapsed < Duration::from_secs(1));
+    }
+
     #[test]
     fn test_search_with_score() {
         let registry = create_test_registry();
@@ -1,25 +1,42 @@
 //! WASM plugin runtime using wasmtime.
 //!
 //! This module provides the runtime environment for executing WASM plugins.
-//! It handles plugin loading, memory management, and host function binding.
+//! It handles plugin loading, memory management, and the scanner ABI
+//! described in [`super::abi`].
+//!
+//! # Sandbox
+//!
+//! The host links no WASI implementation: plugins get no preopened
+//! directories, no sockets, and no process spawning, and every import they
+//! declare traps if called. On top of that, a module that imports a
+//! capability-bearing function (such as `path_open` or `sock_connect`)
+//! without the matching manifest permission is rejected at load time, so a
+//! plugin can't ship code paths it isn't allowed to use.
 
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
 
-use super::{PluginCommand, PluginError, PluginResult};
+use wasmtime::{Config, Engine, Instance, Linker, Memory, Module, Store};
+
+use super::abi::{self, ScanContext};
+use super::{PluginCommand, PluginError, PluginPermissions, PluginResult};
 
 /// Plugin runtime for executing WASM plugins.
 ///
-/// Note: This is a placeholder implementation. Full WASM integration
-/// will be implemented when the `plugins` feature is enabled.
+/// Each call instantiates the module in a fresh store, so plugins keep no
+/// state between scans. See [`super::abi`] for the calling convention.
 pub struct PluginRuntime {
     /// Plugin name.
     name: String,
-    /// WASM module bytes.
-    #[allow(dead_code)]
-    module_bytes: Vec<u8>,
+    /// Engine the module was compiled with.
+    engine: Engine,
+    /// Compiled WASM module.
+    module: Module,
     /// Plugin timeout in seconds.
     timeout_secs: u64,
+    /// Permissions granted by the plugin manifest.
+    permissions: PluginPermissions,
 }
 
 impl PluginRuntime {
@@ -34,7 +51,31 @@ impl PluginRuntime {
         let module_bytes =
             std::fs::read(wasm_path).map_err(|e| PluginError::LoadError(e.to_string()))?;
 
-        Ok(Self { name: name.to_string(), module_bytes, timeout_secs })
+        Self::from_bytes(name, &module_bytes, timeout_secs)
+    }
+
+    /// Create a runtime from WASM (or WAT) bytes.
+    pub fn from_bytes(name: &str, module_bytes: &[u8], timeout_secs: u64) -> PluginResult<Self> {
+        let mut config = Config::new();
+        config.epoch_interruption(true);
+        let engine = Engine::new(&config).map_err(|e| PluginError::LoadError(e.to_string()))?;
+        let module = Module::new(&engine, module_bytes)
+            .map_err(|e| PluginError::LoadError(format!("{name}: {e}")))?;
+
+        Ok(Self {
+            name: name.to_string(),
+            engine,
+            module,
+            timeout_secs,
+            permissions: PluginPermissions::default(),
+        })
+    }
+
+    /// Grant the permissions declared in the plugin's manifest.
+    #[must_use]
+    pub fn with_permissions(mut self, permissions: PluginPermissions) -> Self {
+        self.permissions = permissions;
+        self
     }
 
     /// Get the plugin name.
@@ -46,6 +87,196 @@ impl PluginRuntime {
     pub fn timeout_secs(&self) -> u64 {
         self.timeout_secs
     }
+
+    /// Check the module's imports against the granted permissions.
+    ///
+    /// Fails with [`PluginError::PermissionDenied`] for the first import
+    /// that needs a permission the manifest doesn't grant.
+    pub fn check_imports(&self) -> PluginResult<()> {
+        for import in self.module.imports() {
+            if let Some(permission) = import_permission(import.module(), import.name()) {
+                if !self.permissions.grants(permission) {
+                    return Err(PluginError::PermissionDenied {
+                        plugin: self.name.clone(),
+                        permission: format!(
+                            "{permission} (imports {}::{})",
+                            import.module(),
+                            import.name()
+                        ),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Scan a project: read the files the plugin asks for and run its `scan` export.
+    pub fn scan(&self, project_root: &Path) -> PluginResult<Vec<PluginCommand>> {
+        let patterns = self.file_patterns()?;
+        let context = ScanContext::collect(project_root, &patterns, &self.permissions);
+        let input =
+            serde_json::to_string(&context).map_err(|e| self.execution_error(&e.to_string()))?;
+
+        let output = self.call_scan(&input)?;
+        serde_json::from_str(&output)
+            .map_err(|e| self.execution_error(&format!("invalid scan output: {e}")))
+    }
+
+    /// File patterns the plugin wants to read, from its `file_patterns` export.
+    pub fn file_patterns(&self) -> PluginResult<Vec<String>> {
+        let mut session = self.instantiate()?;
+        let Ok(export) =
+            session.instance.get_typed_func::<(), i64>(&mut session.store, "file_patterns")
+        else {
+            return Ok(Vec::new());
+        };
+
+        let packed = export.call(&mut session.store, ()).map_err(|e| self.trap(&e))?;
+        let output = self.take_output(&mut session, packed)?;
+        serde_json::from_str(&output)
+            .map_err(|e| self.execution_error(&format!("invalid file patterns: {e}")))
+    }
+
+    /// Call the `scan` export with a JSON context and return its JSON output.
+    pub fn call_scan(&self, context_json: &str) -> PluginResult<String> {
+        let mut session = self.instantiate()?;
+        let (instance, store) = (session.instance, &mut session.store);
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut *store, "alloc")
+            .map_err(|e| self.execution_error(&format!("missing alloc export: {e}")))?;
+        let dealloc = instance
+            .get_typed_func::<(i32, i32), ()>(&mut *store, "dealloc")
+            .map_err(|e| self.execution_error(&format!("missing dealloc export: {e}")))?;
+        let scan = instance
+            .get_typed_func::<(i32, i32), i64>(&mut *store, "scan")
+            .map_err(|e| self.execution_error(&format!("missing scan export: {e}")))?;
+
+        let len = i32::try_from(context_json.len())
+            .map_err(|_| self.execution_error("scan context too large"))?;
+        let ptr = alloc.call(&mut *store, len).map_err(|e| self.trap(&e))?;
+        let offset = guest_offset(ptr)
+            .ok_or_else(|| self.execution_error(&format!("invalid guest pointer {ptr}")))?;
+        session
+            .memory
+            .write(&mut session.store, offset, context_json.as_bytes())
+            .map_err(|e| self.execution_error(&format!("writing scan context: {e}")))?;
+
+        let packed = scan.call(&mut session.store, (ptr, len)).map_err(|e| self.trap(&e))?;
+        dealloc.call(&mut session.store, (ptr, len)).map_err(|e| self.trap(&e))?;
+
+        self.take_output(&mut session, packed)
+    }
+
+    /// Instantiate the module in a fresh store with the timeout armed.
+    fn instantiate(&self) -> PluginResult<Session> {
+        self.check_imports()?;
+
+        let mut store = Store::new(&self.engine, ());
+        store.set_epoch_deadline(1);
+
+        // Plugins built for WASI import functions they don't call during a
+        // scan; those imports trap instead of giving the guest any access.
+        let mut linker = Linker::new(&self.engine);
+        linker
+            .define_unknown_imports_as_traps(&self.module)
+            .map_err(|e| PluginError::LoadError(e.to_string()))?;
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .map_err(|e| PluginError::LoadError(format!("{}: {e}", self.name)))?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| self.execution_error("missing memory export"))?;
+
+        // Interrupt the guest once the timeout passes, unless the session
+        // is dropped first (which disconnects the channel)
+        let (timer, cancelled) = mpsc::channel::<()>();
+        let engine = self.engine.clone();
+        let timeout = Duration::from_secs(self.timeout_secs);
+        std::thread::spawn(move || {
+            if cancelled.recv_timeout(timeout) == Err(mpsc::RecvTimeoutError::Timeout) {
+                engine.increment_epoch();
+            }
+        });
+
+        Ok(Session { store, instance, memory, _timer: timer })
+    }
+
+    /// Copy a packed output buffer out of guest memory and free it.
+    fn take_output(&self, session: &mut Session, packed: i64) -> PluginResult<String> {
+        let (ptr, len) = abi::unpack(packed);
+        let mut bytes = vec![0; len as usize];
+        session
+            .memory
+            .read(&session.store, ptr as usize, &mut bytes)
+            .map_err(|e| self.execution_error(&format!("reading plugin output: {e}")))?;
+
+        let store = &mut session.store;
+        if let Ok(dealloc) =
+            session.instance.get_typed_func::<(i32, i32), ()>(&mut *store, "dealloc")
+        {
+            #[allow(clippy::cast_possible_wrap)]
+            dealloc.call(&mut *store, (ptr as i32, len as i32)).map_err(|e| self.trap(&e))?;
+        }
+
+        String::from_utf8(bytes).map_err(|_| self.execution_error("plugin output is not UTF-8"))
+    }
+
+    fn trap(&self, error: &wasmtime::Error) -> PluginError {
+        if matches!(error.downcast_ref::<wasmtime::Trap>(), Some(wasmtime::Trap::Interrupt)) {
+            PluginError::Timeout(self.name.clone(), self.timeout_secs)
+        } else {
+            self.execution_error(&error.to_string())
+        }
+    }
+
+    fn execution_error(&self, message: &str) -> PluginError {
+        PluginError::ExecutionError(format!("{}: {message}", self.name))
+    }
+}
+
+/// A module instance used for a single call.
+struct Session {
+    store: Store<()>,
+    instance: Instance,
+    memory: Memory,
+    /// Dropping this cancels the timeout
+    _timer: mpsc::Sender<()>,
+}
+
+/// The permission a module import requires, if any.
+///
+/// Covers WASI preview 1 (`wasi_snapshot_preview1` and the older
+/// `wasi_unstable`) plus process spawning under the `palrun` host module.
+/// Imports the standard library pulls into every module, like `fd_write`
+/// for stdout or `environ_get` for panic settings, need no permission; they
+/// trap like any other import, and environment variables only reach a
+/// plugin through [`ScanContext`].
+fn import_permission(module: &str, name: &str) -> Option<&'static str> {
+    match module {
+        "wasi_snapshot_preview1" | "wasi_unstable" => match name {
+            _ if name.starts_with("sock_") => Some("network"),
+            "path_open" | "path_readlink" | "path_filestat_get" | "fd_readdir" => {
+                Some("filesystem.read")
+            }
+            "path_create_directory"
+            | "path_remove_directory"
+            | "path_rename"
+            | "path_symlink"
+            | "path_link"
+            | "path_unlink_file"
+            | "path_filestat_set_times" => Some("filesystem.write"),
+            _ => None,
+        },
+        "palrun" if matches!(name, "exec" | "spawn") => Some("execute"),
+        _ => None,
+    }
+}
+
+/// Guest pointers are `i32` in the ABI but address memory as unsigned.
+///
+/// Returns `None` if the host can't address that far.
+fn guest_offset(ptr: i32) -> Option<usize> {
+    usize::try_from(u32::from_ne_bytes(ptr.to_ne_bytes())).ok()
 }
 
 /// Scanner plugin interface.
@@ -200,6 +431,7 @@ mod tests {
                 description: None,
                 working_dir: None,
                 tags: vec![],
+                confirm: false,
             }],
         });
 
@@ -227,4 +459,17 @@ mod tests {
         let executor = PluginExecutor::default();
         assert_eq!(executor.scanner_count(), 0);
     }
+
+    #[test]
+    fn test_import_permission() {
+        let wasi = "wasi_snapshot_preview1";
+        assert_eq!(import_permission(wasi, "path_open"), Some("filesystem.read"));
+        assert_eq!(import_permission(wasi, "path_unlink_file"), Some("filesystem.write"));
+        assert_eq!(import_permission("wasi_unstable", "sock_connect"), Some("network"));
+        assert_eq!(import_permission(wasi, "environ_get"), None);
+        assert_eq!(import_permission("palrun", "spawn"), Some("execute"));
+        assert_eq!(import_permission(wasi, "fd_write"), None);
+        assert_eq!(import_permission(wasi, "proc_exit"), None);
+        assert_eq!(import_permission("env", "path_open"), None);
+    }
 }
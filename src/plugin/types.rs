@@ -1,7 +1,11 @@
 //! Core plugin types.
 
+use std::path::Path;
+
 use serde::{Deserialize, Serialize};
 
+use crate::core::{Command, CommandSource};
+
 /// Type of plugin.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -79,6 +83,28 @@ pub struct PluginCommand {
     /// Tags for categorization.
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Whether to ask for confirmation before running.
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+impl PluginCommand {
+    /// Convert into a registry command attributed to `plugin`.
+    ///
+    /// A relative working directory is resolved against `project_root`.
+    pub fn into_command(self, plugin: &str, project_root: &Path) -> Command {
+        let mut command = Command::new(self.name, self.command)
+            .with_source(CommandSource::Plugin { name: plugin.to_string() })
+            .with_tags(self.tags)
+            .with_confirm(self.confirm);
+        if let Some(description) = self.description {
+            command = command.with_description(description);
+        }
+        if let Some(dir) = self.working_dir {
+            command = command.with_working_dir(project_root.join(dir));
+        }
+        command
+    }
 }
 
 /// Plugin API version.
@@ -102,6 +128,40 @@ mod tests {
         assert_eq!(PluginType::Ui.display_name(), "UI");
     }
 
+    #[test]
+    fn test_plugin_command_into_command() {
+        let plugin_command = PluginCommand {
+            name: "gradle build".to_string(),
+            command: "./gradlew build".to_string(),
+            description: Some("Build".to_string()),
+            working_dir: Some("app".to_string()),
+            tags: vec!["gradle".to_string()],
+            confirm: true,
+        };
+
+        let command = plugin_command.into_command("gradle-scanner", Path::new("/project"));
+        assert_eq!(command.name, "gradle build");
+        assert_eq!(command.source, CommandSource::Plugin { name: "gradle-scanner".to_string() });
+        assert_eq!(command.working_dir.as_deref(), Some(Path::new("/project/app")));
+        assert_eq!(command.tags, ["gradle"]);
+        assert!(command.confirm);
+    }
+
+    #[test]
+    fn test_plugin_command_from_sdk_json() {
+        // As serialized by the SDK's `Command`, which omits unset fields
+        let json = r#"[
+            {"name":"deploy","command":"./deploy.sh","working_dir":"packages/api","confirm":true},
+            {"name":"test","command":"npm test"}
+        ]"#;
+        let commands: Vec<PluginCommand> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(commands[0].working_dir.as_deref(), Some("packages/api"));
+        assert!(commands[0].confirm);
+        assert!(commands[1].working_dir.is_none());
+        assert!(!commands[1].confirm);
+    }
+
     #[test]
     fn test_plugin_type_icon() {
         assert_eq!(PluginType::Scanner.icon(), "🔍");
@@ -133,6 +193,7 @@ mod tests {
             description: Some("Build the project".to_string()),
             working_dir: None,
             tags: vec!["build".to_string()],
+            confirm: false,
         };
 
         assert_eq!(cmd.name, "build");
@@ -0,0 +1,297 @@
+//! Step condition expressions.
+//!
+//! A small boolean language for `when:` / `condition:` on runbook steps:
+//! variable lookups, quoted strings, `==`, `!=`, `!`, `&&`, `||`, and
+//! parentheses. `&&` binds tighter than `||`.
+//!
+//! A bare word is a variable lookup. Inside a comparison, a word that
+//! names no variable stands for itself, so `env == prod` still works;
+//! on its own an unset variable is empty, and so false.
+
+use std::collections::HashMap;
+
+/// Evaluate a condition against runbook variables.
+pub fn evaluate(expr: &str, variables: &HashMap<String, String>) -> anyhow::Result<bool> {
+    let tokens = tokenize(expr)?;
+    let mut parser = Parser { tokens, pos: 0, variables };
+    let value = parser.or()?;
+    if let Some(token) = parser.tokens.get(parser.pos) {
+        anyhow::bail!("Unexpected {token:?} in condition '{expr}'");
+    }
+    Ok(value.truthy())
+}
+
+/// A lexical token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// Variable name or unquoted literal
+    Word(String),
+
+    /// Quoted string literal
+    Str(String),
+    Eq,
+    Ne,
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+/// Split an expression into tokens.
+fn tokenize(expr: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = expr.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        chars.next();
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '=' | '&' | '|' => {
+                if chars.next_if_eq(&c).is_none() {
+                    anyhow::bail!("Expected '{c}{c}' in condition '{expr}'");
+                }
+                match c {
+                    '=' => Token::Eq,
+                    '&' => Token::And,
+                    _ => Token::Or,
+                }
+            }
+            '!' => {
+                if chars.next_if_eq(&'=').is_some() {
+                    Token::Ne
+                } else {
+                    Token::Not
+                }
+            }
+            '\'' | '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(ch) if ch == c => break,
+                        Some(ch) => text.push(ch),
+                        None => anyhow::bail!("Unterminated string in condition '{expr}'"),
+                    }
+                }
+                Token::Str(text)
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(ch) = chars.next_if(|&ch| is_word_char(ch)) {
+                    word.push(ch);
+                }
+                Token::Word(word)
+            }
+            c => anyhow::bail!("Unexpected '{c}' in condition '{expr}'"),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Characters allowed in variable names and unquoted literals.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/')
+}
+
+/// The value of a subexpression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    /// Result of a comparison or logical operator
+    Bool(bool),
+
+    /// A variable's value or a literal
+    Text(String),
+}
+
+impl Value {
+    /// Whether the value counts as true: a set, non-empty value other
+    /// than `false` or `0`.
+    fn truthy(&self) -> bool {
+        match self {
+            Self::Bool(b) => *b,
+            Self::Text(s) => !s.is_empty() && s != "false" && s != "0",
+        }
+    }
+
+    /// The value as text, for comparisons.
+    fn text(&self) -> &str {
+        match self {
+            Self::Bool(true) => "true",
+            Self::Bool(false) => "false",
+            Self::Text(s) => s,
+        }
+    }
+}
+
+/// Recursive-descent parser that evaluates as it goes.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    variables: &'a HashMap<String, String>,
+}
+
+impl Parser<'_> {
+    /// Consume the next token if it equals `token`.
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// `and ('||' and)*`
+    fn or(&mut self) -> anyhow::Result<Value> {
+        let mut value = self.and()?;
+        while self.eat(&Token::Or) {
+            let rhs = self.and()?;
+            value = Value::Bool(value.truthy() || rhs.truthy());
+        }
+        Ok(value)
+    }
+
+    /// `unary ('&&' unary)*`
+    fn and(&mut self) -> anyhow::Result<Value> {
+        let mut value = self.unary()?;
+        while self.eat(&Token::And) {
+            let rhs = self.unary()?;
+            value = Value::Bool(value.truthy() && rhs.truthy());
+        }
+        Ok(value)
+    }
+
+    /// `'!' unary | comparison`
+    fn unary(&mut self) -> anyhow::Result<Value> {
+        if self.eat(&Token::Not) {
+            return Ok(Value::Bool(!self.unary()?.truthy()));
+        }
+        self.comparison()
+    }
+
+    /// `operand (('==' | '!=') operand)?`
+    fn comparison(&mut self) -> anyhow::Result<Value> {
+        let lhs = self.operand()?;
+        let negate = if self.eat(&Token::Eq) {
+            false
+        } else if self.eat(&Token::Ne) {
+            true
+        } else {
+            return Ok(lhs.resolve(self.variables, false));
+        };
+        let rhs = self.operand()?;
+
+        let equal =
+            lhs.resolve(self.variables, true).text() == rhs.resolve(self.variables, true).text();
+        Ok(Value::Bool(equal != negate))
+    }
+
+    /// `'(' or ')' | word | string`
+    fn operand(&mut self) -> anyhow::Result<Operand> {
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            anyhow::bail!("Condition ended unexpectedly");
+        };
+        self.pos += 1;
+
+        match token {
+            Token::Open => {
+                let value = self.or()?;
+                if !self.eat(&Token::Close) {
+                    anyhow::bail!("Missing ')' in condition");
+                }
+                Ok(Operand::Value(value))
+            }
+            Token::Word(word) => Ok(Operand::Word(word)),
+            Token::Str(text) => Ok(Operand::Value(Value::Text(text))),
+            other => anyhow::bail!("Unexpected {other:?} in condition"),
+        }
+    }
+}
+
+/// An operand whose meaning depends on whether it is compared.
+enum Operand {
+    /// A bare word: a variable, or a literal in a comparison
+    Word(String),
+
+    /// Anything else
+    Value(Value),
+}
+
+impl Operand {
+    /// Resolve the operand to a value.
+    fn resolve(self, variables: &HashMap<String, String>, compared: bool) -> Value {
+        match self {
+            Self::Word(word) => match variables.get(&word) {
+                Some(value) => Value::Text(value.clone()),
+                None if compared => Value::Text(word),
+                None => Value::Text(String::new()),
+            },
+            Self::Value(value) => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HashMap<String, String> {
+        HashMap::from([
+            ("env".to_string(), "prod".to_string()),
+            ("region".to_string(), "eu".to_string()),
+            ("skip_tests".to_string(), "false".to_string()),
+        ])
+    }
+
+    fn eval(expr: &str) -> bool {
+        evaluate(expr, &vars()).unwrap()
+    }
+
+    #[test]
+    fn test_comparisons() {
+        assert!(eval(r#"env == "prod""#));
+        assert!(eval("env == 'prod'"));
+        assert!(eval("env == prod"));
+        assert!(!eval("env != prod"));
+        assert!(eval("\"prod\" == env"));
+        assert!(!eval("missing == prod"));
+    }
+
+    #[test]
+    fn test_logic_and_precedence() {
+        assert!(eval("env == prod && region == eu"));
+        assert!(!eval("env == prod && region == us"));
+        assert!(eval("env == dev || region == eu"));
+        // && binds tighter: false || (true && true)
+        assert!(eval("env == dev || env == prod && region == eu"));
+        assert!(!eval("(env == dev || env == prod) && region == us"));
+        assert!(eval("!(env == dev)"));
+    }
+
+    #[test]
+    fn test_truthiness() {
+        assert!(eval("!skip_tests"));
+        assert!(!eval("skip_tests"));
+        assert!(!eval("unset"));
+        assert!(eval("!unset && env"));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expr in [
+            "env = prod",
+            "env == ",
+            "(env == prod",
+            "env == prod)",
+            "env & x",
+            "a ; b",
+            "env == 'prod",
+        ] {
+            assert!(evaluate(expr, &vars()).is_err(), "{expr} should not parse");
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! Runbooks are YAML files that define step-by-step workflows with
 //! variables, conditions, and confirmations.
 
+mod expr;
 mod parser;
 mod runner;
 mod schema;
@@ -41,6 +41,9 @@ fn validate_runbook(runbook: &Runbook) -> anyhow::Result<()> {
         }
     }
 
+    // Check that `depends_on` names real steps without cycles
+    runbook.waves()?;
+
     // Check for undefined variables in commands
     if let Some(ref variables) = runbook.variables {
         let var_pattern = regex::Regex::new(r"\{\{\s*(\w+)\s*\}\}").unwrap();
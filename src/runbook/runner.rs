@@ -2,12 +2,14 @@
 //!
 //! Executes runbook steps with variable interpolation and condition evaluation.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use parking_lot::Mutex;
 use regex::Regex;
 
 use super::{Runbook, Step};
-use crate::core::Executor;
+use crate::core::{Executor, Stream};
 
 /// Runbook runner state.
 #[derive(Debug)]
@@ -26,6 +28,12 @@ pub struct RunbookRunner {
 
     /// Execution results
     results: Vec<StepResult>,
+
+    /// Most steps to run at once (overrides the runbook's `max_parallel`)
+    max_parallel: Option<usize>,
+
+    /// Indices of the steps to run (`None` = all)
+    selected: Option<HashSet<usize>>,
 }
 
 /// Runner state.
@@ -70,7 +78,24 @@ impl RunbookRunner {
             }
         }
 
-        Self { runbook, variables, current_step: 0, state: RunnerState::Ready, results: Vec::new() }
+        Self {
+            runbook,
+            variables,
+            current_step: 0,
+            state: RunnerState::Ready,
+            results: Vec::new(),
+            max_parallel: None,
+            selected: None,
+        }
+    }
+
+    /// Set the most steps to run at once.
+    ///
+    /// Defaults to the runbook's `max_parallel`, or the number of CPUs.
+    #[must_use]
+    pub fn with_max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = Some(max_parallel);
+        self
     }
 
     /// Set a variable value.
@@ -83,6 +108,69 @@ impl RunbookRunner {
         self.variables.extend(vars);
     }
 
+    /// Run only the named step and the steps after it (`--from-step`).
+    ///
+    /// Returns a warning for each explicit `depends_on` prerequisite that
+    /// will be skipped.
+    pub fn start_from(&mut self, name: &str) -> anyhow::Result<Vec<String>> {
+        let start = self.step_index(name)?;
+        self.select((start..self.runbook.steps.len()).collect())
+    }
+
+    /// Run only the named steps (`--only`).
+    ///
+    /// Returns a warning for each explicit `depends_on` prerequisite that
+    /// will be skipped.
+    pub fn only(&mut self, names: &[String]) -> anyhow::Result<Vec<String>> {
+        let selected =
+            names.iter().map(|name| self.step_index(name)).collect::<anyhow::Result<_>>()?;
+        self.select(selected)
+    }
+
+    /// Get the steps that will run, in runbook order.
+    pub fn planned_steps(&self) -> Vec<&Step> {
+        (0..self.runbook.steps.len())
+            .filter(|i| self.is_selected(*i))
+            .map(|i| &self.runbook.steps[i])
+            .collect()
+    }
+
+    /// Find a step by name, listing the available steps if there is none.
+    fn step_index(&self, name: &str) -> anyhow::Result<usize> {
+        self.runbook.steps.iter().position(|step| step.name == name).ok_or_else(|| {
+            let available: Vec<&str> = self.runbook.steps.iter().map(|s| s.name.as_str()).collect();
+            anyhow::anyhow!("No step named '{name}'. Available steps: {}", available.join(", "))
+        })
+    }
+
+    /// Restrict the run to `selected` and warn about skipped prerequisites.
+    fn select(&mut self, selected: HashSet<usize>) -> anyhow::Result<Vec<String>> {
+        let deps = self.runbook.dependencies()?;
+        let steps = &self.runbook.steps;
+
+        let mut warnings = Vec::new();
+        for (i, step) in steps.iter().enumerate().filter(|(i, _)| selected.contains(i)) {
+            // Only explicit prerequisites; the implicit previous step is expected to be skipped
+            if step.depends_on.is_none() {
+                continue;
+            }
+            for &dep in deps[i].iter().filter(|dep| !selected.contains(dep)) {
+                warnings.push(format!(
+                    "Step '{}' depends on '{}', which will be skipped",
+                    step.name, steps[dep].name
+                ));
+            }
+        }
+
+        self.selected = Some(selected);
+        Ok(warnings)
+    }
+
+    /// Whether the step at `index` is part of the run.
+    fn is_selected(&self, index: usize) -> bool {
+        self.selected.as_ref().is_none_or(|selected| selected.contains(&index))
+    }
+
     /// Get the current state.
     pub fn state(&self) -> &RunnerState {
         &self.state
@@ -113,57 +201,105 @@ impl RunbookRunner {
             }
         }
 
-        // Execute each step
-        while self.current_step < self.runbook.steps.len() {
-            let step = &self.runbook.steps[self.current_step];
+        let waves = self.runbook.waves()?;
+        let max_parallel =
+            self.max_parallel.or(self.runbook.max_parallel).unwrap_or_else(num_cpus::get).max(1);
 
-            // Check condition
-            if let Some(ref condition) = step.condition {
-                if !self.evaluate_condition(condition) {
-                    tracing::debug!(step = step.name, "Skipping step (condition not met)");
-                    self.current_step += 1;
+        // Execute the steps wave by wave; a wave's steps only depend on earlier waves
+        for wave in waves {
+            self.current_step = wave[0];
+
+            let mut to_run = Vec::new();
+            for index in wave {
+                let step = &self.runbook.steps[index];
+
+                if !self.is_selected(index) {
+                    tracing::debug!(step = step.name, "Skipping step (not selected)");
                     continue;
                 }
-            }
 
-            // Check confirmation
-            if step.confirm.unwrap_or(false) {
-                self.state = RunnerState::AwaitingConfirmation;
-                // In a real implementation, we'd pause here for user input
-                // For now, we'll just continue
-            }
-
-            // Execute the step
-            match self.execute_step(step) {
-                Ok(result) => {
-                    let success = result.success;
-                    self.results.push(result);
-
-                    if !success && !step.continue_on_error.unwrap_or(false) {
-                        if !step.optional.unwrap_or(false) {
-                            self.state =
-                                RunnerState::Failed(format!("Step '{}' failed", step.name));
-                            return Err(anyhow::anyhow!("Step '{}' failed", step.name));
+                // Check condition
+                if let Some(ref condition) = step.condition {
+                    let met = match self.evaluate_condition(condition) {
+                        Ok(met) => met,
+                        Err(e) => {
+                            self.state = RunnerState::Failed(e.to_string());
+                            return Err(
+                                e.context(format!("Invalid condition on step '{}'", step.name))
+                            );
                         }
+                    };
+                    if !met {
+                        tracing::debug!(step = step.name, "Skipping step (condition not met)");
+                        continue;
                     }
                 }
-                Err(e) => {
-                    if !step.optional.unwrap_or(false) {
-                        self.state = RunnerState::Failed(e.to_string());
-                        return Err(e);
-                    }
+
+                // Check confirmation
+                if step.confirm.unwrap_or(false) {
+                    self.state = RunnerState::AwaitingConfirmation;
+                    // In a real implementation, we'd pause here for user input
+                    // For now, we'll just continue
                 }
+
+                to_run.push(index);
             }
 
-            self.current_step += 1;
+            let results = self.execute_wave(&to_run, max_parallel);
+
+            // The whole wave finishes before a failure stops the runbook
+            let failed = to_run.iter().zip(&results).find_map(|(&index, result)| {
+                let step = &self.runbook.steps[index];
+                let tolerated =
+                    step.continue_on_error.unwrap_or(false) || step.optional.unwrap_or(false);
+                (!result.success && !tolerated).then(|| step.name.clone())
+            });
+            self.results.extend(results);
+
+            if let Some(name) = failed {
+                self.state = RunnerState::Failed(format!("Step '{name}' failed"));
+                return Err(anyhow::anyhow!("Step '{name}' failed"));
+            }
         }
 
+        self.current_step = self.runbook.steps.len();
         self.state = RunnerState::Completed;
         Ok(())
     }
 
-    /// Execute a single step.
-    fn execute_step(&self, step: &Step) -> anyhow::Result<StepResult> {
+    /// Execute the steps of one wave, at most `max_parallel` at a time.
+    ///
+    /// Results are returned in the order of `indices`. When more than one
+    /// step runs, each output line is prefixed with its step's name.
+    fn execute_wave(&self, indices: &[usize], max_parallel: usize) -> Vec<StepResult> {
+        let prefixed = indices.len() > 1;
+        if !prefixed || max_parallel == 1 {
+            return indices
+                .iter()
+                .map(|&i| self.execute_step(&self.runbook.steps[i], prefixed))
+                .collect();
+        }
+
+        let next = AtomicUsize::new(0);
+        let slots: Vec<Mutex<Option<StepResult>>> =
+            indices.iter().map(|_| Mutex::new(None)).collect();
+
+        std::thread::scope(|scope| {
+            for _ in 0..max_parallel.min(indices.len()) {
+                scope.spawn(|| loop {
+                    let slot = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&index) = indices.get(slot) else { break };
+                    let result = self.execute_step(&self.runbook.steps[index], true);
+                    *slots[slot].lock() = Some(result);
+                });
+            }
+        });
+
+        slots.into_iter().filter_map(Mutex::into_inner).collect()
+    }
+
+    /// Execute a single step, printing its output as it arrives.
+    fn execute_step(&self, step: &Step, prefixed: bool) -> StepResult {
         let command = self.interpolate(&step.command);
 
         tracing::info!(step = step.name, command = command, "Executing step");
@@ -180,32 +316,31 @@ impl RunbookRunner {
             }
         }
 
-        let executor = Executor::new().capture(true);
+        let prefix = if prefixed { format!("[{}] ", step.name) } else { String::new() };
         let start = std::time::Instant::now();
 
-        match executor.execute(&cmd) {
-            Ok(result) => {
-                let duration_ms = start.elapsed().as_millis() as u64;
-
-                Ok(StepResult {
-                    name: step.name.clone(),
-                    success: result.success(),
-                    exit_code: result.code(),
-                    error: if result.success() { None } else { result.stderr },
-                    duration_ms,
-                })
-            }
-            Err(e) => {
-                let duration_ms = start.elapsed().as_millis() as u64;
-
-                Ok(StepResult {
-                    name: step.name.clone(),
-                    success: false,
-                    exit_code: None,
-                    error: Some(e.to_string()),
-                    duration_ms,
-                })
-            }
+        let result =
+            Executor::new().capture(true).execute_streaming(&cmd, |line| match line.stream {
+                Stream::Stdout => println!("{prefix}{}", line.text),
+                Stream::Stderr => eprintln!("{prefix}{}", line.text),
+            });
+        let duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+        match result {
+            Ok(result) => StepResult {
+                name: step.name.clone(),
+                success: result.success(),
+                exit_code: result.code(),
+                error: if result.success() { None } else { result.stderr },
+                duration_ms,
+            },
+            Err(e) => StepResult {
+                name: step.name.clone(),
+                success: false,
+                exit_code: None,
+                error: Some(e.to_string()),
+                duration_ms,
+            },
         }
     }
 
@@ -220,42 +355,9 @@ impl RunbookRunner {
         .to_string()
     }
 
-    /// Evaluate a condition expression.
-    fn evaluate_condition(&self, condition: &str) -> bool {
-        let condition = condition.trim();
-
-        // Handle negation
-        if let Some(rest) = condition.strip_prefix('!') {
-            let var_name = rest.trim();
-            let value = self.variables.get(var_name).map(String::as_str).unwrap_or("");
-            return value.is_empty() || value == "false" || value == "0";
-        }
-
-        // Handle equality
-        if condition.contains("==") {
-            let parts: Vec<&str> = condition.split("==").collect();
-            if parts.len() == 2 {
-                let var_name = parts[0].trim();
-                let expected = parts[1].trim().trim_matches(|c| c == '\'' || c == '"');
-                let actual = self.variables.get(var_name).map(String::as_str).unwrap_or("");
-                return actual == expected;
-            }
-        }
-
-        // Handle inequality
-        if condition.contains("!=") {
-            let parts: Vec<&str> = condition.split("!=").collect();
-            if parts.len() == 2 {
-                let var_name = parts[0].trim();
-                let expected = parts[1].trim().trim_matches(|c| c == '\'' || c == '"');
-                let actual = self.variables.get(var_name).map(String::as_str).unwrap_or("");
-                return actual != expected;
-            }
-        }
-
-        // Simple truthiness check
-        let value = self.variables.get(condition).map(String::as_str).unwrap_or("");
-        !value.is_empty() && value != "false" && value != "0"
+    /// Evaluate a condition expression against the runbook variables.
+    fn evaluate_condition(&self, condition: &str) -> anyhow::Result<bool> {
+        super::expr::evaluate(condition, &self.variables)
     }
 }
 
@@ -297,10 +399,10 @@ steps:
         let mut runner = RunbookRunner::new(runbook);
 
         runner.set_variable("skip", "true");
-        assert!(!runner.evaluate_condition("!skip"));
+        assert!(!runner.evaluate_condition("!skip").unwrap());
 
         runner.set_variable("skip", "false");
-        assert!(runner.evaluate_condition("!skip"));
+        assert!(runner.evaluate_condition("!skip").unwrap());
     }
 
     #[test]
@@ -317,8 +419,190 @@ steps:
 
         runner.set_variable("env", "prod");
 
-        assert!(runner.evaluate_condition("env == 'prod'"));
-        assert!(!runner.evaluate_condition("env == 'staging'"));
-        assert!(runner.evaluate_condition("env != 'staging'"));
+        assert!(runner.evaluate_condition("env == 'prod'").unwrap());
+        assert!(!runner.evaluate_condition("env == 'staging'").unwrap());
+        assert!(runner.evaluate_condition("env != 'staging'").unwrap());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_skips_step_when_condition_false() {
+        let yaml = r#"
+name: test
+variables:
+  env:
+    type: string
+    default: staging
+steps:
+  - name: build
+    command: "true"
+  - name: deploy
+    command: "false"
+    when: env == "prod" && !dry_run
+"#;
+
+        let mut runner = RunbookRunner::new(parse_runbook_str(yaml).unwrap());
+        runner.run().unwrap();
+
+        let names: Vec<_> = runner.results().iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["build"]);
+        assert_eq!(runner.state(), &RunnerState::Completed);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_tolerates_failure_with_continue_on_error() {
+        let yaml = r#"
+name: test
+steps:
+  - name: flaky
+    command: exit 3
+    continue_on_error: true
+  - name: cleanup
+    command: "true"
+"#;
+
+        let mut runner = RunbookRunner::new(parse_runbook_str(yaml).unwrap());
+        runner.run().unwrap();
+
+        let results = runner.results();
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].success);
+        assert_eq!(results[0].exit_code, Some(3));
+        assert!(results[1].success);
+        assert_eq!(runner.state(), &RunnerState::Completed);
+    }
+
+    #[test]
+    fn test_run_fails_on_invalid_condition() {
+        let yaml = r#"
+name: test
+steps:
+  - name: step1
+    command: echo "test"
+    when: env = prod
+"#;
+
+        let mut runner = RunbookRunner::new(parse_runbook_str(yaml).unwrap());
+        let err = runner.run().unwrap_err();
+        assert!(err.to_string().contains("step1"));
+        assert!(runner.results().is_empty());
+    }
+
+    /// A diamond: `start`, then `left` and `right` together, then `join`.
+    ///
+    /// `left` only succeeds if `right` runs at the same time, which proves
+    /// the middle wave is concurrent without relying on timing.
+    const DIAMOND: &str = r#"
+name: diamond
+steps:
+  - name: start
+    command: echo start > order
+    working_dir: "{{ dir }}"
+  - name: left
+    command: for i in $(seq 50); do [ -f right.done ] && exit 0; sleep 0.1; done; exit 1
+    working_dir: "{{ dir }}"
+    depends_on: [start]
+  - name: right
+    command: "{{ right }}"
+    working_dir: "{{ dir }}"
+    depends_on: [start]
+  - name: join
+    command: echo join >> order
+    working_dir: "{{ dir }}"
+    depends_on: [left, right]
+"#;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_diamond_in_parallel() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut runner =
+            RunbookRunner::new(parse_runbook_str(DIAMOND).unwrap()).with_max_parallel(2);
+        runner.set_variable("dir", temp.path().display().to_string());
+        runner.set_variable("right", "touch right.done");
+
+        runner.run().unwrap();
+
+        let names: Vec<_> = runner.results().iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["start", "left", "right", "join"]);
+        assert!(runner.results().iter().all(|r| r.success));
+        let order = std::fs::read_to_string(temp.path().join("order")).unwrap();
+        assert_eq!(order, "start\njoin\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_fails_after_wave_completes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut runner =
+            RunbookRunner::new(parse_runbook_str(DIAMOND).unwrap()).with_max_parallel(2);
+        runner.set_variable("dir", temp.path().display().to_string());
+        runner.set_variable("right", "touch right.done; exit 7");
+
+        let err = runner.run().unwrap_err();
+        assert!(err.to_string().contains("right"));
+
+        // Both middle steps finished; the join never ran
+        let results = runner.results();
+        let names: Vec<_> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["start", "left", "right"]);
+        assert!(results[1].success);
+        assert_eq!(results[2].exit_code, Some(7));
+        assert!(matches!(runner.state(), RunnerState::Failed(_)));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_start_from_step() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut runner = RunbookRunner::new(parse_runbook_str(DIAMOND).unwrap());
+        runner.set_variable("dir", temp.path().display().to_string());
+        runner.set_variable("right", "touch right.done");
+
+        let warnings = runner.start_from("right").unwrap();
+        assert_eq!(
+            warnings,
+            vec![
+                "Step 'right' depends on 'start', which will be skipped",
+                "Step 'join' depends on 'left', which will be skipped",
+            ]
+        );
+
+        let planned: Vec<_> = runner.planned_steps().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(planned, vec!["right", "join"]);
+
+        runner.run().unwrap();
+        let names: Vec<_> = runner.results().iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["right", "join"]);
+        assert_eq!(std::fs::read_to_string(temp.path().join("order")).unwrap(), "join\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_only_steps() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut runner = RunbookRunner::new(parse_runbook_str(DIAMOND).unwrap());
+        runner.set_variable("dir", temp.path().display().to_string());
+        runner.set_variable("right", "touch right.done");
+
+        let warnings = runner.only(&["start".to_string(), "right".to_string()]).unwrap();
+        assert_eq!(warnings, Vec::<String>::new());
+
+        runner.run().unwrap();
+        let names: Vec<_> = runner.results().iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["start", "right"]);
+    }
+
+    #[test]
+    fn test_unknown_step_lists_available() {
+        let mut runner = RunbookRunner::new(parse_runbook_str(DIAMOND).unwrap());
+
+        let err = runner.start_from("deploy").unwrap_err().to_string();
+        assert!(err.contains("No step named 'deploy'"));
+        assert!(err.contains("start, left, right, join"));
+
+        assert!(runner.only(&["left".to_string(), "nope".to_string()]).is_err());
+        assert_eq!(runner.planned_steps().len(), 4);
     }
 }
@@ -24,6 +24,9 @@ pub struct Runbook {
     /// Variables that can be set by the user
     pub variables: Option<HashMap<String, Variable>>,
 
+    /// Most steps to run at once when `depends_on` allows (default: CPU count)
+    pub max_parallel: Option<usize>,
+
     /// Steps to execute
     pub steps: Vec<Step>,
 }
@@ -71,7 +74,8 @@ pub struct Step {
     /// Description of this step
     pub description: Option<String>,
 
-    /// Condition for running this step (e.g., "!skip_tests")
+    /// Condition for running this step (e.g., "!skip_tests"); also `when`
+    #[serde(alias = "when")]
     pub condition: Option<String>,
 
     /// Whether to confirm before running
@@ -80,7 +84,7 @@ pub struct Step {
     /// Whether this step is optional
     pub optional: Option<bool>,
 
-    /// Whether to continue on error
+    /// Whether later steps still run if this one fails
     pub continue_on_error: Option<bool>,
 
     /// Timeout in seconds
@@ -91,6 +95,9 @@ pub struct Step {
 
     /// Environment variables for this step
     pub env: Option<HashMap<String, String>>,
+
+    /// Steps that must finish first (default: the previous step)
+    pub depends_on: Option<Vec<String>>,
 }
 
 impl Runbook {
@@ -105,6 +112,72 @@ impl Runbook {
     pub fn variable_names(&self) -> Vec<&str> {
         self.variables.as_ref().map(|v| v.keys().map(String::as_str).collect()).unwrap_or_default()
     }
+
+    /// Get the steps each step waits for, by index.
+    ///
+    /// A step with `depends_on` waits for the named steps; a step without it
+    /// waits for the step before it, so runbooks without `depends_on` run in
+    /// order. Fails on unknown dependencies.
+    pub fn dependencies(&self) -> anyhow::Result<Vec<Vec<usize>>> {
+        let by_name: HashMap<&str, usize> =
+            self.steps.iter().enumerate().rev().map(|(i, step)| (step.name.as_str(), i)).collect();
+
+        let mut deps = Vec::with_capacity(self.steps.len());
+        for (i, step) in self.steps.iter().enumerate() {
+            let step_deps = match step.depends_on {
+                Some(ref names) => names
+                    .iter()
+                    .map(|name| match by_name.get(name.as_str()) {
+                        Some(&dep) if dep != i => Ok(dep),
+                        Some(_) => Err(anyhow::anyhow!("Step '{name}' depends on itself")),
+                        None => Err(anyhow::anyhow!(
+                            "Step '{}' depends on unknown step '{name}'",
+                            step.name
+                        )),
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+                None => i.checked_sub(1).into_iter().collect(),
+            };
+            deps.push(step_deps);
+        }
+        Ok(deps)
+    }
+
+    /// Group steps into waves that can each run concurrently.
+    ///
+    /// Each wave holds the steps whose [dependencies](Self::dependencies)
+    /// are all in earlier waves, in runbook order. Fails on unknown or
+    /// circular dependencies.
+    pub fn waves(&self) -> anyhow::Result<Vec<Vec<usize>>> {
+        let steps = &self.steps;
+        let deps = self.dependencies()?;
+
+        let mut wave_of: Vec<Option<usize>> = vec![None; steps.len()];
+        let mut waves: Vec<Vec<usize>> = Vec::new();
+        let mut placed = 0;
+
+        while placed < steps.len() {
+            let ready: Vec<usize> = (0..steps.len())
+                .filter(|&i| wave_of[i].is_none() && deps[i].iter().all(|&d| wave_of[d].is_some()))
+                .collect();
+
+            if ready.is_empty() {
+                let cycle: Vec<&str> = (0..steps.len())
+                    .filter(|&i| wave_of[i].is_none())
+                    .map(|i| steps[i].name.as_str())
+                    .collect();
+                anyhow::bail!("Steps have circular dependencies: {}", cycle.join(", "));
+            }
+
+            for &i in &ready {
+                wave_of[i] = Some(waves.len());
+            }
+            placed += ready.len();
+            waves.push(ready);
+        }
+
+        Ok(waves)
+    }
 }
 
 #[cfg(test)]
@@ -179,4 +252,53 @@ env:
         assert_eq!(step.timeout, Some(30));
         assert_eq!(step.env.unwrap().get("FOO"), Some(&"bar".to_string()));
     }
+
+    #[test]
+    fn test_waves() {
+        let yaml = r"
+name: ci
+steps:
+  - name: install
+    command: npm ci
+  - name: lint
+    command: npm run lint
+    depends_on: [install]
+  - name: test
+    command: npm test
+    depends_on: [install]
+  - name: package
+    command: npm pack
+    depends_on: [lint, test]
+  - name: publish
+    command: npm publish
+";
+        let runbook: Runbook = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(runbook.waves().unwrap(), vec![vec![0], vec![1, 2], vec![3], vec![4]]);
+    }
+
+    #[test]
+    fn test_waves_reject_bad_dependencies() {
+        let unknown = r"
+name: bad
+steps:
+  - name: a
+    command: 'true'
+    depends_on: [missing]
+";
+        let runbook: Runbook = serde_yaml::from_str(unknown).unwrap();
+        assert!(runbook.waves().unwrap_err().to_string().contains("missing"));
+
+        let cycle = r"
+name: bad
+steps:
+  - name: a
+    command: 'true'
+    depends_on: [b]
+  - name: b
+    command: 'true'
+    depends_on: [a]
+";
+        let runbook: Runbook = serde_yaml::from_str(cycle).unwrap();
+        assert!(runbook.waves().unwrap_err().to_string().contains("circular"));
+    }
 }
@@ -17,6 +17,10 @@ impl Scanner for BuiltinScanner {
         "builtin"
     }
 
+    fn rule(&self) -> &str {
+        "Palrun's own CLI commands, always available"
+    }
+
     fn scan(&self, _path: &Path) -> anyhow::Result<Vec<Command>> {
         let mut commands = Vec::new();
 
@@ -6,7 +6,7 @@ use std::path::Path;
 
 use serde::Deserialize;
 
-use super::Scanner;
+use super::{read_source, Scanner};
 use crate::core::{Command, CommandSource};
 
 /// Scanner for Rust/Cargo projects.
@@ -17,6 +17,14 @@ impl Scanner for CargoScanner {
         "cargo"
     }
 
+    fn source_files(&self) -> &[&'static str] {
+        &["Cargo.toml"]
+    }
+
+    fn rule(&self) -> &str {
+        "standard cargo commands for the package or workspace"
+    }
+
     fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
         let mut commands = Vec::new();
 
@@ -272,7 +280,7 @@ struct Target {
 
 /// Parse Cargo.toml file.
 fn parse_cargo_toml(path: &Path) -> anyhow::Result<CargoConfig> {
-    let content = std::fs::read_to_string(path)?;
+    let content = read_source(path)?;
     let config: CargoConfig = toml::from_str(&content)?;
     Ok(config)
 }
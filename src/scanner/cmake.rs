@@ -0,0 +1,398 @@
+//! CMake scanner.
+//!
+//! Scans CMakeLists.txt files for targets and CMakePresets.json for
+//! configure, build, and test presets.
+//! See: https://cmake.org/cmake/help/latest/manual/cmake-presets.7.html
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use super::{read_source, should_skip_dir, Scanner};
+use crate::core::{Command, CommandSource};
+
+/// Build directory used by the configure and build commands.
+const BUILD_DIR: &str = "build";
+
+/// How deep below the project root to look for CMakeLists.txt files.
+const MAX_LISTS_DEPTH: usize = 4;
+
+/// Preset files, in lookup order.
+const PRESET_FILES: &[&str] = &["CMakePresets.json", "CMakeUserPresets.json"];
+
+/// `add_executable(<name> ...)` and `add_library(<name> ...)` calls.
+static TARGET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?im)^\s*add_(executable|library)\s*\(\s*([^\s()#]+)([^)]*)").unwrap()
+});
+
+/// Scanner for CMake projects.
+pub struct CMakeScanner;
+
+impl Scanner for CMakeScanner {
+    fn name(&self) -> &str {
+        "cmake"
+    }
+
+    fn source_files(&self) -> &[&'static str] {
+        &["CMakeLists.txt", "CMakePresets.json", "CMakeUserPresets.json"]
+    }
+
+    fn rule(&self) -> &str {
+        "configure/build/test commands, a build for each `add_executable`/`add_library` target, \
+         and each visible preset in CMakePresets.json and CMakeUserPresets.json"
+    }
+
+    fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
+        let lists_path = dir.join("CMakeLists.txt");
+        if !lists_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let source = CommandSource::CMake(lists_path.clone());
+        let cmake_command = |cmd: String, description: String, tags: &[&str]| {
+            let mut all_tags = vec!["cmake".to_string()];
+            all_tags.extend(tags.iter().map(ToString::to_string));
+            Command::new(&cmd, &cmd)
+                .with_description(description)
+                .with_source(source.clone())
+                .with_working_dir(dir)
+                .with_tags(all_tags)
+        };
+
+        let mut commands = vec![
+            cmake_command(
+                format!("cmake -B {BUILD_DIR}"),
+                format!("Configure the project into {BUILD_DIR}/"),
+                &["configure"],
+            ),
+            cmake_command(
+                format!("cmake --build {BUILD_DIR}"),
+                "Build all targets".to_string(),
+                &["build"],
+            ),
+            cmake_command(
+                format!("ctest --test-dir {BUILD_DIR}"),
+                "Run tests with CTest".to_string(),
+                &["test"],
+            ),
+        ];
+
+        for target in find_targets(dir) {
+            commands.push(cmake_command(
+                format!("cmake --build {BUILD_DIR} --target {}", target.name),
+                format!("Build the {} {}", target.name, target.kind),
+                &["build", &target.name],
+            ));
+        }
+
+        for preset_file in PRESET_FILES {
+            let path = dir.join(preset_file);
+            if !path.exists() {
+                continue;
+            }
+            let presets = read_source(&path)
+                .and_then(|content| Ok(serde_json::from_str::<PresetsFile>(&content)?));
+            match presets {
+                Ok(presets) => commands.extend(preset_commands(&presets, &source, dir)),
+                Err(e) => tracing::warn!("Skipping presets in {}: {e}", path.display()),
+            }
+        }
+
+        Ok(commands)
+    }
+}
+
+/// A target declared in a CMakeLists.txt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+    /// Target name, as written (variables are not evaluated)
+    name: String,
+
+    /// `executable` or `library`
+    kind: &'static str,
+}
+
+/// Find the targets declared in every CMakeLists.txt of the project.
+///
+/// Files are read in path order, so targets from the root come first.
+fn find_targets(dir: &Path) -> Vec<Target> {
+    let mut targets = Vec::new();
+    let mut seen = HashSet::new();
+
+    let walker =
+        WalkDir::new(dir).max_depth(MAX_LISTS_DEPTH).sort_by_file_name().into_iter().filter_entry(
+            |entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_dir()
+                    || !should_skip_dir(&entry.file_name().to_string_lossy())
+            },
+        );
+
+    for entry in walker.filter_map(Result::ok) {
+        if entry.file_name() != "CMakeLists.txt" {
+            continue;
+        }
+        let Ok(content) = read_source(entry.path()) else {
+            continue;
+        };
+        for target in parse_targets(&content) {
+            if seen.insert(target.name.clone()) {
+                targets.push(target);
+            }
+        }
+    }
+
+    targets
+}
+
+/// Parse `add_executable(<name> ...)` and `add_library(<name> ...)` calls.
+///
+/// Imported and alias targets are skipped because they cannot be built.
+fn parse_targets(content: &str) -> Vec<Target> {
+    TARGET_RE
+        .captures_iter(content)
+        .filter(|caps| {
+            let args = caps[3].to_uppercase();
+            !args.split_whitespace().any(|arg| matches!(arg, "IMPORTED" | "ALIAS"))
+        })
+        .map(|caps| Target {
+            name: caps[2].to_string(),
+            kind: if caps[1].eq_ignore_ascii_case("executable") { "executable" } else { "library" },
+        })
+        .collect()
+}
+
+/// A CMakePresets.json or CMakeUserPresets.json file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PresetsFile {
+    /// Configure presets
+    #[serde(default)]
+    configure_presets: Vec<Preset>,
+
+    /// Build presets
+    #[serde(default)]
+    build_presets: Vec<Preset>,
+
+    /// Test presets
+    #[serde(default)]
+    test_presets: Vec<Preset>,
+}
+
+/// A single preset.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Preset {
+    /// Preset name
+    name: String,
+
+    /// Hidden presets only exist to be inherited from
+    #[serde(default)]
+    hidden: bool,
+
+    /// Presets this one inherits from
+    #[serde(default)]
+    inherits: Inherits,
+
+    /// Human-readable name
+    display_name: Option<String>,
+
+    /// Longer description
+    description: Option<String>,
+
+    /// Generator, for configure presets
+    generator: Option<String>,
+}
+
+/// The `inherits` field: a single preset name or a list of them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(untagged)]
+enum Inherits {
+    /// No parents
+    #[default]
+    None,
+
+    /// A single parent
+    One(String),
+
+    /// Several parents, highest priority first
+    Many(Vec<String>),
+}
+
+impl Inherits {
+    /// The parent preset names, highest priority first.
+    fn names(&self) -> &[String] {
+        match self {
+            Self::None => &[],
+            Self::One(name) => std::slice::from_ref(name),
+            Self::Many(names) => names,
+        }
+    }
+}
+
+/// Generate commands for the visible presets in a presets file.
+fn preset_commands(presets: &PresetsFile, source: &CommandSource, dir: &Path) -> Vec<Command> {
+    let kinds: [(&[Preset], &str, &str); 3] = [
+        (&presets.configure_presets, "cmake --preset", "Configure"),
+        (&presets.build_presets, "cmake --build --preset", "Build"),
+        (&presets.test_presets, "ctest --preset", "Test"),
+    ];
+
+    let mut commands = Vec::new();
+    for (list, program, verb) in kinds {
+        let by_name: HashMap<&str, &Preset> = list.iter().map(|p| (p.name.as_str(), p)).collect();
+
+        for preset in list.iter().filter(|p| !p.hidden) {
+            let cmd = format!("{program} {}", preset.name);
+            let label = preset.display_name.as_deref().unwrap_or(&preset.name);
+            let description = match (&preset.description, inherited_generator(preset, &by_name)) {
+                (Some(description), _) => format!("{verb} {label}: {description}"),
+                (None, Some(generator)) => format!("{verb} {label} ({generator})"),
+                (None, None) => format!("{verb} {label}"),
+            };
+
+            commands.push(
+                Command::new(&cmd, &cmd)
+                    .with_description(description)
+                    .with_source(source.clone())
+                    .with_working_dir(dir)
+                    .with_tags(vec![
+                        "cmake".to_string(),
+                        "preset".to_string(),
+                        preset.name.clone(),
+                    ]),
+            );
+        }
+    }
+
+    commands
+}
+
+/// Resolve a preset's generator, following `inherits` depth-first.
+fn inherited_generator<'a>(
+    preset: &'a Preset,
+    by_name: &HashMap<&str, &'a Preset>,
+) -> Option<&'a str> {
+    let mut stack = vec![preset];
+    let mut visited = HashSet::new();
+
+    while let Some(current) = stack.pop() {
+        if !visited.insert(current.name.as_str()) {
+            continue;
+        }
+        if let Some(ref generator) = current.generator {
+            return Some(generator);
+        }
+        // Earlier parents take priority, so they are popped first
+        stack.extend(current.inherits.names().iter().rev().filter_map(|n| by_name.get(n.as_str())));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CMAKE_LISTS: &str = r"
+cmake_minimum_required(VERSION 3.20)
+project(demo CXX)
+
+add_library(core STATIC src/core.cpp)
+add_library(demo::core ALIAS core)
+add_library(zlib SHARED IMPORTED)
+add_executable(demo src/main.cpp)
+ADD_EXECUTABLE(${PROJECT_NAME}_bench bench/main.cpp)
+# add_executable(disabled src/old.cpp)
+";
+
+    const PRESETS: &str = r#"{
+        "version": 6,
+        "configurePresets": [
+            { "name": "base", "hidden": true, "generator": "Ninja", "binaryDir": "build/${presetName}" },
+            { "name": "debug", "inherits": "base", "displayName": "Debug" },
+            { "name": "release", "inherits": ["base"], "description": "Optimized build" }
+        ],
+        "buildPresets": [
+            { "name": "debug", "configurePreset": "debug" }
+        ],
+        "testPresets": [
+            { "name": "debug", "configurePreset": "debug" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_targets() {
+        let targets = parse_targets(CMAKE_LISTS);
+        let names: Vec<_> = targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["core", "demo", "${PROJECT_NAME}_bench"]);
+        assert_eq!(targets[0].kind, "library");
+        assert_eq!(targets[1].kind, "executable");
+    }
+
+    #[test]
+    fn test_preset_inheritance() {
+        let presets: PresetsFile = serde_json::from_str(PRESETS).unwrap();
+        let source = CommandSource::CMake("CMakeLists.txt".into());
+        let commands = preset_commands(&presets, &source, Path::new("."));
+
+        let names: Vec<_> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "cmake --preset debug",
+                "cmake --preset release",
+                "cmake --build --preset debug",
+                "ctest --preset debug",
+            ]
+        );
+        assert_eq!(commands[0].description.as_deref(), Some("Configure Debug (Ninja)"));
+        assert_eq!(commands[1].description.as_deref(), Some("Configure release: Optimized build"));
+    }
+
+    #[test]
+    fn test_scan_cmake_project() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("CMakeLists.txt"), CMAKE_LISTS).unwrap();
+        std::fs::write(temp.path().join("CMakePresets.json"), PRESETS).unwrap();
+        let tools = temp.path().join("tools");
+        std::fs::create_dir(&tools).unwrap();
+        std::fs::write(tools.join("CMakeLists.txt"), "add_executable(codegen gen.cpp)\n").unwrap();
+
+        let commands = CMakeScanner.scan(temp.path()).unwrap();
+        let names: Vec<_> = commands.iter().map(|c| c.name.as_str()).collect();
+
+        assert!(names.contains(&"cmake -B build"));
+        assert!(names.contains(&"cmake --build build"));
+        assert!(names.contains(&"cmake --build build --target demo"));
+        assert!(names.contains(&"cmake --build build --target ${PROJECT_NAME}_bench"));
+        assert!(names.contains(&"cmake --build build --target codegen"));
+        assert!(names.contains(&"cmake --preset debug"));
+        assert!(!names.contains(&"cmake --preset base"));
+        assert!(commands.iter().all(|c| matches!(c.source, CommandSource::CMake(_))));
+    }
+
+    #[test]
+    fn test_malformed_presets_are_skipped() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("CMakeLists.txt"), CMAKE_LISTS).unwrap();
+        std::fs::write(temp.path().join("CMakePresets.json"), "{ not json").unwrap();
+        std::fs::write(temp.path().join("CMakeUserPresets.json"), PRESETS).unwrap();
+
+        let commands = CMakeScanner.scan(temp.path()).unwrap();
+        let names: Vec<_> = commands.iter().map(|c| c.name.as_str()).collect();
+
+        assert!(names.contains(&"cmake --build build --target demo"));
+        assert!(names.contains(&"cmake --preset debug"));
+    }
+
+    #[test]
+    fn test_no_cmake_lists() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(CMakeScanner.scan(temp.path()).unwrap(), Vec::new());
+    }
+}
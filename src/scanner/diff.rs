@@ -0,0 +1,259 @@
+//! Comparing scan results with a saved baseline (`pal scan --diff`).
+//!
+//! Commands are matched by source type, working directory, and name, so a
+//! command whose script changed shows up as changed rather than as one
+//! removal plus one addition. Paths are made relative to the scanned
+//! directory first, so a baseline saved in another checkout still matches.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use super::META_SOURCE_FILE;
+use crate::core::Command;
+
+/// A command present in both scans with different details.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandChange {
+    /// The command as recorded in the baseline
+    pub before: Command,
+
+    /// The command as currently discovered
+    pub after: Command,
+
+    /// Names of the fields that differ
+    pub fields: Vec<&'static str>,
+}
+
+/// Differences between a baseline scan and the current one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ScanDiff {
+    /// Commands only in the current scan
+    pub added: Vec<Command>,
+
+    /// Commands only in the baseline
+    pub removed: Vec<Command>,
+
+    /// Commands in both whose details differ
+    pub changed: Vec<CommandChange>,
+}
+
+impl ScanDiff {
+    /// Check if the scans match.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare a baseline command set with the current one.
+pub fn diff_commands(baseline: &[Command], current: &[Command]) -> ScanDiff {
+    let mut diff = ScanDiff::default();
+
+    for after in current {
+        match baseline.iter().find(|before| same_command(before, after)) {
+            Some(before) => {
+                let fields = changed_fields(before, after);
+                if !fields.is_empty() {
+                    diff.changed.push(CommandChange {
+                        before: before.clone(),
+                        after: after.clone(),
+                        fields,
+                    });
+                }
+            }
+            None => diff.added.push(after.clone()),
+        }
+    }
+
+    diff.removed = baseline
+        .iter()
+        .filter(|before| !current.iter().any(|after| same_command(before, after)))
+        .cloned()
+        .collect();
+
+    diff
+}
+
+/// Order commands by source type, then working directory, then name.
+///
+/// Gives `pal scan --format json` and saved baselines a stable order that
+/// does not depend on scanner order, with each source's commands together.
+pub fn sort_commands(commands: &mut [Command]) {
+    commands.sort_by(|a, b| {
+        (a.source.type_name(), &a.working_dir, &a.name, &a.command).cmp(&(
+            b.source.type_name(),
+            &b.working_dir,
+            &b.name,
+            &b.command,
+        ))
+    });
+}
+
+/// Make working directories and source files relative to `root`.
+///
+/// Covers the source file recorded in the command's [`CommandSource`] as
+/// well as its provenance. Commands running in `root` itself get no
+/// working directory.
+pub fn relativize(commands: &[Command], root: &Path) -> Vec<Command> {
+    let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let relative_to_root = |path: &Path| {
+        let absolute = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        absolute.strip_prefix(&root).ok().map(Path::to_path_buf)
+    };
+
+    commands
+        .iter()
+        .cloned()
+        .map(|mut cmd| {
+            cmd.working_dir = cmd.working_dir.and_then(|dir| match relative_to_root(&dir) {
+                Some(relative) if relative.as_os_str().is_empty() => None,
+                Some(relative) => Some(relative),
+                None => Some(dir),
+            });
+            if let Some(path) = cmd.source.path_mut().filter(|path| path.is_absolute()) {
+                if let Some(relative) = relative_to_root(path) {
+                    *path = relative;
+                }
+            }
+            if let Some(file) = cmd.metadata.get_mut(META_SOURCE_FILE) {
+                if let Ok(relative) = Path::new(file.as_str()).strip_prefix(&root) {
+                    *file = relative.display().to_string();
+                }
+            }
+            cmd
+        })
+        .collect()
+}
+
+fn same_command(a: &Command, b: &Command) -> bool {
+    a.name == b.name
+        && a.working_dir == b.working_dir
+        && a.source.type_name() == b.source.type_name()
+}
+
+fn changed_fields(before: &Command, after: &Command) -> Vec<&'static str> {
+    let checks = [
+        ("command", before.command != after.command),
+        ("description", before.description != after.description),
+        ("tags", before.tags != after.tags),
+        ("env", before.env != after.env),
+        ("confirm", before.confirm != after.confirm),
+        ("shell", before.shell != after.shell),
+    ];
+
+    checks.into_iter().filter(|(_, differs)| *differs).map(|(field, _)| field).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::core::CommandSource;
+
+    fn npm(name: &str, command: &str) -> Command {
+        Command::new(name, command).with_source(CommandSource::PackageJson(PathBuf::from(".")))
+    }
+
+    fn names(commands: &[Command]) -> Vec<&str> {
+        commands.iter().map(|cmd| cmd.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_added_removed_and_changed() {
+        let baseline = vec![
+            npm("npm run build", "tsc"),
+            npm("npm run test", "jest"),
+            Command::new("make old", "make old")
+                .with_source(CommandSource::Makefile(PathBuf::from("."))),
+        ];
+        let current = vec![
+            npm("npm run build", "tsc -b"),
+            npm("npm run test", "jest"),
+            npm("npm run lint", "eslint ."),
+        ];
+
+        let diff = diff_commands(&baseline, &current);
+        assert_eq!(names(&diff.added), vec!["npm run lint"]);
+        assert_eq!(names(&diff.removed), vec!["make old"]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].after.name, "npm run build");
+        assert_eq!(diff.changed[0].before.command, "tsc");
+        assert_eq!(diff.changed[0].fields, vec!["command"]);
+    }
+
+    #[test]
+    fn test_identical_scans_have_no_diff() {
+        let commands = vec![npm("npm run build", "tsc"), npm("npm run test", "jest")];
+        assert!(diff_commands(&commands, &commands).is_empty());
+    }
+
+    #[test]
+    fn test_same_name_in_other_directory_is_distinct() {
+        let baseline = vec![npm("npm run build", "tsc").with_working_dir("packages/a")];
+        let current = vec![
+            npm("npm run build", "tsc").with_working_dir("packages/a"),
+            npm("npm run build", "tsc").with_working_dir("packages/b"),
+        ];
+
+        let diff = diff_commands(&baseline, &current);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].working_dir, Some(PathBuf::from("packages/b")));
+        assert_eq!(diff.removed, Vec::<Command>::new());
+        assert_eq!(diff.changed, Vec::<CommandChange>::new());
+    }
+
+    #[test]
+    fn test_non_command_fields_are_reported() {
+        let baseline = vec![npm("npm run deploy", "./deploy.sh")];
+        let current = vec![npm("npm run deploy", "./deploy.sh")
+            .with_confirm(true)
+            .with_description("Deploy to production")];
+
+        let diff = diff_commands(&baseline, &current);
+        assert_eq!(diff.changed[0].fields, vec!["description", "confirm"]);
+    }
+
+    #[test]
+    fn test_relativize_paths() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let mut cmd = npm("npm run build", "tsc").with_working_dir(root.join("packages/a"));
+        cmd.metadata.insert(
+            META_SOURCE_FILE.to_string(),
+            root.join("packages/a/package.json").display().to_string(),
+        );
+        let cmd = cmd.with_source(CommandSource::PackageJson(root.join("packages/a/package.json")));
+        let at_root = npm("npm run test", "jest").with_working_dir(&root);
+
+        let relative = relativize(&[cmd, at_root], &root);
+        assert_eq!(relative[0].working_dir, Some(PathBuf::from("packages/a")));
+        assert_eq!(
+            relative[0].source,
+            CommandSource::PackageJson(PathBuf::from("packages/a/package.json"))
+        );
+        assert_eq!(relative[0].metadata[META_SOURCE_FILE], "packages/a/package.json");
+        assert_eq!(relative[1].working_dir, None);
+    }
+
+    #[test]
+    fn test_sort_commands_groups_by_source() {
+        let make = |name: &str| {
+            Command::new(name, name).with_source(CommandSource::Makefile(PathBuf::from(".")))
+        };
+        let mut commands = vec![
+            npm("npm run test", "jest").with_working_dir("packages/a"),
+            make("make test"),
+            npm("npm run test", "jest"),
+            make("make build"),
+            npm("npm run build", "tsc"),
+        ];
+
+        sort_commands(&mut commands);
+        assert_eq!(
+            names(&commands),
+            vec!["make build", "make test", "npm run build", "npm run test", "npm run test"]
+        );
+        assert_eq!(commands[4].working_dir, Some(PathBuf::from("packages/a")));
+    }
+}
@@ -1,17 +1,18 @@
-//! Docker Compose scanner.
+//! Docker scanner.
 //!
 //! Scans docker-compose.yml, docker-compose.yaml, or compose.yaml files
-//! to discover Docker Compose services and generate related commands.
+//! to discover Docker Compose services and generate related commands, and
+//! `Dockerfile`/`Dockerfile.<variant>` files to generate image builds.
 
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
-use super::Scanner;
-use crate::core::{Command, CommandSource};
+use super::{read_source, Scanner, META_SOURCE_FILE};
+use crate::core::{program_exists, Command, CommandSource};
 
-/// Scanner for Docker Compose projects.
+/// Scanner for Docker Compose projects and Dockerfiles.
 pub struct DockerScanner;
 
 impl Scanner for DockerScanner {
@@ -19,120 +20,144 @@ impl Scanner for DockerScanner {
         "docker"
     }
 
-    fn scan(&self, path: &Path) -> anyhow::Result<Vec<Command>> {
-        // Try to find a docker compose file
-        let compose_file = find_compose_file(path);
-        let Some(compose_path) = compose_file else {
-            return Ok(Vec::new());
-        };
+    fn source_files(&self) -> &[&'static str] {
+        COMPOSE_FILES
+    }
 
-        let content = std::fs::read_to_string(&compose_path)?;
-        let compose: DockerCompose = serde_yaml::from_str(&content)?;
+    fn rule(&self) -> &str {
+        "compose lifecycle commands, `--profile` variants for each declared profile, \
+         plus per-service commands for each entry in `services`; a `docker build` \
+         for each Dockerfile, listing its `ARG`s"
+    }
 
-        let mut commands = Vec::new();
-        let source = CommandSource::DockerCompose(compose_path.clone());
+    fn scan(&self, path: &Path) -> anyhow::Result<Vec<Command>> {
+        let mut commands = match find_compose_file(path) {
+            Some(compose_path) => scan_compose(&compose_path)?,
+            None => Vec::new(),
+        };
+        commands.extend(scan_dockerfiles(path)?);
 
-        // Generate global docker compose commands
-        commands.push(
-            Command::new("docker compose up -d", "docker compose up -d")
-                .with_description("Start all services in detached mode")
-                .with_source(source.clone())
-                .with_tags(vec!["docker".to_string(), "compose".to_string(), "up".to_string()]),
-        );
+        Ok(commands)
+    }
+}
 
-        commands.push(
-            Command::new("docker compose down", "docker compose down")
-                .with_description("Stop and remove all services")
-                .with_source(source.clone())
-                .with_tags(vec!["docker".to_string(), "compose".to_string(), "down".to_string()]),
-        );
+/// Generate commands for a docker compose file.
+fn scan_compose(compose_path: &Path) -> anyhow::Result<Vec<Command>> {
+    let content = read_source(compose_path)?;
+    let compose: DockerCompose = serde_yaml::from_str(&content)?;
 
-        commands.push(
-            Command::new("docker compose build", "docker compose build")
-                .with_description("Build all services")
-                .with_source(source.clone())
-                .with_tags(vec!["docker".to_string(), "compose".to_string(), "build".to_string()]),
-        );
+    // A compose file without services has nothing to run
+    let services = compose.services.unwrap_or_default();
+    if services.is_empty() {
+        return Ok(Vec::new());
+    }
 
-        commands.push(
-            Command::new("docker compose ps", "docker compose ps")
-                .with_description("List running containers")
-                .with_source(source.clone())
-                .with_tags(vec!["docker".to_string(), "compose".to_string(), "ps".to_string()]),
-        );
+    let cli = compose_cli();
+    let source = CommandSource::DockerCompose(compose_path.to_path_buf());
+    let compose_command = |args: &str, description: String, tags: &[&str]| {
+        let cmd = format!("{cli} {args}");
+        let mut all_tags = vec!["docker".to_string(), "compose".to_string()];
+        all_tags.extend(tags.iter().map(ToString::to_string));
+        Command::new(&cmd, &cmd)
+            .with_description(description)
+            .with_source(source.clone())
+            .with_tags(all_tags)
+    };
+
+    let mut commands = vec![
+        // Generate global docker compose commands
+        compose_command("up -d", "Start all services in detached mode".into(), &["up"]),
+        compose_command("down", "Stop and remove all services".into(), &["down"]),
+        compose_command("build", "Build all services".into(), &["build"]),
+        compose_command("ps", "List running containers".into(), &["ps"]),
+    ];
+
+    // Services in a profile only start when the profile is enabled
+    let profiles: BTreeSet<&str> = services
+        .values()
+        .flatten()
+        .filter_map(|s| s.profiles.as_ref())
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    for profile in profiles {
+        commands.push(compose_command(
+            &format!("--profile {profile} up -d"),
+            format!("Start all services including the {profile} profile"),
+            &["up", "profile", profile],
+        ));
+        commands.push(compose_command(
+            &format!("--profile {profile} down"),
+            format!("Stop all services including the {profile} profile"),
+            &["down", "profile", profile],
+        ));
+    }
 
-        // Generate per-service commands
-        if let Some(services) = compose.services {
-            for (service_name, service_config) in services {
-                // Get description from labels if available
-                let description = service_config
-                    .as_ref()
-                    .and_then(|s| s.labels.as_ref())
-                    .and_then(|labels| labels.get("description").cloned())
-                    .or_else(|| {
-                        service_config.as_ref().and_then(|s| s.labels.as_ref()).and_then(|labels| {
-                            labels.get("com.docker.compose.description").cloned()
-                        })
-                    });
-
-                // docker compose up <service>
-                let up_cmd = format!("docker compose up {service_name}");
-                let up_desc = description
-                    .clone()
-                    .map(|d| format!("Start {service_name}: {d}"))
-                    .unwrap_or_else(|| format!("Start {service_name} service"));
-                commands.push(
-                    Command::new(&up_cmd, &up_cmd)
-                        .with_description(up_desc)
-                        .with_source(source.clone())
-                        .with_tags(vec![
-                            "docker".to_string(),
-                            "compose".to_string(),
-                            "up".to_string(),
-                            service_name.clone(),
-                        ]),
-                );
-
-                // docker compose logs <service>
-                let logs_cmd = format!("docker compose logs {service_name}");
-                commands.push(
-                    Command::new(&logs_cmd, &logs_cmd)
-                        .with_description(format!("View logs for {service_name}"))
-                        .with_source(source.clone())
-                        .with_tags(vec![
-                            "docker".to_string(),
-                            "compose".to_string(),
-                            "logs".to_string(),
-                            service_name.clone(),
-                        ]),
-                );
-
-                // docker compose restart <service>
-                let restart_cmd = format!("docker compose restart {service_name}");
-                commands.push(
-                    Command::new(&restart_cmd, &restart_cmd)
-                        .with_description(format!("Restart {service_name} service"))
-                        .with_source(source.clone())
-                        .with_tags(vec![
-                            "docker".to_string(),
-                            "compose".to_string(),
-                            "restart".to_string(),
-                            service_name.clone(),
-                        ]),
-                );
-            }
+    // Generate per-service commands
+    for (service_name, service_config) in &services {
+        let labels = service_config.as_ref().and_then(|s| s.labels.as_ref());
+
+        // Get description from labels if available
+        let description = labels.and_then(|labels| {
+            labels
+                .get("description")
+                .or_else(|| labels.get("com.docker.compose.description"))
+                .cloned()
+        });
+
+        let up_desc = description
+            .map(|d| format!("Start {service_name}: {d}"))
+            .unwrap_or_else(|| format!("Start {service_name} service"));
+        commands.push(compose_command(
+            &format!("up {service_name}"),
+            up_desc,
+            &["up", service_name],
+        ));
+
+        commands.push(compose_command(
+            &format!("logs {service_name}"),
+            format!("View logs for {service_name}"),
+            &["logs", service_name],
+        ));
+
+        commands.push(compose_command(
+            &format!("restart {service_name}"),
+            format!("Restart {service_name} service"),
+            &["restart", service_name],
+        ));
+
+        // Only services with a build section have an image to build
+        if service_config.as_ref().is_some_and(|s| s.build.is_some()) {
+            commands.push(compose_command(
+                &format!("build {service_name}"),
+                format!("Build {service_name} service"),
+                &["build", service_name],
+            ));
         }
+    }
 
-        Ok(commands)
+    Ok(commands)
+}
+
+/// Pick the compose CLI to run.
+///
+/// Prefers the `docker compose` plugin and falls back to the legacy
+/// standalone `docker-compose` binary when only that is installed.
+fn compose_cli() -> &'static str {
+    if !program_exists("docker") && program_exists("docker-compose") {
+        "docker-compose"
+    } else {
+        "docker compose"
     }
 }
 
+/// Docker compose file names, in lookup order.
+const COMPOSE_FILES: &[&str] = &["docker-compose.yml", "docker-compose.yaml", "compose.yaml"];
+
 /// Find the docker compose file in the given directory.
 /// Checks for docker-compose.yml, docker-compose.yaml, and compose.yaml in that order.
 fn find_compose_file(path: &Path) -> Option<std::path::PathBuf> {
-    let candidates = ["docker-compose.yml", "docker-compose.yaml", "compose.yaml"];
-
-    for candidate in candidates {
+    for candidate in COMPOSE_FILES {
         let file_path = path.join(candidate);
         if file_path.exists() {
             return Some(file_path);
@@ -142,6 +167,122 @@ fn find_compose_file(path: &Path) -> Option<std::path::PathBuf> {
     None
 }
 
+/// Generate a `docker build` command for each Dockerfile in the directory.
+fn scan_dockerfiles(dir: &Path) -> anyhow::Result<Vec<Command>> {
+    let image = image_name(dir);
+    let mut commands = Vec::new();
+
+    for (file_name, file_path) in find_dockerfiles(dir) {
+        let variant = file_name.strip_prefix("Dockerfile.").map(str::to_lowercase);
+        let tag = match variant {
+            Some(ref variant) => format!("{image}:{variant}"),
+            None => image.clone(),
+        };
+
+        let cmd = format!("docker build -f {file_name} -t {tag} .");
+        let args = parse_build_args(&read_source(&file_path)?);
+        let description = if args.is_empty() {
+            format!("Build the {tag} image from {file_name}")
+        } else {
+            let hints: Vec<String> = args.iter().map(|arg| format!("--build-arg {arg}")).collect();
+            format!("Build the {tag} image from {file_name} ({})", hints.join(", "))
+        };
+
+        let mut command = Command::new(&cmd, &cmd)
+            .with_description(description)
+            .with_source(CommandSource::DockerCompose(file_path.clone()))
+            .with_working_dir(dir)
+            .with_tags(vec!["docker".to_string(), "build".to_string()]);
+        if let Some(variant) = variant {
+            command = command.with_tag(variant);
+        }
+        command.metadata.insert(META_SOURCE_FILE.to_string(), file_path.display().to_string());
+        commands.push(command);
+    }
+
+    Ok(commands)
+}
+
+/// Find `Dockerfile` and `Dockerfile.<variant>` files, sorted by name.
+fn find_dockerfiles(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let is_dockerfile = name == "Dockerfile"
+                        || (name.starts_with("Dockerfile.") && !name.ends_with(".dockerignore"));
+                    (is_dockerfile && entry.path().is_file()).then(|| (name, entry.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Derive an image name from the directory name.
+///
+/// Image names must be lowercase and may only contain letters, digits,
+/// and separators, so anything else becomes a `-`.
+fn image_name(dir: &Path) -> String {
+    let name: String = dir
+        .canonicalize()
+        .ok()
+        .as_deref()
+        .and_then(Path::file_name)
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '-' })
+        .collect();
+    let name = name.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+
+    if name.is_empty() {
+        "app".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Parse the `ARG` instructions of a Dockerfile.
+///
+/// Every stage is read, so args declared after a `FROM` in a multi-stage
+/// build are included. Each entry is `NAME` or `NAME=default`, in order of
+/// first appearance.
+fn parse_build_args(content: &str) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    let mut args = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        let Some((instruction, rest)) = line.split_once(char::is_whitespace) else {
+            continue;
+        };
+        if !instruction.eq_ignore_ascii_case("ARG") {
+            continue;
+        }
+
+        for declaration in rest.split_whitespace() {
+            let (name, default) = match declaration.split_once('=') {
+                Some((name, default)) => (name, Some(default.trim_matches(['"', '\'']))),
+                None => (declaration, None),
+            };
+            if name.is_empty() || !seen.insert(name.to_string()) {
+                continue;
+            }
+            args.push(match default {
+                Some(default) => format!("{name}={default}"),
+                None => name.to_string(),
+            });
+        }
+    }
+
+    args
+}
+
 /// Parsed docker-compose.yml structure.
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
@@ -150,7 +291,7 @@ struct DockerCompose {
     version: Option<String>,
 
     /// Services defined in the compose file
-    services: Option<HashMap<String, Option<ServiceConfig>>>,
+    services: Option<BTreeMap<String, Option<ServiceConfig>>>,
 }
 
 /// Configuration for a single service.
@@ -174,6 +315,9 @@ struct ServiceConfig {
 
     /// Container name
     container_name: Option<String>,
+
+    /// Profiles the service belongs to
+    profiles: Option<Vec<String>>,
 }
 
 /// Build configuration for a service.
@@ -372,6 +516,116 @@ services:
         assert_eq!(commands.len(), 7);
     }
 
+    #[test]
+    fn test_scan_empty_services() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("compose.yaml"), "services: {}\n").unwrap();
+
+        let scanner = DockerScanner;
+        let commands = scanner.scan(temp_dir.path()).unwrap();
+        assert_eq!(commands, Vec::new());
+    }
+
+    #[test]
+    fn test_scan_service_build_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let compose_content = r"
+services:
+  app:
+    build: .
+  db:
+    image: postgres
+";
+        fs::write(temp_dir.path().join("compose.yaml"), compose_content).unwrap();
+
+        let scanner = DockerScanner;
+        let commands = scanner.scan(temp_dir.path()).unwrap();
+        let cli = compose_cli();
+
+        let build = commands.iter().find(|c| c.name == format!("{cli} build app")).unwrap();
+        assert!(build.tags.contains(&"app".to_string()));
+        assert!(!commands.iter().any(|c| c.name == format!("{cli} build db")));
+    }
+
+    #[test]
+    fn test_scan_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let compose_content = r"
+services:
+  app:
+    image: alpine
+  debugger:
+    image: busybox
+    profiles: [debug, tools]
+  adminer:
+    image: adminer
+    profiles: [tools]
+";
+        fs::write(temp_dir.path().join("compose.yaml"), compose_content).unwrap();
+
+        let scanner = DockerScanner;
+        let commands = scanner.scan(temp_dir.path()).unwrap();
+        let cli = compose_cli();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+        assert!(names.contains(&format!("{cli} --profile debug up -d").as_str()));
+        assert!(names.contains(&format!("{cli} --profile tools up -d").as_str()));
+        assert!(names.contains(&format!("{cli} --profile tools down").as_str()));
+        assert_eq!(names.iter().filter(|n| n.contains("--profile")).count(), 4);
+    }
+
+    #[test]
+    fn test_scan_dockerfile_variants() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("My App");
+        fs::create_dir(&project).unwrap();
+        fs::write(project.join("Dockerfile"), "FROM alpine\n").unwrap();
+        fs::write(project.join("Dockerfile.dev"), "FROM alpine\n").unwrap();
+        fs::write(project.join("Dockerfile.prod"), "FROM alpine\n").unwrap();
+        fs::write(project.join("Dockerfile.dockerignore"), "target\n").unwrap();
+
+        let scanner = DockerScanner;
+        let commands = scanner.scan(&project).unwrap();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "docker build -f Dockerfile -t my-app .",
+                "docker build -f Dockerfile.dev -t my-app:dev .",
+                "docker build -f Dockerfile.prod -t my-app:prod .",
+            ]
+        );
+        assert!(commands[1].tags.contains(&"dev".to_string()));
+        assert_eq!(commands[1].working_dir.as_deref(), Some(project.as_path()));
+        assert!(commands[2].metadata[META_SOURCE_FILE].ends_with("Dockerfile.prod"));
+    }
+
+    #[test]
+    fn test_scan_dockerfile_build_args_in_description() {
+        let temp_dir = TempDir::new().unwrap();
+        let dockerfile = r#"
+ARG NODE_VERSION=20
+FROM node:${NODE_VERSION} AS build
+ARG APP_ENV
+arg BUILD_ID="local" NODE_VERSION
+FROM alpine
+"#;
+        fs::write(temp_dir.path().join("Dockerfile"), dockerfile).unwrap();
+
+        let scanner = DockerScanner;
+        let commands = scanner.scan(temp_dir.path()).unwrap();
+        assert_eq!(commands.len(), 1);
+        assert!(commands[0].description.as_ref().unwrap().ends_with(
+            "(--build-arg NODE_VERSION=20, --build-arg APP_ENV, --build-arg BUILD_ID=local)"
+        ));
+    }
+
+    #[test]
+    fn test_image_name_fallback() {
+        assert_eq!(image_name(Path::new("/nonexistent/path")), "app");
+    }
+
     #[test]
     fn test_parse_compose_with_build() {
         let yaml = r#"
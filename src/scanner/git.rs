@@ -1,6 +1,9 @@
 //! Git commands scanner.
 //!
 //! Adds common git operations to the command palette when in a git repository.
+//! With the `git` feature, the list follows the repository state: stash
+//! commands appear only when stashes exist, and `--continue`/`--abort`
+//! commands while a merge, rebase, cherry-pick, or revert is in progress.
 
 use std::path::Path;
 
@@ -16,16 +19,27 @@ impl Scanner for GitScanner {
         "git"
     }
 
+    fn rule(&self) -> &str {
+        "common git operations, added inside a git repository; `commit --amend` once \
+         there are commits, stash commands while stashes exist, `--continue`/`--abort` \
+         while a merge, rebase, cherry-pick, or revert is in progress, and a conflict \
+         listing while files are unmerged"
+    }
+
     fn scan(&self, path: &Path) -> anyhow::Result<Vec<Command>> {
         // Check if we're in a git repository
         #[cfg(feature = "git")]
-        {
+        let state = {
             use crate::git::GitRepository;
 
-            if GitRepository::discover(path).is_none() {
+            let Some(repo) = GitRepository::discover(path) else {
                 return Ok(Vec::new());
-            }
-        }
+            };
+            RepoState::read(&repo)
+        };
+
+        #[cfg(not(feature = "git"))]
+        let state = RepoState::default();
 
         #[cfg(not(feature = "git"))]
         {
@@ -46,7 +60,7 @@ impl Scanner for GitScanner {
         }
 
         // Build list of git commands
-        let commands = vec![
+        let mut commands = vec![
             // Status & Info
             git_command("git status", "git status", "Show the working tree status"),
             git_command("git log", "git log --oneline -20", "Show recent commit history"),
@@ -62,9 +76,6 @@ impl Scanner for GitScanner {
             git_command("git reset", "git reset", "Unstage all staged changes"),
             // Stash
             git_command("git stash", "git stash", "Stash current changes"),
-            git_command("git stash pop", "git stash pop", "Apply and remove latest stash"),
-            git_command("git stash list", "git stash list", "List all stashes"),
-            git_command("git stash drop", "git stash drop", "Remove latest stash"),
             // Branches
             git_command("git branch list", "git branch -a", "List all branches"),
             git_command(
@@ -74,7 +85,6 @@ impl Scanner for GitScanner {
             ),
             // Commit (basic - for now without interactive input)
             git_command("git commit", "git commit", "Create a commit (opens editor)"),
-            git_command("git commit amend", "git commit --amend", "Amend the last commit"),
             // Cleanup
             git_command("git clean", "git clean -fd", "Remove untracked files and directories"),
             git_command("git gc", "git gc", "Cleanup and optimize repository"),
@@ -82,10 +92,95 @@ impl Scanner for GitScanner {
             git_command("git remote", "git remote -v", "Show remote repositories"),
         ];
 
+        if state.has_commits {
+            commands.push(git_command(
+                "git commit amend",
+                "git commit --amend",
+                "Amend the last commit",
+            ));
+        }
+
+        if let Some(latest) = state.latest_stash {
+            commands.extend([
+                git_command(
+                    "git stash pop",
+                    "git stash pop",
+                    &format!("Apply and remove latest stash ({latest})"),
+                ),
+                git_command("git stash list", "git stash list", "List all stashes"),
+                git_command("git stash drop", "git stash drop", "Remove latest stash"),
+            ]);
+        }
+
+        if let Some(operation) = state.operation {
+            commands.extend([
+                git_command(
+                    &format!("git {operation} continue"),
+                    &format!("git {operation} --continue"),
+                    &format!("Continue the {operation} in progress"),
+                ),
+                git_command(
+                    &format!("git {operation} abort"),
+                    &format!("git {operation} --abort"),
+                    &format!("Abort the {operation} in progress"),
+                ),
+            ]);
+        }
+
+        if state.has_conflicts {
+            commands.push(git_command(
+                "git conflicts",
+                "git diff --name-only --diff-filter=U",
+                "List files with merge conflicts",
+            ));
+        }
+
         Ok(commands)
     }
 }
 
+/// The repository state that decides which commands are offered.
+#[derive(Debug)]
+struct RepoState {
+    /// Whether HEAD points at a commit
+    has_commits: bool,
+
+    /// Message of the most recent stash
+    latest_stash: Option<String>,
+
+    /// Operation in progress (`merge`, `rebase`, ...)
+    operation: Option<&'static str>,
+
+    /// Whether the index has unresolved conflicts
+    has_conflicts: bool,
+}
+
+impl Default for RepoState {
+    /// Without git support the state is unknown, so offer everything
+    /// except the in-progress commands.
+    fn default() -> Self {
+        Self {
+            has_commits: true,
+            latest_stash: Some("stash@{0}".to_string()),
+            operation: None,
+            has_conflicts: false,
+        }
+    }
+}
+
+#[cfg(feature = "git")]
+impl RepoState {
+    /// Read the state of a repository.
+    fn read(repo: &crate::git::GitRepository) -> Self {
+        Self {
+            has_commits: !repo.recent_commits(1).is_empty(),
+            latest_stash: repo.stashes().into_iter().next().map(|stash| stash.message),
+            operation: repo.operation_in_progress(),
+            has_conflicts: repo.has_conflicts(),
+        }
+    }
+}
+
 /// Create a git command with the given name, command, and description.
 fn git_command(name: &str, command: &str, description: &str) -> Command {
     Command::new(name, command)
@@ -128,4 +223,40 @@ mod tests {
         assert!(names.contains(&"git pull"));
         assert!(names.contains(&"git push"));
     }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_commands_follow_repo_state() {
+        use crate::git::{GitRepository, WorkingTree};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let raw = git2::Repository::init(temp.path()).unwrap();
+        let names = || -> Vec<String> {
+            GitScanner.scan(temp.path()).unwrap().into_iter().map(|c| c.name).collect()
+        };
+
+        // A fresh repository has nothing to amend, pop, or continue
+        let fresh = names();
+        assert!(fresh.contains(&"git status".to_string()));
+        assert!(!fresh.contains(&"git commit amend".to_string()));
+        assert!(!fresh.contains(&"git stash pop".to_string()));
+
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        std::fs::write(temp.path().join("file.txt"), "v1").unwrap();
+        let mut index = raw.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        let tree = raw.find_tree(index.write_tree().unwrap()).unwrap();
+        let head = raw.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[]).unwrap();
+
+        std::fs::write(temp.path().join("file.txt"), "v2").unwrap();
+        GitRepository::open(temp.path()).unwrap().stash("wip").unwrap();
+        std::fs::write(raw.path().join("MERGE_HEAD"), format!("{head}\n")).unwrap();
+
+        let names = names();
+        assert!(names.contains(&"git commit amend".to_string()));
+        assert!(names.contains(&"git stash pop".to_string()));
+        assert!(names.contains(&"git merge continue".to_string()));
+        assert!(names.contains(&"git merge abort".to_string()));
+        assert!(!names.contains(&"git conflicts".to_string()));
+    }
 }
@@ -1,10 +1,13 @@
 //! Go project scanner.
 //!
-//! Scans go.mod to discover Go project commands.
+//! Scans go.mod to discover Go project commands, plus `//go:generate`
+//! directives and packages under cmd/ and internal/.
 
 use std::path::Path;
 
-use super::Scanner;
+use walkdir::WalkDir;
+
+use super::{read_source, should_skip_dir, Scanner};
 use crate::core::{Command, CommandSource};
 
 /// Scanner for Go projects.
@@ -15,6 +18,15 @@ impl Scanner for GoScanner {
         "go"
     }
 
+    fn source_files(&self) -> &[&'static str] {
+        &["go.mod"]
+    }
+
+    fn rule(&self) -> &str {
+        "standard go commands, `go run` for each main package under cmd/, `go test` for each \
+         package under cmd/ and internal/, and `go generate` when a directive is present"
+    }
+
     fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
         let mut commands = Vec::new();
 
@@ -115,6 +127,28 @@ impl Scanner for GoScanner {
             }
         }
 
+        // Per-package tests for the top-level packages of a multi-package layout
+        for parent in ["cmd", "internal"] {
+            for package in top_level_packages(&dir.join(parent)) {
+                let cmd = format!("go test ./{parent}/{package}/...");
+                commands.push(
+                    Command::new(&cmd, &cmd)
+                        .with_description(format!("Test {module_name}/{parent}/{package}"))
+                        .with_source(source.clone())
+                        .with_tags(vec!["go".to_string(), "test".to_string(), package]),
+                );
+            }
+        }
+
+        if has_go_generate(dir) {
+            commands.push(
+                Command::new("go generate ./...", "go generate ./...")
+                    .with_description("Run //go:generate directives")
+                    .with_source(source.clone())
+                    .with_tags(vec!["go".to_string(), "generate".to_string()]),
+            );
+        }
+
         // Check for main.go in project root (only add "go run ." if it exists)
         // We already added "go run ." above, but let's check if main.go exists
         // to potentially add a more specific command
@@ -134,7 +168,7 @@ impl Scanner for GoScanner {
 
 /// Parse go.mod file to extract the module name.
 fn parse_go_mod(path: &Path) -> anyhow::Result<String> {
-    let content = std::fs::read_to_string(path)?;
+    let content = read_source(path)?;
 
     // go.mod format is simple text:
     // module github.com/user/project
@@ -169,6 +203,54 @@ fn has_go_files(dir: &Path) -> bool {
     false
 }
 
+/// Check whether a directory is ignored by the go tool.
+///
+/// `vendor/` and `testdata/` hold no packages of the module, and the go
+/// tool skips directories starting with `.` or `_`.
+fn is_ignored_dir(name: &str) -> bool {
+    matches!(name, "vendor" | "testdata") || name.starts_with(['.', '_']) || should_skip_dir(name)
+}
+
+/// Walk the Go source files below a directory, skipping ignored directories.
+fn go_files(dir: &Path) -> impl Iterator<Item = walkdir::DirEntry> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || !is_ignored_dir(&entry.file_name().to_string_lossy())
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.file_type().is_file() && entry.path().extension().is_some_and(|ext| ext == "go")
+        })
+}
+
+/// List the subdirectories of `dir` that contain Go packages, sorted by name.
+fn top_level_packages(dir: &Path) -> Vec<String> {
+    let mut packages: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| !is_ignored_dir(name))
+                .filter(|name| go_files(&dir.join(name)).next().is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+    packages.sort();
+    packages
+}
+
+/// Check whether any Go source file has a `//go:generate` directive.
+fn has_go_generate(dir: &Path) -> bool {
+    go_files(dir).any(|entry| {
+        std::fs::read_to_string(entry.path())
+            .is_ok_and(|content| content.lines().any(|line| line.starts_with("//go:generate ")))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -353,6 +435,68 @@ require (
         }
     }
 
+    #[test]
+    fn test_single_package_module_has_no_extras() {
+        let scanner = GoScanner;
+        let temp_dir = TempDir::new().unwrap();
+
+        fs::write(temp_dir.path().join("go.mod"), "module example.com/tool\n\ngo 1.22\n").unwrap();
+        fs::write(temp_dir.path().join("main.go"), "package main\nfunc main() {}").unwrap();
+
+        let commands = scanner.scan(temp_dir.path()).unwrap();
+        let command_names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert!(command_names.contains(&"go test ./..."));
+        assert!(command_names.contains(&"go run ."));
+        assert!(!command_names.contains(&"go generate ./..."));
+        assert!(!command_names.iter().any(|n| n.starts_with("go test ./internal")));
+    }
+
+    #[test]
+    fn test_scan_packages_and_generate() {
+        let scanner = GoScanner;
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        fs::write(root.join("go.mod"), "module github.com/example/svc\n\ngo 1.22\n").unwrap();
+
+        let auth = root.join("internal/auth");
+        fs::create_dir_all(auth.join("testdata")).unwrap();
+        fs::write(auth.join("auth.go"), "package auth\n").unwrap();
+        // Directives in testdata are not part of the module
+        fs::write(auth.join("testdata/fixture.go"), "//go:generate stringer -type=X\n").unwrap();
+
+        let store = root.join("internal/store/sql");
+        fs::create_dir_all(&store).unwrap();
+        fs::write(store.join("sql.go"), "package sql\n\n//go:generate sqlc generate\n").unwrap();
+
+        fs::create_dir_all(root.join("internal/docs")).unwrap();
+        fs::create_dir_all(root.join("vendor/github.com/dep")).unwrap();
+        fs::write(root.join("vendor/github.com/dep/dep.go"), "package dep\n").unwrap();
+
+        let commands = scanner.scan(root).unwrap();
+        let command_names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert!(command_names.contains(&"go test ./internal/auth/..."));
+        assert!(command_names.contains(&"go test ./internal/store/..."));
+        assert!(!command_names.contains(&"go test ./internal/docs/..."));
+        assert!(command_names.contains(&"go generate ./..."));
+
+        let auth_test = commands.iter().find(|c| c.name == "go test ./internal/auth/...").unwrap();
+        assert_eq!(
+            auth_test.description.as_deref(),
+            Some("Test github.com/example/svc/internal/auth")
+        );
+    }
+
+    #[test]
+    fn test_generate_directive_only_in_skipped_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let vendored = temp_dir.path().join("vendor/dep");
+        fs::create_dir_all(&vendored).unwrap();
+        fs::write(vendored.join("dep.go"), "//go:generate mockgen\n").unwrap();
+
+        assert!(!has_go_generate(temp_dir.path()));
+    }
+
     #[test]
     fn test_cmd_directory_without_go_files_skipped() {
         let scanner = GoScanner;
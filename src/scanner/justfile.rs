@@ -0,0 +1,357 @@
+//! Justfile scanner.
+//!
+//! Scans justfiles to discover recipes for the `just` command runner.
+//! See: https://just.systems
+
+use std::path::Path;
+
+use super::{read_source, Scanner};
+use crate::core::{Command, CommandSource};
+
+/// Justfile names, in lookup order.
+const JUSTFILE_NAMES: &[&str] = &["justfile", "Justfile", ".justfile"];
+
+/// Scanner for justfile recipes.
+pub struct JustfileScanner;
+
+impl Scanner for JustfileScanner {
+    fn name(&self) -> &str {
+        "just"
+    }
+
+    fn source_files(&self) -> &[&'static str] {
+        JUSTFILE_NAMES
+    }
+
+    fn rule(&self) -> &str {
+        "each recipe except private ones, described by the comment above it"
+    }
+
+    fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
+        let Some(justfile_path) = JUSTFILE_NAMES.iter().map(|n| dir.join(n)).find(|p| p.exists())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let content = read_source(&justfile_path)?;
+        let source = CommandSource::Justfile(dir.to_path_buf());
+        let mut commands = Vec::new();
+
+        for recipe in parse_justfile(&content) {
+            let mut name = format!("just {}", recipe.name);
+            for param in &recipe.params {
+                name.push(' ');
+                name.push_str(&param.hint());
+            }
+
+            let mut cmd = Command::new(name, format!("just {}", recipe.name))
+                .with_source(source.clone())
+                .with_working_dir(dir)
+                .with_tag("just");
+            for alias in &recipe.aliases {
+                cmd = cmd.with_tag(format!("alias:{alias}"));
+            }
+            if let Some(description) = recipe.description {
+                cmd = cmd.with_description(description);
+            }
+            commands.push(cmd);
+        }
+
+        commands.push(
+            Command::new("just --list", "just --list")
+                .with_description("List all available recipes")
+                .with_source(source)
+                .with_working_dir(dir)
+                .with_tag("just"),
+        );
+
+        Ok(commands)
+    }
+}
+
+/// A recipe parsed from a justfile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Recipe {
+    /// Recipe name
+    name: String,
+
+    /// Recipe parameters, in order
+    params: Vec<Param>,
+
+    /// Description from the comment above the recipe
+    description: Option<String>,
+
+    /// Aliases declared with `alias name := recipe`
+    aliases: Vec<String>,
+}
+
+/// A recipe parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Param {
+    /// Parameter name
+    name: String,
+
+    /// Whether the parameter has a default value
+    has_default: bool,
+
+    /// `+` (one or more) or `*` (zero or more) variadic marker
+    variadic: Option<char>,
+}
+
+impl Param {
+    /// Parse a parameter like `env`, `env='prod'`, `+files`, or `$token`.
+    fn parse(token: &str) -> Self {
+        let (token, variadic) = match token.chars().next() {
+            Some(marker @ ('+' | '*')) => (&token[1..], Some(marker)),
+            _ => (token, None),
+        };
+        let (name, has_default) = match token.split_once('=') {
+            Some((name, _)) => (name, true),
+            None => (token, false),
+        };
+
+        Self { name: name.trim_start_matches('$').to_string(), has_default, variadic }
+    }
+
+    /// Usage hint: `<env>` when required, `[env]` when optional.
+    fn hint(&self) -> String {
+        match (self.variadic, self.has_default) {
+            (Some('+'), false) => format!("<{}>...", self.name),
+            (Some(_), _) => format!("[{}...]", self.name),
+            (None, false) => format!("<{}>", self.name),
+            (None, true) => format!("[{}]", self.name),
+        }
+    }
+}
+
+/// Parse justfile content into public recipes.
+///
+/// Recipes starting with `_` or marked `[private]` are skipped.
+fn parse_justfile(content: &str) -> Vec<Recipe> {
+    let mut recipes: Vec<Recipe> = Vec::new();
+    let mut aliases: Vec<(String, String)> = Vec::new();
+
+    let mut comment: Option<String> = None;
+    let mut private = false;
+
+    for line in content.lines() {
+        // Recipe bodies are indented
+        if line.starts_with([' ', '\t']) || line.trim().is_empty() {
+            comment = None;
+            private = false;
+            continue;
+        }
+
+        let line = line.trim_end();
+
+        if let Some(text) = line.strip_prefix('#') {
+            if !text.starts_with('!') {
+                comment = Some(text.trim().to_string());
+            }
+            continue;
+        }
+
+        // Attributes like [private] or [group('ci')] sit between comment and recipe
+        if line.starts_with('[') {
+            private |= line.contains("private");
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("alias ") {
+            if let Some((alias, target)) = rest.split_once(":=") {
+                aliases.push((alias.trim().to_string(), target.trim().to_string()));
+            }
+            comment = None;
+            continue;
+        }
+
+        if let Some(recipe) = parse_recipe_header(line) {
+            if !private && !recipe.0.starts_with('_') {
+                recipes.push(Recipe {
+                    name: recipe.0,
+                    params: recipe.1,
+                    description: comment.take().filter(|c| !c.is_empty()),
+                    aliases: Vec::new(),
+                });
+            }
+        }
+
+        comment = None;
+        private = false;
+    }
+
+    for (alias, target) in aliases {
+        if let Some(recipe) = recipes.iter_mut().find(|r| r.name == target) {
+            recipe.aliases.push(alias);
+        }
+    }
+
+    recipes
+}
+
+/// Parse a recipe header like `deploy env='prod' +targets: build`.
+///
+/// Returns `None` for settings, assignments, and other non-recipe lines.
+fn parse_recipe_header(line: &str) -> Option<(String, Vec<Param>)> {
+    if ["set ", "export ", "import ", "import? ", "mod ", "mod? "]
+        .iter()
+        .any(|keyword| line.starts_with(keyword))
+    {
+        return None;
+    }
+
+    // Find the first `:` outside quotes; `:=` is an assignment
+    let mut quote = None;
+    let mut colon = None;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, ':') => {
+                colon = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let colon = colon?;
+    if line[colon + 1..].starts_with('=') {
+        return None;
+    }
+
+    let mut tokens = split_params(&line[..colon]).into_iter();
+    let name = tokens.next()?;
+    let name = name.trim_start_matches('@');
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        return None;
+    }
+
+    Some((name.to_string(), tokens.map(|token| Param::parse(&token)).collect()))
+}
+
+/// Split a recipe header on whitespace outside quotes and parentheses.
+fn split_params(header: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut depth = 0usize;
+
+    for c in header.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, c) if c.is_whitespace() && depth == 0 => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    // `name = 'value'` is written with spaces around `=` in some justfiles
+    let mut merged: Vec<String> = Vec::new();
+    for token in tokens {
+        match merged.last_mut() {
+            Some(last) if last.ends_with('=') || token.starts_with('=') => last.push_str(&token),
+            _ => merged.push(token),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JUSTFILE: &str = r#"
+set dotenv-load
+
+version := "1.0"
+
+alias b := build
+
+# Build the project
+build:
+    cargo build --release
+
+# Deploy to an environment
+deploy env target='web' +flags: build
+    ./deploy.sh {{env}} {{target}} {{flags}}
+
+_setup:
+    ./scripts/setup.sh
+
+# Internal helper
+[private]
+helper:
+    echo helper
+
+@lint:
+    cargo clippy
+"#;
+
+    #[test]
+    fn test_parse_recipes() {
+        let recipes = parse_justfile(JUSTFILE);
+        let names: Vec<_> = recipes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "deploy", "lint"]);
+
+        assert_eq!(recipes[0].description.as_deref(), Some("Build the project"));
+        assert_eq!(recipes[0].aliases, vec!["b"]);
+        assert_eq!(recipes[2].description, None);
+    }
+
+    #[test]
+    fn test_parameter_hints() {
+        let recipes = parse_justfile(JUSTFILE);
+        let hints: Vec<_> = recipes[1].params.iter().map(Param::hint).collect();
+        assert_eq!(hints, vec!["<env>", "[target]", "<flags>..."]);
+        assert_eq!(Param::parse("*args").hint(), "[args...]");
+        assert_eq!(Param::parse("$token").hint(), "<token>");
+    }
+
+    #[test]
+    fn test_scan_justfile() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("justfile"), JUSTFILE).unwrap();
+
+        let commands = JustfileScanner.scan(temp.path()).unwrap();
+        let names: Vec<_> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["just build", "just deploy <env> [target] <flags>...", "just lint", "just --list"]
+        );
+
+        assert_eq!(commands[1].command, "just deploy");
+        assert_eq!(commands[1].description.as_deref(), Some("Deploy to an environment"));
+        assert!(commands[0].tags.contains(&"alias:b".to_string()));
+        assert!(!names.iter().any(|n| n.contains("setup") || n.contains("helper")));
+    }
+
+    #[test]
+    fn test_assignments_are_not_recipes() {
+        assert_eq!(parse_recipe_header("version := \"1.0\""), None);
+        assert_eq!(parse_recipe_header("set shell := [\"bash\", \"-c\"]"), None);
+        assert_eq!(
+            parse_recipe_header("serve port=\"8080\":").map(|(name, params)| (name, params.len())),
+            Some(("serve".to_string(), 1))
+        );
+    }
+
+    #[test]
+    fn test_no_justfile() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let commands = JustfileScanner.scan(temp.path()).unwrap();
+        assert_eq!(commands, Vec::<Command>::new());
+    }
+}
@@ -0,0 +1,157 @@
+//! Lockfile drift detection.
+//!
+//! Flags projects whose manifest was modified after its lockfile, which
+//! usually means dependencies changed without the lockfile being refreshed.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Manifest changes within this window of the lockfile are not reported.
+///
+/// Checkouts and package managers write both files at nearly the same
+/// time, so tiny differences are not meaningful.
+pub const DRIFT_TOLERANCE: Duration = Duration::from_secs(2);
+
+/// A manifest/lockfile pair for one ecosystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockfileKind {
+    /// Ecosystem name shown in warnings.
+    pub ecosystem: &'static str,
+
+    /// Manifest file name.
+    pub manifest: &'static str,
+
+    /// Lockfile file name.
+    pub lockfile: &'static str,
+
+    /// Command that brings the lockfile back in sync.
+    pub fix: &'static str,
+}
+
+/// Supported manifest/lockfile pairs.
+pub const LOCKFILE_KINDS: &[LockfileKind] = &[
+    LockfileKind {
+        ecosystem: "npm",
+        manifest: "package.json",
+        lockfile: "package-lock.json",
+        fix: "npm install",
+    },
+    LockfileKind {
+        ecosystem: "cargo",
+        manifest: "Cargo.toml",
+        lockfile: "Cargo.lock",
+        fix: "cargo update --workspace",
+    },
+    LockfileKind {
+        ecosystem: "poetry",
+        manifest: "pyproject.toml",
+        lockfile: "poetry.lock",
+        fix: "poetry lock",
+    },
+    LockfileKind { ecosystem: "go", manifest: "go.mod", lockfile: "go.sum", fix: "go mod tidy" },
+];
+
+/// A lockfile that is older than its manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockfileDrift {
+    /// The ecosystem the files belong to.
+    pub kind: LockfileKind,
+
+    /// Path to the manifest.
+    pub manifest: PathBuf,
+
+    /// How much newer the manifest is.
+    pub newer_by: Duration,
+}
+
+impl LockfileDrift {
+    /// Human-readable warning with the suggested fix.
+    pub fn message(&self) -> String {
+        format!(
+            "{} is newer than {} ({}); run `{}` to update it",
+            self.kind.manifest, self.kind.lockfile, self.kind.ecosystem, self.kind.fix
+        )
+    }
+}
+
+/// Decide whether a lockfile is out of date given both modification times.
+///
+/// Returns how much newer the manifest is, or `None` if it is not newer
+/// by more than [`DRIFT_TOLERANCE`].
+pub fn drift_between(manifest: SystemTime, lockfile: SystemTime) -> Option<Duration> {
+    manifest.duration_since(lockfile).ok().filter(|newer_by| *newer_by > DRIFT_TOLERANCE)
+}
+
+/// Check every supported ecosystem in `dir` for lockfile drift.
+///
+/// Projects without a lockfile are skipped; not every project commits one.
+pub fn check_lockfiles(dir: &Path) -> Vec<LockfileDrift> {
+    LOCKFILE_KINDS
+        .iter()
+        .filter_map(|kind| {
+            let manifest = dir.join(kind.manifest);
+            let manifest_time = modified(&manifest)?;
+            let lockfile_time = modified(&dir.join(kind.lockfile))?;
+            let newer_by = drift_between(manifest_time, lockfile_time)?;
+            Some(LockfileDrift { kind: *kind, manifest, newer_by })
+        })
+        .collect()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_manifest_newer_is_drift() {
+        assert_eq!(drift_between(at(1_000), at(900)), Some(Duration::from_secs(100)));
+    }
+
+    #[test]
+    fn test_lockfile_newer_is_not_drift() {
+        assert_eq!(drift_between(at(900), at(1_000)), None);
+        assert_eq!(drift_between(at(1_000), at(1_000)), None);
+    }
+
+    #[test]
+    fn test_small_difference_is_tolerated() {
+        assert_eq!(drift_between(at(1_001), at(1_000)), None);
+        assert_eq!(drift_between(at(1_002), at(1_000)), None);
+        assert!(drift_between(at(1_003), at(1_000)).is_some());
+    }
+
+    #[test]
+    fn test_check_lockfiles() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let set_mtime = |name: &str, secs: u64| {
+            let path = temp.path().join(name);
+            std::fs::write(&path, "").unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(at(secs))
+                .unwrap();
+        };
+
+        set_mtime("Cargo.toml", 2_000);
+        set_mtime("Cargo.lock", 1_000);
+        set_mtime("package.json", 1_000);
+        set_mtime("package-lock.json", 2_000);
+        set_mtime("go.mod", 2_000);
+
+        let drifts = check_lockfiles(temp.path());
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].kind.ecosystem, "cargo");
+        assert_eq!(drifts[0].newer_by, Duration::from_secs(1_000));
+        assert!(drifts[0].message().contains("cargo update --workspace"));
+    }
+}
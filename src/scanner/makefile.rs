@@ -6,9 +6,12 @@ use std::path::Path;
 
 use regex::Regex;
 
-use super::Scanner;
+use super::{read_source, Scanner};
 use crate::core::Command;
 
+/// Makefile names, in the order make looks for them.
+const MAKEFILE_NAMES: &[&str] = &["GNUmakefile", "Makefile", "makefile"];
+
 /// Scanner for Makefile targets.
 pub struct MakefileScanner;
 
@@ -17,28 +20,43 @@ impl Scanner for MakefileScanner {
         "make"
     }
 
+    fn source_files(&self) -> &[&'static str] {
+        MAKEFILE_NAMES
+    }
+
+    fn rule(&self) -> &str {
+        "each `target:` line except % pattern rules, .PHONY targets first, \
+         described by a trailing `## comment`"
+    }
+
     fn scan(&self, path: &Path) -> anyhow::Result<Vec<Command>> {
         let makefile_path = match find_makefile(path) {
             Ok(p) => p,
             Err(_) => return Ok(Vec::new()),
         };
-        let content = std::fs::read_to_string(&makefile_path)?;
+        let content = read_source(&makefile_path)?;
 
-        let targets = parse_makefile_targets(&content);
+        let targets = parse_makefile(&content);
         let mut commands = Vec::new();
 
         for target in targets {
             // Skip internal targets (starting with .)
-            if target.starts_with('.') {
+            if target.name.starts_with('.') {
                 continue;
             }
 
             // Skip special targets
-            if is_special_target(&target) {
+            if is_special_target(&target.name) {
                 continue;
             }
 
-            let cmd = Command::from_make_target(&target, Some(path.to_path_buf()));
+            let mut cmd = Command::from_make_target(&target.name, Some(path.to_path_buf()));
+            if target.phony {
+                cmd = cmd.with_tag(PHONY_TAG);
+            }
+            if let Some(description) = target.description {
+                cmd = cmd.with_description(description);
+            }
             commands.push(cmd);
         }
 
@@ -48,7 +66,7 @@ impl Scanner for MakefileScanner {
 
 /// Find the Makefile in the given directory.
 fn find_makefile(path: &Path) -> anyhow::Result<std::path::PathBuf> {
-    for name in &["GNUmakefile", "Makefile", "makefile"] {
+    for name in MAKEFILE_NAMES {
         let makefile_path = path.join(name);
         if makefile_path.exists() {
             return Ok(makefile_path);
@@ -58,13 +76,36 @@ fn find_makefile(path: &Path) -> anyhow::Result<std::path::PathBuf> {
     anyhow::bail!("No Makefile found in {:?}", path)
 }
 
+/// Tag for targets declared `.PHONY`, which are usually the user-facing ones.
+const PHONY_TAG: &str = "phony";
+
+/// A target parsed from a Makefile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MakeTarget {
+    /// Target name
+    name: String,
+
+    /// Whether the target is declared `.PHONY`
+    phony: bool,
+
+    /// Description from a `target: ## description` comment
+    description: Option<String>,
+}
+
 /// Parse Makefile content and extract target names.
 fn parse_makefile_targets(content: &str) -> Vec<String> {
-    let mut targets = Vec::new();
+    parse_makefile(content).into_iter().map(|target| target.name).collect()
+}
 
-    // Match lines like "target: deps" or "target:"
-    // But not variable assignments like "VAR = value"
-    let target_re = Regex::new(r"^([a-zA-Z_][a-zA-Z0-9_.-]*):\s*").unwrap();
+/// Parse Makefile content into targets, phony targets first.
+///
+/// Pattern rules (`%.o: %.c`) are skipped, since they cannot be run by name.
+fn parse_makefile(content: &str) -> Vec<MakeTarget> {
+    let mut targets: Vec<MakeTarget> = Vec::new();
+
+    // Match lines like "target: deps" or "target:", capturing any "## description"
+    // But not variable assignments like "VAR = value" or "VAR := value"
+    let target_re = Regex::new(r"^([a-zA-Z_%][a-zA-Z0-9_.%-]*):([^=].*)?$").unwrap();
 
     // Track .PHONY targets for prioritization
     let mut phony_targets: Vec<String> = Vec::new();
@@ -78,34 +119,43 @@ fn parse_makefile_targets(content: &str) -> Vec<String> {
         }
 
         // Check for .PHONY declaration
-        if line.starts_with(".PHONY:") {
-            let phony_str = line.strip_prefix(".PHONY:").unwrap_or("").trim();
+        if let Some(phony_str) = line.strip_prefix(".PHONY:") {
+            let phony_str = phony_str.split('#').next().unwrap_or_default();
             phony_targets.extend(phony_str.split_whitespace().map(String::from));
             continue;
         }
 
         // Match regular targets
         if let Some(captures) = target_re.captures(line) {
-            let target = captures.get(1).unwrap().as_str().to_string();
+            let name = captures.get(1).unwrap().as_str().to_string();
+            if name.contains('%') {
+                continue;
+            }
 
-            // Avoid duplicates
-            if !targets.contains(&target) {
-                targets.push(target);
+            let description = captures
+                .get(2)
+                .and_then(|rest| rest.as_str().split_once("##"))
+                .map(|(_, description)| description.trim().to_string())
+                .filter(|description| !description.is_empty());
+
+            match targets.iter_mut().find(|target| target.name == name) {
+                // Avoid duplicates, but keep a description from any definition
+                Some(existing) => {
+                    if existing.description.is_none() {
+                        existing.description = description;
+                    }
+                }
+                None => targets.push(MakeTarget { name, phony: false, description }),
             }
         }
     }
 
+    for target in &mut targets {
+        target.phony = phony_targets.contains(&target.name);
+    }
+
     // Sort: phony targets first (they're typically the user-facing ones)
-    targets.sort_by(|a, b| {
-        let a_phony = phony_targets.contains(a);
-        let b_phony = phony_targets.contains(b);
-
-        match (a_phony, b_phony) {
-            (true, false) => std::cmp::Ordering::Less,
-            (false, true) => std::cmp::Ordering::Greater,
-            _ => a.cmp(b),
-        }
-    });
+    targets.sort_by(|a, b| b.phony.cmp(&a.phony).then_with(|| a.name.cmp(&b.name)));
 
     targets
 }
@@ -250,6 +300,54 @@ helper:
         assert!(test_pos < internal_pos);
     }
 
+    #[test]
+    fn test_phony_help_comments_and_pattern_rules() {
+        let content = r"
+.PHONY: build test
+
+build: ## Build the release binary
+	cargo build --release
+
+test: build ## Run all tests
+	cargo test
+
+%.o: %.c
+	$(CC) -c $< -o $@
+
+app.o: app.c
+	$(CC) -c app.c
+
+VERSION := 1.0
+";
+
+        let targets = parse_makefile(content);
+        let names: Vec<_> = targets.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test", "app.o"]);
+
+        assert!(targets[0].phony);
+        assert_eq!(targets[0].description.as_deref(), Some("Build the release binary"));
+        assert_eq!(targets[1].description.as_deref(), Some("Run all tests"));
+        assert!(!targets[2].phony);
+        assert_eq!(targets[2].description, None);
+    }
+
+    #[test]
+    fn test_scan_tags_phony_targets() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("Makefile"),
+            ".PHONY: lint\nlint: ## Run linters\n\tcargo clippy\nout.txt:\n\ttouch out.txt\n",
+        )
+        .unwrap();
+
+        let commands = MakefileScanner.scan(temp.path()).unwrap();
+        assert_eq!(commands[0].name, "make lint");
+        assert!(commands[0].tags.contains(&PHONY_TAG.to_string()));
+        assert_eq!(commands[0].description.as_deref(), Some("Run linters"));
+        assert_eq!(commands[1].name, "make out.txt");
+        assert!(!commands[1].tags.contains(&PHONY_TAG.to_string()));
+    }
+
     #[test]
     fn test_is_special_target() {
         assert!(is_special_target("FORCE"));
@@ -72,6 +72,10 @@ impl Scanner for MCPScanner {
         "mcp"
     }
 
+    fn rule(&self) -> &str {
+        "each tool exposed by a configured MCP server"
+    }
+
     fn scan(&self, _path: &Path) -> anyhow::Result<Vec<Command>> {
         let mut commands = Vec::new();
 
@@ -0,0 +1,128 @@
+//! Meson scanner.
+//!
+//! Scans meson.build to discover setup, compile, and test commands.
+//! See: https://mesonbuild.com
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{read_source, Scanner};
+use crate::core::{Command, CommandSource};
+
+/// Build directory used by the setup and compile commands.
+const BUILD_DIR: &str = "builddir";
+
+/// `executable('name', ...)` and the library target calls.
+static TARGET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^[^#\n]*?\b(?:executable|library|shared_library|static_library|both_libraries)\s*\(\s*'([^']+)'",
+    )
+    .unwrap()
+});
+
+/// Scanner for Meson projects.
+pub struct MesonScanner;
+
+impl Scanner for MesonScanner {
+    fn name(&self) -> &str {
+        "meson"
+    }
+
+    fn source_files(&self) -> &[&'static str] {
+        &["meson.build"]
+    }
+
+    fn rule(&self) -> &str {
+        "setup/compile/test commands, plus a compile for each executable or library target"
+    }
+
+    fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
+        let build_path = dir.join("meson.build");
+        if !build_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = read_source(&build_path)?;
+        let source = CommandSource::Meson(build_path.clone());
+        let meson_command = |cmd: String, description: String, tags: &[&str]| {
+            let mut all_tags = vec!["meson".to_string()];
+            all_tags.extend(tags.iter().map(ToString::to_string));
+            Command::new(&cmd, &cmd)
+                .with_description(description)
+                .with_source(source.clone())
+                .with_working_dir(dir)
+                .with_tags(all_tags)
+        };
+
+        let mut commands = vec![
+            meson_command(
+                format!("meson setup {BUILD_DIR}"),
+                format!("Configure the project into {BUILD_DIR}/"),
+                &["configure"],
+            ),
+            meson_command(
+                format!("meson compile -C {BUILD_DIR}"),
+                "Build all targets".to_string(),
+                &["build"],
+            ),
+            meson_command(format!("meson test -C {BUILD_DIR}"), "Run tests".to_string(), &["test"]),
+        ];
+
+        for name in parse_targets(&content) {
+            commands.push(meson_command(
+                format!("meson compile -C {BUILD_DIR} {name}"),
+                format!("Build the {name} target"),
+                &["build", &name],
+            ));
+        }
+
+        Ok(commands)
+    }
+}
+
+/// Parse target names from `executable('name', ...)` and library calls.
+fn parse_targets(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for caps in TARGET_RE.captures_iter(content) {
+        if !names.contains(&caps[1].to_string()) {
+            names.push(caps[1].to_string());
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_meson_project() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("meson.build"),
+            r"
+project('demo', 'c')
+lib = static_library('demo', 'src/demo.c')
+exe = executable('demo-cli', 'src/main.c', link_with : lib)
+# executable('old', 'old.c')
+test('basic', exe)
+",
+        )
+        .unwrap();
+
+        let commands = MesonScanner.scan(temp.path()).unwrap();
+        let names: Vec<_> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "meson setup builddir",
+                "meson compile -C builddir",
+                "meson test -C builddir",
+                "meson compile -C builddir demo",
+                "meson compile -C builddir demo-cli",
+            ]
+        );
+    }
+}
@@ -5,34 +5,57 @@
 
 mod builtin;
 mod cargo;
+mod cmake;
+mod diff;
 mod docker;
 mod git;
 mod go_lang;
+mod justfile;
+mod lockfile;
 mod makefile;
 mod mcp;
+mod meson;
 mod npm;
 mod nx;
 mod python;
 mod taskfile;
+mod tox;
 mod turbo;
+mod vscode;
 
 pub use builtin::BuiltinScanner;
 pub use cargo::CargoScanner;
+pub use cmake::CMakeScanner;
+pub use diff::{diff_commands, relativize, sort_commands, CommandChange, ScanDiff};
 pub use docker::DockerScanner;
 pub use git::GitScanner;
 pub use go_lang::GoScanner;
+pub use justfile::JustfileScanner;
+pub use lockfile::{check_lockfiles, LockfileDrift, LockfileKind, LOCKFILE_KINDS};
 pub use makefile::MakefileScanner;
 pub use mcp::MCPScanner;
+pub use meson::MesonScanner;
 pub use npm::NpmScanner;
 pub use nx::NxScanner;
 pub use python::PythonScanner;
 pub use taskfile::TaskfileScanner;
+pub use tox::ToxScanner;
 pub use turbo::TurboScanner;
+pub use vscode::VsCodeScanner;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::core::Command;
 
+/// Metadata key for the scanner that discovered a command.
+pub const META_SCANNER: &str = "scanner";
+
+/// Metadata key for the file a command was parsed from.
+pub const META_SOURCE_FILE: &str = "source_file";
+
+/// Metadata key for the rule that turned the file into a command.
+pub const META_RULE: &str = "rule";
+
 /// Trait for project scanners.
 pub trait Scanner: Send + Sync {
     /// Get the name of this scanner.
@@ -40,6 +63,76 @@ pub trait Scanner: Send + Sync {
 
     /// Scan the directory and return discovered commands.
     fn scan(&self, path: &Path) -> anyhow::Result<Vec<Command>>;
+
+    /// Files this scanner reads, relative to the scanned directory.
+    ///
+    /// Listed in lookup order; the first one present is recorded as the
+    /// source of each discovered command.
+    fn source_files(&self) -> &[&'static str] {
+        &[]
+    }
+
+    /// Describe how commands are derived from the source files.
+    fn rule(&self) -> &str {
+        "commands found by this scanner"
+    }
+}
+
+/// Why a command was discovered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    /// Name of the scanner that produced the command.
+    pub scanner: String,
+
+    /// File the command was parsed from, if any.
+    pub source_file: Option<PathBuf>,
+
+    /// Parse rule that produced the command.
+    pub rule: String,
+}
+
+impl Provenance {
+    /// Read the provenance recorded on a command during scanning.
+    pub fn of(command: &Command) -> Option<Self> {
+        let scanner = command.metadata.get(META_SCANNER)?.clone();
+        Some(Self {
+            scanner,
+            source_file: command.metadata.get(META_SOURCE_FILE).map(PathBuf::from),
+            rule: command.metadata.get(META_RULE).cloned().unwrap_or_default(),
+        })
+    }
+}
+
+impl std::fmt::Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "scanner: {}", self.scanner)?;
+        if let Some(ref file) = self.source_file {
+            write!(f, ", file: {}", file.display())?;
+        }
+        write!(f, ", rule: {}", self.rule)
+    }
+}
+
+/// Bounds on how much work [`ProjectScanner::scan_recursive`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanLimits {
+    /// Maximum subdirectories descended into per directory
+    pub max_breadth: usize,
+
+    /// Maximum subdirectories descended into in total
+    pub max_dirs: usize,
+}
+
+impl Default for ScanLimits {
+    fn default() -> Self {
+        Self { max_breadth: 100, max_dirs: 1000 }
+    }
+}
+
+impl From<&crate::core::ScannerConfig> for ScanLimits {
+    fn from(config: &crate::core::ScannerConfig) -> Self {
+        Self { max_breadth: config.max_breadth, max_dirs: config.max_dirs }
+    }
 }
 
 /// Main project scanner that aggregates all individual scanners.
@@ -49,6 +142,9 @@ pub struct ProjectScanner {
 
     /// Enabled scanners
     scanners: Vec<Box<dyn Scanner>>,
+
+    /// Limits for recursive scans
+    limits: ScanLimits,
 }
 
 impl ProjectScanner {
@@ -62,13 +158,25 @@ impl ProjectScanner {
             Box::new(TurboScanner),
             Box::new(CargoScanner),
             Box::new(TaskfileScanner),
+            Box::new(JustfileScanner),
             Box::new(DockerScanner),
             Box::new(GoScanner),
+            Box::new(CMakeScanner),
+            Box::new(MesonScanner),
             Box::new(PythonScanner),
+            Box::new(ToxScanner),
+            Box::new(VsCodeScanner),
             Box::new(GitScanner),
         ];
 
-        Self { root: root.to_path_buf(), scanners }
+        Self { root: root.to_path_buf(), scanners, limits: ScanLimits::default() }
+    }
+
+    /// Set the limits for recursive scans.
+    #[must_use]
+    pub fn with_limits(mut self, limits: ScanLimits) -> Self {
+        self.limits = limits;
+        self
     }
 
     /// Scan the project and return all discovered commands.
@@ -77,8 +185,9 @@ impl ProjectScanner {
 
         for scanner in &self.scanners {
             match scanner.scan(&self.root) {
-                Ok(commands) => {
+                Ok(mut commands) => {
                     if !commands.is_empty() {
+                        record_provenance(&mut commands, scanner.as_ref(), &self.root);
                         tracing::debug!(
                             scanner = scanner.name(),
                             count = commands.len(),
@@ -104,39 +213,162 @@ impl ProjectScanner {
     }
 
     /// Scan with recursive workspace detection.
+    ///
+    /// Subdirectories are visited in name order and bounded by the
+    /// scanner's [`ScanLimits`]; anything over the limits is skipped.
+    /// A command found twice in one directory is kept once, and subdirectory
+    /// commands that repeat a root command line are tagged with their
+    /// relative directory.
     pub fn scan_recursive(&self, max_depth: usize) -> anyhow::Result<Vec<Command>> {
+        let mut budget = self.limits.max_dirs;
+        let mut commands = self.scan_within(max_depth, &mut budget)?;
+
+        // Workspace packages already found from the root are visited again;
+        // keep only the workspace-named commands for them.
+        let in_workspace: std::collections::HashSet<(String, Option<PathBuf>)> = commands
+            .iter()
+            .filter(|cmd| cmd.workspace.is_some())
+            .map(|cmd| (cmd.command.clone(), cmd.working_dir.clone()))
+            .collect();
+        commands.retain(|cmd| {
+            cmd.workspace.is_some()
+                || !in_workspace.contains(&(cmd.command.clone(), cmd.working_dir.clone()))
+        });
+
+        // A directory reached twice, e.g. through a symlink, is listed once
+        let canonical = |cmd: &Command| {
+            cmd.working_dir
+                .as_deref()
+                .map(|dir| std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf()))
+        };
+        let mut seen = std::collections::HashSet::new();
+        commands.retain(|cmd| seen.insert((cmd.command.clone(), canonical(cmd))));
+
+        // A subdirectory command with the same command line as a root one is
+        // tagged with its directory, so the two can be told apart
+        let root = std::fs::canonicalize(&self.root).unwrap_or_else(|_| self.root.clone());
+        let subdir = |cmd: &Command| {
+            let relative = canonical(cmd)?.strip_prefix(&root).ok()?.to_path_buf();
+            (!relative.as_os_str().is_empty()).then_some(relative)
+        };
+        let at_root: std::collections::HashSet<String> = commands
+            .iter()
+            .filter(|cmd| subdir(cmd).is_none())
+            .map(|cmd| cmd.command.clone())
+            .collect();
+        for cmd in &mut commands {
+            if !at_root.contains(&cmd.command) {
+                continue;
+            }
+            if let Some(relative) = subdir(cmd) {
+                let tag = relative.to_string_lossy().replace('\\', "/");
+                if !cmd.tags.contains(&tag) {
+                    cmd.tags.push(tag);
+                }
+            }
+        }
+
+        Ok(commands)
+    }
+
+    fn scan_within(&self, max_depth: usize, budget: &mut usize) -> anyhow::Result<Vec<Command>> {
         let mut all_commands = self.scan()?;
 
-        if max_depth > 0 {
-            // Look for workspace subdirectories
-            if let Ok(entries) = std::fs::read_dir(&self.root) {
-                for entry in entries.filter_map(Result::ok) {
-                    let path = entry.path();
-                    if path.is_dir() {
+        if max_depth == 0 {
+            return Ok(all_commands);
+        }
+
+        // Look for workspace subdirectories
+        let mut subdirs: Vec<PathBuf> = std::fs::read_dir(&self.root)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir())
+                    .filter(|path| {
                         // Skip common non-project directories
                         let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                        if should_skip_dir(name) {
-                            continue;
-                        }
-
-                        let sub_scanner = ProjectScanner::new(&path);
-                        if let Ok(sub_commands) = sub_scanner.scan_recursive(max_depth - 1) {
-                            all_commands.extend(sub_commands);
-                        }
-                    }
-                }
+                        !should_skip_dir(name) && !is_linked_worktree(path)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        subdirs.sort();
+
+        let total = subdirs.len();
+        subdirs.truncate(self.limits.max_breadth.min(*budget));
+        if subdirs.len() < total {
+            tracing::warn!(
+                dir = %self.root.display(),
+                skipped = total - subdirs.len(),
+                "Recursive scan limit reached; skipping subdirectories"
+            );
+        }
+        *budget -= subdirs.len();
+
+        for path in subdirs {
+            let sub_scanner = ProjectScanner::new(&path).with_limits(self.limits);
+            if let Ok(sub_commands) = sub_scanner.scan_within(max_depth - 1, budget) {
+                all_commands.extend(sub_commands);
             }
         }
 
         Ok(all_commands)
     }
 
+    /// Get the names of the enabled scanners.
+    pub fn scanner_names(&self) -> impl Iterator<Item = &str> {
+        self.scanners.iter().map(|s| s.name())
+    }
+
     /// Get the number of scanners.
     pub fn scanner_count(&self) -> usize {
         self.scanners.len()
     }
 }
 
+/// Record which scanner, file, and rule produced each command.
+///
+/// Scanners may set these keys themselves for a more specific answer;
+/// existing values are kept.
+fn record_provenance(commands: &mut [Command], scanner: &dyn Scanner, root: &Path) {
+    let source_file =
+        scanner.source_files().iter().map(|name| root.join(name)).find(|path| path.exists());
+
+    for command in commands {
+        let metadata = &mut command.metadata;
+        metadata.entry(META_SCANNER.to_string()).or_insert_with(|| scanner.name().to_string());
+        metadata.entry(META_RULE.to_string()).or_insert_with(|| scanner.rule().to_string());
+        if let Some(ref file) = source_file {
+            metadata
+                .entry(META_SOURCE_FILE.to_string())
+                .or_insert_with(|| file.display().to_string());
+        }
+    }
+}
+
+/// Read a project file as text, tolerating encoding problems.
+///
+/// A leading UTF-8 byte order mark is removed. Invalid UTF-8 is decoded
+/// lossily with a warning, so one stray byte (a Latin-1 comment, say)
+/// does not hide every command in the file.
+pub(crate) fn read_source(path: &Path) -> std::io::Result<String> {
+    let bytes = std::fs::read(path)?;
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+
+    Ok(match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(e) => {
+            tracing::warn!(
+                path = %path.display(),
+                offset = e.valid_up_to(),
+                "File is not valid UTF-8; replacing invalid bytes"
+            );
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+    })
+}
+
 /// Check if a directory should be skipped during scanning.
 fn should_skip_dir(name: &str) -> bool {
     matches!(
@@ -161,6 +393,21 @@ fn should_skip_dir(name: &str) -> bool {
     )
 }
 
+/// Check if a directory is a linked Git worktree.
+///
+/// Worktrees nested inside a project are separate checkouts of the same
+/// repository, so scanning them would only duplicate commands. Submodules
+/// also use a `.git` file but point into `.git/modules`, and are kept.
+fn is_linked_worktree(path: &Path) -> bool {
+    std::fs::read_to_string(path.join(".git"))
+        .map(|content| {
+            content
+                .strip_prefix("gitdir:")
+                .is_some_and(|dir| dir.trim().replace('\\', "/").contains("/worktrees/"))
+        })
+        .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,7 +427,9 @@ mod tests {
     #[test]
     fn test_project_scanner_creation() {
         let scanner = ProjectScanner::new(Path::new("."));
-        assert_eq!(scanner.scanner_count(), 11);
+        assert_eq!(scanner.scanner_count(), 16);
+        assert_eq!(scanner.scanner_names().count(), 16);
+        assert!(scanner.scanner_names().any(|name| name == "cargo"));
     }
 
     #[test]
@@ -190,4 +439,210 @@ mod tests {
             assert!(!s.name().is_empty());
         }
     }
+
+    #[test]
+    fn test_scan_records_provenance() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("package.json"), r#"{"scripts": {"build": "tsc"}}"#)
+            .unwrap();
+
+        let commands = ProjectScanner::new(temp.path()).scan().unwrap();
+        let build = commands.iter().find(|c| c.name == "npm run build").unwrap();
+        let provenance = Provenance::of(build).unwrap();
+
+        assert_eq!(provenance.scanner, "npm");
+        assert_eq!(provenance.source_file, Some(temp.path().join("package.json")));
+        assert!(provenance.rule.contains("scripts"));
+
+        let explanation = provenance.to_string();
+        assert!(explanation.contains("scanner: npm"));
+        assert!(explanation.contains("package.json"));
+    }
+
+    #[test]
+    fn test_scanner_provenance_is_kept() {
+        let mut commands = vec![Command::new("a", "a")];
+        commands[0].metadata.insert(META_RULE.to_string(), "custom".to_string());
+        record_provenance(&mut commands, &GitScanner, Path::new("."));
+
+        let provenance = Provenance::of(&commands[0]).unwrap();
+        assert_eq!(provenance.scanner, "git");
+        assert_eq!(provenance.rule, "custom");
+        assert_eq!(provenance.source_file, None);
+    }
+
+    #[test]
+    fn test_scanner_without_rule_uses_default() {
+        struct Minimal;
+
+        impl Scanner for Minimal {
+            fn name(&self) -> &str {
+                "minimal"
+            }
+
+            fn scan(&self, _path: &Path) -> anyhow::Result<Vec<Command>> {
+                Ok(vec![Command::new("a", "a")])
+            }
+        }
+
+        let mut commands = Minimal.scan(Path::new(".")).unwrap();
+        record_provenance(&mut commands, &Minimal, Path::new("."));
+        assert_eq!(Provenance::of(&commands[0]).unwrap().rule, Minimal.rule());
+    }
+
+    #[test]
+    fn test_read_source_strips_bom() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Makefile");
+        std::fs::write(&path, b"\xEF\xBB\xBFbuild:\n\tcargo build\n").unwrap();
+
+        assert_eq!(read_source(&path).unwrap(), "build:\n\tcargo build\n");
+    }
+
+    #[test]
+    fn test_read_source_decodes_invalid_bytes_lossily() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("Makefile");
+        std::fs::write(&path, b"# caf\xE9\ntest:\n\tcargo test\n").unwrap();
+
+        let content = read_source(&path).unwrap();
+        assert!(content.starts_with("# caf\u{FFFD}\n"));
+        assert!(content.contains("test:"));
+    }
+
+    #[test]
+    fn test_bom_and_invalid_bytes_still_scan() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("Makefile"), b"\xEF\xBB\xBFbuild:\n\t@echo \xFF\n")
+            .unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            b"\xEF\xBB\xBF{\"scripts\": {\"lint\": \"eslint\"}}",
+        )
+        .unwrap();
+
+        let commands = ProjectScanner::new(temp.path()).scan().unwrap();
+        let names: Vec<_> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.contains(&"make build"), "{names:?}");
+        assert!(names.contains(&"npm run lint"), "{names:?}");
+    }
+
+    fn make_projects(root: &Path, names: &[&str]) {
+        for name in names {
+            let dir = root.join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            let target = name.replace('/', "-");
+            std::fs::write(dir.join("Makefile"), format!("{target}:\n\techo\n")).unwrap();
+        }
+    }
+
+    fn make_targets(commands: &[Command]) -> Vec<&str> {
+        let mut names: Vec<_> =
+            commands.iter().filter_map(|c| c.name.strip_prefix("make ")).collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_scan_recursive_breadth_cap() {
+        let temp = tempfile::TempDir::new().unwrap();
+        make_projects(temp.path(), &["a", "b", "c", "d", "e"]);
+
+        let limits = ScanLimits { max_breadth: 2, max_dirs: 100 };
+        let commands =
+            ProjectScanner::new(temp.path()).with_limits(limits).scan_recursive(3).unwrap();
+
+        assert_eq!(make_targets(&commands), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_scan_recursive_breadth_cap_applies_per_level() {
+        let temp = tempfile::TempDir::new().unwrap();
+        make_projects(temp.path(), &["a/x", "a/y", "a/z", "b/x", "c"]);
+
+        let limits = ScanLimits { max_breadth: 2, max_dirs: 100 };
+        let commands =
+            ProjectScanner::new(temp.path()).with_limits(limits).scan_recursive(3).unwrap();
+
+        assert_eq!(make_targets(&commands), vec!["a-x", "a-y", "b-x"]);
+    }
+
+    #[test]
+    fn test_scan_recursive_total_budget() {
+        let temp = tempfile::TempDir::new().unwrap();
+        make_projects(temp.path(), &["a/x", "a/y", "b", "c"]);
+
+        // a, b, and c use up the budget, so a's subdirectories are skipped
+        let limits = ScanLimits { max_breadth: 10, max_dirs: 3 };
+        let commands =
+            ProjectScanner::new(temp.path()).with_limits(limits).scan_recursive(3).unwrap();
+
+        assert_eq!(make_targets(&commands), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_scan_recursive_keeps_workspace_commands_once() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let web = temp.path().join("packages/web");
+        std::fs::create_dir_all(&web).unwrap();
+        std::fs::write(temp.path().join("package.json"), r#"{"workspaces": ["packages/*"]}"#)
+            .unwrap();
+        std::fs::write(
+            web.join("package.json"),
+            r#"{"name": "web", "scripts": {"build": "vite"}}"#,
+        )
+        .unwrap();
+
+        let commands = ProjectScanner::new(temp.path()).scan_recursive(3).unwrap();
+        let builds: Vec<_> =
+            commands.iter().filter(|c| c.command == "npm run build").map(|c| &c.name).collect();
+        assert_eq!(builds, vec!["web: build"]);
+    }
+
+    #[test]
+    fn test_scan_recursive_labels_repeated_commands() {
+        let temp = tempfile::TempDir::new().unwrap();
+        make_projects(temp.path(), &["tools"]);
+        std::fs::write(temp.path().join("Makefile"), "test:\n\techo\n").unwrap();
+        std::fs::write(temp.path().join("tools/Makefile"), "test:\n\techo\nlint:\n\techo\n")
+            .unwrap();
+
+        let commands = ProjectScanner::new(temp.path()).scan_recursive(3).unwrap();
+        let tests: Vec<_> = commands.iter().filter(|c| c.command == "make test").collect();
+        assert_eq!(tests.len(), 2);
+        assert!(!tests[0].tags.contains(&"tools".to_string()));
+        assert!(tests[1].tags.contains(&"tools".to_string()));
+
+        // Only repeated command lines are labeled
+        let lint = commands.iter().find(|c| c.command == "make lint").unwrap();
+        assert!(!lint.tags.contains(&"tools".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_recursive_lists_symlinked_directory_once() {
+        let temp = tempfile::TempDir::new().unwrap();
+        make_projects(temp.path(), &["app"]);
+        std::os::unix::fs::symlink(temp.path().join("app"), temp.path().join("alias")).unwrap();
+
+        let commands = ProjectScanner::new(temp.path()).scan_recursive(3).unwrap();
+        assert_eq!(make_targets(&commands), vec!["app"]);
+    }
+
+    #[test]
+    fn test_is_linked_worktree() {
+        let temp = tempfile::TempDir::new().unwrap();
+
+        let worktree = temp.path().join("feature");
+        std::fs::create_dir(&worktree).unwrap();
+        std::fs::write(worktree.join(".git"), "gitdir: /repo/.git/worktrees/feature\n").unwrap();
+        assert!(is_linked_worktree(&worktree));
+
+        let submodule = temp.path().join("vendor-lib");
+        std::fs::create_dir(&submodule).unwrap();
+        std::fs::write(submodule.join(".git"), "gitdir: ../.git/modules/vendor-lib\n").unwrap();
+        assert!(!is_linked_worktree(&submodule));
+
+        assert!(!is_linked_worktree(temp.path()));
+    }
 }
@@ -3,12 +3,12 @@
 //! Scans package.json files to discover npm scripts.
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
-use super::Scanner;
-use crate::core::{Command, CommandSource};
+use super::{read_source, Scanner};
+use crate::core::{glob_match, Command, CommandSource};
 
 /// Scanner for package.json scripts.
 pub struct NpmScanner;
@@ -18,13 +18,22 @@ impl Scanner for NpmScanner {
         "npm"
     }
 
+    fn source_files(&self) -> &[&'static str] {
+        &["package.json"]
+    }
+
+    fn rule(&self) -> &str {
+        "each entry in `scripts`, run with the package manager detected from the lockfile, \
+         plus each `bin` executable and the scripts of each workspace package"
+    }
+
     fn scan(&self, path: &Path) -> anyhow::Result<Vec<Command>> {
         let package_json_path = path.join("package.json");
         if !package_json_path.exists() {
             return Ok(Vec::new());
         }
 
-        let content = std::fs::read_to_string(&package_json_path)?;
+        let content = read_source(&package_json_path)?;
         let package: PackageJson = serde_json::from_str(&content)?;
 
         let package_manager = detect_package_manager(path);
@@ -35,15 +44,25 @@ impl Scanner for NpmScanner {
                 let cmd = Command::from_npm_script(
                     &name,
                     &script,
-                    &package_manager,
+                    package_manager.as_str(),
                     Some(path.to_path_buf()),
                 );
                 commands.push(cmd);
             }
         }
 
+        if let Some(ref bin) = package.bin {
+            let name = package.name.as_deref().unwrap_or_default();
+            commands.extend(generate_bin_commands(bin, name, package_manager, path));
+        }
+
+        let patterns = get_workspace_patterns(path).unwrap_or_default();
+        for dir in resolve_workspace_dirs(path, &patterns) {
+            commands.extend(generate_workspace_commands(&dir, package_manager));
+        }
+
         // Add common package manager commands
-        commands.extend(generate_common_commands(&package_manager, path));
+        commands.extend(generate_common_commands(package_manager, path));
 
         Ok(commands)
     }
@@ -62,9 +81,16 @@ pub struct PackageJson {
     /// Scripts defined in package.json
     pub scripts: Option<HashMap<String, String>>,
 
+    /// Executables provided by the package
+    pub bin: Option<Bin>,
+
     /// Workspace configuration
     pub workspaces: Option<Workspaces>,
 
+    /// Declared package manager, e.g. `pnpm@8.6.0`
+    #[serde(rename = "packageManager")]
+    pub package_manager: Option<String>,
+
     /// Dependencies (for detecting project type)
     pub dependencies: Option<HashMap<String, String>>,
 
@@ -103,21 +129,110 @@ impl Workspaces {
     }
 }
 
-/// Detect which package manager is being used.
-pub fn detect_package_manager(path: &Path) -> String {
-    if path.join("bun.lockb").exists() {
-        "bun".to_string()
-    } else if path.join("pnpm-lock.yaml").exists() {
-        "pnpm".to_string()
-    } else if path.join("yarn.lock").exists() {
-        "yarn".to_string()
+/// The `bin` field (a single path or a map of names to paths).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Bin {
+    /// One executable, named after the package
+    Single(String),
+
+    /// Executable names mapped to script paths
+    Map(HashMap<String, String>),
+}
+
+impl Bin {
+    /// Get `(name, path)` pairs, sorted by name.
+    ///
+    /// A single bin takes the package name, without any `@scope/` prefix.
+    pub fn entries(&self, package_name: &str) -> Vec<(String, String)> {
+        let mut entries = match self {
+            Self::Single(path) => {
+                let name = package_name.rsplit('/').next().unwrap_or(package_name);
+                if name.is_empty() {
+                    return Vec::new();
+                }
+                vec![(name.to_string(), path.clone())]
+            }
+            Self::Map(map) => map.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        };
+        entries.sort();
+        entries
+    }
+}
+
+/// A JavaScript package manager.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PackageManager {
+    /// npm
+    #[default]
+    Npm,
+    /// Yarn (classic or berry)
+    Yarn,
+    /// pnpm
+    Pnpm,
+    /// Bun
+    Bun,
+}
+
+impl PackageManager {
+    /// Parse a package manager name, ignoring any `@version` suffix.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.split('@').next().unwrap_or_default().trim() {
+            "npm" => Some(Self::Npm),
+            "yarn" => Some(Self::Yarn),
+            "pnpm" => Some(Self::Pnpm),
+            "bun" => Some(Self::Bun),
+            _ => None,
+        }
+    }
+
+    /// The executable name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Npm => "npm",
+            Self::Yarn => "yarn",
+            Self::Pnpm => "pnpm",
+            Self::Bun => "bun",
+        }
+    }
+
+    /// The command that runs a package's executables.
+    pub fn exec_prefix(self) -> &'static str {
+        match self {
+            Self::Npm => "npx",
+            Self::Yarn => "yarn",
+            Self::Pnpm => "pnpm exec",
+            Self::Bun => "bunx",
+        }
+    }
+}
+
+/// Detect which package manager a project uses.
+///
+/// The `packageManager` field in package.json wins; otherwise the lockfile
+/// decides, falling back to npm.
+pub fn detect_package_manager(root: &Path) -> PackageManager {
+    let declared = parse_package_json(root)
+        .ok()
+        .and_then(|package| package.package_manager)
+        .and_then(|name| PackageManager::from_name(&name));
+    if let Some(package_manager) = declared {
+        return package_manager;
+    }
+
+    if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+        PackageManager::Bun
+    } else if root.join("pnpm-lock.yaml").exists() {
+        PackageManager::Pnpm
+    } else if root.join("yarn.lock").exists() {
+        PackageManager::Yarn
     } else {
-        "npm".to_string()
+        PackageManager::Npm
     }
 }
 
 /// Generate common package manager commands.
-fn generate_common_commands(package_manager: &str, path: &Path) -> Vec<Command> {
+fn generate_common_commands(package_manager: PackageManager, path: &Path) -> Vec<Command> {
     let mut commands = Vec::new();
 
     let common_ops = [
@@ -127,12 +242,7 @@ fn generate_common_commands(package_manager: &str, path: &Path) -> Vec<Command>
     ];
 
     for (op, desc) in common_ops {
-        let cmd_str = match package_manager {
-            "yarn" => format!("yarn {op}"),
-            "pnpm" => format!("pnpm {op}"),
-            "bun" => format!("bun {op}"),
-            _ => format!("npm {op}"),
-        };
+        let cmd_str = format!("{} {op}", package_manager.as_str());
 
         let cmd = Command::new(&cmd_str, &cmd_str)
             .with_description(desc)
@@ -146,10 +256,131 @@ fn generate_common_commands(package_manager: &str, path: &Path) -> Vec<Command>
     commands
 }
 
+/// Generate commands that run the package's `bin` executables.
+fn generate_bin_commands(
+    bin: &Bin,
+    package_name: &str,
+    package_manager: PackageManager,
+    path: &Path,
+) -> Vec<Command> {
+    let runner = package_manager.exec_prefix();
+
+    bin.entries(package_name)
+        .into_iter()
+        .map(|(name, script)| {
+            let cmd_str = format!("{runner} {name}");
+            Command::new(&cmd_str, &cmd_str)
+                .with_description(format!("Run the {name} executable ({script})"))
+                .with_source(CommandSource::PackageJson(path.to_path_buf()))
+                .with_working_dir(path)
+                .with_tag("bin")
+        })
+        .collect()
+}
+
+/// Generate script commands for a workspace package.
+///
+/// Names are prefixed with the package name (`@acme/web: build`), and each
+/// command is tagged `workspace:<name>` for filtering.
+fn generate_workspace_commands(dir: &Path, package_manager: PackageManager) -> Vec<Command> {
+    let Ok(package) = parse_package_json(dir) else {
+        return Vec::new();
+    };
+    let Some(scripts) = package.scripts else {
+        return Vec::new();
+    };
+    let name = package.name.unwrap_or_else(|| {
+        dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+    });
+
+    let mut scripts: Vec<_> = scripts.into_iter().collect();
+    scripts.sort();
+
+    scripts
+        .into_iter()
+        .map(|(script_name, script)| {
+            let run = Command::from_npm_script(
+                &script_name,
+                &script,
+                package_manager.as_str(),
+                Some(dir.to_path_buf()),
+            );
+            Command::new(format!("{name}: {script_name}"), run.command)
+                .with_description(script)
+                .with_source(run.source)
+                .with_working_dir(dir)
+                .with_tags(run.tags)
+                .with_tag(format!("workspace:{name}"))
+                .with_workspace(&name)
+        })
+        .collect()
+}
+
+/// Find the workspace package directories matching `patterns`.
+///
+/// Supports `*` and `?` within a path segment and `**` for any depth;
+/// patterns starting with `!` exclude matches. Only directories with a
+/// package.json are returned, and `node_modules` is never entered.
+pub fn resolve_workspace_dirs(root: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let mut included = Vec::new();
+    let mut excluded = Vec::new();
+
+    for pattern in patterns {
+        let (pattern, matches) = match pattern.strip_prefix('!') {
+            Some(pattern) => (pattern, &mut excluded),
+            None => (pattern.as_str(), &mut included),
+        };
+        let segments: Vec<&str> =
+            pattern.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+        expand_workspace_pattern(root, &segments, matches);
+    }
+
+    included.sort();
+    included.dedup();
+    included.retain(|dir| !excluded.contains(dir) && dir.as_path() != root);
+    included
+}
+
+fn expand_workspace_pattern(dir: &Path, segments: &[&str], matches: &mut Vec<PathBuf>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        if dir.join("package.json").is_file() {
+            matches.push(dir.to_path_buf());
+        }
+        return;
+    };
+
+    if !segment.contains(['*', '?']) {
+        let next = dir.join(segment);
+        if next.is_dir() {
+            expand_workspace_pattern(&next, rest, matches);
+        }
+        return;
+    }
+
+    if *segment == "**" {
+        expand_workspace_pattern(dir, rest, matches);
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !path.is_dir() || name == "node_modules" || name.starts_with('.') {
+            continue;
+        }
+        if *segment == "**" {
+            expand_workspace_pattern(&path, segments, matches);
+        } else if glob_match(segment, &name) {
+            expand_workspace_pattern(&path, rest, matches);
+        }
+    }
+}
+
 /// Parse package.json from a path.
-#[allow(dead_code)]
 pub fn parse_package_json(path: &Path) -> anyhow::Result<PackageJson> {
-    let content = std::fs::read_to_string(path.join("package.json"))?;
+    let content = read_source(&path.join("package.json"))?;
     let package: PackageJson = serde_json::from_str(&content)?;
     Ok(package)
 }
@@ -166,7 +397,6 @@ pub fn is_monorepo(path: &Path) -> bool {
 }
 
 /// Get workspace patterns from package.json or pnpm-workspace.yaml.
-#[allow(dead_code)]
 pub fn get_workspace_patterns(path: &Path) -> anyhow::Result<Vec<String>> {
     // Try package.json first
     if let Ok(package) = parse_package_json(path) {
@@ -178,7 +408,7 @@ pub fn get_workspace_patterns(path: &Path) -> anyhow::Result<Vec<String>> {
     // Try pnpm-workspace.yaml
     let pnpm_workspace = path.join("pnpm-workspace.yaml");
     if pnpm_workspace.exists() {
-        let content = std::fs::read_to_string(&pnpm_workspace)?;
+        let content = read_source(&pnpm_workspace)?;
 
         #[derive(Deserialize)]
         struct PnpmWorkspace {
@@ -249,6 +479,141 @@ mod tests {
         assert!(patterns.contains(&"packages/*".to_string()));
     }
 
+    #[test]
+    fn test_single_string_bin() {
+        let json = r#"{"name": "@acme/deploy-tool", "bin": "./cli.js"}"#;
+        let package: PackageJson = serde_json::from_str(json).unwrap();
+        let bin = package.bin.unwrap();
+
+        assert_eq!(
+            bin.entries("@acme/deploy-tool"),
+            vec![("deploy-tool".into(), "./cli.js".into())]
+        );
+
+        let commands =
+            generate_bin_commands(&bin, "@acme/deploy-tool", PackageManager::Npm, Path::new("."));
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].name, "npx deploy-tool");
+        assert_eq!(commands[0].command, "npx deploy-tool");
+        assert!(commands[0].tags.contains(&"bin".to_string()));
+    }
+
+    #[test]
+    fn test_object_bin_map() {
+        let json =
+            r#"{"name": "tools", "bin": {"tools-lint": "bin/lint.js", "tools-fmt": "bin/fmt.js"}}"#;
+        let package: PackageJson = serde_json::from_str(json).unwrap();
+        let bin = package.bin.unwrap();
+
+        let commands = generate_bin_commands(&bin, "tools", PackageManager::Pnpm, Path::new("."));
+        let names: Vec<_> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["pnpm exec tools-fmt", "pnpm exec tools-lint"]);
+        assert_eq!(
+            commands[1].description.as_deref(),
+            Some("Run the tools-lint executable (bin/lint.js)")
+        );
+    }
+
+    #[test]
+    fn test_scan_includes_bin() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "mytool", "bin": "index.js", "scripts": {"test": "jest"}}"#,
+        )
+        .unwrap();
+
+        let commands = NpmScanner.scan(temp.path()).unwrap();
+        assert!(commands.iter().any(|c| c.command == "npx mytool"));
+        assert!(commands.iter().any(|c| c.command == "npm run test"));
+    }
+
+    #[test]
+    fn test_detect_package_manager_from_lockfile() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/pnpm-project");
+        assert_eq!(detect_package_manager(&fixture), PackageManager::Pnpm);
+
+        let commands = NpmScanner.scan(&fixture).unwrap();
+        assert!(commands.iter().any(|c| c.command == "pnpm run build"));
+        assert!(commands.iter().any(|c| c.command == "pnpm install"));
+    }
+
+    #[test]
+    fn test_package_manager_field_wins_over_lockfile() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"packageManager": "yarn@4.1.0", "scripts": {"build": "tsc", "install": "node setup.js"}}"#,
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("package-lock.json"), "{}").unwrap();
+
+        assert_eq!(detect_package_manager(temp.path()), PackageManager::Yarn);
+        let commands = NpmScanner.scan(temp.path()).unwrap();
+        assert!(commands.iter().any(|c| c.command == "yarn build"));
+        assert!(commands.iter().any(|c| c.command == "yarn run install"));
+    }
+
+    #[test]
+    fn test_detect_bun_and_default() {
+        let temp = tempfile::TempDir::new().unwrap();
+        assert_eq!(detect_package_manager(temp.path()), PackageManager::Npm);
+
+        std::fs::write(temp.path().join("bun.lock"), "").unwrap();
+        assert_eq!(detect_package_manager(temp.path()), PackageManager::Bun);
+        assert_eq!(PackageManager::from_name("pnpm@8.6.0"), Some(PackageManager::Pnpm));
+        assert_eq!(PackageManager::from_name("deno@1.0.0"), None);
+    }
+
+    #[test]
+    fn test_scan_workspace_packages() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"name": "acme", "workspaces": ["packages/*"], "scripts": {"lint": "eslint ."}}"#,
+        )
+        .unwrap();
+        for (dir, json) in [
+            ("packages/web", r#"{"name": "@acme/web", "scripts": {"build": "vite build"}}"#),
+            ("packages/api", r#"{"name": "@acme/api", "scripts": {"test": "jest"}}"#),
+            ("packages/types", r#"{"name": "@acme/types"}"#),
+            ("packages/web/node_modules/dep", r#"{"name": "dep", "scripts": {"x": "y"}}"#),
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("package.json"), json).unwrap();
+        }
+
+        let commands = NpmScanner.scan(root).unwrap();
+        let names: Vec<_> =
+            commands.iter().filter(|c| c.workspace.is_some()).map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["@acme/api: test", "@acme/web: build"]);
+        assert!(commands.iter().any(|c| c.name == "npm run lint"));
+
+        let build = commands.iter().find(|c| c.name == "@acme/web: build").unwrap();
+        assert_eq!(build.command, "npm run build");
+        assert_eq!(build.working_dir, Some(root.join("packages/web")));
+        assert!(build.tags.contains(&"workspace:@acme/web".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_workspace_dirs() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        for dir in ["apps/web", "apps/legacy", "libs/ui/button", "docs"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("package.json"), "{}").unwrap();
+        }
+
+        let patterns: Vec<String> =
+            ["apps/*", "!apps/legacy", "libs/**", "docs"].iter().map(ToString::to_string).collect();
+        let dirs = resolve_workspace_dirs(root, &patterns);
+        assert_eq!(
+            dirs,
+            vec![root.join("apps/web"), root.join("docs"), root.join("libs/ui/button")]
+        );
+    }
+
     #[test]
     fn test_npm_scanner_name() {
         let scanner = NpmScanner;
@@ -2,12 +2,13 @@
 //!
 //! Scans nx.json and project.json files to discover Nx targets.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::Path;
 
 use serde::Deserialize;
+use walkdir::WalkDir;
 
-use super::Scanner;
+use super::{read_source, should_skip_dir, Scanner};
 use crate::core::{Command, CommandSource};
 
 /// Scanner for Nx monorepo targets.
@@ -18,6 +19,15 @@ impl Scanner for NxScanner {
         "nx"
     }
 
+    fn source_files(&self) -> &[&'static str] {
+        &["nx.json", "project.json"]
+    }
+
+    fn rule(&self) -> &str {
+        "each target in project.json files, inheriting from `targetDefaults` in nx.json, \
+         plus workspace-wide nx commands"
+    }
+
     fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
         let mut commands = Vec::new();
 
@@ -28,25 +38,26 @@ impl Scanner for NxScanner {
         }
 
         // Parse nx.json for workspace-level targets
-        if let Ok(nx_config) = parse_nx_json(&nx_json_path) {
-            // Add workspace-level targets from targetDefaults
-            if let Some(target_defaults) = nx_config.target_defaults {
-                for target_name in target_defaults.keys() {
-                    commands.push(
-                        Command::new(
-                            format!("nx run-many --target={target_name}"),
-                            format!("npx nx run-many --target={target_name}"),
-                        )
-                        .with_description(format!("Run {target_name} for all projects"))
-                        .with_source(CommandSource::NxProject("workspace".to_string()))
-                        .with_tags(vec!["nx".to_string(), "monorepo".to_string()]),
-                    );
-                }
-            }
+        let target_defaults = parse_nx_json(&nx_json_path)
+            .ok()
+            .and_then(|config| config.target_defaults)
+            .unwrap_or_default();
+
+        // Add workspace-level targets from targetDefaults
+        for target_name in target_defaults.keys() {
+            commands.push(
+                Command::new(
+                    format!("nx run-many --target={target_name}"),
+                    format!("npx nx run-many --target={target_name}"),
+                )
+                .with_description(format!("Run {target_name} for all projects"))
+                .with_source(CommandSource::NxProject("workspace".to_string()))
+                .with_tags(vec!["nx".to_string(), "monorepo".to_string()]),
+            );
         }
 
         // Scan for project.json files in the workspace
-        commands.extend(scan_nx_projects(dir)?);
+        commands.extend(scan_nx_projects(dir, &target_defaults));
 
         // Add common Nx commands
         commands.extend(get_common_nx_commands(dir));
@@ -55,13 +66,19 @@ impl Scanner for NxScanner {
     }
 }
 
+/// How deep below the workspace root to look for project.json files.
+const MAX_PROJECT_DEPTH: usize = 4;
+
+/// Targets assumed for projects whose targets are all inferred by plugins.
+const INFERRED_TARGETS: &[&str] = &["build", "test", "lint"];
+
 /// Nx workspace configuration (nx.json).
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[allow(dead_code)]
 struct NxConfig {
-    /// Target defaults for all projects
-    target_defaults: Option<HashMap<String, serde_json::Value>>,
+    /// Target defaults for all projects, keyed by target name
+    target_defaults: Option<BTreeMap<String, Target>>,
     /// Named inputs for caching
     #[serde(default)]
     named_inputs: HashMap<String, serde_json::Value>,
@@ -78,14 +95,14 @@ struct ProjectJson {
     name: Option<String>,
     /// Project targets
     #[serde(default)]
-    targets: HashMap<String, Target>,
+    targets: BTreeMap<String, Target>,
     /// Project tags
     #[serde(default)]
     tags: Vec<String>,
 }
 
 /// Nx target definition.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[allow(dead_code)]
 struct Target {
@@ -96,111 +113,138 @@ struct Target {
     options: serde_json::Value,
     /// Target configurations
     #[serde(default)]
-    configurations: HashMap<String, serde_json::Value>,
+    configurations: BTreeMap<String, serde_json::Value>,
 }
 
 /// Parse nx.json file.
 fn parse_nx_json(path: &Path) -> anyhow::Result<NxConfig> {
-    let content = std::fs::read_to_string(path)?;
+    let content = read_source(path)?;
     let config: NxConfig = serde_json::from_str(&content)?;
     Ok(config)
 }
 
 /// Parse project.json file.
 fn parse_project_json(path: &Path) -> anyhow::Result<ProjectJson> {
-    let content = std::fs::read_to_string(path)?;
+    let content = read_source(path)?;
     let project: ProjectJson = serde_json::from_str(&content)?;
     Ok(project)
 }
 
-/// Scan for project.json files and extract targets.
-fn scan_nx_projects(dir: &Path) -> anyhow::Result<Vec<Command>> {
+/// Walk the workspace for project.json files and extract targets.
+///
+/// Projects are visited in path order so the output is stable.
+fn scan_nx_projects(dir: &Path, target_defaults: &BTreeMap<String, Target>) -> Vec<Command> {
     let mut commands = Vec::new();
 
-    // Common project directories in Nx workspaces
-    let project_dirs = ["apps", "libs", "packages", "projects"];
-
-    for project_dir in &project_dirs {
-        let path = dir.join(project_dir);
-        if path.exists() {
-            commands.extend(scan_project_directory(&path)?);
-        }
-    }
-
-    // Also check root-level project.json
-    let root_project = dir.join("project.json");
-    if root_project.exists() {
-        if let Ok(project) = parse_project_json(&root_project) {
-            let project_name = project.name.clone().unwrap_or_else(|| "root".to_string());
-            commands.extend(project_to_commands(&project_name, &project, dir));
+    let walker = WalkDir::new(dir)
+        .max_depth(MAX_PROJECT_DEPTH)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || !should_skip_dir(&entry.file_name().to_string_lossy())
+        });
+
+    for entry in walker.filter_map(Result::ok) {
+        if !entry.file_type().is_file() || entry.file_name() != "project.json" {
+            continue;
         }
-    }
-
-    Ok(commands)
-}
-
-/// Scan a directory for project.json files.
-fn scan_project_directory(dir: &Path) -> anyhow::Result<Vec<Command>> {
-    let mut commands = Vec::new();
+        let project_path = entry.path().parent().unwrap_or(dir);
 
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for entry in entries.filter_map(Result::ok) {
-            let path = entry.path();
-            if path.is_dir() {
-                let project_json = path.join("project.json");
-                if project_json.exists() {
-                    if let Ok(project) = parse_project_json(&project_json) {
-                        let project_name = project.name.clone().unwrap_or_else(|| {
-                            path.file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("unknown")
-                                .to_string()
-                        });
-                        commands.extend(project_to_commands(&project_name, &project, &path));
+        match parse_project_json(entry.path()) {
+            Ok(project) => {
+                let project_name = project.name.clone().unwrap_or_else(|| {
+                    if project_path == dir {
+                        return "root".to_string();
                     }
-                }
+                    project_path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("unknown")
+                        .to_string()
+                });
+                commands.extend(project_to_commands(
+                    &project_name,
+                    &project,
+                    project_path,
+                    target_defaults,
+                ));
+            }
+            Err(e) => {
+                tracing::debug!(path = %entry.path().display(), error = %e, "Skipping project.json");
             }
         }
     }
 
-    Ok(commands)
+    commands
 }
 
 /// Convert a project to commands.
+///
+/// Targets missing an executor or configurations inherit them from the
+/// matching `targetDefaults` entry. A project without any explicit targets
+/// gets the common inferred set.
 fn project_to_commands(
     project_name: &str,
     project: &ProjectJson,
     _project_path: &Path,
+    target_defaults: &BTreeMap<String, Target>,
 ) -> Vec<Command> {
     let mut commands = Vec::new();
+    let source = CommandSource::NxProject(project_name.to_string());
+    let mut tags = vec!["nx".to_string(), project_name.to_string()];
+    tags.extend(project.tags.clone());
+
+    if project.targets.is_empty() {
+        for target_name in INFERRED_TARGETS {
+            commands.push(
+                Command::new(
+                    format!("nx run {project_name}:{target_name}"),
+                    format!("npx nx run {project_name}:{target_name}"),
+                )
+                .with_description(format!("Inferred {target_name} target"))
+                .with_source(source.clone())
+                .with_tags(tags.clone()),
+            );
+        }
+        return commands;
+    }
 
     for (target_name, target) in &project.targets {
-        let mut tags = vec!["nx".to_string(), project_name.to_string()];
-        tags.extend(project.tags.clone());
+        let defaults = target_defaults.get(target_name);
 
         let mut cmd = Command::new(
-            format!("nx {target_name} {project_name}"),
-            format!("npx nx {target_name} {project_name}"),
+            format!("nx run {project_name}:{target_name}"),
+            format!("npx nx run {project_name}:{target_name}"),
         )
-        .with_source(CommandSource::NxProject(project_name.to_string()))
-        .with_tags(tags);
+        .with_source(source.clone())
+        .with_tags(tags.clone());
 
         // Add description based on executor
-        if let Some(executor) = &target.executor {
+        let executor = target.executor.as_ref().or_else(|| defaults?.executor.as_ref());
+        if let Some(executor) = executor {
             cmd = cmd.with_description(format!("Nx target using {executor}"));
         }
 
         commands.push(cmd);
 
         // Add configuration variants
-        for config_name in target.configurations.keys() {
+        let configurations = defaults
+            .map(|d| &d.configurations)
+            .into_iter()
+            .flatten()
+            .chain(&target.configurations)
+            .map(|(name, _)| name)
+            .collect::<BTreeSet<_>>();
+        for config_name in configurations {
             commands.push(
                 Command::new(
-                    format!("nx {target_name} {project_name} --configuration={config_name}"),
-                    format!("npx nx {target_name} {project_name} --configuration={config_name}"),
+                    format!("nx run {project_name}:{target_name}:{config_name}"),
+                    format!("npx nx run {project_name}:{target_name}:{config_name}"),
                 )
                 .with_description(format!("{target_name} with {config_name} configuration"))
-                .with_source(CommandSource::NxProject(project_name.to_string()))
+                .with_source(source.clone())
                 .with_tags(vec![
                     "nx".to_string(),
                     project_name.to_string(),
@@ -328,11 +372,83 @@ mod tests {
         }"#;
 
         let project: ProjectJson = serde_json::from_str(json).unwrap();
-        let commands = project_to_commands("my-app", &project, Path::new("."));
+        let commands = project_to_commands("my-app", &project, Path::new("."), &BTreeMap::new());
 
         // Should have base command + production configuration
         assert_eq!(commands.len(), 2);
-        assert!(commands[0].name.contains("build"));
-        assert!(commands[1].name.contains("production"));
+        assert_eq!(commands[0].name, "nx run my-app:build");
+        assert_eq!(commands[1].name, "nx run my-app:build:production");
+    }
+
+    #[test]
+    fn test_target_defaults_inheritance() {
+        let defaults: BTreeMap<String, Target> = serde_json::from_str(
+            r#"{
+                "build": {
+                    "executor": "@nx/vite:build",
+                    "configurations": { "production": {} }
+                }
+            }"#,
+        )
+        .unwrap();
+        let project: ProjectJson =
+            serde_json::from_str(r#"{ "name": "web", "targets": { "build": {} } }"#).unwrap();
+
+        let commands = project_to_commands("web", &project, Path::new("."), &defaults);
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].description.as_deref(), Some("Nx target using @nx/vite:build"));
+        assert_eq!(commands[1].name, "nx run web:build:production");
+    }
+
+    #[test]
+    fn test_scan_workspace_projects() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("nx.json"), "{}").unwrap();
+
+        let api = root.join("apps/api");
+        std::fs::create_dir_all(&api).unwrap();
+        std::fs::write(
+            api.join("project.json"),
+            r#"{ "name": "api", "targets": { "serve": {}, "e2e": {} } }"#,
+        )
+        .unwrap();
+
+        let ui = root.join("libs/shared/ui");
+        std::fs::create_dir_all(&ui).unwrap();
+        std::fs::write(ui.join("project.json"), r#"{ "targets": { "storybook": {} } }"#).unwrap();
+
+        // Only inferred targets
+        let utils = root.join("packages/utils");
+        std::fs::create_dir_all(&utils).unwrap();
+        std::fs::write(utils.join("project.json"), r#"{ "name": "utils" }"#).unwrap();
+
+        // Dependencies are never scanned
+        let dep = root.join("node_modules/dep");
+        std::fs::create_dir_all(&dep).unwrap();
+        std::fs::write(dep.join("project.json"), r#"{ "targets": { "build": {} } }"#).unwrap();
+
+        let commands = NxScanner.scan(root).unwrap();
+        let names: Vec<&str> = commands
+            .iter()
+            .map(|c| c.name.as_str())
+            .filter(|name| name.starts_with("nx run ") && !name.contains("--"))
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "nx run api:e2e",
+                "nx run api:serve",
+                "nx run ui:storybook",
+                "nx run utils:build",
+                "nx run utils:test",
+                "nx run utils:lint",
+            ]
+        );
+
+        let serve = commands.iter().find(|c| c.name == "nx run api:serve").unwrap();
+        assert!(serve.tags.contains(&"api".to_string()));
+        assert!(!serve.tags.contains(&"ui".to_string()));
     }
 }
@@ -1,14 +1,14 @@
 //! Python project scanner.
 //!
 //! Scans pyproject.toml, setup.py, and requirements.txt to discover
-//! Python project commands.
+//! Python project commands for Poetry, PDM, Hatch, uv, or plain pip.
 
 use std::collections::HashMap;
 use std::path::Path;
 
 use serde::Deserialize;
 
-use super::Scanner;
+use super::{read_source, Scanner};
 use crate::core::{Command, CommandSource};
 
 /// Scanner for Python projects.
@@ -19,6 +19,15 @@ impl Scanner for PythonScanner {
         "python"
     }
 
+    fn source_files(&self) -> &[&'static str] {
+        &["pyproject.toml", "setup.py", "requirements.txt"]
+    }
+
+    fn rule(&self) -> &str {
+        "commands for the build tool detected from lockfiles and `[tool.*]` tables, \
+         the test runner, plus each project script"
+    }
+
     fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
         let mut commands = Vec::new();
 
@@ -30,7 +39,7 @@ impl Scanner for PythonScanner {
         if pyproject_path.exists() {
             let config = parse_pyproject_toml(&pyproject_path)?;
             let source = CommandSource::Python(pyproject_path.clone());
-            let tool_type = detect_tool_type(&config);
+            let tool_type = detect_tool(&config, dir);
 
             // Get project name for context
             let project_name = config
@@ -57,6 +66,9 @@ impl Scanner for PythonScanner {
                 ToolType::Hatch => {
                     commands.extend(generate_hatch_commands(&config, &source, &project_name));
                 }
+                ToolType::Uv => {
+                    commands.extend(generate_uv_commands(&config, &source, &project_name));
+                }
                 ToolType::Generic => {
                     commands.extend(generate_generic_commands(&source, &project_name));
                 }
@@ -200,14 +212,19 @@ impl Scanner for PythonScanner {
 }
 
 /// Detected Python tool type.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ToolType {
     Poetry,
     Pdm,
     Hatch,
+    Uv,
     Generic,
 }
 
+/// Lockfiles that identify a Python tool, in preference order.
+const TOOL_LOCKFILES: &[(&str, ToolType)] =
+    &[("poetry.lock", ToolType::Poetry), ("pdm.lock", ToolType::Pdm), ("uv.lock", ToolType::Uv)];
+
 /// pyproject.toml configuration.
 #[derive(Debug, Deserialize, Default)]
 struct PyProjectConfig {
@@ -238,6 +255,8 @@ struct ToolConfig {
     pdm: Option<PdmConfig>,
     /// Hatch configuration
     hatch: Option<HatchConfig>,
+    /// uv configuration (just to detect its presence)
+    uv: Option<toml::Value>,
     /// pytest configuration
     pytest: Option<PytestConfig>,
 }
@@ -350,25 +369,52 @@ struct PytestConfig {
 
 /// Parse pyproject.toml file.
 fn parse_pyproject_toml(path: &Path) -> anyhow::Result<PyProjectConfig> {
-    let content = std::fs::read_to_string(path)?;
+    let content = read_source(path)?;
     let config: PyProjectConfig = toml::from_str(&content)?;
     Ok(config)
 }
 
-/// Detect which Python tool is being used.
-fn detect_tool_type(config: &PyProjectConfig) -> ToolType {
-    if let Some(tool) = &config.tool {
-        if tool.poetry.is_some() {
-            return ToolType::Poetry;
-        }
-        if tool.pdm.is_some() {
-            return ToolType::Pdm;
-        }
-        if tool.hatch.is_some() {
-            return ToolType::Hatch;
+/// Detect which Python tool manages the project in `dir`.
+///
+/// A lockfile is the strongest signal. When several lockfiles are present,
+/// the one whose tool also has a `[tool.*]` table in pyproject.toml wins,
+/// then the first in [`TOOL_LOCKFILES`] order. Without a lockfile, the
+/// tool tables decide.
+fn detect_tool(config: &PyProjectConfig, dir: &Path) -> ToolType {
+    let locked: Vec<ToolType> = TOOL_LOCKFILES
+        .iter()
+        .filter(|(lockfile, _)| dir.join(lockfile).exists())
+        .map(|(_, tool)| *tool)
+        .collect();
+
+    match locked.first() {
+        Some(&first) => {
+            locked.into_iter().find(|&tool| has_tool_table(config, tool)).unwrap_or(first)
         }
+        None => detect_tool_type(config),
+    }
+}
+
+/// Detect which Python tool is configured in pyproject.toml.
+fn detect_tool_type(config: &PyProjectConfig) -> ToolType {
+    [ToolType::Poetry, ToolType::Pdm, ToolType::Hatch, ToolType::Uv]
+        .into_iter()
+        .find(|&tool| has_tool_table(config, tool))
+        .unwrap_or(ToolType::Generic)
+}
+
+/// Check whether pyproject.toml has a `[tool.*]` table for a tool.
+fn has_tool_table(config: &PyProjectConfig, tool_type: ToolType) -> bool {
+    let Some(tool) = &config.tool else {
+        return false;
+    };
+    match tool_type {
+        ToolType::Poetry => tool.poetry.is_some(),
+        ToolType::Pdm => tool.pdm.is_some(),
+        ToolType::Hatch => tool.hatch.is_some(),
+        ToolType::Uv => tool.uv.is_some(),
+        ToolType::Generic => false,
     }
-    ToolType::Generic
 }
 
 /// Check if pytest is configured.
@@ -586,6 +632,65 @@ fn generate_hatch_commands(
     commands
 }
 
+/// Generate uv-specific commands.
+fn generate_uv_commands(
+    config: &PyProjectConfig,
+    source: &CommandSource,
+    project_name: &str,
+) -> Vec<Command> {
+    let mut commands = Vec::new();
+
+    // Basic uv commands
+    commands.push(
+        Command::new("uv sync", "uv sync")
+            .with_description(format!("Install dependencies for {project_name}"))
+            .with_source(source.clone())
+            .with_tags(vec!["python".to_string(), "uv".to_string()]),
+    );
+
+    commands.push(
+        Command::new("uv lock --upgrade", "uv lock --upgrade")
+            .with_description("Update dependencies")
+            .with_source(source.clone())
+            .with_tags(vec!["python".to_string(), "uv".to_string()]),
+    );
+
+    commands.push(
+        Command::new("uv build", "uv build")
+            .with_description("Build package")
+            .with_source(source.clone())
+            .with_tags(vec!["python".to_string(), "uv".to_string(), "build".to_string()]),
+    );
+
+    commands.push(
+        Command::new("uv publish", "uv publish")
+            .with_description("Publish package to PyPI")
+            .with_source(source.clone())
+            .with_tags(vec!["python".to_string(), "uv".to_string()]),
+    );
+
+    commands.push(
+        Command::new("uv run pytest", "uv run pytest")
+            .with_description("Run tests")
+            .with_source(source.clone())
+            .with_tags(vec!["python".to_string(), "uv".to_string(), "test".to_string()]),
+    );
+
+    // Add project entry points
+    if let Some(scripts) = config.project.as_ref().and_then(|p| p.scripts.as_ref()) {
+        for (name, entry_point) in scripts {
+            commands.push(
+                Command::new(format!("uv run {name}"), format!("uv run {name}"))
+                    .with_description(format!("Run {name} ({entry_point})"))
+                    .with_source(source.clone())
+                    .with_tags(vec!["python".to_string(), "uv".to_string(), "script".to_string()]),
+            );
+        }
+    }
+
+    commands
+}
+
 /// Generate generic Python commands (no specific tool detected).
 fn generate_generic_commands(source: &CommandSource, project_name: &str) -> Vec<Command> {
     let mut commands = Vec::new();
@@ -777,6 +882,81 @@ name = "generic-project"
         assert_eq!(detect_tool_type(&config), ToolType::Generic);
     }
 
+    #[test]
+    fn test_detect_tool_type_uv() {
+        let toml = r#"
+[tool.uv]
+dev-dependencies = ["pytest"]
+"#;
+        let config: PyProjectConfig = toml::from_str(toml).unwrap();
+        assert_eq!(detect_tool_type(&config), ToolType::Uv);
+    }
+
+    #[test]
+    fn test_detect_tool_from_lockfile() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let config: PyProjectConfig = toml::from_str("[project]\nname = \"app\"\n").unwrap();
+        assert_eq!(detect_tool(&config, temp.path()), ToolType::Generic);
+
+        std::fs::write(temp.path().join("uv.lock"), "").unwrap();
+        assert_eq!(detect_tool(&config, temp.path()), ToolType::Uv);
+
+        // Several lockfiles and no tool table: fixed preference order
+        std::fs::write(temp.path().join("pdm.lock"), "").unwrap();
+        assert_eq!(detect_tool(&config, temp.path()), ToolType::Pdm);
+
+        // The tool table breaks the tie
+        let config: PyProjectConfig = toml::from_str("[tool.uv]\n").unwrap();
+        assert_eq!(detect_tool(&config, temp.path()), ToolType::Uv);
+    }
+
+    #[test]
+    fn test_scan_pdm_scripts() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("pyproject.toml"),
+            r#"
+[project]
+name = "pdm-app"
+
+[tool.pdm.scripts]
+start = "flask run"
+test = { cmd = "pytest", help = "Run the suite" }
+"#,
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("pdm.lock"), "").unwrap();
+
+        let commands = PythonScanner.scan(temp.path()).unwrap();
+        let start = commands.iter().find(|c| c.name == "pdm run start").unwrap();
+        assert_eq!(start.command, "pdm run start");
+        assert_eq!(start.description.as_deref(), Some("flask run"));
+        let test = commands.iter().find(|c| c.name == "pdm run test").unwrap();
+        assert_eq!(test.description.as_deref(), Some("Run the suite"));
+    }
+
+    #[test]
+    fn test_scan_uv_project() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("pyproject.toml"),
+            r#"
+[project]
+name = "uv-app"
+
+[project.scripts]
+serve = "app.main:serve"
+"#,
+        )
+        .unwrap();
+        std::fs::write(temp.path().join("uv.lock"), "").unwrap();
+
+        let commands = PythonScanner.scan(temp.path()).unwrap();
+        assert!(commands.iter().any(|c| c.name == "uv sync"));
+        assert!(commands.iter().any(|c| c.name == "uv run serve"));
+        assert!(!commands.iter().any(|c| c.name == "pip install -e ."));
+    }
+
     #[test]
     fn test_has_pytest_config() {
         let toml = r#"
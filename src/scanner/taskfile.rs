@@ -8,7 +8,7 @@ use std::path::Path;
 
 use serde::Deserialize;
 
-use super::Scanner;
+use super::{read_source, Scanner};
 use crate::core::{Command, CommandSource};
 
 /// Scanner for Taskfile tasks.
@@ -19,6 +19,14 @@ impl Scanner for TaskfileScanner {
         "taskfile"
     }
 
+    fn source_files(&self) -> &[&'static str] {
+        TASKFILE_NAMES
+    }
+
+    fn rule(&self) -> &str {
+        "each entry in `tasks`, run with task"
+    }
+
     fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
         let mut commands = Vec::new();
 
@@ -30,7 +38,7 @@ impl Scanner for TaskfileScanner {
         };
 
         // Parse the taskfile
-        let content = std::fs::read_to_string(&taskfile_path)?;
+        let content = read_source(&taskfile_path)?;
         let taskfile: Taskfile = serde_yaml::from_str(&content)?;
 
         // Extract tasks
@@ -76,18 +84,19 @@ impl Scanner for TaskfileScanner {
     }
 }
 
+/// Taskfile names, in lookup order.
+const TASKFILE_NAMES: &[&str] = &[
+    "Taskfile.yml",
+    "Taskfile.yaml",
+    "taskfile.yml",
+    "taskfile.yaml",
+    "Taskfile.dist.yml",
+    "Taskfile.dist.yaml",
+];
+
 /// Find the Taskfile in a directory.
 fn find_taskfile(dir: &Path) -> Option<std::path::PathBuf> {
-    let candidates = [
-        "Taskfile.yml",
-        "Taskfile.yaml",
-        "taskfile.yml",
-        "taskfile.yaml",
-        "Taskfile.dist.yml",
-        "Taskfile.dist.yaml",
-    ];
-
-    for candidate in &candidates {
+    for candidate in TASKFILE_NAMES {
         let path = dir.join(candidate);
         if path.exists() {
             return Some(path);
@@ -0,0 +1,338 @@
+//! tox and nox scanner.
+//!
+//! Scans `tox.ini` for test environments and `noxfile.py` for sessions.
+//! See: https://tox.wiki and https://nox.thea.codes
+
+use std::path::Path;
+
+use super::{read_source, Scanner, META_SOURCE_FILE};
+use crate::core::{Command, CommandSource};
+
+/// Scanner for tox environments and nox sessions.
+pub struct ToxScanner;
+
+impl Scanner for ToxScanner {
+    fn name(&self) -> &str {
+        "tox"
+    }
+
+    fn source_files(&self) -> &[&'static str] {
+        &["tox.ini", "noxfile.py"]
+    }
+
+    fn rule(&self) -> &str {
+        "each tox environment from `envlist` and `[testenv:*]` sections, \
+         and each `@nox.session` function"
+    }
+
+    fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
+        let mut commands = Vec::new();
+
+        let tox_path = dir.join("tox.ini");
+        if tox_path.exists() {
+            let source = CommandSource::Python(tox_path.clone());
+            let tox_command = |cmd: String, description: String| {
+                let mut cmd = Command::new(&cmd, &cmd)
+                    .with_description(description)
+                    .with_source(source.clone())
+                    .with_working_dir(dir)
+                    .with_tags(vec!["python".to_string(), "tox".to_string(), "test".to_string()]);
+                cmd.metadata.insert(META_SOURCE_FILE.to_string(), tox_path.display().to_string());
+                cmd
+            };
+
+            commands.push(tox_command("tox".to_string(), "Run all tox environments".to_string()));
+            for env in parse_tox_envs(&read_source(&tox_path)?) {
+                commands.push(tox_command(
+                    format!("tox -e {env}"),
+                    format!("Run the {env} tox environment"),
+                ));
+            }
+        }
+
+        let nox_path = dir.join("noxfile.py");
+        if nox_path.exists() {
+            let source = CommandSource::Python(nox_path.clone());
+            let nox_command = |cmd: String, description: String| {
+                let mut cmd = Command::new(&cmd, &cmd)
+                    .with_description(description)
+                    .with_source(source.clone())
+                    .with_working_dir(dir)
+                    .with_tags(vec!["python".to_string(), "nox".to_string(), "test".to_string()]);
+                cmd.metadata.insert(META_SOURCE_FILE.to_string(), nox_path.display().to_string());
+                cmd
+            };
+
+            commands
+                .push(nox_command("nox".to_string(), "Run the default nox sessions".to_string()));
+            for session in parse_nox_sessions(&read_source(&nox_path)?) {
+                commands.push(nox_command(
+                    format!("nox -s {session}"),
+                    format!("Run the {session} nox session"),
+                ));
+            }
+        }
+
+        Ok(commands)
+    }
+}
+
+/// Parse the environments of a tox.ini.
+///
+/// Environments from `envlist` come first, in order, with factor groups
+/// like `py{38,39}` expanded. Environments that only have their own
+/// `[testenv:name]` section follow.
+fn parse_tox_envs(content: &str) -> Vec<String> {
+    let mut named: Vec<String> = Vec::new();
+    let mut envlist: Option<String> = None;
+    let mut section = String::new();
+    let mut in_envlist = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with(['#', ';']) {
+            continue;
+        }
+
+        // Continuation lines of a multi-line value are indented
+        if line.starts_with([' ', '\t']) {
+            if let Some(list) = envlist.as_mut().filter(|_| in_envlist) {
+                list.push('\n');
+                list.push_str(trimmed);
+            }
+            continue;
+        }
+        in_envlist = false;
+
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            section = name.trim().to_string();
+            if let Some(env) = section.strip_prefix("testenv:") {
+                // `[testenv:py{38,39}-lint]` configures several envs at once
+                named.extend(expand_factors(env.trim()));
+            }
+            continue;
+        }
+
+        if section == "tox" {
+            if let Some((key, value)) = trimmed.split_once('=') {
+                if matches!(key.trim(), "envlist" | "env_list") {
+                    envlist = Some(value.trim().to_string());
+                    in_envlist = true;
+                }
+            }
+        }
+    }
+
+    let mut envs: Vec<String> = envlist
+        .as_deref()
+        .map(split_envlist)
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|item| expand_factors(item))
+        .collect();
+    for env in named {
+        if !envs.contains(&env) {
+            envs.push(env);
+        }
+    }
+    envs
+}
+
+/// Split an envlist on commas and newlines outside `{...}` groups.
+fn split_envlist(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+
+    for c in list.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' | '\n' if depth == 0 => {
+                items.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(current);
+
+    items.into_iter().map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect()
+}
+
+/// Expand tox factor groups: `py{38,39}-django{32,40}` becomes four envs.
+///
+/// Only the first group is split per call; the rest are expanded
+/// recursively. Ranges like `py3{8-11}` are left as written.
+fn expand_factors(env: &str) -> Vec<String> {
+    let (Some(open), Some(close)) = (env.find('{'), env.find('}')) else {
+        return vec![env.to_string()];
+    };
+    if close < open {
+        return vec![env.to_string()];
+    }
+
+    let (prefix, suffix) = (&env[..open], &env[close + 1..]);
+    env[open + 1..close]
+        .split(',')
+        .map(str::trim)
+        .flat_map(|factor| expand_factors(&format!("{prefix}{factor}{suffix}")))
+        .collect()
+}
+
+/// Parse the session names of a noxfile.
+///
+/// A lightweight scan: each `@nox.session` decorator names the next
+/// function, unless it passes `name="..."`.
+fn parse_nox_sessions(content: &str) -> Vec<String> {
+    let mut sessions = Vec::new();
+    let mut decorator: Option<String> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("@nox.session") {
+            decorator = Some(trimmed.to_string());
+            continue;
+        }
+
+        let Some(ref mut text) = decorator else {
+            continue;
+        };
+
+        if let Some(rest) = trimmed.strip_prefix("def ") {
+            let function = rest.split('(').next().unwrap_or_default().trim();
+            let name = session_name_argument(text).unwrap_or_else(|| function.to_string());
+            if !name.is_empty() {
+                sessions.push(name);
+            }
+            decorator = None;
+        } else {
+            // Decorator arguments spanning several lines, or stacked decorators
+            text.push(' ');
+            text.push_str(trimmed);
+        }
+    }
+
+    sessions
+}
+
+/// Read the `name="..."` argument of a `@nox.session(...)` decorator.
+fn session_name_argument(decorator: &str) -> Option<String> {
+    let start = decorator.find("name")?;
+    let rest = decorator[start + "name".len()..].trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let value = &rest[1..];
+    Some(value[..value.find(quote)?].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOX_INI: &str = r"
+[tox]
+requires = tox>=4
+envlist =
+    py{38,39}-django{32,40}
+    lint, docs
+skip_missing_interpreters = true
+
+[testenv]
+commands = pytest
+
+[testenv:lint]
+commands = ruff check .
+
+[testenv:typecheck]
+commands = mypy .
+";
+
+    const NOXFILE: &str = r#"
+import nox
+
+
+@nox.session
+def tests(session):
+    session.run("pytest")
+
+
+@nox.session(python=["3.11", "3.12"])
+def lint(session):
+    session.run("ruff", "check", ".")
+
+
+@nox.session(
+    name="type-check",
+    python="3.12",
+)
+def typecheck(session):
+    session.run("mypy", ".")
+
+
+def helper():
+    pass
+"#;
+
+    #[test]
+    fn test_parse_tox_envs() {
+        let envs = parse_tox_envs(TOX_INI);
+        assert_eq!(
+            envs,
+            vec![
+                "py38-django32",
+                "py38-django40",
+                "py39-django32",
+                "py39-django40",
+                "lint",
+                "docs",
+                "typecheck",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_line_envlist() {
+        let envs = parse_tox_envs("[tox]\nenvlist = py311, py312,lint\n");
+        assert_eq!(envs, vec!["py311", "py312", "lint"]);
+        assert_eq!(expand_factors("py3{8-11}"), vec!["py38-11"]);
+        assert_eq!(expand_factors("lint"), vec!["lint"]);
+    }
+
+    #[test]
+    fn test_parse_nox_sessions() {
+        assert_eq!(parse_nox_sessions(NOXFILE), vec!["tests", "lint", "type-check"]);
+    }
+
+    #[test]
+    fn test_scan_tox_and_nox() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("tox.ini"), "[tox]\nenvlist = py312, lint\n").unwrap();
+        std::fs::write(temp.path().join("noxfile.py"), NOXFILE).unwrap();
+
+        let commands = ToxScanner.scan(temp.path()).unwrap();
+        let names: Vec<_> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "tox",
+                "tox -e py312",
+                "tox -e lint",
+                "nox",
+                "nox -s tests",
+                "nox -s lint",
+                "nox -s type-check",
+            ]
+        );
+        assert!(commands[1].tags.contains(&"tox".to_string()));
+        assert!(commands[4].metadata[META_SOURCE_FILE].ends_with("noxfile.py"));
+    }
+
+    #[test]
+    fn test_no_tox_or_nox() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let commands = ToxScanner.scan(temp.path()).unwrap();
+        assert_eq!(commands, Vec::new());
+    }
+}
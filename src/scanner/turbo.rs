@@ -7,7 +7,7 @@ use std::path::Path;
 
 use serde::Deserialize;
 
-use super::Scanner;
+use super::{read_source, Scanner};
 use crate::core::{Command, CommandSource};
 
 /// Scanner for Turborepo pipelines.
@@ -18,6 +18,14 @@ impl Scanner for TurboScanner {
         "turbo"
     }
 
+    fn source_files(&self) -> &[&'static str] {
+        &["turbo.json"]
+    }
+
+    fn rule(&self) -> &str {
+        "each task in `pipeline`, run with turbo"
+    }
+
     fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
         let mut commands = Vec::new();
 
@@ -134,7 +142,7 @@ struct PipelineTask {
 
 /// Parse turbo.json file.
 fn parse_turbo_json(path: &Path) -> anyhow::Result<TurboConfig> {
-    let content = std::fs::read_to_string(path)?;
+    let content = read_source(path)?;
     let config: TurboConfig = serde_json::from_str(&content)?;
     Ok(config)
 }
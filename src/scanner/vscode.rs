@@ -0,0 +1,568 @@
+//! VS Code scanner.
+//!
+//! Scans `.vscode/tasks.json` for tasks and `.vscode/launch.json` for
+//! launch configurations that can run outside the debugger.
+//! See: https://code.visualstudio.com/docs/editor/tasks
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::{read_source, Scanner, META_SOURCE_FILE};
+use crate::core::{shell_quote, Command, CommandSource};
+
+/// Scanner for VS Code tasks and launch configurations.
+pub struct VsCodeScanner;
+
+impl Scanner for VsCodeScanner {
+    fn name(&self) -> &str {
+        "vscode"
+    }
+
+    fn source_files(&self) -> &[&'static str] {
+        &[".vscode/tasks.json", ".vscode/launch.json"]
+    }
+
+    fn rule(&self) -> &str {
+        "each task in tasks.json, plus launch.json configurations with a program or module"
+    }
+
+    fn scan(&self, dir: &Path) -> anyhow::Result<Vec<Command>> {
+        let mut commands = Vec::new();
+
+        // Each file is scanned on its own, so a broken one doesn't hide the other
+        let tasks_path = dir.join(".vscode/tasks.json");
+        if tasks_path.exists() {
+            match read_jsonc::<TasksFile>(&tasks_path) {
+                Ok(tasks) => commands.extend(
+                    tasks.tasks.iter().filter_map(|task| task_command(task, dir, &tasks_path)),
+                ),
+                Err(e) => tracing::warn!("Skipping {}: {e}", tasks_path.display()),
+            }
+        }
+
+        let launch_path = dir.join(".vscode/launch.json");
+        if launch_path.exists() {
+            match read_jsonc::<LaunchFile>(&launch_path) {
+                Ok(launch) => commands.extend(
+                    launch
+                        .configurations
+                        .iter()
+                        .filter_map(|config| launch_command(config, dir, &launch_path)),
+                ),
+                Err(e) => tracing::warn!("Skipping {}: {e}", launch_path.display()),
+            }
+        }
+
+        Ok(commands)
+    }
+}
+
+/// Read and parse a JSON-with-comments file.
+fn read_jsonc<T: serde::de::DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    Ok(serde_json::from_str(&strip_jsonc(&read_source(path)?))?)
+}
+
+/// `.vscode/tasks.json`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TasksFile {
+    tasks: Vec<Task>,
+}
+
+/// A task in tasks.json.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Task {
+    label: Option<String>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    command: Option<String>,
+    args: Vec<TaskArg>,
+    detail: Option<String>,
+    /// Script name for `"type": "npm"` tasks
+    script: Option<String>,
+    options: TaskOptions,
+}
+
+/// An argument, either a plain string or `{ "value": ..., "quoting": ... }`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum TaskArg {
+    Plain(String),
+    Quoted { value: String },
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TaskOptions {
+    cwd: Option<String>,
+    env: HashMap<String, String>,
+}
+
+/// `.vscode/launch.json`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct LaunchFile {
+    configurations: Vec<LaunchConfig>,
+}
+
+/// A launch configuration.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct LaunchConfig {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    request: Option<String>,
+    program: Option<String>,
+    module: Option<String>,
+    args: Vec<String>,
+    cwd: Option<String>,
+    env: HashMap<String, String>,
+}
+
+/// Build a command for a task.
+///
+/// Shell and process tasks run their `command` with `args`; npm tasks run
+/// their script. Tasks provided by extensions have no command line and
+/// are skipped.
+fn task_command(task: &Task, dir: &Path, file: &Path) -> Option<Command> {
+    let label = task.label.as_deref()?;
+
+    let line = match (task.kind.as_deref(), &task.command, &task.script) {
+        (Some("npm"), _, Some(script)) => format!("npm run {script}"),
+        (_, Some(command), _) => {
+            let args = task.args.iter().map(|arg| match arg {
+                TaskArg::Plain(value) | TaskArg::Quoted { value } => quote_variables(value, dir),
+            });
+            std::iter::once(resolve_variables(command, dir))
+                .chain(args)
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+        _ => return None,
+    };
+
+    let mut cmd = Command::new(label, line)
+        .with_source(CommandSource::VsCode(file.to_path_buf()))
+        .with_tags(vec!["vscode".to_string(), "task".to_string()]);
+    if let Some(ref detail) = task.detail {
+        cmd = cmd.with_description(detail);
+    }
+    Some(with_options(cmd, task.options.cwd.as_deref(), &task.options.env, dir, file))
+}
+
+/// Build a command for a launch configuration, if it can run without a debugger.
+///
+/// Node and Python configurations that launch a program or module are
+/// runnable; attach requests and other debugger types are skipped.
+fn launch_command(config: &LaunchConfig, dir: &Path, file: &Path) -> Option<Command> {
+    if config.request.as_deref() == Some("attach") {
+        return None;
+    }
+
+    let program = config.program.as_deref().map(|p| quote_variables(p, dir));
+    let mut line = match (config.kind.as_str(), program, &config.module) {
+        ("node" | "pwa-node", Some(program), _) => format!("node {program}"),
+        ("python" | "debugpy", Some(program), _) => format!("python {program}"),
+        ("python" | "debugpy", None, Some(module)) => format!("python -m {module}"),
+        _ => return None,
+    };
+    for arg in &config.args {
+        line.push(' ');
+        line.push_str(&quote_variables(arg, dir));
+    }
+
+    let cmd = Command::new(format!("launch: {}", config.name), line)
+        .with_description(format!("Run the \"{}\" launch configuration", config.name))
+        .with_source(CommandSource::VsCode(file.to_path_buf()))
+        .with_tags(vec!["vscode".to_string(), "launch".to_string()]);
+    Some(with_options(cmd, config.cwd.as_deref(), &config.env, dir, file))
+}
+
+/// Apply the working directory and environment, and record the source file.
+///
+/// Environment values that reference other variables are assigned on the
+/// command line, so the shell expands them when the command runs.
+fn with_options(
+    mut cmd: Command,
+    cwd: Option<&str>,
+    env: &HashMap<String, String>,
+    dir: &Path,
+    file: &Path,
+) -> Command {
+    if let Some(cwd) = cwd {
+        cmd = cmd.with_working_dir(dir.join(resolve_path(cwd, dir)));
+    }
+
+    let mut env: Vec<_> = env.iter().collect();
+    env.sort();
+    let mut assignments = String::new();
+    for (key, value) in env {
+        let parts = parse_variables(value, dir);
+        if parts.iter().any(|part| matches!(part, Part::Env(_))) {
+            assignments.push_str(&env_assignment(key, &parts));
+        } else {
+            cmd = cmd.with_env(key, join_parts(&parts, |text| text.to_string(), env_reference));
+        }
+    }
+    if !assignments.is_empty() {
+        cmd.command = format!("{assignments}{}", cmd.command);
+    }
+
+    // Both files share one scanner, so record which one this came from
+    cmd.metadata.insert(META_SOURCE_FILE.to_string(), file.display().to_string());
+    cmd
+}
+
+/// A piece of a value after VS Code variables are resolved.
+#[derive(Debug, PartialEq, Eq)]
+enum Part {
+    /// Literal text, including resolved workspace variables
+    Text(String),
+    /// `${env:NAME}`, left for the shell to expand at run time
+    Env(String),
+}
+
+/// Resolve common VS Code variables, keeping `${env:NAME}` as a reference.
+///
+/// Supports `${workspaceFolder}`, `${workspaceRoot}`,
+/// `${workspaceFolderBasename}`, `${userHome}`, `${env:NAME}`,
+/// `${pathSeparator}` and `${/}`. Editor-specific variables like
+/// `${file}` are left as they are.
+fn parse_variables(value: &str, workspace: &Path) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut text = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        text.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let variable = &rest[start + 2..start + end];
+        if let Some(name) = variable.strip_prefix("env:") {
+            if !text.is_empty() {
+                parts.push(Part::Text(std::mem::take(&mut text)));
+            }
+            parts.push(Part::Env(name.to_string()));
+        } else {
+            match variable_value(variable, workspace) {
+                Some(resolved) => text.push_str(&resolved),
+                None => text.push_str(&rest[start..=start + end]),
+            }
+        }
+        rest = &rest[start + end + 1..];
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    parts
+}
+
+/// Join resolved parts, formatting text and environment references.
+fn join_parts(
+    parts: &[Part],
+    text: impl Fn(&str) -> String,
+    env: impl Fn(&str) -> String,
+) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            Part::Text(value) => text(value),
+            Part::Env(name) => env(name),
+        })
+        .collect()
+}
+
+/// Resolve variables for use as a bare word on the command line.
+fn resolve_variables(value: &str, workspace: &Path) -> String {
+    join_parts(&parse_variables(value, workspace), |text| text.to_string(), env_reference)
+}
+
+/// Resolve variables for use as a single quoted argument.
+fn quote_variables(value: &str, workspace: &Path) -> String {
+    let parts = parse_variables(value, workspace);
+    if parts.is_empty() {
+        return shell_quote("");
+    }
+    join_parts(&parts, shell_quote, quoted_env_reference)
+}
+
+/// Resolve variables in a path, which is used without a shell, so
+/// environment variables are read while scanning.
+fn resolve_path(value: &str, workspace: &Path) -> String {
+    join_parts(
+        &parse_variables(value, workspace),
+        |text| text.to_string(),
+        |name| std::env::var(name).unwrap_or_default(),
+    )
+}
+
+/// A shell reference to an environment variable.
+#[cfg(not(windows))]
+fn env_reference(name: &str) -> String {
+    format!("${{{name}}}")
+}
+
+/// A shell reference to an environment variable.
+#[cfg(windows)]
+fn env_reference(name: &str) -> String {
+    format!("%{name}%")
+}
+
+/// An environment reference that expands to a single argument.
+#[cfg(not(windows))]
+fn quoted_env_reference(name: &str) -> String {
+    format!("\"${{{name}}}\"")
+}
+
+/// An environment reference that expands to a single argument.
+#[cfg(windows)]
+fn quoted_env_reference(name: &str) -> String {
+    env_reference(name)
+}
+
+/// A command-line prefix that sets `key` for the command.
+#[cfg(not(windows))]
+fn env_assignment(key: &str, parts: &[Part]) -> String {
+    format!("{key}={} ", join_parts(parts, shell_quote, quoted_env_reference))
+}
+
+/// A command-line prefix that sets `key` for the command.
+#[cfg(windows)]
+fn env_assignment(key: &str, parts: &[Part]) -> String {
+    format!("set \"{key}={}\" && ", join_parts(parts, |text| text.to_string(), env_reference))
+}
+
+fn variable_value(variable: &str, workspace: &Path) -> Option<String> {
+    match variable {
+        "workspaceFolder" | "workspaceRoot" => Some(workspace.display().to_string()),
+        "workspaceFolderBasename" => {
+            workspace.file_name().map(|name| name.to_string_lossy().into_owned())
+        }
+        "userHome" => dirs::home_dir().map(|home| home.display().to_string()),
+        "pathSeparator" | "/" => Some(std::path::MAIN_SEPARATOR.to_string()),
+        _ => None,
+    }
+}
+
+/// Convert JSON with comments (JSONC) to plain JSON.
+///
+/// Removes `//` and `/* */` comments and trailing commas, leaving string
+/// contents untouched.
+fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push(c);
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    remove_trailing_commas(&out)
+}
+
+/// Drop commas that directly precede `}` or `]` (outside strings).
+fn remove_trailing_commas(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in json.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' && json[i + 1..].trim_start().starts_with(['}', ']']) {
+            continue;
+        }
+        out.push(c);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TASKS_JSON: &str = r#"{
+        // See https://go.microsoft.com/fwlink/?LinkId=733558
+        "version": "2.0.0",
+        "tasks": [
+            {
+                "label": "build",
+                "type": "shell",
+                "command": "cargo",
+                "args": ["build", "--manifest-path", "${workspaceFolder}/Cargo.toml"],
+                "detail": "Build the workspace",
+                "group": { "kind": "build", "isDefault": true },
+            },
+            {
+                "label": "Run tests",
+                "type": "process",
+                "command": "pytest",
+                "args": [{ "value": "tests/unit tests", "quoting": "strong" }, "-k", "not slow"],
+                "options": { "cwd": "${workspaceFolder}/api", "env": { "MODE": "ci" } }
+            },
+            /* provided by an extension, no command line */
+            { "label": "tsc: watch", "type": "typescript", "tsconfig": "tsconfig.json" },
+        ],
+    }"#;
+
+    fn scan(files: &[(&str, &str)]) -> (tempfile::TempDir, Vec<Command>) {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".vscode")).unwrap();
+        for (name, content) in files {
+            std::fs::write(temp.path().join(".vscode").join(name), content).unwrap();
+        }
+        let commands = VsCodeScanner.scan(temp.path()).unwrap();
+        (temp, commands)
+    }
+
+    #[test]
+    fn test_tasks_with_args() {
+        let (temp, commands) = scan(&[("tasks.json", TASKS_JSON)]);
+        let root = temp.path().display().to_string();
+        assert_eq!(commands.len(), 2);
+
+        let build = &commands[0];
+        assert_eq!(build.name, "build");
+        assert_eq!(build.command, format!("cargo build --manifest-path {root}/Cargo.toml"));
+        assert_eq!(build.description.as_deref(), Some("Build the workspace"));
+        assert!(build.tags.contains(&"vscode".to_string()));
+
+        let test = &commands[1];
+        assert_eq!(test.name, "Run tests");
+        assert_eq!(test.command, "pytest 'tests/unit tests' -k 'not slow'");
+        assert_eq!(test.working_dir, Some(temp.path().join("api")));
+        assert_eq!(test.env, vec![("MODE".to_string(), "ci".to_string())]);
+    }
+
+    #[test]
+    fn test_npm_task() {
+        let (_temp, commands) = scan(&[(
+            "tasks.json",
+            r#"{"tasks": [{"type": "npm", "script": "lint", "label": "npm: lint"}]}"#,
+        )]);
+        assert_eq!(commands[0].name, "npm: lint");
+        assert_eq!(commands[0].command, "npm run lint");
+    }
+
+    #[test]
+    fn test_launch_configurations() {
+        let launch = r#"{
+            "version": "0.2.0",
+            "configurations": [
+                { "name": "Server", "type": "node", "request": "launch",
+                  "program": "${workspaceFolder}/server.js", "args": ["--port", "3000"] },
+                { "name": "CLI", "type": "debugpy", "request": "launch", "module": "app.cli" },
+                { "name": "Attach", "type": "node", "request": "attach", "port": 9229 },
+                { "name": "Chrome", "type": "chrome", "request": "launch", "url": "http://localhost" }
+            ]
+        }"#;
+        let (temp, commands) = scan(&[("launch.json", launch)]);
+        let root = temp.path().display();
+
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].name, "launch: Server");
+        assert_eq!(commands[0].command, format!("node {root}/server.js --port 3000"));
+        assert_eq!(commands[1].command, "python -m app.cli");
+        assert!(commands[1].metadata[META_SOURCE_FILE].ends_with("launch.json"));
+    }
+
+    #[test]
+    fn test_resolve_variables() {
+        let workspace = Path::new("/home/dev/project");
+
+        assert_eq!(resolve_variables("${workspaceFolder}/src", workspace), "/home/dev/project/src");
+        assert_eq!(resolve_variables("${workspaceFolderBasename}", workspace), "project");
+        assert_eq!(resolve_variables("${env:API_TOKEN}", workspace), "${API_TOKEN}");
+        assert_eq!(
+            resolve_variables("${file} ${unterminated", workspace),
+            "${file} ${unterminated"
+        );
+        assert_eq!(quote_variables("${env:HOME}/my dir", workspace), "\"${HOME}\"'/my dir'");
+        assert_eq!(quote_variables("", workspace), "''");
+    }
+
+    #[test]
+    #[allow(clippy::literal_string_with_formatting_args)] // VS Code `${env:NAME}` syntax
+    fn test_env_variables_are_not_expanded_while_scanning() {
+        let tasks = r#"{"tasks": [{
+            "label": "deploy",
+            "command": "deploy",
+            "args": ["--token", "${env:DEPLOY_TOKEN}"],
+            "options": { "env": { "AUTH": "Bearer ${env:DEPLOY_TOKEN}", "MODE": "ci" } }
+        }]}"#;
+        let (_temp, commands) = scan(&[("tasks.json", tasks)]);
+
+        assert_eq!(
+            commands[0].command,
+            "AUTH='Bearer '\"${DEPLOY_TOKEN}\" deploy --token \"${DEPLOY_TOKEN}\""
+        );
+        assert_eq!(commands[0].env, vec![("MODE".to_string(), "ci".to_string())]);
+    }
+
+    #[test]
+    fn test_broken_file_does_not_hide_the_other() {
+        let launch = r#"{"configurations": [
+            { "name": "Server", "type": "node", "request": "launch", "program": "server.js" }
+        ]}"#;
+        let (_temp, commands) = scan(&[("tasks.json", "{ not json"), ("launch.json", launch)]);
+
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].command, "node server.js");
+    }
+
+    #[test]
+    fn test_strip_jsonc() {
+        let jsonc =
+            "{\n  // comment\n  \"url\": \"http://x//y\", /* block */\n  \"list\": [1, 2,],\n}";
+        let value: serde_json::Value = serde_json::from_str(&strip_jsonc(jsonc)).unwrap();
+        assert_eq!(value["url"], "http://x//y");
+        assert_eq!(value["list"], serde_json::json!([1, 2]));
+    }
+}
@@ -5,6 +5,8 @@
 //! - Environment variable sanitization
 //! - File permission verification
 //! - Path traversal prevention
+//! - Redaction of secret values from output and logs
+//! - One-shot audits of commands, `.env` files, and Git hooks
 //!
 //! # Security Philosophy
 //!
@@ -14,14 +16,21 @@
 //! 3. **Least Privilege**: File permissions are checked to ensure secure defaults
 //! 4. **Fail-Safe Defaults**: When in doubt, deny access
 
+mod audit;
 mod permissions;
+mod redaction;
 mod sanitization;
 mod secrets;
 mod validation;
 
+pub use audit::{credential_kind, shannon_entropy, AuditFinding, AuditReport, FindingKind};
 pub use permissions::{
     FilePermissions, PermissionCheck, PermissionError, PermissionLevel, SecureFileChecker,
 };
+pub use redaction::{
+    disable_redaction, enable_redaction, redact, redaction_enabled, register_secret,
+    RedactingWriter, Redactor, REDACTED,
+};
 pub use sanitization::{
     EnvSanitizer, SanitizationOptions, SanitizationResult, SanitizedEnv, SensitivePattern,
 };
@@ -33,6 +42,8 @@ pub use validation::{
 
 use std::path::Path;
 
+use crate::core::CommandSource;
+
 /// Security configuration for Palrun.
 #[derive(Debug, Clone)]
 pub struct SecurityConfig {
@@ -56,6 +67,10 @@ pub struct SecurityConfig {
 
     /// Custom blocked patterns
     pub custom_blocked_patterns: Vec<String>,
+
+    /// Whether commands declared by the project (scripts, make targets, ...)
+    /// skip pattern checks and only get length checks
+    pub trust_project_commands: bool,
 }
 
 impl Default for SecurityConfig {
@@ -68,6 +83,7 @@ impl Default for SecurityConfig {
             max_command_length: 10_000,
             max_env_vars: 1_000,
             custom_blocked_patterns: Vec::new(),
+            trust_project_commands: false,
         }
     }
 }
@@ -83,6 +99,7 @@ impl SecurityConfig {
             max_command_length: 100_000,
             max_env_vars: 10_000,
             custom_blocked_patterns: Vec::new(),
+            trust_project_commands: false,
         }
     }
 
@@ -96,6 +113,7 @@ impl SecurityConfig {
             max_command_length: 5_000,
             max_env_vars: 500,
             custom_blocked_patterns: Vec::new(),
+            trust_project_commands: false,
         }
     }
 
@@ -105,6 +123,13 @@ impl SecurityConfig {
         self.custom_blocked_patterns.push(pattern.into());
         self
     }
+
+    /// Set whether project-declared commands are trusted.
+    #[must_use]
+    pub fn with_trusted_project_commands(mut self, trust: bool) -> Self {
+        self.trust_project_commands = trust;
+        self
+    }
 }
 
 /// Security manager that coordinates all security checks.
@@ -143,18 +168,39 @@ impl SecurityManager {
             return ValidationResult::ok();
         }
 
-        // Check length
-        if command.len() > self.config.max_command_length {
-            return ValidationResult::error(
+        if let Some(result) = self.check_length(command) {
+            return result;
+        }
+
+        self.validator.validate(command)
+    }
+
+    /// Validate a command, taking into account where it came from.
+    ///
+    /// AI-generated and plugin commands always get full validation. When
+    /// `trust_project_commands` is set, commands from any other source only
+    /// get the length check.
+    pub fn validate_with_source(&self, command: &str, source: &CommandSource) -> ValidationResult {
+        if self.config.trust_project_commands && !source.is_untrusted() {
+            if !self.config.strict_validation {
+                return ValidationResult::ok();
+            }
+            return self.check_length(command).unwrap_or_else(ValidationResult::ok);
+        }
+
+        self.validate_command(command)
+    }
+
+    fn check_length(&self, command: &str) -> Option<ValidationResult> {
+        (command.len() > self.config.max_command_length).then(|| {
+            ValidationResult::error(
                 ValidationError::CommandTooLong {
                     length: command.len(),
                     max: self.config.max_command_length,
                 },
                 ValidationSeverity::High,
-            );
-        }
-
-        self.validator.validate(command)
+            )
+        })
     }
 
     /// Sanitize environment variables before passing to a child process.
@@ -281,6 +327,34 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_validate_with_source() {
+        let trusting =
+            SecurityManager::new(SecurityConfig::default().with_trusted_project_commands(true));
+        let cargo = CommandSource::Cargo(std::path::PathBuf::from("Cargo.toml"));
+
+        let script = "rm -rf target && rm -rf /tmp/build-cache";
+
+        assert!(!trusting.validate_with_source("rm -rf /", &CommandSource::AiGenerated).is_safe());
+        assert!(!trusting.validate_with_source(script, &CommandSource::AiGenerated).is_safe());
+        assert!(!trusting
+            .validate_with_source(script, &CommandSource::Plugin { name: "x".to_string() })
+            .is_safe());
+        assert!(trusting.validate_with_source(script, &cargo).is_safe());
+
+        // Length limits still apply to trusted commands
+        let short = SecurityManager::new(SecurityConfig {
+            max_command_length: 10,
+            trust_project_commands: true,
+            ..Default::default()
+        });
+        assert!(!short.validate_with_source("cargo build --release", &cargo).is_safe());
+
+        // Without the toggle every source is validated
+        let strict = SecurityManager::with_defaults();
+        assert!(!strict.validate_with_source(script, &cargo).is_safe());
+    }
+
     #[test]
     fn test_permissive_skips_validation() {
         let manager = SecurityManager::new(SecurityConfig::permissive());
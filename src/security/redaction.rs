@@ -0,0 +1,219 @@
+//! Redaction of known secret values from output and logs.
+//!
+//! When enabled (`security.redact_output`), secret values are replaced with
+//! `****` in captured and streamed command output, AI prompts and responses,
+//! and log lines. Output a command writes straight to the terminal is not
+//! seen by Palrun and cannot be redacted.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::RwLock;
+
+use super::EnvSanitizer;
+
+/// Replacement for redacted values.
+pub const REDACTED: &str = "****";
+
+/// Values shorter than this are not redacted, to avoid mangling output
+/// with common short strings like `1` or `true`.
+const MIN_SECRET_LEN: usize = 6;
+
+/// Environment variables whose names look sensitive but hold paths.
+const NON_SECRET_VARS: &[&str] = &["PWD", "OLDPWD"];
+
+/// The active redactor, if redaction is enabled.
+static ACTIVE: RwLock<Option<Redactor>> = RwLock::new(None);
+
+/// A set of secret values to scrub from text.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    /// Secret values, longest first so overlapping values are fully masked
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    /// Create a redactor for the given secret values.
+    pub fn new(secrets: impl IntoIterator<Item = String>) -> Self {
+        let mut redactor = Self::default();
+        for secret in secrets {
+            redactor.add(secret);
+        }
+        redactor
+    }
+
+    /// Create a redactor for the values of sensitive environment variables.
+    pub fn from_env() -> Self {
+        let sanitizer = EnvSanitizer::new();
+        Self::new(
+            std::env::vars()
+                .filter(|(name, _)| {
+                    sanitizer.is_sensitive(name) && !NON_SECRET_VARS.contains(&name.as_str())
+                })
+                .map(|(_, value)| value),
+        )
+    }
+
+    /// Also redact the values of sensitive variables in `root`'s `.env` files.
+    #[must_use]
+    pub fn with_dotenv(mut self, root: &Path) -> Self {
+        let mut manager = crate::env::EnvManager::new(root);
+        let files: Vec<_> = manager
+            .scan()
+            .map(|files| files.iter().map(|file| file.path.clone()).collect())
+            .unwrap_or_default();
+        for file in files {
+            for var in manager.preview_env_file(&file).unwrap_or_default() {
+                if var.is_sensitive {
+                    self.add(var.value);
+                }
+            }
+        }
+        self
+    }
+
+    /// Add a secret value.
+    pub fn add(&mut self, secret: impl Into<String>) {
+        let secret = secret.into();
+        if secret.len() < MIN_SECRET_LEN || self.secrets.contains(&secret) {
+            return;
+        }
+        self.secrets.push(secret);
+        self.secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    }
+
+    /// Number of secret values known.
+    pub fn len(&self) -> usize {
+        self.secrets.len()
+    }
+
+    /// Check if no secret values are known.
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    /// Replace every known secret value in `text` with `****`.
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut result = Cow::Borrowed(text);
+        for secret in &self.secrets {
+            if result.contains(secret.as_str()) {
+                result = Cow::Owned(result.replace(secret.as_str(), REDACTED));
+            }
+        }
+        result
+    }
+}
+
+/// Enable redaction with the given secret values.
+pub fn enable_redaction(redactor: Redactor) {
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = Some(redactor);
+    }
+}
+
+/// Disable redaction, returning the redactor that was active.
+pub fn disable_redaction() -> Option<Redactor> {
+    ACTIVE.write().ok().and_then(|mut active| active.take())
+}
+
+/// Check whether redaction is enabled.
+pub fn redaction_enabled() -> bool {
+    ACTIVE.read().is_ok_and(|active| active.is_some())
+}
+
+/// Add a secret value to the active redactor, e.g. one resolved at runtime.
+///
+/// Does nothing when redaction is disabled.
+pub fn register_secret(secret: impl Into<String>) {
+    if let Ok(mut active) = ACTIVE.write() {
+        if let Some(redactor) = active.as_mut() {
+            redactor.add(secret);
+        }
+    }
+}
+
+/// Redact `text` with the active redactor; returns it unchanged when disabled.
+pub fn redact(text: &str) -> Cow<'_, str> {
+    match ACTIVE.read() {
+        Ok(active) => match active.as_ref() {
+            Some(redactor) => Cow::Owned(redactor.redact(text).into_owned()),
+            None => Cow::Borrowed(text),
+        },
+        Err(_) => Cow::Borrowed(text),
+    }
+}
+
+/// Stderr writer for `tracing` that redacts each log line.
+#[derive(Debug, Default)]
+pub struct RedactingWriter;
+
+impl RedactingWriter {
+    /// Create a writer; usable as a `tracing_subscriber` `MakeWriter`.
+    pub fn stderr() -> Self {
+        Self
+    }
+}
+
+impl Write for RedactingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        io::stderr().write_all(redact(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacts_known_values() {
+        let redactor = Redactor::new(["sk-live-abc123".to_string()]);
+        assert_eq!(redactor.redact("key=sk-live-abc123 ok"), "key=**** ok");
+        assert_eq!(redactor.redact("nothing here"), "nothing here");
+    }
+
+    #[test]
+    fn test_log_line_keeps_surrounding_text() {
+        let url = "postgres://app:hunter22@db:5432/app";
+        let redactor = Redactor::new([url.to_string(), "app".to_string()]);
+
+        let line = format!("2024-01-01 INFO connecting to {url} (attempt 1)");
+        assert_eq!(redactor.redact(&line), "2024-01-01 INFO connecting to **** (attempt 1)");
+    }
+
+    #[test]
+    fn test_short_values_are_ignored() {
+        let redactor = Redactor::new(["1".to_string(), "true".to_string()]);
+        assert!(redactor.is_empty());
+        assert_eq!(redactor.redact("debug=true level=1"), "debug=true level=1");
+    }
+
+    #[test]
+    fn test_dotenv_secrets_are_redacted() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join(".env"),
+            "DATABASE_URL=postgres://app:hunter22@db/app\nAPI_TOKEN=tok-123456\nPORT=5432\n",
+        )
+        .unwrap();
+
+        let redactor = Redactor::default().with_dotenv(temp.path());
+        assert_eq!(redactor.len(), 2);
+        assert_eq!(
+            redactor.redact("url=postgres://app:hunter22@db/app token=tok-123456 port=5432"),
+            "url=**** token=**** port=5432"
+        );
+    }
+
+    #[test]
+    fn test_longest_value_wins() {
+        let redactor = Redactor::new(["secret".to_string(), "secret-extended".to_string()]);
+        assert_eq!(redactor.len(), 2);
+        assert_eq!(redactor.redact("a=secret-extended b=secret"), "a=**** b=****");
+    }
+}
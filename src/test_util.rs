@@ -0,0 +1,86 @@
+//! Helpers shared by unit tests.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+/// A request received by [`mock_http`].
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// Request method, e.g. `GET`
+    pub method: String,
+
+    /// Request target: the path and query
+    pub target: String,
+
+    /// Headers, with lowercase names
+    pub headers: HashMap<String, String>,
+
+    /// Request body
+    pub body: String,
+}
+
+/// Serve HTTP on a local port, one request per connection.
+///
+/// `reply` gets each request and its index and returns the raw HTTP
+/// response, e.g. from [`http_response`]. Returns the server's base URL
+/// and every request received so far.
+pub fn mock_http(
+    reply: impl Fn(&MockRequest, usize) -> String + Send + 'static,
+) -> (String, Arc<Mutex<Vec<MockRequest>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&requests);
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { return };
+            let mut reader = BufReader::new(stream);
+
+            let mut line = String::new();
+            let _ = reader.read_line(&mut line);
+            let mut parts = line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let target = parts.next().unwrap_or_default().to_string();
+
+            let mut headers = HashMap::new();
+            loop {
+                line.clear();
+                if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.insert(name.to_ascii_lowercase(), value.trim().to_string());
+                }
+            }
+            let length = headers.get("content-length").and_then(|v| v.parse().ok());
+            let mut body = vec![0; length.unwrap_or(0)];
+            let _ = reader.read_exact(&mut body);
+
+            let request = MockRequest {
+                method,
+                target,
+                headers,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            };
+            let index = {
+                let mut log = log.lock().unwrap();
+                log.push(request.clone());
+                log.len() - 1
+            };
+            let _ = reader.get_mut().write_all(reply(&request, index).as_bytes());
+        }
+    });
+
+    (base, requests)
+}
+
+/// A complete HTTP response; `headers` are extra `Name: value\r\n` lines.
+pub fn http_response(status: u16, headers: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status} Mock\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
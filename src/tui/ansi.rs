@@ -0,0 +1,274 @@
+//! ANSI escape sequence rendering for captured output.
+//!
+//! Converts SGR color/style codes into ratatui spans and collapses
+//! carriage-return progress updates so only the final state is shown.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Convert text containing ANSI escape codes into styled lines.
+///
+/// `base` is the style used for uncolored text and restored on `ESC[0m`.
+/// Styles carry across line breaks like they do in a terminal.
+pub fn ansi_to_lines(text: &str, base: Style) -> Vec<Line<'static>> {
+    let mut style = base;
+    text.lines()
+        .map(|line| {
+            let line = last_carriage_return_segment(line);
+            let (spans, end_style) = parse_line(line, base, style);
+            style = end_style;
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Keep only what a terminal would show after `\r` overwrites.
+///
+/// Progress bars redraw by returning to column 0, so the last non-empty
+/// segment is the final state of the line.
+fn last_carriage_return_segment(line: &str) -> &str {
+    if !line.contains('\r') {
+        return line;
+    }
+    line.rsplit('\r').find(|segment| has_visible_text(segment)).unwrap_or("")
+}
+
+/// Check whether a segment contains anything besides escape sequences.
+fn has_visible_text(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            skip_escape(&mut chars);
+        } else if !c.is_control() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Parse one line into spans, starting from `style`.
+fn parse_line(line: &str, base: Style, mut style: Style) -> (Vec<Span<'static>>, Style) {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            if c == '\t' || !c.is_control() {
+                current.push(c);
+            }
+            continue;
+        }
+
+        if chars.peek() == Some(&'[') {
+            chars.next();
+            let mut params = String::new();
+            let mut command = None;
+            for c in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    command = Some(c);
+                    break;
+                }
+                params.push(c);
+            }
+
+            // Only SGR (`m`) changes appearance; cursor/erase codes are dropped
+            if command == Some('m') {
+                if !current.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut current), style));
+                }
+                style = apply_sgr(&params, base, style);
+            }
+        } else {
+            skip_escape(&mut chars);
+        }
+    }
+
+    if !current.is_empty() {
+        spans.push(Span::styled(current, style));
+    }
+
+    (spans, style)
+}
+
+/// Skip the remainder of a non-CSI escape sequence (e.g. OSC hyperlinks).
+fn skip_escape(chars: &mut impl Iterator<Item = char>) {
+    match chars.next() {
+        // CSI: parameters until a final byte
+        Some('[') => {
+            for c in chars.by_ref() {
+                if ('\x40'..='\x7e').contains(&c) {
+                    break;
+                }
+            }
+        }
+        // OSC: terminated by BEL or ST (ESC \)
+        Some(']') => {
+            while let Some(c) = chars.next() {
+                if c == '\x07' {
+                    break;
+                }
+                if c == '\x1b' {
+                    chars.next();
+                    break;
+                }
+            }
+        }
+        // Two-character sequences (ESC 7, ESC =, ...)
+        _ => {}
+    }
+}
+
+/// Apply SGR parameters (the `1;31` in `ESC[1;31m`) to a style.
+fn apply_sgr(params: &str, base: Style, mut style: Style) -> Style {
+    let codes: Vec<u16> = if params.is_empty() {
+        vec![0]
+    } else {
+        params.split(';').map(|p| p.parse().unwrap_or(0)).collect()
+    };
+
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => style = base,
+            1 => style = style.add_modifier(Modifier::BOLD),
+            2 => style = style.add_modifier(Modifier::DIM),
+            3 => style = style.add_modifier(Modifier::ITALIC),
+            4 => style = style.add_modifier(Modifier::UNDERLINED),
+            5 | 6 => style = style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style = style.add_modifier(Modifier::REVERSED),
+            9 => style = style.add_modifier(Modifier::CROSSED_OUT),
+            21 | 22 => style = style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style = style.remove_modifier(Modifier::ITALIC),
+            24 => style = style.remove_modifier(Modifier::UNDERLINED),
+            25 => style = style.remove_modifier(Modifier::SLOW_BLINK),
+            27 => style = style.remove_modifier(Modifier::REVERSED),
+            29 => style = style.remove_modifier(Modifier::CROSSED_OUT),
+            n @ 30..=37 => style = style.fg(basic_color(n - 30, false)),
+            n @ 90..=97 => style = style.fg(basic_color(n - 90, true)),
+            n @ 40..=47 => style = style.bg(basic_color(n - 40, false)),
+            n @ 100..=107 => style = style.bg(basic_color(n - 100, true)),
+            39 => style.fg = base.fg,
+            49 => style.bg = base.bg,
+            n @ (38 | 48) => {
+                let (color, used) = extended_color(&codes[i + 1..]);
+                if let Some(color) = color {
+                    style = if n == 38 { style.fg(color) } else { style.bg(color) };
+                }
+                i += used;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    style
+}
+
+/// Parse a 256-color (`5;n`) or truecolor (`2;r;g;b`) argument list.
+///
+/// Returns the color and how many codes were consumed.
+fn extended_color(args: &[u16]) -> (Option<Color>, usize) {
+    match args {
+        [5, n, ..] => (u8::try_from(*n).ok().map(Color::Indexed), 2),
+        [2, r, g, b, ..] => {
+            let channel = |v: u16| u8::try_from(v).unwrap_or(u8::MAX);
+            (Some(Color::Rgb(channel(*r), channel(*g), channel(*b))), 4)
+        }
+        _ => (None, args.len()),
+    }
+}
+
+/// Map an ANSI color index (0-7) to a ratatui color.
+const fn basic_color(index: u16, bright: bool) -> Color {
+    match (index, bright) {
+        (0, false) => Color::Black,
+        (1, false) => Color::Red,
+        (2, false) => Color::Green,
+        (3, false) => Color::Yellow,
+        (4, false) => Color::Blue,
+        (5, false) => Color::Magenta,
+        (6, false) => Color::Cyan,
+        (7, false) => Color::Gray,
+        (0, true) => Color::DarkGray,
+        (1, true) => Color::LightRed,
+        (2, true) => Color::LightGreen,
+        (3, true) => Color::LightYellow,
+        (4, true) => Color::LightBlue,
+        (5, true) => Color::LightMagenta,
+        (6, true) => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> Style {
+        Style::default().fg(Color::White)
+    }
+
+    #[test]
+    fn test_colored_cargo_line() {
+        let lines = ansi_to_lines(
+            "\x1b[1m\x1b[32m   Compiling\x1b[0m palrun v0.3.0\n\x1b[1;31merror\x1b[0m: oops",
+            base(),
+        );
+
+        assert_eq!(lines.len(), 2);
+        let spans = &lines[0].spans;
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].content, "   Compiling");
+        assert_eq!(spans[0].style, base().fg(Color::Green).add_modifier(Modifier::BOLD));
+        assert_eq!(spans[1].content, " palrun v0.3.0");
+        assert_eq!(spans[1].style, base());
+
+        let spans = &lines[1].spans;
+        assert_eq!(spans[0].content, "error");
+        assert_eq!(spans[0].style, base().fg(Color::Red).add_modifier(Modifier::BOLD));
+        assert_eq!(spans[1].content, ": oops");
+    }
+
+    #[test]
+    fn test_extended_colors() {
+        let lines =
+            ansi_to_lines("\x1b[38;5;208mwarn\x1b[48;2;10;20;30m bg\x1b[39;49m plain", base());
+        let spans = &lines[0].spans;
+
+        assert_eq!(spans[0].style.fg, Some(Color::Indexed(208)));
+        assert_eq!(spans[1].style.bg, Some(Color::Rgb(10, 20, 30)));
+        assert_eq!(spans[2].style.fg, Some(Color::White));
+        assert_eq!(spans[2].style.bg, None);
+    }
+
+    #[test]
+    fn test_style_carries_across_lines() {
+        let lines = ansi_to_lines("\x1b[33mfirst\nsecond\x1b[0m", base());
+        assert_eq!(lines[1].spans[0].style.fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn test_carriage_return_overwrites() {
+        let lines =
+            ansi_to_lines("Downloading  10%\rDownloading  55%\rDownloading 100%\ndone", base());
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].spans[0].content, "Downloading 100%");
+
+        // A trailing \r (from \r\n or a cleared bar) keeps the last visible state
+        let lines = ansi_to_lines("progress 100%\r\x1b[K\n", base());
+        assert_eq!(lines[0].spans[0].content, "progress 100%");
+    }
+
+    #[test]
+    fn test_non_sgr_sequences_are_dropped() {
+        let lines = ansi_to_lines(
+            "\x1b[2K\x1b]8;;https://example.com\x07link\x1b]8;;\x07 text\x1b[1A",
+            base(),
+        );
+        let text: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "link text");
+    }
+}
@@ -36,9 +36,20 @@ pub fn run_tui(mut app: App) -> Result<()> {
     // Run the main loop
     let result = run_main_loop(&mut terminal, &mut app);
 
+    // Stop a command still running in the output pane
+    if let Some(mut pane) = app.pane.take() {
+        pane.interrupt();
+        pane.wait();
+    }
+
     // Restore terminal
     restore_terminal()?;
 
+    // Print the command for the shell to pick up (`print` Enter action)
+    if let Some(command) = app.print_on_exit.take() {
+        println!("{command}");
+    }
+
     // Execute the selected command if user chose one (Enter, not Esc)
     if app.command_selected {
         if let Some(cmd) = app.get_selected_command() {
@@ -0,0 +1,194 @@
+//! Shell syntax highlighting for command lines.
+//!
+//! This is a display aid, not a parser: it splits on whitespace and shell
+//! operators closely enough to color programs, flags, strings, variables,
+//! and operators in the preview pane.
+
+use ratatui::{style::Style, text::Span};
+
+use super::Theme;
+
+/// Kind of a highlighted piece of a command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Program being run (first word of each pipeline stage)
+    Program,
+    /// `-f` or `--flag`
+    Flag,
+    /// Quoted string
+    String,
+    /// `$VAR` or `${VAR}`
+    Variable,
+    /// `NAME=value` before the program
+    Assignment,
+    /// `&&`, `||`, `|`, `;`, or a redirection
+    Operator,
+    /// Any other argument
+    Argument,
+    /// Whitespace between tokens
+    Space,
+}
+
+/// Split a command line into highlighted tokens.
+///
+/// Concatenating the token texts gives back the original command.
+pub fn tokenize(command: &str) -> Vec<(TokenKind, &str)> {
+    let mut tokens = Vec::new();
+    let mut expect_program = true;
+    let mut rest = command;
+
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_whitespace() {
+            let len = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+            tokens.push((TokenKind::Space, &rest[..len]));
+            len
+        } else if let Some(op) = operator(rest) {
+            tokens.push((TokenKind::Operator, op));
+            // A redirection's target is a file, not a program
+            expect_program = !op.contains(['>', '<']);
+            op.len()
+        } else {
+            let len = word_len(rest);
+            let word = &rest[..len];
+            let kind = if word.starts_with(['"', '\'']) {
+                TokenKind::String
+            } else if word.starts_with('$') {
+                TokenKind::Variable
+            } else if expect_program && is_assignment(word) {
+                TokenKind::Assignment
+            } else if expect_program {
+                expect_program = false;
+                TokenKind::Program
+            } else if word.starts_with('-') {
+                TokenKind::Flag
+            } else {
+                TokenKind::Argument
+            };
+            if kind == TokenKind::String || kind == TokenKind::Variable {
+                expect_program = false;
+            }
+            tokens.push((kind, word));
+            len
+        };
+        rest = &rest[len..];
+    }
+
+    tokens
+}
+
+/// Highlight a command line with theme colors.
+pub fn highlight_command<'a>(command: &'a str, theme: &Theme) -> Vec<Span<'a>> {
+    tokenize(command)
+        .into_iter()
+        .map(|(kind, text)| {
+            let color = match kind {
+                TokenKind::Program => theme.accent,
+                TokenKind::Flag => theme.secondary,
+                TokenKind::String => theme.success,
+                TokenKind::Variable | TokenKind::Assignment => theme.highlight,
+                TokenKind::Operator => theme.warning,
+                TokenKind::Argument | TokenKind::Space => theme.text,
+            };
+            Span::styled(text, Style::default().fg(color))
+        })
+        .collect()
+}
+
+/// Shell operator at the start of `s`, if any.
+fn operator(s: &str) -> Option<&str> {
+    ["&&", "||", "2>&1", ">>", "2>", "|", ";", ">", "<", "&"]
+        .into_iter()
+        .find(|op| s.starts_with(op))
+        .map(|op| &s[..op.len()])
+}
+
+/// Length of the word at the start of `s`, keeping quoted strings whole.
+fn word_len(s: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c.is_whitespace() || (i > 0 && operator(&s[i..]).is_some()) => return i,
+            None => {}
+        }
+    }
+    s.len()
+}
+
+/// Whether `word` is a `NAME=value` environment assignment.
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(command: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(command).into_iter().filter(|(kind, _)| *kind != TokenKind::Space).collect()
+    }
+
+    #[test]
+    fn test_tokenize_roundtrips() {
+        let command = "NODE_ENV=prod npm run build -- --watch && echo \"done now\" | tee log.txt";
+        let joined: String = tokenize(command).into_iter().map(|(_, text)| text).collect();
+        assert_eq!(joined, command);
+    }
+
+    #[test]
+    fn test_tokenize_kinds() {
+        assert_eq!(
+            kinds("NODE_ENV=prod npm run build --watch"),
+            [
+                (TokenKind::Assignment, "NODE_ENV=prod"),
+                (TokenKind::Program, "npm"),
+                (TokenKind::Argument, "run"),
+                (TokenKind::Argument, "build"),
+                (TokenKind::Flag, "--watch"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_pipelines() {
+        assert_eq!(
+            kinds("cargo test&&echo 'all ok' | tee $LOG"),
+            [
+                (TokenKind::Program, "cargo"),
+                (TokenKind::Argument, "test"),
+                (TokenKind::Operator, "&&"),
+                (TokenKind::Program, "echo"),
+                (TokenKind::String, "'all ok'"),
+                (TokenKind::Operator, "|"),
+                (TokenKind::Program, "tee"),
+                (TokenKind::Variable, "$LOG"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redirection_target_is_not_a_program() {
+        assert_eq!(
+            kinds("make > build.log"),
+            [
+                (TokenKind::Program, "make"),
+                (TokenKind::Operator, ">"),
+                (TokenKind::Argument, "build.log"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quotes_keep_operators() {
+        assert_eq!(
+            kinds("echo \"a && b\""),
+            [(TokenKind::Program, "echo"), (TokenKind::String, "\"a && b\"")]
+        );
+    }
+}
@@ -35,6 +35,9 @@ pub fn handle_events(key: KeyEvent, app: &mut App) {
         AppMode::PassThrough => {
             handle_pass_through_mode(key, app);
         }
+        AppMode::Confirm => {
+            handle_confirm_mode(key, app);
+        }
         AppMode::Palette => {
             handle_palette_mode(key, app);
         }
@@ -212,11 +215,20 @@ fn handle_normal_mode(key: KeyEvent, app: &mut App) {
             if app.multi_select_mode {
                 // Exit multi-select mode first
                 app.toggle_multi_select();
+            } else if app.close_pane() {
+                // Closed the finished command's output pane
+            } else if app.has_filter_bar() {
+                app.clear_filter_bar();
             } else {
                 app.quit();
             }
         }
-        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit(),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            // Stop the command in the output pane before quitting
+            if !app.interrupt_pane() {
+                app.quit();
+            }
+        }
         KeyCode::Char('q') if app.input.is_empty() && !app.multi_select_mode => app.quit(),
 
         // Multi-select mode toggle (Ctrl+Space)
@@ -244,11 +256,26 @@ fn handle_normal_mode(key: KeyEvent, app: &mut App) {
             }
         }
 
+        // Re-run last failed command (Ctrl+R)
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.rerun_last_failed();
+        }
+
         // Toggle favorite (Ctrl+S)
         KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.toggle_favorite();
         }
 
+        // Cycle source filter (Ctrl+F)
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cycle_source_filter();
+        }
+
+        // Cycle tag filter (Ctrl+L)
+        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.cycle_tag_filter();
+        }
+
         // Show history (Ctrl+H)
         KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.show_history();
@@ -259,6 +286,21 @@ fn handle_normal_mode(key: KeyEvent, app: &mut App) {
             app.show_analytics();
         }
 
+        // Output pane scrollback (Alt+arrows, Alt+PageUp/PageDown)
+        KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown | KeyCode::End
+            if key.modifiers.contains(KeyModifiers::ALT) && app.pane.is_some() =>
+        {
+            if let Some(pane) = app.pane.as_mut() {
+                match key.code {
+                    KeyCode::Up => pane.output.scroll_up(1),
+                    KeyCode::Down => pane.output.scroll_down(1),
+                    KeyCode::PageUp => pane.output.scroll_up(10),
+                    KeyCode::PageDown => pane.output.scroll_down(10),
+                    _ => pane.output.scroll_to_bottom(),
+                }
+            }
+        }
+
         // Navigation - use directory navigation when browsing directories
         KeyCode::Up | KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             if app.is_dir_browsing() {
@@ -306,7 +348,7 @@ fn handle_normal_mode(key: KeyEvent, app: &mut App) {
                     // Execute selected commands in parallel
                     app.execute_parallel_commands();
                 } else {
-                    app.execute_selected_command();
+                    app.activate_selected_command();
                 }
             } else if !app.input.is_empty() {
                 // No command matched - try auto-execute safe shell commands first
@@ -396,6 +438,22 @@ fn handle_pass_through_mode(key: KeyEvent, app: &mut App) {
     }
 }
 
+/// Handle input when confirming the selected command.
+fn handle_confirm_mode(key: KeyEvent, app: &mut App) {
+    match key.code {
+        KeyCode::Enter | KeyCode::Char('y') => {
+            app.confirm_selected_command();
+        }
+        KeyCode::Esc | KeyCode::Char('n') => {
+            app.cancel_confirm();
+        }
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.quit();
+        }
+        _ => {}
+    }
+}
+
 /// Handle input in command palette mode.
 fn handle_palette_mode(key: KeyEvent, app: &mut App) {
     match key.code {
@@ -941,6 +999,21 @@ mod tests {
         assert_eq!(app.cursor_position, 0);
     }
 
+    #[test]
+    fn test_escape_clears_filter_bar_before_quitting() {
+        let mut app = App::new_test();
+        app.registry.add(crate::core::Command::new("test", "npm test"));
+        handle_events(create_key_event(KeyCode::Char('f'), KeyModifiers::CONTROL), &mut app);
+        assert!(app.has_filter_bar());
+
+        handle_events(create_key_event(KeyCode::Esc, KeyModifiers::NONE), &mut app);
+        assert!(!app.has_filter_bar());
+        assert!(!app.should_quit);
+
+        handle_events(create_key_event(KeyCode::Esc, KeyModifiers::NONE), &mut app);
+        assert!(app.should_quit);
+    }
+
     #[test]
     fn test_show_help() {
         use crate::app::AppMode;
@@ -2,14 +2,20 @@
 //!
 //! This module handles the TUI rendering and input handling using ratatui.
 
+mod ansi;
 mod app;
+mod clipboard;
+mod highlight;
 mod input;
+mod pane;
 mod theme;
 mod ui;
 
 #[cfg(feature = "ai")]
 pub use app::run_ai_chat_inline;
 pub use app::run_tui;
+pub use clipboard::copy_to_clipboard;
 pub use input::handle_events;
+pub use pane::{CommandPane, OutputBuffer, PaneExit};
 pub use theme::{parse_hex_color, Theme};
 pub use ui::draw;
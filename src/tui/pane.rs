@@ -0,0 +1,371 @@
+//! Output pane for running commands without leaving the palette.
+//!
+//! With `ui.run_in_pane`, a command runs on a worker thread and its output
+//! streams into an [`OutputBuffer`] shown below the palette, which stays
+//! usable while the command runs.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::core::{Command, Executor, Stream, StreamLine};
+
+/// Lines kept in an output pane before the oldest are dropped.
+pub const SCROLLBACK_LINES: usize = 10_000;
+
+/// Scrollback buffer of output lines.
+///
+/// Keeps at most `capacity` lines. The view follows new output until it is
+/// scrolled up, and then stays on the same lines as more output arrives.
+#[derive(Debug, Clone)]
+pub struct OutputBuffer {
+    lines: VecDeque<StreamLine>,
+    capacity: usize,
+
+    /// Lines between the bottom of the view and the newest line
+    scroll: usize,
+}
+
+impl OutputBuffer {
+    /// Create an empty buffer holding up to `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        Self { lines: VecDeque::new(), capacity: capacity.max(1), scroll: 0 }
+    }
+
+    /// Append a line, dropping the oldest when full.
+    pub fn push(&mut self, line: StreamLine) {
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+
+        // A scrolled-up view keeps showing the same lines
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.max_scroll());
+        }
+    }
+
+    /// Number of lines held.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Whether no output has arrived.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Scroll towards older output.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.max_scroll());
+    }
+
+    /// Scroll towards newer output.
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Jump to the newest output and follow it again.
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Whether the view follows new output.
+    pub fn is_following(&self) -> bool {
+        self.scroll == 0
+    }
+
+    /// Lines below the view.
+    pub fn lines_below(&self) -> usize {
+        self.scroll
+    }
+
+    /// The lines that fit in a view `height` lines tall, oldest first.
+    pub fn visible(&self, height: usize) -> impl Iterator<Item = &StreamLine> {
+        let end = self.lines.len() - self.scroll;
+        // Near the top, fill the view rather than leave it part empty
+        let end = end.max(height.min(self.lines.len()));
+        self.lines.range(end.saturating_sub(height)..end)
+    }
+
+    /// All lines from one stream, joined with newlines.
+    pub fn text(&self, stream: Stream) -> String {
+        self.lines
+            .iter()
+            .filter(|line| line.stream == stream)
+            .map(|line| line.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.lines.len().saturating_sub(1)
+    }
+}
+
+/// How a command run in the pane ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaneExit {
+    /// The command exited, with its code unless it was killed by a signal
+    Exited(Option<i32>),
+
+    /// The command could not be started
+    Failed(String),
+}
+
+/// Message from the worker thread running a pane's command.
+#[derive(Debug)]
+enum PaneEvent {
+    Line(StreamLine),
+    Done(PaneExit),
+}
+
+/// A command running, or finished, in the output pane.
+#[derive(Debug)]
+pub struct CommandPane {
+    /// The command being run
+    pub command: Command,
+
+    /// Output read so far
+    pub output: OutputBuffer,
+
+    exit: Option<PaneExit>,
+    started: Instant,
+    duration: Option<Duration>,
+    interrupted: bool,
+    interrupt: Arc<AtomicBool>,
+    events: Receiver<PaneEvent>,
+}
+
+impl CommandPane {
+    /// Start running `command` on a worker thread.
+    pub fn spawn(command: Command, executor: Executor) -> Self {
+        let (tx, events) = mpsc::channel();
+        let interrupt = Arc::new(AtomicBool::new(false));
+
+        let worker_command = command.clone();
+        let worker_interrupt = Arc::clone(&interrupt);
+        std::thread::spawn(move || {
+            let result =
+                executor.execute_streaming_until(&worker_command, &worker_interrupt, |line| {
+                    let _ = tx.send(PaneEvent::Line(line));
+                });
+            let exit = match result {
+                Ok(result) => PaneExit::Exited(result.status.code()),
+                Err(e) => PaneExit::Failed(e.to_string()),
+            };
+            let _ = tx.send(PaneEvent::Done(exit));
+        });
+
+        Self {
+            command,
+            output: OutputBuffer::new(SCROLLBACK_LINES),
+            exit: None,
+            started: Instant::now(),
+            duration: None,
+            interrupted: false,
+            interrupt,
+            events,
+        }
+    }
+
+    /// Move output that has arrived into the buffer without blocking.
+    ///
+    /// Returns true on the call that sees the command finish.
+    pub fn poll(&mut self) -> bool {
+        let mut finished = false;
+        while let Ok(event) = self.events.try_recv() {
+            finished |= self.handle(event);
+        }
+        finished
+    }
+
+    /// Block until the command has finished.
+    pub fn wait(&mut self) {
+        while self.is_running() {
+            match self.events.recv() {
+                Ok(event) => {
+                    self.handle(event);
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Ask the running command to stop.
+    pub fn interrupt(&mut self) {
+        if self.is_running() {
+            self.interrupted = true;
+            self.interrupt.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Whether the command is still running.
+    pub fn is_running(&self) -> bool {
+        self.exit.is_none()
+    }
+
+    /// Whether the command was stopped with [`Self::interrupt`].
+    pub fn was_interrupted(&self) -> bool {
+        self.interrupted
+    }
+
+    /// How the command ended, once it has.
+    pub fn exit(&self) -> Option<&PaneExit> {
+        self.exit.as_ref()
+    }
+
+    /// Exit code of the finished command.
+    pub fn exit_code(&self) -> Option<i32> {
+        match self.exit {
+            Some(PaneExit::Exited(code)) => code,
+            _ => None,
+        }
+    }
+
+    /// Whether the command finished with exit code 0.
+    pub fn success(&self) -> bool {
+        self.exit_code() == Some(0)
+    }
+
+    /// Time the command has been running, or took to finish.
+    pub fn elapsed(&self) -> Duration {
+        self.duration.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    fn handle(&mut self, event: PaneEvent) -> bool {
+        match event {
+            PaneEvent::Line(line) => {
+                self.output.push(line);
+                false
+            }
+            PaneEvent::Done(exit) => {
+                self.exit = Some(exit);
+                self.duration = Some(self.started.elapsed());
+                true
+            }
+        }
+    }
+}
+
+impl Drop for CommandPane {
+    fn drop(&mut self) {
+        // Don't leave a command running that nobody can see or stop
+        self.interrupt();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> StreamLine {
+        StreamLine { stream: Stream::Stdout, text: text.to_string() }
+    }
+
+    fn buffer(lines: usize, capacity: usize) -> OutputBuffer {
+        let mut buffer = OutputBuffer::new(capacity);
+        for i in 0..lines {
+            buffer.push(line(&i.to_string()));
+        }
+        buffer
+    }
+
+    fn visible(buffer: &OutputBuffer, height: usize) -> Vec<&str> {
+        buffer.visible(height).map(|line| line.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_view_follows_new_output() {
+        let mut buffer = buffer(5, 100);
+        assert_eq!(visible(&buffer, 3), ["2", "3", "4"]);
+
+        buffer.push(line("5"));
+        assert!(buffer.is_following());
+        assert_eq!(visible(&buffer, 3), ["3", "4", "5"]);
+
+        // Short output is shown whole
+        assert_eq!(visible(&buffer, 10).len(), 6);
+    }
+
+    #[test]
+    fn test_scrolled_view_stays_put() {
+        let mut buffer = buffer(10, 100);
+        buffer.scroll_up(4);
+        assert_eq!(visible(&buffer, 3), ["3", "4", "5"]);
+
+        buffer.push(line("10"));
+        assert!(!buffer.is_following());
+        assert_eq!(buffer.lines_below(), 5);
+        assert_eq!(visible(&buffer, 3), ["3", "4", "5"]);
+
+        buffer.scroll_down(2);
+        assert_eq!(visible(&buffer, 3), ["5", "6", "7"]);
+
+        buffer.scroll_to_bottom();
+        assert_eq!(visible(&buffer, 3), ["8", "9", "10"]);
+    }
+
+    #[test]
+    fn test_scroll_is_clamped() {
+        let mut buffer = buffer(10, 100);
+        buffer.scroll_up(100);
+        assert_eq!(visible(&buffer, 3), ["0", "1", "2"]);
+
+        buffer.scroll_down(100);
+        assert!(buffer.is_following());
+    }
+
+    #[test]
+    fn test_capacity_drops_oldest_lines() {
+        let mut buffer = buffer(10, 4);
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(visible(&buffer, 10), ["6", "7", "8", "9"]);
+
+        // A scrolled view keeps its lines until they are dropped
+        buffer.scroll_up(1);
+        buffer.push(line("10"));
+        assert_eq!(visible(&buffer, 2), ["7", "8"]);
+    }
+
+    #[test]
+    fn test_text_by_stream() {
+        let mut buffer = OutputBuffer::new(10);
+        buffer.push(line("out 1"));
+        buffer.push(StreamLine { stream: Stream::Stderr, text: "err".to_string() });
+        buffer.push(line("out 2"));
+
+        assert_eq!(buffer.text(Stream::Stdout), "out 1\nout 2");
+        assert_eq!(buffer.text(Stream::Stderr), "err");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pane_collects_output_and_exit_code() {
+        let command = Command::new("fail", "echo one; echo two >&2; exit 3");
+        let mut pane = CommandPane::spawn(command, Executor::new());
+
+        pane.wait();
+        assert!(!pane.is_running());
+        assert_eq!(pane.exit(), Some(&PaneExit::Exited(Some(3))));
+        assert!(!pane.success());
+        assert_eq!(pane.output.text(Stream::Stdout), "one");
+        assert_eq!(pane.output.text(Stream::Stderr), "two");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_pane_interrupt() {
+        let command = Command::new("slow", "sleep 30");
+        let mut pane = CommandPane::spawn(command, Executor::new());
+        assert!(pane.is_running());
+
+        pane.interrupt();
+        pane.wait();
+        assert!(pane.was_interrupted());
+        assert!(!pane.success());
+        assert!(pane.elapsed() < Duration::from_secs(10));
+    }
+}
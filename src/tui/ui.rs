@@ -11,7 +11,10 @@ use ratatui::{
     Frame,
 };
 
+use super::highlight::highlight_command;
+use super::{CommandPane, PaneExit};
 use crate::app::AppMode;
+use crate::core::Stream;
 use crate::App;
 
 /// Draw the main UI.
@@ -78,24 +81,44 @@ pub fn draw(frame: &mut Frame, app: &App) {
         ])
         .split(area);
 
-    // Split main content into command list and preview (horizontal)
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(60), // Command list
-            Constraint::Percentage(40), // Preview panel
-        ])
-        .split(chunks[1]);
-
     draw_header(frame, app, chunks[0]);
-    draw_command_list(frame, app, content_chunks[0]);
-    draw_preview_panel(frame, app, content_chunks[1]);
+
+    // The output pane takes the lower half while it is open
+    let content = if let Some(ref pane) = app.pane {
+        let pane_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+        draw_output_pane(frame, app, pane, pane_chunks[1]);
+        pane_chunks[0]
+    } else {
+        chunks[1]
+    };
+
+    if app.config.ui.show_preview {
+        // Split main content into command list and preview (horizontal)
+        let content_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(60), // Command list
+                Constraint::Percentage(40), // Preview panel
+            ])
+            .split(content);
+
+        draw_command_list(frame, app, content_chunks[0]);
+        draw_preview_panel(frame, app, content_chunks[1]);
+    } else {
+        draw_command_list(frame, app, content);
+    }
     draw_status_bar(frame, app, chunks[2]);
 
     // Draw overlays for special modes
     if matches!(app.mode, AppMode::PassThrough) {
         draw_pass_through_overlay(frame, app);
     }
+    if matches!(app.mode, AppMode::Confirm) {
+        draw_confirm_overlay(frame, app);
+    }
     if matches!(app.mode, AppMode::Palette) {
         draw_palette_overlay(frame, app);
     }
@@ -334,7 +357,20 @@ fn draw_command_list(frame: &mut Frame, app: &App, area: Rect) {
             }
         }
 
-        let empty_message = if app.input.is_empty() {
+        let empty_message = if app.input.is_empty() && app.has_filter_bar() {
+            vec![
+                Line::from(""),
+                Line::from(Span::styled(
+                    "No commands match the filters",
+                    Style::default().fg(theme.text_dim),
+                )),
+                Line::from(""),
+                Line::from(Span::styled(
+                    "Press Esc to clear filters",
+                    Style::default().fg(theme.text_muted),
+                )),
+            ]
+        } else if app.input.is_empty() {
             vec![
                 Line::from(""),
                 Line::from(Span::styled("No commands found", Style::default().fg(theme.text_dim))),
@@ -395,7 +431,7 @@ fn draw_command_list(frame: &mut Frame, app: &App, area: Rect) {
 
             match cmd {
                 Some(cmd) => {
-                    let source_icon = cmd.source.icon();
+                    let source_icon = app.config.ui.icons.icon(&cmd.source, &app.sources);
                     let name = &cmd.name;
 
                     // Check if this item is multi-selected
@@ -625,6 +661,16 @@ fn draw_preview_panel(frame: &mut Frame, app: &App, area: Rect) {
             Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
         )));
 
+        if cmd.confirm {
+            lines.push(Line::from(Span::styled(
+                " ⚠ Asks for confirmation before running ",
+                Style::default()
+                    .fg(theme.background)
+                    .bg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
         // Description if available
         if let Some(ref desc) = cmd.description {
             lines
@@ -633,18 +679,40 @@ fn draw_preview_panel(frame: &mut Frame, app: &App, area: Rect) {
 
         lines.push(Line::from("")); // Spacer
 
-        // Command to execute
-        lines.push(Line::from(vec![
-            Span::styled("$ ", Style::default().fg(theme.secondary)),
-            Span::styled(&cmd.command, Style::default().fg(theme.text)),
-        ]));
+        // Command to execute, highlighted and wrapped in full
+        let mut command_spans = vec![Span::styled("$ ", Style::default().fg(theme.secondary))];
+        command_spans.extend(highlight_command(&cmd.command, theme));
+        lines.push(Line::from(command_spans));
+
+        lines.push(Line::from("")); // Spacer
+
+        // Working directory, relative to the project when inside it
+        if let Some(ref dir) = cmd.working_dir {
+            let dir = dir.strip_prefix(&app.context.project_root).map_or_else(
+                |_| dir.display().to_string(),
+                |relative| format!("./{}", relative.display()),
+            );
+            lines.push(Line::from(vec![
+                Span::styled("dir ", Style::default().fg(theme.text_muted)),
+                Span::styled(dir, Style::default().fg(theme.text_dim)),
+            ]));
+        }
 
-        // Source info (compact)
+        // Source info
         lines.push(Line::from(vec![Span::styled(
-            format!("{} {}", cmd.source.icon(), cmd.source.short_name()),
+            format!(
+                "{} {}",
+                app.config.ui.icons.icon(&cmd.source, &app.sources),
+                app.sources.name(&cmd.source)
+            ),
             Style::default().fg(theme.text_muted),
         )]));
 
+        if !cmd.tags.is_empty() {
+            let tags: Vec<String> = cmd.tags.iter().map(|tag| format!("#{tag}")).collect();
+            lines.push(Line::from(Span::styled(tags.join(" "), Style::default().fg(theme.accent))));
+        }
+
         // Execution stats from history
         if let Some(entry) = app.get_history_entry(&cmd.id) {
             lines.push(Line::from("")); // Spacer
@@ -763,6 +831,8 @@ const STATUS_TIPS: &[&str] = &[
     "Ctrl+S favorite • Ctrl+B background",
     "Ctrl+Space multi-select",
     "Ctrl+H history • Ctrl+G analytics",
+    "Ctrl+R re-run last failed",
+    "Ctrl+F filter source • Ctrl+L filter tag",
 ];
 
 /// Draw the smart status bar at the bottom.
@@ -814,6 +884,26 @@ fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         Style::default().fg(theme.text_muted),
     ));
 
+    // Filter bar
+    if app.has_filter_bar() {
+        left_spans.push(Span::styled(" │ ", Style::default().fg(theme.border)));
+        if let Some(ref source) = app.source_filter {
+            left_spans.push(Span::styled(
+                format!(" {} ", source),
+                Style::default().bg(theme.accent).fg(theme.background),
+            ));
+        }
+        if let Some(ref tag) = app.tag_filter {
+            if app.source_filter.is_some() {
+                left_spans.push(Span::raw(" "));
+            }
+            left_spans.push(Span::styled(
+                format!(" #{} ", tag),
+                Style::default().bg(theme.secondary).fg(theme.background),
+            ));
+        }
+    }
+
     // Degradation/Offline indicator
     if app.degradation.has_degradations() {
         let count = app.degradation.degraded_features().len();
@@ -840,6 +930,8 @@ fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     let right_text = if app.multi_select_mode {
         let count = app.selected_count();
         format!("Space select • Enter run {} • Esc cancel", count)
+    } else if app.has_filter_bar() {
+        "Ctrl+F source • Ctrl+L tag • Esc clear filters".to_string()
     } else {
         STATUS_TIPS[app.tip_index % STATUS_TIPS.len()].to_string()
     };
@@ -1037,11 +1129,8 @@ fn draw_output_panel(
 ) {
     let theme = &app.theme;
 
-    // Split content into lines
-    let all_lines: Vec<Line> = content
-        .lines()
-        .map(|line| Line::from(Span::styled(line, Style::default().fg(color))))
-        .collect();
+    // Split content into lines, keeping the command's own colors
+    let all_lines = super::ansi::ansi_to_lines(content, Style::default().fg(color));
 
     let total_lines = all_lines.len();
     let visible_height = area.height.saturating_sub(2) as usize; // Account for borders
@@ -1069,6 +1158,66 @@ fn draw_output_panel(
     frame.render_widget(output, area);
 }
 
+/// Draw the output pane for a command run with `ui.run_in_pane`.
+fn draw_output_pane(frame: &mut Frame, app: &App, pane: &CommandPane, area: Rect) {
+    let theme = &app.theme;
+    let elapsed = format!("{:.1}s", pane.elapsed().as_secs_f64());
+
+    let (status, status_color) = match pane.exit() {
+        None if pane.was_interrupted() => (format!("stopping… {elapsed}"), theme.warning),
+        None => (format!("{} running {elapsed}", app.spinner_char()), theme.accent),
+        Some(_) if pane.was_interrupted() => (format!("stopped after {elapsed}"), theme.warning),
+        Some(PaneExit::Failed(e)) => (format!("✗ {e}"), theme.error),
+        Some(PaneExit::Exited(Some(0))) => (format!("✓ exit 0 in {elapsed}"), theme.success),
+        Some(PaneExit::Exited(Some(code))) => (format!("✗ exit {code} in {elapsed}"), theme.error),
+        Some(PaneExit::Exited(None)) => (format!("✗ killed after {elapsed}"), theme.error),
+    };
+
+    let title = Line::from(vec![
+        Span::styled(
+            format!(" {} ", pane.command.name),
+            Style::default().fg(theme.secondary).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!("{status} "), Style::default().fg(status_color)),
+    ]);
+
+    let mut hints = if pane.is_running() {
+        "Ctrl+C stop • Alt+↑↓ scroll".to_string()
+    } else {
+        "Esc close • Alt+↑↓ scroll".to_string()
+    };
+    if !pane.output.is_following() {
+        hints = format!("↓ {} more • {hints}", pane.output.lines_below());
+    }
+
+    let height = area.height.saturating_sub(2) as usize; // Account for borders
+    let lines: Vec<Line> = pane
+        .output
+        .visible(height)
+        .flat_map(|line| {
+            let color = match line.stream {
+                Stream::Stdout => theme.text,
+                Stream::Stderr => theme.error,
+            };
+            super::ansi::ansi_to_lines(&line.text, Style::default().fg(color))
+        })
+        .collect();
+
+    let output = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(title)
+            .title_bottom(
+                Line::from(Span::styled(format!(" {hints} "), Style::default().fg(theme.text_dim)))
+                    .right_aligned(),
+            )
+            .padding(Padding::horizontal(1)),
+    );
+
+    frame.render_widget(output, area);
+}
+
 /// Draw the help screen showing all keyboard shortcuts.
 fn draw_help_screen(frame: &mut Frame, app: &App) {
     let theme = &app.theme;
@@ -1120,6 +1269,9 @@ fn draw_help_screen(frame: &mut Frame, app: &App) {
     lines.push(Line::from(""));
     lines.push(help_line("Enter", "Run selected command", theme));
     lines.push(help_line("Ctrl+B", "Run in background", theme));
+    lines.push(help_line("Ctrl+R", "Re-run last failed command", theme));
+    lines.push(help_line("Ctrl+C", "Stop command in output pane", theme));
+    lines.push(help_line("Alt+↑/↓", "Scroll output pane", theme));
     lines.push(Line::from(""));
 
     // Multi-select section
@@ -1157,6 +1309,9 @@ fn draw_help_screen(frame: &mut Frame, app: &App) {
     lines.push(help_line("#tag", "Filter by tag", theme));
     lines.push(help_line("source:npm", "Filter by source type", theme));
     lines.push(help_line("@workspace", "Filter by workspace", theme));
+    lines.push(help_line("Ctrl+F", "Cycle source filter", theme));
+    lines.push(help_line("Ctrl+L", "Cycle tag filter", theme));
+    lines.push(help_line("Esc", "Clear source and tag filters", theme));
     lines.push(Line::from(""));
 
     // Favorites section
@@ -1605,6 +1760,55 @@ fn draw_pass_through_overlay(frame: &mut Frame, app: &App) {
     frame.render_widget(popup, popup_area);
 }
 
+/// Draw the run confirmation overlay for the selected command.
+fn draw_confirm_overlay(frame: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let area = frame.area();
+
+    let popup_width = 50.min(area.width.saturating_sub(4));
+    let popup_height = 5;
+    let popup_area = Rect::new(
+        (area.width.saturating_sub(popup_width)) / 2,
+        (area.height.saturating_sub(popup_height)) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    frame.render_widget(Clear, popup_area);
+
+    let cmd = app.get_selected_command().map_or("", |cmd| cmd.command.as_str());
+    let truncated_cmd: String = if cmd.chars().count() > 40 {
+        format!("{}...", cmd.chars().take(37).collect::<String>())
+    } else {
+        cmd.to_string()
+    };
+
+    let content = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" $ ", Style::default().fg(theme.secondary)),
+            Span::styled(truncated_cmd, Style::default().fg(theme.text)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" Run this command? ", Style::default().fg(theme.text_dim)),
+            Span::styled("[Enter] Yes  ", Style::default().fg(theme.success)),
+            Span::styled("[Esc] No", Style::default().fg(theme.text_muted)),
+        ]),
+    ];
+
+    let popup = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.warning))
+            .title(" Confirm ")
+            .title_style(Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
+            .style(Style::default().bg(theme.background)),
+    );
+
+    frame.render_widget(popup, popup_area);
+}
+
 /// Draw the command palette overlay.
 fn draw_palette_overlay(frame: &mut Frame, app: &App) {
     let theme = &app.theme;
@@ -2423,7 +2627,128 @@ fn draw_trust_confirmation(frame: &mut Frame, app: &App) {
 
 #[cfg(test)]
 mod tests {
+    use ratatui::{backend::TestBackend, Terminal};
+
     use super::super::Theme;
+    use crate::core::Command;
+    use crate::App;
+
+    /// Render the palette and return the screen as text, one row per line.
+    fn render(app: &App, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| super::draw(frame, app)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect::<String>() + "\n"
+            })
+            .collect()
+    }
+
+    fn app_with(commands: Vec<Command>) -> App {
+        let mut app = App::new_test();
+        for command in commands {
+            app.registry.add(command);
+        }
+        app.update_filtered_commands();
+        app
+    }
+
+    /// Position of a command in the filtered list.
+    fn position(app: &App, name: &str) -> usize {
+        app.filtered_commands
+            .iter()
+            .position(|&i| app.registry.get_by_index(i).is_some_and(|cmd| cmd.name == name))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_preview_shows_selected_command() {
+        let mut app = app_with(vec![
+            Command::new("build", "cargo build --release")
+                .with_description("Compile optimized")
+                .with_tags(vec!["rust".to_string()]),
+            Command::new("deploy", "./scripts/deploy.sh production").with_confirm(true),
+        ]);
+        app.selected = position(&app, "build");
+
+        let screen = render(&app, 120, 30);
+        assert!(screen.contains(" Preview "));
+        assert!(screen.contains("$ cargo build --release"));
+        assert!(screen.contains("Compile optimized"));
+        assert!(screen.contains("#rust"));
+        assert!(!screen.contains("Asks for confirmation"));
+
+        // The preview follows the selection
+        app.selected = position(&app, "deploy");
+        let screen = render(&app, 120, 30);
+        assert!(screen.contains("$ ./scripts/deploy.sh production"));
+        assert!(screen.contains("Asks for confirmation"));
+    }
+
+    #[test]
+    fn test_preview_wraps_long_commands() {
+        let long = format!("echo {}", "z".repeat(60));
+        let app = app_with(vec![Command::new("long", long.as_str())]);
+
+        // All of the command is shown, over more than one row
+        let screen = render(&app, 100, 30);
+        assert_eq!(screen.matches('z').count(), 60);
+        assert!(!screen.contains(&"z".repeat(60)));
+    }
+
+    #[test]
+    fn test_preview_hidden_when_disabled() {
+        let mut app = app_with(vec![Command::new("build", "cargo build")]);
+        app.config.ui.show_preview = false;
+
+        let screen = render(&app, 120, 30);
+        assert!(!screen.contains(" Preview "));
+        assert!(!screen.contains("$ cargo build"));
+    }
+
+    #[test]
+    fn test_status_bar_shows_filter_bar() {
+        let mut app =
+            app_with(
+                vec![Command::new("build", "cargo build").with_tags(vec!["rust".to_string()])],
+            );
+        let screen = render(&app, 120, 30);
+        assert!(!screen.contains("Esc clear filters"));
+
+        app.source_filter = Some("manual".to_string());
+        app.tag_filter = Some("rust".to_string());
+        app.update_filtered_commands();
+        let screen = render(&app, 140, 30);
+        assert!(screen.contains(" manual   #rust "));
+        assert!(screen.contains("Esc clear filters"));
+
+        app.tag_filter = Some("docs".to_string());
+        app.update_filtered_commands();
+        assert!(render(&app, 140, 30).contains("No commands match the filters"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_pane_shows_output_and_exit_code() {
+        let mut app = app_with(vec![Command::new("build", "cargo build")]);
+        app.pane = Some(super::CommandPane::spawn(
+            Command::new("greet", "echo hello from the pane"),
+            crate::core::Executor::new(),
+        ));
+        while app.pane.as_ref().unwrap().is_running() {
+            app.poll_pane();
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let screen = render(&app, 120, 30);
+        assert!(screen.contains(" greet ✓ exit 0 in "));
+        assert!(screen.contains("hello from the pane"));
+        assert!(screen.contains("Esc close"));
+        // The palette is still shown above
+        assert!(screen.contains("cargo build"));
+    }
 
     #[test]
     fn test_theme_used_in_rendering() {
@@ -68,7 +68,14 @@ fn test_list_with_json_output() {
         .args(["list", "--format", "json"])
         .assert()
         .success()
-        .stdout(predicate::str::starts_with("["));
+        .stdout(predicate::str::starts_with("{").and(predicate::str::contains("\"version\": 1")));
+}
+
+#[test]
+fn test_list_json_schema() {
+    palrun().args(["list", "--format", "json-schema"]).assert().success().stdout(
+        predicate::str::contains("\"$schema\"").and(predicate::str::contains("\"commands\"")),
+    );
 }
 
 // ============================================================================
@@ -148,6 +155,73 @@ fn test_scan_makefile_project() {
     temp.close().unwrap();
 }
 
+#[test]
+fn test_scan_explain() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("Makefile").write_str("build:\n\techo building\n").unwrap();
+
+    palrun()
+        .args(["scan", "--explain"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("scanner: make"))
+        .stdout(predicate::str::contains("Makefile, rule: each `target:` line"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_scan_json_grouped_by_source() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("Makefile").write_str("test:\n\techo root\n").unwrap();
+    temp.child("package.json")
+        .write_str(r#"{"name": "test", "scripts": {"lint": "eslint ."}}"#)
+        .unwrap();
+    temp.child("tools/Makefile").write_str("test:\n\techo tools\nfmt:\n\techo\n").unwrap();
+
+    let output = palrun()
+        .args(["scan", "--recursive", "--format", "json"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let commands = json.as_array().unwrap();
+    let source = |cmd: &serde_json::Value| match &cmd["source"] {
+        serde_json::Value::Object(map) => map.keys().next().unwrap().clone(),
+        other => other.as_str().unwrap().to_string(),
+    };
+
+    // Each source's commands are together
+    let mut sources: Vec<String> = commands.iter().map(source).collect();
+    sources.dedup();
+    let mut unique = sources.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(sources.len(), unique.len(), "{sources:?}");
+
+    // The root and tools copies of `make test` are both kept, the second labeled
+    let tests: Vec<_> = commands.iter().filter(|cmd| cmd["command"] == "make test").collect();
+    assert_eq!(tests.len(), 2);
+    assert_eq!(tests[0]["working_dir"], serde_json::Value::Null);
+    assert_eq!(tests[1]["working_dir"], "tools");
+    assert!(tests[1]["tags"].as_array().unwrap().contains(&"tools".into()));
+    assert!(!tests[0]["tags"].as_array().unwrap().contains(&"tools".into()));
+    assert!(commands.iter().any(|cmd| cmd["command"] == "npm run lint"));
+
+    // The order is stable between runs
+    let again = palrun()
+        .args(["scan", "--recursive", "--format", "json"])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    assert_eq!(output.stdout, again.stdout);
+
+    temp.close().unwrap();
+}
+
 #[test]
 fn test_scan_taskfile_project() {
     let temp = assert_fs::TempDir::new().unwrap();
@@ -310,6 +384,281 @@ fn test_exec_with_dry_run() {
     temp.close().unwrap();
 }
 
+#[test]
+fn test_exec_ambiguous_match() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+
+    temp.child("Makefile").write_str("deploy-staging:\n\ttrue\ndeploy-prod:\n\ttrue\n").unwrap();
+
+    // Without a terminal to ask on, several matches are an error
+    palrun()
+        .args(["exec", "make deploy", "--dry-run"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("make deploy-staging"))
+        .stderr(predicate::str::contains("make deploy-prod"))
+        .stderr(predicate::str::contains("--first"));
+
+    palrun()
+        .args(["exec", "make deploy", "--dry-run", "--first"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would execute"));
+
+    // An exact name wins over longer names it also matches
+    palrun()
+        .args(["exec", "make deploy-prod", "--dry-run"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Name: make deploy-prod"));
+}
+
+#[test]
+fn test_exec_validates_project_commands() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+
+    temp.child(".git").create_dir_all().unwrap();
+    temp.child(".palrun.toml")
+        .write_str(
+            "[aliases]\nleak = \"echo /etc/shadow\"\n\n[security]\ntrust_project_commands = true\n",
+        )
+        .unwrap();
+
+    // The project config cannot trust its own commands
+    palrun()
+        .args(["exec", "leak", "-y"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not executing 'leak'"));
+
+    temp.child(".palrun.local.toml")
+        .write_str("[security]\ntrust_project_commands = true\n")
+        .unwrap();
+    palrun()
+        .args(["exec", "leak", "-y"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/etc/shadow"));
+
+    temp.close().unwrap();
+    home.close().unwrap();
+}
+
+#[test]
+fn test_exec_cache_hit_is_recorded_in_history() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+
+    temp.child(".git").create_dir_all().unwrap();
+    temp.child(".palrun.toml")
+        .write_str("[aliases]\ndeps = \"echo dependency-tree\"\n\n[exec.cache]\nenabled = true\ncommands = [\"echo dependency-tree\"]\n")
+        .unwrap();
+    let run = || {
+        palrun()
+            .args(["exec", "deps"])
+            .current_dir(temp.path())
+            .env("HOME", home.path())
+            .env("XDG_CONFIG_HOME", home.path().join(".config"))
+            .env("XDG_CACHE_HOME", home.path().join(".cache"))
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("dependency-tree"))
+    };
+
+    run();
+    let history = home.child(".palrun/history.json");
+    std::fs::remove_file(history.path()).unwrap();
+
+    run().stderr(predicate::str::contains("(cached)"));
+    history.assert(predicate::str::contains("\"deps\""));
+
+    temp.close().unwrap();
+    home.close().unwrap();
+}
+
+#[test]
+fn test_exec_alias() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+
+    temp.child(".git").create_dir_all().unwrap();
+    temp.child("Makefile").write_str("test:\n\ttrue\n").unwrap();
+    temp.child(".palrun.toml")
+        .write_str("[aliases]\nt = \"make test\"\nci = \"t\"\nloop = \"again\"\nagain = \"loop\"\n")
+        .unwrap();
+
+    palrun()
+        .args(["exec", "ci", "--dry-run"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Name: ci"))
+        .stdout(predicate::str::contains("Command: make test"))
+        .stdout(predicate::str::contains("Source: Alias"));
+
+    palrun()
+        .args(["exec", "loop", "--dry-run"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("alias cycle: loop -> again -> loop"));
+
+    palrun()
+        .args(["list", "--source", "alias"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Total: 2 commands"));
+}
+
+#[test]
+fn test_exec_last_failed() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+
+    temp.child("Makefile").write_str("pass:\n\ttrue\nfail:\n\texit 3\n").unwrap();
+
+    palrun()
+        .args(["exec", "--last-failed", "-y"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No failed command in history"));
+
+    palrun()
+        .args(["exec", "make fail", "-y"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .failure();
+
+    palrun()
+        .args(["exec", "make pass", "-y"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .success();
+
+    palrun()
+        .args(["exec", "--last-failed", "--dry-run"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Last failed: make fail"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_forwards_sigterm_to_child() {
+    use std::process::Stdio;
+    use std::time::{Duration, Instant};
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+
+    temp.child("Makefile").write_str("slow:\n\t@echo $$$$ > child.pid; exec sleep 30\n").unwrap();
+
+    let mut pal = std::process::Command::new(env!("CARGO_BIN_EXE_palrun"))
+        .args(["exec", "make slow", "-y"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    fn wait_for(what: &str, mut done: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !done() {
+            assert!(Instant::now() < deadline, "timed out waiting for {what}");
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    let pid_file = temp.path().join("child.pid");
+    let read_pid = || std::fs::read_to_string(&pid_file).unwrap_or_default().trim().to_string();
+    wait_for("the command to start", || read_pid().parse::<u32>().is_ok());
+    let child_pid = read_pid();
+
+    let sent =
+        std::process::Command::new("kill").args(["-TERM", &pal.id().to_string()]).status().unwrap();
+    assert!(sent.success());
+
+    let mut status = None;
+    wait_for("palrun to exit", || {
+        status = pal.try_wait().unwrap();
+        status.is_some()
+    });
+    assert!(!status.unwrap().success());
+
+    // The child is gone too (zombies awaiting a reaper count as gone)
+    let running = || match std::fs::read_to_string(format!("/proc/{child_pid}/stat")) {
+        Ok(stat) => !stat.contains(") Z"),
+        Err(_) => std::process::Command::new("kill")
+            .args(["-0", &child_pid])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success()),
+    };
+    wait_for("the child to exit", || !running());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_exec_with_secrets_passes_secret_to_child_only() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".env").write_str("API_TOKEN=op://vault/item/token\n").unwrap();
+    temp.child("Makefile").write_str("show:\n\t@echo \"token=$$API_TOKEN\"\n").unwrap();
+
+    // Stand-in for the 1Password CLI
+    let op = temp.child("bin/op");
+    op.write_str("#!/bin/sh\necho s3cret\n").unwrap();
+    std::fs::set_permissions(op.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!("{}:{}", temp.child("bin").path().display(), std::env::var("PATH").unwrap());
+
+    palrun()
+        .args(["exec", "make show", "--with-secrets", "-y"])
+        .current_dir(temp.path())
+        .env("PATH", &path)
+        .env_remove("API_TOKEN")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("token=s3cret"));
+
+    palrun()
+        .args(["exec", "make show", "-y"])
+        .current_dir(temp.path())
+        .env("PATH", &path)
+        .env_remove("API_TOKEN")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("token=\n"));
+
+    temp.close().unwrap();
+}
+
 // ============================================================================
 // Config Command Tests
 // ============================================================================
@@ -328,6 +677,27 @@ fn test_config_display() {
     palrun().arg("config").assert().success();
 }
 
+#[test]
+fn test_config_shows_value_sources() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+    temp.child(".git").create_dir_all().unwrap();
+    temp.child(".palrun.toml").write_str("[ui]\ntheme = \"nord\"\n").unwrap();
+
+    palrun()
+        .arg("config")
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("theme = \"nord\"  # .palrun.toml"))
+        .stdout(predicate::str::contains("max_display = 50\n"));
+
+    temp.close().unwrap();
+    home.close().unwrap();
+}
+
 #[test]
 fn test_config_path_flag() {
     palrun().args(["config", "--path"]).assert().success();
@@ -419,6 +789,176 @@ fn test_respects_palrun_config_env() {
     temp.close().unwrap();
 }
 
+// ============================================================================
+// Offline Mode Tests
+// ============================================================================
+
+#[test]
+fn test_list_works_offline() {
+    palrun().args(["--offline", "list"]).assert().success();
+}
+
+#[test]
+fn test_scan_works_with_offline_env() {
+    palrun().arg("scan").env("PALRUN_OFFLINE", "1").assert().success();
+}
+
+#[test]
+fn test_ci_fails_fast_offline() {
+    let start = std::time::Instant::now();
+
+    palrun()
+        .args(["--offline", "ci", "workflows"])
+        .env("GITHUB_TOKEN", "test-token")
+        .env("GITHUB_REPOSITORY", "owner/repo")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("offline mode"));
+
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+}
+
+#[test]
+fn test_notify_fails_fast_offline() {
+    palrun()
+        .args(["notify", "webhook", "--url", "https://example.com/hook", "hello"])
+        .env("PALRUN_OFFLINE", "1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("offline mode"));
+}
+
+#[test]
+fn test_notify_from_stdin_replaces_message() {
+    palrun()
+        .args(["notify", "slack", "--url", "https://example.com/hook", "--from-stdin"])
+        .env("PALRUN_OFFLINE", "1")
+        .write_stdin("error: build failed\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("offline mode"));
+}
+
+#[test]
+fn test_notify_on_complete_uses_user_endpoints_and_exit_code() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+    let endpoint =
+        "[[notify.endpoints]]\nname = \"team\"\ntype = \"webhook\"\nwebhook_url = \"http://127.0.0.1:9\"\n";
+
+    temp.child(".git").create_dir_all().unwrap();
+    temp.child(".palrun.toml").write_str(endpoint).unwrap();
+
+    // A project config cannot choose where output is sent
+    palrun()
+        .args(["notify", "on-complete", "--", "exit 3"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No notification endpoints configured"));
+
+    // Quick commands don't notify, but the exit code is passed through
+    temp.child(".palrun.local.toml").write_str(endpoint).unwrap();
+    palrun()
+        .args(["notify", "on-complete", "--", "exit 3"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .code(3);
+
+    // Separate arguments keep their quoting
+    palrun()
+        .args(["notify", "on-complete", "--", "sh", "-c", "exit 4"])
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .code(4);
+
+    temp.close().unwrap();
+    home.close().unwrap();
+}
+
+#[test]
+fn test_notify_requires_message_or_stdin() {
+    palrun()
+        .args(["notify", "webhook", "--url", "https://example.com/hook"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--from-stdin").or(predicate::str::contains("MESSAGE")));
+}
+
+// ============================================================================
+// Doctor Tests
+// ============================================================================
+
+#[test]
+fn test_doctor_reports_incompatible_engines() {
+    if std::process::Command::new("node").arg("--version").output().is_err() {
+        return;
+    }
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+
+    temp.child("package.json")
+        .write_str(r#"{"name": "test", "engines": {"node": ">=18"}}"#)
+        .unwrap();
+    palrun()
+        .arg("doctor")
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found"));
+
+    temp.child("package.json")
+        .write_str(r#"{"name": "test", "engines": {"node": ">=999"}}"#)
+        .unwrap();
+    palrun()
+        .arg("doctor")
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("engines.node requires >=999"));
+
+    temp.close().unwrap();
+    home.close().unwrap();
+}
+
+#[test]
+fn test_doctor_reports_lockfile_drift() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = assert_fs::TempDir::new().unwrap();
+
+    temp.child("go.sum").write_str("").unwrap();
+    temp.child("go.mod").write_str("module example.com/app\n").unwrap();
+    let an_hour_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+    std::fs::File::options()
+        .write(true)
+        .open(temp.child("go.sum").path())
+        .unwrap()
+        .set_modified(an_hour_ago)
+        .unwrap();
+
+    palrun()
+        .arg("doctor")
+        .current_dir(temp.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join(".config"))
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("go.mod is newer than go.sum"));
+
+    temp.close().unwrap();
+    home.close().unwrap();
+}
+
 // ============================================================================
 // Monorepo Tests
 // ============================================================================
@@ -446,6 +986,40 @@ fn test_scan_npm_workspace() {
 // Output Format Tests
 // ============================================================================
 
+#[test]
+fn test_debug_commands_json() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("package.json")
+        .write_str(r#"{"name": "test", "scripts": {"build": "echo build"}}"#)
+        .unwrap();
+    temp.child("Makefile").write_str("test:\n\techo test\n").unwrap();
+
+    let output =
+        palrun().args(["debug", "commands", "--json"]).current_dir(temp.path()).output().unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let sources = json["sources"].as_object().unwrap();
+    let total: usize = sources.values().map(|cmds| cmds.as_array().unwrap().len()).sum();
+    assert_eq!(json["total"].as_u64().unwrap() as usize, total);
+
+    let build = sources["npm"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|cmd| cmd["command"] == "npm run build")
+        .unwrap();
+    assert_eq!(build["confirm"], false);
+    assert!(build["tags"].as_array().unwrap().contains(&"npm".into()));
+    assert_eq!(build["provenance"]["scanner"], "npm");
+    assert!(build["provenance"]["source_file"].as_str().unwrap().ends_with("package.json"));
+    assert!(build.get("working_dir").is_some());
+
+    assert!(sources["make"].as_array().unwrap().iter().any(|cmd| cmd["command"] == "make test"));
+
+    temp.close().unwrap();
+}
+
 #[test]
 fn test_list_table_format() {
     palrun().args(["list", "--format", "table"]).assert().success();
@@ -0,0 +1,14 @@
+;; Minimal scanner plugin used by the plugin manager tests.
+;; Rebuild hello_scanner.wasm with: wat2wasm hello_scanner.wat
+(module
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 4096))
+  (data (i32.const 1024) "[{\"name\":\"hello\",\"command\":\"echo hello\",\"description\":\"Say hello\",\"working_dir\":null,\"tags\":[\"dev\"]}]")
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $len)))
+    (local.get $ptr))
+  (func (export "dealloc") (param i32 i32))
+  (func (export "scan") (param i32 i32) (result i64)
+    (i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const 101))))
@@ -15,9 +15,9 @@ fn test_detect_nextjs_project() {
     fs::write(path.join("next.config.js"), "module.exports = {}").unwrap();
 
     let detector = ProjectDetector::new(path);
-    let project_type = detector.detect().unwrap();
+    let project_types = detector.detect().unwrap();
 
-    assert_eq!(project_type, ProjectType::NextJs);
+    assert_eq!(project_types, [ProjectType::NextJs]);
 }
 
 #[test]
@@ -29,9 +29,9 @@ fn test_detect_rust_project() {
     fs::write(path.join("Cargo.toml"), "[package]\nname = \"test\"").unwrap();
 
     let detector = ProjectDetector::new(path);
-    let project_type = detector.detect().unwrap();
+    let project_types = detector.detect().unwrap();
 
-    assert_eq!(project_type, ProjectType::Rust);
+    assert_eq!(project_types, [ProjectType::Rust]);
 }
 
 #[test]
@@ -43,9 +43,9 @@ fn test_detect_python_project() {
     fs::write(path.join("pyproject.toml"), "[tool.poetry]\nname = \"test\"").unwrap();
 
     let detector = ProjectDetector::new(path);
-    let project_type = detector.detect().unwrap();
+    let project_types = detector.detect().unwrap();
 
-    assert_eq!(project_type, ProjectType::Python);
+    assert_eq!(project_types, [ProjectType::Python]);
 }
 
 #[test]
@@ -57,9 +57,9 @@ fn test_detect_go_project() {
     fs::write(path.join("go.mod"), "module test").unwrap();
 
     let detector = ProjectDetector::new(path);
-    let project_type = detector.detect().unwrap();
+    let project_types = detector.detect().unwrap();
 
-    assert_eq!(project_type, ProjectType::Go);
+    assert_eq!(project_types, [ProjectType::Go]);
 }
 
 #[test]
@@ -72,9 +72,9 @@ fn test_detect_nx_monorepo() {
     fs::write(path.join("package.json"), r#"{"name": "monorepo"}"#).unwrap();
 
     let detector = ProjectDetector::new(path);
-    let project_type = detector.detect().unwrap();
+    let project_types = detector.detect().unwrap();
 
-    assert_eq!(project_type, ProjectType::NxMonorepo);
+    assert_eq!(project_types, [ProjectType::NxMonorepo]);
 }
 
 #[test]
@@ -87,9 +87,9 @@ fn test_detect_turborepo() {
     fs::write(path.join("package.json"), r#"{"name": "monorepo"}"#).unwrap();
 
     let detector = ProjectDetector::new(path);
-    let project_type = detector.detect().unwrap();
+    let project_types = detector.detect().unwrap();
 
-    assert_eq!(project_type, ProjectType::Turborepo);
+    assert_eq!(project_types, [ProjectType::Turborepo]);
 }
 
 #[test]
@@ -135,6 +135,34 @@ fn test_setup_creates_runbooks() {
     assert!(runbooks_dir.join("dev-setup.yml").exists());
 }
 
+#[test]
+fn test_setup_polyglot_project() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path();
+
+    // A Rust backend with a Node frontend
+    fs::write(path.join("Cargo.toml"), "[package]\nname = \"test\"").unwrap();
+    fs::write(path.join("Cargo.lock"), "").unwrap();
+    fs::write(path.join("package.json"), r#"{"name": "web"}"#).unwrap();
+
+    let detector = ProjectDetector::new(path);
+    assert_eq!(detector.detect().unwrap(), [ProjectType::Rust, ProjectType::NodeJs]);
+    assert_eq!(detector.primary().unwrap(), ProjectType::Rust);
+
+    let options = SetupOptions { force: true, dry_run: false, non_interactive: true };
+    setup_project(path, options).unwrap();
+
+    let content = fs::read_to_string(path.join(".palrun.toml")).unwrap();
+    assert!(content.contains("Palrun Configuration for Rust/Cargo + Node.js/NPM Project"));
+    assert!(content.contains("\"cargo\""));
+    assert!(content.contains("\"npm\""));
+
+    let runbooks_dir = path.join(".palrun").join("runbooks");
+    for name in ["build.yml", "test.yml", "deploy.yml", "dev-setup.yml"] {
+        assert!(runbooks_dir.join(name).exists(), "{name} missing");
+    }
+}
+
 #[test]
 fn test_setup_dry_run() {
     let temp_dir = TempDir::new().unwrap();
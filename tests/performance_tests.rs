@@ -143,8 +143,8 @@ fn test_json_output_efficiency() {
     let json: serde_json::Value =
         serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
 
-    // Should be an array
-    assert!(json.is_array(), "JSON output should be an array");
+    // Should be the versioned envelope
+    assert!(json["commands"].is_array(), "JSON output should list commands");
 
     // Output should not be excessively large (< 1MB for reasonable projects)
     let size = output.stdout.len();
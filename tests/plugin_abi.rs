@@ -0,0 +1,181 @@
+//! Integration tests for the scanner plugin ABI.
+//!
+//! The test plugins are small WAT modules implementing the exports described
+//! in `palrun::plugin::abi`, so no WASM toolchain is needed to run them.
+
+#![cfg(feature = "plugins")]
+
+use palrun::plugin::abi::ScanContext;
+use palrun::plugin::{FilesystemPermissions, PluginError, PluginPermissions, PluginRuntime};
+
+/// Bump allocator, no-op `dealloc`, and a `file_patterns` export returning
+/// the JSON stored at offset 16.
+fn module(patterns_json: &str, data_json: &str, scan_body: &str) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    format!(
+        r#"(module
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 4096))
+  (data (i32.const 16) "{patterns}")
+  (data (i32.const 1024) "{data}")
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $len)))
+    (local.get $ptr))
+  (func (export "dealloc") (param i32 i32))
+  (func (export "file_patterns") (result i64)
+    (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const {patterns_len})))
+  (func (export "scan") (param $ptr i32) (param $len i32) (result i64)
+    {scan_body}))"#,
+        patterns = escape(patterns_json),
+        patterns_len = patterns_json.len(),
+        data = escape(data_json),
+    )
+}
+
+/// A plugin whose `scan` returns its input, so the test can see exactly
+/// what the host passed in.
+fn echo_plugin() -> String {
+    module(
+        r#"["notes.txt"]"#,
+        "",
+        "(i64.or (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                 (i64.extend_i32_u (local.get $len)))",
+    )
+}
+
+fn read_permissions() -> PluginPermissions {
+    PluginPermissions {
+        filesystem: FilesystemPermissions { read: true, write: false, paths: vec![] },
+        ..PluginPermissions::default()
+    }
+}
+
+#[test]
+fn test_plugin_receives_matched_files() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::write(project.path().join("notes.txt"), "build: make all\n").unwrap();
+    std::fs::write(project.path().join("other.txt"), "not requested").unwrap();
+
+    let runtime = PluginRuntime::from_bytes("echo", echo_plugin().as_bytes(), 5)
+        .unwrap()
+        .with_permissions(read_permissions());
+
+    let patterns = runtime.file_patterns().unwrap();
+    assert_eq!(patterns, ["notes.txt"]);
+
+    let context = ScanContext::collect(project.path(), &patterns, &read_permissions());
+    let output = runtime.call_scan(&serde_json::to_string(&context).unwrap()).unwrap();
+    let seen: ScanContext = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(seen.matched_files.len(), 1);
+    assert_eq!(seen.matched_files["notes.txt"], "build: make all\n");
+    assert_eq!(seen.project_path, project.path().display().to_string());
+}
+
+#[test]
+fn test_plugin_without_read_permission_gets_no_files() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::write(project.path().join("notes.txt"), "secret notes").unwrap();
+
+    let runtime = PluginRuntime::from_bytes("echo", echo_plugin().as_bytes(), 5).unwrap();
+    let patterns = runtime.file_patterns().unwrap();
+    let context = ScanContext::collect(project.path(), &patterns, &PluginPermissions::default());
+    let output = runtime.call_scan(&serde_json::to_string(&context).unwrap()).unwrap();
+    let seen: ScanContext = serde_json::from_str(&output).unwrap();
+
+    assert!(seen.matched_files.is_empty());
+}
+
+#[test]
+fn test_scan_returns_commands() {
+    let commands = r#"[{"name":"hello","command":"echo hello","description":"Say hello","working_dir":null,"tags":["demo"]}]"#;
+    let wat = module(
+        "[]",
+        commands,
+        &format!(
+            "(i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const {}))",
+            commands.len()
+        ),
+    );
+
+    let project = tempfile::tempdir().unwrap();
+    let runtime = PluginRuntime::from_bytes("static", wat.as_bytes(), 5).unwrap();
+    let found = runtime.scan(project.path()).unwrap();
+
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name, "hello");
+    assert_eq!(found[0].command, "echo hello");
+    assert_eq!(found[0].tags, ["demo"]);
+}
+
+#[test]
+fn test_runaway_plugin_times_out() {
+    let wat = module("[]", "", "(loop $spin (br $spin)) (unreachable)");
+    let runtime = PluginRuntime::from_bytes("spin", wat.as_bytes(), 1).unwrap();
+
+    let project = tempfile::tempdir().unwrap();
+    let result = runtime.scan(project.path());
+    assert!(matches!(result, Err(PluginError::Timeout(name, 1)) if name == "spin"));
+}
+
+/// A plugin that tries to open `/etc/passwd` through WASI from its `scan`.
+fn file_reading_plugin() -> &'static str {
+    r#"(module
+  (import "wasi_snapshot_preview1" "path_open"
+    (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 16) "/etc/passwd")
+  (func (export "alloc") (param i32) (result i32) (i32.const 4096))
+  (func (export "dealloc") (param i32 i32))
+  (func (export "scan") (param i32 i32) (result i64)
+    (drop (call $path_open (i32.const 3) (i32.const 0) (i32.const 16) (i32.const 11)
+                           (i32.const 0) (i64.const 2) (i64.const 0) (i32.const 0)
+                           (i32.const 64)))
+    (i64.const 0)))"#
+}
+
+#[test]
+fn test_plugin_without_permissions_cannot_read_files() {
+    let project = tempfile::tempdir().unwrap();
+    let runtime = PluginRuntime::from_bytes("reader", file_reading_plugin().as_bytes(), 5).unwrap();
+
+    let result = runtime.check_imports();
+    assert!(matches!(
+        result,
+        Err(PluginError::PermissionDenied { ref plugin, ref permission })
+            if plugin == "reader" && permission.starts_with("filesystem.read")
+    ));
+    assert!(matches!(runtime.scan(project.path()), Err(PluginError::PermissionDenied { .. })));
+}
+
+#[test]
+fn test_granted_plugin_still_has_no_preopened_dirs() {
+    let project = tempfile::tempdir().unwrap();
+    let runtime = PluginRuntime::from_bytes("reader", file_reading_plugin().as_bytes(), 5)
+        .unwrap()
+        .with_permissions(read_permissions());
+
+    // Loading succeeds, but the host provides no WASI filesystem to call into
+    runtime.check_imports().unwrap();
+    assert!(matches!(runtime.scan(project.path()), Err(PluginError::ExecutionError(_))));
+}
+
+#[test]
+fn test_network_import_requires_permission() {
+    let wat = r#"(module
+  (import "wasi_snapshot_preview1" "sock_accept" (func (param i32 i32 i32) (result i32)))
+  (memory (export "memory") 1))"#;
+
+    let runtime = PluginRuntime::from_bytes("net", wat.as_bytes(), 5).unwrap();
+    assert!(matches!(
+        runtime.check_imports(),
+        Err(PluginError::PermissionDenied { ref permission, .. }) if permission.starts_with("network")
+    ));
+
+    let granted = PluginRuntime::from_bytes("net", wat.as_bytes(), 5)
+        .unwrap()
+        .with_permissions(PluginPermissions { network: true, ..PluginPermissions::default() });
+    granted.check_imports().unwrap();
+}